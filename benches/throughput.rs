@@ -0,0 +1,76 @@
+// benches/throughput.rs - Rendimiento por frase de las operaciones de un lote
+//
+// Las mismas mediciones que `scypher-rust bench`, con la estadística de
+// criterion: `cargo bench --bench throughput`. Argon2id se excluye; la
+// transformación se mide con el keystream ya en la caché de la sesión.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use scypher_rust::bip39;
+use scypher_rust::cli::bench::sample_phrases;
+use scypher_rust::crypto::{SessionKey, TransformBuilder};
+use scypher_rust::format;
+
+fn bench_validate(c: &mut Criterion) {
+    let phrases = sample_phrases().unwrap();
+    let mut group = c.benchmark_group("validate");
+    group.throughput(Throughput::Elements(phrases.len() as u64));
+
+    group.bench_function("bip39", |b| {
+        b.iter(|| {
+            for phrase in &phrases {
+                bip39::validate_seed_phrase(black_box(phrase)).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_formats(c: &mut Criterion) {
+    let phrases = sample_phrases().unwrap();
+    let mut group = c.benchmark_group("format");
+    group.throughput(Throughput::Elements(phrases.len() as u64));
+
+    for output_format in format::registered_formats() {
+        let encoded: Vec<String> = phrases.iter().map(|phrase| output_format.encode(phrase).unwrap()).collect();
+
+        group.bench_function(format!("{}/encode", output_format.name()), |b| {
+            b.iter(|| {
+                for phrase in &phrases {
+                    black_box(output_format.encode(black_box(phrase)).unwrap());
+                }
+            })
+        });
+        group.bench_function(format!("{}/decode", output_format.name()), |b| {
+            b.iter(|| {
+                for text in &encoded {
+                    black_box(output_format.decode(black_box(text)).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_transform_post_kdf(c: &mut Criterion) {
+    let phrases = sample_phrases().unwrap();
+    let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+    let mut session = SessionKey::unlock("scypher-bench", params);
+    for phrase in &phrases {
+        session.transform(phrase).unwrap();
+    }
+
+    let mut group = c.benchmark_group("transform");
+    group.throughput(Throughput::Elements(phrases.len() as u64));
+    group.bench_function("post_kdf", |b| {
+        b.iter(|| {
+            for phrase in &phrases {
+                black_box(session.transform(black_box(phrase)).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_validate, bench_formats, bench_transform_post_kdf);
+criterion_main!(benches);
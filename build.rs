@@ -0,0 +1,92 @@
+// build.rs - Configuración de compilación
+//
+// Los bindings de Node.js necesitan preparación del enlazador y el servidor
+// gRPC genera sus stubs de servicio (sin protoc: los mensajes están escritos
+// a mano en src/grpc.rs y descritos en proto/scypher.proto). Las listas
+// BIP39 no inglesas se convierten en arrays constantes ya ordenados para
+// que el binario no tenga que procesarlas al arrancar.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Listas de src/bip39/wordlists y nombre de sus constantes generadas
+const WORDLISTS: [(&str, &str); 9] = [
+    ("spanish", "SPANISH"),
+    ("french", "FRENCH"),
+    ("italian", "ITALIAN"),
+    ("portuguese", "PORTUGUESE"),
+    ("czech", "CZECH"),
+    ("japanese", "JAPANESE"),
+    ("korean", "KOREAN"),
+    ("chinese_simplified", "CHINESE_SIMPLIFIED"),
+    ("chinese_traditional", "CHINESE_TRADITIONAL"),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bip39/wordlists");
+    generate_wordlists();
+
+    #[cfg(feature = "node")]
+    napi_build::setup();
+
+    #[cfg(feature = "grpc")]
+    generate_grpc_service();
+}
+
+/// Escribir `$OUT_DIR/wordlists.rs`: por idioma, las palabras en su orden
+/// oficial (`<IDIOMA>_WORDS`) y sus índices en orden de bytes (`<IDIOMA>_SORTED`)
+fn generate_wordlists() {
+    let mut code = String::new();
+
+    for (file, constant) in WORDLISTS {
+        let path = Path::new("src/bip39/wordlists").join(format!("{}.txt", file));
+        let content = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+        let words: Vec<&str> = content.lines().collect();
+        assert_eq!(words.len(), 2048, "{} must contain 2048 words", path.display());
+
+        let mut sorted: Vec<usize> = (0..words.len()).collect();
+        sorted.sort_by_key(|&index| words[index]);
+        assert!(sorted.windows(2).all(|pair| words[pair[0]] != words[pair[1]]), "{} has duplicates", path.display());
+
+        writeln!(code, "pub(super) static {}_WORDS: [&str; 2048] = {:?};", constant, words).unwrap();
+        writeln!(code, "pub(super) static {}_SORTED: [u16; 2048] = {:?};", constant, sorted).unwrap();
+    }
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
+    std::fs::write(Path::new(&out_dir).join("wordlists.rs"), code).expect("cannot write wordlists.rs");
+}
+
+#[cfg(feature = "grpc")]
+fn generate_grpc_service() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    let codec = "tonic::codec::ProstCodec";
+
+    let service = Service::builder()
+        .name("Scypher")
+        .package("scypher.v1")
+        .method(
+            Method::builder()
+                .name("transform_batch")
+                .route_name("TransformBatch")
+                .input_type("crate::grpc::TransformRequest")
+                .output_type("crate::grpc::TransformEvent")
+                .codec_path(codec)
+                .server_streaming()
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("validate")
+                .route_name("Validate")
+                .input_type("crate::grpc::ValidateRequest")
+                .output_type("crate::grpc::ValidateResponse")
+                .codec_path(codec)
+                .build(),
+        )
+        .build();
+
+    Builder::new().compile(&[service]);
+}
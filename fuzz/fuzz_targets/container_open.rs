@@ -0,0 +1,35 @@
+//! Fuzzing de la lectura de contenedores (`--armor`)
+//!
+//! Archivos dañados o manipulados no deben provocar pánico ni colgarse:
+//! armor OpenPGP y sus paquetes, archivos age y el documento de
+//! recuperación que va dentro. El corpus está cifrado con la passphrase
+//! "fuzz" para que las mutaciones lleguen también al contenido descifrado.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scypher_rust::armor::{self, Armor};
+use scypher_rust::SCypherError;
+
+fuzz_target!(|data: &[u8]| {
+    // age admite también el formato binario
+    let _ = armor::open_age_file(data, "fuzz");
+
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = armor::parse_recovery_document(text);
+    for armor in Armor::ALL {
+        match armor.open(text, "fuzz") {
+            Ok(document) => {
+                let _ = armor::parse_recovery_document(&document);
+            }
+            // Todo lo que no es la passphrase es un defecto concreto del formato
+            Err(SCypherError::MalformedContainer { .. })
+            | Err(SCypherError::InvalidPassword)
+            | Err(SCypherError::CryptoError(_)) => {}
+            Err(e) => panic!("unexpected error kind: {:?}", e),
+        }
+    }
+});
@@ -0,0 +1,16 @@
+//! Fuzzing de la decodificación hexadecimal de entropía
+//!
+//! Cadenas arbitrarias no deben provocar pánico; cuando se decodifican,
+//! volver a codificar la frase debe devolver el mismo hexadecimal.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scypher_rust::bip39::conversion;
+
+fuzz_target!(|input: &str| {
+    if let Ok(phrase) = conversion::hex_to_phrase(input) {
+        let hex = conversion::phrase_to_hex(&phrase).expect("decoded phrase re-encodes");
+        assert_eq!(hex, input.to_lowercase());
+    }
+});
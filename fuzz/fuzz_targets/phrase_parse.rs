@@ -0,0 +1,26 @@
+//! Fuzzing del análisis de frases
+//!
+//! Ninguna entrada debe provocar pánico. Si una frase es válida, su
+//! entropía debe regenerar exactamente la misma frase normalizada.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scypher_rust::bip39::{self, conversion, validation};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let phrase = validation::sanitize_seed_phrase(input);
+    let _ = validation::analyze_seed_phrase(&phrase).generate_report();
+    let _ = bip39::verify_checksum(&phrase);
+    let _ = conversion::phrase_to_bits(&phrase);
+
+    if bip39::validate_seed_phrase(&phrase).is_ok() {
+        let entropy = conversion::phrase_to_entropy(&phrase).expect("valid phrase has entropy");
+        let rebuilt = conversion::entropy_to_phrase(&entropy).expect("entropy re-encodes");
+        assert_eq!(rebuilt, phrase);
+    }
+});
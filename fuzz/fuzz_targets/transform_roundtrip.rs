@@ -0,0 +1,37 @@
+//! Fuzzing del round-trip de la transformación
+//!
+//! Para cualquier entropía y contraseña, descifrar(cifrar(frase)) == frase,
+//! y el resultado cifrado siempre es una frase BIP39 válida.
+//! Usa los parámetros Argon2 mínimos para mantener el ritmo de ejecuciones.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use scypher_rust::{bip39, ScypherParams};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    /// Selecciona la longitud de la frase (12, 15, 18, 21 o 24 palabras)
+    length: u8,
+    entropy: [u8; 32],
+    password: String,
+}
+
+fuzz_target!(|input: Input| {
+    let entropy_len = [16, 20, 24, 28, 32][input.length as usize % 5];
+    let phrase = bip39::conversion::entropy_to_phrase(&input.entropy[..entropy_len])
+        .expect("valid entropy length");
+
+    let params = ScypherParams::builder()
+        .iterations(1)
+        .memory_cost(8192)
+        .build()
+        .expect("minimum parameters are valid");
+
+    let encrypted = params.transform(&phrase, &input.password).expect("valid phrase encrypts");
+    assert!(bip39::validate_seed_phrase(&encrypted).is_ok());
+
+    let decrypted = params.transform(&encrypted, &input.password).expect("encrypted phrase decrypts");
+    assert_eq!(*decrypted, phrase);
+});
@@ -0,0 +1,28 @@
+//! Fuzzing de la lectura de UR (una parte o varias, una por línea)
+//!
+//! Ninguna secuencia de partes debe provocar pánico, reservas desmesuradas
+//! ni bucles largos. Un UR de una sola parte que se lee bien debe volver a
+//! codificarse igual.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scypher_rust::ur::{Ur, UrDecoder};
+
+fuzz_target!(|input: &str| {
+    let mut decoder = UrDecoder::new();
+    for line in input.lines() {
+        let _ = decoder.receive(line);
+
+        if let Ok(ur) = Ur::from_single_part(line) {
+            assert_eq!(ur.to_single_part(), line.trim().to_ascii_lowercase());
+            let _ = ur.to_phrase();
+            let _ = ur.to_bytes();
+        }
+    }
+
+    if let Some(ur) = decoder.result() {
+        let _ = ur.to_phrase();
+        let _ = ur.to_bytes();
+    }
+});
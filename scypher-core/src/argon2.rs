@@ -0,0 +1,581 @@
+//! Argon2 (RFC 9106) por segmentos, interrumpible entre ellos
+//!
+//! El crate `argon2` hace todas las pasadas en una sola llamada: con 2 GB y
+//! muchas iteraciones eso son minutos sin ningún punto en el que parar. Aquí
+//! está el mismo algoritmo (Argon2i y Argon2id, versiones 0x10 y 0x13, con
+//! secreto opcional y sin datos asociados) con una comprobación de `cancelled` antes de
+//! cada segmento: cuatro por pasada, así que con 128 MB la espera es de
+//! unas decenas de milisegundos. Si se cancela, los bloques y la salida se
+//! limpian antes de devolver `Error::Cancelled`.
+//!
+//! Los límites de los parámetros los comprueba el llamador (`scypher-rust`
+//! con `argon2::Params`); aquí solo se rechaza lo que haría fallar el
+//! cálculo. Las pruebas comparan el resultado con el del crate `argon2`.
+//!
+//! Con la feature `parallel` las lanes de cada segmento se rellenan en
+//! hilos de rayon, como permite el algoritmo: con p lanes y p núcleos el
+//! tiempo se divide casi por p sin cambiar el resultado ni el coste de
+//! memoria para un atacante.
+
+use blake2::digest::{self, Digest, VariableOutput};
+use blake2::{Blake2b512, Blake2bVar};
+use core::marker::PhantomData;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::Error;
+
+/// Segmentos por pasada en cada lane
+const SYNC_POINTS: usize = 4;
+
+/// Direcciones pseudoaleatorias que caben en un bloque (modo independiente de los datos)
+const ADDRESSES_IN_BLOCK: usize = 128;
+
+/// Salt mínimo de Argon2
+pub const MIN_SALT_LEN: usize = 8;
+
+/// Mitad baja de una palabra de 64 bits
+const TRUNC: u64 = u32::MAX as u64;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+cpufeatures::new!(avx2_cpuid, "avx2");
+
+/// Bloque de memoria de 1 KB (128 palabras de 64 bits)
+#[derive(Clone, Copy)]
+#[repr(align(64))]
+pub struct Block([u64; Block::WORDS]);
+
+impl Block {
+    /// Tamaño en bytes
+    pub const SIZE: usize = 1024;
+
+    const WORDS: usize = Self::SIZE / 8;
+
+    pub const fn new() -> Self {
+        Self([0; Self::WORDS])
+    }
+
+    fn xor(&mut self, other: &Block) {
+        self.0.iter_mut().zip(&other.0).for_each(|(word, other)| *word ^= other);
+    }
+
+    fn load(&mut self, bytes: &[u8; Block::SIZE]) {
+        for (word, chunk) in self.0.iter_mut().zip(bytes.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().expect("8-byte chunk"));
+        }
+    }
+}
+
+impl Default for Block {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<[u64]> for Block {
+    fn as_ref(&self) -> &[u64] {
+        &self.0
+    }
+}
+
+impl Zeroize for Block {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Variante de Argon2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Argon2i = 1,
+    Argon2id = 2,
+}
+
+/// Versión del algoritmo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    V0x10 = 0x10,
+    V0x13 = 0x13,
+}
+
+/// Parámetros de una derivación Argon2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2 {
+    pub variant: Variant,
+    pub version: Version,
+    /// Memoria en KB (bloques)
+    pub m_cost: u32,
+    /// Pasadas sobre la memoria
+    pub t_cost: u32,
+    pub lanes: u32,
+}
+
+impl Argon2 {
+    /// Bloques de memoria que usa: `m_cost` redondeado a un múltiplo de 4 por lane
+    pub fn block_count(&self) -> usize {
+        let lanes = self.lanes as usize;
+        let blocks = (self.m_cost as usize).max(2 * SYNC_POINTS * lanes);
+        blocks / (lanes * SYNC_POINTS) * lanes * SYNC_POINTS
+    }
+
+    /// Argon2 de `input` y `salt` en `output`, usando `blocks` como memoria
+    ///
+    /// `secret` es el valor secreto K de Argon2 (vacío si no hay). `blocks`
+    /// debe tener al menos `block_count()` bloques; su contenido previo no
+    /// importa. `cancelled` se consulta antes de cada segmento.
+    pub fn hash_into(
+        &self,
+        input: &[u8],
+        secret: &[u8],
+        salt: &[u8],
+        output: &mut [u8],
+        blocks: &mut [Block],
+        cancelled: &dyn Fn() -> bool,
+    ) -> Result<(), Error> {
+        if self.lanes == 0 || self.t_cost == 0 || salt.len() < MIN_SALT_LEN || output.len() < 4 {
+            return Err(Error::InvalidKdfParams);
+        }
+        let blocks = blocks.get_mut(..self.block_count()).ok_or(Error::InvalidKdfParams)?;
+
+        if let Err(error) = self.fill_blocks(input, secret, salt, output.len(), blocks, cancelled) {
+            blocks.iter_mut().for_each(Zeroize::zeroize);
+            output.zeroize();
+            return Err(error);
+        }
+        finalize(blocks, self.lanes as usize, output)
+    }
+
+    /// H0: hash de los parámetros y las entradas
+    fn initial_hash(&self, input: &[u8], secret: &[u8], salt: &[u8], output_len: usize) -> Zeroizing<[u8; 64]> {
+        let mut digest = Blake2b512::new();
+        digest.update(self.lanes.to_le_bytes());
+        digest.update((output_len as u32).to_le_bytes());
+        digest.update(self.m_cost.to_le_bytes());
+        digest.update(self.t_cost.to_le_bytes());
+        digest.update((self.version as u32).to_le_bytes());
+        digest.update((self.variant as u32).to_le_bytes());
+        digest.update((input.len() as u32).to_le_bytes());
+        digest.update(input);
+        digest.update((salt.len() as u32).to_le_bytes());
+        digest.update(salt);
+        digest.update((secret.len() as u32).to_le_bytes());
+        digest.update(secret);
+        // Sin datos asociados
+        digest.update(0u32.to_le_bytes());
+
+        let mut output = digest.finalize();
+        let hash = Zeroizing::new(output.into());
+        output.as_mut_slice().zeroize();
+        hash
+    }
+
+    /// Todas las pasadas sobre `blocks`, comprobando `cancelled` antes de cada segmento
+    fn fill_blocks(
+        &self,
+        input: &[u8],
+        secret: &[u8],
+        salt: &[u8],
+        output_len: usize,
+        blocks: &mut [Block],
+        cancelled: &dyn Fn() -> bool,
+    ) -> Result<(), Error> {
+        let lanes = self.lanes as usize;
+        let lane_length = blocks.len() / lanes;
+        let compress = compress_fn();
+
+        // Los dos primeros bloques de cada lane: H'(H0 || i || lane)
+        let initial_hash = self.initial_hash(input, secret, salt, output_len);
+        let mut hash = Zeroizing::new([0u8; Block::SIZE]);
+        for (lane, lane_blocks) in blocks.chunks_exact_mut(lane_length).enumerate() {
+            for (i, block) in lane_blocks[..2].iter_mut().enumerate() {
+                blake2b_long(&[initial_hash.as_slice(), &(i as u32).to_le_bytes(), &(lane as u32).to_le_bytes()], hash.as_mut_slice())?;
+                block.load(&hash);
+            }
+        }
+
+        let memory = Memory::new(blocks);
+        for pass in 0..self.t_cost as usize {
+            for slice in 0..SYNC_POINTS {
+                if cancelled() {
+                    return Err(Error::Cancelled);
+                }
+
+                for_each_lane(lanes, |lane| {
+                    // SAFETY: cada lane escribe solo su segmento de este slice y
+                    // lee de otras lanes solo bloques fuera de él (RFC 9106, 3.4.1.2)
+                    unsafe { self.fill_segment(&memory, pass, slice, lane, compress) }
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rellenar el segmento `slice` de `lane` en la pasada `pass`
+    ///
+    /// # Safety
+    /// Ningún otro hilo puede estar escribiendo este segmento, ni los
+    /// bloques de otras lanes que no pertenezcan al slice actual.
+    unsafe fn fill_segment(
+        &self,
+        memory: &Memory,
+        pass: usize,
+        slice: usize,
+        lane: usize,
+        compress: fn(&Block, &Block) -> Block,
+    ) {
+        let lanes = self.lanes as usize;
+        let iterations = self.t_cost as usize;
+        let lane_length = memory.len / lanes;
+        let segment_length = lane_length / SYNC_POINTS;
+        let data_independent = self.variant == Variant::Argon2i || (pass == 0 && slice < SYNC_POINTS / 2);
+
+        let mut address_block = Block::new();
+        let mut input_block = Block::new();
+        let zero_block = Block::new();
+        let next_addresses = |address_block: &mut Block, input_block: &mut Block| {
+            input_block.0[6] += 1;
+            *address_block = compress(&zero_block, input_block);
+            *address_block = compress(&zero_block, address_block);
+        };
+
+        if data_independent {
+            input_block.0[..6].copy_from_slice(&[
+                pass as u64,
+                lane as u64,
+                slice as u64,
+                memory.len as u64,
+                iterations as u64,
+                self.variant as u64,
+            ]);
+        }
+
+        // Los dos primeros bloques de cada lane ya están rellenos
+        let first_block = if pass == 0 && slice == 0 {
+            if data_independent {
+                next_addresses(&mut address_block, &mut input_block);
+            }
+            2
+        } else {
+            0
+        };
+
+        let segment_start = lane * lane_length + slice * segment_length;
+        let mut prev_index = if slice == 0 && first_block == 0 {
+            segment_start + lane_length - 1
+        } else {
+            segment_start + first_block - 1
+        };
+
+        for block in first_block..segment_length {
+            let cur_index = segment_start + block;
+            let rand = if data_independent {
+                let address_index = block % ADDRESSES_IN_BLOCK;
+                if address_index == 0 {
+                    next_addresses(&mut address_block, &mut input_block);
+                }
+                address_block.0[address_index]
+            } else {
+                memory.get(prev_index).0[0]
+            };
+
+            // En el primer segmento solo se puede referenciar el propio lane
+            let ref_lane = if pass == 0 && slice == 0 { lane } else { (rand >> 32) as usize % lanes };
+            let reference_area_size = match (pass, ref_lane == lane) {
+                (0, _) if slice == 0 => block - 1,
+                (0, true) => slice * segment_length + block - 1,
+                (0, false) => slice * segment_length - usize::from(block == 0),
+                (_, true) => lane_length - segment_length + block - 1,
+                (_, false) => lane_length - segment_length - usize::from(block == 0),
+            };
+
+            let map = ((rand & TRUNC) * (rand & TRUNC)) >> 32;
+            let relative_position = reference_area_size - 1 - ((reference_area_size as u64 * map) >> 32) as usize;
+            let start_position = if pass != 0 && slice != SYNC_POINTS - 1 { (slice + 1) * segment_length } else { 0 };
+            let ref_index = ref_lane * lane_length + (start_position + relative_position) % lane_length;
+
+            let result = compress(memory.get(prev_index), memory.get(ref_index));
+            let current = memory.get_mut(cur_index);
+            if self.version == Version::V0x10 || pass == 0 {
+                *current = result;
+            } else {
+                current.xor(&result);
+            }
+
+            prev_index = cur_index;
+        }
+    }
+}
+
+/// Memoria de Argon2 compartida por las lanes de un mismo slice
+///
+/// Las lanes de un slice se rellenan a la vez: cada una escribe su segmento
+/// y lee bloques ya terminados del resto, así que no se puede repartir en
+/// préstamos `&mut` disjuntos. El acceso es por índice y cada llamador
+/// garantiza que no pisa un bloque que otro hilo está escribiendo.
+struct Memory<'a> {
+    blocks: *mut Block,
+    len: usize,
+    _borrow: PhantomData<&'a mut [Block]>,
+}
+
+// SAFETY: `Memory` solo da acceso a través de `get`/`get_mut`, cuyos
+// llamadores garantizan que los bloques que lee un hilo no los escribe otro
+unsafe impl Send for Memory<'_> {}
+unsafe impl Sync for Memory<'_> {}
+
+impl<'a> Memory<'a> {
+    fn new(blocks: &'a mut [Block]) -> Self {
+        Self { blocks: blocks.as_mut_ptr(), len: blocks.len(), _borrow: PhantomData }
+    }
+
+    /// # Safety
+    /// Ningún otro hilo escribe `index` mientras vive la referencia
+    unsafe fn get(&self, index: usize) -> &Block {
+        assert!(index < self.len);
+        &*self.blocks.add(index)
+    }
+
+    /// # Safety
+    /// Ningún otro hilo lee ni escribe `index` mientras vive la referencia
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn get_mut(&self, index: usize) -> &mut Block {
+        assert!(index < self.len);
+        &mut *self.blocks.add(index)
+    }
+}
+
+/// Ejecutar `fill` para cada lane: en hilos de rayon con la feature `parallel`
+fn for_each_lane(lanes: usize, fill: impl Fn(usize) + Sync) {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        (0..lanes).into_par_iter().for_each(&fill);
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    (0..lanes).for_each(fill);
+}
+
+/// XOR del último bloque de cada lane y H' del resultado
+fn finalize(blocks: &[Block], lanes: usize, output: &mut [u8]) -> Result<(), Error> {
+    let lane_length = blocks.len() / lanes;
+    let mut last = Zeroizing::new(blocks[lane_length - 1]);
+    for lane in 1..lanes {
+        last.xor(&blocks[lane * lane_length + lane_length - 1]);
+    }
+
+    let mut bytes = Zeroizing::new([0u8; Block::SIZE]);
+    for (chunk, word) in bytes.chunks_exact_mut(8).zip(&last.0) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    blake2b_long(&[bytes.as_slice()], output)
+}
+
+/// H': BLAKE2b de longitud variable
+fn blake2b_long(inputs: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+    fn invalid<E>(_: E) -> Error {
+        Error::InvalidKdfParams
+    }
+    let len_bytes = u32::try_from(output.len()).map_err(invalid)?.to_le_bytes();
+
+    if output.len() <= 64 {
+        let mut digest = Blake2bVar::new(output.len()).map_err(invalid)?;
+        digest::Update::update(&mut digest, &len_bytes);
+        for input in inputs {
+            digest::Update::update(&mut digest, input);
+        }
+        return digest.finalize_variable(output).map_err(invalid);
+    }
+
+    // Bloques de 32 bytes encadenados; el último, de 33 a 64 bytes, sale entero del hash
+    let mut digest = Blake2b512::new();
+    digest.update(len_bytes);
+    for input in inputs {
+        digest.update(input);
+    }
+    let mut last = digest.finalize();
+    output[..32].copy_from_slice(&last[..32]);
+
+    let mut written = 32;
+    while output.len() - written > 64 {
+        last = Blake2b512::digest(last);
+        output[written..written + 32].copy_from_slice(&last[..32]);
+        written += 32;
+    }
+
+    let mut digest = Blake2bVar::new(output.len() - written).map_err(invalid)?;
+    digest::Update::update(&mut digest, &last);
+    last.as_mut_slice().zeroize();
+    digest.finalize_variable(&mut output[written..]).map_err(invalid)
+}
+
+macro_rules! blamka_step {
+    ($a:expr, $b:expr, $c:expr, $d:expr) => {
+        $a = blamka($a, $b);
+        $d = ($d ^ $a).rotate_right(32);
+        $c = blamka($c, $d);
+        $b = ($b ^ $c).rotate_right(24);
+        $a = blamka($a, $b);
+        $d = ($d ^ $a).rotate_right(16);
+        $c = blamka($c, $d);
+        $b = ($b ^ $c).rotate_right(63);
+    };
+}
+
+/// Ronda de BLAKE2b con multiplicaciones (BlaMka) sobre 16 palabras de `$q`
+macro_rules! permute {
+    ($q:ident, $v0:expr, $v1:expr, $v2:expr, $v3:expr, $v4:expr, $v5:expr, $v6:expr, $v7:expr,
+     $v8:expr, $v9:expr, $v10:expr, $v11:expr, $v12:expr, $v13:expr, $v14:expr, $v15:expr) => {
+        blamka_step!($q[$v0], $q[$v4], $q[$v8], $q[$v12]);
+        blamka_step!($q[$v1], $q[$v5], $q[$v9], $q[$v13]);
+        blamka_step!($q[$v2], $q[$v6], $q[$v10], $q[$v14]);
+        blamka_step!($q[$v3], $q[$v7], $q[$v11], $q[$v15]);
+        blamka_step!($q[$v0], $q[$v5], $q[$v10], $q[$v15]);
+        blamka_step!($q[$v1], $q[$v6], $q[$v11], $q[$v12]);
+        blamka_step!($q[$v2], $q[$v7], $q[$v8], $q[$v13]);
+        blamka_step!($q[$v3], $q[$v4], $q[$v9], $q[$v14]);
+    };
+}
+
+#[inline(always)]
+fn blamka(x: u64, y: u64) -> u64 {
+    x.wrapping_add(y).wrapping_add(((x & TRUNC) * (y & TRUNC)).wrapping_mul(2))
+}
+
+/// G: compresión de dos bloques en uno
+#[inline(always)]
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = *x;
+    r.xor(y);
+    let mut q = r;
+    let words = &mut q.0;
+
+    // Filas de 16 palabras y después columnas de pares
+    for row in 0..8 {
+        let b = row * 16;
+        permute!(words, b, b + 1, b + 2, b + 3, b + 4, b + 5, b + 6, b + 7,
+            b + 8, b + 9, b + 10, b + 11, b + 12, b + 13, b + 14, b + 15);
+    }
+    for column in 0..8 {
+        let b = column * 2;
+        permute!(words, b, b + 1, b + 16, b + 17, b + 32, b + 33, b + 48, b + 49,
+            b + 64, b + 65, b + 80, b + 81, b + 96, b + 97, b + 112, b + 113);
+    }
+
+    q.xor(&r);
+    q
+}
+
+/// `compress`, compilada con AVX2 si la CPU lo tiene
+fn compress_fn() -> fn(&Block, &Block) -> Block {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        #[target_feature(enable = "avx2")]
+        unsafe fn compress_avx2(x: &Block, y: &Block) -> Block {
+            compress(x, y)
+        }
+
+        fn compress_avx2_checked(x: &Block, y: &Block) -> Block {
+            // SAFETY: solo se elige tras detectar AVX2 en tiempo de ejecución
+            unsafe { compress_avx2(x, y) }
+        }
+
+        if avx2_cpuid::get() {
+            return compress_avx2_checked;
+        }
+    }
+
+    compress
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::vec;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_matches_argon2_crate() {
+        let salt = [0x5Au8; 32];
+        for (variant, algorithm) in [(Variant::Argon2id, ::argon2::Algorithm::Argon2id), (Variant::Argon2i, ::argon2::Algorithm::Argon2i)] {
+            for (version, crate_version) in [(Version::V0x13, ::argon2::Version::V0x13), (Version::V0x10, ::argon2::Version::V0x10)] {
+                for (m_cost, t_cost, lanes, output_len) in [(8, 1, 1, 16), (64, 3, 2, 32), (256, 2, 4, 100), (200, 1, 3, 64), (1024, 4, 1, 65)] {
+                    for secret in [&b""[..], b"pepper"] {
+                        let params = ::argon2::Params::new(m_cost, t_cost, lanes, Some(output_len)).unwrap();
+                        let mut expected = vec![0u8; output_len];
+                        ::argon2::Argon2::new_with_secret(secret, algorithm, crate_version, params.clone())
+                            .unwrap()
+                            .hash_password_into(b"password", &salt, &mut expected)
+                            .unwrap();
+
+                        let argon2 = Argon2 { variant, version, m_cost, t_cost, lanes };
+                        assert_eq!(argon2.block_count(), params.block_count());
+                        let mut blocks = vec![Block::new(); argon2.block_count()];
+                        let mut output = vec![0u8; output_len];
+                        argon2.hash_into(b"password", secret, &salt, &mut output, &mut blocks, &|| false).unwrap();
+                        assert_eq!(output, expected, "{:?} m={} t={} p={} secret={}", argon2, m_cost, t_cost, lanes, secret.len());
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_lanes_run_on_separate_threads() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::{Duration, Instant};
+
+        // Cada lane espera a que lleguen todas: en serie, la primera no vería a las demás
+        let lanes = 4;
+        let arrived = AtomicUsize::new(0);
+        let met = AtomicUsize::new(0);
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(lanes).build().unwrap();
+        pool.install(|| for_each_lane(lanes, |_| {
+            arrived.fetch_add(1, Ordering::SeqCst);
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while arrived.load(Ordering::SeqCst) < lanes && Instant::now() < deadline {
+                std::thread::yield_now();
+            }
+            if arrived.load(Ordering::SeqCst) == lanes {
+                met.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+        assert_eq!(met.load(Ordering::SeqCst), lanes);
+
+        // Con las lanes en hilos de verdad el resultado no cambia
+        let params = ::argon2::Params::new(2048, 3, lanes as u32, Some(32)).unwrap();
+        let mut expected = [0u8; 32];
+        ::argon2::Argon2::new(::argon2::Algorithm::Argon2id, ::argon2::Version::V0x13, params)
+            .hash_password_into(b"password", &[0x5A; 16], &mut expected)
+            .unwrap();
+
+        let argon2 = Argon2 { variant: Variant::Argon2id, version: Version::V0x13, m_cost: 2048, t_cost: 3, lanes: lanes as u32 };
+        let mut blocks = vec![Block::new(); argon2.block_count()];
+        let mut output = [0u8; 32];
+        pool.install(|| argon2.hash_into(b"password", &[], &[0x5A; 16], &mut output, &mut blocks, &|| false)).unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_cancel_wipes_memory() {
+        let argon2 = Argon2 { variant: Variant::Argon2id, version: Version::V0x13, m_cost: 64, t_cost: 3, lanes: 2 };
+        let mut blocks = vec![Block::new(); argon2.block_count()];
+        let mut output = [0xFFu8; 32];
+
+        // Cancelar tras la primera pasada: la memoria ya tenía datos
+        let checks = Cell::new(0);
+        let cancelled = || {
+            checks.set(checks.get() + 1);
+            checks.get() > SYNC_POINTS
+        };
+        assert_eq!(argon2.hash_into(b"password", &[], &[7; 16], &mut output, &mut blocks, &cancelled), Err(Error::Cancelled));
+        assert_eq!(checks.get(), SYNC_POINTS + 1);
+        assert!(blocks.iter().all(|block| block.0.iter().all(|&word| word == 0)));
+        assert_eq!(output, [0u8; 32]);
+
+        assert_eq!(argon2.hash_into(b"password", &[], &[7; 4], &mut output, &mut blocks, &|| false), Err(Error::InvalidKdfParams));
+    }
+}
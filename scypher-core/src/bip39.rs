@@ -0,0 +1,72 @@
+//! Checksum BIP39 y conversión entre entropía e índices
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::{entropy_bits, pack_indices_into, unpack_indices_into, Error, MAX_PACKED_BYTES};
+
+/// Checksum BIP39 de `entropy`: los primeros ENT/32 bits de su SHA-256,
+/// alineados a la izquierda en un byte
+pub fn checksum_byte(entropy: &[u8]) -> u8 {
+    let checksum_bits = entropy.len() * 8 / 32;
+    let hash = Sha256::digest(entropy);
+    (0xFF00u16 >> checksum_bits) as u8 & hash[0]
+}
+
+/// Índices de palabra de `entropy` (16-32 bytes, múltiplo de 4), checksum incluido
+pub fn entropy_to_indices(entropy: &[u8]) -> Result<Vec<u16>, Error> {
+    if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+        return Err(Error::InvalidEntropyLength(entropy.len()));
+    }
+
+    let mut packed = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+    packed[..entropy.len()].copy_from_slice(entropy);
+    packed[entropy.len()] = checksum_byte(entropy);
+
+    let mut indices = vec![0u16; entropy.len() * 8 * 3 / 32];
+    unpack_indices_into(&packed[..=entropy.len()], &mut indices);
+    Ok(indices)
+}
+
+/// Entropía de una frase dada por sus índices, comprobando el checksum
+pub fn indices_to_entropy(indices: &[u16]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let entropy_len = entropy_bits(indices.len())? / 8;
+    if let Some(&index) = indices.iter().find(|&&index| index > 0x7FF) {
+        return Err(Error::InvalidIndex(index));
+    }
+
+    let mut packed = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+    pack_indices_into(indices, &mut packed[..]);
+    if packed[entropy_len] != checksum_byte(&packed[..entropy_len]) {
+        return Err(Error::InvalidChecksum);
+    }
+    Ok(Zeroizing::new(packed[..entropy_len].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_roundtrip() {
+        // "abandon" x11 + "about"
+        let indices = entropy_to_indices(&[0u8; 16]).unwrap();
+        assert_eq!(&indices[..11], &[0u16; 11]);
+        assert_eq!(indices[11], 3);
+        assert_eq!(indices_to_entropy(&indices).unwrap().as_slice(), &[0u8; 16]);
+
+        // "zoo" x23 + "vote"
+        let indices = entropy_to_indices(&[0xFF; 32]).unwrap();
+        assert_eq!(indices.len(), 24);
+        assert_eq!(indices[23], 1967);
+
+        let mut wrong = indices.clone();
+        wrong[23] = 2047;
+        assert_eq!(indices_to_entropy(&wrong), Err(Error::InvalidChecksum));
+        assert_eq!(indices_to_entropy(&[0u16; 11]), Err(Error::InvalidWordCount(11)));
+        assert_eq!(entropy_to_indices(&[0u8; 17]), Err(Error::InvalidEntropyLength(17)));
+    }
+}
@@ -0,0 +1,82 @@
+//! # scypher-core
+//!
+//! Núcleo de la transformación SCypher sin `std` (solo `core` y `alloc`),
+//! para firmadores embebidos (p. ej. un dispositivo casero con STM32):
+//!
+//! - empaquetado de índices de palabra de 11 bits en entropía + checksum
+//!   y vuelta (`pack_indices_into`, `unpack_indices_into`);
+//! - checksum BIP39 (`checksum_byte`) y conversión entropía ↔ índices;
+//! - la transformación completa sobre índices (`transform_indices`), con la
+//!   derivación del keystream detrás del trait `KeystreamSource`: el crate
+//!   `scypher-rust` lo implementa con Argon2id y un dispositivo puede usar
+//!   su propio hardware.
+//! - Argon2i/Argon2id interrumpible entre segmentos (`argon2`), el KDF
+//!   que usa `scypher-rust`.
+//!
+//! Las listas de palabras no están aquí: el llamador convierte palabras en
+//! índices (0-2047) y de vuelta. Todos los buffers intermedios son arrays en
+//! la pila que se limpian al salir.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod argon2;
+mod bip39;
+mod packing;
+mod transform;
+
+use core::fmt;
+
+pub use bip39::{checksum_byte, entropy_to_indices, indices_to_entropy};
+pub use packing::{pack_indices_into, unpack_indices_into};
+pub use transform::{transform_indices, xor_in_place, KeystreamSource};
+
+/// Máximo de palabras de una frase BIP39 (256 bits de entropía)
+pub const MAX_WORDS: usize = 24;
+
+/// Bytes de una frase de `MAX_WORDS` palabras empaquetada: entropía + checksum
+pub const MAX_PACKED_BYTES: usize = MAX_WORDS * 11 / 8;
+
+/// Errores del núcleo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Número de palabras distinto de 12, 15, 18, 21 o 24
+    InvalidWordCount(usize),
+    /// Entropía que no mide 16, 20, 24, 28 o 32 bytes
+    InvalidEntropyLength(usize),
+    /// Índice de palabra fuera de 0-2047
+    InvalidIndex(u16),
+    /// El checksum BIP39 no coincide
+    InvalidChecksum,
+    /// Datos y keystream de distinta longitud
+    LengthMismatch { data: usize, keystream: usize },
+    /// Parámetros de Argon2 con los que no se puede calcular (salt corto, memoria insuficiente...)
+    InvalidKdfParams,
+    /// Derivación interrumpida por el llamador
+    Cancelled,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidWordCount(count) => write!(f, "invalid word count {} (expected 12, 15, 18, 21 or 24)", count),
+            Error::InvalidEntropyLength(len) => write!(f, "invalid entropy length {} bytes (expected 16-32, multiple of 4)", len),
+            Error::InvalidIndex(index) => write!(f, "word index {} out of range (0-2047)", index),
+            Error::InvalidChecksum => write!(f, "invalid BIP39 checksum"),
+            Error::LengthMismatch { data, keystream } => {
+                write!(f, "data length ({}) doesn't match keystream length ({})", data, keystream)
+            }
+            Error::InvalidKdfParams => write!(f, "invalid Argon2 parameters"),
+            Error::Cancelled => write!(f, "operation cancelled"),
+        }
+    }
+}
+
+/// Bits de entropía de una frase de `word_count` palabras
+fn entropy_bits(word_count: usize) -> Result<usize, Error> {
+    match word_count {
+        12 | 15 | 18 | 21 | 24 => Ok(word_count * 32 / 3),
+        _ => Err(Error::InvalidWordCount(word_count)),
+    }
+}
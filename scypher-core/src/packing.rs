@@ -0,0 +1,60 @@
+//! Empaquetado de índices de palabra de 11 bits
+//!
+//! Un acumulador de 64 bits en lugar de un vector de bits: los campos
+//! cruzan límites de byte, así que no hay una forma vectorial más rápida
+//! para frases de 12-24 palabras.
+
+/// Empaquetar índices en `out` sin reservar memoria; devuelve los bytes escritos
+///
+/// MSB primero; el último byte se rellena con ceros.
+///
+/// # Panics
+/// Si `out` no tiene espacio para `indices.len() * 11` bits.
+pub fn pack_indices_into(indices: &[u16], out: &mut [u8]) -> usize {
+    let needed = (indices.len() * 11).div_ceil(8);
+    assert!(out.len() >= needed, "not enough space for {} indices", indices.len());
+
+    let mut written = 0;
+    let mut accumulator: u64 = 0;
+    let mut pending = 0u32;
+
+    for &index in indices {
+        accumulator = (accumulator << 11) | u64::from(index & 0x7FF);
+        pending += 11;
+
+        while pending >= 8 {
+            pending -= 8;
+            out[written] = (accumulator >> pending) as u8;
+            written += 1;
+        }
+    }
+
+    if pending > 0 {
+        out[written] = (accumulator << (8 - pending)) as u8;
+        written += 1;
+    }
+
+    written
+}
+
+/// Extraer `out.len()` índices de `bytes` sin reservar memoria
+///
+/// # Panics
+/// Si `bytes` no contiene `out.len() * 11` bits.
+pub fn unpack_indices_into(bytes: &[u8], out: &mut [u16]) {
+    assert!(bytes.len() * 8 >= out.len() * 11, "not enough bytes for {} indices", out.len());
+
+    let mut accumulator: u64 = 0;
+    let mut available = 0u32;
+    let mut bytes = bytes.iter();
+
+    for index in out.iter_mut() {
+        while available < 11 {
+            accumulator = (accumulator << 8) | u64::from(*bytes.next().unwrap());
+            available += 8;
+        }
+
+        available -= 11;
+        *index = ((accumulator >> available) & 0x7FF) as u16;
+    }
+}
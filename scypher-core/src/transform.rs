@@ -0,0 +1,86 @@
+//! Transformación XOR sobre índices con un keystream intercambiable
+
+use zeroize::Zeroizing;
+
+use crate::{checksum_byte, entropy_bits, pack_indices_into, unpack_indices_into, Error, MAX_PACKED_BYTES};
+
+/// Origen del keystream de una transformación
+///
+/// Quien lo implementa guarda la contraseña y los parámetros y escribe en
+/// `keystream` tantos bytes como mida (los de la entropía). En
+/// `scypher-rust` es Argon2id; un firmador puede delegarlo en su hardware.
+/// El keystream debe ser el mismo para la misma contraseña y parámetros, o
+/// el resultado no se podrá revertir.
+pub trait KeystreamSource {
+    /// Error propio del KDF; también recibe los errores del núcleo
+    type Error: From<Error>;
+
+    /// Escribir el keystream en `keystream`
+    fn fill_keystream(&mut self, keystream: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// `data[i] ^= keystream[i]`, comprobando que las longitudes coinciden
+pub fn xor_in_place(data: &mut [u8], keystream: &[u8]) -> Result<(), Error> {
+    if data.len() != keystream.len() {
+        return Err(Error::LengthMismatch { data: data.len(), keystream: keystream.len() });
+    }
+    data.iter_mut().zip(keystream).for_each(|(byte, key)| *byte ^= key);
+    Ok(())
+}
+
+/// Transformar en el sitio los índices de una frase
+///
+/// XOR de la entropía con el keystream y checksum recalculado: el
+/// resultado es otra frase BIP39 válida del mismo tamaño, y aplicar la
+/// transformación otra vez con el mismo keystream devuelve la original. El
+/// checksum de la entrada no se comprueba (eso es cosa del llamador).
+pub fn transform_indices<S: KeystreamSource>(indices: &mut [u16], source: &mut S) -> Result<(), S::Error> {
+    let entropy_len = entropy_bits(indices.len())? / 8;
+    if let Some(&index) = indices.iter().find(|&&index| index > 0x7FF) {
+        return Err(Error::InvalidIndex(index).into());
+    }
+
+    let mut packed = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+    pack_indices_into(indices, &mut packed[..]);
+
+    let mut keystream = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+    source.fill_keystream(&mut keystream[..entropy_len])?;
+    xor_in_place(&mut packed[..entropy_len], &keystream[..entropy_len])?;
+
+    packed[entropy_len] = checksum_byte(&packed[..entropy_len]);
+    unpack_indices_into(&packed[..=entropy_len], indices);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entropy_to_indices, indices_to_entropy};
+
+    /// Keystream fijo, como lo daría un KDF
+    struct Fixed(u8);
+
+    impl KeystreamSource for Fixed {
+        type Error = Error;
+
+        fn fill_keystream(&mut self, keystream: &mut [u8]) -> Result<(), Error> {
+            keystream.fill(self.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_transform_is_involution() {
+        let original = entropy_to_indices(&[0x5A; 16]).unwrap();
+        let mut indices = original.clone();
+
+        transform_indices(&mut indices, &mut Fixed(0xA5)).unwrap();
+        assert_eq!(indices_to_entropy(&indices).unwrap().as_slice(), &[0xFF; 16]);
+        transform_indices(&mut indices, &mut Fixed(0xA5)).unwrap();
+        assert_eq!(indices, original);
+
+        assert_eq!(transform_indices(&mut [0u16; 13], &mut Fixed(0)), Err(Error::InvalidWordCount(13)));
+        assert_eq!(transform_indices(&mut [2048u16; 12], &mut Fixed(0)), Err(Error::InvalidIndex(2048)));
+        assert!(xor_in_place(&mut [0u8; 2], &[0u8; 3]).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! API pública estable
+//!
+//! Superficie pequeña para crates que dependen de `scypher-rust`, separada
+//! de los módulos internos (`crypto`, `bip39`, `armor`...), que se
+//! reorganizan cuando hace falta. Lo que se exporta aquí sigue semver: un
+//! cambio incompatible solo llega con una versión mayor, y los tipos no
+//! exponen tipos internos.
+//!
+//! - [`Params`]: parámetros de derivación (Argon2id, iteraciones, memoria,
+//!   lanes, salt), serializables a JSON
+//! - [`Mnemonic`]: frase BIP39 validada, en cualquier idioma admitido; se
+//!   borra de memoria al soltarla
+//! - [`Transform`]: cifrar o descifrar una frase (la misma operación)
+//! - [`Container`] (feature `armor`): la frase cifrada en un contenedor
+//!   age/OpenPGP junto con sus parámetros
+//!
+//! Los errores son [`Error`] (`SCypherError`), con códigos numéricos estables.
+//!
+//! ```rust,no_run
+//! use scypher_rust::api::{Mnemonic, Params, Transform};
+//!
+//! let phrase = Mnemonic::parse("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")?;
+//! let transform = Transform::new(Params::default());
+//! let encrypted = transform.apply(&phrase, "my_secure_password")?;
+//! assert_eq!(transform.apply(&encrypted, "my_secure_password")?.phrase(), phrase.phrase());
+//! # Ok::<(), scypher_rust::api::Error>(())
+//! ```
+//!
+//! El protocolo JSON de `scypher api` y del daemon no forma parte de esta
+//! fachada: está en [`crate::protocol`], cuyos tipos sí usan los internos.
+
+use std::fmt;
+
+use zeroize::Zeroizing;
+
+use crate::bip39::Language;
+use crate::crypto::{SaltSource, ScypherParams};
+
+pub use crate::error::{Result, SCypherError as Error};
+
+/// Parámetros de derivación de una transformación
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Params(ScypherParams);
+
+impl Params {
+    /// Argon2id con `iterations` pasadas y `memory_kib` KiB; el resto por defecto
+    pub fn new(iterations: u32, memory_kib: u32) -> Result<Self> {
+        ScypherParams::builder().iterations(iterations).memory_cost(memory_kib).build().map(Params)
+    }
+
+    /// Los mismos parámetros con `lanes` carriles de Argon2
+    pub fn with_lanes(self, lanes: u32) -> Result<Self> {
+        self.0.to_builder().lanes(lanes).build().map(Params)
+    }
+
+    /// Los mismos parámetros con un salt explícito en lugar del derivado de la contraseña
+    pub fn with_salt(self, salt: &[u8]) -> Result<Self> {
+        self.0.to_builder().salt(SaltSource::Explicit(salt.to_vec())).build().map(Params)
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.0.iterations()
+    }
+
+    pub fn memory_kib(&self) -> u32 {
+        self.0.memory_cost()
+    }
+
+    pub fn lanes(&self) -> u32 {
+        self.0.lanes()
+    }
+
+    /// JSON con todos los parámetros, el mismo que aceptan `scypher api` y los contenedores
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).expect("parameters always serialize")
+    }
+
+    /// Leer parámetros de `to_json`; los campos ausentes toman el valor por defecto
+    pub fn from_json(json: &str) -> Result<Self> {
+        ScypherParams::from_json(json).map(Params)
+    }
+}
+
+/// Frase BIP39 validada (palabras, longitud y checksum)
+pub struct Mnemonic {
+    phrase: Zeroizing<String>,
+    language: Language,
+}
+
+impl Mnemonic {
+    /// Validar una frase; el idioma se deduce de las palabras
+    pub fn parse(phrase: &str) -> Result<Self> {
+        let language = crate::bip39::languages::detect_language(phrase)?.language;
+        Self::with_language(phrase, language)
+    }
+
+    /// Validar una frase de la lista `language` (código: "en", "es", "ja"...)
+    pub fn parse_in(phrase: &str, language: &str) -> Result<Self> {
+        let language = Language::from_code(language)
+            .ok_or_else(|| Error::InvalidRequest(format!("unknown language '{}'", language)))?;
+        Self::with_language(phrase, language)
+    }
+
+    /// Frase inglesa aleatoria de `word_count` palabras (12, 15, 18, 21 o 24)
+    pub fn generate(word_count: usize) -> Result<Self> {
+        crate::bip39::validate_word_count(word_count)?;
+        let phrase = Zeroizing::new(crate::bip39::conversion::generate_seed_phrase(word_count * 32 / 3)?);
+        Ok(Self { phrase, language: Language::English })
+    }
+
+    fn with_language(phrase: &str, language: Language) -> Result<Self> {
+        crate::bip39::validation::validate_seed_phrase_with(phrase, language.wordlist())?;
+        let mut normalized = Zeroizing::new(String::with_capacity(phrase.len()));
+        for word in phrase.split_whitespace() {
+            if !normalized.is_empty() {
+                normalized.push(' ');
+            }
+            normalized.push_str(word);
+        }
+        Ok(Self { phrase: normalized, language })
+    }
+
+    /// Palabras separadas por un espacio
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.phrase.split_whitespace().count()
+    }
+
+    /// Código del idioma de la lista de palabras
+    pub fn language(&self) -> &'static str {
+        self.language.code()
+    }
+}
+
+// Nunca se muestra la frase
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("words", &self.word_count())
+            .field("language", &self.language())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cifrado/descifrado de frases con unos parámetros fijos
+#[derive(Debug, Clone, Default)]
+pub struct Transform {
+    params: Params,
+}
+
+impl Transform {
+    pub fn new(params: Params) -> Self {
+        Self { params }
+    }
+
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Cifrar o descifrar `mnemonic`: aplicarlo dos veces con la misma
+    /// contraseña devuelve la frase original
+    pub fn apply(&self, mnemonic: &Mnemonic, password: &str) -> Result<Mnemonic> {
+        let language = mnemonic.language;
+        let phrase = crate::crypto::transform_seed_with_wordlist(
+            mnemonic.phrase(), password, &self.params.0, language.wordlist())?;
+        Ok(Mnemonic { phrase, language })
+    }
+}
+
+/// Formato de un [`Container`]
+#[cfg(feature = "armor")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContainerKind {
+    /// Archivo age con passphrase, en ASCII armor
+    Age,
+    /// Mensaje OpenPGP con passphrase, en ASCII armor
+    Pgp,
+}
+
+/// Frase (normalmente ya cifrada) dentro de un contenedor age/OpenPGP
+///
+/// La passphrase del contenedor es independiente de la contraseña de
+/// SCypher. Con una frase cifrada se guardan también sus parámetros, de modo
+/// que el contenedor basta para la recuperación.
+#[cfg(feature = "armor")]
+#[derive(Debug, Clone)]
+pub struct Container {
+    kind: ContainerKind,
+    armored: String,
+}
+
+#[cfg(feature = "armor")]
+impl Container {
+    /// Guardar `mnemonic` (y los `params` con los que se cifró, si los hay)
+    pub fn seal(mnemonic: &Mnemonic, params: Option<&Params>, kind: ContainerKind, passphrase: &str) -> Result<Self> {
+        let document = crate::armor::recovery_document(mnemonic.phrase(), params.map(|params| &params.0))?;
+        let armored = Self::armor(kind).seal(&document, passphrase)?;
+        Ok(Self { kind, armored })
+    }
+
+    /// Reconocer un contenedor en ASCII armor
+    pub fn parse(text: &str) -> Result<Self> {
+        let kind = match crate::armor::Armor::detect(text) {
+            Some(crate::armor::Armor::Age) => ContainerKind::Age,
+            Some(crate::armor::Armor::Pgp) => ContainerKind::Pgp,
+            None => return Err(Error::malformed("container", crate::error::Defect::Encoding, "not an age or OpenPGP armored container")),
+        };
+        Ok(Self { kind, armored: text.to_string() })
+    }
+
+    pub fn kind(&self) -> ContainerKind {
+        self.kind
+    }
+
+    /// Texto ASCII armor del contenedor
+    pub fn as_str(&self) -> &str {
+        &self.armored
+    }
+
+    /// Abrir el contenedor: la frase y, si es una frase cifrada, sus parámetros
+    ///
+    /// Una passphrase incorrecta da `Error::InvalidPassword`.
+    pub fn open(&self, passphrase: &str) -> Result<(Mnemonic, Option<Params>)> {
+        let document = Self::armor(self.kind).open(&self.armored, passphrase)?;
+        let recovered = crate::armor::parse_recovery_document(&document)?;
+        if recovered.keyfiles > 0 {
+            return Err(Error::InvalidRequest(format!(
+                "the container was made with {} keyfile(s), which api::Params does not support",
+                recovered.keyfiles,
+            )));
+        }
+        Ok((Mnemonic::parse(&recovered.phrase)?, recovered.params.map(Params)))
+    }
+
+    fn armor(kind: ContainerKind) -> crate::armor::Armor {
+        match kind {
+            ContainerKind::Age => crate::armor::Armor::Age,
+            ContainerKind::Pgp => crate::armor::Armor::Pgp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_stable_api() {
+        let params = Params::new(1, 8192).unwrap();
+        assert_eq!(Params::from_json(&params.to_json()).unwrap(), params);
+        assert!(Params::new(0, 8192).is_err());
+
+        let mnemonic = Mnemonic::parse(&format!("  {}\n", PHRASE)).unwrap();
+        assert_eq!(mnemonic.phrase(), PHRASE);
+        assert_eq!((mnemonic.word_count(), mnemonic.language()), (12, "en"));
+        assert!(!format!("{:?}", mnemonic).contains("abandon"));
+        assert!(Mnemonic::parse("abandon abandon").is_err());
+
+        let transform = Transform::new(params.clone());
+        let encrypted = transform.apply(&mnemonic, "password123").unwrap();
+        assert_eq!(encrypted.phrase(), "shock strong glory exchange syrup east illness crater board genuine wish clarify");
+        assert_eq!(transform.apply(&encrypted, "password123").unwrap().phrase(), PHRASE);
+
+        #[cfg(feature = "armor")]
+        {
+            let container = Container::seal(&encrypted, Some(&params), ContainerKind::Age, "container pass").unwrap();
+            let parsed = Container::parse(container.as_str()).unwrap();
+            assert_eq!(parsed.kind(), ContainerKind::Age);
+            let (opened, opened_params) = parsed.open("container pass").unwrap();
+            assert_eq!(opened.phrase(), encrypted.phrase());
+            assert_eq!(opened_params, Some(params));
+            assert!(matches!(parsed.open("wrong"), Err(Error::InvalidPassword)));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Archivos age con destinatario de passphrase
+//!
+//! Se abren con cualquier implementación de age (`age -d`, rage, typage...).
+//! El factor de trabajo de scrypt lo elige age para ~1 s en este equipo.
+
+use std::io::{Read, Write};
+use std::iter;
+
+use ::age::armor::{ArmoredReader, ArmoredWriter, Format};
+use ::age::secrecy::SecretString;
+use ::age::{scrypt, DecryptError, Decryptor, Encryptor};
+use base64::engine::general_purpose::STANDARD_NO_PAD as BASE64;
+use base64::Engine;
+use zeroize::Zeroizing;
+
+use crate::error::{Defect, SCypherError, Result};
+
+use super::{Armor, ContainerHeader};
+
+/// Texto en claro de cada trozo del payload (STREAM de age)
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Nonce del payload y etiqueta de cada trozo
+const NONCE_LEN: usize = 16;
+const TAG_LEN: usize = 16;
+
+fn malformed(defect: Defect, message: impl Into<String>) -> SCypherError {
+    SCypherError::malformed("age", defect, message)
+}
+
+/// Cifrar `plaintext`; `work_factor` fija log2(N) de scrypt en vez del automático
+pub(super) fn seal(plaintext: &[u8], passphrase: &str, work_factor: Option<u8>) -> Result<String> {
+    let mut recipient = scrypt::Recipient::new(SecretString::from(passphrase.to_string()));
+    if let Some(log_n) = work_factor {
+        recipient.set_work_factor(log_n);
+    }
+
+    let encryptor = Encryptor::with_recipients(iter::once(&recipient as _))
+        .map_err(|e| SCypherError::crypto(format!("Cannot create age file: {}", e)))?;
+
+    let io_error = |e: std::io::Error| SCypherError::crypto(format!("Cannot write age file: {}", e));
+    let armor = ArmoredWriter::wrap_output(Vec::new(), Format::AsciiArmor).map_err(io_error)?;
+    let mut writer = encryptor.wrap_output(armor).map_err(io_error)?;
+    writer.write_all(plaintext).map_err(io_error)?;
+    let output = writer.finish().and_then(|armor| armor.finish()).map_err(io_error)?;
+
+    String::from_utf8(output).map_err(|_| SCypherError::crypto("age armor is not UTF-8"))
+}
+
+/// Descifrar un archivo age (armored o binario) con destinatario de passphrase
+pub(super) fn open(data: &[u8], passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let decryptor = Decryptor::new_buffered(ArmoredReader::new(data))
+        .map_err(|e| malformed(Defect::Encoding, format!("not an age file: {}", e)))?;
+    if !decryptor.is_scrypt() {
+        return Err(malformed(Defect::Unsupported, "not passphrase-encrypted"));
+    }
+
+    let identity = scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+    let mut reader = decryptor
+        .decrypt(iter::once(&identity as _))
+        .map_err(|e| match e {
+            DecryptError::DecryptionFailed | DecryptError::NoMatchingKeys => SCypherError::InvalidPassword,
+            e => SCypherError::crypto(format!("Cannot decrypt age file: {}", e)),
+        })?;
+
+    let mut plaintext = Zeroizing::new(Vec::new());
+    std::io::Read::read_to_end(&mut reader, &mut plaintext)
+        .map_err(|e| SCypherError::crypto(format!("Cannot decrypt age file: {}", e)))?;
+    Ok(plaintext)
+}
+
+/// Lo que la cabecera de un archivo age deja leer sin la passphrase
+///
+/// La cabecera es texto: la versión, una estrofa `-> tipo argumentos` por
+/// destinatario con su cuerpo en base64 (líneas de 64 caracteres, la última
+/// más corta) y la línea `---` con el MAC. Después va el payload binario.
+pub(super) fn header(data: &[u8]) -> Result<ContainerHeader> {
+    let mut raw = Vec::new();
+    ArmoredReader::new(data)
+        .read_to_end(&mut raw)
+        .map_err(|e| malformed(Defect::Encoding, format!("not an age file: {}", e)))?;
+
+    let mut rest = &raw[..];
+    let version = next_line(&mut rest)?;
+    if !version.starts_with("age-encryption.org/") {
+        return Err(malformed(Defect::Encoding, "missing age-encryption.org version line"));
+    }
+    let mut stanzas = Vec::new();
+    loop {
+        let line = next_line(&mut rest)?;
+        if line.starts_with("---") {
+            break;
+        }
+        let arguments = line.strip_prefix("-> ")
+            .ok_or_else(|| malformed(Defect::Encoding, "expected a recipient stanza"))?;
+        stanzas.push(arguments.split(' ').map(str::to_string).collect::<Vec<_>>());
+        while next_line(&mut rest)?.len() == 64 {}
+    }
+
+    let (kdf, parameters, salt) = match &stanzas[..] {
+        [scrypt] if scrypt.len() == 3 && scrypt[0] == "scrypt" => {
+            let salt = BASE64.decode(&scrypt[1]).map_err(|_| malformed(Defect::Encoding, "scrypt salt"))?;
+            let log_n: u8 = scrypt[2].parse().map_err(|_| malformed(Defect::Encoding, "scrypt work factor"))?;
+            let n = 1u64.checked_shl(log_n.into()).unwrap_or(0);
+            ("scrypt".to_string(), format!("work factor {} (N = {}, r = 8, p = 1)", log_n, n), Some(salt))
+        }
+        stanzas => {
+            let mut types: Vec<&str> = stanzas.iter().filter_map(|stanza| stanza.first().map(String::as_str)).collect();
+            types.dedup();
+            ("public key".to_string(), format!("{} recipient(s): {}", stanzas.len(), types.join(", ")), None)
+        }
+    };
+
+    // Cada trozo de hasta 64 KiB lleva su etiqueta
+    let sealed = rest.len().saturating_sub(NONCE_LEN);
+    let chunks = sealed.div_ceil(CHUNK_SIZE + TAG_LEN).max(1);
+    Ok(ContainerHeader {
+        armor: Armor::Age,
+        armored: data.trim_ascii_start().starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----"),
+        version: version.to_string(),
+        kdf,
+        parameters,
+        cipher: "ChaCha20-Poly1305".to_string(),
+        salt,
+        payload_len: Some(sealed.saturating_sub(chunks * TAG_LEN)),
+        armor_headers: Vec::new(),
+    })
+}
+
+/// Siguiente línea de la cabecera (sin el salto)
+fn next_line<'a>(rest: &mut &'a [u8]) -> Result<&'a str> {
+    let end = rest.iter().position(|&byte| byte == b'\n')
+        .ok_or_else(|| malformed(Defect::Truncated, "header ends early"))?;
+    let line = std::str::from_utf8(&rest[..end]).map_err(|_| malformed(Defect::Encoding, "header is not text"))?;
+    *rest = &rest[end + 1..];
+    Ok(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_roundtrip() {
+        let armored = seal(b"secret phrase\n", "container passphrase", Some(10)).unwrap();
+        assert!(armored.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+
+        assert_eq!(&open(armored.as_bytes(), "container passphrase").unwrap()[..], b"secret phrase\n");
+        assert!(matches!(open(armored.as_bytes(), "wrong"), Err(SCypherError::InvalidPassword)));
+        assert!(matches!(open(b"not age", "x"), Err(SCypherError::MalformedContainer { .. })));
+    }
+}
@@ -0,0 +1,328 @@
+//! Contenedores cifrados estándar para el resultado (age, OpenPGP)
+//!
+//! Una copia de seguridad debe poder abrirse aunque dentro de décadas no
+//! quede ningún binario de SCypher. Este módulo envuelve el resultado en
+//! formatos con implementaciones independientes y muy extendidas, cifrados
+//! con una passphrase propia del contenedor (distinta de la contraseña de
+//! SCypher). Junto a una frase cifrada se guardan los parámetros de
+//! derivación que hacen falta para terminar la recuperación.
+//!
+//! Los lectores de contenedores reciben archivos que pueden venir dañados o
+//! manipulados: comprueban cada longitud antes de usarla, limitan el tamaño
+//! de lo que aceptan y devuelven `SCypherError::MalformedContainer` con el
+//! defecto concreto, nunca un pánico. Tienen objetivos de fuzzing en `fuzz/`.
+
+mod age;
+mod pgp;
+
+use zeroize::Zeroizing;
+
+use crate::crypto::ScypherParams;
+use crate::error::{Defect, SCypherError, Result};
+
+/// Tamaño máximo de un contenedor (armored o binario) que se intenta abrir
+///
+/// Uno de SCypher ocupa menos de 2 KiB; el límite deja margen para archivos
+/// de otras herramientas sin leer entradas arbitrariamente grandes.
+pub const MAX_CONTAINER_LEN: usize = 1 << 20;
+
+/// Rechazar un contenedor de más de `MAX_CONTAINER_LEN` bytes
+fn check_size(format: &'static str, len: usize) -> Result<()> {
+    if len > MAX_CONTAINER_LEN {
+        return Err(SCypherError::malformed(
+            format,
+            Defect::TooLarge,
+            format!("{} bytes (at most {} accepted)", len, MAX_CONTAINER_LEN),
+        ));
+    }
+    Ok(())
+}
+
+/// Formato del contenedor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Armor {
+    /// Archivo age (age-encryption.org) en ASCII armor, con destinatario de passphrase (scrypt)
+    Age,
+    /// Mensaje OpenPGP cifrado con passphrase (AES-256, SEIPD con MDC) en ASCII armor
+    Pgp,
+}
+
+impl Armor {
+    /// Todos los contenedores disponibles
+    pub const ALL: [Armor; 2] = [Armor::Age, Armor::Pgp];
+
+    /// Nombre para `--armor`
+    pub fn name(self) -> &'static str {
+        match self {
+            Armor::Age => "age",
+            Armor::Pgp => "pgp",
+        }
+    }
+
+    /// Buscar un contenedor por nombre
+    pub fn from_name(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|armor| armor.name() == name)
+            .ok_or_else(|| SCypherError::InvalidRequest(format!("unknown armor '{}'", name)))
+    }
+
+    /// Reconocer un contenedor por su cabecera ASCII armor
+    pub fn detect(text: &str) -> Option<Self> {
+        match text.trim_start().lines().next()?.trim_end() {
+            "-----BEGIN AGE ENCRYPTED FILE-----" => Some(Armor::Age),
+            "-----BEGIN PGP MESSAGE-----" => Some(Armor::Pgp),
+            _ => None,
+        }
+    }
+
+    /// Nombre del formato en los errores
+    fn format_name(self) -> &'static str {
+        match self {
+            Armor::Age => "age",
+            Armor::Pgp => "openpgp",
+        }
+    }
+
+    /// Cifrar `plaintext` con `passphrase` y devolver el contenedor en texto
+    pub fn seal(self, plaintext: &str, passphrase: &str) -> Result<String> {
+        match self {
+            Armor::Age => age::seal(plaintext.as_bytes(), passphrase, None),
+            Armor::Pgp => pgp::seal(plaintext.as_bytes(), passphrase, pgp::S2K_COUNT),
+        }
+    }
+
+    /// Descifrar un contenedor; una passphrase incorrecta da `InvalidPassword`
+    pub fn open(self, armored: &str, passphrase: &str) -> Result<Zeroizing<String>> {
+        check_size(self.format_name(), armored.len())?;
+        let plaintext = match self {
+            Armor::Age => age::open(armored.as_bytes(), passphrase)?,
+            Armor::Pgp => pgp::open(armored, passphrase)?,
+        };
+        std::str::from_utf8(&plaintext)
+            .map(|text| Zeroizing::new(text.to_string()))
+            .map_err(|_| SCypherError::crypto("Container does not hold UTF-8 text"))
+    }
+}
+
+/// Lo que un contenedor deja leer sin su passphrase (`scypher inspect`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerHeader {
+    pub armor: Armor,
+    /// En ASCII armor; si no, binario
+    pub armored: bool,
+    /// Versión del formato y de sus paquetes
+    pub version: String,
+    /// Cómo se protege la clave: KDF de la passphrase o clave pública
+    pub kdf: String,
+    /// Parámetros de la KDF o destinatarios
+    pub parameters: String,
+    pub cipher: String,
+    /// Salt de la KDF; `None` sin passphrase o sin salt
+    pub salt: Option<Vec<u8>>,
+    /// Tamaño aproximado del contenido en bytes, si la cabecera lo deja calcular
+    ///
+    /// En OpenPGP es el del contenido tal como se cifró: gpg lo comprime.
+    pub payload_len: Option<usize>,
+    /// Cabeceras del ASCII armor (`Comment`, `Version`...)
+    pub armor_headers: Vec<(String, String)>,
+}
+
+impl ContainerHeader {
+    /// Si por su tamaño guarda una nota de `recovery_document` (frase y parámetros)
+    ///
+    /// La nota solo con el título y la explicación ya ocupa más que
+    /// cualquier frase, semilla o clave extendida sueltas.
+    pub fn holds_recovery_document(&self) -> Option<bool> {
+        self.payload_len.map(|len| len > RECOVERY_TITLE.len() + RECOVERY_NOTE.len())
+    }
+}
+
+/// Leer la cabecera de un contenedor sin descifrarlo; `None` si `data` no lo es
+///
+/// Reconoce age y OpenPGP en ASCII armor o binarios, también los que no
+/// escribe SCypher (`age -p`, `gpg --symmetric` o `gpg --encrypt`).
+pub fn inspect(data: &[u8]) -> Option<Result<ContainerHeader>> {
+    let armor = match std::str::from_utf8(data).ok().and_then(Armor::detect) {
+        Some(armor) => armor,
+        None if data.starts_with(b"age-encryption.org/") => Armor::Age,
+        None if pgp::starts_with_session_key(data) => Armor::Pgp,
+        None => return None,
+    };
+    Some(check_size(armor.format_name(), data.len()).and_then(|()| match armor {
+        Armor::Age => age::header(data),
+        Armor::Pgp => pgp::header(data),
+    }))
+}
+
+/// Descifrar un archivo age con passphrase, en ASCII armor o binario (`age -p`)
+pub fn open_age_file(data: &[u8], passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    check_size("age", data.len())?;
+    age::open(data, passphrase)
+}
+
+/// Título de la nota que acompaña a una frase cifrada
+const RECOVERY_TITLE: &str = "SCypher encrypted seed phrase";
+
+/// Explicación de la nota, entre el título y los parámetros
+const RECOVERY_NOTE: &str = "\n\n\
+The phrase below is NOT the wallet seed. SCypher XORs the BIP39 entropy
+with a keystream derived by Argon2id from the password, then recomputes the
+checksum. Applying the same transformation with the same password and these
+parameters returns the original phrase.\n\n";
+
+/// Contenido de un contenedor abierto
+#[derive(Debug)]
+pub struct RecoveryDocument {
+    pub phrase: Zeroizing<String>,
+    /// Parámetros de una frase cifrada; `None` si el contenedor guarda una frase sin cifrar
+    pub params: Option<ScypherParams>,
+    /// Número de keyfiles usados al cifrar (sus hashes no se guardan)
+    pub keyfiles: usize,
+}
+
+/// Leer la frase y los parámetros de un documento de `recovery_document`
+pub fn parse_recovery_document(document: &str) -> Result<RecoveryDocument> {
+    let malformed = |defect, message: &str| SCypherError::malformed("recovery document", defect, message);
+
+    let phrase = document.lines().rev().map(str::trim).find(|line| !line.is_empty())
+        .ok_or_else(|| malformed(Defect::Truncated, "container is empty"))?;
+    let phrase = Zeroizing::new(phrase.to_string());
+
+    if document.lines().next().map(str::trim) != Some(RECOVERY_TITLE) {
+        return Ok(RecoveryDocument { phrase, params: None, keyfiles: 0 });
+    }
+
+    let json = document.lines().find_map(|line| line.strip_prefix("Parameters: "))
+        .ok_or_else(|| malformed(Defect::Truncated, "no Parameters line"))?;
+    let params = serde_json::from_str(json)
+        .map_err(|e| malformed(Defect::Encoding, &format!("invalid parameters: {}", e)))?;
+    let keyfiles = document.lines()
+        .find_map(|line| line.strip_prefix("Keyfiles: "))
+        .map(|rest| rest.split_whitespace().next().unwrap_or("").parse::<usize>())
+        .transpose()
+        .map_err(|_| malformed(Defect::Encoding, "invalid Keyfiles line"))?
+        .unwrap_or(0);
+
+    Ok(RecoveryDocument { phrase, params: Some(params), keyfiles })
+}
+
+/// Texto a guardar en el contenedor
+///
+/// Con `params` (frase cifrada por SCypher) se antepone una nota con los
+/// parámetros en el mismo JSON que acepta la API; sin ellos se guarda solo
+/// `phrase`.
+pub fn recovery_document(phrase: &str, params: Option<&ScypherParams>) -> Result<Zeroizing<String>> {
+    let params = match params {
+        Some(params) => params,
+        None => return Ok(Zeroizing::new(format!("{}\n", phrase))),
+    };
+
+    let json = serde_json::to_string(params)
+        .map_err(|e| SCypherError::crypto(format!("Cannot serialize parameters: {}", e)))?;
+
+    let mut document = Zeroizing::new(String::with_capacity(640 + json.len() + phrase.len()));
+    document.push_str(RECOVERY_TITLE);
+    document.push_str(RECOVERY_NOTE);
+    document.push_str(&format!("Parameters: {}\n", json));
+    if !params.keyfile_digests().is_empty() {
+        document.push_str(&format!(
+            "Keyfiles: {} (the same files, in the same order, are needed)\n",
+            params.keyfile_digests().len()
+        ));
+    }
+    document.push_str(&format!("\n{}\n", phrase));
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_document() {
+        let phrase = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+
+        let document = recovery_document(phrase, Some(&params)).unwrap();
+        assert!(document.contains("\"iterations\":1,\"memory_cost\":8192"));
+        assert!(document.ends_with(&format!("\n\n{}\n", phrase)));
+        assert!(!document.contains("Keyfiles"));
+
+        // Los parámetros de la nota se pueden volver a cargar tal cual
+        let json = document.lines().find_map(|line| line.strip_prefix("Parameters: ")).unwrap();
+        assert_eq!(serde_json::from_str::<ScypherParams>(json).unwrap(), params);
+
+        assert_eq!(*recovery_document(phrase, None).unwrap(), format!("{}\n", phrase));
+    }
+
+    #[test]
+    fn test_parse_recovery_document() {
+        let phrase = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+        let params = ScypherParams::builder().iterations(2).memory_cost(16384).lanes(4)
+            .keyfile(b"one").keyfile(b"two").build().unwrap();
+
+        let parsed = parse_recovery_document(&recovery_document(phrase, Some(&params)).unwrap()).unwrap();
+        assert_eq!(*parsed.phrase, phrase);
+        assert_eq!(parsed.keyfiles, 2);
+        let recorded = parsed.params.unwrap();
+        assert_eq!((recorded.iterations(), recorded.memory_cost(), recorded.lanes()), (2, 16384, 4));
+
+        let parsed = parse_recovery_document(&recovery_document(phrase, None).unwrap()).unwrap();
+        assert_eq!(*parsed.phrase, phrase);
+        assert!(parsed.params.is_none());
+
+        assert!(parse_recovery_document("SCypher encrypted seed phrase\n\nabandon").is_err());
+        assert!(matches!(
+            parse_recovery_document("\n \n"),
+            Err(SCypherError::MalformedContainer { defect: Defect::Truncated, .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Armor::from_name("age").unwrap(), Armor::Age);
+        assert_eq!(Armor::from_name("pgp").unwrap(), Armor::Pgp);
+        assert!(Armor::from_name("zip").is_err());
+
+        assert_eq!(Armor::detect("\n-----BEGIN AGE ENCRYPTED FILE-----\nYWdl"), Some(Armor::Age));
+        assert_eq!(Armor::detect("-----BEGIN PGP MESSAGE-----\r\n"), Some(Armor::Pgp));
+        assert_eq!(Armor::detect("abandon abandon"), None);
+
+        let huge = format!("-----BEGIN PGP MESSAGE-----\n\n{}", "A".repeat(MAX_CONTAINER_LEN));
+        assert!(matches!(
+            Armor::Pgp.open(&huge, "x"),
+            Err(SCypherError::MalformedContainer { defect: Defect::TooLarge, .. })
+        ));
+    }
+    #[test]
+    fn test_inspect() {
+        let phrase = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+        let document = recovery_document(phrase, Some(&params)).unwrap();
+        assert!(document.starts_with("SCypher encrypted seed phrase\n\nThe phrase below is NOT the wallet seed."));
+
+        let aged = age::seal(document.as_bytes(), "container passphrase", Some(10)).unwrap();
+        let header = inspect(aged.as_bytes()).unwrap().unwrap();
+        assert_eq!((header.armor, header.armored), (Armor::Age, true));
+        assert_eq!(header.version, "age-encryption.org/v1");
+        assert_eq!(header.kdf, "scrypt");
+        assert!(header.parameters.starts_with("work factor 10 "));
+        assert_eq!(header.salt.as_ref().map(Vec::len), Some(16));
+        assert_eq!(header.payload_len, Some(document.len()));
+        assert_eq!(header.holds_recovery_document(), Some(true));
+
+        let pgp = Armor::Pgp.seal(phrase, "container passphrase").unwrap();
+        let header = inspect(pgp.as_bytes()).unwrap().unwrap();
+        assert_eq!(header.version, "SKESK v4, SEIPD v1");
+        assert_eq!(header.cipher, "AES-256");
+        assert_eq!(header.parameters, "iterated and salted, 65011712 bytes hashed");
+        assert_eq!(header.salt.as_ref().map(Vec::len), Some(8));
+        // Literal: cabecera de paquete y de datos
+        assert_eq!(header.payload_len, Some(phrase.len() + 2 + 6));
+        assert_eq!(header.holds_recovery_document(), Some(false));
+
+        assert!(inspect(phrase.as_bytes()).is_none());
+        assert!(inspect(b"-----BEGIN PGP MESSAGE-----\n\n!!\n").unwrap().is_err());
+    }
+}
@@ -0,0 +1,495 @@
+//! Mensajes OpenPGP cifrados con passphrase (RFC 4880), en ASCII armor
+//!
+//! El mensaje es un paquete SKESK v4 (AES-256, S2K iterado y con salt sobre
+//! SHA-256) seguido de un paquete SEIPD v1 con MDC: lo que produce
+//! `gpg --symmetric --cipher-algo AES256`, y se abre con `gpg -d` o
+//! cualquier implementación OpenPGP.
+
+use aes::Aes256;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use cfb_mode::cipher::{AsyncStreamCipher, KeyIvInit};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::crypto::SecureRng;
+use crate::error::{Defect, SCypherError, Result};
+
+use super::{Armor, ContainerHeader};
+
+/// Cuenta codificada del S2K: 0xFF = 65011712 bytes hasheados (el máximo)
+pub(super) const S2K_COUNT: u8 = 0xFF;
+
+const TAG_PKESK: u8 = 1;
+const TAG_SKESK: u8 = 3;
+const TAG_SED: u8 = 9;
+const TAG_LITERAL: u8 = 11;
+const TAG_SEIPD: u8 = 18;
+const TAG_MDC: u8 = 19;
+
+const CIPHER_AES256: u8 = 9;
+const HASH_SHA256: u8 = 8;
+const S2K_SIMPLE: u8 = 0;
+const S2K_SALTED: u8 = 1;
+const S2K_ITERATED_SALTED: u8 = 3;
+
+const BLOCK_SIZE: usize = 16;
+const ARMOR_BEGIN: &str = "-----BEGIN PGP MESSAGE-----";
+const ARMOR_END: &str = "-----END PGP MESSAGE-----";
+
+fn malformed(defect: Defect, message: &str) -> SCypherError {
+    SCypherError::malformed("openpgp", defect, message)
+}
+
+/// Cifrar `plaintext` con `passphrase`; `s2k_count` es la cuenta codificada del S2K
+pub(super) fn seal(plaintext: &[u8], passphrase: &str, s2k_count: u8) -> Result<String> {
+    seal_with_rng(plaintext, passphrase, s2k_count, &mut rand::rngs::OsRng)
+}
+
+/// `seal` con el salt del S2K y el prefijo aleatorio tomados de `rng`
+pub(super) fn seal_with_rng(plaintext: &[u8], passphrase: &str, s2k_count: u8, rng: &mut dyn SecureRng) -> Result<String> {
+    let mut salt = [0u8; 8];
+    let mut prefix = [0u8; BLOCK_SIZE + 2];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut prefix[..BLOCK_SIZE]);
+    // Los dos últimos bytes del prefijo repiten los dos anteriores (comprobación rápida de clave)
+    prefix[BLOCK_SIZE] = prefix[BLOCK_SIZE - 2];
+    prefix[BLOCK_SIZE + 1] = prefix[BLOCK_SIZE - 1];
+
+    let key = s2k(passphrase.as_bytes(), &salt, s2k_count);
+
+    let mut skesk = vec![4, CIPHER_AES256, S2K_ITERATED_SALTED, HASH_SHA256];
+    skesk.extend_from_slice(&salt);
+    skesk.push(s2k_count);
+
+    // Literal binario, sin nombre de archivo ni fecha
+    let mut literal = vec![b'b', 0, 0, 0, 0, 0];
+    literal.extend_from_slice(plaintext);
+    let literal = Zeroizing::new(literal);
+
+    // prefijo || literal || MDC, donde el MDC es SHA-1 de todo lo anterior más su propia cabecera
+    let mut body = Zeroizing::new(Vec::with_capacity(prefix.len() + literal.len() + 32));
+    body.extend_from_slice(&prefix);
+    write_packet(&mut body, TAG_LITERAL, &literal);
+    body.extend_from_slice(&[0xC0 | TAG_MDC, 20]);
+    let mdc = Sha1::digest(&body[..]);
+    body.extend_from_slice(&mdc);
+
+    cfb_mode::Encryptor::<Aes256>::new(key[..].into(), &[0u8; BLOCK_SIZE].into()).encrypt(&mut body[..]);
+
+    let mut seipd = Vec::with_capacity(body.len() + 1);
+    seipd.push(1);
+    seipd.extend_from_slice(&body);
+
+    let mut message = Vec::new();
+    write_packet(&mut message, TAG_SKESK, &skesk);
+    write_packet(&mut message, TAG_SEIPD, &seipd);
+    Ok(armor(&message))
+}
+
+/// Descifrar un mensaje producido por `seal`
+///
+/// Solo lee lo que escribe `seal`: longitudes definidas y literal sin
+/// comprimir. Los mensajes de gpg (comprimidos, con longitudes parciales) se
+/// abren con gpg. La estructura se comprueba entera antes de derivar la
+/// clave, y cualquier byte de más es un error.
+pub(super) fn open(armored: &str, passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let message = dearmor(armored)?;
+    let mut packets = Packets { data: &message };
+
+    let (tag, skesk) = packets.next().ok_or_else(|| malformed(Defect::Truncated, "no packets"))??;
+    if tag != TAG_SKESK {
+        return Err(malformed(Defect::Unsupported, "expected a passphrase packet"));
+    }
+    if skesk.len() != 13 || skesk[..4] != [4, CIPHER_AES256, S2K_ITERATED_SALTED, HASH_SHA256] {
+        return Err(malformed(Defect::Unsupported, "expected AES-256 with an iterated SHA-256 S2K"));
+    }
+
+    let (tag, seipd) = packets.next().ok_or_else(|| malformed(Defect::Truncated, "no encrypted data"))??;
+    if tag != TAG_SEIPD || seipd.first() != Some(&1) {
+        return Err(malformed(Defect::Unsupported, "expected an integrity-protected data packet"));
+    }
+    // Prefijo, cabecera de un literal vacío y MDC como mínimo
+    if seipd.len() < 1 + BLOCK_SIZE + 2 + 8 + 22 {
+        return Err(malformed(Defect::Truncated, "encrypted data is too short"));
+    }
+    if packets.next().is_some() {
+        return Err(malformed(Defect::Inconsistent, "unexpected packets after the encrypted data"));
+    }
+
+    let key = s2k(passphrase.as_bytes(), &skesk[4..12], skesk[12]);
+    let mut body = Zeroizing::new(seipd[1..].to_vec());
+    cfb_mode::Decryptor::<Aes256>::new(key[..].into(), &[0u8; BLOCK_SIZE].into()).decrypt(&mut body[..]);
+
+    // Con otra clave la repetición del prefijo o el MDC no cuadran
+    let (content, mdc) = body.split_at(body.len() - 20);
+    if body[BLOCK_SIZE - 2..BLOCK_SIZE] != body[BLOCK_SIZE..BLOCK_SIZE + 2]
+        || content[content.len() - 2..] != [0xC0 | TAG_MDC, 20]
+        || Sha1::digest(content)[..] != mdc[..]
+    {
+        return Err(SCypherError::InvalidPassword);
+    }
+
+    let mut inner = Packets { data: &content[BLOCK_SIZE + 2..content.len() - 2] };
+    let (tag, literal) = inner.next().ok_or_else(|| malformed(Defect::Truncated, "empty message"))??;
+    if tag != TAG_LITERAL {
+        return Err(malformed(Defect::Unsupported, "expected literal data (compressed messages are not supported)"));
+    }
+    if inner.next().is_some() {
+        return Err(malformed(Defect::Inconsistent, "unexpected packets after the literal data"));
+    }
+    let header_len = literal.get(1).map_or(usize::MAX, |&name_len| 6 + name_len as usize);
+    let data = literal.get(header_len..).ok_or_else(|| malformed(Defect::Truncated, "literal data header"))?;
+    Ok(Zeroizing::new(data.to_vec()))
+}
+
+/// Si `data` empieza por un mensaje binario con clave de sesión (PKESK o SKESK)
+pub(super) fn starts_with_session_key(data: &[u8]) -> bool {
+    matches!(Packets { data }.next(), Some(Ok((TAG_PKESK | TAG_SKESK, _))))
+}
+
+/// Lo que un mensaje cifrado (armored o binario) deja leer sin la passphrase
+///
+/// Admite también lo que produce gpg: varios paquetes de clave de sesión,
+/// otros cifrados y S2K, y datos con longitudes parciales (de los que no se
+/// sabe el tamaño sin leerlos enteros).
+pub(super) fn header(data: &[u8]) -> Result<ContainerHeader> {
+    let armored = std::str::from_utf8(data).ok().filter(|text| Armor::detect(text) == Some(Armor::Pgp));
+    let message = match armored {
+        Some(text) => dearmor(text)?,
+        None => data.to_vec(),
+    };
+
+    let mut header = ContainerHeader {
+        armor: Armor::Pgp,
+        armored: armored.is_some(),
+        version: String::new(),
+        kdf: String::new(),
+        parameters: String::new(),
+        cipher: String::new(),
+        salt: None,
+        payload_len: None,
+        armor_headers: armored.map(armor_headers).unwrap_or_default(),
+    };
+    let mut versions = Vec::new();
+    let mut recipients = Vec::new();
+    for packet in (Packets { data: &message }) {
+        let (tag, body) = match packet {
+            Ok(packet) => packet,
+            // Datos en streaming de gpg: las claves de sesión ya están leídas
+            Err(_) if !versions.is_empty() || !recipients.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+        match (tag, body) {
+            (TAG_SKESK, [4, cipher, s2k_type, hash, rest @ ..]) => {
+                versions.push("SKESK v4".to_string());
+                header.cipher = cipher_name(*cipher);
+                header.kdf = format!("OpenPGP S2K with {}", hash_name(*hash));
+                match (*s2k_type, rest) {
+                    (S2K_SIMPLE, _) => header.parameters = "simple (no salt, no iterations)".to_string(),
+                    (S2K_SALTED, rest) if rest.len() >= 8 => {
+                        header.salt = Some(rest[..8].to_vec());
+                        header.parameters = "salted, no iterations".to_string();
+                    }
+                    (S2K_ITERATED_SALTED, rest) if rest.len() >= 9 => {
+                        header.salt = Some(rest[..8].to_vec());
+                        header.parameters = format!("iterated and salted, {} bytes hashed", s2k_bytes(rest[8]));
+                    }
+                    (s2k_type, _) => header.parameters = format!("S2K type {}", s2k_type),
+                }
+            }
+            (TAG_SKESK, [version, ..]) => versions.push(format!("SKESK v{}", version)),
+            (TAG_PKESK, [3, rest @ ..]) if rest.len() > 8 => {
+                recipients.push(format!("0x{}", hex::encode_upper(&rest[..8])));
+            }
+            (TAG_PKESK, [version, ..]) => versions.push(format!("PKESK v{}", version)),
+            (TAG_SEIPD, [version, rest @ ..]) => {
+                versions.push(format!("SEIPD v{}", version));
+                // Sin el prefijo aleatorio ni el MDC
+                header.payload_len = (*version == 1).then(|| rest.len().saturating_sub(BLOCK_SIZE + 2 + 22));
+                break;
+            }
+            (TAG_SED, body) => {
+                versions.push("SED (no integrity protection)".to_string());
+                header.payload_len = Some(body.len().saturating_sub(BLOCK_SIZE + 2));
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if !recipients.is_empty() {
+        versions.insert(0, "PKESK v3".to_string());
+        header.kdf = "public key".to_string();
+        header.parameters = format!("encrypted to key {}", recipients.join(", "));
+        if header.cipher.is_empty() {
+            header.cipher = "chosen inside the encrypted session key".to_string();
+        }
+    }
+    if !versions.iter().any(|version| version.contains("ESK")) {
+        return Err(malformed(Defect::Truncated, "no session key packet"));
+    }
+    header.version = versions.join(", ");
+    Ok(header)
+}
+
+/// Cabeceras "Clave: valor" del ASCII armor
+fn armor_headers(armored: &str) -> Vec<(String, String)> {
+    armored.lines()
+        .map(str::trim)
+        .skip_while(|line| *line != ARMOR_BEGIN)
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Cifrados simétricos de OpenPGP (RFC 4880, 9.2)
+fn cipher_name(id: u8) -> String {
+    match id {
+        2 => "TripleDES".to_string(),
+        3 => "CAST5".to_string(),
+        4 => "Blowfish".to_string(),
+        7 => "AES-128".to_string(),
+        8 => "AES-192".to_string(),
+        CIPHER_AES256 => "AES-256".to_string(),
+        10 => "Twofish".to_string(),
+        11 => "Camellia-128".to_string(),
+        12 => "Camellia-192".to_string(),
+        13 => "Camellia-256".to_string(),
+        id => format!("cipher {}", id),
+    }
+}
+
+/// Hashes de OpenPGP (RFC 4880, 9.4)
+fn hash_name(id: u8) -> String {
+    match id {
+        1 => "MD5".to_string(),
+        2 => "SHA-1".to_string(),
+        3 => "RIPEMD-160".to_string(),
+        HASH_SHA256 => "SHA-256".to_string(),
+        9 => "SHA-384".to_string(),
+        10 => "SHA-512".to_string(),
+        11 => "SHA-224".to_string(),
+        id => format!("hash {}", id),
+    }
+}
+
+/// Bytes que hashea el S2K iterado con la cuenta codificada `count`
+fn s2k_bytes(count: u8) -> usize {
+    (16 + (count as usize & 15)) << ((count >> 4) + 6)
+}
+
+/// S2K iterado y con salt: SHA-256 de (salt || passphrase) repetido hasta `count` bytes
+fn s2k(passphrase: &[u8], salt: &[u8], count: u8) -> Zeroizing<[u8; 32]> {
+    let count = s2k_bytes(count).max(salt.len() + passphrase.len());
+
+    let mut hasher = Sha256::new();
+    let mut remaining = count;
+    while remaining > 0 {
+        for part in [salt, passphrase] {
+            let take = part.len().min(remaining);
+            hasher.update(&part[..take]);
+            remaining -= take;
+        }
+    }
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&hasher.finalize());
+    key
+}
+
+/// Añadir un paquete con cabecera de formato nuevo y longitud definida
+fn write_packet(output: &mut Vec<u8>, tag: u8, body: &[u8]) {
+    output.push(0xC0 | tag);
+    match body.len() {
+        len if len < 192 => output.push(len as u8),
+        len if len < 8384 => {
+            let len = len - 192;
+            output.extend_from_slice(&[(len >> 8) as u8 + 192, len as u8]);
+        }
+        len => {
+            output.push(0xFF);
+            output.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+    }
+    output.extend_from_slice(body);
+}
+
+/// Iterador de paquetes (formato nuevo y antiguo, longitudes definidas)
+///
+/// Tras el primer error no devuelve nada más.
+struct Packets<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Packets<'a> {
+    type Item = Result<(u8, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&header, rest) = self.data.split_first()?;
+
+        let parsed = if header & 0xC0 == 0xC0 {
+            let tag = header & 0x3F;
+            match rest {
+                [len, rest @ ..] if *len < 192 => Ok((tag, *len as usize, rest)),
+                [a, b, rest @ ..] if *a < 224 => Ok((tag, ((*a as usize - 192) << 8) + *b as usize + 192, rest)),
+                [0xFF, a, b, c, d, rest @ ..] => Ok((tag, u32::from_be_bytes([*a, *b, *c, *d]) as usize, rest)),
+                [224..=254, ..] => Err(malformed(Defect::Unsupported, "partial body lengths")),
+                _ => Err(malformed(Defect::Truncated, "packet length")),
+            }
+        } else if header & 0x80 != 0 {
+            let tag = (header >> 2) & 0x0F;
+            match (header & 3, rest) {
+                (0, [len, rest @ ..]) => Ok((tag, *len as usize, rest)),
+                (1, [a, b, rest @ ..]) => Ok((tag, u16::from_be_bytes([*a, *b]) as usize, rest)),
+                (2, [a, b, c, d, rest @ ..]) => Ok((tag, u32::from_be_bytes([*a, *b, *c, *d]) as usize, rest)),
+                (3, _) => Err(malformed(Defect::Unsupported, "indeterminate packet length")),
+                _ => Err(malformed(Defect::Truncated, "packet length")),
+            }
+        } else {
+            Err(malformed(Defect::Encoding, "not a packet header"))
+        };
+
+        let parsed = parsed.and_then(|(tag, len, rest)| match rest.get(..len) {
+            Some(body) => Ok((tag, body, &rest[len..])),
+            None => Err(malformed(Defect::Truncated, "packet body")),
+        });
+        match parsed {
+            Ok((tag, body, rest)) => {
+                self.data = rest;
+                Some(Ok((tag, body)))
+            }
+            Err(e) => {
+                self.data = &[];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// ASCII armor con checksum CRC-24
+fn armor(message: &[u8]) -> String {
+    let encoded = BASE64.encode(message);
+    let mut output = String::with_capacity(encoded.len() * 65 / 64 + 80);
+    output.push_str(ARMOR_BEGIN);
+    output.push_str("\n\n");
+    for line in encoded.as_bytes().chunks(64) {
+        output.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        output.push('\n');
+    }
+    output.push('=');
+    output.push_str(&BASE64.encode(&crc24(message).to_be_bytes()[1..]));
+    output.push('\n');
+    output.push_str(ARMOR_END);
+    output.push('\n');
+    output
+}
+
+/// Quitar el armor: cabeceras, base64 y (si está) el checksum
+fn dearmor(armored: &str) -> Result<Vec<u8>> {
+    let mut lines = armored.lines().map(str::trim);
+    if !lines.by_ref().any(|line| line == ARMOR_BEGIN) {
+        return Err(malformed(Defect::Encoding, "missing BEGIN PGP MESSAGE line"));
+    }
+
+    // Cabeceras "Clave: valor" hasta la primera línea vacía
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        if !line.contains(": ") {
+            return Err(malformed(Defect::Encoding, "missing blank line after the armor headers"));
+        }
+    }
+
+    let mut encoded = String::with_capacity(armored.len());
+    let mut checksum = None;
+    for line in lines {
+        if line == ARMOR_END {
+            let message = BASE64.decode(&encoded).map_err(|_| malformed(Defect::Encoding, "bad base64"))?;
+            if let Some(checksum) = checksum {
+                let crc = crc24(&message).to_be_bytes();
+                if BASE64.decode(checksum).ok().as_deref() != Some(&crc[1..]) {
+                    return Err(malformed(Defect::Checksum, "armor checksum mismatch"));
+                }
+            }
+            return Ok(message);
+        }
+        if checksum.is_some() {
+            return Err(malformed(Defect::Encoding, "data after the armor checksum"));
+        }
+        match line.strip_prefix('=') {
+            Some(crc) => checksum = Some(crc),
+            None => encoded.push_str(line),
+        }
+    }
+    Err(malformed(Defect::Truncated, "missing END PGP MESSAGE line"))
+}
+
+/// CRC-24 de OpenPGP (RFC 4880, sección 6.1)
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xB704CE;
+    for &byte in data {
+        crc ^= (byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x100_0000 != 0 {
+                crc ^= 0x186_4CFB;
+            }
+        }
+    }
+    crc & 0xFF_FFFF
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pgp_roundtrip() {
+        let armored = seal(b"secret phrase\n", "container passphrase", 0x10).unwrap();
+        assert!(armored.starts_with(ARMOR_BEGIN));
+        assert!(armored.trim_end().ends_with(ARMOR_END));
+
+        assert_eq!(&open(&armored, "container passphrase").unwrap()[..], b"secret phrase\n");
+        assert!(matches!(open(&armored, "wrong"), Err(SCypherError::InvalidPassword)));
+        assert!(open("not pgp", "x").is_err());
+
+        // Todo prefijo del mensaje es un error de formato, nunca un pánico
+        let message = dearmor(&armored).unwrap();
+        for len in 0..message.len() {
+            let truncated = armor(&message[..len]);
+            assert!(matches!(open(&truncated, "container passphrase"), Err(SCypherError::MalformedContainer { .. })));
+        }
+        let mut trailing = message.clone();
+        trailing.extend_from_slice(&[0xCB, 0]);
+        assert!(matches!(
+            open(&armor(&trailing), "container passphrase"),
+            Err(SCypherError::MalformedContainer { defect: Defect::Inconsistent, .. })
+        ));
+
+        // Con el mismo RNG el contenedor es idéntico byte a byte
+        let seeded = |seed| seal_with_rng(b"secret phrase\n", "pass", 0x10, &mut crate::crypto::seeded_rng(seed)).unwrap();
+        assert_eq!(seeded([1; 32]), seeded([1; 32]));
+        assert_ne!(seeded([1; 32]), seeded([2; 32]));
+    }
+
+    #[test]
+    fn test_s2k_and_crc24() {
+        // La cuenta 0x60 equivale a 65536 bytes; nunca menos que salt + passphrase
+        let key = s2k(b"abc", &[1, 2, 3, 4, 5, 6, 7, 8], 0x60);
+        let mut expected = Sha256::new();
+        for _ in 0..65536 / 11 {
+            expected.update([1, 2, 3, 4, 5, 6, 7, 8]);
+            expected.update(b"abc");
+        }
+        expected.update(&[1, 2, 3, 4, 5, 6, 7, 8, b'a', b'b', b'c'][..65536 % 11]);
+        assert_eq!(key[..], expected.finalize()[..]);
+
+        // Valor de referencia de CRC-24/OpenPGP
+        assert_eq!(crc24(b"123456789"), 0x21CF02);
+    }
+}
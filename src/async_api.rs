@@ -0,0 +1,225 @@
+//! API asíncrona para embebedores con runtime tokio
+//!
+//! La derivación Argon2id tarda varios segundos y bloquearía el runtime de
+//! una GUI o un servidor. Estas funciones ejecutan la transformación en el
+//! thread pool bloqueante de tokio con los mismos `ScypherParams` y el mismo
+//! `ProgressSink` que la API síncrona: las fases y el avance llegan al sink,
+//! y su `is_cancelled` (por ejemplo un `CancelFlag`) interrumpe la tarea.
+//! Soltar el future antes de que termine también cancela la derivación en
+//! curso, en lugar de dejarla corriendo en segundo plano.
+
+use std::sync::Arc;
+
+use zeroize::Zeroizing;
+
+use crate::crypto::{CancelFlag, Phase, ProgressSink, ScypherParams};
+use crate::error::{SCypherError, Result};
+
+/// Sink de la tarea: reenvía al del llamador y se cancela si se suelta el future
+struct TaskSink {
+    sink: Arc<dyn ProgressSink>,
+    dropped: CancelFlag,
+}
+
+impl ProgressSink for TaskSink {
+    fn on_phase(&self, phase: Phase) {
+        self.sink.on_phase(phase);
+    }
+
+    fn on_progress(&self, completed: usize, total: usize) {
+        self.sink.on_progress(completed, total);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.dropped.is_cancelled() || self.sink.is_cancelled()
+    }
+}
+
+/// Cancela la tarea bloqueante cuando el future que la espera se suelta
+struct CancelOnDrop(CancelFlag);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Ejecutar `task` en el pool bloqueante con un sink ligado a la vida del future
+async fn run_blocking<T, F>(sink: Arc<dyn ProgressSink>, task: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&dyn ProgressSink) -> Result<T> + Send + 'static,
+{
+    let dropped = CancelFlag::new();
+    let _guard = CancelOnDrop(dropped.clone());
+    let sink = TaskSink { sink, dropped };
+
+    tokio::task::spawn_blocking(move || task(&sink))
+        .await
+        .map_err(|e| SCypherError::crypto(format!("Transform task failed: {}", e)))?
+}
+
+/// Transformar una seed phrase sin bloquear el runtime
+///
+/// Equivale a `transform_seed_with_params` informando a `sink`; con
+/// `NoProgress` no hay eventos ni cancelación.
+pub async fn transform_seed_async(
+    seed_phrase: Zeroizing<String>,
+    password: Zeroizing<String>,
+    params: ScypherParams,
+    sink: Arc<dyn ProgressSink>,
+) -> Result<Zeroizing<String>> {
+    run_blocking(sink, move |sink| {
+        crate::crypto::transform_seed_with_progress(&seed_phrase, &password, &params, &crate::bip39::ENGLISH, sink)
+    })
+    .await
+}
+
+/// Transformar varias seed phrases con la misma contraseña sin bloquear el runtime
+///
+/// Equivale a `crypto::transform_batch`: un error en un elemento no detiene
+/// el resto, el vector conserva el orden de entrada y una cancelación
+/// devuelve `SCypherError::Cancelled` para el lote entero.
+pub async fn transform_batch_async(
+    seed_phrases: Vec<Zeroizing<String>>,
+    password: Zeroizing<String>,
+    params: ScypherParams,
+    sink: Arc<dyn ProgressSink>,
+) -> Result<Vec<Result<Zeroizing<String>>>> {
+    run_blocking(sink, move |sink| {
+        let phrases: Vec<&str> = seed_phrases.iter().map(|phrase| phrase.as_str()).collect();
+        crate::crypto::transform_batch(&phrases, &password, &params, sink)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use crate::crypto::NoProgress;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread().build().unwrap()
+    }
+
+    fn fast_params() -> ScypherParams {
+        ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap()
+    }
+
+    fn secret(text: &str) -> Zeroizing<String> {
+        Zeroizing::new(text.to_string())
+    }
+
+    /// Registra fases y avance
+    #[derive(Default)]
+    struct Recorder {
+        phases: Mutex<Vec<Phase>>,
+        progress: Mutex<Vec<(usize, usize)>>,
+    }
+
+    impl ProgressSink for Recorder {
+        fn on_phase(&self, phase: Phase) {
+            self.phases.lock().unwrap().push(phase);
+        }
+
+        fn on_progress(&self, completed: usize, total: usize) {
+            self.progress.lock().unwrap().push((completed, total));
+        }
+    }
+
+    #[test]
+    fn test_transform_seed_async_matches_sync() {
+        let recorder = Arc::new(Recorder::default());
+
+        let result = runtime().block_on(transform_seed_async(
+            secret(TEST_PHRASE),
+            secret("test_password"),
+            fast_params(),
+            recorder.clone(),
+        )).unwrap();
+
+        let expected = crate::crypto::transform_seed_with_params(TEST_PHRASE, "test_password", &fast_params()).unwrap();
+        assert_eq!(result, expected);
+        assert_eq!(recorder.phases.lock().unwrap()[0], Phase::Validate);
+        assert!(recorder.phases.lock().unwrap().contains(&Phase::DeriveKey));
+    }
+
+    #[test]
+    fn test_transform_batch_async() {
+        let recorder = Arc::new(Recorder::default());
+        let phrases = vec![secret(TEST_PHRASE), secret("not a seed phrase")];
+
+        let results = runtime().block_on(transform_batch_async(
+            phrases,
+            secret("test_password"),
+            fast_params(),
+            recorder.clone(),
+        )).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(*recorder.progress.lock().unwrap(), vec![(1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_cancel_flag_stops_async_transform() {
+        let flag = CancelFlag::new();
+        flag.cancel();
+
+        let result = runtime().block_on(transform_seed_async(
+            secret(TEST_PHRASE),
+            secret("test_password"),
+            fast_params(),
+            Arc::new(flag),
+        ));
+        assert!(matches!(result, Err(SCypherError::Cancelled)));
+    }
+
+    /// Sink lento que avisa de cada frase terminada
+    struct Probe(std::sync::mpsc::Sender<usize>);
+
+    impl ProgressSink for Probe {
+        fn on_phase(&self, _phase: Phase) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        fn on_progress(&self, completed: usize, _total: usize) {
+            let _ = self.0.send(completed);
+        }
+    }
+
+    #[test]
+    fn test_dropped_future_cancels_task() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let phrases = vec![secret(TEST_PHRASE); 50];
+
+        let rt = runtime();
+        let handle = rt.spawn(transform_batch_async(phrases, secret("test_password"), fast_params(), Arc::new(Probe(sender))));
+        // Un poll arranca la tarea bloqueante; tras la primera frase se suelta el future
+        rt.block_on(tokio::task::yield_now());
+        assert_eq!(receiver.recv().unwrap(), 1);
+        handle.abort();
+        // Soltar el runtime espera a que la tarea bloqueante termine
+        drop(rt);
+
+        let completed = 1 + receiver.iter().count();
+        assert!(completed < 50, "task kept running after the future was dropped");
+    }
+
+    #[test]
+    fn test_no_progress_sink() {
+        let result = runtime().block_on(transform_seed_async(
+            secret(TEST_PHRASE),
+            secret("test_password"),
+            fast_params(),
+            Arc::new(NoProgress),
+        ));
+        assert!(result.is_ok());
+    }
+}
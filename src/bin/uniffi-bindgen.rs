@@ -0,0 +1,10 @@
+// src/bin/uniffi-bindgen.rs - Generador de bindings Kotlin/Swift
+//
+// Uso:
+//   cargo build --release --features mobile
+//   cargo run --features mobile-bindgen --bin uniffi-bindgen -- generate \
+//       --library target/release/libscypher_rust.so --language kotlin --out-dir bindings/kotlin
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -4,23 +4,22 @@
 //! y su representación binaria, incluyendo manejo de checksums.
 
 use crate::error::{SCypherError, Result};
-use crate::bip39::wordlist::{word_to_index, index_to_word};
+use crate::bip39::wordlist::{word_to_index, Wordlist, ENGLISH, WORDLIST_SIZE};
 use crate::crypto::checksum;
+use crate::crypto::SecureRng;
+use zeroize::Zeroizing;
 
 /// Convierte una seed phrase BIP39 a su representación en bits
 pub fn phrase_to_bits(phrase: &str) -> Result<Vec<bool>> {
-    let words: Vec<&str> = phrase.split_whitespace().collect();
-
-    if words.is_empty() {
-        return Err(SCypherError::InvalidSeedPhrase);
-    }
-
-    let mut bits = Vec::new();
+    phrase_to_bits_with(phrase, &ENGLISH)
+}
 
-    for word in words {
-        let index = word_to_index(word)
-            .ok_or_else(|| SCypherError::InvalidBip39Word(word.to_string()))?;
+/// Convierte una seed phrase a bits usando la lista de palabras indicada
+pub fn phrase_to_bits_with(phrase: &str, wordlist: &dyn Wordlist) -> Result<Vec<bool>> {
+    let indices = phrase_to_indices_with(phrase, wordlist)?;
+    let mut bits = Vec::with_capacity(indices.len() * 11);
 
+    for index in indices.iter() {
         // Cada palabra BIP39 se representa con 11 bits
         for i in (0..11).rev() {
             bits.push((index >> i) & 1 == 1);
@@ -30,9 +29,60 @@ pub fn phrase_to_bits(phrase: &str) -> Result<Vec<bool>> {
     Ok(bits)
 }
 
+/// Convierte una seed phrase a índices de la lista (11 bits cada uno)
+///
+/// Los índices se limpian de memoria al liberarse.
+pub fn phrase_to_indices_with(phrase: &str, wordlist: &dyn Wordlist) -> Result<Zeroizing<Vec<u16>>> {
+    // Capacidad exacta: una realocación dejaría copias de los índices sin limpiar
+    let mut indices = Zeroizing::new(Vec::with_capacity(phrase.split_whitespace().count()));
+
+    for (position, word) in phrase.split_whitespace().enumerate() {
+        let index = wordlist.index_of(word)
+            .ok_or_else(|| SCypherError::invalid_word_at(position + 1, word))?;
+        indices.push(index as u16);
+    }
+
+    if indices.is_empty() {
+        return Err(SCypherError::InvalidSeedPhrase);
+    }
+
+    Ok(indices)
+}
+
+/// Convierte índices de la lista a seed phrase
+#[tracing::instrument(name = "encode", level = "debug", skip_all, fields(words = indices.len(), language = wordlist.language_id()))]
+pub fn indices_to_phrase_with(indices: &[u16], wordlist: &dyn Wordlist) -> Result<String> {
+    let word = |index: u16| {
+        wordlist.word(usize::from(index))
+            .ok_or_else(|| SCypherError::crypto(format!("Invalid word index: {}", index)))
+    };
+
+    // Reservar la longitud exacta para que la frase no se copie al crecer
+    let mut length = indices.len().saturating_sub(1);
+    for &index in indices {
+        length += word(index)?.len();
+    }
+
+    let mut phrase = String::with_capacity(length);
+    for (position, &index) in indices.iter().enumerate() {
+        if position > 0 {
+            phrase.push(' ');
+        }
+        phrase.push_str(word(index)?);
+    }
+
+    Ok(phrase)
+}
+
 /// Convierte una representación de bits a seed phrase BIP39
 pub fn bits_to_phrase(bits: &[bool]) -> Result<String> {
-    if bits.len() % 11 != 0 {
+    bits_to_phrase_with(bits, &ENGLISH)
+}
+
+/// Convierte bits a seed phrase usando la lista de palabras indicada
+#[tracing::instrument(name = "encode", level = "debug", skip_all, fields(bits = bits.len(), language = wordlist.language_id()))]
+pub fn bits_to_phrase_with(bits: &[bool], wordlist: &dyn Wordlist) -> Result<String> {
+    if !bits.len().is_multiple_of(11) {
         return Err(SCypherError::crypto(
             format!("Bit length {} is not divisible by 11", bits.len())
         ));
@@ -50,13 +100,13 @@ pub fn bits_to_phrase(bits: &[bool]) -> Result<String> {
             }
         }
 
-        if index >= 2048 {
+        if index >= WORDLIST_SIZE {
             return Err(SCypherError::crypto(
                 format!("Word index {} is out of range (0-2047)", index)
             ));
         }
 
-        let word = index_to_word(index)
+        let word = wordlist.word(index)
             .ok_or_else(|| SCypherError::crypto(format!("Invalid word index: {}", index)))?;
 
         words.push(word);
@@ -67,6 +117,11 @@ pub fn bits_to_phrase(bits: &[bool]) -> Result<String> {
 
 /// Convierte entropía pura a seed phrase BIP39 válida (con checksum)
 pub fn entropy_to_phrase(entropy: &[u8]) -> Result<String> {
+    entropy_to_phrase_with(entropy, &ENGLISH)
+}
+
+/// Convierte entropía a seed phrase usando la lista de palabras indicada
+pub fn entropy_to_phrase_with(entropy: &[u8], wordlist: &dyn Wordlist) -> Result<String> {
     let entropy_bits = entropy.len() * 8;
 
     // Validar longitud de entropía
@@ -88,12 +143,17 @@ pub fn entropy_to_phrase(entropy: &[u8]) -> Result<String> {
     full_bits.extend(checksum_bits);
 
     // Convertir a frase
-    bits_to_phrase(&full_bits)
+    bits_to_phrase_with(&full_bits, wordlist)
 }
 
 /// Convierte seed phrase BIP39 a entropía pura (sin checksum)
 pub fn phrase_to_entropy(phrase: &str) -> Result<Vec<u8>> {
-    let bits = phrase_to_bits(phrase)?;
+    phrase_to_entropy_with(phrase, &ENGLISH)
+}
+
+/// Convierte una seed phrase de la lista de palabras indicada a entropía (sin checksum)
+pub fn phrase_to_entropy_with(phrase: &str, wordlist: &dyn Wordlist) -> Result<Vec<u8>> {
+    let bits = phrase_to_bits_with(phrase, wordlist)?;
     let word_count = phrase.split_whitespace().count();
 
     // Calcular longitudes
@@ -137,18 +197,48 @@ fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
 
 /// Genera una seed phrase BIP39 desde entropía aleatoria
 pub fn generate_seed_phrase(entropy_bits: usize) -> Result<String> {
-    checksum::validate_entropy_length(entropy_bits)?;
+    generate_seed_phrase_with_rng(entropy_bits, &mut rand::rngs::OsRng)
+}
 
-    let entropy_bytes = entropy_bits / 8;
-    let mut entropy = vec![0u8; entropy_bytes];
+/// `generate_seed_phrase` con la entropía de `rng`
+pub fn generate_seed_phrase_with_rng(entropy_bits: usize, rng: &mut dyn SecureRng) -> Result<String> {
+    checksum::validate_entropy_length(entropy_bits)?;
 
-    // Generar entropía aleatoria
-    use rand::RngCore;
-    rand::thread_rng().fill_bytes(&mut entropy);
+    let mut entropy = Zeroizing::new(vec![0u8; entropy_bits / 8]);
+    rng.fill_bytes(&mut entropy);
 
     entropy_to_phrase(&entropy)
 }
 
+/// Genera una seed phrase mezclando el RNG del sistema con entropía adicional
+///
+/// Extractor basado en hash: la entropía es SHA-256 de 32 bytes del RNG del
+/// sistema y de `extra` (p.ej. tiempos entre pulsaciones), truncado a su
+/// longitud. El resultado es al menos tan impredecible como la mejor de
+/// las dos fuentes, así que sirve de defensa si se desconfía del RNG.
+pub fn generate_seed_phrase_mixed(entropy_bits: usize, extra: &[u8]) -> Result<String> {
+    generate_seed_phrase_mixed_with_rng(entropy_bits, extra, &mut rand::rngs::OsRng)
+}
+
+/// `generate_seed_phrase_mixed` con `rng` en lugar del RNG del sistema
+pub fn generate_seed_phrase_mixed_with_rng(entropy_bits: usize, extra: &[u8], rng: &mut dyn SecureRng) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    checksum::validate_entropy_length(entropy_bits)?;
+
+    let mut system = Zeroizing::new([0u8; 32]);
+    rng.fill_bytes(&mut system[..]);
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"scypher/entropy-mix/v1");
+    hasher.update(&system[..]);
+    hasher.update((extra.len() as u64).to_be_bytes());
+    hasher.update(extra);
+    let mixed = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+
+    entropy_to_phrase(&mixed[..entropy_bits / 8])
+}
+
 /// Valida que una seed phrase tenga el checksum correcto y lo recalcula si es necesario
 pub fn validate_and_fix_checksum(phrase: &str) -> Result<String> {
     let words: Vec<&str> = phrase.split_whitespace().collect();
@@ -161,9 +251,9 @@ pub fn validate_and_fix_checksum(phrase: &str) -> Result<String> {
     }
 
     // Validar palabras
-    for word in &words {
+    for (position, word) in words.iter().enumerate() {
         if word_to_index(word).is_none() {
-            return Err(SCypherError::InvalidBip39Word(word.to_string()));
+            return Err(SCypherError::invalid_word_at(position + 1, *word));
         }
     }
 
@@ -175,12 +265,14 @@ pub fn validate_and_fix_checksum(phrase: &str) -> Result<String> {
 }
 
 /// Información sobre una seed phrase
-#[derive(Debug, Clone)]
+/// (la entropía es secreta y nunca se serializa)
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct SeedPhraseInfo {
     pub word_count: usize,
     pub entropy_bits: usize,
     pub checksum_bits: usize,
     pub total_bits: usize,
+    #[serde(skip)]
     pub entropy_bytes: Vec<u8>,
 }
 
@@ -236,7 +328,7 @@ mod tests {
 
         // "ability" es índice 1 (00000000001)
         let ability_bits = &bits[11..22];
-        assert_eq!(ability_bits[10], true);
+        assert!(ability_bits[10]);
         assert!(ability_bits[0..10].iter().all(|&bit| !bit));
     }
 
@@ -303,21 +395,18 @@ mod tests {
 
     #[test]
     fn test_invalid_word_index() {
-        // Crear bits que representan un índice > 2047
-        let mut invalid_bits = vec![true; 11]; // 0b11111111111 = 2047
-        invalid_bits[0] = true; // Hacer que sea > 2047
-
-        // Esto debería fallar en la implementación real
-        // Por ahora, el test puede pasar dependiendo de la implementación
+        // Con 11 bits el índice máximo posible es 0b11111111111 = 2047
+        let max_bits = [true; 11];
+        assert_eq!(bits_to_phrase(&max_bits).unwrap(), "zoo");
     }
 
     #[test]
     fn test_hex_conversion() {
-        let entropy = vec![0xDE, 0xAD, 0xBE, 0xEF];
+        let entropy = [0xDE, 0xAD, 0xBE, 0xEF].repeat(4); // 128 bits
         let phrase = entropy_to_phrase(&entropy).unwrap();
         let hex = phrase_to_hex(&phrase).unwrap();
 
-        assert_eq!(hex, "deadbeef");
+        assert_eq!(hex, "deadbeef".repeat(4));
 
         let phrase_from_hex = hex_to_phrase(&hex).unwrap();
         assert_eq!(phrase, phrase_from_hex);
@@ -352,6 +441,16 @@ mod tests {
         assert_ne!(phrase, phrase2); // Extremadamente improbable que sean iguales
     }
 
+    #[test]
+    fn test_generate_seed_phrase_mixed() {
+        let phrase = generate_seed_phrase_mixed(256, b"keystroke timings").unwrap();
+        assert_eq!(analyze_phrase(&phrase).unwrap().entropy_bits, 256);
+
+        // Con la misma entrada adicional el RNG del sistema sigue variando el resultado
+        assert_ne!(phrase, generate_seed_phrase_mixed(256, b"keystroke timings").unwrap());
+        assert!(generate_seed_phrase_mixed(100, b"").is_err());
+    }
+
     #[test]
     fn test_validate_and_fix_checksum() {
         // Crear una frase con entropía conocida
@@ -0,0 +1,384 @@
+//! Listas BIP39 oficiales en otros idiomas
+//!
+//! Los archivos de `wordlists/` son los publicados en el repositorio de
+//! BIP39. `build.rs` los convierte en arrays constantes (`wordlists.rs` en
+//! `OUT_DIR`), así que no se procesa nada en tiempo de ejecución: ni al
+//! arrancar ni en la primera validación. Las búsquedas palabra -> índice
+//! usan búsqueda binaria sobre una permutación precalculada, porque no
+//! todas las listas están en orden de bytes (chino, japonés, checo,
+//! francés, español).
+//!
+//! Las listas están en NFKD, como exige BIP39; las palabras no ASCII se
+//! normalizan antes de buscarlas para aceptar texto escrito en NFC.
+
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use super::wordlist::{Wordlist, ENGLISH, WORDLIST_SIZE};
+use crate::error::{SCypherError, Result};
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/wordlists.rs"));
+}
+
+/// Idioma de una lista BIP39 oficial
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Language {
+    English,
+    Spanish,
+    French,
+    Italian,
+    Portuguese,
+    Czech,
+    Japanese,
+    Korean,
+    ChineseSimplified,
+    ChineseTraditional,
+}
+
+impl Language {
+    /// Todos los idiomas disponibles, inglés primero
+    pub const ALL: [Language; 10] = [
+        Language::English,
+        Language::Spanish,
+        Language::French,
+        Language::Italian,
+        Language::Portuguese,
+        Language::Czech,
+        Language::Japanese,
+        Language::Korean,
+        Language::ChineseSimplified,
+        Language::ChineseTraditional,
+    ];
+
+    /// Código del idioma, el mismo que devuelve `Wordlist::language_id`
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Czech => "cs",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+            Language::ChineseSimplified => "zh-hans",
+            Language::ChineseTraditional => "zh-hant",
+        }
+    }
+
+    /// Nombre del idioma, para los mensajes
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Spanish",
+            Language::French => "French",
+            Language::Italian => "Italian",
+            Language::Portuguese => "Portuguese",
+            Language::Czech => "Czech",
+            Language::Japanese => "Japanese",
+            Language::Korean => "Korean",
+            Language::ChineseSimplified => "Chinese (Simplified)",
+            Language::ChineseTraditional => "Chinese (Traditional)",
+        }
+    }
+
+    /// Idioma a partir de su código
+    pub fn from_code(code: &str) -> Option<Language> {
+        Self::ALL.into_iter().find(|language| language.code() == code)
+    }
+
+    /// Lista de palabras del idioma
+    pub fn wordlist(self) -> &'static dyn Wordlist {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+            Language::French => &FRENCH,
+            Language::Italian => &ITALIAN,
+            Language::Portuguese => &PORTUGUESE,
+            Language::Czech => &CZECH,
+            Language::Japanese => &JAPANESE,
+            Language::Korean => &KOREAN,
+            Language::ChineseSimplified => &CHINESE_SIMPLIFIED,
+            Language::ChineseTraditional => &CHINESE_TRADITIONAL,
+        }
+    }
+}
+
+/// Idiomas cuya lista contiene todas las palabras de `phrase`
+///
+/// Algunas palabras existen en varias listas (p.ej. inglés y francés), así
+/// que puede haber más de un candidato; el checksum decide entre ellos.
+pub fn detect_languages(phrase: &str) -> Vec<Language> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    Language::ALL
+        .into_iter()
+        .filter(|language| {
+            let wordlist = language.wordlist();
+            words.iter().all(|word| wordlist.contains(word))
+        })
+        .collect()
+}
+
+/// Idioma de la lista que contiene más de la mitad de las palabras de `phrase`
+///
+/// Sirve para frases con alguna palabra mal escrita, que no están enteras
+/// en ninguna lista: una copia francesa con una errata sigue siendo
+/// francesa. En caso de empate gana el primero de `Language::ALL`.
+pub fn likely_language(phrase: &str) -> Option<Language> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    let mut best: Option<(Language, usize)> = None;
+    for language in Language::ALL {
+        let wordlist = language.wordlist();
+        let matched = words.iter().filter(|word| wordlist.contains(word)).count();
+        if best.is_none_or(|(_, most)| matched > most) {
+            best = Some((language, matched));
+        }
+    }
+    best.filter(|&(_, matched)| matched * 2 > words.len()).map(|(language, _)| language)
+}
+
+/// Idioma detectado para una frase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageDetection {
+    /// Lista con la que se codifica la frase
+    pub language: Language,
+    /// Posiciones (desde 1) de las palabras que también existen en otras listas
+    pub ambiguous_positions: Vec<usize>,
+}
+
+/// Elegir la lista de `phrase` sin que el usuario tenga que indicarla
+///
+/// Si varias listas contienen todas las palabras, decide el checksum; si
+/// sigue habiendo empate y todas dan los mismos índices, da igual cuál se
+/// use. Si ninguna tiene checksum válido se devuelve la primera, para que la
+/// validación posterior informe del error.
+pub fn detect_language(phrase: &str) -> Result<LanguageDetection> {
+    let candidates = detect_languages(phrase);
+    let Some(&first) = candidates.first() else {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        // Alguna errata: la corrección sale de la lista de la mayoría de las palabras
+        if let Some(language) = likely_language(phrase) {
+            let wordlist = language.wordlist();
+            if let Some(position) = words.iter().position(|word| !wordlist.contains(word)) {
+                return Err(super::validation::unknown_word(words[position], position, wordlist));
+            }
+        }
+        let unknown = words
+            .iter()
+            .position(|word| Language::ALL.iter().all(|language| !language.wordlist().contains(word)));
+        return Err(match unknown {
+            Some(position) => SCypherError::invalid_word_at(position + 1, words[position]),
+            None => SCypherError::invalid_word(""),
+        });
+    };
+
+    let valid: Vec<Language> = candidates
+        .iter()
+        .copied()
+        .filter(|language| super::validation::validate_checksum_with(phrase, language.wordlist()).is_ok())
+        .collect();
+
+    let language = match valid.as_slice() {
+        [] => first,
+        [language] => *language,
+        [language, others @ ..] => {
+            let indices = |language: &Language| -> Vec<Option<usize>> {
+                phrase.split_whitespace().map(|word| language.wordlist().index_of(word)).collect()
+            };
+            if others.iter().any(|other| indices(other) != indices(language)) {
+                let codes: Vec<&str> = valid.iter().map(|language| language.code()).collect();
+                return Err(SCypherError::InvalidRequest(format!(
+                    "phrase is valid in several wordlists ({}); specify the language",
+                    codes.join(", "),
+                )));
+            }
+            *language
+        }
+    };
+
+    let ambiguous_positions = phrase
+        .split_whitespace()
+        .enumerate()
+        .filter(|(_, word)| Language::ALL.iter().filter(|language| language.wordlist().contains(word)).count() > 1)
+        .map(|(position, _)| position + 1)
+        .collect();
+
+    Ok(LanguageDetection { language, ambiguous_positions })
+}
+
+/// Lista incrustada en el binario como arrays constantes
+pub struct EmbeddedWordlist {
+    language: Language,
+    words: &'static [&'static str; WORDLIST_SIZE],
+    /// Índices de `words` en orden de bytes de la palabra
+    sorted: &'static [u16; WORDLIST_SIZE],
+}
+
+impl EmbeddedWordlist {
+    const fn new(
+        language: Language,
+        words: &'static [&'static str; WORDLIST_SIZE],
+        sorted: &'static [u16; WORDLIST_SIZE],
+    ) -> Self {
+        Self { language, words, sorted }
+    }
+
+    /// Idioma de la lista
+    pub fn language(&self) -> Language {
+        self.language
+    }
+}
+
+impl std::fmt::Debug for EmbeddedWordlist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddedWordlist")
+            .field("language", &self.language)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Wordlist for EmbeddedWordlist {
+    fn language_id(&self) -> &str {
+        self.language.code()
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        self.words.get(index).copied()
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        // NFKD puede triplicar los bytes de estas palabras (hangul, kana con
+        // dakuten); reservar de antemano evita copias sin limpiar al crecer
+        let normalized;
+        let word = if word.is_ascii() {
+            word
+        } else {
+            let mut buffer = Zeroizing::new(String::with_capacity(word.len() * 3));
+            buffer.extend(word.nfkd());
+            normalized = buffer;
+            normalized.as_str()
+        };
+        self.sorted
+            .binary_search_by(|&index| self.words[usize::from(index)].cmp(word))
+            .ok()
+            .map(|position| usize::from(self.sorted[position]))
+    }
+}
+
+pub static SPANISH: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::Spanish, &generated::SPANISH_WORDS, &generated::SPANISH_SORTED);
+pub static FRENCH: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::French, &generated::FRENCH_WORDS, &generated::FRENCH_SORTED);
+pub static ITALIAN: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::Italian, &generated::ITALIAN_WORDS, &generated::ITALIAN_SORTED);
+pub static PORTUGUESE: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::Portuguese, &generated::PORTUGUESE_WORDS, &generated::PORTUGUESE_SORTED);
+pub static CZECH: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::Czech, &generated::CZECH_WORDS, &generated::CZECH_SORTED);
+pub static JAPANESE: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::Japanese, &generated::JAPANESE_WORDS, &generated::JAPANESE_SORTED);
+pub static KOREAN: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::Korean, &generated::KOREAN_WORDS, &generated::KOREAN_SORTED);
+pub static CHINESE_SIMPLIFIED: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::ChineseSimplified, &generated::CHINESE_SIMPLIFIED_WORDS, &generated::CHINESE_SIMPLIFIED_SORTED);
+pub static CHINESE_TRADITIONAL: EmbeddedWordlist =
+    EmbeddedWordlist::new(Language::ChineseTraditional, &generated::CHINESE_TRADITIONAL_WORDS, &generated::CHINESE_TRADITIONAL_SORTED);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    /// SHA-256 de los archivos oficiales (README de BIP39)
+    const OFFICIAL_HASHES: [(Language, &str); 10] = [
+        (Language::English, "2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda"),
+        (Language::Spanish, "46846a5a0139d1e3cb77293e521c2865f7bcdb82c44e8d0a06a2cd0ecba48c0b"),
+        (Language::French, "ebc3959ab7801a1df6bac4fa7d970652f1df76b683cd2f4003c941c63d517e59"),
+        (Language::Italian, "d392c49fdb700a24cd1fceb237c1f65dcc128f6b34a8aacb58b59384b5c648c2"),
+        (Language::Portuguese, "2685e9c194c82ae67e10ba59d9ea5345a23dc093e92276fc5361f6667d79cd3f"),
+        (Language::Czech, "7e80e161c3e93d9554c2efb78d4e3cebf8fc727e9c52e03b83b94406bdcc95fc"),
+        (Language::Japanese, "2eed0aef492291e061633d7ad8117f1a2b03eb80a29d0e4e3117ac2528d05ffd"),
+        (Language::Korean, "9e95f86c167de88f450f0aaf89e87f6624a57f973c67b516e338e8e8b8897f60"),
+        (Language::ChineseSimplified, "5c5942792bd8340cb8b27cd592f1015edf56a8c5b26276ee18a482428e7c5726"),
+        (Language::ChineseTraditional, "417b26b3d8500a4ae3d59717d7011952db6fc2fb84b807f3f94ac734e89c1b5f"),
+    ];
+
+    #[test]
+    fn test_wordlists_match_official_files() {
+        for (language, expected) in OFFICIAL_HASHES {
+            let wordlist = language.wordlist();
+            let mut hasher = Sha256::new();
+            for index in 0..WORDLIST_SIZE {
+                let word = wordlist.word(index).unwrap();
+                hasher.update(word.as_bytes());
+                hasher.update(b"\n");
+
+                assert_eq!(wordlist.index_of(word), Some(index), "{:?} '{}'", language, word);
+            }
+
+            assert_eq!(wordlist.word(WORDLIST_SIZE), None);
+            assert_eq!(hex::encode(hasher.finalize()), expected, "{:?}", language);
+            assert_eq!(Language::from_code(wordlist.language_id()), Some(language));
+        }
+    }
+
+    #[test]
+    fn test_detect_languages() {
+        // Entrada en NFC; la lista española está en NFKD
+        assert_eq!(detect_languages("\u{e1}baco abdomen abeja"), vec![Language::Spanish]);
+        assert_eq!(SPANISH.index_of("a\u{301}baco"), Some(0));
+        assert_eq!(detect_languages("zoo zone zero"), vec![Language::English]);
+        assert_eq!(detect_languages("的 一 是"), vec![Language::ChineseSimplified, Language::ChineseTraditional]);
+        assert!(detect_languages("abandon notaword").is_empty());
+        assert!(detect_languages("").is_empty());
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let english = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let detection = detect_language(english).unwrap();
+        // "abandon" también es francesa; el checksum decide
+        assert_eq!(detection.language, Language::English);
+        assert_eq!(detection.ambiguous_positions, (1..=11).collect::<Vec<_>>());
+
+        // Misma entropía en la lista española, escrita en NFC
+        let spanish = "\u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco abierto";
+        assert_eq!(detect_language(spanish).unwrap().language, Language::Spanish);
+
+        // "animal" está en las listas inglesa y francesa
+        let detection = detect_language("animal zoo zero").unwrap();
+        assert_eq!(detection.language, Language::English);
+        assert_eq!(detection.ambiguous_positions, vec![1]);
+
+        assert!(matches!(detect_language("abandon notaword"), Err(SCypherError::InvalidBip39Word { word, position: Some(2), .. }) if word == "notaword"));
+    }
+
+    #[test]
+    fn test_likely_language() {
+        // Entropía cero en la lista francesa, con una errata en la palabra 11
+        let french = "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaiser abeille";
+        assert_eq!(likely_language(french), Some(Language::French));
+        assert_eq!(likely_language("abandon notaword"), None);
+        assert_eq!(likely_language(""), None);
+
+        // La corrección sale de la lista francesa, no de la inglesa
+        assert!(matches!(
+            detect_language(french),
+            Err(SCypherError::InvalidBip39Word { position: Some(11), suggestion: Some(suggestion), .. }) if suggestion == "abaisser"
+        ));
+
+        // Frase francesa validada como inglesa (--language en)
+        let french = french.replace("abaiser", "abaisser");
+        assert!(matches!(
+            crate::bip39::validate_seed_phrase_with(&french, &ENGLISH),
+            Err(SCypherError::WrongWordlist { wordlist, detected }) if wordlist == "en" && detected == "fr"
+        ));
+        assert!(crate::bip39::validate_seed_phrase_with(&french, &FRENCH).is_ok());
+    }
+}
@@ -3,13 +3,16 @@
 pub mod wordlist;
 pub mod validation;
 pub mod conversion;
+pub mod languages;
 
 use crate::error::Result;
 
 // Re-exportar funciones principales para fácil acceso
 pub use wordlist::{BIP39_WORDLIST, word_to_index, index_to_word, is_valid_word};
-pub use validation::{validate_seed_phrase, validate_word_count, validate_words, analyze_seed_phrase, is_valid_seed_phrase};
-pub use conversion::{phrase_to_bits, bits_to_phrase, entropy_to_phrase, phrase_to_entropy, phrase_to_hex, hex_to_phrase};
+pub use wordlist::{Wordlist, WordlistChecksum, English, CustomWordlist, ENGLISH};
+pub use languages::{Language, LanguageDetection, EmbeddedWordlist, detect_language, detect_languages, likely_language};
+pub use validation::{validate_seed_phrase, validate_seed_phrase_with, validate_word_count, validate_words, analyze_seed_phrase, is_valid_seed_phrase};
+pub use conversion::{phrase_to_bits, phrase_to_bits_with, bits_to_phrase, bits_to_phrase_with, phrase_to_indices_with, indices_to_phrase_with, entropy_to_phrase, entropy_to_phrase_with, phrase_to_entropy, phrase_to_entropy_with, phrase_to_hex, hex_to_phrase};
 
 /// Validar formato de seed phrase BIP39 (función principal)
 pub fn validate_seed_phrase_complete(seed_phrase: &str) -> Result<()> {
@@ -18,7 +21,12 @@ pub fn validate_seed_phrase_complete(seed_phrase: &str) -> Result<()> {
 
 /// Verificar checksum BIP39 (implementación actualizada)
 pub fn verify_checksum(seed_phrase: &str) -> Result<bool> {
-    match validation::validate_checksum(seed_phrase) {
+    verify_checksum_with(seed_phrase, &ENGLISH)
+}
+
+/// Verificar checksum con cualquier lista de palabras
+pub fn verify_checksum_with(seed_phrase: &str, wordlist: &dyn Wordlist) -> Result<bool> {
+    match validation::validate_checksum_with(seed_phrase, wordlist) {
         Ok(()) => Ok(true),
         Err(crate::error::SCypherError::InvalidChecksum) => Ok(false),
         Err(e) => Err(e),
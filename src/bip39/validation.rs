@@ -5,13 +5,15 @@
 //! longitud y checksums.
 
 use crate::error::{SCypherError, Result};
-use crate::bip39::wordlist::{is_valid_word, find_closest_word};
+use crate::bip39::languages::likely_language;
+use crate::bip39::wordlist::{is_valid_word, find_closest_word, find_closest_word_in, Wordlist, WordlistChecksum, ENGLISH};
 use crate::crypto::checksum;
 
 /// Longitudes válidas de seed phrases BIP39 (en palabras)
 const VALID_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
 
 /// Valida una seed phrase completa BIP39
+#[tracing::instrument(name = "validate", level = "debug", skip_all, fields(word_count = phrase.split_whitespace().count()))]
 pub fn validate_seed_phrase(phrase: &str) -> Result<()> {
     let words = phrase.split_whitespace().collect::<Vec<&str>>();
 
@@ -27,6 +29,35 @@ pub fn validate_seed_phrase(phrase: &str) -> Result<()> {
     Ok(())
 }
 
+/// Valida una seed phrase completa usando la lista de palabras indicada
+///
+/// Si la mayoría de las palabras son de otra lista oficial (una copia en
+/// francés validada como inglesa) falla con `WrongWordlist`; si no, la
+/// palabra rechazada lleva la corrección más parecida de `wordlist`.
+#[tracing::instrument(
+    name = "validate",
+    level = "debug",
+    skip_all,
+    fields(word_count = phrase.split_whitespace().count(), language = wordlist.language_id())
+)]
+pub fn validate_seed_phrase_with(phrase: &str, wordlist: &dyn Wordlist) -> Result<()> {
+    let words = phrase.split_whitespace().collect::<Vec<&str>>();
+
+    validate_word_count(words.len())?;
+
+    if let Some(position) = words.iter().position(|word| !wordlist.contains(word)) {
+        if let Some(language) = likely_language(phrase).filter(|language| language.code() != wordlist.language_id()) {
+            return Err(SCypherError::WrongWordlist {
+                wordlist: wordlist.language_id().to_string(),
+                detected: language.code().to_string(),
+            });
+        }
+        return Err(unknown_word(words[position], position, wordlist));
+    }
+
+    validate_checksum_with(phrase, wordlist)
+}
+
 /// Valida que el número de palabras sea correcto para BIP39
 pub fn validate_word_count(count: usize) -> Result<()> {
     if VALID_WORD_COUNTS.contains(&count) {
@@ -37,42 +68,34 @@ pub fn validate_word_count(count: usize) -> Result<()> {
 }
 
 /// Valida que todas las palabras estén en la lista BIP39
+///
+/// El error indica la primera palabra inválida, su posición y, si hay una
+/// palabra de la lista a distancia 2 o menos, la corrección sugerida.
 pub fn validate_words(words: &[&str]) -> Result<()> {
-    let mut invalid_words = Vec::new();
-    let mut suggestions = Vec::new();
-
-    for &word in words {
-        if !is_valid_word(word) {
-            invalid_words.push(word);
-
-            // Intentar encontrar sugerencia para palabra inválida
-            if let Some((closest, distance)) = find_closest_word(word) {
-                if distance <= 2 { // Solo sugerir si la distancia es pequeña
-                    suggestions.push(format!("'{}' -> '{}'", word, closest));
-                }
-            }
-        }
-    }
+    let Some(position) = words.iter().position(|word| !is_valid_word(word)) else {
+        return Ok(());
+    };
 
-    if !invalid_words.is_empty() {
-        let mut error_msg = format!(
-            "Invalid BIP39 words found: {}",
-            invalid_words.join(", ")
-        );
+    Err(unknown_word(words[position], position, &ENGLISH))
+}
 
-        if !suggestions.is_empty() {
-            error_msg.push_str("\nSuggested corrections: ");
-            error_msg.push_str(&suggestions.join(", "));
-        }
+/// Error de la palabra `word` (posición `position` desde 0) que no está en
+/// `wordlist`, con la palabra de la lista a distancia 2 o menos, si la hay
+pub(crate) fn unknown_word(word: &str, position: usize, wordlist: &dyn Wordlist) -> SCypherError {
+    let suggestion = find_closest_word_in(word, wordlist)
+        .filter(|&(_, distance)| distance <= 2) // Solo sugerir si la distancia es pequeña
+        .map(|(closest, _)| closest.to_string());
 
-        return Err(SCypherError::InvalidBip39Word(error_msg));
-    }
-
-    Ok(())
+    SCypherError::InvalidBip39Word { word: word.to_string(), position: Some(position + 1), suggestion }
 }
 
 /// Valida el checksum BIP39 de una seed phrase
 pub fn validate_checksum(phrase: &str) -> Result<()> {
+    validate_checksum_with(phrase, &ENGLISH)
+}
+
+/// Valida el checksum de una frase según el esquema de su lista de palabras
+pub fn validate_checksum_with(phrase: &str, wordlist: &dyn Wordlist) -> Result<()> {
     let words: Vec<&str> = phrase.split_whitespace().collect();
     let word_count = words.len();
 
@@ -82,27 +105,31 @@ pub fn validate_checksum(phrase: &str) -> Result<()> {
     let total_bits = entropy_bits + checksum_bits;
 
     // Convertir palabras a bits
-    let seed_bits = words_to_bits(&words)?;
+    let seed_bits = words_to_bits(&words, wordlist)?;
 
     if seed_bits.len() != total_bits {
         return Err(SCypherError::InvalidChecksum);
     }
 
     // Verificar checksum
-    if checksum::verify_bip39_checksum(&seed_bits, entropy_bits)? {
+    let valid = match wordlist.checksum() {
+        WordlistChecksum::Bip39 => checksum::verify_bip39_checksum(&seed_bits, entropy_bits)?,
+    };
+
+    if valid {
         Ok(())
     } else {
         Err(SCypherError::InvalidChecksum)
     }
 }
 
-/// Convierte palabras BIP39 a representación de bits
-fn words_to_bits(words: &[&str]) -> Result<Vec<bool>> {
+/// Convierte palabras a representación de bits
+fn words_to_bits(words: &[&str], wordlist: &dyn Wordlist) -> Result<Vec<bool>> {
     let mut bits = Vec::new();
 
-    for &word in words {
-        let index = crate::bip39::wordlist::word_to_index(word)
-            .ok_or_else(|| SCypherError::InvalidBip39Word(word.to_string()))?;
+    for (position, &word) in words.iter().enumerate() {
+        let index = wordlist.index_of(word)
+            .ok_or_else(|| SCypherError::invalid_word_at(position + 1, word))?;
 
         // Convertir índice a 11 bits (cada palabra BIP39 es 11 bits)
         for i in (0..11).rev() {
@@ -155,7 +182,7 @@ pub fn analyze_seed_phrase(phrase: &str) -> SeedPhraseAnalysis {
 }
 
 /// Estructura que contiene análisis detallado de una seed phrase
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct SeedPhraseAnalysis {
     pub word_count: usize,
     pub is_valid_length: bool,
@@ -171,7 +198,7 @@ impl SeedPhraseAnalysis {
     pub fn generate_report(&self) -> String {
         let mut report = String::new();
 
-        report.push_str(&format!("Seed Phrase Analysis:\n"));
+        report.push_str("Seed Phrase Analysis:\n");
         report.push_str(&format!("  Word count: {} ", self.word_count));
 
         if self.is_valid_length {
@@ -254,7 +281,7 @@ mod tests {
     #[test]
     fn test_words_to_bits() {
         let words = vec!["abandon", "ability"];
-        let bits = words_to_bits(&words).unwrap();
+        let bits = words_to_bits(&words, &ENGLISH).unwrap();
 
         // Cada palabra son 11 bits
         assert_eq!(bits.len(), 22);
@@ -265,7 +292,7 @@ mod tests {
 
         // "ability" es índice 1, así que debería ser 00000000001
         let ability_bits = &bits[11..22];
-        assert_eq!(ability_bits[10], true); // Último bit en 1
+        assert!(ability_bits[10]); // Último bit en 1
         assert!(ability_bits[0..10].iter().all(|&bit| !bit)); // Resto en 0
     }
 
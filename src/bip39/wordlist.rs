@@ -1,13 +1,133 @@
 //! Lista completa de palabras BIP39
 //!
 //! Contiene las 2048 palabras oficiales del estándar BIP39
-//! para la validación y conversión de seed phrases.
+//! para la validación y conversión de seed phrases, y el trait `Wordlist`
+//! que permite usar otras listas con la misma ruta de codificación.
 
 use std::collections::HashMap;
-use std::sync::OnceLock;
+
+use crate::error::{SCypherError, Result};
+
+/// Número de palabras de una lista compatible con BIP39 (11 bits por palabra)
+pub const WORDLIST_SIZE: usize = 2048;
+
+/// Esquema de checksum que usa una lista de palabras
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WordlistChecksum {
+    /// Checksum BIP39: primeros ENT/32 bits de SHA-256 de la entropía
+    Bip39,
+}
+
+/// Lista de palabras utilizable por la codificación y validación de frases
+///
+/// Las funciones `*_with` de `conversion` y `validation` aceptan cualquier
+/// implementación; las funciones sin sufijo usan `ENGLISH`.
+pub trait Wordlist: Send + Sync {
+    /// Identificador del idioma o formato (p.ej. "en")
+    fn language_id(&self) -> &str;
+
+    /// Palabra en la posición `index`
+    fn word(&self, index: usize) -> Option<&str>;
+
+    /// Posición de una palabra en la lista
+    fn index_of(&self, word: &str) -> Option<usize>;
+
+    /// Número de palabras de la lista
+    fn size(&self) -> usize {
+        WORDLIST_SIZE
+    }
+
+    /// Esquema de checksum de las frases codificadas con esta lista
+    fn checksum(&self) -> WordlistChecksum {
+        WordlistChecksum::Bip39
+    }
+
+    /// Verifica si una palabra pertenece a la lista
+    fn contains(&self, word: &str) -> bool {
+        self.index_of(word).is_some()
+    }
+}
+
+/// Lista BIP39 oficial en inglés
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+/// Instancia de la lista inglesa, usada por las funciones sin sufijo `_with`
+pub static ENGLISH: English = English;
+
+impl Wordlist for English {
+    fn language_id(&self) -> &str {
+        "en"
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        index_to_word(index)
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        word_to_index(word)
+    }
+}
+
+/// Lista de palabras personalizada cargada en tiempo de ejecución
+#[derive(Debug, Clone)]
+pub struct CustomWordlist {
+    language_id: String,
+    words: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+impl CustomWordlist {
+    /// Crear una lista personalizada
+    ///
+    /// Debe contener exactamente 2048 palabras únicas y no vacías, sin espacios.
+    pub fn new<S: Into<String>>(language_id: S, words: Vec<String>) -> Result<Self> {
+        if words.len() != WORDLIST_SIZE {
+            return Err(SCypherError::crypto(format!(
+                "Wordlist must contain {} words, found {}",
+                WORDLIST_SIZE, words.len()
+            )));
+        }
+
+        let mut index = HashMap::with_capacity(words.len());
+        for (position, word) in words.iter().enumerate() {
+            if word.is_empty() || word.chars().any(char::is_whitespace) {
+                return Err(SCypherError::crypto(format!(
+                    "Invalid wordlist entry at position {}", position
+                )));
+            }
+            if index.insert(word.clone(), position).is_some() {
+                return Err(SCypherError::crypto(format!(
+                    "Duplicate wordlist entry '{}'", word
+                )));
+            }
+        }
+
+        Ok(Self {
+            language_id: language_id.into(),
+            words,
+            index,
+        })
+    }
+}
+
+impl Wordlist for CustomWordlist {
+    fn language_id(&self) -> &str {
+        &self.language_id
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        self.words.get(index).map(String::as_str)
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        self.index.get(word).copied()
+    }
+}
 
 /// Lista completa de 2048 palabras BIP39 (ordenadas alfabéticamente)
-pub const BIP39_WORDLIST: [&str; 2048] = [
+pub static BIP39_WORDLIST: [&str; 2048] = [
     "abandon", "ability", "able", "about", "above", "absent", "absorb", "abstract", "absurd", "abuse",
     "access", "accident", "account", "accuse", "achieve", "acid", "acoustic", "acquire", "across", "act",
     "action", "actor", "actress", "actual", "adapt", "add", "addict", "address", "adjust", "admit",
@@ -215,20 +335,12 @@ pub const BIP39_WORDLIST: [&str; 2048] = [
     "yellow", "you", "young", "youth", "zebra", "zero", "zone", "zoo"
 ];
 
-/// HashMap estático para búsquedas O(1) de palabra -> índice
-static WORD_TO_INDEX_MAP: OnceLock<HashMap<&'static str, usize>> = OnceLock::new();
-
 /// Obtiene el índice de una palabra en la lista BIP39
+///
+/// `BIP39_WORDLIST` está ordenada, así que basta una búsqueda binaria sobre
+/// la tabla estática, sin inicialización en tiempo de ejecución.
 pub fn word_to_index(word: &str) -> Option<usize> {
-    let map = WORD_TO_INDEX_MAP.get_or_init(|| {
-        BIP39_WORDLIST
-            .iter()
-            .enumerate()
-            .map(|(index, &word)| (word, index))
-            .collect()
-    });
-
-    map.get(word).copied()
+    BIP39_WORDLIST.binary_search(&word).ok()
 }
 
 /// Obtiene la palabra BIP39 para un índice dado
@@ -253,10 +365,24 @@ pub fn words_with_prefix(prefix: &str) -> Vec<&'static str> {
 /// Encuentra la palabra más cercana usando distancia de Levenshtein
 /// Útil para sugerir correcciones
 pub fn find_closest_word(input: &str) -> Option<(&'static str, usize)> {
+    find_closest_word_in(input, &ENGLISH)
+}
+
+/// Palabra de `wordlist` más cercana a `input` y su distancia
+///
+/// Las listas no inglesas están en NFKD: la entrada se normaliza igual para
+/// que un acento escrito en NFC no cuente como error.
+pub fn find_closest_word_in<'a>(input: &str, wordlist: &'a dyn Wordlist) -> Option<(&'a str, usize)> {
+    use unicode_normalization::UnicodeNormalization;
+
+    // Como en `index_of`: reservado de antemano y limpiado al soltarse
+    let mut normalized = zeroize::Zeroizing::new(String::with_capacity(input.len() * 3));
+    normalized.extend(input.nfkd());
+    let input = normalized.as_str();
     let mut best_word = None;
     let mut best_distance = usize::MAX;
 
-    for &word in &BIP39_WORDLIST {
+    for word in (0..wordlist.size()).filter_map(|index| wordlist.word(index)) {
         let distance = levenshtein_distance(input, word);
         if distance < best_distance {
             best_distance = distance;
@@ -269,21 +395,22 @@ pub fn find_closest_word(input: &str) -> Option<(&'static str, usize)> {
 
 /// Calcula la distancia de Levenshtein entre dos strings
 fn levenshtein_distance(s1: &str, s2: &str) -> usize {
-    let len1 = s1.len();
-    let len2 = s2.len();
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+
+    // Longitudes en caracteres (no en bytes) para soportar entrada no ASCII
+    let len1 = s1_chars.len();
+    let len2 = s2_chars.len();
     let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
 
     // Inicializar primera fila y columna
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
-    let s1_chars: Vec<char> = s1.chars().collect();
-    let s2_chars: Vec<char> = s2.chars().collect();
-
     // Llenar la matriz
     for i in 1..=len1 {
         for j in 1..=len2 {
@@ -306,6 +433,23 @@ mod tests {
         assert_eq!(BIP39_WORDLIST.len(), 2048);
     }
 
+    #[test]
+    fn test_wordlist_trait() {
+        assert_eq!(ENGLISH.language_id(), "en");
+        assert_eq!(ENGLISH.index_of("zoo"), Some(2047));
+        assert_eq!(ENGLISH.word(0), Some("abandon"));
+        assert_eq!(ENGLISH.size(), 2048);
+
+        let words: Vec<String> = (0..2048).map(|i| format!("w{}", i)).collect();
+        let custom = CustomWordlist::new("test", words).unwrap();
+        assert_eq!(custom.index_of("w42"), Some(42));
+        assert!(!custom.contains("abandon"));
+
+        assert!(CustomWordlist::new("short", vec!["a".to_string()]).is_err());
+        let duplicated = vec!["same".to_string(); 2048];
+        assert!(CustomWordlist::new("dup", duplicated).is_err());
+    }
+
     #[test]
     fn test_word_to_index() {
         assert_eq!(word_to_index("abandon"), Some(0));
@@ -355,6 +499,7 @@ mod tests {
         assert_eq!(levenshtein_distance("abandon", "abandon"), 0);
         assert_eq!(levenshtein_distance("abandon", "abandun"), 1);
         assert_eq!(levenshtein_distance("cat", "dog"), 3);
+        assert_eq!(levenshtein_distance("«", "abc"), 3); // Multibyte
     }
 
     #[test]
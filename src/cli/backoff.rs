@@ -0,0 +1,156 @@
+// src/cli/backoff.rs - Espera creciente tras descifrados fallidos seguidos
+//
+// Solo cuentan los fallos que se pueden detectar: una frase de `--pad-24`
+// que no se autentica o una contraseña que no da el código de
+// `--key-check`. Un descifrado normal siempre da una frase con checksum
+// válido (se recalcula), así que no dice nada de la contraseña y no toca
+// el contador. Tras `FREE_FAILURES` fallos seguidos, cada nuevo intento
+// espera el doble que el anterior (hasta `MAX_DELAY`) antes de pedir la
+// contraseña, y se avisa de cuántos fallos lleva el equipo. La espera se
+// aplica siempre, también a scripts y tuberías (probar contraseñas en bucle
+// es justo lo que frena); sin terminal solo se omite la cuenta atrás. Un
+// descifrado autenticado pone el contador a cero; `--reset-failures`
+// también.
+//
+// Es un freno local contra quien prueba contraseñas en la máquina: el
+// contador vive en el directorio de configuración
+// (`failed-decrypts`) y quien controla el equipo puede borrarlo. En modo
+// solo RAM no se escribe.
+
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::{IoOperation, SCypherError, Result};
+
+/// Fallos seguidos sin espera
+const FREE_FAILURES: u32 = 3;
+
+/// Espera tras el primer fallo que ya la tiene
+const BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Espera máxima entre intentos
+const MAX_DELAY: Duration = Duration::from_secs(300);
+
+/// Archivo del contador dentro del directorio de configuración
+const COUNTER_FILE: &str = "failed-decrypts";
+
+/// Espera antes del siguiente intento con `failures` fallos seguidos
+fn delay_for(failures: u32) -> Duration {
+    if failures < FREE_FAILURES {
+        return Duration::ZERO;
+    }
+    let doublings = (failures - FREE_FAILURES).min(31);
+    BASE_DELAY.saturating_mul(1 << doublings).min(MAX_DELAY)
+}
+
+fn counter_path(config: &Path) -> PathBuf {
+    config.join(COUNTER_FILE)
+}
+
+/// Fallos seguidos registrados en `config` (0 si no hay contador o no se lee)
+fn load_from(config: &Path) -> u32 {
+    fs::read_to_string(counter_path(config))
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Guardar `failures` en `config`; con 0 se borra el contador
+fn store_in(config: &Path, failures: u32) -> Result<()> {
+    let path = counter_path(config);
+    if failures == 0 {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(SCypherError::io_at(IoOperation::Write, path.display().to_string(), e)),
+            _ => Ok(()),
+        };
+    }
+    fs::create_dir_all(config).map_err(|e| SCypherError::io_at(IoOperation::Write, config.display().to_string(), e))?;
+    fs::write(&path, format!("{}\n", failures)).map_err(|e| SCypherError::io_at(IoOperation::Write, path.display().to_string(), e))
+}
+
+/// Avisar de los fallos anteriores y esperar lo que toque antes de descifrar
+pub fn before_decrypt() -> Result<()> {
+    let Ok(config) = crate::cli::preset::config_dir() else {
+        return Ok(());
+    };
+    let failures = load_from(&config);
+    if failures == 0 {
+        return Ok(());
+    }
+
+    crate::cli::events::warning(&format!(
+        "{} failed decryption(s) in a row on this device (--reset-failures clears the count)", failures));
+    let delay = delay_for(failures);
+    if delay.is_zero() {
+        return Ok(());
+    }
+    // Sin terminal (scripts, tuberías) se espera igual, sin cuenta atrás
+    if !io::stdin().is_terminal() {
+        println!("Waiting {} s before the next attempt...", delay.as_secs());
+        std::thread::sleep(delay);
+        return Ok(());
+    }
+    for remaining in (1..=delay.as_secs()).rev() {
+        print!("\rWaiting {} s before the next attempt... ", remaining);
+        io::stdout().flush().map_err(SCypherError::from)?;
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    println!();
+    Ok(())
+}
+
+/// Registrar el resultado de un descifrado autenticado: un fallo suma, un acierto pone a cero
+pub fn record(succeeded: bool) -> Result<()> {
+    if crate::security::persistence::is_ram_only() {
+        return Ok(());
+    }
+    let Ok(config) = crate::cli::preset::config_dir() else {
+        return Ok(());
+    };
+    let failures = load_from(&config);
+    if succeeded {
+        return if failures > 0 { store_in(&config, 0) } else { Ok(()) };
+    }
+
+    let failures = failures.saturating_add(1);
+    store_in(&config, failures)?;
+    let delay = delay_for(failures);
+    if !delay.is_zero() {
+        println!("Failed decryption #{} in a row: the next attempt waits {} s", failures, delay.as_secs());
+    }
+    Ok(())
+}
+
+/// Poner el contador a cero (`--reset-failures`)
+pub fn reset() -> Result<()> {
+    let config = crate::cli::preset::config_dir()?;
+    let failures = load_from(&config);
+    store_in(&config, 0)?;
+    println!("Failed-decryption counter cleared ({} recorded)", failures);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_and_counter() {
+        assert_eq!(delay_for(0), Duration::ZERO);
+        assert_eq!(delay_for(FREE_FAILURES - 1), Duration::ZERO);
+        assert_eq!(delay_for(FREE_FAILURES), BASE_DELAY);
+        assert_eq!(delay_for(FREE_FAILURES + 2), BASE_DELAY * 4);
+        assert_eq!(delay_for(u32::MAX), MAX_DELAY);
+
+        let config = std::env::temp_dir().join(format!("scypher_backoff_{}", std::process::id()));
+        assert_eq!(load_from(&config), 0);
+        store_in(&config, 4).unwrap();
+        assert_eq!(load_from(&config), 4);
+        store_in(&config, 0).unwrap();
+        assert!(!counter_path(&config).exists());
+        store_in(&config, 0).unwrap();
+        fs::remove_dir(&config).unwrap();
+    }
+}
@@ -0,0 +1,476 @@
+// src/cli/batch.rs - Modo lote: muchas frases con una sola contraseña
+//
+// Formato de entrada: una frase por línea, opcionalmente precedida de una
+// etiqueta (`etiqueta: palabra palabra ...`). Las líneas vacías y las que
+// empiezan por `#` se ignoran; sin etiqueta se usa `item-<n>`. Cada
+// resultado se guarda en `<directorio>/<etiqueta>.txt` con permisos 0600 y
+// nunca se sobrescribe un archivo existente.
+//
+// Con `derive_once` Argon2id se ejecuta una sola vez y cada elemento usa
+// un keystream expandido desde su etiqueta (`crypto::MasterKey`): para
+// revertirlo hace falta la misma etiqueta, no solo la misma contraseña.
+//
+// Con `--manifest` se escribe además un inventario JSON del lote: por cada
+// elemento su etiqueta, el identificador `fp1` de la frase original (con
+// un salt común a todo el manifiesto), la ruta del resultado y el estado.
+// No lleva frases ni mensajes de error, que pueden citar palabras: de un
+// fallo solo se guarda su código numérico.
+//
+// Con `--report` se escribe un CSV con una fila por elemento para los
+// procesos de auditoría: validación, estado, tiempo y error. Del error de
+// una palabra desconocida solo se anota la posición.
+//
+// Con `--resume` se guarda en el directorio de salida un punto de control
+// cifrado (`cli::checkpoint`) tras cada ronda de derivaciones: si el lote se
+// interrumpe, la siguiente ejecución con `--resume` salta lo ya terminado.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::cli::checkpoint::{checkpoint_path, Checkpoint};
+use crate::cli::output::save_to_new_file;
+use crate::crypto::{self, fingerprint, BatchOptions, ProgressSink, ScypherParams};
+use crate::error::{ErrorCategory, IoOperation, SCypherError, Result};
+
+/// Versión del formato del manifiesto
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Elemento del lote
+pub struct BatchItem {
+    pub label: String,
+    pub phrase: Zeroizing<String>,
+}
+
+/// Resultado de un lote
+#[derive(Debug, Default)]
+pub struct BatchSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Código de salida del primer elemento fallido
+    pub exit_code: i32,
+    /// Tiempo de las derivaciones y transformaciones
+    pub duration: Duration,
+    /// Derivaciones Argon2 realizadas
+    pub derivations: usize,
+    /// Derivaciones simultáneas como máximo
+    pub concurrency: usize,
+    /// Elementos ya terminados en una ejecución anterior (`--resume`)
+    pub resumed: usize,
+}
+
+/// Modo de un lote
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchMode {
+    /// Una sola derivación y claves expandidas por etiqueta
+    pub derive_once: bool,
+    /// Anotar lo terminado en un punto de control y retomar el que haya
+    pub resume: bool,
+}
+
+/// Dónde escribir el manifiesto y con qué salt calcular los identificadores
+#[derive(Debug, Clone, Copy)]
+pub struct ManifestOptions<'a> {
+    pub path: &'a str,
+    /// Sin salt se genera uno aleatorio, que queda anotado en el manifiesto
+    pub salt: Option<&'a str>,
+}
+
+/// Inventario de un lote
+#[derive(Debug, Serialize)]
+pub struct BatchManifest {
+    pub version: u32,
+    /// Segundos desde la época Unix
+    pub created: u64,
+    /// Salt de los identificadores (`verify-backup --salt`)
+    pub fingerprint_salt: String,
+    pub derive_once: bool,
+    /// Parámetros comunes (sin los hashes de los keyfiles)
+    pub params: ScypherParams,
+    /// Keyfiles usados
+    pub keyfiles: usize,
+    pub items: Vec<ManifestItem>,
+}
+
+/// Elemento del manifiesto
+#[derive(Debug, Serialize)]
+pub struct ManifestItem {
+    pub label: String,
+    /// `fp1...` de la frase original; ausente si no es una frase válida
+    pub fingerprint: Option<String>,
+    pub output: String,
+    /// `ok` o `failed`
+    pub status: &'static str,
+    /// Código del error (`SCypherError::code`) si falló
+    pub error_code: Option<u16>,
+}
+
+/// Cabecera del informe CSV
+const REPORT_HEADER: &str = "label,validation,status,duration_ms,error_code,error";
+
+/// Salt aleatorio para un manifiesto (16 caracteres hex)
+fn random_salt() -> String {
+    let mut bytes = [0u8; 8];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Etiquetas válidas: letras, dígitos, `.`, `_` y `-`, sin empezar por `.`
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 64
+        && !label.starts_with('.')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+/// Interpretar el contenido de un archivo de lote
+pub fn parse_batch_input(content: &str) -> Result<Vec<BatchItem>> {
+    let mut items: Vec<BatchItem> = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (label, phrase) = match line.split_once(':') {
+            Some((label, phrase)) => (label.trim().to_string(), phrase),
+            None => (format!("item-{}", items.len() + 1), line),
+        };
+
+        if !is_valid_label(&label) {
+            return Err(SCypherError::InvalidRequest(format!("line {}: invalid label '{}'", line_number + 1, label)));
+        }
+        if items.iter().any(|item| item.label == label) {
+            return Err(SCypherError::InvalidRequest(format!("line {}: duplicate label '{}'", line_number + 1, label)));
+        }
+
+        let phrase = Zeroizing::new(phrase.split_whitespace().collect::<Vec<_>>().join(" "));
+        items.push(BatchItem { label, phrase });
+    }
+
+    Ok(items)
+}
+
+/// Contador de progreso en stderr
+struct ConsoleProgress;
+
+impl ProgressSink for ConsoleProgress {
+    fn on_progress(&self, completed: usize, total: usize) {
+        // En un terminal simple cada avance va en su propia línea
+        if crate::cli::display::is_plain_terminal() {
+            eprintln!("Processed {}/{}", completed, total);
+        } else {
+            eprint!("\rProcessed {}/{}", completed, total);
+            if completed == total {
+                eprintln!();
+            }
+        }
+        let _ = std::io::stderr().flush();
+        crate::cli::events::emit(&crate::cli::events::Event::progress(completed, total));
+    }
+}
+
+/// Contador de progreso de una tanda dentro del lote entero
+struct ChunkProgress {
+    offset: usize,
+    total: usize,
+}
+
+impl ProgressSink for ChunkProgress {
+    fn on_progress(&self, completed: usize, _total: usize) {
+        ConsoleProgress.on_progress(self.offset + completed, self.total);
+    }
+}
+
+/// Transformar todos los elementos de `input` y guardar los resultados en `output_dir`
+pub fn run_batch(
+    input: &str,
+    output_dir: &str,
+    params: &ScypherParams,
+    options: &BatchOptions,
+    mode: BatchMode,
+    manifest: Option<ManifestOptions>,
+    report: Option<&str>,
+) -> Result<BatchSummary> {
+    let derive_once = mode.derive_once;
+    let content = Zeroizing::new(std::fs::read_to_string(input)
+        .map_err(|e| SCypherError::io_at(IoOperation::Read, input, e))?);
+    let items = parse_batch_input(&content)?;
+    if items.is_empty() {
+        return Err(SCypherError::InvalidRequest(format!("no phrases found in '{}'", input)));
+    }
+
+    if let Some(manifest) = manifest {
+        if Path::new(manifest.path).exists() {
+            return Err(SCypherError::file_at(manifest.path, "Manifest file already exists"));
+        }
+    }
+    if let Some(report) = report {
+        if Path::new(report).exists() {
+            return Err(SCypherError::file_at(report, "Report file already exists"));
+        }
+    }
+    let checkpoint_file = checkpoint_path(output_dir);
+    if !mode.resume && checkpoint_file.exists() {
+        return Err(SCypherError::file_at(
+            checkpoint_file.display().to_string(),
+            "An interrupted batch left a checkpoint here; add --resume to continue it, or delete the file to start over",
+        ));
+    }
+
+    if !crate::cli::output::confirm_destination(output_dir)? {
+        return Err(SCypherError::Cancelled);
+    }
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| SCypherError::io_at(IoOperation::CreateDir, output_dir, e))?;
+
+    let concurrency = if derive_once || !cfg!(feature = "parallel") { 1 } else { options.concurrency(params)? };
+    if derive_once {
+        println!("Batch: {} phrases, one derivation ({} KB), keys expanded per label", items.len(), params.memory_cost());
+    } else {
+        println!(
+            "Batch: {} phrases, up to {} in parallel ({} KB per derivation)",
+            items.len(),
+            concurrency,
+            params.memory_cost()
+        );
+    }
+
+    let password = crate::cli::read_password_secure()?;
+    let started = Instant::now();
+
+    // Con `--resume` sin `--derive-once` la clave del punto de control cuesta una derivación más
+    let master = if derive_once || mode.resume { Some(crypto::MasterKey::derive(&password, params)?) } else { None };
+    let mut checkpoint = match &master {
+        Some(master) if mode.resume => Some(Checkpoint::open(output_dir, master, derive_once)?),
+        _ => None,
+    };
+
+    let pending: Vec<usize> = (0..items.len())
+        .filter(|&index| !checkpoint.as_ref().is_some_and(|checkpoint| checkpoint.is_done(&items[index].label, &items[index].phrase)))
+        .collect();
+    let resumed = items.len() - pending.len();
+    if resumed > 0 {
+        println!("Resuming: {} of {} phrases already done", resumed, items.len());
+    }
+
+    // Sin punto de control todo va en una tanda; con él, una por ronda de
+    // derivaciones simultáneas, y tras cada una se anota lo terminado
+    let chunk_size = if checkpoint.is_some() { concurrency } else { pending.len().max(1) };
+    let mut outcomes: Vec<Option<(Result<()>, Duration)>> = (0..items.len()).map(|_| None).collect();
+    for (chunk_index, chunk) in pending.chunks(chunk_size).enumerate() {
+        let progress = ChunkProgress { offset: chunk_index * chunk_size, total: pending.len() };
+        let results = match (&master, derive_once) {
+            (Some(master), true) => Ok(chunk
+                .iter()
+                .enumerate()
+                .map(|(position, &index)| {
+                    let item_started = Instant::now();
+                    let result = master.transform(&items[index].label, &items[index].phrase);
+                    progress.on_progress(position + 1, chunk.len());
+                    (result, item_started.elapsed())
+                })
+                .collect()),
+            _ => {
+                let phrases: Vec<&str> = chunk.iter().map(|&index| items[index].phrase.as_str()).collect();
+                crypto::transform_batch_parallel_timed(&phrases, &password, params, options, &progress)
+            }
+        };
+        let results: Vec<(Result<Zeroizing<String>>, Duration)> = match results {
+            Ok(results) => results,
+            Err(e) => {
+                if checkpoint.is_some() {
+                    let done = outcomes.iter().filter(|outcome| matches!(outcome, Some((Ok(()), _)))).count();
+                    eprintln!("\nCheckpoint saved: {} of {} phrases done; run the same command with --resume to continue", resumed + done, items.len());
+                }
+                return Err(e);
+            }
+        };
+
+        for (&index, (result, duration)) in chunk.iter().zip(results) {
+            let item = &items[index];
+            let path = output_path(output_dir, &item.label);
+            let saved = result.and_then(|phrase| save_result(&path, &phrase, checkpoint.is_some()));
+            if let (Ok(()), Some(checkpoint)) = (&saved, checkpoint.as_mut()) {
+                checkpoint.mark_done(&item.label, &item.phrase);
+            }
+            outcomes[index] = Some((saved, duration));
+        }
+        if let Some(checkpoint) = &checkpoint {
+            checkpoint.save()?;
+        }
+    }
+
+    let mut summary = BatchSummary {
+        duration: started.elapsed(),
+        derivations: usize::from(master.is_some()) + if derive_once { 0 } else { pending.len() },
+        concurrency,
+        resumed,
+        ..BatchSummary::default()
+    };
+    let salt = manifest.map(|manifest| manifest.salt.map_or_else(random_salt, str::to_string));
+    let mut entries = Vec::new();
+    let mut rows = vec![REPORT_HEADER.to_string()];
+    for (item, outcome) in items.iter().zip(outcomes) {
+        let path = output_path(output_dir, &item.label);
+
+        let error_code = match outcome {
+            None => {
+                summary.succeeded += 1;
+                println!("  ✓ {} -> {} (resumed)", item.label, path.display());
+                rows.push(format!("{},valid,resumed,,,", csv_field(&item.label)));
+                None
+            }
+            Some((Ok(()), duration)) => {
+                summary.succeeded += 1;
+                println!("  ✓ {} -> {}", item.label, path.display());
+                rows.push(report_row(&item.label, duration, None));
+                None
+            }
+            Some((Err(e), duration)) => {
+                if summary.failed == 0 {
+                    summary.exit_code = e.exit_code();
+                }
+                summary.failed += 1;
+                println!("  ✗ {}: {}", item.label, e);
+                rows.push(report_row(&item.label, duration, Some(&e)));
+                Some(e.code())
+            }
+        };
+        if let Some(salt) = &salt {
+            entries.push(ManifestItem {
+                label: item.label.clone(),
+                fingerprint: fingerprint::phrase_id(&item.phrase, &crate::bip39::ENGLISH, salt).ok(),
+                output: path.display().to_string(),
+                status: if error_code.is_none() { "ok" } else { "failed" },
+                error_code,
+            });
+        }
+    }
+
+    if resumed > 0 {
+        println!("\n{} succeeded ({} resumed), {} failed", summary.succeeded, resumed, summary.failed);
+    } else {
+        println!("\n{} succeeded, {} failed", summary.succeeded, summary.failed);
+    }
+    if let Some(checkpoint) = &checkpoint {
+        if summary.failed == 0 {
+            checkpoint.remove()?;
+        } else {
+            println!("Checkpoint kept: after fixing the failed phrases, --resume retries only those");
+        }
+    }
+
+    if let (Some(manifest), Some(fingerprint_salt)) = (manifest, salt) {
+        let document = BatchManifest {
+            version: MANIFEST_VERSION,
+            created: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()),
+            fingerprint_salt,
+            derive_once,
+            params: params.clone(),
+            keyfiles: params.keyfile_digests().len(),
+            items: entries,
+        };
+        let json = serde_json::to_string_pretty(&document)
+            .map_err(|e| SCypherError::crypto(format!("Cannot serialize manifest: {}", e)))?;
+        save_new_file(Path::new(manifest.path), &json)?;
+        println!("Manifest saved to: {}", manifest.path);
+    }
+    if let Some(report) = report {
+        rows.push(String::new());
+        save_new_file(Path::new(report), &rows.join("\n"))?;
+        println!("Report saved to: {}", report);
+    }
+    Ok(summary)
+}
+
+/// Fila del informe CSV de un elemento
+fn report_row(label: &str, duration: Duration, error: Option<&SCypherError>) -> String {
+    let validation = match error {
+        Some(e) if e.category() == ErrorCategory::Validation => "invalid",
+        _ => "valid",
+    };
+    format!(
+        "{},{},{},{:.3},{},{}",
+        csv_field(label),
+        validation,
+        if error.is_some() { "failed" } else { "ok" },
+        duration.as_secs_f64() * 1000.0,
+        error.map(|e| e.code().to_string()).unwrap_or_default(),
+        error.map(|e| csv_field(&report_message(e))).unwrap_or_default(),
+    )
+}
+
+/// Mensaje de un error sin texto de la frase: de una palabra desconocida, solo su posición
+fn report_message(error: &SCypherError) -> String {
+    match error {
+        SCypherError::InvalidBip39Word { position: Some(position), .. } => format!("Word #{} is not in the BIP39 wordlist", position),
+        SCypherError::InvalidBip39Word { position: None, .. } => "A word is not in the BIP39 wordlist".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Campo CSV (RFC 4180): entre comillas si lleva comas, comillas o saltos de línea
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn output_path(output_dir: &str, label: &str) -> PathBuf {
+    Path::new(output_dir).join(format!("{}.txt", label))
+}
+
+/// Guardar sin sobrescribir resultados anteriores
+fn save_new_file(path: &Path, content: &str) -> Result<()> {
+    save_to_new_file(content, &path.display().to_string())
+}
+
+/// Guardar un resultado; al retomar, uno idéntico escrito justo antes de la
+/// interrupción (sin llegar a anotarse) cuenta como guardado
+fn save_result(path: &Path, phrase: &str, resuming: bool) -> Result<()> {
+    if resuming && std::fs::read_to_string(path).map(Zeroizing::new).is_ok_and(|existing| existing.as_str() == phrase) {
+        return Ok(());
+    }
+    save_new_file(path, phrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch_input() {
+        let content = "# backups\n\nvault-a: abandon  ability\nzoo zoo\n";
+        let items = parse_batch_input(content).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].label, "vault-a");
+        assert_eq!(items[0].phrase.as_str(), "abandon ability");
+        assert_eq!(items[1].label, "item-2");
+
+        assert!(parse_batch_input("a: zoo\na: zoo").is_err());
+        assert!(parse_batch_input("../etc: zoo").is_err());
+    }
+
+    #[test]
+    fn test_report_row() {
+        let duration = Duration::from_micros(1500);
+        assert_eq!(report_row("vault-a", duration, None), "vault-a,valid,ok,1.500,,");
+
+        // La palabra desconocida no llega al informe
+        let row = report_row("vault-b", duration, Some(&SCypherError::invalid_word_at(3, "zzzz")));
+        assert_eq!(row, "vault-b,invalid,failed,1.500,2003,Word #3 is not in the BIP39 wordlist");
+
+        let row = report_row("vault-c", duration, Some(&SCypherError::file_at("out/vault-c.txt", "Output file already exists")));
+        assert!(row.starts_with("vault-c,valid,failed,1.500,4002,"));
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+}
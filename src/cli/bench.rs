@@ -0,0 +1,134 @@
+// src/cli/bench.rs - Medición de rendimiento (subcomando oculto `bench`)
+//
+// Mide frases por segundo de las operaciones que se repiten en cada
+// elemento de un lote: validación, codificación/decodificación de cada
+// formato registrado y la transformación con el keystream ya derivado
+// (post-KDF). Argon2id queda fuera a propósito: su coste lo fijan los
+// parámetros, no el código, y taparía cualquier regresión del resto.
+// El informe JSON sirve para comparar ejecuciones entre versiones.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::bip39;
+use crate::crypto::{simd, SessionKey, TransformBuilder};
+use crate::error::Result;
+use crate::format;
+
+/// Contraseña fija de las mediciones; el resultado no se muestra
+const BENCH_PASSWORD: &str = "scypher-bench";
+
+/// Resultado de una operación
+#[derive(Debug, Serialize)]
+pub struct BenchResult {
+    pub name: String,
+    pub operations: u64,
+    pub seconds: f64,
+    pub phrases_per_second: f64,
+}
+
+/// Informe completo de `scypher-rust bench`
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub version: &'static str,
+    pub xor_backend: &'static str,
+    pub duration_ms: u64,
+    pub results: Vec<BenchResult>,
+}
+
+/// Frases de prueba deterministas: una de cada longitud válida
+pub fn sample_phrases() -> Result<Vec<String>> {
+    [16usize, 20, 24, 28, 32]
+        .iter()
+        .map(|&bytes| {
+            let entropy: Vec<u8> = (0..bytes).map(|i| (i * 31 + bytes) as u8).collect();
+            bip39::entropy_to_phrase(&entropy)
+        })
+        .collect()
+}
+
+/// Ejecutar todas las mediciones, cada una durante `duration`
+pub fn run_bench(duration: Duration) -> Result<BenchReport> {
+    let phrases = sample_phrases()?;
+    let mut results = Vec::new();
+
+    results.push(measure("validate", duration, &phrases, |phrase| {
+        bip39::validate_seed_phrase(phrase)
+    })?);
+
+    for output_format in format::registered_formats() {
+        let encoded = phrases
+            .iter()
+            .map(|phrase| output_format.encode(phrase))
+            .collect::<Result<Vec<String>>>()?;
+
+        let name = format!("format/{}/encode", output_format.name());
+        results.push(measure(&name, duration, &phrases, |phrase| output_format.encode(phrase).map(drop))?);
+
+        let name = format!("format/{}/decode", output_format.name());
+        results.push(measure(&name, duration, &encoded, |encoded| output_format.decode(encoded).map(drop))?);
+    }
+
+    // Una derivación por longitud antes de medir; después todo sale de la caché
+    let params = TransformBuilder::new().iterations(1).memory_cost(8192).build()?;
+    let mut session = SessionKey::unlock(BENCH_PASSWORD, params);
+    for phrase in &phrases {
+        session.transform(phrase)?;
+    }
+    results.push(measure("transform_post_kdf", duration, &phrases, |phrase| {
+        session.transform(phrase).map(drop)
+    })?);
+
+    Ok(BenchReport {
+        version: env!("CARGO_PKG_VERSION"),
+        xor_backend: simd::active_backend(),
+        duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        results,
+    })
+}
+
+/// Repetir `operation` sobre `inputs` en ciclo hasta agotar `duration`
+fn measure<F>(name: &str, duration: Duration, inputs: &[String], mut operation: F) -> Result<BenchResult>
+where
+    F: FnMut(&str) -> Result<()>,
+{
+    let start = Instant::now();
+    let mut operations = 0u64;
+
+    while start.elapsed() < duration {
+        for input in inputs {
+            operation(input)?;
+        }
+        operations += inputs.len() as u64;
+    }
+
+    let seconds = start.elapsed().as_secs_f64();
+    Ok(BenchResult {
+        name: name.to_string(),
+        operations,
+        seconds,
+        phrases_per_second: operations as f64 / seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_bench_report() {
+        let report = run_bench(Duration::from_millis(1)).unwrap();
+        let names: Vec<&str> = report.results.iter().map(|result| result.name.as_str()).collect();
+
+        assert!(names.contains(&"validate"));
+        assert!(names.contains(&"format/words/encode"));
+        #[cfg(feature = "formats-extra")]
+        assert!(names.contains(&"format/indices/decode"));
+        assert!(names.contains(&"transform_post_kdf"));
+        assert!(report.results.iter().all(|result| result.operations > 0));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["results"][0]["phrases_per_second"].is_number());
+    }
+}
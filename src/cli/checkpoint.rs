@@ -0,0 +1,242 @@
+// src/cli/checkpoint.rs - Punto de control cifrado de un lote (`batch --resume`)
+//
+// Un lote con parámetros altos puede tardar horas. Con `--resume` se guarda
+// en el directorio de salida qué elementos ya terminaron, y una ejecución
+// interrumpida (Ctrl-C, corte de luz) continúa sin repetir sus derivaciones.
+// De cada elemento terminado se anota una huella con clave (HMAC de la
+// etiqueta y la frase): si la frase de una etiqueta cambia en la entrada,
+// deja de coincidir y el elemento se vuelve a procesar.
+//
+// Las claves salen de la `MasterKey` del lote (misma contraseña y
+// parámetros): con otros no se puede leer, y una modificación se detecta.
+// Formato: cabecera, nonce aleatorio de 16 bytes, cuerpo JSON cifrado con
+// HMAC-SHA256 en modo contador y HMAC-SHA256 de todo lo anterior. Se
+// reescribe entero (archivo temporal y rename) tras cada tanda.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::cli::output::save_bytes_to_file;
+use crate::crypto::MasterKey;
+use crate::error::{Defect, IoOperation, SCypherError, Result};
+
+/// Nombre del archivo dentro del directorio de salida (las etiquetas no empiezan por `.`)
+pub const CHECKPOINT_FILE: &str = ".scypher-checkpoint";
+
+/// Cabecera del formato
+const MAGIC: &[u8] = b"SCYPHER-CHECKPOINT-1\n";
+
+const NONCE_LEN: usize = 16;
+const TAG_LEN: usize = 32;
+
+/// Etiquetas de `MasterKey` de las claves (llevan '/', que no admiten las etiquetas de batch)
+const ENCRYPTION_LABEL: &str = "batch/checkpoint/encrypt";
+const MAC_LABEL: &str = "batch/checkpoint/mac";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Contenido descifrado
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Body {
+    derive_once: bool,
+    /// Huellas hex de los elementos terminados
+    completed: BTreeSet<String>,
+}
+
+/// Elementos terminados de un lote y las claves para guardarlos
+pub struct Checkpoint {
+    path: PathBuf,
+    encryption_key: Zeroizing<[u8; 32]>,
+    mac_key: Zeroizing<[u8; 32]>,
+    derive_once: bool,
+    completed: BTreeSet<String>,
+}
+
+fn mismatch() -> SCypherError {
+    SCypherError::malformed(
+        "checkpoint",
+        Defect::Checksum,
+        "cannot be verified: the password or parameters differ from the interrupted run, or the file was modified",
+    )
+}
+
+/// Ruta del punto de control de `output_dir`
+pub fn checkpoint_path(output_dir: &str) -> PathBuf {
+    Path::new(output_dir).join(CHECKPOINT_FILE)
+}
+
+impl Checkpoint {
+    /// Leer el punto de control de `output_dir`, o empezar uno vacío si no hay
+    pub fn open(output_dir: &str, master: &MasterKey, derive_once: bool) -> Result<Self> {
+        let mut encryption_key = Zeroizing::new([0u8; 32]);
+        let mut mac_key = Zeroizing::new([0u8; 32]);
+        master.expand(ENCRYPTION_LABEL, &mut encryption_key[..])?;
+        master.expand(MAC_LABEL, &mut mac_key[..])?;
+
+        let mut checkpoint = Self { path: checkpoint_path(output_dir), encryption_key, mac_key, derive_once, completed: BTreeSet::new() };
+        if !checkpoint.path.exists() {
+            return Ok(checkpoint);
+        }
+
+        let name = checkpoint.path.display().to_string();
+        let data = std::fs::read(&checkpoint.path).map_err(|e| SCypherError::io_at(IoOperation::Read, &name, e))?;
+        let body = checkpoint.decrypt(&data)?;
+        if body.derive_once != derive_once {
+            return Err(SCypherError::InvalidRequest(format!(
+                "the interrupted run {} --derive-once; use the same mode to resume it",
+                if body.derive_once { "used" } else { "did not use" },
+            )));
+        }
+        checkpoint.completed = body.completed;
+        Ok(checkpoint)
+    }
+
+    /// Elementos terminados anotados
+    pub fn len(&self) -> usize {
+        self.completed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_empty()
+    }
+
+    /// Si el elemento `label` con esta `phrase` ya terminó
+    pub fn is_done(&self, label: &str, phrase: &str) -> bool {
+        self.completed.contains(&self.fingerprint(label, phrase))
+    }
+
+    /// Anotar un elemento terminado (se guarda con `save`)
+    pub fn mark_done(&mut self, label: &str, phrase: &str) {
+        let fingerprint = self.fingerprint(label, phrase);
+        self.completed.insert(fingerprint);
+    }
+
+    /// Reescribir el archivo con los elementos anotados
+    pub fn save(&self) -> Result<()> {
+        let body = Body { derive_once: self.derive_once, completed: self.completed.clone() };
+        let mut plaintext = Zeroizing::new(serde_json::to_vec(&body)
+            .map_err(|e| SCypherError::crypto(format!("Cannot serialize checkpoint: {}", e)))?);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        self.apply_keystream(&nonce, &mut plaintext);
+
+        let mut data = Vec::with_capacity(MAGIC.len() + NONCE_LEN + plaintext.len() + TAG_LEN);
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&plaintext);
+        let tag = self.tag(&data);
+        data.extend_from_slice(&tag);
+
+        // Temporal y rename: un corte a mitad no deja un punto de control roto
+        let temporary = self.path.with_extension("tmp");
+        let temporary_name = temporary.display().to_string();
+        save_bytes_to_file(&data, &temporary_name)?;
+        std::fs::rename(&temporary, &self.path)
+            .map_err(|e| SCypherError::io_at(IoOperation::Write, self.path.display().to_string(), e))
+    }
+
+    /// Borrar el archivo (el lote terminó sin fallos)
+    pub fn remove(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(SCypherError::io_at(IoOperation::Write, self.path.display().to_string(), e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Body> {
+        if !data.starts_with(MAGIC) {
+            return Err(SCypherError::malformed("checkpoint", Defect::Unsupported, "not a SCypher batch checkpoint"));
+        }
+        if data.len() < MAGIC.len() + NONCE_LEN + TAG_LEN {
+            return Err(SCypherError::malformed("checkpoint", Defect::Truncated, "the file is incomplete"));
+        }
+
+        let (signed, tag) = data.split_at(data.len() - TAG_LEN);
+        let mut mac = HmacSha256::new_from_slice(&self.mac_key[..]).expect("HMAC accepts any key length");
+        mac.update(signed);
+        mac.verify_slice(tag).map_err(|_| mismatch())?;
+
+        let (nonce, ciphertext) = signed[MAGIC.len()..].split_at(NONCE_LEN);
+        let mut plaintext = Zeroizing::new(ciphertext.to_vec());
+        self.apply_keystream(nonce, &mut plaintext);
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| SCypherError::malformed("checkpoint", Defect::Encoding, e.to_string()))
+    }
+
+    /// XOR con HMAC(clave, nonce || contador), bloque a bloque
+    fn apply_keystream(&self, nonce: &[u8], data: &mut [u8]) {
+        for (counter, chunk) in data.chunks_mut(32).enumerate() {
+            let mut mac = HmacSha256::new_from_slice(&self.encryption_key[..]).expect("HMAC accepts any key length");
+            mac.update(nonce);
+            mac.update(&(counter as u64).to_be_bytes());
+            let block: Zeroizing<[u8; 32]> = Zeroizing::new(mac.finalize().into_bytes().into());
+            chunk.iter_mut().zip(block.iter()).for_each(|(byte, key)| *byte ^= key);
+        }
+    }
+
+    fn tag(&self, data: &[u8]) -> [u8; TAG_LEN] {
+        let mut mac = HmacSha256::new_from_slice(&self.mac_key[..]).expect("HMAC accepts any key length");
+        mac.update(data);
+        mac.finalize().into_bytes().into()
+    }
+
+    /// HMAC de la etiqueta y la frase (16 bytes en hex)
+    fn fingerprint(&self, label: &str, phrase: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(&self.mac_key[..]).expect("HMAC accepts any key length");
+        mac.update(b"item\0");
+        mac.update(label.as_bytes());
+        mac.update(b"\0");
+        mac.update(phrase.as_bytes());
+        hex::encode(&mac.finalize().into_bytes()[..16])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::TransformBuilder;
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output_dir = dir.path().to_str().unwrap();
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let master = MasterKey::derive("test_password", &params).unwrap();
+
+        let mut checkpoint = Checkpoint::open(output_dir, &master, false).unwrap();
+        assert!(checkpoint.is_empty());
+        checkpoint.mark_done("vault-a", "abandon about");
+        checkpoint.save().unwrap();
+
+        // Cifrado: ni etiquetas ni frases a la vista
+        let data = std::fs::read(checkpoint_path(output_dir)).unwrap();
+        assert!(!String::from_utf8_lossy(&data).contains("vault-a"));
+
+        let checkpoint = Checkpoint::open(output_dir, &master, false).unwrap();
+        assert_eq!(checkpoint.len(), 1);
+        assert!(checkpoint.is_done("vault-a", "abandon about"));
+        assert!(!checkpoint.is_done("vault-a", "abandon ability"));
+
+        // Otra contraseña, otro modo o un bit cambiado se rechazan
+        let other = MasterKey::derive("other_password", &params).unwrap();
+        assert!(matches!(Checkpoint::open(output_dir, &other, false), Err(SCypherError::MalformedContainer { .. })));
+        assert!(matches!(Checkpoint::open(output_dir, &master, true), Err(SCypherError::InvalidRequest(_))));
+        let mut tampered = data.clone();
+        tampered[MAGIC.len() + NONCE_LEN] ^= 1;
+        std::fs::write(checkpoint_path(output_dir), tampered).unwrap();
+        assert!(Checkpoint::open(output_dir, &master, false).is_err());
+
+        checkpoint.remove().unwrap();
+        assert!(!checkpoint_path(output_dir).exists());
+    }
+}
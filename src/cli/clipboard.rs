@@ -0,0 +1,90 @@
+// src/cli/clipboard.rs - Resultado en el portapapeles (--clipboard)
+//
+// El portapapeles lo leen otras aplicaciones, los gestores de historial y
+// los escritorios remotos, así que el resultado solo se deja ahí un tiempo:
+// pasado `CLEAR_AFTER` (o antes, con Enter) se borra si sigue siendo lo que
+// se copió. Si el proceso termina antes, en X11 y Wayland el contenido se
+// pierde con él.
+
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Result};
+
+/// Tiempo que el resultado permanece en el portapapeles
+pub const CLEAR_AFTER: Duration = Duration::from_secs(30);
+
+fn clipboard_error(message: &str, source: arboard::Error) -> SCypherError {
+    SCypherError::FileError {
+        path: None,
+        message: message.to_string(),
+        source: Some(Box::new(source)),
+    }
+}
+
+/// Texto copiado al portapapeles; se borra al soltarlo
+#[must_use = "dropping the guard clears the clipboard right away"]
+pub struct ClipboardGuard {
+    clipboard: arboard::Clipboard,
+    text: Zeroizing<String>,
+    deadline: Instant,
+}
+
+impl ClipboardGuard {
+    /// Copiar `text` al portapapeles
+    pub fn copy(text: &str) -> Result<Self> {
+        let mut clipboard = arboard::Clipboard::new()
+            .map_err(|e| clipboard_error("Cannot open the clipboard", e))?;
+        clipboard
+            .set_text(text.to_string())
+            .map_err(|e| clipboard_error("Cannot copy the result to the clipboard", e))?;
+
+        Ok(Self {
+            clipboard,
+            text: Zeroizing::new(text.to_string()),
+            deadline: Instant::now() + CLEAR_AFTER,
+        })
+    }
+
+    /// Esperar hasta el plazo (o hasta que se pulse Enter) y borrar
+    pub fn wait_and_clear(self) {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return;
+        }
+
+        println!("\nThe clipboard will be cleared in {} s. Press Enter to clear it now.", remaining.as_secs().max(1));
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                // Sin terminal (stdin cerrado) se espera el plazo entero
+                let mut line = String::new();
+                if io::stdin().read_line(&mut line).is_ok_and(|read| read > 0) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            });
+        }
+
+        while !stop.load(Ordering::Relaxed) && Instant::now() < self.deadline {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+impl Drop for ClipboardGuard {
+    fn drop(&mut self) {
+        // Solo se borra si nadie ha copiado otra cosa entretanto
+        let current = self.clipboard.get_text().ok().map(Zeroizing::new);
+        if current.as_deref().is_some_and(|current| **current == **self.text) {
+            match self.clipboard.clear() {
+                Ok(()) => println!("✓ Clipboard cleared"),
+                Err(e) => eprintln!("Warning: could not clear the clipboard: {}", e),
+            }
+        }
+    }
+}
@@ -1,24 +1,63 @@
 // src/cli/display.rs - Pantallas visuales y banners
 
+use std::fmt;
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Versión de SCypher para mostrar en el banner
 const VERSION: &str = "3.0";
 
 /// Colores ANSI para tema amber/terminal retro
+///
+/// Con `--plain-terminal` se escriben como texto vacío.
 pub mod colors {
-    pub const RESET: &str = "\x1b[0m";
-    pub const PRIMARY: &str = "\x1b[38;5;214m";      // Amber primary
-    pub const BRIGHT: &str = "\x1b[1;38;5;220m";     // Bright amber
-    pub const DIM: &str = "\x1b[38;5;172m";          // Dark orange
-    pub const WARNING: &str = "\x1b[38;5;228m";      // Warm yellow
-    pub const ERROR: &str = "\x1b[38;5;124m";        // Brick red
-    pub const FRAME: &str = "\x1b[38;5;240m";        // Dark gray
-    pub const SUCCESS: &str = "\x1b[1;32m";          // Green
+    use super::Style;
+
+    pub const RESET: Style = Style("\x1b[0m");
+    pub const PRIMARY: Style = Style("\x1b[38;5;214m");      // Amber primary
+    pub const BRIGHT: Style = Style("\x1b[1;38;5;220m");     // Bright amber
+    pub const DIM: Style = Style("\x1b[38;5;172m");          // Dark orange
+    pub const WARNING: Style = Style("\x1b[38;5;228m");      // Warm yellow
+    pub const ERROR: Style = Style("\x1b[38;5;124m");        // Brick red
+    pub const FRAME: Style = Style("\x1b[38;5;240m");        // Dark gray
+    pub const SUCCESS: Style = Style("\x1b[1;32m");          // Green
+}
+
+/// Modo de terminal simple (consolas serie, impresoras de línea)
+static PLAIN_TERMINAL: AtomicBool = AtomicBool::new(false);
+
+/// Activar o desactivar el modo sin secuencias ANSI ni control del cursor
+pub fn set_plain_terminal(plain: bool) {
+    PLAIN_TERMINAL.store(plain, Ordering::Relaxed);
+}
+
+/// Si la salida debe ser texto plano, línea a línea
+pub fn is_plain_terminal() -> bool {
+    PLAIN_TERMINAL.load(Ordering::Relaxed)
+}
+
+/// Secuencia ANSI de color que desaparece en modo de terminal simple
+#[derive(Debug, Clone, Copy)]
+pub struct Style(&'static str);
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_plain_terminal() {
+            Ok(())
+        } else {
+            f.write_str(self.0)
+        }
+    }
 }
 
 /// Limpiar pantalla usando múltiples métodos para compatibilidad total
 pub fn clear_screen() {
+    // Una consola serie o una impresora no se pueden borrar: solo separar
+    if is_plain_terminal() {
+        println!("\n{}\n", crate::cli::output::format::separator_line(61));
+        return;
+    }
+
     // Detectar tipo de terminal para mejor compatibilidad
     let term_type = std::env::var("TERM").unwrap_or_default();
     let is_windows = cfg!(target_os = "windows");
@@ -41,6 +80,15 @@ pub fn clear_screen() {
     }
 }
 
+/// Limpiar pantalla y también el scrollback, donde seguiría lo mostrado
+pub fn clear_screen_and_scrollback() {
+    clear_screen();
+    if !is_plain_terminal() {
+        print!("\x1b[3J");
+    }
+    io::stdout().flush().unwrap_or(());
+}
+
 /// Mostrar banner principal de SCypher con ASCII art
 pub fn show_banner() {
     println!("{}SCypher v{}{} {}- XOR-based BIP39 Seed Cipher{}",
@@ -0,0 +1,118 @@
+// src/cli/events.rs - Eventos de estado en JSON por stderr (--events json)
+//
+// Para interfaces gráficas que envuelven el CLI: cada evento es un objeto
+// JSON en su propia línea de stderr, así no hace falta interpretar el
+// texto para personas, que sigue saliendo igual. Eventos:
+//
+//     {"event":"phase","phase":"derive_key","percent":5}
+//     {"event":"progress","completed":3,"total":10,"percent":30}
+//     {"event":"warning","message":"..."}
+//     {"event":"done","exit_code":0}
+//     {"event":"error","code":3002,"exit_code":3,"message":"Passwords do not match"}
+//
+// Los porcentajes de las fases son aproximados: casi todo el tiempo se va
+// en la derivación. Los eventos no llevan frases ni contraseñas; los
+// mensajes son los mismos que ve el usuario.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+use crate::crypto::{Phase, ProgressSink};
+use crate::error::SCypherError;
+
+/// Si se emiten eventos
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Activar los eventos JSON en stderr
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Evento de estado
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    Phase { phase: &'static str, percent: u8 },
+    Progress { completed: usize, total: usize, percent: u8 },
+    Warning { message: &'a str },
+    Done { exit_code: i32 },
+    Error { code: u16, exit_code: i32, message: String },
+}
+
+impl Event<'_> {
+    /// Inicio de una fase de la transformación
+    pub fn phase(phase: Phase) -> Self {
+        let percent = match phase {
+            Phase::Validate => 0,
+            Phase::DeriveKey => 5,
+            Phase::Encrypt => 90,
+            Phase::Encode => 95,
+        };
+        Event::Phase { phase: phase.name(), percent }
+    }
+
+    /// `completed` de `total` elementos
+    pub fn progress(completed: usize, total: usize) -> Self {
+        let percent = (completed * 100).checked_div(total).unwrap_or(100).min(100) as u8;
+        Event::Progress { completed, total, percent }
+    }
+
+    /// Final con error
+    pub fn error(error: &SCypherError) -> Self {
+        Event::Error { code: error.code(), exit_code: error.exit_code(), message: error.to_string() }
+    }
+}
+
+/// Escribir `event` en stderr si los eventos están activados
+pub fn emit(event: &Event) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(line) = serde_json::to_string(event) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = writeln!(stderr, "{}", line);
+        let _ = stderr.flush();
+    }
+}
+
+/// Mostrar un aviso al usuario y emitirlo como evento
+pub fn warning(message: &str) {
+    println!("⚠️  {}", message);
+    emit(&Event::Warning { message });
+}
+
+/// Progreso de una transformación como eventos
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventProgress;
+
+impl ProgressSink for EventProgress {
+    fn on_phase(&self, phase: Phase) {
+        emit(&Event::phase(phase));
+    }
+
+    fn on_progress(&self, completed: usize, total: usize) {
+        emit(&Event::progress(completed, total));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let json = |event: &Event| serde_json::to_string(event).unwrap();
+        assert_eq!(json(&Event::phase(Phase::DeriveKey)), r#"{"event":"phase","phase":"derive_key","percent":5}"#);
+        assert_eq!(json(&Event::progress(3, 10)), r#"{"event":"progress","completed":3,"total":10,"percent":30}"#);
+        assert_eq!(json(&Event::progress(0, 0)), r#"{"event":"progress","completed":0,"total":0,"percent":100}"#);
+        assert_eq!(json(&Event::Warning { message: "low memory" }), r#"{"event":"warning","message":"low memory"}"#);
+        assert_eq!(json(&Event::error(&SCypherError::PasswordMismatch)),
+            r#"{"event":"error","code":3002,"exit_code":3,"message":"Passwords do not match"}"#);
+    }
+}
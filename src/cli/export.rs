@@ -0,0 +1,122 @@
+// src/cli/export.rs - Exportar claves de cuentas tras descifrar
+//
+// Deriva la cuenta de la frase resultante y la guarda protegida con una
+// contraseña nueva, para importarla en otras herramientas sin escribir
+// nunca la frase en claro en un archivo intermedio.
+
+
+use crate::cli::output::save_to_file;
+use crate::error::{SCypherError, Result};
+use crate::wallet::descriptor::{self, ScriptType};
+use crate::wallet::{self, bitcoin, cardano, ethereum, DerivationPath, ExtendedPrivateKey};
+
+/// Guardar en `output` un keystore V3 de Ethereum con la cuenta `path` de `phrase`
+pub fn export_eth_keystore(phrase: &str, path: Option<&DerivationPath>, output: &str) -> Result<()> {
+    let (account, path) = derive_for_export(phrase, path, ethereum::DEFAULT_PATH, "a keystore")?;
+    let address = ethereum::checksum_address(&ethereum::address(&account.public_key()));
+
+    println!("\nEthereum keystore export");
+    println!("Account: {} ({})", address, path);
+    println!("Choose a password for the keystore file (it replaces the seed for this account).\n");
+    let password = crate::cli::input::read_new_password("keystore password")?;
+
+    println!("Encrypting keystore (scrypt, 256 MB)...");
+    let keystore = ethereum::encrypt_keystore(&account.private_key_bytes(), &password, ethereum::ScryptParams::STANDARD)?;
+    save_to_file(&keystore, output)?;
+
+    println!("✓ Keystore for {} saved to {}", address, output);
+    Ok(())
+}
+
+/// Guardar en `output` la clave privada de `path` protegida con BIP38 (`6P...`)
+pub fn export_bip38(phrase: &str, path: Option<&DerivationPath>, output: &str) -> Result<()> {
+    let (account, path) = derive_for_export(phrase, path, bitcoin::DEFAULT_PATH, "a BIP38 key")?;
+    let address = bitcoin::p2pkh_address(&account.public_key());
+
+    println!("\nBIP38 key export");
+    println!("Address: {} ({})", address, path);
+    println!("Choose a passphrase for this key only (it does not reveal the seed or other accounts).\n");
+    let passphrase = crate::cli::input::read_new_password("BIP38 passphrase")?;
+
+    println!("Encrypting key (scrypt)...");
+    let encrypted = bitcoin::encrypt_bip38(&account.private_key_bytes(), &passphrase)?;
+    save_to_file(&format!("{}\n", encrypted), output)?;
+
+    println!("✓ BIP38 key for {} saved to {}", address, output);
+    Ok(())
+}
+
+/// Guardar en `output` los descriptores de la cuenta 0 (wpkh y tr) para `importdescriptors`
+///
+/// Son solo claves públicas: sirven para crear en Bitcoin Core una cartera
+/// watch-only y comprobar los fondos antes de usar la frase en otro sitio.
+pub fn export_descriptors(phrase: &str, output: &str) -> Result<()> {
+    let (master, _) = derive_for_export(phrase, None, "m", "descriptors")?;
+
+    println!("\nBitcoin Core descriptor export (watch-only, mainnet)");
+    let mut requests = Vec::new();
+    for script_type in ScriptType::ALL {
+        let descriptors = descriptor::account_descriptors(&master, script_type, 0)?;
+        println!("{}", descriptors.receive);
+        println!("{}", descriptors.change);
+        for (desc, internal) in [(descriptors.receive, false), (descriptors.change, true)] {
+            // Timestamp 0: reescanear desde el inicio para no perder fondos antiguos
+            requests.push(serde_json::json!({
+                "desc": desc,
+                "active": true,
+                "internal": internal,
+                "range": [0, 999],
+                "timestamp": 0,
+            }));
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&requests)
+        .map_err(|e| SCypherError::crypto(format!("Cannot serialize descriptors: {}", e)))?;
+    save_to_file(&format!("{}\n", json), output)?;
+
+    println!("✓ Descriptors saved to {}", output);
+    println!("  Import with: bitcoin-cli createwallet \"watch\" true true \"\" false true");
+    println!("               bitcoin-cli -rpcwallet=watch importdescriptors \"$(cat {})\"", output);
+    Ok(())
+}
+
+/// Mostrar la cuenta 0 y la primera dirección de la cartera Cardano (Icarus) de `phrase`
+///
+/// Solo datos públicos, para comparar con la cartera y confirmar que la
+/// frase recuperada es la del usuario y no solo una frase válida.
+pub fn show_cardano_account(phrase: &str) -> Result<()> {
+    crate::bip39::validate_seed_phrase(phrase).map_err(|e| {
+        SCypherError::crypto(format!("Cannot derive a Cardano wallet from an invalid phrase ({})", e))
+    })?;
+    let account = cardano::icarus_account(phrase, "")?;
+
+    println!("\nCardano wallet check (Icarus, mainnet)");
+    let words = phrase.split_whitespace().count();
+    if words != 15 && words != 24 {
+        println!("⚠️  Shelley wallets use 15 or 24 words; this phrase has {}", words);
+    }
+    println!("Account ({}): {}", cardano::ACCOUNT_PATH, account.account_public_key);
+    println!("First address: {}", account.first_address);
+    println!("Compare it with the first receive address shown by your wallet.");
+    Ok(())
+}
+
+/// Validar la frase y derivar la cuenta de `path` (o de `default_path`)
+fn derive_for_export(
+    phrase: &str,
+    path: Option<&DerivationPath>,
+    default_path: &str,
+    what: &str,
+) -> Result<(ExtendedPrivateKey, DerivationPath)> {
+    crate::bip39::validate_seed_phrase(phrase).map_err(|e| {
+        SCypherError::crypto(format!("Cannot export {} from an invalid phrase ({})", what, e))
+    })?;
+
+    let path = match path {
+        Some(path) => path.clone(),
+        None => default_path.parse()?,
+    };
+    let account = wallet::derive_account(phrase, "", &path)?;
+    Ok((account, path))
+}
@@ -0,0 +1,90 @@
+// src/cli/gpg.rs - Descifrado de archivos OpenPGP con `gpg`
+//
+// Para copias antiguas protegidas con GnuPG (`seed.txt.gpg`) y para
+// `--password-gpg`. El texto descifrado nunca toca el disco: `gpg` escribe
+// en una tubería (`--output -`) que se lee a un buffer de tamaño fijo,
+// reservado de antemano para que ninguna realocación deje copias sin
+// limpiar, y que se limpia al soltarse. `gpg` no recibe nuestro stdin (las
+// preguntas de SCypher siguen leyéndolo); la passphrase o la clave privada
+// las pide gpg-agent con su pinentry. Tampoco busca claves en la red.
+
+use std::io::Read;
+use std::process::{Command, Stdio};
+
+use zeroize::Zeroizing;
+
+use crate::error::{IoOperation, SCypherError, Result};
+
+/// Tamaño máximo del texto descifrado (una frase o una contraseña)
+pub const MAX_PLAINTEXT: usize = 64 * 1024;
+
+/// Si `bytes` empieza por un mensaje OpenPGP cifrado en binario
+///
+/// El primer paquete de `gpg --encrypt` o `gpg --symmetric` es una clave de
+/// sesión (PKESK, etiqueta 1, o SKESK, etiqueta 3), en formato antiguo o
+/// nuevo. Ningún texto UTF-8 empieza por esos bytes.
+pub fn is_encrypted_message(bytes: &[u8]) -> bool {
+    matches!(bytes.first(), Some(0x84..=0x87 | 0x8c..=0x8f | 0xc1 | 0xc3))
+}
+
+/// Descifrar `path` con `gpg` y devolver el contenido
+pub fn decrypt_file(path: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let mut child = Command::new("gpg")
+        .args(["--quiet", "--no-auto-key-retrieve", "--decrypt", "--output", "-", "--"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| SCypherError::file_caused_by(path, "Cannot run gpg", e))?;
+
+    let mut plaintext = Zeroizing::new(vec![0u8; MAX_PLAINTEXT]);
+    let read = read_bounded(child.stdout.take().expect("stdout is piped"), &mut plaintext);
+    if !matches!(read, Ok(len) if len < MAX_PLAINTEXT) {
+        let _ = child.kill();
+    }
+    let status = child.wait().map_err(|e| SCypherError::io_at(IoOperation::Execute, "gpg", e))?;
+
+    let len = read.map_err(|e| SCypherError::io_at(IoOperation::Read, path, e))?;
+    if len == MAX_PLAINTEXT {
+        return Err(SCypherError::file_at(path, format!("Decrypted content exceeds {} KB", MAX_PLAINTEXT / 1024)));
+    }
+    if !status.success() {
+        return Err(SCypherError::file_at(path, format!("gpg could not decrypt the file ({})", status)));
+    }
+    plaintext.truncate(len);
+    Ok(plaintext)
+}
+
+/// Leer hasta llenar `buffer` o hasta el final; devuelve los bytes leídos
+fn read_bounded(mut source: impl Read, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+    while len < buffer.len() {
+        match source.read(&mut buffer[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_encrypted_message() {
+        // PKESK en formato antiguo (gpg -e) y SKESK en formato nuevo
+        assert!(is_encrypted_message(&[0x84, 0x5e, 0x03]));
+        assert!(is_encrypted_message(&[0xc3, 0x2e, 0x04]));
+        assert!(!is_encrypted_message(b"abandon abandon"));
+        assert!(!is_encrypted_message(b"-----BEGIN PGP MESSAGE-----"));
+        assert!(!is_encrypted_message(&[]));
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(read_bounded(&b"ab"[..], &mut buffer).unwrap(), 2);
+        assert_eq!(read_bounded(&b"abcdef"[..], &mut buffer).unwrap(), 4);
+    }
+}
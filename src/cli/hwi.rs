@@ -0,0 +1,120 @@
+// src/cli/hwi.rs - Comprobar el resultado contra una cartera hardware
+//
+// Usa HWI (https://github.com/bitcoin-core/HWI) como proceso externo: pide
+// al dispositivo conectado su huella maestra y el xpub de la cuenta BIP84 y
+// los compara con los derivados de la frase descifrada. Solo viajan datos
+// públicos; la frase nunca sale de este proceso.
+
+use std::process::{Command, Stdio};
+
+use serde_json::Value;
+
+use crate::error::{IoOperation, SCypherError, Result};
+use crate::wallet::descriptor::{self, ScriptType};
+use crate::wallet::{mnemonic_to_seed, ExtendedPrivateKey};
+
+/// Dispositivo enumerado por HWI
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Device {
+    model: String,
+    fingerprint: String,
+}
+
+/// Comparar `phrase` con la cartera hardware conectada, usando el ejecutable `hwi`
+///
+/// Devuelve error si el dispositivo no responde o si no coincide.
+pub fn cross_check(phrase: &str, hwi: &str) -> Result<()> {
+    crate::bip39::validate_seed_phrase(phrase).map_err(|e| {
+        SCypherError::crypto(format!("Cannot compare an invalid phrase with a device ({})", e))
+    })?;
+
+    println!("\nHardware wallet cross-check (HWI)");
+    let device = pick_device(&run(hwi, &["enumerate"])?)?;
+    println!("Device: {} ({})", device.model, device.fingerprint);
+
+    let seed = mnemonic_to_seed(phrase, "");
+    let master = ExtendedPrivateKey::from_seed(&seed[..])?;
+    let fingerprint = hex::encode(descriptor::fingerprint(&master));
+    if fingerprint != device.fingerprint {
+        return Err(SCypherError::crypto(format!(
+            "Master fingerprint {} does not match the device ({}); wrong phrase or a device passphrase is active",
+            fingerprint, device.fingerprint,
+        )));
+    }
+
+    let path = ScriptType::Wpkh.account_path(0)?;
+    let hwi_path = path.to_string().replace('\'', "h");
+    let response = run(hwi, &["--fingerprint", &device.fingerprint, "getxpub", &hwi_path])?;
+    let device_xpub = response.get("xpub").and_then(Value::as_str)
+        .ok_or_else(|| hwi_error(&response, "no xpub in the HWI response"))?;
+
+    if descriptor::xpub(&master, &path)? != device_xpub {
+        return Err(SCypherError::crypto(format!("Account xpub at {} does not match the device", path)));
+    }
+
+    println!("✓ Fingerprint {} and account xpub at {} match the device", fingerprint, path);
+    Ok(())
+}
+
+/// Ejecutar HWI y leer su respuesta JSON
+fn run(hwi: &str, args: &[&str]) -> Result<Value> {
+    let output = Command::new(hwi)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| SCypherError::io_at(IoOperation::Execute, hwi, e))?;
+
+    let response: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|_| SCypherError::file_at(hwi, format!("HWI did not return JSON ({})", output.status)))?;
+    if !output.status.success() {
+        return Err(hwi_error(&response, "HWI failed"));
+    }
+    Ok(response)
+}
+
+fn hwi_error(response: &Value, fallback: &str) -> SCypherError {
+    let message = response.get("error").and_then(Value::as_str).unwrap_or(fallback);
+    SCypherError::InvalidRequest(format!("HWI: {}", message))
+}
+
+/// Único dispositivo desbloqueado de `hwi enumerate`
+fn pick_device(devices: &Value) -> Result<Device> {
+    let devices = devices.as_array()
+        .ok_or_else(|| hwi_error(devices, "unexpected enumerate response"))?;
+
+    let mut ready = Vec::new();
+    for device in devices {
+        let model = device.get("model").and_then(Value::as_str).unwrap_or("unknown").to_string();
+        match device.get("fingerprint").and_then(Value::as_str) {
+            Some(fingerprint) => ready.push(Device { model, fingerprint: fingerprint.to_lowercase() }),
+            None => println!("⚠️  Skipping {}: {}", model, device.get("error").and_then(Value::as_str).unwrap_or("locked")),
+        }
+    }
+
+    match ready.len() {
+        1 => Ok(ready.remove(0)),
+        0 => Err(SCypherError::InvalidRequest("HWI: no unlocked hardware wallet found".to_string())),
+        _ => Err(SCypherError::InvalidRequest("HWI: connect only the device to compare".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_device() {
+        let devices = serde_json::json!([
+            {"type": "trezor", "model": "trezor_t", "fingerprint": "73C5DA0A"},
+            {"type": "ledger", "model": "ledger_nano_s", "error": "Device is locked"},
+        ]);
+        assert_eq!(
+            pick_device(&devices).unwrap(),
+            Device { model: "trezor_t".to_string(), fingerprint: "73c5da0a".to_string() }
+        );
+
+        assert!(pick_device(&serde_json::json!([])).is_err());
+        assert!(pick_device(&serde_json::json!({"error": "boom"})).is_err());
+    }
+}
@@ -1,14 +1,24 @@
 // src/cli/input.rs - Manejo seguro de entrada del usuario
 
 use std::io::{self, Write};
-use rpassword::read_password;
-use crate::error::{SCypherError, Result};
+use std::time::Duration;
+use zeroize::Zeroizing;
+use crate::cli::secure_input::{read_masked, Mask};
+use crate::error::{IoOperation, SCypherError, Result};
+#[cfg(feature = "formats-extra")]
+use crate::format::{Base58Seed, Bech32Seed, OutputFormat, RecoverySheet, Worksheet};
+#[cfg(feature = "slip39")]
+use crate::slip39::{Share, ShareSet};
+use crate::ur::UrDecoder;
 
 const MIN_PASSWORD_LENGTH: usize = 8;
 const MAX_SEED_LENGTH: usize = 1000; // Límite razonable para frases semilla
 
+/// Pausa obligatoria entre los dos operadores de `--dual-control`
+const HANDOVER_PAUSE: Duration = Duration::from_secs(5);
+
 /// Lee la frase semilla de forma interactiva
-pub fn read_seed_interactive(is_decrypt_mode: bool) -> Result<String> {
+pub fn read_seed_interactive(is_decrypt_mode: bool) -> Result<Zeroizing<String>> {
     let prompt = if is_decrypt_mode {
         "\nEnter encrypted seed phrase to decrypt:"
     } else {
@@ -19,15 +29,40 @@ pub fn read_seed_interactive(is_decrypt_mode: bool) -> Result<String> {
     print!("> ");
     io::stdout().flush().map_err(SCypherError::from)?;
 
-    // Leer una sola línea directamente
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).map_err(SCypherError::from)?;
+    // Leer una sola línea, enmascarada (los espacios se ven)
+    let input = read_masked(Mask::Seed)?;
+    println!();
+
+    let seed_phrase = Zeroizing::new(input.trim().to_string());
+
+    // Semilla escaneada como UR (una parte o la primera de varias)
+    if is_ur(&seed_phrase) {
+        return read_ur_interactive(&seed_phrase);
+    }
+
+    // Primer share de una copia SLIP-39
+    #[cfg(feature = "slip39")]
+    if Share::parse(&seed_phrase).is_ok() {
+        return read_slip39_interactive(&seed_phrase);
+    }
 
-    let seed_phrase = input.trim().to_string();
+    // Hoja de recuperación numerada pegada en una línea
+    #[cfg(feature = "formats-extra")]
+    if RecoverySheet::looks_like_sheet(&seed_phrase) {
+        let seed_phrase = Zeroizing::new(RecoverySheet.decode(&seed_phrase)?);
+        validate_seed_input(&seed_phrase)?;
+        return Ok(seed_phrase);
+    }
+
+    // Entropía en bech32m (`scypher1...`) o Base58Check
+    #[cfg(feature = "formats-extra")]
+    if let Some(seed_phrase) = decode_compact(&seed_phrase) {
+        return seed_phrase;
+    }
 
     // Verificar si es un archivo
     if seed_phrase.ends_with(".txt") && std::path::Path::new(&seed_phrase).exists() {
-        println!("Reading from file: {}", seed_phrase);
+        println!("Reading from file: {}", *seed_phrase);
         return read_seed_from_file(&seed_phrase);
     }
 
@@ -40,22 +75,69 @@ pub fn read_seed_interactive(is_decrypt_mode: bool) -> Result<String> {
 }
 
 /// Lee la frase semilla desde un archivo
-pub fn read_seed_from_file(file_path: &str) -> Result<String> {
+#[tracing::instrument(name = "input", level = "debug")]
+pub fn read_seed_from_file(file_path: &str) -> Result<Zeroizing<String>> {
     println!("Reading seed phrase from file: {}", file_path);
 
-    let content = std::fs::read_to_string(file_path)
-        .map_err(|e| SCypherError::file(format!("Cannot read file '{}': {}", file_path, e)))?;
+    let content = read_input_file(file_path)?;
+
+    // Partes UR, una por línea, en cualquier orden
+    if let Some(ur) = decode_ur_file(file_path, &content)? {
+        let seed_phrase = Zeroizing::new(ur.to_phrase()?);
+        println!("✓ Successfully decoded {} words from UR\n", seed_phrase.split_whitespace().count());
+        return Ok(seed_phrase);
+    }
+
+    // Shares SLIP-39, uno por línea
+    #[cfg(feature = "slip39")]
+    let mut lines = content.lines().map(str::trim).filter(|line| !line.is_empty()).peekable();
+    #[cfg(feature = "slip39")]
+    if lines.peek().is_some_and(|line| Share::parse(line).is_ok()) {
+        let mut shares = ShareSet::new();
+        for line in lines {
+            shares.add(line).map_err(|e| SCypherError::file_caused_by(file_path, "Invalid SLIP-39 share", e))?;
+        }
+        let seed_phrase = slip39_phrase(&shares).map_err(|e| SCypherError::file_caused_by(file_path, "Cannot combine SLIP-39 shares", e))?;
+        println!("✓ Successfully combined {} SLIP-39 shares\n", shares.len());
+        return Ok(seed_phrase);
+    }
+
+    // Hoja de `--format worksheet`: cada palabra se compara con su valor de control
+    #[cfg(feature = "formats-extra")]
+    if Worksheet::looks_like_worksheet(&content) {
+        let seed_phrase = Zeroizing::new(Worksheet.decode(&content).map_err(|e| SCypherError::file_caused_by(file_path, "Invalid recovery worksheet", e))?);
+        validate_seed_input(&seed_phrase)?;
+        println!("✓ Successfully read {} words from worksheet\n", seed_phrase.split_whitespace().count());
+        return Ok(seed_phrase);
+    }
+
+    // Hoja de recuperación numerada (Trezor/Ledger), quizá con abreviaturas
+    #[cfg(feature = "formats-extra")]
+    if RecoverySheet::looks_like_sheet(&content) {
+        let seed_phrase = Zeroizing::new(RecoverySheet.decode(&content).map_err(|e| SCypherError::file_caused_by(file_path, "Invalid recovery sheet", e))?);
+        validate_seed_input(&seed_phrase)?;
+        println!("✓ Successfully read {} words from recovery sheet\n", seed_phrase.split_whitespace().count());
+        return Ok(seed_phrase);
+    }
+
+    // Entropía en bech32m o Base58Check
+    #[cfg(feature = "formats-extra")]
+    if let Some(seed_phrase) = decode_compact(&content) {
+        let seed_phrase = seed_phrase.map_err(|e| SCypherError::file_caused_by(file_path, "Invalid compact encoding", e))?;
+        println!("✓ Successfully decoded {} words\n", seed_phrase.split_whitespace().count());
+        return Ok(seed_phrase);
+    }
 
     // Limpiar contenido: remover saltos de línea excesivos y espacios
-    let seed_phrase = content
+    let seed_phrase = Zeroizing::new(content
         .lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
         .collect::<Vec<&str>>()
-        .join(" ");
+        .join(" "));
 
     if seed_phrase.is_empty() {
-        return Err(SCypherError::file("File is empty or contains no valid content".to_string()));
+        return Err(SCypherError::file_at(file_path, "File is empty or contains no valid content"));
     }
 
     validate_seed_input(&seed_phrase)?;
@@ -64,27 +146,308 @@ pub fn read_seed_from_file(file_path: &str) -> Result<String> {
     Ok(seed_phrase)
 }
 
+/// Abrir `file_path` si es un contenedor de `--armor`; `None` si no lo es
+///
+/// Pide la passphrase del contenedor. El documento de una frase cifrada
+/// incluye los parámetros con que se cifró.
+#[cfg(feature = "armor")]
+pub fn read_container(file_path: &str) -> Result<Option<crate::armor::RecoveryDocument>> {
+    if is_gpg_file(file_path) {
+        return Ok(None);
+    }
+    let content = read_input_file(file_path)?;
+    let Some(armor) = crate::armor::Armor::detect(&content) else {
+        return Ok(None);
+    };
+
+    println!("Reading {} container: {}", armor.name(), file_path);
+    let passphrase = read_existing_password("container passphrase")?;
+    let document = armor.open(&content, &passphrase)?;
+    let recovered = crate::armor::parse_recovery_document(&document)
+        .map_err(|e| SCypherError::file_caused_by(file_path, "Invalid recovery document", e))?;
+
+    // El contenido (frase o clave extendida) se valida como cualquier otra entrada
+    println!("✓ Container opened\n");
+    Ok(Some(recovered))
+}
+
+/// UR completo de un archivo con partes UR, una por línea; `None` si no lo son
+///
+/// Las partes de un QR repartido con `--qr-split` se combinan en el UR original.
+pub(crate) fn decode_ur_file(file_path: &str, content: &str) -> Result<Option<crate::ur::Ur>> {
+    let mut lines = content.lines().map(str::trim).filter(|line| !line.is_empty()).peekable();
+    if !lines.peek().is_some_and(|line| is_ur(line)) {
+        return Ok(None);
+    }
+    if lines.peek().is_some_and(|line| crate::ur::share::is_share_line(line)) {
+        let ur = crate::ur::share::combine_share_lines(content.lines())
+            .map_err(|e| SCypherError::file_caused_by(file_path, "Cannot combine split QR shares", e))?;
+        println!("✓ Split QR rebuilt from its shares");
+        return Ok(Some(ur));
+    }
+
+    let mut decoder = UrDecoder::new();
+    for line in lines {
+        decoder.receive(line).map_err(|e| SCypherError::file_caused_by(file_path, "Invalid UR part", e))?;
+    }
+    decoder.result().cloned().map(Some).ok_or_else(|| SCypherError::file_at(
+        file_path,
+        format!("Incomplete UR: {:.0}% of the parts received", decoder.progress() * 100.0),
+    ))
+}
+
+/// Leer `file_path` si es un QR de copia de seguridad (`ur:scypher-backup`); `None` si no lo es
+///
+/// La copia trae la frase cifrada y los parámetros con que se cifró.
+pub fn read_backup(file_path: &str) -> Result<Option<crate::ur::SeedBackup>> {
+    if is_gpg_file(file_path) {
+        return Ok(None);
+    }
+    let content = read_input_file(file_path)?;
+    let Some(ur) = decode_ur_file(file_path, &content)? else {
+        return Ok(None);
+    };
+    if ur.ur_type() != crate::ur::SCYPHER_BACKUP {
+        return Ok(None);
+    }
+
+    println!("Reading SCypher backup QR: {}", file_path);
+    let backup = ur.to_scypher_backup().map_err(|e| SCypherError::file_caused_by(file_path, "Invalid backup QR", e))?;
+    println!("✓ Backup read: {} words, {} with {} iterations and {}KB memory\n",
+        backup.phrase.split_whitespace().count(), backup.params.kdf().name(), backup.params.iterations(), backup.params.memory_cost());
+    Ok(Some(backup))
+}
+
+/// Texto de un archivo de entrada; de una imagen PNG/BMP, lo que lleva oculto
+///
+/// Un `ur:bytes` (las partes escaneadas de un QR animado, p. ej. de un
+/// contenedor de `--armor`) se sustituye por el texto que transporta. Un
+/// mensaje OpenPGP binario (`seed.txt.gpg`) se descifra con `gpg` en memoria.
+fn read_input_file(file_path: &str) -> Result<Zeroizing<String>> {
+    let bytes = std::fs::read(file_path).map_err(|e| SCypherError::io_at(IoOperation::Read, file_path, e))?;
+
+    if crate::cli::gpg::is_encrypted_message(&bytes) {
+        println!("Decrypting input file with GnuPG: {}", file_path);
+        let plaintext = crate::cli::gpg::decrypt_file(file_path)?;
+        return std::str::from_utf8(&plaintext)
+            .map(|text| Zeroizing::new(text.to_string()))
+            .map_err(|_| SCypherError::file_at(file_path, "The GnuPG-encrypted file does not hold text"));
+    }
+
+    #[cfg(feature = "stego")]
+    let content = if crate::stego::ImageFormat::detect(&bytes).is_some() {
+        let hidden = crate::stego::extract(&bytes)
+            .map_err(|e| SCypherError::file_caused_by(file_path, "Cannot read hidden data", e))?;
+        Zeroizing::new(String::from_utf8(hidden.to_vec()).map_err(|_| SCypherError::file_at(file_path, "Hidden data is not text"))?)
+    } else {
+        utf8_file(file_path, bytes)?
+    };
+    #[cfg(not(feature = "stego"))]
+    let content = utf8_file(file_path, bytes)?;
+
+    match decode_ur_file(file_path, &content)? {
+        Some(ur) if ur.ur_type() == crate::ur::BYTES => String::from_utf8(ur.to_bytes()?.to_vec())
+            .map(Zeroizing::new)
+            .map_err(|_| SCypherError::file_at(file_path, "The ur:bytes payload is not text")),
+        _ => Ok(content),
+    }
+}
+
+/// Si `file_path` es un mensaje OpenPGP binario: solo se descifra (una vez)
+/// al leer la frase, no al buscar contenedores o copias de seguridad
+fn is_gpg_file(file_path: &str) -> bool {
+    let mut first = [0u8; 1];
+    std::fs::File::open(file_path)
+        .and_then(|mut file| io::Read::read(&mut file, &mut first))
+        .is_ok_and(|read| read == 1 && crate::cli::gpg::is_encrypted_message(&first))
+}
+
+fn utf8_file(file_path: &str, bytes: Vec<u8>) -> Result<Zeroizing<String>> {
+    String::from_utf8(bytes).map(Zeroizing::new).map_err(|e| SCypherError::io_at(
+        IoOperation::Read, file_path, io::Error::new(io::ErrorKind::InvalidData, e.utf8_error())))
+}
+
+/// Frase de una cadena bech32m o Base58Check, si el texto lo parece
+#[cfg(feature = "formats-extra")]
+fn decode_compact(text: &str) -> Option<Result<Zeroizing<String>>> {
+    if Bech32Seed::looks_like(text) {
+        Some(Bech32Seed.decode(text).map(Zeroizing::new))
+    } else if Base58Seed::looks_like(text) {
+        Some(Base58Seed.decode(text).map(Zeroizing::new))
+    } else {
+        None
+    }
+}
+
+/// Si una línea es una parte UR (`ur:tipo/...`)
+fn is_ur(line: &str) -> bool {
+    line.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("ur:"))
+}
+
+/// Completa una semilla UR pidiendo partes hasta poder reconstruirla
+///
+/// Las partes malformadas se avisan y se ignoran; una línea vacía cancela.
+fn read_ur_interactive(first_part: &str) -> Result<Zeroizing<String>> {
+    let mut decoder = UrDecoder::new();
+    let mut part = first_part.to_string();
+
+    loop {
+        if let Err(e) = decoder.receive(&part) {
+            println!("⚠️  Ignoring UR part: {}", e);
+        }
+        if let Some(ur) = decoder.result() {
+            if ur.ur_type() == crate::ur::SCYPHER_SHARE {
+                return Err(SCypherError::InvalidRequest(
+                    "this QR is one share of a split QR; scan every share into a file and read it with -f".to_string(),
+                ));
+            }
+            // Los parámetros de la copia solo se aplican leyéndola con -f
+            if ur.ur_type() == crate::ur::SCYPHER_BACKUP {
+                return Err(SCypherError::InvalidRequest(
+                    "this QR is a SCypher backup with its own parameters; save the scanned parts to a file and read it with -f".to_string(),
+                ));
+            }
+            if ur.ur_type() == crate::ur::BYTES {
+                return phrase_from_ur_bytes(ur);
+            }
+            return ur.to_phrase().map(Zeroizing::new);
+        }
+
+        println!("UR progress: {:.0}% — enter the next part (empty line to cancel):", decoder.progress() * 100.0);
+        print!("> ");
+        io::stdout().flush().map_err(SCypherError::from)?;
+
+        part.clear();
+        io::stdin().read_line(&mut part).map_err(SCypherError::from)?;
+        part = part.trim().to_string();
+        if part.is_empty() {
+            return Err(SCypherError::InvalidRequest("UR input cancelled before it was complete".to_string()));
+        }
+    }
+}
+
+/// Frase transportada como texto en un `ur:bytes` escaneado
+///
+/// Un contenedor de `--armor` necesita su passphrase y se lee con -f.
+fn phrase_from_ur_bytes(ur: &crate::ur::Ur) -> Result<Zeroizing<String>> {
+    let text = Zeroizing::new(String::from_utf8(ur.to_bytes()?.to_vec())
+        .map_err(|_| SCypherError::InvalidRequest("the scanned ur:bytes payload is not text".to_string()))?);
+
+    #[cfg(feature = "armor")]
+    if crate::armor::Armor::detect(&text).is_some() {
+        return Err(SCypherError::InvalidRequest(
+            "this QR holds an armored container; save the scanned parts to a file and read it with -f".to_string(),
+        ));
+    }
+
+    let seed_phrase = Zeroizing::new(text.split_whitespace().collect::<Vec<_>>().join(" "));
+    validate_seed_input(&seed_phrase)?;
+    Ok(seed_phrase)
+}
+
+/// Completa una copia SLIP-39 pidiendo shares hasta poder reconstruirla
+///
+/// Los shares erróneos se avisan y se ignoran; una línea vacía cancela.
+#[cfg(feature = "slip39")]
+fn read_slip39_interactive(first_share: &str) -> Result<Zeroizing<String>> {
+    let mut shares = ShareSet::new();
+    let mut share = first_share.to_string();
+
+    loop {
+        if let Err(e) = shares.add(&share) {
+            println!("⚠️  Ignoring share: {}", e);
+        }
+        if shares.is_complete() {
+            return slip39_phrase(&shares);
+        }
+
+        let (complete, needed) = shares.progress();
+        println!("SLIP-39: {} share(s), {} of {} groups complete — enter the next share (empty line to cancel):",
+            shares.len(), complete, needed);
+        print!("> ");
+        io::stdout().flush().map_err(SCypherError::from)?;
+
+        share.clear();
+        io::stdin().read_line(&mut share).map_err(SCypherError::from)?;
+        share = share.trim().to_string();
+        if share.is_empty() {
+            return Err(SCypherError::InvalidRequest("SLIP-39 input cancelled before it was complete".to_string()));
+        }
+    }
+}
+
+/// Master secret de los shares (sin passphrase) como frase BIP39 de la misma entropía
+#[cfg(feature = "slip39")]
+fn slip39_phrase(shares: &ShareSet) -> Result<Zeroizing<String>> {
+    let secret = shares.master_secret("")?;
+    let phrase = Zeroizing::new(crate::bip39::entropy_to_phrase(&secret)?);
+    println!("⚠️  The master secret is carried as a BIP39 phrase of the same entropy.");
+    println!("   Restore it as SLIP-39 shares: a BIP39 wallet would derive different accounts from it.");
+    Ok(phrase)
+}
+
 /// Lee la contraseña de forma segura (sin mostrar en pantalla)
-pub fn read_password_secure() -> Result<String> {
+pub fn read_password_secure() -> Result<Zeroizing<String>> {
     println!("Password Requirements:");
     println!("• Minimum {} characters", MIN_PASSWORD_LENGTH);
     println!("• Use a strong, unique password");
     println!("• Remember: same password needed for decryption\n");
 
+    read_new_password("password")
+}
+
+/// Contraseñas de los dos operadores de `--dual-control`, ya combinadas
+///
+/// Entre una y otra se borra la pantalla (con el scrollback) y hay una
+/// pausa obligatoria, para que el primer operador se aparte antes de que
+/// se siente el segundo. Cada uno confirma la suya; deben ser distintas.
+pub fn read_dual_control_password() -> Result<Zeroizing<String>> {
+    println!("Dual control: each operator enters their own password; neither can decrypt alone.");
+    println!("• Minimum {} characters each, and the two must differ", MIN_PASSWORD_LENGTH);
+    println!("• Both operators are needed again for decryption\n");
+
+    println!("Operator 1:");
+    let first = read_new_password("operator 1 password")?;
+
+    crate::cli::display::clear_screen_and_scrollback();
+    println!("Operator 1 is done: please step away from the screen and keyboard.");
+    for remaining in (1..=HANDOVER_PAUSE.as_secs()).rev() {
+        print!("\rOperator 2 can start in {} s... ", remaining);
+        io::stdout().flush().map_err(SCypherError::from)?;
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    print!("\nOperator 2: press Enter when ready...");
+    io::stdout().flush().map_err(SCypherError::from)?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).map_err(SCypherError::from)? == 0 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    println!("\nOperator 2:");
+    let second = read_new_password("operator 2 password")?;
+    crate::cli::display::clear_screen_and_scrollback();
+
+    crate::crypto::dual::combine_passwords(&first, &second)
+}
+
+/// Pedir una contraseña nueva dos veces hasta que coincidan y cumpla la longitud mínima
+///
+/// `what` nombra la contraseña en los mensajes (p.ej. "keystore password").
+pub fn read_new_password(what: &str) -> Result<Zeroizing<String>> {
     loop {
-        print!("Enter password: ");
+        print!("Enter {}: ", what);
         io::stdout().flush().map_err(SCypherError::from)?;
 
         let password = read_password_with_asterisks()?;
         println!(); // Nueva línea después de la entrada
 
-        print!("Confirm password: ");
+        print!("Confirm {}: ", what);
         io::stdout().flush().map_err(SCypherError::from)?;
 
         let password_confirm = read_password_with_asterisks()?;
         println!(); // Nueva línea después de la confirmación
 
-        if password != password_confirm {
+        if *password != *password_confirm {
             println!("❌ Password mismatch. Please try again.\n");
             continue;
         }
@@ -99,71 +462,57 @@ pub fn read_password_secure() -> Result<String> {
     }
 }
 
-/// Función mejorada para leer contraseña con asteriscos
-fn read_password_with_asterisks() -> Result<String> {
-    use std::io::Read;
+/// Leer la frase clave de `--key-mnemonic`: de un archivo o tecleada sin eco
+pub fn read_key_phrase(file_path: Option<&str>) -> Result<Zeroizing<String>> {
+    if let Some(file_path) = file_path {
+        return read_seed_from_file(file_path);
+    }
 
-    let mut password = String::new();
+    println!("The key phrase is a second BIP39 phrase with the same number of words.");
+    print!("Enter key phrase: ");
+    io::stdout().flush().map_err(SCypherError::from)?;
+    let typed = read_masked(Mask::Seed)?;
+    println!();
 
-    // Configurar terminal para modo raw
-    #[cfg(unix)]
-    {
-        use std::os::unix::io::AsRawFd;
-        let stdin_fd = io::stdin().as_raw_fd();
+    let key_phrase = Zeroizing::new(typed.split_whitespace().collect::<Vec<&str>>().join(" "));
+    validate_seed_input(&key_phrase)?;
+    println!("✓ Read {} words\n", key_phrase.split_whitespace().count());
+    Ok(key_phrase)
+}
 
-        // Obtener configuración actual del terminal
-        let mut termios = unsafe { std::mem::zeroed() };
-        if unsafe { libc::tcgetattr(stdin_fd, &mut termios) } != 0 {
-            // Si falla, usar rpassword como fallback
-            return Ok(rpassword::read_password().map_err(|e|
-                SCypherError::crypto(format!("Failed to read password: {}", e)))?);
-        }
+/// Pedir una contraseña que ya existe, una sola vez (para abrir algo, no hace falta confirmarla)
+pub fn read_existing_password(what: &str) -> Result<Zeroizing<String>> {
+    print!("Enter {}: ", what);
+    io::stdout().flush().map_err(SCypherError::from)?;
 
-        // Guardar configuración original
-        let original_termios = termios;
+    let password = read_password_with_asterisks()?;
+    println!();
+    Ok(password)
+}
 
-        // Deshabilitar echo y modo canónico
-        termios.c_lflag &= !(libc::ECHO | libc::ICANON);
+/// Pedir dos veces una contraseña que ya existe, cuando un error de tecleo no se detectaría después
+///
+/// Con un descifrado XOR una contraseña equivocada da otra frase válida:
+/// quien la vuelva a cifrar (`rotate`) perdería el secreto sin saberlo.
+pub fn read_existing_password_confirmed(what: &str) -> Result<Zeroizing<String>> {
+    loop {
+        let password = read_existing_password(what)?;
 
-        if unsafe { libc::tcsetattr(stdin_fd, libc::TCSANOW, &termios) } != 0 {
-            return Ok(rpassword::read_password().map_err(|e|
-                SCypherError::crypto(format!("Failed to read password: {}", e)))?);
-        }
+        print!("Confirm {}: ", what);
+        io::stdout().flush().map_err(SCypherError::from)?;
+        let password_confirm = read_password_with_asterisks()?;
+        println!();
 
-        // Leer caracteres uno por uno
-        let stdin = io::stdin();
-        for byte in stdin.bytes() {
-            match byte {
-                Ok(b'\n') | Ok(b'\r') => break,
-                Ok(127) | Ok(8) => { // Backspace o DEL
-                    if !password.is_empty() {
-                        password.pop();
-                        print!("\x08 \x08"); // Borrar asterisco
-                        io::stdout().flush().unwrap_or(());
-                    }
-                }
-                Ok(b) if b >= 32 && b <= 126 => { // Caracteres imprimibles
-                    password.push(b as char);
-                    print!("*");
-                    io::stdout().flush().unwrap_or(());
-                }
-                Ok(_) => {} // Ignorar otros caracteres de control
-                Err(_) => break,
-            }
+        if *password == *password_confirm {
+            return Ok(password);
         }
-
-        // Restaurar configuración original del terminal
-        unsafe { libc::tcsetattr(stdin_fd, libc::TCSANOW, &original_termios) };
-    }
-
-    #[cfg(not(unix))]
-    {
-        // En Windows o otros sistemas, usar rpassword como fallback
-        return Ok(rpassword::read_password().map_err(|e|
-            SCypherError::crypto(format!("Failed to read password: {}", e)))?);
+        println!("❌ Password mismatch. Please try again.\n");
     }
+}
 
-    Ok(password)
+/// Leer una contraseña mostrando un asterisco por carácter
+fn read_password_with_asterisks() -> Result<Zeroizing<String>> {
+    read_masked(Mask::Password)
 }
 
 /// Validar entrada de frase semilla
@@ -183,8 +532,11 @@ fn validate_seed_input(seed_phrase: &str) -> Result<()> {
         return Ok(()); // Los archivos se validan en otra función
     }
 
-    // Verificar caracteres básicos (solo letras, números y espacios)
-    if !seed_phrase.chars().all(|c| c.is_ascii_alphanumeric() || c.is_ascii_whitespace()) {
+    // Verificar caracteres básicos (letras y números de cualquier idioma, espacios y acentos combinables)
+    let is_word_char = |c: char| {
+        c.is_alphanumeric() || c.is_whitespace() || unicode_normalization::char::is_combining_mark(c)
+    };
+    if !seed_phrase.chars().all(is_word_char) {
         return Err(SCypherError::InvalidSeedPhrase);
     }
 
@@ -211,8 +563,11 @@ pub fn read_confirmation(prompt: &str) -> Result<bool> {
         print!("{} (y/n): ", prompt);
         io::stdout().flush().map_err(SCypherError::from)?;
 
+        // Sin más entrada (stdin cerrado) se repetiría la pregunta para siempre
         let mut input = String::new();
-        io::stdin().read_line(&mut input).map_err(SCypherError::from)?;
+        if io::stdin().read_line(&mut input).map_err(SCypherError::from)? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
 
         match input.trim().to_lowercase().as_str() {
             "y" | "yes" => return Ok(true),
@@ -233,7 +588,9 @@ where
         io::stdout().flush().map_err(SCypherError::from)?;
 
         let mut input = String::new();
-        io::stdin().read_line(&mut input).map_err(SCypherError::from)?;
+        if io::stdin().read_line(&mut input).map_err(SCypherError::from)? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
 
         match input.trim().parse::<T>() {
             Ok(num) if num >= min && num <= max => return Ok(num),
@@ -258,6 +615,7 @@ mod tests {
         assert!(validate_seed_input("word1").is_err());              // Solo 1 palabra
         assert!(validate_seed_input("word1 word2 word3 word4 word5 word6 word7 word8 word9 word10 word11").is_err()); // 11 palabras
         assert!(validate_seed_input("word1 word2! word3").is_err()); // Caracteres especiales
+        assert!(validate_seed_input(&["ábaco"; 12].join(" ")).is_ok()); // Listas no inglesas
     }
 
     #[test]
@@ -0,0 +1,261 @@
+// src/cli/inspect.rs - Qué es un archivo de copia, sin pedir contraseñas
+//
+// `scypher inspect FILE` reconoce los contenedores de `--armor` (y los age u
+// OpenPGP de otras herramientas), los QR de copia de seguridad y las frases
+// sueltas, y muestra lo que se puede leer sin descifrar: versión, KDF y
+// parámetros, una huella del salt, qué guarda y las fechas del archivo. Nunca
+// muestra palabras: una frase suelta puede ser la semilla de la cartera.
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::crypto::{SaltSource, ScypherParams};
+use crate::error::{IoOperation, SCypherError, Result};
+
+/// Lo que se sabe de un archivo sin descifrarlo
+#[derive(Debug, Default, Serialize)]
+pub struct Inspection {
+    pub file: String,
+    /// Tipo de archivo reconocido
+    pub container: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cipher: Option<String>,
+    /// Huella del salt (no el salt), para comparar copias entre sí
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    /// Qué guarda
+    pub payload: String,
+    /// Fechas del archivo y cabeceras del contenedor
+    pub metadata: Vec<String>,
+}
+
+/// Inspeccionar `path` y mostrar el resultado (en JSON con `json`)
+pub fn run_inspect(path: &str, json: bool) -> Result<()> {
+    let inspection = inspect_file(path)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&inspection).map_err(|e| SCypherError::crypto(e.to_string()))?);
+        return Ok(());
+    }
+
+    println!("File:        {}", inspection.file);
+    println!("Container:   {}", inspection.container);
+    let fields = [
+        ("Version:", &inspection.version),
+        ("KDF:", &inspection.kdf),
+        ("Parameters:", &inspection.parameters),
+        ("Cipher:", &inspection.cipher),
+        ("Salt:", &inspection.salt),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            println!("{:<12} {}", label, value);
+        }
+    }
+    println!("Payload:     {}", inspection.payload);
+    for line in &inspection.metadata {
+        println!("Metadata:    {}", line);
+    }
+    Ok(())
+}
+
+/// Reconocer `path` y leer lo que deja ver sin contraseñas
+pub fn inspect_file(path: &str) -> Result<Inspection> {
+    let bytes = Zeroizing::new(std::fs::read(path).map_err(|e| SCypherError::io_at(IoOperation::Read, path, e))?);
+    let mut inspection = Inspection { file: path.to_string(), ..Inspection::default() };
+
+    if !identify(path, &bytes, &mut inspection)? {
+        return Err(SCypherError::file_at(
+            path,
+            "Not a recognized file: no age or OpenPGP container, SCypher backup QR or BIP39 phrase",
+        ));
+    }
+
+    if let Ok(metadata) = std::fs::metadata(Path::new(path)) {
+        if let Ok(created) = metadata.created() {
+            inspection.metadata.push(format!("file created {} (file system)", utc(created)));
+        }
+        if let Ok(modified) = metadata.modified() {
+            inspection.metadata.push(format!("file modified {} (file system)", utc(modified)));
+        }
+    }
+    Ok(inspection)
+}
+
+/// Rellenar `inspection` si reconoce `bytes`; `false` si no sabe qué son
+fn identify(path: &str, bytes: &[u8], inspection: &mut Inspection) -> Result<bool> {
+    #[cfg(feature = "armor")]
+    if let Some(header) = crate::armor::inspect(bytes) {
+        describe_container(&header?, inspection);
+        return Ok(true);
+    }
+
+    #[cfg(not(feature = "armor"))]
+    if crate::cli::gpg::is_encrypted_message(bytes) {
+        inspection.container = "OpenPGP encrypted message (binary)".to_string();
+        inspection.payload = "unknown: reading OpenPGP headers needs a build with the armor feature".to_string();
+        return Ok(true);
+    }
+
+    // Datos ocultos en una imagen: se inspecciona lo que lleva dentro
+    #[cfg(feature = "stego")]
+    if let Some(format) = crate::stego::ImageFormat::detect(bytes) {
+        let Ok(hidden) = crate::stego::extract(bytes) else {
+            return Ok(false);
+        };
+        let found = identify(path, &hidden, inspection)?;
+        inspection.container = format!("{} hidden in a {} image", inspection.container, format.name());
+        return Ok(found);
+    }
+
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return Ok(false);
+    };
+
+    if let Some(ur) = crate::cli::input::decode_ur_file(path, text)? {
+        match ur.ur_type() {
+            crate::ur::SCYPHER_BACKUP => {
+                let backup = ur.to_scypher_backup()
+                    .map_err(|e| SCypherError::file_caused_by(path, "Invalid backup QR", e))?;
+                inspection.container = format!("SCypher backup QR (ur:{})", crate::ur::SCYPHER_BACKUP);
+                inspection.version = Some(format!("envelope {}", crate::ur::BACKUP_VERSION));
+                describe_params(&backup.params, backup.keyfiles, inspection);
+                inspection.payload = format!(
+                    "SCypher-encrypted BIP39 phrase, {} words (the words are readable but useless without the password)",
+                    backup.phrase.split_whitespace().count(),
+                );
+                inspection.metadata.push("no creation date recorded in the backup".to_string());
+            }
+            crate::ur::BYTES => {
+                let inner = ur.to_bytes()?;
+                let found = identify(path, &inner, inspection)?;
+                inspection.container = format!("{} in animated QR parts (ur:bytes)", inspection.container);
+                return Ok(found);
+            }
+            other => {
+                inspection.container = format!("UR parts (ur:{})", other);
+                inspection.payload = "an unencrypted seed or data in the Blockchain Commons format, not a SCypher container".to_string();
+            }
+        }
+        return Ok(true);
+    }
+
+    // Frase suelta: no lleva cabecera que diga si está cifrada
+    let words = text.split_whitespace().count();
+    if (12..=24).contains(&words) {
+        if let Some(language) = crate::bip39::likely_language(text) {
+            inspection.container = format!("BIP39 phrase ({} words, {})", words, language.name());
+            inspection.payload = "a phrase in plain text: a SCypher-encrypted phrase and a wallet seed look the same, so the file cannot tell which one it is".to_string();
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Campos de un contenedor age u OpenPGP
+#[cfg(feature = "armor")]
+fn describe_container(header: &crate::armor::ContainerHeader, inspection: &mut Inspection) {
+    let name = match header.armor {
+        crate::armor::Armor::Age => "age",
+        crate::armor::Armor::Pgp => "OpenPGP",
+    };
+    inspection.container = format!("{} ({})", name, if header.armored { "ASCII armor" } else { "binary" });
+    inspection.version = Some(header.version.clone());
+    inspection.kdf = Some(header.kdf.clone());
+    inspection.parameters = Some(header.parameters.clone());
+    inspection.cipher = Some(header.cipher.clone());
+    inspection.salt = header.salt.as_deref().map(salt_fingerprint);
+
+    // gpg comprime por defecto: un contenido pequeño puede ser más grande al descomprimirlo
+    let compressed = match header.armor {
+        crate::armor::Armor::Pgp => ", unless gpg compressed it",
+        crate::armor::Armor::Age => "",
+    };
+    inspection.payload = match (header.payload_len, header.holds_recovery_document()) {
+        (Some(len), Some(true)) => format!(
+            "about {} bytes: a SCypher recovery document (an encrypted phrase and the parameters to decrypt it)", len),
+        (Some(len), _) => format!(
+            "about {} bytes: a phrase, seed or key on its own, without recorded parameters{}", len, compressed),
+        (None, _) => "size unknown without decrypting".to_string(),
+    };
+
+    for (key, value) in &header.armor_headers {
+        inspection.metadata.push(format!("armor header {}: {}", key, value));
+    }
+    inspection.metadata.push("no creation date recorded in the container".to_string());
+}
+
+/// Campos de los parámetros de SCypher
+fn describe_params(params: &ScypherParams, keyfiles: usize, inspection: &mut Inspection) {
+    inspection.kdf = Some(format!("{} (version {})", params.kdf().name(), params.argon2_version().name()));
+    let mut parameters = format!("{} iterations, {}KB memory, {} lanes", params.iterations(), params.memory_cost(), params.lanes());
+    if keyfiles > 0 {
+        parameters.push_str(&format!(", {} keyfile(s)", keyfiles));
+    }
+    inspection.parameters = Some(parameters);
+    inspection.cipher = Some("XOR of the BIP39 entropy with the derived keystream".to_string());
+    inspection.salt = Some(match params.salt() {
+        SaltSource::PasswordDerived => "derived from the password (none stored)".to_string(),
+        SaltSource::Explicit(salt) => salt_fingerprint(salt),
+    });
+}
+
+/// Huella corta de un salt: distingue copias sin mostrar el salt
+fn salt_fingerprint(salt: &[u8]) -> String {
+    format!("fingerprint {} ({} bytes)", hex::encode(&Sha256::digest(salt)[..4]), salt.len())
+}
+
+/// Fecha en UTC (`AAAA-MM-DD HH:MM UTC`)
+fn utc(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let (days, minutes) = (seconds / 86_400, seconds % 86_400 / 60);
+
+    // Días desde 1970-01-01 a fecha civil (algoritmo de H. Hinnant)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_utc_and_backup() {
+        assert_eq!(utc(UNIX_EPOCH), "1970-01-01 00:00 UTC");
+        assert_eq!(utc(UNIX_EPOCH + Duration::from_secs(951_782_400 + 3_660)), "2000-02-29 01:01 UTC");
+        assert_eq!(utc(UNIX_EPOCH + Duration::from_secs(1_792_108_800)), "2026-10-16 00:00 UTC");
+
+        let phrase = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+        let params = ScypherParams::builder().iterations(3).memory_cost(16384).build().unwrap();
+        let path = std::env::temp_dir().join(format!("scypher_inspect_{}.txt", std::process::id()));
+        let ur = crate::ur::Ur::scypher_backup(phrase, &params).unwrap();
+        std::fs::write(&path, ur.to_single_part()).unwrap();
+
+        let inspection = inspect_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(inspection.container.starts_with("SCypher backup QR"));
+        assert_eq!(inspection.parameters.as_deref(), Some("3 iterations, 16384KB memory, 1 lanes"));
+        assert_eq!(inspection.salt.as_deref(), Some("derived from the password (none stored)"));
+        assert!(inspection.payload.contains("12 words"));
+        assert!(!serde_json::to_string(&inspection).unwrap().contains("shock"));
+    }
+}
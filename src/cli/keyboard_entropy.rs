@@ -0,0 +1,101 @@
+// src/cli/keyboard_entropy.rs - Entropía de los tiempos entre pulsaciones
+//
+// Para quien desconfía del RNG del sistema: el usuario teclea al azar y se
+// guardan el intervalo en nanosegundos desde la pulsación anterior y la
+// tecla. El jitter de los últimos bits del intervalo no es reproducible;
+// se mezcla con el RNG del sistema en
+// `bip39::conversion::generate_seed_phrase_mixed`, nunca se usa solo. El
+// terminal se pone en modo sin eco y sin búfer de línea mientras dura la
+// captura y se restaura al terminar.
+
+use std::io::{self, Read, Write};
+use std::time::Instant;
+
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Result};
+
+/// Pulsaciones que se piden por defecto
+pub const DEFAULT_KEYSTROKES: usize = 100;
+
+/// Bytes guardados por pulsación: intervalo (u64) y tecla
+const SAMPLE_LEN: usize = 9;
+
+/// Terminal sin eco ni modo canónico; se restaura al soltarlo
+#[cfg(unix)]
+struct RawMode(libc::termios);
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> Result<Self> {
+        // SAFETY: tcgetattr/tcsetattr sobre stdin con un termios local
+        unsafe {
+            if libc::isatty(libc::STDIN_FILENO) != 1 {
+                return Err(SCypherError::InvalidRequest("keyboard entropy needs an interactive terminal".to_string()));
+            }
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            Ok(Self(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: restaura la configuración leída en `enable`
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0) };
+    }
+}
+
+/// Pedir `keystrokes` pulsaciones y devolver sus tiempos para mezclarlos
+pub fn collect_keystroke_timings(keystrokes: usize) -> Result<Zeroizing<Vec<u8>>> {
+    #[cfg(not(unix))]
+    {
+        let _ = keystrokes;
+        Err(SCypherError::UnsupportedPlatform)
+    }
+
+    #[cfg(unix)]
+    {
+        println!("Type random keys until the counter reaches {} (nothing is shown).", keystrokes);
+        println!("Vary the rhythm; the timing between keys is mixed with the system RNG.\n");
+
+        let _raw = RawMode::enable()?;
+        let mut samples = Zeroizing::new(Vec::with_capacity(keystrokes * SAMPLE_LEN));
+        let mut stdin = io::stdin().lock();
+        let mut previous = Instant::now();
+        let mut key = [0u8; 1];
+
+        for count in 1..=keystrokes {
+            stdin.read_exact(&mut key).map_err(SCypherError::from)?;
+            let now = Instant::now();
+            let interval = u64::try_from(now.duration_since(previous).as_nanos()).unwrap_or(u64::MAX);
+            previous = now;
+
+            samples.extend_from_slice(&interval.to_be_bytes());
+            samples.push(key[0]);
+            key[0] = 0;
+
+            if crate::cli::display::is_plain_terminal() {
+                if count % 10 == 0 || count == keystrokes {
+                    println!("Keystrokes: {}/{}", count, keystrokes);
+                }
+            } else {
+                print!("\rKeystrokes: {}/{}", count, keystrokes);
+                io::stdout().flush().map_err(SCypherError::from)?;
+            }
+        }
+        println!("\n");
+        Ok(samples)
+    }
+}
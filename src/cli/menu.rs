@@ -38,21 +38,11 @@ pub enum PostSaveChoice {
 }
 
 /// Estado del sistema de menús para controlar flujo
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct MenuState {
     pub should_exit: bool,
     pub return_to_main: bool,
-    pub processed_result: Option<String>,
-}
-
-impl Default for MenuState {
-    fn default() -> Self {
-        Self {
-            should_exit: false,
-            return_to_main: false,
-            processed_result: None,
-        }
-    }
+    pub processed_result: Option<zeroize::Zeroizing<String>>,
 }
 
 /// Mostrar y manejar el menú principal
@@ -67,6 +57,9 @@ pub fn show_main_menu() -> Result<MainMenuChoice> {
         println!("2. Help/License/Details");
         println!("3. Exit");
         println!();
+        if crate::cli::panic_wipe::is_armed() {
+            println!("{}Ctrl-X twice at any prompt: wipe the screen and exit{}\n", colors::DIM, colors::RESET);
+        }
 
         let choice = display::read_user_input("Select option [1-3]: ");
         println!();
@@ -140,7 +133,7 @@ pub fn handle_help_submenu() -> Result<bool> {
 }
 
 /// Mostrar menú post-procesamiento después de una operación exitosa
-pub fn show_post_processing_menu(result: &str) -> Result<PostProcessChoice> {
+pub fn show_post_processing_menu(_result: &str) -> Result<PostProcessChoice> {
     loop {
         println!();
         println!("{}What would you like to do next?{}", colors::SUCCESS, colors::RESET);
@@ -167,6 +160,13 @@ pub fn show_post_processing_menu(result: &str) -> Result<PostProcessChoice> {
 
 /// Manejar guardado de resultado en archivo
 pub fn handle_save_result(result: &str) -> Result<bool> {
+    // Sin escrituras implícitas en un sistema en vivo
+    if crate::security::persistence::is_ram_only() {
+        println!("{}RAM-only mode: saving to a file is disabled (use -o FILE on the command line){}",
+                 colors::WARNING, colors::RESET);
+        return handle_post_save_menu();
+    }
+
     loop {
         println!("{}Enter filename to save result:{}", colors::PRIMARY, colors::RESET);
         let save_file = display::read_user_input("> ");
@@ -187,6 +187,18 @@ pub fn handle_save_result(result: &str) -> Result<bool> {
             format!("{}.txt", save_file)
         };
 
+        // Mostrar dónde acabará el archivo; si no se confirma, pedir otro nombre
+        match crate::cli::output::confirm_destination(&save_file) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                println!("{}Error: {}{}", colors::ERROR, e, colors::RESET);
+                println!();
+                display::wait_for_enter();
+                continue;
+            }
+        }
+
         // Intentar guardar el archivo usando la función del módulo output
         match crate::cli::output::save_to_file(result, &save_file) {
             Ok(()) => {
@@ -241,25 +253,21 @@ pub fn handle_post_save_menu() -> Result<bool> {
 
 /// Manejar el menú post-procesamiento completo
 pub fn handle_post_processing_menu(result: &str) -> Result<bool> {
-    loop {
-        match show_post_processing_menu(result)? {
-            PostProcessChoice::SaveToFile => {
-                if handle_save_result(result)? {
-                    return Ok(true); // Usuario eligió salir después de guardar
-                }
-                // Si no salió, volver al menú principal
-                return Ok(false);
-            }
-            PostProcessChoice::ReturnToMain => {
-                display::clear_screen();
-                return Ok(false); // Volver al menú principal
-            }
-            PostProcessChoice::Exit => {
-                println!("{}Exiting...{}", colors::DIM, colors::RESET);
-                std::thread::sleep(std::time::Duration::from_millis(1000));
-                display::clear_screen();
-                return Ok(true); // Salir
-            }
+    match show_post_processing_menu(result)? {
+        PostProcessChoice::SaveToFile => {
+            // true si el usuario eligió salir después de guardar,
+            // false para volver al menú principal
+            handle_save_result(result)
+        }
+        PostProcessChoice::ReturnToMain => {
+            display::clear_screen();
+            Ok(false) // Volver al menú principal
+        }
+        PostProcessChoice::Exit => {
+            println!("{}Exiting...{}", colors::DIM, colors::RESET);
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+            display::clear_screen();
+            Ok(true) // Salir
         }
     }
 }
@@ -0,0 +1,129 @@
+// src/cli/metrics.rs - Métricas de rendimiento locales (--metrics)
+//
+// Desactivadas por defecto. Con `--metrics FILE` cada ejecución añade una
+// línea JSON al archivo: parámetros de Argon2, duración de la derivación,
+// memoria y ritmo del lote. Sirve para ajustar parámetros en varios equipos
+// aislados y juntar después los archivos. No se registran frases,
+// contraseñas, salts, etiquetas ni rutas, y nada sale del archivo local.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::crypto::ScypherParams;
+use crate::error::{IoOperation, SCypherError, Result};
+
+/// Versión del formato de cada línea
+pub const METRICS_VERSION: u32 = 1;
+
+/// Métricas de una ejecución
+#[derive(Debug, Clone, Serialize)]
+pub struct RunMetrics {
+    pub version: u32,
+    /// Segundos desde la época Unix
+    pub timestamp: u64,
+    /// `transform` o `batch`
+    pub operation: &'static str,
+    pub kdf: &'static str,
+    pub iterations: u32,
+    pub memory_kib: u32,
+    pub lanes: u32,
+    /// Frases procesadas
+    pub items: usize,
+    /// Derivaciones Argon2 realizadas (1 con `--derive-once`)
+    pub derivations: usize,
+    /// Derivaciones simultáneas como máximo
+    pub concurrency: usize,
+    /// Tiempo de derivación y transformación, sin contar las preguntas al usuario
+    pub duration_ms: u64,
+    pub items_per_second: f64,
+    /// Memoria de Argon2 reservada a la vez (`memory_kib` × `concurrency`)
+    pub argon2_memory_kib: u64,
+    /// Pico de memoria residente del proceso, si la plataforma lo indica
+    pub peak_rss_kib: Option<u64>,
+}
+
+impl RunMetrics {
+    /// Métricas de `items` frases transformadas en `duration`
+    pub fn new(operation: &'static str, params: &ScypherParams, items: usize, duration: Duration) -> Self {
+        let seconds = duration.as_secs_f64();
+        Self {
+            version: METRICS_VERSION,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()),
+            operation,
+            kdf: params.kdf().name(),
+            iterations: params.iterations(),
+            memory_kib: params.memory_cost(),
+            lanes: params.lanes(),
+            items,
+            derivations: items,
+            concurrency: 1,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            items_per_second: if seconds > 0.0 { items as f64 / seconds } else { 0.0 },
+            argon2_memory_kib: u64::from(params.memory_cost()),
+            peak_rss_kib: crate::security::memory::peak_resident_memory_kb(),
+        }
+    }
+
+    /// Con `derivations` derivaciones de hasta `concurrency` en paralelo
+    pub fn with_derivations(mut self, derivations: usize, concurrency: usize) -> Self {
+        self.derivations = derivations;
+        self.concurrency = concurrency;
+        self.argon2_memory_kib = u64::from(self.memory_kib) * concurrency as u64;
+        self
+    }
+
+    /// Añadir una línea JSON a `path` (se crea si no existe)
+    pub fn append_to(&self, path: &str) -> Result<()> {
+        let mut line = serde_json::to_string(self)
+            .map_err(|e| SCypherError::file_caused_by(path, "Cannot serialize metrics", e))?;
+        line.push('\n');
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| SCypherError::io_at(IoOperation::Write, path, e))
+    }
+}
+
+/// Guardar `metrics` si se pidió `--metrics`; un fallo solo se avisa
+pub fn record(path: Option<&String>, metrics: impl FnOnce() -> RunMetrics) {
+    if let Some(path) = path {
+        if let Err(e) = metrics().append_to(path) {
+            eprintln!("Warning: metrics not recorded: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_lines() {
+        let params = ScypherParams::builder().iterations(2).memory_cost(8192).lanes(2).build().unwrap();
+        let path = std::env::temp_dir().join(format!("scypher-metrics-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        RunMetrics::new("transform", &params, 1, Duration::from_millis(500)).append_to(path).unwrap();
+        RunMetrics::new("batch", &params, 10, Duration::from_secs(2))
+            .with_derivations(10, 4)
+            .append_to(path)
+            .unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let lines: Vec<serde_json::Value> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["operation"], "transform");
+        assert_eq!(lines[0]["duration_ms"], 500);
+        assert_eq!(lines[0]["iterations"], 2);
+        assert_eq!(lines[1]["items_per_second"], 5.0);
+        assert_eq!(lines[1]["argon2_memory_kib"], 8192 * 4);
+    }
+}
@@ -3,7 +3,42 @@
 pub mod input;
 pub mod output;
 pub mod display;
+pub mod events;
+pub mod serve;
+pub mod batch;
+pub mod checkpoint;
+pub mod watch;
+pub mod file;
+pub mod bench;
+pub mod gpg;
+pub mod password_file;
+pub mod pepper;
+pub mod preset;
+pub mod secure_input;
+pub mod rotate;
+pub mod inspect;
+pub mod backoff;
+pub mod metrics;
+#[cfg(feature = "interactive")]
 pub mod menu;
+#[cfg(feature = "interactive")]
+pub mod shell;
+#[cfg(feature = "interactive")]
+pub mod panic_wipe;
+#[cfg(feature = "interactive")]
+pub mod preview;
+#[cfg(feature = "interactive")]
+pub mod keyboard_entropy;
+#[cfg(feature = "wallet")]
+pub mod export;
+#[cfg(feature = "hardware")]
+pub mod hwi;
+#[cfg(feature = "wallet")]
+pub mod verify;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
 
 // Re-exportar funciones principales para fácil acceso
 pub use input::{
@@ -14,9 +49,14 @@ pub use input::{
 
 pub use output::{
     output_result,
+    output_result_split,
     save_to_file,
+    Output,
+    OutputRouter,
+    Sink,
 };
 
+#[cfg(feature = "interactive")]
 pub use menu::{
     run_interactive_menu,
     handle_post_processing_menu,
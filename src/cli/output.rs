@@ -1,52 +1,366 @@
 // src/cli/output.rs - Manejo de salida y archivos
 
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::Path;
-use crate::error::{SCypherError, Result};
+use crate::error::{IoOperation, SCypherError, Result};
 use crate::cli::input::read_confirmation;
 
 const DEFAULT_EXTENSION: &str = ".txt";
 const FILE_PERMISSIONS: u32 = 0o600; // Solo lectura/escritura para el propietario
 
+/// Destino del resultado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sink {
+    /// En pantalla; con `split`, en dos mitades que se borran (`--split-display`)
+    Stdout { split: bool },
+    /// Archivo de texto (`-o`), con extensión .txt
+    File(String),
+    /// QR en PNG (`--qr-out`)
+    #[cfg(feature = "qr")]
+    QrImage(String),
+    /// QR repartido en `total` PNG, todos necesarios (`--qr-out` con `--qr-split`)
+    #[cfg(feature = "qr")]
+    QrShares { path: String, total: usize },
+    /// Portapapeles (`--clipboard`), que se borra pasado un tiempo
+    #[cfg(feature = "clipboard")]
+    Clipboard,
+    /// Oculto en una copia de la imagen `carrier` (`--stego-carrier`, `--stego-out`)
+    #[cfg(feature = "stego")]
+    Stego { carrier: String, path: String },
+}
+
+impl Sink {
+    /// Si el destino deja el resultado fuera de la pantalla
+    fn persists(&self) -> bool {
+        !matches!(self, Sink::Stdout { .. })
+    }
+
+    /// Archivos que escribe el destino
+    fn file_paths(&self) -> Vec<String> {
+        match self {
+            Sink::File(path) => vec![ensure_extension(path)],
+            #[cfg(feature = "qr")]
+            Sink::QrImage(path) => vec![path.clone()],
+            #[cfg(feature = "qr")]
+            Sink::QrShares { path, total } => (1..=*total).map(|index| share_path(path, index, *total)).collect(),
+            #[cfg(feature = "stego")]
+            Sink::Stego { path, .. } => vec![path.clone()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Resultado a entregar: el texto y, si es distinto, el UR que va en el QR
+pub struct Output<'a> {
+    text: &'a str,
+    #[cfg(feature = "qr")]
+    ur: Option<&'a crate::ur::Ur>,
+}
+
+impl<'a> Output<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            #[cfg(feature = "qr")]
+            ur: None,
+        }
+    }
+
+    /// Codificar `ur` en el QR en lugar del texto (animado si no cabe en una parte)
+    #[cfg(feature = "qr")]
+    pub fn with_ur(mut self, ur: &'a crate::ur::Ur) -> Self {
+        self.ur = Some(ur);
+        self
+    }
+}
+
+/// Reparto del resultado entre varios destinos en una misma ejecución
+///
+/// Cada destino aplica su formato (texto, PNG) y sus avisos una sola vez:
+/// permisos 0600 en los archivos, copias que pueden hacer visores y
+/// gestores de portapapeles, y la pregunta de guardar solo cuando la
+/// pantalla es el único destino.
+#[derive(Debug, Clone)]
+pub struct OutputRouter {
+    sinks: Vec<Sink>,
+}
+
+impl OutputRouter {
+    /// Destinos en el orden en que se entregan; al menos uno
+    pub fn new(sinks: Vec<Sink>) -> Result<Self> {
+        if sinks.is_empty() {
+            return Err(SCypherError::InvalidRequest("no output destination (drop --no-stdout or add -o, --qr-out, --clipboard or --stego-out)".to_string()));
+        }
+        if sinks.iter().filter(|sink| matches!(sink, Sink::Stdout { .. })).count() > 1 {
+            return Err(SCypherError::InvalidRequest("the screen can only be used once as output".to_string()));
+        }
+        // `--require-removable` se comprueba antes de pedir la frase
+        for path in sinks.iter().flat_map(Sink::file_paths) {
+            crate::security::storage::check_destination(Path::new(&path))?;
+        }
+        Ok(Self { sinks })
+    }
+
+    /// Pantalla y, si se indica, un archivo (la salida de siempre)
+    pub fn screen(split: bool, output_file: Option<&String>) -> Self {
+        let mut sinks = vec![Sink::Stdout { split }];
+        sinks.extend(output_file.cloned().map(Sink::File));
+        Self { sinks }
+    }
+
+    pub fn sinks(&self) -> &[Sink] {
+        &self.sinks
+    }
+
+    /// Si algún destino es un QR (hace falta `Output::with_ur` para usar un UR)
+    #[cfg(feature = "qr")]
+    pub fn needs_qr(&self) -> bool {
+        self.sinks.iter().any(|sink| matches!(sink, Sink::QrImage(_) | Sink::QrShares { .. }))
+    }
+
+    /// Entregar `output` a todos los destinos
+    ///
+    /// Con el portapapeles entre ellos, `Delivery::finish` espera el plazo
+    /// y lo borra; si se suelta antes (error o salida), se borra en el acto.
+    pub fn deliver(&self, output: &Output) -> Result<Delivery> {
+        #[cfg_attr(not(feature = "clipboard"), allow(unused_mut))]
+        let mut delivery = Delivery::default();
+
+        // Todos los archivos se confirman antes de escribir el primero
+        for path in self.sinks.iter().flat_map(Sink::file_paths) {
+            if !confirm_destination(&path)? {
+                return Err(SCypherError::Cancelled);
+            }
+        }
+
+        for sink in &self.sinks {
+            match sink {
+                Sink::Stdout { split: false } => show_result(output.text),
+                Sink::Stdout { split: true } => show_result_split(output.text)?,
+                Sink::File(path) => {
+                    let final_path = ensure_extension(path);
+                    save_to_file(output.text, &final_path)?;
+                    println!("\n✓ Result saved to: {}", final_path);
+                }
+                #[cfg(feature = "qr")]
+                Sink::QrImage(path) => {
+                    let (image, parts) = match output.ur {
+                        Some(ur) => crate::cli::qr::ur_png(ur)?,
+                        None => (crate::cli::qr::png(output.text)?, 1),
+                    };
+                    save_bytes_to_file(&image, path)?;
+                    if parts > 1 {
+                        println!("\n✓ Animated QR code ({} parts) saved to: {}", parts, path);
+                        println!("   Play it in a viewer with APNG support and scan until the wallet has every part.");
+                    } else {
+                        println!("\n✓ QR code saved to: {}", path);
+                    }
+                    println!("⚠️  The image holds the result in the clear; photo libraries, thumbnails and sync tools may keep copies.");
+                }
+                #[cfg(feature = "qr")]
+                Sink::QrShares { path, total } => {
+                    let text_ur;
+                    let ur = match output.ur {
+                        Some(ur) => ur,
+                        None => {
+                            text_ur = crate::ur::Ur::bytes(output.text.as_bytes());
+                            &text_ur
+                        }
+                    };
+                    println!();
+                    for (index, share) in ur.split(*total)?.iter().enumerate() {
+                        let share_path = share_path(path, index + 1, *total);
+                        let (image, parts) = crate::cli::qr::ur_png(share)?;
+                        save_bytes_to_file(&image, &share_path)?;
+                        let animated = if parts > 1 { format!(" (animated, {} parts)", parts) } else { String::new() };
+                        println!("✓ QR share {} of {}{} saved to: {}", index + 1, total, animated, share_path);
+                    }
+                    println!("⚠️  Every share is needed to rebuild the result: store them in different places, and losing one loses this copy.");
+                    println!("   To read them back, scan all of them into one text file (a blank line between animated shares) and use -f.");
+                }
+                #[cfg(feature = "clipboard")]
+                Sink::Clipboard => {
+                    delivery.clipboard = Some(crate::cli::clipboard::ClipboardGuard::copy(output.text)?);
+                    println!("\n✓ Result copied to the clipboard");
+                    println!("⚠️  Clipboard managers and remote desktop sessions may keep a copy.");
+                }
+                #[cfg(feature = "stego")]
+                Sink::Stego { carrier, path } => {
+                    let carrier = read_carrier(carrier)?;
+                    save_bytes_to_file(&carrier.embed(output.text.as_bytes())?, path)?;
+                    println!("\n✓ Result hidden in {} image: {}", carrier.format().name(), path);
+                    println!("⚠️  Do not keep the original image next to it: comparing both reveals the change. Converting, resizing or re-compressing the image destroys the hidden data.");
+                }
+            }
+        }
+
+        // Sin otro destino, ofrecer guardarlo como siempre
+        if !self.sinks.iter().any(Sink::persists) {
+            offer_to_save(output.text)?;
+        }
+        Ok(delivery)
+    }
+}
+
+/// Lo que queda pendiente tras entregar el resultado
+#[derive(Default)]
+#[must_use = "call finish() so the clipboard stays filled for its grace period"]
+pub struct Delivery {
+    #[cfg(feature = "clipboard")]
+    clipboard: Option<crate::cli::clipboard::ClipboardGuard>,
+}
+
+impl Delivery {
+    /// Esperar a que se use el portapapeles y borrarlo (nada que hacer sin él)
+    pub fn finish(self) {
+        #[cfg(feature = "clipboard")]
+        if let Some(clipboard) = self.clipboard {
+            clipboard.wait_and_clear();
+        }
+    }
+}
+
+/// Archivo de la parte `index` de `total` de un QR repartido: `qr.png` da `qr-1of3.png`
+#[cfg(feature = "qr")]
+fn share_path(path: &str, index: usize, total: usize) -> String {
+    let suffix = format!("-{}of{}", index, total);
+    let file = Path::new(path);
+    match (file.file_stem(), file.extension()) {
+        (Some(stem), Some(extension)) => file
+            .with_file_name(format!("{}{}.{}", stem.to_string_lossy(), suffix, extension.to_string_lossy()))
+            .to_string_lossy()
+            .into_owned(),
+        _ => format!("{}{}", path, suffix),
+    }
+}
+
+/// Leer y comprobar una imagen portadora de `--stego-carrier`
+#[cfg(feature = "stego")]
+pub fn read_carrier(path: &str) -> Result<crate::stego::Carrier> {
+    let bytes = fs::read(path).map_err(|e| SCypherError::io_at(IoOperation::Read, path, e))?;
+    crate::stego::Carrier::parse(&bytes).map_err(|e| SCypherError::file_caused_by(path, "Unusable carrier image", e))
+}
+
 /// Mostrar resultado y opcionalmente guardarlo en archivo
 pub fn output_result(result: &str, output_file: Option<&String>) -> Result<()> {
-    // Siempre mostrar el resultado en pantalla
+    OutputRouter::screen(false, output_file).deliver(&Output::new(result))?.finish();
+    Ok(())
+}
+
+/// Mostrar la frase en dos mitades, sin tenerla nunca entera en pantalla
+///
+/// Cada mitad se borra (también del scrollback) cuando el usuario confirma
+/// que la ha copiado. El guardado en archivo funciona igual que en
+/// `output_result`.
+pub fn output_result_split(result: &str, output_file: Option<&String>) -> Result<()> {
+    OutputRouter::screen(true, output_file).deliver(&Output::new(result))?.finish();
+    Ok(())
+}
+
+fn show_result(result: &str) {
     println!("Result:");
-    println!("─────────────────────────────────────────────────────────────");
+    println!("{}", format::separator_line(61));
     println!("{}", result);
-    println!("─────────────────────────────────────────────────────────────");
+    println!("{}", format::separator_line(61));
+}
 
-    // Guardar en archivo si se especificó
-    if let Some(file_path) = output_file {
-        let final_path = ensure_extension(file_path);
-        save_to_file(result, &final_path)?;
-        println!("\n✓ Result saved to: {}", final_path);
-    } else {
-        // Preguntar si quiere guardar en archivo
-        if read_confirmation("\nDo you want to save the result to a file?")? {
-            print!("Enter filename (without extension): ");
-            io::stdout().flush().map_err(SCypherError::from)?;
-
-            let mut filename = String::new();
-            io::stdin().read_line(&mut filename).map_err(SCypherError::from)?;
-            let filename = filename.trim();
-
-            if !filename.is_empty() {
-                let file_path = ensure_extension(filename);
-                save_to_file(result, &file_path)?;
-                println!("✓ Result saved to: {}", file_path);
+fn show_result_split(result: &str) -> Result<()> {
+    let words: Vec<&str> = result.split_whitespace().collect();
+    let half = words.len().div_ceil(2);
+
+    for (part, chunk) in words.chunks(half.max(1)).enumerate() {
+        let first = part * half + 1;
+        println!("Result, words {}-{} of {}:", first, first + chunk.len() - 1, words.len());
+        println!("{}", format::separator_line(61));
+        for (offset, word) in chunk.iter().enumerate() {
+            println!("{:>2}. {}", first + offset, word);
+        }
+        println!("{}", format::separator_line(61));
+
+        print!("Press Enter when you have copied these words...");
+        io::stdout().flush().map_err(SCypherError::from)?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).map_err(SCypherError::from)?;
+
+        crate::cli::display::clear_screen_and_scrollback();
+    }
+    Ok(())
+}
+
+/// Preguntar si se quiere guardar el resultado (nunca en modo solo RAM)
+fn offer_to_save(result: &str) -> Result<()> {
+    if crate::security::persistence::is_ram_only() {
+        println!("\nRAM-only mode: the result was not saved (use -o FILE to write it).");
+        return Ok(());
+    }
+
+    if read_confirmation("\nDo you want to save the result to a file?")? {
+        print!("Enter filename (without extension): ");
+        io::stdout().flush().map_err(SCypherError::from)?;
+
+        let mut filename = String::new();
+        io::stdin().read_line(&mut filename).map_err(SCypherError::from)?;
+        let filename = filename.trim();
+
+        if !filename.is_empty() {
+            let file_path = ensure_extension(filename);
+            if !confirm_destination(&file_path)? {
+                println!("The result was not saved.");
+                return Ok(());
             }
+            save_to_file(result, &file_path)?;
+            println!("✓ Result saved to: {}", file_path);
         }
     }
 
     Ok(())
 }
 
+/// Decir dónde acabará físicamente `file_path` y, en un terminal, confirmarlo
+///
+/// Falla si `--require-removable` no permite el destino. Sin terminal
+/// (scripts, tuberías) solo se informa.
+pub fn confirm_destination(file_path: &str) -> Result<bool> {
+    let location = crate::security::storage::check_destination(Path::new(file_path))?;
+    println!("\n{} will be written to {}", file_path, location);
+    if !io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    read_confirmation("Write it there?")
+}
+
+/// Envolver `document` en el contenedor `armor` con una passphrase nueva
+#[cfg(feature = "armor")]
+pub fn armor_result(armor: crate::armor::Armor, document: &str) -> Result<String> {
+    println!("\n{} container", armor.name());
+    println!("Choose a passphrase for the container (needed to open it, separate from the SCypher password).\n");
+    let passphrase = crate::cli::input::read_new_password("container passphrase")?;
+
+    armor.seal(document, &passphrase)
+}
+
 /// Guardar contenido en archivo con permisos seguros
 pub fn save_to_file(content: &str, file_path: &str) -> Result<()> {
-    use std::path::Path;
+    save_bytes_to_file(content.as_bytes(), file_path)
+}
+
+/// Guardar datos binarios (p.ej. un PNG) con los mismos permisos y cuidados que `save_to_file`
+#[tracing::instrument(name = "output", level = "debug", skip(content), fields(bytes = content.len()))]
+pub fn save_bytes_to_file(content: &[u8], file_path: &str) -> Result<()> {
+    write_file(content, file_path, false)
+}
 
+/// Como `save_to_file`, pero sin sobrescribir nunca un archivo existente
+///
+/// La creación es exclusiva (`create_new`): si otro proceso crea el archivo
+/// entre la comprobación y la escritura, falla en lugar de pisarlo.
+pub fn save_to_new_file(content: &str, file_path: &str) -> Result<()> {
+    write_file(content.as_bytes(), file_path, true)
+}
+
+/// Comprobar el destino y abrir el archivo de salida para escribir
+fn open_output(file_path: &str, create_new: bool) -> Result<fs::File> {
     if file_path.is_empty() {
         return Err(SCypherError::file("File path is empty".to_string()));
     }
@@ -61,33 +375,70 @@ pub fn save_to_file(content: &str, file_path: &str) -> Result<()> {
 
     // Verificar que el directorio padre existe
     if !parent_dir.exists() {
-        return Err(SCypherError::file(
-            format!("Directory '{}' does not exist", parent_dir.display())
+        return Err(SCypherError::file_at(
+            parent_dir.display().to_string(), "Directory does not exist"
         ));
     }
 
     if !parent_dir.is_dir() {
-        return Err(SCypherError::file(
-            format!("'{}' is not a directory", parent_dir.display())
+        return Err(SCypherError::file_at(
+            parent_dir.display().to_string(), "Not a directory"
         ));
     }
 
-    // Escribir archivo
-    fs::write(file_path, content)
-        .map_err(|e| SCypherError::file(format!("Cannot write to '{}': {}", file_path, e)))?;
+    // Política --require-removable, para cualquier archivo que se escriba
+    crate::security::storage::check_destination(path)?;
+
+    // Permisos 0600 desde la creación
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    if create_new {
+        options.create_new(true);
+    } else {
+        options.create(true).truncate(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(FILE_PERMISSIONS);
+    }
+    options.open(file_path).map_err(|e| match e.kind() {
+        io::ErrorKind::AlreadyExists if create_new => SCypherError::file_at(file_path, "Output file already exists"),
+        _ => SCypherError::io_at(IoOperation::Write, file_path, e),
+    })
+}
+
+/// Crear un archivo de salida nuevo (nunca sobrescribe) con permisos 0600
+///
+/// Para quien escribe por partes (`scypher file`): se aplican las mismas
+/// comprobaciones que en `save_to_file`, y el archivo se crea de forma
+/// exclusiva. Quien llama debe proteger la escritura con `PendingWrite`.
+pub fn create_new_file(file_path: &str) -> Result<fs::File> {
+    open_output(file_path, true)
+}
+
+fn write_file(content: &[u8], file_path: &str, create_new: bool) -> Result<()> {
+    // Si algo falla (o Ctrl-C) una vez creado, lo escrito se sobrescribe y se borra
+    let mut file = open_output(file_path, create_new)?;
+    let pending = crate::security::persistence::PendingWrite::begin(file_path);
+    file.write_all(content)
+        .and_then(|()| file.sync_all())
+        .map_err(|e| SCypherError::io_at(IoOperation::Write, file_path, e))?;
+    drop(file);
 
     // Establecer permisos seguros (solo en sistemas Unix)
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
         let mut perms = fs::metadata(file_path)
-            .map_err(|e| SCypherError::file(format!("Cannot read file metadata: {}", e)))?
+            .map_err(|e| SCypherError::io_at(IoOperation::Metadata, file_path, e))?
             .permissions();
         perms.set_mode(FILE_PERMISSIONS);
         fs::set_permissions(file_path, perms)
-            .map_err(|e| SCypherError::file(format!("Cannot set file permissions: {}", e)))?;
+            .map_err(|e| SCypherError::io_at(IoOperation::SetPermissions, file_path, e))?;
     }
 
+    pending.commit();
     Ok(())
 }
 
@@ -106,8 +457,8 @@ pub fn validate_output_path(file_path: &str) -> Result<()> {
 
     // Verificar que no sea un directorio
     if path.is_dir() {
-        return Err(SCypherError::file(
-            format!("'{}' is a directory, not a file", file_path)
+        return Err(SCypherError::file_at(
+            file_path, "Is a directory, not a file"
         ));
     }
 
@@ -139,7 +490,7 @@ pub fn show_file_info(file_path: &str) -> Result<()> {
 
     if path.exists() {
         let metadata = fs::metadata(path)
-            .map_err(|e| SCypherError::file(format!("Cannot read file metadata: {}", e)))?;
+            .map_err(|e| SCypherError::io_at(IoOperation::Metadata, file_path, e))?;
 
         println!("• Status: File exists (will be overwritten)");
         println!("• Size: {} bytes", metadata.len());
@@ -158,9 +509,13 @@ pub fn show_file_info(file_path: &str) -> Result<()> {
 
 /// Utilidades para formateo de salida
 pub mod format {
-    /// Crear una línea separadora
+    /// Crear una línea separadora (ASCII en modo de terminal simple)
     pub fn separator_line(length: usize) -> String {
-        "─".repeat(length)
+        if crate::cli::display::is_plain_terminal() {
+            "-".repeat(length)
+        } else {
+            "─".repeat(length)
+        }
     }
 
     /// Formatear texto en columnas
@@ -221,6 +576,14 @@ mod tests {
         assert_eq!(ensure_extension("path/test"), "path/test.txt");
     }
 
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_share_path() {
+        assert_eq!(share_path("backup.png", 1, 3), "backup-1of3.png");
+        assert_eq!(share_path("dir/backup.png", 3, 3), "dir/backup-3of3.png");
+        assert_eq!(share_path("backup", 2, 2), "backup-2of2");
+    }
+
     #[test]
     fn test_validate_output_path() {
         // Casos válidos
@@ -250,6 +613,24 @@ mod tests {
         assert!(formatted.contains("4. about"));
     }
 
+    #[test]
+    fn test_output_router() {
+        assert!(OutputRouter::new(Vec::new()).is_err());
+        assert!(OutputRouter::new(vec![Sink::Stdout { split: false }, Sink::Stdout { split: true }]).is_err());
+
+        let path = env::temp_dir().join(format!("scypher_router_{}", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let router = OutputRouter::new(vec![Sink::File(path.clone())]).unwrap();
+        router.deliver(&Output::new("abandon about")).unwrap().finish();
+
+        let saved = format!("{}.txt", path);
+        assert_eq!(fs::read_to_string(&saved).unwrap(), "abandon about");
+        let _ = fs::remove_file(&saved);
+
+        let router = OutputRouter::screen(false, Some(&path));
+        assert_eq!(router.sinks(), [Sink::Stdout { split: false }, Sink::File(path)]);
+    }
+
     #[test]
     fn test_save_to_file() {
         let temp_dir = env::temp_dir();
@@ -267,6 +648,11 @@ mod tests {
         let saved_content = fs::read_to_string(&test_file).unwrap();
         assert_eq!(saved_content, test_content);
 
+        // Sin sobrescribir: falla y deja el archivo como estaba
+        let error = save_to_new_file("other content", test_file.to_str().unwrap()).unwrap_err();
+        assert!(error.to_string().contains("already exists"));
+        assert_eq!(fs::read_to_string(&test_file).unwrap(), test_content);
+
         // Limpiar
         let _ = fs::remove_file(&test_file);
     }
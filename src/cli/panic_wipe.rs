@@ -0,0 +1,179 @@
+// src/cli/panic_wipe.rs - Tecla de pánico en los modos interactivos
+//
+// Ctrl-X pulsado dos veces seguidas borra la pantalla y el historial del
+// terminal y termina el proceso al instante, aunque el programa esté
+// esperando una línea o una contraseña. Para que funcione sin pulsar Enter
+// se asigna Ctrl-X como carácter QUIT del terminal: el driver envía SIGQUIT
+// y el manejador hace todo con llamadas seguras en una señal (write,
+// tcsetattr, _exit). Al terminar el proceso, el kernel recupera sus páginas
+// (bloqueadas en RAM, ver `security::memory`) y las limpia antes de
+// reutilizarlas. La configuración original del terminal se restaura al
+// salir, también en la salida normal.
+//
+// Mientras se teclea una contraseña o una frase el terminal está en modo
+// raw, sin señales: ahí el editor de `cli::secure_input` detecta la doble
+// pulsación y llama a `key_pressed`, que hace lo mismo que el manejador.
+
+/// Carácter de control de Ctrl-X
+#[cfg(unix)]
+const CTRL_X: u8 = 0x18;
+
+/// Tiempo máximo entre las dos pulsaciones
+#[cfg_attr(not(unix), allow(dead_code))]
+const DOUBLE_PRESS_MS: u64 = crate::cli::secure_input::PANIC_DOUBLE_PRESS.as_millis() as u64;
+
+/// Código de salida (128 + SIGQUIT)
+#[cfg(unix)]
+const EXIT_CODE: i32 = 131;
+
+/// Mover al inicio, borrar la pantalla y el scrollback, y reiniciar el terminal
+#[cfg(unix)]
+const CLEAR_ALL: &[u8] = b"\x1b[H\x1b[2J\x1b[3J\x1bc";
+
+#[cfg(unix)]
+const HINT: &[u8] = b"\r\n(press Ctrl-X again to wipe the screen and exit)\r\n";
+
+/// Si la pulsación en `now` es la segunda de una doble pulsación
+#[cfg_attr(not(unix), allow(dead_code))]
+fn is_second_press(previous_ms: u64, now_ms: u64) -> bool {
+    previous_ms != 0 && now_ms.saturating_sub(previous_ms) <= DOUBLE_PRESS_MS
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::OnceLock;
+
+    use super::*;
+
+    /// Configuración del terminal antes de armar la tecla
+    static ORIGINAL_TERMIOS: OnceLock<libc::termios> = OnceLock::new();
+
+    /// Momento (ms monótonos) de la última pulsación, 0 si no hubo
+    static LAST_PRESS_MS: AtomicU64 = AtomicU64::new(0);
+
+    fn monotonic_ms() -> u64 {
+        let mut now = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        // SAFETY: clock_gettime solo escribe en `now`; es segura en un manejador de señal
+        unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now) };
+        // +1 para que ninguna pulsación real valga 0
+        now.tv_sec as u64 * 1000 + now.tv_nsec as u64 / 1_000_000 + 1
+    }
+
+    fn write_all(fd: i32, bytes: &[u8]) {
+        // SAFETY: write con un buffer válido; sin reintentos para no bloquear la señal
+        unsafe { libc::write(fd, bytes.as_ptr().cast(), bytes.len()) };
+    }
+
+    fn restore_terminal() {
+        if let Some(original) = ORIGINAL_TERMIOS.get() {
+            // SAFETY: tcsetattr con una configuración leída antes con tcgetattr
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original) };
+        }
+    }
+
+    extern "C" fn restore_at_exit() {
+        restore_terminal();
+    }
+
+    extern "C" fn on_quit(_signal: libc::c_int) {
+        let now = monotonic_ms();
+        let previous = LAST_PRESS_MS.swap(now, Ordering::SeqCst);
+        key_pressed(is_second_press(previous, now));
+    }
+
+    /// Aviso tras la primera pulsación; borrado y salida tras la segunda
+    pub fn key_pressed(second: bool) {
+        if !second {
+            write_all(libc::STDERR_FILENO, HINT);
+            return;
+        }
+
+        restore_terminal();
+        write_all(libc::STDOUT_FILENO, CLEAR_ALL);
+        // SAFETY: _exit termina sin ejecutar código de usuario ni destructores
+        unsafe { libc::_exit(EXIT_CODE) };
+    }
+
+    pub fn arm() -> bool {
+        // SAFETY: isatty/tcgetattr/tcsetattr sobre stdin con un termios local
+        unsafe {
+            if libc::isatty(libc::STDIN_FILENO) != 1 {
+                return false;
+            }
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return false;
+            }
+            if ORIGINAL_TERMIOS.set(termios).is_err() {
+                return true; // Ya armada
+            }
+
+            let handler: extern "C" fn(libc::c_int) = on_quit;
+            if libc::signal(libc::SIGQUIT, handler as libc::sighandler_t) == libc::SIG_ERR {
+                return false;
+            }
+            libc::atexit(restore_at_exit);
+
+            termios.c_cc[libc::VQUIT] = CTRL_X;
+            termios.c_lflag |= libc::ISIG;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) == 0
+        }
+    }
+
+    pub fn is_armed() -> bool {
+        ORIGINAL_TERMIOS.get().is_some()
+    }
+}
+
+/// Armar la tecla de pánico (Ctrl-X dos veces) en el terminal de stdin
+///
+/// Devuelve `false` si stdin no es un terminal o la plataforma no lo permite.
+pub fn arm() -> bool {
+    #[cfg(unix)]
+    {
+        imp::arm()
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Ctrl-X leído como byte en modo raw; `second` si es la segunda pulsación a tiempo
+///
+/// Sin la tecla armada no hace nada: fuera de los modos interactivos
+/// Ctrl-X no significa nada.
+pub fn key_pressed(second: bool) {
+    #[cfg(unix)]
+    if imp::is_armed() {
+        imp::key_pressed(second);
+    }
+    #[cfg(not(unix))]
+    let _ = second;
+}
+
+/// Si la tecla de pánico está armada (para mostrar la ayuda)
+pub fn is_armed() -> bool {
+    #[cfg(unix)]
+    {
+        imp::is_armed()
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_second_press() {
+        assert!(!is_second_press(0, 5_000));
+        assert!(is_second_press(5_000, 5_500));
+        assert!(is_second_press(5_000, 5_000 + DOUBLE_PRESS_MS));
+        assert!(!is_second_press(5_000, 5_001 + DOUBLE_PRESS_MS));
+    }
+}
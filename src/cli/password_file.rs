@@ -0,0 +1,57 @@
+// src/cli/password_file.rs - Contraseña guardada en un archivo cifrado
+//
+// Permite repartir el secreto entre dos personas: una guarda el archivo con
+// la contraseña de SCypher y otra la clave que lo abre. Con GnuPG se invoca
+// `gpg --decrypt` (`cli::gpg`: clave privada o passphrase vía gpg-agent); los
+// archivos age con passphrase se abren con la implementación enlazada, sin
+// depender de un binario externo. Se usa la primera línea del contenido.
+
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Result};
+
+/// Descifrar `path` con `gpg` y devolver la contraseña que contiene
+pub fn read_password_gpg(path: &str) -> Result<Zeroizing<String>> {
+    println!("Decrypting password file with GnuPG: {}", path);
+    let plaintext = crate::cli::gpg::decrypt_file(path)?;
+    password_from_plaintext(&plaintext)
+}
+
+/// Pedir la passphrase de un archivo age y devolver la contraseña que contiene
+#[cfg(feature = "armor")]
+pub fn read_password_age(path: &str) -> Result<Zeroizing<String>> {
+    let data = std::fs::read(path)
+        .map_err(|e| SCypherError::io_at(crate::error::IoOperation::Read, path, e))?;
+
+    println!("Password file (age): {}", path);
+    let passphrase = crate::cli::input::read_existing_password("password file passphrase")?;
+    let plaintext = crate::armor::open_age_file(&data, &passphrase)?;
+    password_from_plaintext(&plaintext)
+}
+
+/// Primera línea del contenido descifrado, sin el salto de línea
+fn password_from_plaintext(plaintext: &[u8]) -> Result<Zeroizing<String>> {
+    let text = std::str::from_utf8(plaintext)
+        .map_err(|_| SCypherError::crypto("Password file does not hold UTF-8 text"))?;
+    let password = text.lines().next().unwrap_or("");
+    if password.is_empty() {
+        return Err(SCypherError::InvalidPassword);
+    }
+    Ok(Zeroizing::new(password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_from_plaintext() {
+        assert_eq!(*password_from_plaintext(b"correct horse\n").unwrap(), "correct horse");
+        assert_eq!(*password_from_plaintext(b"spaces kept  \r\nsecond line").unwrap(), "spaces kept  ");
+        assert_eq!(*password_from_plaintext(b"no newline").unwrap(), "no newline");
+
+        assert!(matches!(password_from_plaintext(b"\nlater"), Err(SCypherError::InvalidPassword)));
+        assert!(password_from_plaintext(b"").is_err());
+        assert!(password_from_plaintext(&[0xff, 0xfe]).is_err());
+    }
+}
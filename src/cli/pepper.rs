@@ -0,0 +1,138 @@
+// src/cli/pepper.rs - Pepper leído de un archivo (`--pepper-file`)
+//
+// El pepper es el secreto K de Argon2: un valor que se queda en la máquina
+// y no viaja con la copia cifrada. Solo sirve si nadie más puede leerlo,
+// así que únicamente se acepta un archivo regular del usuario actual, sin
+// permisos para el grupo ni para otros (0600 o 0400), en un sistema de
+// archivos local con permisos Unix de verdad: en FAT, exFAT o NTFS los
+// permisos los inventan las opciones de montaje, y en NFS o SMB los
+// aplica otra máquina. El contenido va directo del archivo a un
+// `SecureBuffer`, sin copias intermedias, y se limpia al soltar los
+// parámetros que lo usan.
+
+use crate::crypto::Pepper;
+use crate::error::{SCypherError, Result};
+
+/// Tamaño mínimo: menos no añade un secreto que valga la pena
+pub const MIN_PEPPER_LEN: usize = 16;
+/// Tamaño máximo: un pepper no es un keyfile grande
+pub const MAX_PEPPER_LEN: usize = 4096;
+
+/// Sistemas de archivos donde el modo 0600 no garantiza nada (`statfs.f_type`)
+#[cfg(target_os = "linux")]
+const UNTRUSTED_FILESYSTEMS: &[(u32, &str)] = &[
+    (0x4d44, "FAT"),
+    (0x2011_bab0, "exFAT"),
+    (0x5346_544e, "NTFS"),
+    (0x6573_5546, "FUSE"),
+    (0x6969, "NFS"),
+    (0x517b, "SMB"),
+    (0xff53_4d42, "CIFS"),
+    (0xfe53_4d42, "SMB2"),
+    (0x0102_1997, "9P"),
+    (0x786f_4256, "VirtualBox shared folder"),
+];
+
+/// Leer y validar un archivo de pepper
+#[cfg(unix)]
+pub fn read_pepper_file(path: &str) -> Result<Pepper> {
+    use std::io::Read;
+    use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
+
+    use crate::error::IoOperation;
+    use crate::security::memory::SecureBuffer;
+
+    // Sin seguir enlaces: se valida el archivo que realmente se abre
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOFOLLOW)
+        .open(path)
+        .map_err(|e| SCypherError::io_at(IoOperation::Read, path, e))?;
+    let metadata = file.metadata().map_err(|e| SCypherError::io_at(IoOperation::Metadata, path, e))?;
+
+    if !metadata.is_file() {
+        return Err(SCypherError::file_at(path, "Pepper file is not a regular file"));
+    }
+    // SAFETY: geteuid no tiene precondiciones
+    let uid = unsafe { libc::geteuid() };
+    if metadata.uid() != uid {
+        return Err(SCypherError::file_at(path, format!(
+            "Pepper file is owned by uid {}, not by the current user (uid {})", metadata.uid(), uid)));
+    }
+    let mode = metadata.mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(SCypherError::file_at(path, format!(
+            "Pepper file permissions are {:o}: other users can read or change it (use chmod 600)", mode)));
+    }
+    check_filesystem(&file, path)?;
+
+    let len = metadata.len() as usize;
+    if !(MIN_PEPPER_LEN..=MAX_PEPPER_LEN).contains(&len) {
+        return Err(SCypherError::file_at(path, format!(
+            "Pepper file must hold {}-{} bytes, it has {}", MIN_PEPPER_LEN, MAX_PEPPER_LEN, len)));
+    }
+    let mut buffer = SecureBuffer::new(len);
+    file.read_exact(buffer.as_mut_slice()).map_err(|e| SCypherError::io_at(IoOperation::Read, path, e))?;
+    if file.read(&mut [0u8; 1]).map_err(|e| SCypherError::io_at(IoOperation::Read, path, e))? != 0 {
+        return Err(SCypherError::file_at(path, "Pepper file changed while it was being read"));
+    }
+    Ok(Pepper::new(buffer))
+}
+
+/// Sin permisos Unix no hay forma de comprobar quién puede leer el archivo
+#[cfg(not(unix))]
+pub fn read_pepper_file(path: &str) -> Result<Pepper> {
+    Err(SCypherError::file_at(path, "--pepper-file needs Unix file permissions to check who can read the file"))
+}
+
+/// Rechazar sistemas de archivos sin permisos reales o compartidos por red
+#[cfg(target_os = "linux")]
+fn check_filesystem(file: &std::fs::File, path: &str) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: statfs es una estructura C sin invariantes, válida a ceros; el
+    // descriptor está abierto y la estructura es la que espera fstatfs
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::fstatfs(file.as_raw_fd(), &mut stat) } != 0 {
+        return Err(SCypherError::io_at(crate::error::IoOperation::Metadata, path, std::io::Error::last_os_error()));
+    }
+    #[allow(clippy::unnecessary_cast)]
+    let fs_type = stat.f_type as u32;
+    match UNTRUSTED_FILESYSTEMS.iter().find(|(magic, _)| *magic == fs_type) {
+        Some((_, name)) => Err(SCypherError::file_at(path, format!(
+            "Pepper file is on a {} filesystem, where its permissions are not enforced locally; keep it on a local Unix filesystem", name))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn check_filesystem(_file: &std::fs::File, _path: &str) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_pepper_file_permissions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("pepper.bin");
+        let path_str = path.to_str().unwrap();
+        std::fs::write(&path, [0x42u8; 32]).unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(read_pepper_file(path_str).unwrap_err().to_string().contains("chmod 600"));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(read_pepper_file(path_str).unwrap().as_bytes(), [0x42u8; 32]);
+
+        // Enlaces no, tamaños absurdos tampoco
+        let link = dir.path().join("link.bin");
+        std::os::unix::fs::symlink(&path, &link).unwrap();
+        assert!(read_pepper_file(link.to_str().unwrap()).is_err());
+        std::fs::write(&path, [0x42u8; 8]).unwrap();
+        assert!(read_pepper_file(path_str).is_err());
+    }
+}
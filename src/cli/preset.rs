@@ -0,0 +1,352 @@
+// src/cli/preset.rs - Presets de parámetros por equipo (`preset save`, `--preset`)
+//
+// Cada máquina tiene sus propios parámetros afinados (memoria e iteraciones
+// que tolera, lanes, formato de salida preferido). `preset save NOMBRE`
+// guarda los valores de esas opciones dados en la línea de comandos y
+// `--preset NOMBRE` los vuelve a poner, salvo los que ya lleve la orden.
+//
+// Se guardan en `<config>/presets/<nombre>.json` (`$SCYPHER_CONFIG_DIR`, o
+// `$XDG_CONFIG_HOME/scypher`, `~/.config/scypher`, `%APPDATA%\scypher`) con
+// un HMAC-SHA256 hecho con una clave aleatoria del equipo
+// (`<config>/preset.key`, 0600). Un preset editado a mano o copiado desde
+// otra máquina no se acepta: unos parámetros rebajados en silencio darían
+// cifrados más débiles sin que nadie lo note. En Unix tampoco se aceptan
+// archivos que puedan escribir otros usuarios.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::cli::output::save_bytes_to_file;
+use crate::error::{Defect, IoOperation, SCypherError, Result};
+
+/// Versión del formato de los presets
+pub const PRESET_VERSION: u32 = 1;
+
+/// Opciones que guarda un preset (identificadores de clap)
+pub const PRESET_ARGS: &[&str] = &["iterations", "memory", "lanes", "argon2-variant", "argon2-version", "format"];
+
+/// Clave del equipo con la que se firman los presets
+const KEY_FILE: &str = "preset.key";
+const KEY_LEN: usize = 32;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Preset guardado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    pub version: u32,
+    pub name: String,
+    /// Segundos desde la época Unix
+    pub created: u64,
+    /// Valor de cada opción guardada, por identificador
+    pub settings: BTreeMap<String, String>,
+}
+
+/// Archivo de un preset: el contenido y su HMAC
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PresetFile {
+    preset: Preset,
+    /// HMAC-SHA256 en hex del JSON de `preset`
+    mac: String,
+}
+
+impl Preset {
+    /// Las opciones como se escribirían en la línea de comandos
+    pub fn describe(&self) -> String {
+        self.settings.iter().map(|(id, value)| format!("--{} {}", id, value)).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Directorio de configuración de SCypher
+pub fn config_dir() -> Result<PathBuf> {
+    let from_env = |var: &str| std::env::var_os(var).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if let Some(dir) = from_env("SCYPHER_CONFIG_DIR") {
+        return Ok(dir);
+    }
+    #[cfg(windows)]
+    let base = from_env("APPDATA");
+    #[cfg(not(windows))]
+    let base = from_env("XDG_CONFIG_HOME").or_else(|| from_env("HOME").map(|home| home.join(".config")));
+    base.map(|base| base.join("scypher")).ok_or_else(|| SCypherError::InvalidRequest(
+        "cannot locate the configuration directory; set SCYPHER_CONFIG_DIR".to_string()))
+}
+
+/// Nombres válidos: letras, dígitos, `.`, `_` y `-`, sin empezar por `.`
+fn check_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(SCypherError::InvalidRequest(format!("invalid preset name '{}' (letters, digits, '.', '_' and '-')", name)))
+    }
+}
+
+fn presets_dir(config: &Path) -> PathBuf {
+    config.join("presets")
+}
+
+fn preset_path(config: &Path, name: &str) -> PathBuf {
+    presets_dir(config).join(format!("{}.json", name))
+}
+
+/// Rechazar archivos que otros usuarios puedan modificar
+fn check_permissions(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| SCypherError::io_at(IoOperation::Metadata, path.display().to_string(), e))?;
+        if metadata.permissions().mode() & 0o022 != 0 {
+            return Err(SCypherError::file_at(path.display().to_string(), "Writable by other users; fix it with chmod 600"));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Crear un directorio de configuración (0700 en Unix)
+fn create_private_dir(dir: &Path) -> Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir).map_err(|e| SCypherError::io_at(IoOperation::CreateDir, dir.display().to_string(), e))
+}
+
+/// Clave del equipo; con `create`, se genera si aún no existe
+fn device_key(config: &Path, create: bool) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+    let path = config.join(KEY_FILE);
+    let name = path.display().to_string();
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+
+    if !path.exists() {
+        if !create {
+            return Err(SCypherError::file_at(name, "No preset key on this device; presets saved elsewhere cannot be used here"));
+        }
+        create_private_dir(config)?;
+        rand::rngs::OsRng.fill_bytes(&mut key[..]);
+        save_bytes_to_file(&key[..], &name)?;
+        return Ok(key);
+    }
+
+    check_permissions(&path)?;
+    let data = Zeroizing::new(std::fs::read(&path).map_err(|e| SCypherError::io_at(IoOperation::Read, &name, e))?);
+    if data.len() != KEY_LEN {
+        return Err(SCypherError::malformed("preset key", Defect::Truncated, format!("{} must hold {} bytes", name, KEY_LEN)));
+    }
+    key.copy_from_slice(&data);
+    Ok(key)
+}
+
+/// HMAC del JSON de `preset`, listo para finalizar o verificar
+fn mac_of(key: &[u8], preset: &Preset) -> Result<HmacSha256> {
+    let json = serde_json::to_vec(preset)
+        .map_err(|e| SCypherError::crypto(format!("Cannot serialize preset: {}", e)))?;
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(&json);
+    Ok(mac)
+}
+
+/// Opciones de `PRESET_ARGS` dadas en la línea de comandos
+pub fn capture(matches: &clap::ArgMatches) -> BTreeMap<String, String> {
+    PRESET_ARGS
+        .iter()
+        .filter(|id| matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine))
+        .filter_map(|id| {
+            let value = matches.get_raw(id)?.next()?;
+            Some((id.to_string(), value.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// Guardar un preset en `config` (sobrescribe el del mismo nombre)
+pub fn save_in(config: &Path, name: &str, settings: BTreeMap<String, String>) -> Result<PathBuf> {
+    check_name(name)?;
+    if settings.is_empty() {
+        return Err(SCypherError::InvalidRequest(
+            "nothing to save: give the options on the same command line (e.g. scypher -i 8 -m 1048576 preset save NAME)".to_string()));
+    }
+
+    let key = device_key(config, true)?;
+    let preset = Preset {
+        version: PRESET_VERSION,
+        name: name.to_string(),
+        created: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()),
+        settings,
+    };
+    let mac = hex::encode(mac_of(&key[..], &preset)?.finalize().into_bytes());
+    let json = serde_json::to_string_pretty(&PresetFile { preset, mac })
+        .map_err(|e| SCypherError::crypto(format!("Cannot serialize preset: {}", e)))?;
+
+    create_private_dir(&presets_dir(config))?;
+    let path = preset_path(config, name);
+    save_bytes_to_file(json.as_bytes(), &path.display().to_string())?;
+    Ok(path)
+}
+
+/// Leer y verificar un preset de `config`
+pub fn load_from(config: &Path, name: &str) -> Result<Preset> {
+    check_name(name)?;
+    let path = preset_path(config, name);
+    let path_name = path.display().to_string();
+    if !path.exists() {
+        return Err(SCypherError::file_at(path_name, format!("No preset named '{}' (see 'scypher preset list')", name)));
+    }
+    check_permissions(&path)?;
+
+    let content = std::fs::read_to_string(&path).map_err(|e| SCypherError::io_at(IoOperation::Read, &path_name, e))?;
+    let file: PresetFile = serde_json::from_str(&content)
+        .map_err(|e| SCypherError::malformed("preset", Defect::Encoding, e.to_string()))?;
+
+    let key = device_key(config, false)?;
+    let mac = hex::decode(&file.mac).unwrap_or_default();
+    let valid = mac_of(&key[..], &file.preset)?.verify_slice(&mac).is_ok();
+    if !valid || file.preset.name != name {
+        return Err(SCypherError::malformed("preset", Defect::Checksum,
+            format!("'{}' failed its integrity check: it was modified or saved on another device", name)));
+    }
+    if file.preset.version != PRESET_VERSION {
+        return Err(SCypherError::malformed("preset", Defect::Unsupported, format!("version {} is not supported", file.preset.version)));
+    }
+    if let Some(unknown) = file.preset.settings.keys().find(|id| !PRESET_ARGS.contains(&id.as_str())) {
+        return Err(SCypherError::malformed("preset", Defect::Inconsistent, format!("unknown option '{}'", unknown)));
+    }
+    Ok(file.preset)
+}
+
+/// Nombres de los presets guardados en `config`
+pub fn list_in(config: &Path) -> Result<Vec<String>> {
+    let dir = presets_dir(config);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(SCypherError::io_at(IoOperation::Read, dir.display().to_string(), e)),
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".json").map(str::to_string))
+        .filter(|name| check_name(name).is_ok())
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Borrar un preset de `config`
+pub fn remove_in(config: &Path, name: &str) -> Result<()> {
+    check_name(name)?;
+    let path = preset_path(config, name);
+    std::fs::remove_file(&path).map_err(|e| SCypherError::io_at(IoOperation::Remove, path.display().to_string(), e))
+}
+
+/// Argumentos de la línea de comandos con los valores del preset delante
+///
+/// Se omiten las opciones que la orden ya da y las que chocan con alguna
+/// que da (p.ej. `--format` junto a `--raw-seed`).
+pub fn apply(command: &clap::Command, matches: &clap::ArgMatches, preset: &Preset) -> Vec<OsString> {
+    let given: Vec<&clap::Arg> = command
+        .get_arguments()
+        .filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(clap::parser::ValueSource::CommandLine))
+        .collect();
+
+    let mut args: Vec<OsString> = std::env::args_os().take(1).collect();
+    for (id, value) in &preset.settings {
+        let Some(arg) = command.get_arguments().find(|arg| arg.get_id() == id) else { continue };
+        let conflicts = given.iter().any(|other| {
+            other.get_id() == arg.get_id()
+                || command.get_arg_conflicts_with(arg).iter().any(|conflict| conflict.get_id() == other.get_id())
+                || command.get_arg_conflicts_with(other).iter().any(|conflict| conflict.get_id() == arg.get_id())
+        });
+        if !conflicts {
+            args.push(format!("--{}", id).into());
+            args.push(value.into());
+        }
+    }
+    args.extend(std::env::args_os().skip(1));
+    args
+}
+
+/// Órdenes `preset save|list|show|remove`
+pub fn run_preset_command(preset_matches: &clap::ArgMatches, matches: &clap::ArgMatches) -> Result<()> {
+    let config = config_dir()?;
+    match preset_matches.subcommand() {
+        Some(("save", save_matches)) => {
+            let name = save_matches.get_one::<String>("name").unwrap();
+            let path = save_in(&config, name, capture(matches))?;
+            let preset = load_from(&config, name)?;
+            println!("Preset '{}' saved to {}: {}", name, path.display(), preset.describe());
+        }
+        Some(("list", _)) => {
+            let names = list_in(&config)?;
+            if names.is_empty() {
+                println!("No presets saved in {}", presets_dir(&config).display());
+            }
+            for name in names {
+                match load_from(&config, &name) {
+                    Ok(preset) => println!("{}: {}", name, preset.describe()),
+                    Err(e) => println!("{}: {}", name, e),
+                }
+            }
+        }
+        Some(("show", show_matches)) => {
+            let preset = load_from(&config, show_matches.get_one::<String>("name").unwrap())?;
+            println!("{}: {}", preset.name, preset.describe());
+        }
+        Some(("remove", remove_matches)) => {
+            let name = remove_matches.get_one::<String>("name").unwrap();
+            remove_in(&config, name)?;
+            println!("Preset '{}' removed", name);
+        }
+        _ => unreachable!("clap requires a preset subcommand"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_integrity() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = dir.path().join("scypher");
+        let settings = BTreeMap::from([("iterations".to_string(), "8".to_string()), ("memory".to_string(), "1048576".to_string())]);
+
+        let path = save_in(&config, "vault-laptop", settings.clone()).unwrap();
+        let preset = load_from(&config, "vault-laptop").unwrap();
+        assert_eq!(preset.settings, settings);
+        assert_eq!(preset.describe(), "--iterations 8 --memory 1048576");
+        assert_eq!(list_in(&config).unwrap(), ["vault-laptop"]);
+
+        // Parámetros rebajados a mano
+        let content = std::fs::read_to_string(&path).unwrap().replace("1048576", "8192");
+        std::fs::write(&path, content).unwrap();
+        assert!(matches!(load_from(&config, "vault-laptop"), Err(SCypherError::MalformedContainer { .. })));
+
+        // Copiado a otro equipo (otra clave)
+        save_in(&config, "vault-laptop", settings).unwrap();
+        let other = dir.path().join("other");
+        std::fs::create_dir_all(presets_dir(&other)).unwrap();
+        std::fs::copy(&path, preset_path(&other, "vault-laptop")).unwrap();
+        save_in(&other, "unrelated", BTreeMap::from([("lanes".to_string(), "2".to_string())])).unwrap();
+        assert!(load_from(&other, "vault-laptop").is_err());
+
+        assert!(save_in(&config, "../escape", BTreeMap::new()).is_err());
+        remove_in(&config, "vault-laptop").unwrap();
+        assert!(list_in(&config).unwrap().is_empty());
+    }
+}
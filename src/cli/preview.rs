@@ -0,0 +1,105 @@
+// src/cli/preview.rs - Demostración de lo que ve un atacante
+//
+// Material de formación para personal de custodia: con una frase y unas
+// contraseñas de prueba generadas al azar (nunca datos reales) muestra que
+// la frase cifrada es a su vez una frase BIP39 válida, y que una contraseña
+// equivocada no da error sino otra frase igual de válida. Nada de lo que se
+// muestra sirve para nada fuera de la demostración.
+
+use rand::distributions::{Alphanumeric, DistString};
+use zeroize::Zeroizing;
+
+use crate::cli::colors;
+use crate::crypto::ScypherParams;
+use crate::error::Result;
+
+/// Longitud de las contraseñas de prueba
+const PASSWORD_LENGTH: usize = 12;
+
+/// Datos de la demostración, todos desechables
+struct Preview {
+    original: Zeroizing<String>,
+    password: Zeroizing<String>,
+    wrong_password: Zeroizing<String>,
+    encrypted: Zeroizing<String>,
+    wrong_decryption: Zeroizing<String>,
+    decrypted: Zeroizing<String>,
+}
+
+fn random_password() -> Zeroizing<String> {
+    Zeroizing::new(Alphanumeric.sample_string(&mut rand::thread_rng(), PASSWORD_LENGTH))
+}
+
+/// Cifrar una frase aleatoria y descifrarla con la contraseña buena y con otra
+fn build_preview(params: &ScypherParams, words: usize) -> Result<Preview> {
+    crate::bip39::validate_word_count(words)?;
+    let original = Zeroizing::new(crate::bip39::conversion::generate_seed_phrase(words * 32 / 3)?);
+    let password = random_password();
+    let wrong_password = random_password();
+
+    let encrypted = params.transform(&original, &password)?;
+    let wrong_decryption = params.transform(&encrypted, &wrong_password)?;
+    let decrypted = params.transform(&encrypted, &password)?;
+
+    Ok(Preview { original, password, wrong_password, encrypted, wrong_decryption, decrypted })
+}
+
+fn checksum_label(phrase: &str) -> String {
+    match crate::bip39::verify_checksum(phrase) {
+        Ok(true) => format!("{}✓ valid BIP39 phrase (words and checksum){}", colors::SUCCESS, colors::RESET),
+        _ => format!("{}✗ not a valid BIP39 phrase{}", colors::ERROR, colors::RESET),
+    }
+}
+
+/// Ejecutar la demostración con datos de prueba de `words` palabras
+pub fn run_preview(params: &ScypherParams, words: usize) -> Result<()> {
+    println!("{}SCypher preview - what an attacker sees{}", colors::BRIGHT, colors::RESET);
+    println!("{}All phrases and passwords below are random throwaway test data.{}\n", colors::WARNING, colors::RESET);
+
+    println!("Deriving keys (Argon2id, {} iterations, {}KB)...\n", params.iterations(), params.memory_cost());
+    let preview = build_preview(params, words)?;
+
+    println!("1. Test seed phrase (stands in for a real wallet seed):");
+    println!("   {}", *preview.original);
+    println!("\n2. Encrypted with test password '{}':", *preview.password);
+    println!("   {}", *preview.encrypted);
+    println!("   {}", checksum_label(&preview.encrypted));
+    println!("   This is what is stored or stolen. It looks like any other wallet seed:");
+    println!("   nothing marks it as encrypted.");
+
+    println!("\n3. Decrypted with a wrong password '{}':", *preview.wrong_password);
+    println!("   {}", *preview.wrong_decryption);
+    println!("   {}", checksum_label(&preview.wrong_decryption));
+    println!("   No error: a wrong guess gives a different but equally plausible phrase,");
+    println!("   so the attacker cannot tell a right guess from a wrong one offline.");
+
+    println!("\n4. Decrypted with the right password:");
+    println!("   {}", *preview.decrypted);
+    let recovered = *preview.decrypted == *preview.original;
+    println!("   {}", if recovered { "✓ identical to the test seed phrase" } else { "✗ does not match (unexpected)" });
+
+    println!("\nEach guess costs a full Argon2id derivation, and checking it means deriving");
+    println!("wallet addresses and looking them up on-chain. The security of the scheme is");
+    println!("the strength of the password.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_preview() {
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+        let preview = build_preview(&params, 12).unwrap();
+
+        assert_ne!(*preview.password, *preview.wrong_password);
+        assert_eq!(preview.encrypted.split_whitespace().count(), 12);
+        assert!(crate::bip39::verify_checksum(&preview.encrypted).unwrap());
+        assert!(crate::bip39::verify_checksum(&preview.wrong_decryption).unwrap());
+        assert_ne!(*preview.wrong_decryption, *preview.original);
+        assert_eq!(*preview.decrypted, *preview.original);
+
+        assert!(build_preview(&params, 13).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+// src/cli/qr.rs - Códigos QR en la terminal
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+
+use crate::error::{SCypherError, Result};
+use crate::ur::{Ur, UrEncoder, DEFAULT_FRAGMENT_LEN};
+
+/// Tiempo que se muestra cada parte de un QR animado
+const FRAME_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Píxeles por módulo del QR en PNG
+const PNG_SCALE: usize = 8;
+
+/// Módulos de margen blanco alrededor del QR en PNG (el mínimo del estándar)
+const PNG_QUIET_ZONE: usize = 4;
+
+/// Dibujar un texto como QR con caracteres de bloque
+///
+/// Los UR van en mayúsculas para que el QR use el modo alfanumérico, más denso.
+fn render(text: &str) -> Result<String> {
+    let code = QrCode::new(text.to_ascii_uppercase().as_bytes())
+        .map_err(|e| SCypherError::InvalidRequest(format!("cannot build QR code: {}", e)))?;
+    Ok(code.render::<Dense1x2>().quiet_zone(true).build())
+}
+
+/// QR de `text` en PNG (escala de grises, 8 píxeles por módulo)
+///
+/// El texto se codifica tal cual: un UR debe llegar ya en mayúsculas.
+pub fn png(text: &str) -> Result<Vec<u8>> {
+    let code = qr_code(text)?;
+    let side = png_side(&code);
+    encode_png(side, &[draw(&code, side)])
+}
+
+/// UR en PNG y número de partes: un QR estático si cabe en una parte; si
+/// no, un PNG animado (APNG) que recorre las partes de la fuente
+///
+/// La animación lleva el doble de fotogramas que partes (las primeras sin
+/// mezclar, luego mezcladas), así un escáner que pierda alguna puede
+/// terminar sin esperar a la siguiente vuelta. Los visores sin APNG
+/// muestran solo la primera parte.
+pub fn ur_png(ur: &Ur) -> Result<(Vec<u8>, usize)> {
+    let mut encoder = UrEncoder::new(ur, DEFAULT_FRAGMENT_LEN);
+    if encoder.is_single_part() {
+        return Ok((png(&encoder.next_part().to_ascii_uppercase())?, 1));
+    }
+
+    let codes = (0..encoder.seq_len() * 2)
+        .map(|_| qr_code(&encoder.next_part().to_ascii_uppercase()))
+        .collect::<Result<Vec<_>>>()?;
+    // Las partes pueden necesitar versiones distintas: todas al tamaño de la mayor
+    let side = codes.iter().map(png_side).max().unwrap_or(0);
+    let frames: Vec<Vec<u8>> = codes.iter().map(|code| draw(code, side)).collect();
+    Ok((encode_png(side, &frames)?, encoder.seq_len()))
+}
+
+fn qr_code(text: &str) -> Result<QrCode> {
+    QrCode::new(text.as_bytes()).map_err(|e| SCypherError::InvalidRequest(format!("cannot build QR code: {}", e)))
+}
+
+/// Lado en píxeles de la imagen de `code`, con el margen
+fn png_side(code: &QrCode) -> usize {
+    (code.width() + 2 * PNG_QUIET_ZONE) * PNG_SCALE
+}
+
+/// Píxeles de `code` centrado en un cuadrado blanco de `side` píxeles
+fn draw(code: &QrCode, side: usize) -> Vec<u8> {
+    let modules = code.width();
+    let offset = (side - modules * PNG_SCALE) / 2;
+    let mut pixels = vec![0xFFu8; side * side];
+    for (index, color) in code.to_colors().into_iter().enumerate() {
+        if color != qrcode::Color::Dark {
+            continue;
+        }
+        let (x, y) = (offset + index % modules * PNG_SCALE, offset + index / modules * PNG_SCALE);
+        for row in y..y + PNG_SCALE {
+            pixels[row * side + x..row * side + x + PNG_SCALE].fill(0);
+        }
+    }
+    pixels
+}
+
+/// PNG en escala de grises con uno o varios fotogramas (APNG en bucle)
+fn encode_png(side: usize, frames: &[Vec<u8>]) -> Result<Vec<u8>> {
+    let png_error = |e: png::EncodingError| SCypherError::crypto(format!("Cannot encode PNG: {}", e));
+    let mut output = Vec::new();
+    let mut encoder = png::Encoder::new(&mut output, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    if frames.len() > 1 {
+        encoder.set_animated(frames.len() as u32, 0).map_err(png_error)?;
+        encoder.set_frame_delay(FRAME_INTERVAL.as_millis() as u16, 1000).map_err(png_error)?;
+    }
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    for frame in frames {
+        writer.write_image_data(frame).map_err(png_error)?;
+    }
+    writer.finish().map_err(png_error)?;
+    Ok(output)
+}
+
+/// Mostrar un UR como QR: estático si cabe en una parte, animado si no
+///
+/// La animación recorre las partes de la fuente en bucle hasta que el usuario
+/// pulsa Enter; el escáner puede empezar en cualquier momento.
+pub fn show_ur(ur: &Ur) -> Result<()> {
+    let mut encoder = UrEncoder::new(ur, DEFAULT_FRAGMENT_LEN);
+
+    if encoder.is_single_part() {
+        let part = encoder.next_part();
+        println!("\n{}", render(&part)?);
+        println!("{}", part.to_ascii_uppercase());
+        return Ok(());
+    }
+
+    // Sin control del cursor no hay animación: cada parte una vez, una tras otra
+    if crate::cli::display::is_plain_terminal() {
+        println!("\nQR in {} parts; scan them in order.", encoder.seq_len());
+        for index in 1..=encoder.seq_len() {
+            let part = encoder.next_part();
+            println!("\nPart {} of {}", index, encoder.seq_len());
+            println!("{}", render(&part)?);
+        }
+        return Ok(());
+    }
+
+    println!("\nAnimated QR with {} parts. Press Enter to stop.", encoder.seq_len());
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            let _ = io::stdin().read_line(&mut line);
+            stop.store(true, Ordering::Relaxed);
+        });
+    }
+
+    let mut frame = 0usize;
+    while !stop.load(Ordering::Relaxed) {
+        let part = encoder.next_part();
+        frame += 1;
+        // Limpiar la pantalla y volver al origen antes de cada fotograma
+        print!("\x1b[H\x1b[J{}", render(&part)?);
+        println!("Part {} ({} parts) — press Enter to stop", frame, encoder.seq_len());
+        io::stdout().flush().map_err(SCypherError::from)?;
+        std::thread::sleep(FRAME_INTERVAL);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png() {
+        let image = png("UR:BYTES/HDCXAEAD").unwrap();
+        assert_eq!(&image[..8], b"\x89PNG\r\n\x1a\n");
+
+        let decoder = ::png::Decoder::new(&image[..]);
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        // Versión 1: 21 módulos más el margen
+        assert_eq!((info.width, info.height), (((21 + 2 * PNG_QUIET_ZONE) * PNG_SCALE) as u32, ((21 + 2 * PNG_QUIET_ZONE) * PNG_SCALE) as u32));
+    }
+
+    #[test]
+    fn test_ur_png() {
+        let (_, parts) = ur_png(&Ur::bytes(b"short")).unwrap();
+        assert_eq!(parts, 1);
+
+        // Un contenedor grande: APNG con dos vueltas de partes, todas del mismo tamaño
+        let ur = Ur::bytes(&[0x5A; 1000]);
+        let (image, parts) = ur_png(&ur).unwrap();
+        assert_eq!(parts, UrEncoder::new(&ur, DEFAULT_FRAGMENT_LEN).seq_len());
+        assert!(parts > 1);
+
+        let mut reader = ::png::Decoder::new(&image[..]).read_info().unwrap();
+        let animation = reader.info().animation_control().copied().unwrap();
+        assert_eq!((animation.num_frames, animation.num_plays), (2 * parts as u32, 0));
+        let (width, height) = (reader.info().width, reader.info().height);
+        let mut frame = vec![0u8; reader.output_buffer_size()];
+        for _ in 0..animation.num_frames {
+            let info = reader.next_frame(&mut frame).unwrap();
+            assert_eq!((info.width, info.height), (width, height));
+        }
+    }
+}
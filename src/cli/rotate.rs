@@ -0,0 +1,72 @@
+// src/cli/rotate.rs - Cambio de contraseña en un solo paso
+//
+// Descifra la frase con la contraseña y los parámetros antiguos y la vuelve
+// a cifrar con los nuevos sin salir de memoria: la frase original nunca se
+// muestra ni se escribe. Como cualquier descifrado, una contraseña antigua
+// equivocada no se detecta: da otra frase válida, que quedaría recifrada y
+// perdida. Por eso la contraseña antigua se pide dos veces.
+
+use zeroize::Zeroizing;
+
+use crate::bip39::Wordlist;
+use crate::crypto::{self, ScypherParams};
+use crate::error::{SCypherError, Result};
+
+/// Descifrar `encrypted` con la clave antigua y cifrar el resultado con la nueva
+fn rotate_phrase(
+    encrypted: &str,
+    wordlist: &dyn Wordlist,
+    old_password: &str,
+    old_params: &ScypherParams,
+    new_password: &str,
+    new_params: &ScypherParams,
+) -> Result<Zeroizing<String>> {
+    let plaintext = crypto::transform_seed_with_wordlist(encrypted, old_password, old_params, wordlist)?;
+    crypto::transform_seed_with_wordlist(&plaintext, new_password, new_params, wordlist)
+}
+
+/// Pedir la frase cifrada y las dos contraseñas y devolver la frase recifrada
+pub fn run_rotate(old_params: &ScypherParams, new_params: &ScypherParams, input_file: Option<&str>) -> Result<Zeroizing<String>> {
+    println!("Key rotation: the decrypted phrase is never shown or saved");
+    println!("A wrong old password cannot be detected, so it is asked twice: still check the result before deleting the old backup");
+    println!("Old: Argon2id with {} iterations, {}KB memory, {} lanes",
+        old_params.iterations(), old_params.memory_cost(), old_params.lanes());
+    println!("New: Argon2id with {} iterations, {}KB memory, {} lanes\n",
+        new_params.iterations(), new_params.memory_cost(), new_params.lanes());
+
+    let encrypted = match input_file {
+        Some(file_path) => crate::cli::read_seed_from_file(file_path)?,
+        None => crate::cli::read_seed_interactive(true)?,
+    };
+
+    let detection = crate::bip39::detect_language(&encrypted)?;
+    let wordlist = detection.language.wordlist();
+    crate::bip39::validate_seed_phrase_with(&encrypted, wordlist)?;
+
+    let old_password = crate::cli::input::read_existing_password_confirmed("old password")?;
+    let new_password = crate::cli::input::read_new_password("new password")?;
+    if *old_password == *new_password && old_params == new_params {
+        return Err(SCypherError::InvalidRequest("The new password and parameters are the same as the old ones".to_string()));
+    }
+
+    println!("Processing with Argon2id key derivation (old and new keys)...");
+    rotate_phrase(&encrypted, wordlist, &old_password, old_params, &new_password, new_params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39::wordlist::ENGLISH;
+
+    #[test]
+    fn test_rotate_phrase() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let old_params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+        let new_params = ScypherParams::builder().iterations(2).memory_cost(8192).build().unwrap();
+
+        let encrypted = crypto::transform_seed_with_wordlist(phrase, "old_password", &old_params, &ENGLISH).unwrap();
+        let rotated = rotate_phrase(&encrypted, &ENGLISH, "old_password", &old_params, "new_password", &new_params).unwrap();
+        assert_ne!(rotated, encrypted);
+        assert_eq!(*crypto::transform_seed_with_wordlist(&rotated, "new_password", &new_params, &ENGLISH).unwrap(), phrase);
+    }
+}
@@ -0,0 +1,496 @@
+// src/cli/secure_input.rs - Entrada enmascarada de contraseñas y frases
+//
+// Cada sistema tiene su backend en modo raw: termios en Unix y la API de
+// consola Win32 en Windows. Los dos alimentan el mismo editor de línea, que
+// decodifica UTF-8 (entrada de IME para la lista japonesa), reconoce el
+// pegado entre corchetes (bracketed paste: los saltos de línea pegados no
+// envían la línea) e ignora flechas y demás secuencias de escape. Ctrl-C
+// llega como un byte más: se restaura el terminal y se devuelve
+// `Cancelled`, y si el proceso termina por otra vía la configuración
+// original se restaura al salir. Sin terminal (tubería) se lee una línea.
+//
+// En modo raw el terminal no genera señales, así que Ctrl-X no llega como
+// SIGQUIT a la tecla de pánico (`cli::panic_wipe`): el editor reconoce la
+// doble pulsación él mismo y borra la línea antes de llamarla.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{SCypherError, Result};
+
+/// Longitud máxima de la entrada en bytes
+const MAX_INPUT: usize = 1000;
+
+/// Tiempo máximo entre las dos pulsaciones de Ctrl-X de la tecla de pánico
+pub(crate) const PANIC_DOUBLE_PRESS: Duration = Duration::from_millis(2000);
+
+/// Qué se muestra por cada carácter tecleado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mask {
+    /// Un asterisco por carácter
+    Password,
+    /// Asteriscos, pero los espacios se ven: se pueden contar las palabras
+    Seed,
+}
+
+/// Respuesta del editor a un byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    None,
+    /// Mostrar este carácter de máscara
+    Echo(char),
+    /// Borrar el último carácter mostrado
+    Erase,
+    /// Borrar estos caracteres mostrados
+    EraseAll(usize),
+    Submit,
+    Cancel,
+    /// Primera pulsación de Ctrl-X
+    PanicHint,
+    /// Segunda pulsación de Ctrl-X a tiempo: la línea ya está borrada
+    PanicWipe,
+}
+
+/// Estado de una secuencia de escape en curso
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    Esc,
+    Csi { params: [u8; 8], len: usize },
+}
+
+/// Editor de línea enmascarada, independiente del backend
+struct LineEditor {
+    mask: Mask,
+    text: Zeroizing<String>,
+    utf8: [u8; 4],
+    utf8_len: usize,
+    escape: Escape,
+    pasting: bool,
+    /// Última pulsación de Ctrl-X
+    ctrl_x: Option<Instant>,
+}
+
+impl LineEditor {
+    fn new(mask: Mask) -> Self {
+        Self {
+            mask,
+            text: Zeroizing::new(String::with_capacity(MAX_INPUT)),
+            utf8: [0; 4],
+            utf8_len: 0,
+            escape: Escape::None,
+            pasting: false,
+            ctrl_x: None,
+        }
+    }
+
+    fn feed(&mut self, byte: u8) -> Action {
+        match self.escape {
+            Escape::Esc => {
+                // Alt+tecla y demás secuencias de dos bytes se ignoran
+                self.escape = if byte == b'[' { Escape::Csi { params: [0; 8], len: 0 } } else { Escape::None };
+                return Action::None;
+            }
+            Escape::Csi { mut params, len } => {
+                if (0x40..=0x7E).contains(&byte) {
+                    match (&params[..len], byte) {
+                        (b"200", b'~') => self.pasting = true,
+                        (b"201", b'~') => self.pasting = false,
+                        _ => {}
+                    }
+                    self.escape = Escape::None;
+                } else if len < params.len() {
+                    params[len] = byte;
+                    self.escape = Escape::Csi { params, len: len + 1 };
+                }
+                return Action::None;
+            }
+            Escape::None => {}
+        }
+
+        if self.utf8_len > 0 || byte >= 0x80 {
+            return self.feed_utf8(byte);
+        }
+
+        match byte {
+            0x1B => {
+                self.escape = Escape::Esc;
+                Action::None
+            }
+            b'\r' | b'\n' if self.pasting => match self.mask {
+                Mask::Seed => self.push(' '),
+                Mask::Password => Action::None,
+            },
+            b'\r' | b'\n' => Action::Submit,
+            b'\t' if self.mask == Mask::Seed => self.push(' '),
+            0x03 => Action::Cancel,
+            0x18 => self.ctrl_x(Instant::now()),
+            0x04 if self.text.is_empty() => Action::Cancel,
+            0x7F | 0x08 => match self.text.pop() {
+                Some(_) => Action::Erase,
+                None => Action::None,
+            },
+            0x15 => {
+                let shown = self.text.chars().count();
+                self.text.clear();
+                Action::EraseAll(shown)
+            }
+            0x20..=0x7E => self.push(byte as char),
+            _ => Action::None,
+        }
+    }
+
+    /// Ctrl-X en `now`: aviso la primera vez, borrado si es la segunda a tiempo
+    fn ctrl_x(&mut self, now: Instant) -> Action {
+        match self.ctrl_x.replace(now) {
+            Some(previous) if now.duration_since(previous) <= PANIC_DOUBLE_PRESS => {
+                self.text.zeroize();
+                self.utf8.zeroize();
+                Action::PanicWipe
+            }
+            _ => Action::PanicHint,
+        }
+    }
+
+    /// Acumular un carácter multibyte y añadirlo al completarse
+    fn feed_utf8(&mut self, byte: u8) -> Action {
+        let expected = match self.utf8[0] {
+            _ if self.utf8_len == 0 => match byte {
+                0xC2..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF4 => 4,
+                _ => return Action::None,
+            },
+            first if first >= 0xF0 => 4,
+            first if first >= 0xE0 => 3,
+            _ => 2,
+        };
+        self.utf8[self.utf8_len] = byte;
+        self.utf8_len += 1;
+        if self.utf8_len < expected {
+            return Action::None;
+        }
+
+        let decoded = std::str::from_utf8(&self.utf8[..expected]).ok().and_then(|text| text.chars().next());
+        self.utf8.zeroize();
+        self.utf8_len = 0;
+        decoded.map_or(Action::None, |c| self.push(c))
+    }
+
+    fn push(&mut self, c: char) -> Action {
+        if self.text.len() + c.len_utf8() > MAX_INPUT {
+            return Action::None;
+        }
+        self.text.push(c);
+        match self.mask {
+            Mask::Seed if c.is_whitespace() => Action::Echo(' '),
+            _ => Action::Echo('*'),
+        }
+    }
+}
+
+/// Mostrar el efecto de una acción en la línea
+fn echo(action: Action) {
+    let mut stdout = io::stdout();
+    match action {
+        Action::Echo(c) => { let _ = write!(stdout, "{}", c); }
+        Action::Erase => { let _ = write!(stdout, "\x08 \x08"); }
+        Action::EraseAll(shown) => {
+            for _ in 0..shown {
+                let _ = write!(stdout, "\x08 \x08");
+            }
+        }
+        Action::PanicHint | Action::PanicWipe => {
+            panic_key(action == Action::PanicWipe);
+            return;
+        }
+        Action::None | Action::Submit | Action::Cancel => return,
+    }
+    let _ = stdout.flush();
+}
+
+/// Pasar Ctrl-X a la tecla de pánico, si está armada (si no, se ignora)
+fn panic_key(second: bool) {
+    #[cfg(feature = "interactive")]
+    crate::cli::panic_wipe::key_pressed(second);
+    #[cfg(not(feature = "interactive"))]
+    let _ = second;
+}
+
+/// Leer una línea enmascarada del terminal (o una línea de stdin si no lo es)
+pub fn read_masked(mask: Mask) -> Result<Zeroizing<String>> {
+    let mut editor = LineEditor::new(mask);
+    match backend::read_into(&mut editor) {
+        Ok(true) => Ok(std::mem::take(&mut editor.text)),
+        Ok(false) => read_plain_line(),
+        Err(e) => {
+            println!();
+            Err(e)
+        }
+    }
+}
+
+/// Entrada sin terminal: una línea de stdin; el final de la entrada es un error
+fn read_plain_line() -> Result<Zeroizing<String>> {
+    let mut line = Zeroizing::new(String::new());
+    if io::stdin().read_line(&mut line).map_err(SCypherError::from)? == 0 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    let trimmed = line.trim_end_matches(['\r', '\n']).len();
+    line.truncate(trimmed);
+    Ok(line)
+}
+
+#[cfg(unix)]
+mod backend {
+    use std::io::Read;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Configuración original mientras el terminal está en modo raw
+    static ORIGINAL: Mutex<Option<libc::termios>> = Mutex::new(None);
+
+    /// Restaura el terminal si el proceso sale en modo raw (p.ej. `process::exit`)
+    extern "C" fn restore_at_exit() {
+        if let Ok(mut original) = ORIGINAL.try_lock() {
+            if let Some(termios) = original.take() {
+                // SAFETY: configuración leída con tcgetattr
+                unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) };
+            }
+        }
+    }
+
+    /// Terminal en modo raw; se restaura al soltarlo, también con pánico
+    struct RawTerminal {
+        original: libc::termios,
+        bracketed_paste: bool,
+    }
+
+    impl RawTerminal {
+        fn enable() -> Option<Self> {
+            static AT_EXIT: std::sync::Once = std::sync::Once::new();
+
+            // SAFETY: tcgetattr/tcsetattr sobre stdin con estructuras locales
+            let original = unsafe {
+                if libc::isatty(libc::STDIN_FILENO) != 1 {
+                    return None;
+                }
+                let mut original: libc::termios = std::mem::zeroed();
+                if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                    return None;
+                }
+                let mut raw = original;
+                // Sin eco ni línea; Ctrl-C, Ctrl-Z y Ctrl-V llegan como bytes
+                raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::ISIG | libc::IEXTEN);
+                raw.c_iflag &= !(libc::ICRNL | libc::IXON);
+                raw.c_cc[libc::VMIN] = 1;
+                raw.c_cc[libc::VTIME] = 0;
+
+                AT_EXIT.call_once(|| { libc::atexit(restore_at_exit); });
+                if let Ok(mut saved) = ORIGINAL.lock() {
+                    *saved = Some(original);
+                }
+                if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                    ORIGINAL.lock().ok().and_then(|mut saved| saved.take());
+                    return None;
+                }
+                original
+            };
+
+            // Una consola serie no entiende el pegado entre corchetes
+            let bracketed_paste = !crate::cli::display::is_plain_terminal();
+            if bracketed_paste {
+                print!("\x1b[?2004h");
+                let _ = io::stdout().flush();
+            }
+            Some(Self { original, bracketed_paste })
+        }
+    }
+
+    impl Drop for RawTerminal {
+        fn drop(&mut self) {
+            if self.bracketed_paste {
+                print!("\x1b[?2004l");
+                let _ = io::stdout().flush();
+            }
+            // SAFETY: restaura la configuración leída en `enable`
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+            ORIGINAL.lock().ok().and_then(|mut saved| saved.take());
+        }
+    }
+
+    /// Alimentar el editor hasta Enter; `false` si stdin no es un terminal
+    pub(super) fn read_into(editor: &mut LineEditor) -> Result<bool> {
+        let Some(_raw) = RawTerminal::enable() else {
+            return Ok(false);
+        };
+
+        let mut stdin = io::stdin().lock();
+        let mut byte = [0u8; 1];
+        loop {
+            if stdin.read(&mut byte).map_err(SCypherError::from)? == 0 {
+                return Err(SCypherError::Cancelled);
+            }
+            let action = editor.feed(byte[0]);
+            byte[0] = 0;
+            match action {
+                Action::Submit => return Ok(true),
+                Action::Cancel => return Err(SCypherError::Cancelled),
+                action => echo(action),
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod backend {
+    use winapi::shared::minwindef::DWORD;
+    use winapi::um::consoleapi::{GetConsoleMode, ReadConsoleW, SetConsoleMode};
+    use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+    use winapi::um::processenv::GetStdHandle;
+    use winapi::um::winbase::STD_INPUT_HANDLE;
+    use winapi::um::winnt::HANDLE;
+    use winapi::um::wincon::{ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT};
+
+    use super::*;
+
+    /// Consola sin eco ni edición de línea; se restaura al soltarla
+    struct RawConsole {
+        handle: HANDLE,
+        original: DWORD,
+    }
+
+    impl RawConsole {
+        fn enable() -> Option<Self> {
+            // SAFETY: llamadas de consola sobre el handle de entrada estándar
+            unsafe {
+                let handle = GetStdHandle(STD_INPUT_HANDLE);
+                let mut original: DWORD = 0;
+                if handle == INVALID_HANDLE_VALUE || GetConsoleMode(handle, &mut original) == 0 {
+                    return None;
+                }
+                // Sin ENABLE_PROCESSED_INPUT, Ctrl-C llega como carácter
+                let raw = original & !(ENABLE_ECHO_INPUT | ENABLE_LINE_INPUT | ENABLE_PROCESSED_INPUT);
+                if SetConsoleMode(handle, raw) == 0 {
+                    return None;
+                }
+                Some(Self { handle, original })
+            }
+        }
+    }
+
+    impl Drop for RawConsole {
+        fn drop(&mut self) {
+            // SAFETY: restaura el modo leído en `enable`
+            unsafe { SetConsoleMode(self.handle, self.original) };
+        }
+    }
+
+    /// Alimentar el editor hasta Enter; `false` si stdin no es una consola
+    pub(super) fn read_into(editor: &mut LineEditor) -> Result<bool> {
+        let Some(console) = RawConsole::enable() else {
+            return Ok(false);
+        };
+
+        // ReadConsoleW entrega UTF-16 (también lo que compone el IME)
+        let mut high_surrogate: Option<u16> = None;
+        loop {
+            let mut unit: u16 = 0;
+            let mut read: DWORD = 0;
+            // SAFETY: buffer de una unidad UTF-16 en la pila
+            let ok = unsafe {
+                ReadConsoleW(console.handle, (&mut unit as *mut u16).cast(), 1, &mut read, std::ptr::null_mut())
+            };
+            if ok == 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            if read == 0 {
+                continue;
+            }
+
+            let (units, count) = match high_surrogate.take() {
+                Some(high) => ([high, unit], 2),
+                None if (0xD800..0xDC00).contains(&unit) => {
+                    high_surrogate = Some(unit);
+                    continue;
+                }
+                None => ([unit, 0], 1),
+            };
+            let Some(Ok(c)) = char::decode_utf16(units[..count].iter().copied()).next() else {
+                continue;
+            };
+
+            let mut utf8 = [0u8; 4];
+            for &byte in c.encode_utf8(&mut utf8).as_bytes() {
+                match editor.feed(byte) {
+                    Action::Submit => return Ok(true),
+                    Action::Cancel => return Err(SCypherError::Cancelled),
+                    action => echo(action),
+                }
+            }
+            utf8.zeroize();
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod backend {
+    use super::*;
+
+    pub(super) fn read_into(_editor: &mut LineEditor) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(editor: &mut LineEditor, bytes: &[u8]) -> Vec<Action> {
+        bytes.iter().map(|&byte| editor.feed(byte)).collect()
+    }
+
+    #[test]
+    fn test_line_editor() {
+        // UTF-8 multibyte (IME), borrado y envío
+        let mut editor = LineEditor::new(Mask::Seed);
+        let actions = feed_all(&mut editor, "あい\u{3000}う".as_bytes());
+        assert_eq!(actions.iter().filter(|action| **action == Action::Echo('*')).count(), 3);
+        assert!(actions.contains(&Action::Echo(' ')));
+        assert_eq!(editor.feed(0x7F), Action::Erase);
+        assert_eq!(editor.feed(b'\r'), Action::Submit);
+        assert_eq!(*editor.text, "あい\u{3000}");
+
+        // Pegado entre corchetes: los saltos de línea no envían; las flechas se ignoran
+        let mut editor = LineEditor::new(Mask::Seed);
+        let actions = feed_all(&mut editor, b"\x1b[200~abandon\nabout\x1b[201~\x1b[D");
+        assert!(!actions.contains(&Action::Submit));
+        assert_eq!(*editor.text, "abandon about");
+
+        // Contraseña: Ctrl-U borra todo, Ctrl-C cancela
+        let mut editor = LineEditor::new(Mask::Password);
+        feed_all(&mut editor, b"secret");
+        assert_eq!(editor.feed(0x15), Action::EraseAll(6));
+        assert!(editor.text.is_empty());
+        assert_eq!(editor.feed(0x03), Action::Cancel);
+    }
+
+    #[test]
+    fn test_ctrl_x_double_press() {
+        // Ctrl-X dos veces seguidas: aviso y después borrado de lo tecleado
+        let mut editor = LineEditor::new(Mask::Password);
+        feed_all(&mut editor, b"secret");
+        assert_eq!(feed_all(&mut editor, &[0x18, 0x18]), [Action::PanicHint, Action::PanicWipe]);
+        assert!(editor.text.is_empty());
+
+        // Demasiado separadas, la segunda vuelve a ser un aviso
+        let mut editor = LineEditor::new(Mask::Seed);
+        let start = Instant::now();
+        assert_eq!(editor.ctrl_x(start), Action::PanicHint);
+        assert_eq!(editor.ctrl_x(start + PANIC_DOUBLE_PRESS + Duration::from_millis(1)), Action::PanicHint);
+        assert_eq!(editor.ctrl_x(start + PANIC_DOUBLE_PRESS * 2), Action::PanicWipe);
+    }
+}
@@ -0,0 +1,410 @@
+// src/cli/serve.rs - Daemon JSON-RPC sobre socket Unix
+//
+// Protocolo: JSON-RPC 2.0, una petición por línea y una respuesta por línea.
+// Métodos: `transform`, `validate`, `generate` y `version`, con los mismos
+// argumentos que las operaciones de `scypher api` (ver `crate::protocol`).
+// Cada línea se lee en un buffer fijo de `MAX_REQUEST` bytes que nunca se
+// reubica, así que no quedan copias de secretos sin limpiar; una línea más
+// larga se rechaza. Las respuestas serializadas también se limpian en cuanto
+// se envían. Las derivaciones simultáneas de todas las conexiones comparten
+// un presupuesto de memoria, como en `batch`: una petición que no cabe
+// espera a que termine otra.
+
+use std::io::{self, Read, Write};
+use std::sync::{Condvar, Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use zeroize::Zeroizing;
+
+use crate::protocol::{self, ApiResult, Operation};
+use crate::error::{IoOperation, SCypherError};
+
+// Códigos de error estándar de JSON-RPC 2.0
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+/// Error de la aplicación; `data` contiene el `SCypherError` serializado
+const APPLICATION_ERROR: i32 = -32000;
+
+/// Tamaño máximo de una petición, salto de línea incluido
+const MAX_REQUEST: usize = 64 * 1024;
+
+/// Petición JSON-RPC; `params` se conserva sin interpretar hasta conocer el método
+#[derive(Deserialize)]
+struct RpcRequest<'a> {
+    jsonrpc: String,
+    #[serde(default)]
+    id: serde_json::Value,
+    method: String,
+    #[serde(borrow, default)]
+    params: Option<&'a RawValue>,
+}
+
+#[derive(Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<SCypherError>,
+}
+
+#[derive(Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<ApiResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn error(id: serde_json::Value, code: i32, message: String) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(RpcError { code, message, data: None }),
+        }
+    }
+
+    fn application_error(id: serde_json::Value, error: SCypherError) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(RpcError {
+                code: APPLICATION_ERROR,
+                message: error.to_string(),
+                data: Some(error),
+            }),
+        }
+    }
+
+    fn to_line(&self) -> Zeroizing<String> {
+        // La serialización de estos tipos no puede fallar
+        Zeroizing::new(serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Memoria de Argon2id (KB) repartida entre todas las conexiones
+pub struct MemoryBudget {
+    total_kb: u64,
+    available_kb: Mutex<u64>,
+    released: Condvar,
+}
+
+impl MemoryBudget {
+    pub fn new(total_kb: u64) -> Self {
+        Self { total_kb, available_kb: Mutex::new(total_kb), released: Condvar::new() }
+    }
+
+    /// Reservar `kb` esperando a que quepan; `InsufficientMemory` si no caben nunca
+    fn reserve(&self, kb: u64) -> crate::error::Result<Reservation<'_>> {
+        if kb > self.total_kb {
+            return Err(SCypherError::InsufficientMemory);
+        }
+        let available = self.available_kb.lock().unwrap_or_else(|e| e.into_inner());
+        let mut available = self.released
+            .wait_while(available, |available| *available < kb)
+            .unwrap_or_else(|e| e.into_inner());
+        *available -= kb;
+        Ok(Reservation { budget: self, kb })
+    }
+}
+
+/// Memoria reservada para una derivación; se devuelve al soltarse
+struct Reservation<'a> {
+    budget: &'a MemoryBudget,
+    kb: u64,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        *self.budget.available_kb.lock().unwrap_or_else(|e| e.into_inner()) += self.kb;
+        self.budget.released.notify_all();
+    }
+}
+
+/// Una línea del protocolo leída por `LineReader`
+enum Line<'a> {
+    Request(&'a [u8]),
+    /// Más de `MAX_REQUEST` bytes sin salto de línea
+    TooLong,
+}
+
+/// Lector de líneas sobre un buffer fijo que se limpia al soltarse
+///
+/// El buffer no crece: los bytes de una línea ya procesada se borran al
+/// pedir la siguiente.
+struct LineReader<R> {
+    inner: R,
+    buffer: Zeroizing<Vec<u8>>,
+    filled: usize,
+    consumed: usize,
+}
+
+impl<R: Read> LineReader<R> {
+    fn new(inner: R) -> Self {
+        Self { inner, buffer: Zeroizing::new(vec![0; MAX_REQUEST]), filled: 0, consumed: 0 }
+    }
+
+    /// Siguiente línea sin el salto; `None` al cerrarse la conexión
+    fn next_line(&mut self) -> io::Result<Option<Line<'_>>> {
+        // Quitar la línea anterior y borrar los bytes que quedan libres
+        self.buffer.copy_within(self.consumed..self.filled, 0);
+        self.buffer[self.filled - self.consumed..self.filled].fill(0);
+        self.filled -= self.consumed;
+        self.consumed = 0;
+
+        loop {
+            if let Some(end) = self.buffer[..self.filled].iter().position(|&byte| byte == b'\n') {
+                self.consumed = end + 1;
+                return Ok(Some(Line::Request(&self.buffer[..end])));
+            }
+            if self.filled == MAX_REQUEST {
+                return Ok(Some(Line::TooLong));
+            }
+            match self.inner.read(&mut self.buffer[self.filled..]) {
+                Ok(0) if self.filled == 0 => return Ok(None),
+                // Última línea sin salto
+                Ok(0) => {
+                    self.consumed = self.filled;
+                    return Ok(Some(Line::Request(&self.buffer[..self.filled])));
+                }
+                Ok(read) => self.filled += read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Deserializar los parámetros de un método (objeto vacío si se omiten)
+fn parse_params<'de, T: Deserialize<'de>>(params: Option<&'de RawValue>) -> serde_json::Result<T> {
+    serde_json::from_str(params.map_or("{}", RawValue::get))
+}
+
+/// Procesar una línea del protocolo y devolver la respuesta serializada
+pub fn handle_line(line: &str, budget: &MemoryBudget) -> Zeroizing<String> {
+    let response = match serde_json::from_str::<RpcRequest>(line) {
+        Err(e) => RpcResponse::error(serde_json::Value::Null, PARSE_ERROR, e.to_string()),
+        Ok(request) if request.jsonrpc != "2.0" => {
+            RpcResponse::error(request.id, INVALID_REQUEST, "jsonrpc must be \"2.0\"".to_string())
+        }
+        Ok(request) => dispatch(request, budget),
+    };
+    response.to_line()
+}
+
+fn dispatch(request: RpcRequest, budget: &MemoryBudget) -> RpcResponse {
+    let operation = match request.method.as_str() {
+        "transform" => parse_params(request.params).map(Operation::Transform),
+        "validate" => parse_params(request.params).map(Operation::Validate),
+        "generate" => parse_params(request.params).map(Operation::Generate),
+        "version" => Ok(Operation::Version),
+        method => {
+            return RpcResponse::error(request.id, METHOD_NOT_FOUND, format!("Unknown method '{}'", method));
+        }
+    };
+
+    let operation = match operation {
+        Ok(operation) => operation,
+        Err(e) => return RpcResponse::error(request.id, INVALID_PARAMS, e.to_string()),
+    };
+
+    // Solo `transform` deriva con Argon2id; su memoria se reserva hasta terminar
+    let _reservation = match &operation {
+        Operation::Transform(args) => match budget.reserve(u64::from(args.params.memory_cost())) {
+            Ok(reservation) => Some(reservation),
+            Err(error) => return RpcResponse::application_error(request.id, error),
+        },
+        _ => None,
+    };
+
+    match protocol::execute_operation(operation) {
+        Ok(result) => RpcResponse {
+            jsonrpc: "2.0",
+            id: request.id,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => RpcResponse::application_error(request.id, error),
+    }
+}
+
+/// Atender una conexión hasta que se cierre o envíe una línea demasiado larga
+fn serve_connection(stream: impl Read, writer: &mut impl Write, budget: &MemoryBudget) {
+    let mut reader = LineReader::new(stream);
+    loop {
+        let response = match reader.next_line() {
+            Ok(None) | Err(_) => break,
+            Ok(Some(Line::TooLong)) => {
+                let response = RpcResponse::error(serde_json::Value::Null, INVALID_REQUEST,
+                    format!("request longer than {} bytes", MAX_REQUEST)).to_line();
+                let _ = writeln!(writer, "{}", response.as_str());
+                break;
+            }
+            Ok(Some(Line::Request(line))) => match std::str::from_utf8(line) {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => handle_line(line, budget),
+                Err(e) => RpcResponse::error(serde_json::Value::Null, PARSE_ERROR, e.to_string()).to_line(),
+            },
+        };
+        if writeln!(writer, "{}", response.as_str()).is_err() {
+            break;
+        }
+    }
+}
+
+/// Servir peticiones en un socket Unix hasta que el proceso termine
+///
+/// Un socket obsoleto en la ruta se reemplaza; el nuevo socket solo es
+/// accesible por el propietario (0600) desde que se crea. Cada conexión se
+/// atiende en su hilo, y todas comparten `memory_budget_kb` para Argon2id.
+#[cfg(unix)]
+pub fn run_server(socket_path: &str, memory_budget_kb: u64) -> crate::error::Result<()> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+    use std::sync::Arc;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(socket_path) {
+        if !metadata.file_type().is_socket() {
+            return Err(SCypherError::file_at(socket_path, "Path exists and is not a socket"));
+        }
+        std::fs::remove_file(socket_path)
+            .map_err(|e| SCypherError::io_at(IoOperation::Remove, socket_path, e))?;
+    }
+
+    // Con el umask habitual el socket nacería abierto a otros usuarios hasta
+    // el chmod; con 077 no hay ese intervalo
+    let previous_umask = unsafe { libc::umask(0o077) };
+    let bound = UnixListener::bind(socket_path);
+    unsafe { libc::umask(previous_umask) };
+    let listener = bound.map_err(|e| SCypherError::io_at(IoOperation::Bind, socket_path, e))?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| SCypherError::io_at(IoOperation::SetPermissions, socket_path, e))?;
+
+    eprintln!("Listening on {} (JSON-RPC 2.0, one request per line)", socket_path);
+
+    let budget = Arc::new(MemoryBudget::new(memory_budget_kb));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Connection failed: {}", e);
+                continue;
+            }
+        };
+
+        let budget = Arc::clone(&budget);
+        std::thread::spawn(move || {
+            let mut writer = match stream.try_clone() {
+                Ok(writer) => writer,
+                Err(_) => return,
+            };
+            serve_connection(stream, &mut writer, &budget);
+        });
+    }
+
+    Ok(())
+}
+
+/// Los sockets Unix no están disponibles en esta plataforma
+#[cfg(not(unix))]
+pub fn run_server(_socket_path: &str, _memory_budget_kb: u64) -> crate::error::Result<()> {
+    Err(SCypherError::UnsupportedPlatform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn call(line: &str) -> serde_json::Value {
+        let budget = MemoryBudget::new(crate::crypto::batch::DEFAULT_MEMORY_BUDGET_KB);
+        serde_json::from_str(&handle_line(line, &budget)).unwrap()
+    }
+
+    #[test]
+    fn test_rpc_methods() {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "transform",
+            "params": {
+                "phrase": TEST_PHRASE,
+                "password": "test_password",
+                "params": {"iterations": 1, "memory_cost": 8192},
+            },
+        });
+
+        let response = call(&request.to_string());
+        assert_eq!(response["id"], 7);
+        assert!(response["result"]["phrase"].is_string());
+
+        let response = call(r#"{"jsonrpc": "2.0", "id": "a", "method": "generate"}"#);
+        assert_eq!(response["result"]["phrase"].as_str().unwrap().split_whitespace().count(), 12);
+    }
+
+    #[test]
+    fn test_rpc_errors() {
+        assert_eq!(call("{")["error"]["code"], PARSE_ERROR);
+        assert_eq!(call(r#"{"jsonrpc": "1.0", "id": 1, "method": "version"}"#)["error"]["code"], INVALID_REQUEST);
+        assert_eq!(call(r#"{"jsonrpc": "2.0", "id": 1, "method": "nope"}"#)["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(call(r#"{"jsonrpc": "2.0", "id": 1, "method": "validate"}"#)["error"]["code"], INVALID_PARAMS);
+
+        let response = call(r#"{"jsonrpc": "2.0", "id": 1, "method": "generate", "params": {"words": 13}}"#);
+        assert_eq!(response["error"]["code"], APPLICATION_ERROR);
+        assert_eq!(response["error"]["data"]["code"], 2002);
+    }
+
+    #[test]
+    fn test_line_limits() {
+        // Varias peticiones en una lectura, una línea vacía y la última sin salto
+        let input = b"{\"jsonrpc\": \"2.0\", \"id\": 1, \"method\": \"version\"}\n\n{\"jsonrpc\": \"2.0\", \"id\": 2, \"method\": \"version\"}";
+        let mut output = Vec::new();
+        serve_connection(&input[..], &mut output, &MemoryBudget::new(8192));
+        let responses: Vec<serde_json::Value> = output.split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!((responses.len(), &responses[0]["id"], &responses[1]["id"]), (2, &1.into(), &2.into()));
+
+        // Una línea demasiado larga se rechaza y cierra la conexión
+        let mut input = vec![b' '; MAX_REQUEST + 10];
+        input.extend_from_slice(b"\n{\"jsonrpc\": \"2.0\", \"id\": 3, \"method\": \"version\"}\n");
+        let mut output = Vec::new();
+        serve_connection(&input[..], &mut output, &MemoryBudget::new(8192));
+        let response: serde_json::Value = serde_json::from_slice(output.split(|&b| b == b'\n').next().unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+        assert_eq!(output.iter().filter(|&&b| b == b'\n').count(), 1);
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "transform",
+            "params": {"phrase": TEST_PHRASE, "password": "test_password", "params": {"iterations": 1, "memory_cost": 16384}},
+        }).to_string();
+        let response: serde_json::Value = serde_json::from_str(&handle_line(&request, &MemoryBudget::new(8192))).unwrap();
+        assert_eq!(response["error"]["data"]["code"], 1001);
+
+        // Una reserva espera a que se libere la anterior
+        let budget = std::sync::Arc::new(MemoryBudget::new(8192));
+        let first = budget.reserve(8192).unwrap();
+        let waiting = {
+            let budget = std::sync::Arc::clone(&budget);
+            std::thread::spawn(move || budget.reserve(8192).map(|_| ()).is_ok())
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!waiting.is_finished());
+        drop(first);
+        assert!(waiting.join().unwrap());
+    }
+}
@@ -0,0 +1,255 @@
+// src/cli/shell.rs - Sesión interactiva (REPL) con clave en caché
+//
+// `unlock` pide la contraseña una vez; a partir de ahí cada tamaño de frase
+// deriva su keystream Argon2id una sola vez y las transformaciones
+// siguientes son inmediatas. `lock` limpia la contraseña y los keystreams, y
+// un hilo vigilante hace lo mismo tras un periodo de inactividad aunque el
+// usuario no escriba nada.
+
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use zeroize::Zeroizing;
+
+use crate::cli::colors;
+use crate::crypto::{ScypherParams, SessionKey};
+use crate::error::{SCypherError, Result};
+
+/// Inactividad por defecto antes del bloqueo automático
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Comandos del shell
+#[derive(Debug, PartialEq, Eq)]
+enum ShellCommand<'a> {
+    Unlock,
+    Lock,
+    /// Frase en la misma línea, o `None` para pedirla sin eco
+    Transform(Option<&'a str>),
+    Validate(Option<&'a str>),
+    Status,
+    Help,
+    Exit,
+    Empty,
+    Unknown(&'a str),
+}
+
+fn parse_command(line: &str) -> ShellCommand<'_> {
+    let line = line.trim();
+    let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let argument = Some(rest.trim()).filter(|rest| !rest.is_empty());
+
+    match command {
+        "" => ShellCommand::Empty,
+        "unlock" => ShellCommand::Unlock,
+        "lock" => ShellCommand::Lock,
+        "transform" | "t" => ShellCommand::Transform(argument),
+        "validate" | "v" => ShellCommand::Validate(argument),
+        "status" => ShellCommand::Status,
+        "help" | "?" => ShellCommand::Help,
+        "exit" | "quit" => ShellCommand::Exit,
+        other => ShellCommand::Unknown(other),
+    }
+}
+
+/// Estado compartido con el hilo de bloqueo automático
+struct SessionState {
+    key: Option<SessionKey>,
+    last_activity: Instant,
+}
+
+impl SessionState {
+    /// Bloquear si se superó la inactividad; devuelve si se bloqueó ahora
+    fn expire(&mut self, idle_timeout: Duration) -> bool {
+        if self.key.is_some() && self.last_activity.elapsed() >= idle_timeout {
+            self.key = None;
+            return true;
+        }
+        false
+    }
+}
+
+fn lock_state(state: &Mutex<SessionState>) -> MutexGuard<'_, SessionState> {
+    // Un pánico en otro hilo no debe impedir bloquear la sesión
+    state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Vigilar la inactividad hasta que el shell termine
+fn spawn_idle_watcher(state: &Arc<Mutex<SessionState>>, idle_timeout: Duration) {
+    let state = Arc::downgrade(state);
+    let interval = idle_timeout.min(Duration::from_secs(1));
+
+    std::thread::spawn(move || {
+        while let Some(state) = state.upgrade() {
+            if lock_state(&state).expire(idle_timeout) {
+                println!("\n{}🔒 Session locked after inactivity{}", colors::WARNING, colors::RESET);
+                print!("scypher> ");
+                let _ = io::stdout().flush();
+            }
+            drop(state);
+            std::thread::sleep(interval);
+        }
+    });
+}
+
+/// Leer una línea enmascarada (frases y contraseñas)
+fn read_hidden(prompt: &str) -> Result<Zeroizing<String>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let line = crate::cli::secure_input::read_masked(crate::cli::secure_input::Mask::Seed)?;
+    println!();
+    Ok(Zeroizing::new(line.trim().to_string()))
+}
+
+fn print_help() {
+    println!("Commands:");
+    println!("  unlock               Enter the password and start a session");
+    println!("  lock                 Wipe the cached key immediately");
+    println!("  transform [PHRASE]   Encrypt/decrypt a phrase (prompted without echo if omitted)");
+    println!("  validate [PHRASE]    Check words, length and checksum");
+    println!("  status               Show session state");
+    println!("  exit                 Lock and leave the shell");
+}
+
+/// Ejecutar el shell interactivo hasta `exit` o fin de la entrada
+pub fn run_shell(params: ScypherParams, idle_timeout: Duration) -> Result<()> {
+    let state = Arc::new(Mutex::new(SessionState { key: None, last_activity: Instant::now() }));
+    spawn_idle_watcher(&state, idle_timeout);
+
+    println!("SCypher shell - Argon2id with {} iterations, {}KB memory", params.iterations(), params.memory_cost());
+    println!("Auto-lock after {}s of inactivity. Type 'help' for commands.", idle_timeout.as_secs());
+    if crate::cli::panic_wipe::arm() {
+        println!("Ctrl-X twice wipes the screen and exits immediately.");
+    }
+    println!();
+
+    let stdin = io::stdin();
+    loop {
+        print!("scypher> ");
+        io::stdout().flush()?;
+
+        let mut line = Zeroizing::new(String::new());
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+
+        {
+            let mut state = lock_state(&state);
+            if state.expire(idle_timeout) {
+                println!("{}🔒 Session locked after inactivity{}", colors::WARNING, colors::RESET);
+            }
+            state.last_activity = Instant::now();
+        }
+
+        match parse_command(&line) {
+            ShellCommand::Empty => {}
+            ShellCommand::Exit => break,
+            ShellCommand::Help => print_help(),
+            ShellCommand::Unknown(command) => println!("Unknown command '{}'. Type 'help'.", command),
+
+            ShellCommand::Unlock => {
+                let password = crate::cli::read_password_secure()?;
+                let mut state = lock_state(&state);
+                state.key = Some(SessionKey::unlock(&password, params.clone()));
+                state.last_activity = Instant::now();
+                println!("{}🔓 Session unlocked{}", colors::SUCCESS, colors::RESET);
+            }
+
+            ShellCommand::Lock => {
+                lock_state(&state).key = None;
+                println!("🔒 Session locked");
+            }
+
+            ShellCommand::Status => {
+                let state = lock_state(&state);
+                match &state.key {
+                    Some(_) => {
+                        let remaining = idle_timeout.saturating_sub(state.last_activity.elapsed());
+                        println!("Unlocked (auto-lock in {}s)", remaining.as_secs());
+                    }
+                    None => println!("Locked"),
+                }
+            }
+
+            ShellCommand::Validate(phrase) => {
+                let prompted;
+                let phrase = match phrase {
+                    Some(phrase) => phrase,
+                    None => {
+                        prompted = read_hidden("Phrase: ")?;
+                        prompted.as_str()
+                    }
+                };
+                let analysis = crate::bip39::analyze_seed_phrase(phrase);
+                println!("{}", analysis.generate_report());
+            }
+
+            ShellCommand::Transform(phrase) => {
+                if lock_state(&state).key.is_none() {
+                    println!("{}", SCypherError::SessionLocked);
+                    continue;
+                }
+
+                let prompted;
+                let phrase = match phrase {
+                    Some(phrase) => phrase,
+                    None => {
+                        prompted = read_hidden("Phrase: ")?;
+                        prompted.as_str()
+                    }
+                };
+
+                // La sesión pudo bloquearse mientras se escribía la frase
+                let result = match lock_state(&state).key.as_mut() {
+                    Some(key) => {
+                        if !key.is_cached(phrase.split_whitespace().count()) {
+                            println!("Deriving key for this phrase length...");
+                        }
+                        key.transform(phrase).map(Zeroizing::new)
+                    }
+                    None => Err(SCypherError::SessionLocked),
+                };
+
+                match result {
+                    Ok(result) => println!("{}{}{}", colors::BRIGHT, result.as_str(), colors::RESET),
+                    Err(e) => println!("{}Error: {}{}", colors::ERROR, e, colors::RESET),
+                }
+                // Un Ctrl-C que interrumpió la derivación no afecta a la siguiente
+                crate::security::interrupt::reset();
+                lock_state(&state).last_activity = Instant::now();
+            }
+        }
+    }
+
+    // Limpiar la clave al salir
+    lock_state(&state).key = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("  "), ShellCommand::Empty);
+        assert_eq!(parse_command("lock\n"), ShellCommand::Lock);
+        assert_eq!(parse_command("transform"), ShellCommand::Transform(None));
+        assert_eq!(parse_command("t  abandon about \n"), ShellCommand::Transform(Some("abandon about")));
+        assert_eq!(parse_command("frobnicate x"), ShellCommand::Unknown("frobnicate"));
+    }
+
+    #[test]
+    fn test_idle_expiry_wipes_key() {
+        let params = crate::crypto::TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let mut state = SessionState {
+            key: Some(SessionKey::unlock("test_password", params)),
+            last_activity: Instant::now(),
+        };
+
+        assert!(!state.expire(Duration::from_secs(60)));
+        assert!(state.expire(Duration::ZERO));
+        assert!(state.key.is_none());
+        assert!(!state.expire(Duration::ZERO));
+    }
+}
@@ -1,19 +1,20 @@
 //! Modo silent para scripting sin prompts interactivos
 
 use std::io::{self, Read};
-use crate::error::{SCypherError, Result};
+use zeroize::Zeroizing;
+use crate::error::{SCypherError, Parameter, Result};
 
 /// Lee seed phrase desde stdin sin prompts
-pub fn read_seed_from_stdin() -> Result<String> {
-    let mut buffer = String::new();
+pub fn read_seed_from_stdin() -> Result<Zeroizing<String>> {
+    let mut buffer = Zeroizing::new(String::new());
 
     // Leer solo la primera línea para la seed phrase
     io::stdin()
         .read_line(&mut buffer)
-        .map_err(|e| SCypherError::IoError(e))?;
+        .map_err(SCypherError::from)?;
 
     // Limpiar input: remover salto de línea y espacios extra
-    let cleaned = buffer.trim().to_string();
+    let cleaned = Zeroizing::new(buffer.trim().to_string());
 
     if cleaned.is_empty() {
         return Err(SCypherError::InvalidSeedPhrase);
@@ -23,14 +24,14 @@ pub fn read_seed_from_stdin() -> Result<String> {
 }
 
 /// Lee contraseña desde stdin sin prompts
-pub fn read_password_from_stdin() -> Result<String> {
-    let mut password = String::new();
+pub fn read_password_from_stdin() -> Result<Zeroizing<String>> {
+    let mut password = Zeroizing::new(String::new());
 
     io::stdin()
         .read_line(&mut password)
-        .map_err(|e| SCypherError::IoError(e))?;
+        .map_err(SCypherError::from)?;
 
-    let password = password.trim().to_string();
+    let password = Zeroizing::new(password.trim().to_string());
 
     if password.is_empty() {
         return Err(SCypherError::InvalidPassword);
@@ -91,17 +92,17 @@ pub fn read_iterations_from_stdin() -> Result<u32> {
 
     io::stdin()
         .read_line(&mut input)
-        .map_err(|e| SCypherError::IoError(e))?;
+        .map_err(SCypherError::from)?;
 
     let iterations_str = input.trim();
 
     iterations_str.parse::<u32>()
-        .map_err(|_| SCypherError::InvalidIterations(iterations_str.to_string()))
+        .map_err(|_| SCypherError::invalid_parameter(Parameter::Iterations, iterations_str, "a positive number"))
 }
 
 impl InputType {
     /// Obtiene el contenido según el tipo detectado
-    pub fn get_content(&self) -> Result<String> {
+    pub fn get_content(&self) -> Result<Zeroizing<String>> {
         match self {
             InputType::File(path) => {
                 crate::cli::read_seed_from_file(path)
@@ -109,10 +110,10 @@ impl InputType {
             InputType::ProbableFile(path) => {
                 match crate::cli::read_seed_from_file(path) {
                     Ok(content) => Ok(content),
-                    Err(_) => Ok(path.clone()),
+                    Err(_) => Ok(Zeroizing::new(path.clone())),
                 }
             }
-            InputType::SeedPhrase(phrase) => Ok(phrase.clone()),
+            InputType::SeedPhrase(phrase) => Ok(Zeroizing::new(phrase.clone())),
         }
     }
 }
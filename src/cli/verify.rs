@@ -0,0 +1,119 @@
+// src/cli/verify.rs - Simulacro de recuperación de una copia de seguridad
+//
+// Descifra la frase copiada de la copia en papel o QR y compara la huella
+// del resultado con la que se anotó al crear la copia: la huella maestra
+// BIP32 (la que muestran las carteras) o el identificador `fp1...` de
+// `scypher fingerprint`, que no enlaza con la cartera. El texto descifrado
+// solo se usa para calcular la huella: nunca se muestra ni se guarda.
+
+
+use crate::cli::colors;
+use crate::crypto::{self, fingerprint, ScypherParams};
+use crate::error::{SCypherError, Result};
+use crate::wallet::{descriptor, mnemonic_to_seed, ExtendedPrivateKey};
+
+/// Leer una huella maestra de 8 dígitos hexadecimales (p.ej. `73c5da0a`)
+pub fn parse_fingerprint(text: &str) -> Result<[u8; 4]> {
+    let bytes = hex::decode(text.trim())
+        .map_err(|_| SCypherError::InvalidRequest(format!("Invalid fingerprint '{}': expected 8 hex digits", text)))?;
+    bytes.try_into()
+        .map_err(|_| SCypherError::InvalidRequest(format!("Invalid fingerprint '{}': expected 8 hex digits", text)))
+}
+
+/// Huella anotada al crear la copia
+pub enum ExpectedFingerprint {
+    /// Huella maestra BIP32
+    Master([u8; 4]),
+    /// Identificador `fp1...` con su salt
+    PhraseId { id: String, salt: String },
+}
+
+impl ExpectedFingerprint {
+    /// Leer una huella BIP32 (8 hex) o un identificador `fp1...`
+    pub fn parse(text: &str, salt: Option<&str>) -> Result<Self> {
+        if text.trim().to_ascii_lowercase().starts_with(fingerprint::PREFIX) {
+            let id = fingerprint::parse_phrase_id(text)?;
+            return Ok(Self::PhraseId { id, salt: salt.unwrap_or_default().to_string() });
+        }
+        if salt.is_some() {
+            return Err(SCypherError::InvalidRequest("--salt only applies to fp1 phrase fingerprints".to_string()));
+        }
+        parse_fingerprint(text).map(Self::Master)
+    }
+
+    fn matches(&self, phrase: &str, wordlist: &dyn crate::bip39::Wordlist) -> Result<bool> {
+        match self {
+            Self::Master(expected) => Ok(phrase_fingerprint(phrase)? == *expected),
+            Self::PhraseId { id, salt } => Ok(fingerprint::phrase_id(phrase, wordlist, salt)? == *id),
+        }
+    }
+}
+
+impl std::fmt::Display for ExpectedFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Master(expected) => write!(f, "{}", hex::encode(expected)),
+            Self::PhraseId { id, .. } => write!(f, "{}", id),
+        }
+    }
+}
+
+/// Huella maestra BIP32 de una frase, sin passphrase BIP39
+fn phrase_fingerprint(phrase: &str) -> Result<[u8; 4]> {
+    let seed = mnemonic_to_seed(phrase, "");
+    let master = ExtendedPrivateKey::from_seed(&seed[..])?;
+    Ok(descriptor::fingerprint(&master))
+}
+
+/// Pedir la frase de la copia y la contraseña y decir si coincide con `expected`
+///
+/// Devuelve `Ok(false)` también cuando el resultado no es una frase BIP39
+/// válida (normalmente, una contraseña equivocada).
+pub fn verify_backup(params: &ScypherParams, input_file: Option<&str>, expected: &ExpectedFingerprint) -> Result<bool> {
+    println!("Backup drill: the decrypted phrase is never shown or saved");
+
+    let backup = match input_file {
+        Some(file_path) => crate::cli::read_seed_from_file(file_path)?,
+        None => crate::cli::read_seed_interactive(true)?,
+    };
+
+    let detection = crate::bip39::detect_language(&backup)?;
+    let wordlist = detection.language.wordlist();
+    crate::bip39::validate_seed_phrase_with(&backup, wordlist)?;
+
+    let password = crate::cli::input::read_new_password("password")?;
+
+    println!("Processing with Argon2id key derivation...");
+    let phrase = crypto::transform_seed_with_wordlist(&backup, &password, params, wordlist)?;
+
+    let matches = crate::bip39::verify_checksum_with(&phrase, wordlist)?
+        && expected.matches(&phrase, wordlist)?;
+
+    if matches {
+        println!("{}✓ Backup matches expected fingerprint {}: yes{}", colors::SUCCESS, expected, colors::RESET);
+    } else {
+        println!("{}✗ Backup matches expected fingerprint {}: no{}", colors::ERROR, expected, colors::RESET);
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phrase_fingerprint() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(phrase_fingerprint(phrase).unwrap(), parse_fingerprint("73c5da0a").unwrap());
+        assert_eq!(parse_fingerprint(" 73C5DA0A\n").unwrap(), [0x73, 0xc5, 0xda, 0x0a]);
+
+        assert!(parse_fingerprint("73c5da").is_err());
+        assert!(parse_fingerprint("73c5da0g").is_err());
+
+        let id = fingerprint::phrase_id(phrase, &crate::bip39::ENGLISH, "vault").unwrap();
+        let expected = ExpectedFingerprint::parse(&id, Some("vault")).unwrap();
+        assert!(expected.matches(phrase, &crate::bip39::ENGLISH).unwrap());
+        assert!(!ExpectedFingerprint::parse(&id, None).unwrap().matches(phrase, &crate::bip39::ENGLISH).unwrap());
+        assert!(ExpectedFingerprint::parse("73c5da0a", Some("vault")).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+// src/cli/watch.rs - Cola de trabajos en un directorio
+//
+// `scypher watch DIR` pide la contraseña una vez y procesa los archivos
+// `*.json` que van apareciendo en DIR, uno por trabajo:
+//
+//     {"input": "in/vault-a.txt", "output": "out/vault-a.txt"}
+//
+// Las rutas son relativas a DIR y no pueden salir de él: se rechazan las
+// absolutas, las que llevan `..` y las que un enlace simbólico lleva fuera.
+// `params` es opcional y sigue el
+// formato serializado de `ScypherParams`; sin él se usan los -i/-m/-k/...
+// de la línea de comandos. Cada conjunto de parámetros tiene su propia
+// `SessionKey`, así que cada tamaño de frase deriva su keystream una sola
+// vez. El trabajo terminado se mueve a `DIR/done` y el fallido a
+// `DIR/failed`; el resultado nunca sobrescribe un archivo existente.
+//
+// Quien deja los trabajos debe escribirlos con otro nombre (p. ej.
+// `.tmp`) y renombrarlos al final: se leen en cuanto aparecen.
+
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+use zeroize::Zeroizing;
+
+use crate::cli::output::save_to_new_file;
+use crate::crypto::{ScypherParams, SessionKey};
+use crate::error::{IoOperation, SCypherError, Result};
+
+/// Intervalo por defecto entre dos lecturas del directorio
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Subdirectorio de los trabajos terminados
+const DONE_DIR: &str = "done";
+
+/// Subdirectorio de los trabajos fallidos
+const FAILED_DIR: &str = "failed";
+
+/// Tamaño máximo de un archivo de trabajo y de una frase de entrada
+const MAX_FILE_LEN: u64 = 64 * 1024;
+
+/// Trabajo descrito en un archivo de la cola
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Job {
+    input: PathBuf,
+    output: PathBuf,
+    #[serde(default)]
+    params: Option<ScypherParams>,
+}
+
+/// Trabajos procesados al terminar `--once`
+#[derive(Debug, Default)]
+pub struct WatchSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Código de salida del primer trabajo fallido
+    pub exit_code: i32,
+}
+
+/// Claves de sesión por conjunto de parámetros, todas con la misma contraseña
+struct Sessions {
+    password: Zeroizing<String>,
+    keys: Vec<SessionKey>,
+}
+
+impl Sessions {
+    fn key_for(&mut self, params: &ScypherParams) -> &mut SessionKey {
+        let index = match self.keys.iter().position(|key| key.params() == params) {
+            Some(index) => index,
+            None => {
+                self.keys.push(SessionKey::unlock(&self.password, params.clone()));
+                self.keys.len() - 1
+            }
+        };
+        &mut self.keys[index]
+    }
+}
+
+/// Vigilar `dir` y procesar sus trabajos; con `once`, solo los que ya hay
+pub fn run_watch(dir: &str, params: ScypherParams, poll_interval: Duration, once: bool) -> Result<WatchSummary> {
+    let dir = Path::new(dir);
+    if !dir.is_dir() {
+        return Err(SCypherError::file_at(dir.display().to_string(), "Not a directory"));
+    }
+    let (done, failed) = (dir.join(DONE_DIR), dir.join(FAILED_DIR));
+    for subdir in [&done, &failed] {
+        std::fs::create_dir_all(subdir)
+            .map_err(|e| SCypherError::io_at(IoOperation::CreateDir, subdir.display().to_string(), e))?;
+    }
+
+    println!("Job queue: {} (Argon2id with {} iterations, {}KB memory)", dir.display(), params.iterations(), params.memory_cost());
+    let password = crate::cli::read_password_secure()?;
+    let mut sessions = Sessions { password, keys: Vec::new() };
+    if !once {
+        println!("🔓 Session unlocked; watching for job files every {}s (Ctrl-C to stop)", poll_interval.as_secs_f64());
+    }
+
+    let mut summary = WatchSummary::default();
+    loop {
+        for job_path in pending_jobs(dir)? {
+            let name = job_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            match run_job(dir, &job_path, &params, &mut sessions) {
+                Ok(output) => {
+                    summary.succeeded += 1;
+                    println!("  ✓ {} -> {}", name, output.display());
+                    move_job(&job_path, &done)?;
+                }
+                // Ctrl-C a mitad de una derivación: el trabajo se queda en la cola
+                Err(SCypherError::Cancelled) => return Err(SCypherError::Cancelled),
+                Err(e) => {
+                    if summary.failed == 0 {
+                        summary.exit_code = e.exit_code();
+                    }
+                    summary.failed += 1;
+                    println!("  ✗ {}: {}", name, e);
+                    move_job(&job_path, &failed)?;
+                }
+            }
+        }
+
+        if once {
+            break;
+        }
+        std::thread::sleep(poll_interval);
+    }
+
+    println!("\n{} succeeded, {} failed", summary.succeeded, summary.failed);
+    Ok(summary)
+}
+
+/// Archivos `*.json` de `dir` en orden alfabético, sin los ocultos
+fn pending_jobs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| SCypherError::io_at(IoOperation::Read, dir.display().to_string(), e))?;
+    let mut jobs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter(|path| !path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.')))
+        .collect();
+    jobs.sort();
+    Ok(jobs)
+}
+
+/// Procesar un trabajo; devuelve la ruta del resultado
+fn run_job(dir: &Path, job_path: &Path, default_params: &ScypherParams, sessions: &mut Sessions) -> Result<PathBuf> {
+    let job_name = job_path.display().to_string();
+    let content = read_bounded(job_path)?;
+    let job: Job = serde_json::from_str(&content)
+        .map_err(|e| SCypherError::file_at(&job_name, format!("Invalid job file: {}", e)))?;
+
+    // Los keyfiles y el pepper no se serializan: unos parámetros propios los perderían
+    let params = match job.params {
+        Some(_) if !default_params.keyfile_digests().is_empty() || default_params.pepper().is_some() => {
+            return Err(SCypherError::InvalidRequest("job parameters cannot be combined with keyfiles (-k) or --pepper-file; drop \"params\" to use the session's".to_string()));
+        }
+        Some(params) => params,
+        None => default_params.clone(),
+    };
+
+    let input = confined_path(dir, &job.input, false)?;
+    let output = confined_path(dir, &job.output, true)?;
+    let text = read_bounded(&input)?;
+    let phrase = Zeroizing::new(text.split_whitespace().collect::<Vec<_>>().join(" "));
+    let result = sessions.key_for(&params).transform(&phrase)?;
+
+    // Creación exclusiva: un archivo que aparezca entretanto no se pisa
+    save_to_new_file(&result, &output.display().to_string())?;
+    Ok(output)
+}
+
+/// Ruta de un trabajo dentro de `dir`, o error si podría salir de él
+///
+/// `path` debe ser relativa y sin `..`. Además, la ruta real (con los
+/// enlaces simbólicos resueltos) debe seguir dentro de `dir`: la de la
+/// entrada entera y, para la salida, que aún no existe, la de su directorio.
+fn confined_path(dir: &Path, path: &Path, is_output: bool) -> Result<PathBuf> {
+    let name = path.display().to_string();
+    if path.as_os_str().is_empty() || !path.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir)) {
+        return Err(SCypherError::file_at(name, "Job paths must be relative to the queue directory, without '..'"));
+    }
+
+    let joined = dir.join(path);
+    let resolve = |path: &Path| path.canonicalize().map_err(|e| SCypherError::io_at(IoOperation::Read, path.display().to_string(), e));
+    let root = resolve(dir)?;
+    let real = if is_output {
+        let file_name = joined.file_name().ok_or_else(|| SCypherError::file_at(&name, "Job output must name a file"))?;
+        resolve(joined.parent().unwrap_or(dir))?.join(file_name)
+    } else {
+        resolve(&joined)?
+    };
+    if !real.starts_with(&root) {
+        return Err(SCypherError::file_at(name, "Job path leads outside the queue directory through a symbolic link"));
+    }
+    Ok(joined)
+}
+
+/// Leer un archivo de texto de la cola, de `MAX_FILE_LEN` bytes como mucho
+fn read_bounded(path: &Path) -> Result<Zeroizing<String>> {
+    let name = path.display().to_string();
+    let file = std::fs::File::open(path).map_err(|e| SCypherError::io_at(IoOperation::Read, &name, e))?;
+    let mut content = Zeroizing::new(String::new());
+    file.take(MAX_FILE_LEN + 1)
+        .read_to_string(&mut content)
+        .map_err(|e| SCypherError::io_at(IoOperation::Read, &name, e))?;
+    if content.len() as u64 > MAX_FILE_LEN {
+        return Err(SCypherError::file_at(name, format!("File is larger than {} bytes", MAX_FILE_LEN)));
+    }
+    Ok(content)
+}
+
+/// Mover un trabajo a `target_dir` sin pisar otro del mismo nombre
+fn move_job(job_path: &Path, target_dir: &Path) -> Result<()> {
+    let name = job_path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let mut target = target_dir.join(&name);
+    let mut copy = 1;
+    while target.exists() {
+        target = target_dir.join(format!("{}.{}", name, copy));
+        copy += 1;
+    }
+    std::fs::rename(job_path, &target).map_err(|e| SCypherError::io_at(IoOperation::Write, target.display().to_string(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_job() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let params = crate::crypto::TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let mut sessions = Sessions { password: Zeroizing::new("test_password".to_string()), keys: Vec::new() };
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        std::fs::write(dir.path().join("seed.txt"), phrase).unwrap();
+
+        let job = dir.path().join("a.json");
+        std::fs::write(&job, r#"{"input": "seed.txt", "output": "a.txt"}"#).unwrap();
+        let output = run_job(dir.path(), &job, &params, &mut sessions).unwrap();
+        assert_eq!(std::fs::read_to_string(output).unwrap(), *params.transform(phrase, "test_password").unwrap());
+
+        // Otros parámetros: otra clave de sesión; el resultado no se sobrescribe
+        std::fs::write(&job, r#"{"input": "seed.txt", "output": "b.txt", "params": {"iterations": 2, "memory_cost": 8192}}"#).unwrap();
+        run_job(dir.path(), &job, &params, &mut sessions).unwrap();
+        assert_eq!(sessions.keys.len(), 2);
+        std::fs::write(&job, r#"{"input": "seed.txt", "output": "a.txt"}"#).unwrap();
+        assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err());
+
+        std::fs::write(&job, r#"{"input": "seed.txt", "output": "c.txt", "phrase": "abandon"}"#).unwrap();
+        assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err());
+
+        // Las rutas no salen del directorio de la cola
+        let outside = tempfile::TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), phrase).unwrap();
+        for paths in [
+            format!(r#""input": "{}", "output": "d.txt""#, outside.path().join("secret.txt").display()),
+            r#""input": "../secret.txt", "output": "d.txt""#.to_string(),
+            format!(r#""input": "seed.txt", "output": "{}""#, outside.path().join("d.txt").display()),
+            r#""input": "seed.txt", "output": "sub/../../d.txt""#.to_string(),
+        ] {
+            std::fs::write(&job, format!("{{{}}}", paths)).unwrap();
+            assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err(), "{}", paths);
+        }
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+            std::fs::write(&job, r#"{"input": "link/secret.txt", "output": "d.txt"}"#).unwrap();
+            assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err());
+            std::fs::write(&job, r#"{"input": "seed.txt", "output": "link/d.txt"}"#).unwrap();
+            assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err());
+            assert!(!outside.path().join("d.txt").exists());
+        }
+
+        // Ni trabajos ni entradas de tamaño sin límite
+        std::fs::write(dir.path().join("big.txt"), "abandon ".repeat(MAX_FILE_LEN as usize)).unwrap();
+        std::fs::write(&job, r#"{"input": "big.txt", "output": "e.txt"}"#).unwrap();
+        assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err());
+        std::fs::write(&job, " ".repeat(MAX_FILE_LEN as usize + 1)).unwrap();
+        assert!(run_job(dir.path(), &job, &params, &mut sessions).is_err());
+
+        // Un nombre repetido en done/ no pisa el anterior
+        let done = dir.path().join(DONE_DIR);
+        std::fs::create_dir(&done).unwrap();
+        std::fs::write(done.join("a.json"), "{}").unwrap();
+        move_job(&job, &done).unwrap();
+        assert!(done.join("a.json.1").exists() && !job.exists());
+    }
+}
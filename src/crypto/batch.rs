@@ -0,0 +1,200 @@
+//! Lotes en paralelo con presupuesto de memoria
+//!
+//! Cada derivación Argon2id reserva `memory_cost` KB durante su ejecución,
+//! así que el número de frases que se procesan a la vez se limita a lo que
+//! cabe en `memory_budget_kb` (y a `threads`, si se indica). Con la feature
+//! `parallel` los elementos se reparten en un thread pool de rayon dedicado;
+//! sin ella se procesan en serie con el mismo resultado. Cada hilo reutiliza
+//! su memoria de Argon2id (`KdfWorkspace`) entre elementos.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::crypto::keystream::KdfWorkspace;
+use crate::crypto::{transform_with_workspace, ProgressSink, ScypherParams};
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Result};
+
+/// Presupuesto de memoria por defecto para un lote: 1 GiB
+pub const DEFAULT_MEMORY_BUDGET_KB: u64 = 1024 * 1024;
+
+/// Opciones de ejecución de un lote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// Máximo de hilos; `None` usa uno por núcleo
+    pub threads: Option<usize>,
+    /// Memoria total que pueden ocupar las derivaciones simultáneas (KB)
+    pub memory_budget_kb: u64,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            threads: None,
+            memory_budget_kb: DEFAULT_MEMORY_BUDGET_KB,
+        }
+    }
+}
+
+impl BatchOptions {
+    /// Número de derivaciones simultáneas permitidas para `params`
+    ///
+    /// Falla con `InsufficientMemory` si ni una sola derivación cabe en el presupuesto.
+    pub fn concurrency(&self, params: &ScypherParams) -> Result<usize> {
+        let per_item = u64::from(params.memory_cost());
+        if per_item > self.memory_budget_kb {
+            return Err(SCypherError::InsufficientMemory);
+        }
+
+        let by_memory = usize::try_from(self.memory_budget_kb / per_item).unwrap_or(usize::MAX);
+        let threads = self.threads.unwrap_or_else(default_threads).max(1);
+
+        Ok(by_memory.min(threads))
+    }
+}
+
+fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Transformar un lote repartiendo las frases entre varios hilos
+///
+/// Mismo contrato que `transform_batch`: los resultados conservan el orden
+/// de entrada, un error en un elemento no detiene el resto y la cancelación
+/// devuelve `SCypherError::Cancelled` para el lote entero. `on_progress`
+/// recibe el número de elementos terminados, que pueden acabar en cualquier
+/// orden.
+pub fn transform_batch_parallel(
+    seed_phrases: &[&str],
+    password: &str,
+    params: &ScypherParams,
+    options: &BatchOptions,
+    sink: &dyn ProgressSink,
+) -> Result<Vec<Result<Zeroizing<String>>>> {
+    run_batch(seed_phrases, params, options, sink, |workspace, seed_phrase| {
+        transform_with_workspace(seed_phrase, password, params, sink, workspace)
+    }, |result| result)
+}
+
+/// Como `transform_batch_parallel`, con el tiempo que tardó cada elemento
+///
+/// El tiempo es el de su derivación y transformación en su hilo, sin la
+/// espera hasta que quedó un hilo libre.
+pub fn transform_batch_parallel_timed(
+    seed_phrases: &[&str],
+    password: &str,
+    params: &ScypherParams,
+    options: &BatchOptions,
+    sink: &dyn ProgressSink,
+) -> Result<Vec<(Result<Zeroizing<String>>, Duration)>> {
+    run_batch(seed_phrases, params, options, sink, |workspace, seed_phrase| {
+        let started = Instant::now();
+        let result = transform_with_workspace(seed_phrase, password, params, sink, workspace);
+        (result, started.elapsed())
+    }, |(result, _)| result)
+}
+
+/// Repartir el lote, avisar del progreso y convertir una cancelación en la del lote entero
+fn run_batch<T, F>(
+    seed_phrases: &[&str],
+    params: &ScypherParams,
+    options: &BatchOptions,
+    sink: &dyn ProgressSink,
+    transform: F,
+    result_of: fn(&T) -> &Result<Zeroizing<String>>,
+) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&mut KdfWorkspace, &str) -> T + Send + Sync,
+{
+    let concurrency = options.concurrency(params)?;
+    let total = seed_phrases.len();
+    let completed = AtomicUsize::new(0);
+
+    let transform_one = |workspace: &mut KdfWorkspace, seed_phrase: &&str| {
+        let outcome = transform(workspace, seed_phrase);
+        sink.on_progress(completed.fetch_add(1, Ordering::SeqCst) + 1, total);
+        outcome
+    };
+
+    let results = run_items(seed_phrases, concurrency, transform_one)?;
+
+    if results.iter().any(|outcome| matches!(result_of(outcome), Err(SCypherError::Cancelled))) {
+        return Err(SCypherError::Cancelled);
+    }
+
+    Ok(results)
+}
+
+#[cfg(feature = "parallel")]
+fn run_items<T, F>(seed_phrases: &[&str], concurrency: usize, transform_one: F) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&mut KdfWorkspace, &&str) -> T + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(concurrency)
+        .thread_name(|index| format!("scypher-batch-{}", index))
+        .build()
+        .map_err(|e| SCypherError::crypto(format!("Cannot start batch thread pool: {}", e)))?;
+
+    // Un workspace por tarea de rayon: como mucho uno vivo por hilo
+    Ok(pool.install(|| seed_phrases.par_iter().map_init(KdfWorkspace::new, transform_one).collect()))
+}
+
+#[cfg(not(feature = "parallel"))]
+fn run_items<T, F>(seed_phrases: &[&str], _concurrency: usize, transform_one: F) -> Result<Vec<T>>
+where
+    F: Fn(&mut KdfWorkspace, &&str) -> T,
+{
+    let mut workspace = KdfWorkspace::new();
+    Ok(seed_phrases.iter().map(|seed_phrase| transform_one(&mut workspace, seed_phrase)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{CancelFlag, NoProgress, TransformBuilder};
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_concurrency_respects_budget() {
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+
+        let options = BatchOptions { threads: Some(16), memory_budget_kb: 8192 * 3 };
+        assert_eq!(options.concurrency(&params).unwrap(), 3);
+
+        let options = BatchOptions { threads: Some(2), memory_budget_kb: 8192 * 3 };
+        assert_eq!(options.concurrency(&params).unwrap(), 2);
+
+        let options = BatchOptions { threads: None, memory_budget_kb: 4096 };
+        assert!(matches!(options.concurrency(&params), Err(SCypherError::InsufficientMemory)));
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let phrases = [TEST_PHRASE, "not a seed phrase", TEST_PHRASE, TEST_PHRASE];
+        let options = BatchOptions { threads: Some(4), memory_budget_kb: 8192 * 4 };
+
+        let parallel = transform_batch_parallel(&phrases, "test_password", &params, &options, &NoProgress).unwrap();
+        let sequential = crate::crypto::transform_batch(&phrases, "test_password", &params, &NoProgress).unwrap();
+
+        assert_eq!(parallel.len(), sequential.len());
+        for (a, b) in parallel.iter().zip(&sequential) {
+            assert_eq!(a.as_ref().ok(), b.as_ref().ok());
+        }
+        assert!(parallel[1].is_err());
+
+        let cancel = CancelFlag::new();
+        cancel.cancel();
+        assert!(matches!(
+            transform_batch_parallel(&phrases, "test_password", &params, &options, &cancel),
+            Err(SCypherError::Cancelled)
+        ));
+    }
+}
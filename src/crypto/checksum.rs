@@ -27,7 +27,7 @@ pub fn calculate_checksum(entropy: &[u8]) -> Result<Vec<u8>> {
     // donde ENT es la longitud de entropía en bits
     let entropy_bits = entropy.len() * 8;
     let checksum_bits = entropy_bits / 32;
-    let checksum_bytes = (checksum_bits + 7) / 8; // Redondear hacia arriba
+    let checksum_bytes = checksum_bits.div_ceil(8); // Redondear hacia arriba
 
     if checksum_bytes > hash.len() {
         return Err(SCypherError::crypto("Invalid entropy length".to_string()));
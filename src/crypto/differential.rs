@@ -0,0 +1,198 @@
+//! Pruebas diferenciales contra el SCypher original en Bash
+//!
+//! Solo con la característica `legacy-diff` y en tests. Si el script existe,
+//! se genera un lote de frases y contraseñas al azar, se transforma cada una
+//! con el script (modo silent: frase y contraseña por stdin, `-i`/`-m` en la
+//! línea de comandos) y con `transform_seed`, y los resultados deben ser
+//! idénticos. Solo se cubre lo que se declara compatible: lista inglesa,
+//! salt derivado de la contraseña y checksum de entrada sin validar.
+//!
+//! ```text
+//! SCYPHER_LEGACY_SCRIPT=/ruta/SCypher.sh cargo test --features legacy-diff differential
+//! ```
+//!
+//! `SCYPHER_LEGACY_CASES` fija el número de casos (20 por defecto) y
+//! `SCYPHER_LEGACY_SEED` la semilla del generador, para repetir un fallo.
+//! Sin `SCYPHER_LEGACY_SCRIPT` el test se salta.
+//!
+//! `--compat v2` (`crypto::legacy`) se compara de dos formas. Su keystream,
+//! con la cadena de `openssl dgst -shake256` que ejecutaba `SCypherV2.sh`,
+//! siempre que haya un `openssl` en el PATH. Y la transformación completa,
+//! con el propio script si `SCYPHER_LEGACY_V2_SCRIPT` apunta a él (mismo
+//! modo silent, solo con `-i`; de 1 a 50 rondas, porque cada ronda del
+//! script lanza un proceso):
+//!
+//! ```text
+//! SCYPHER_LEGACY_V2_SCRIPT=/ruta/SCypherV2.sh cargo test --features legacy-diff differential
+//! ```
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::bip39::conversion::entropy_to_phrase;
+
+/// Ruta del script Bash original
+const SCRIPT_VAR: &str = "SCYPHER_LEGACY_SCRIPT";
+
+/// Número de casos aleatorios
+const CASES_VAR: &str = "SCYPHER_LEGACY_CASES";
+
+/// Semilla del generador de casos
+const SEED_VAR: &str = "SCYPHER_LEGACY_SEED";
+
+/// Ruta del script v2 (`SCypherV2.sh`, SHAKE-256)
+const V2_SCRIPT_VAR: &str = "SCYPHER_LEGACY_V2_SCRIPT";
+
+/// Rondas máximas de los casos v2
+const V2_MAX_CASE_ITERATIONS: u32 = 50;
+
+/// Un caso de prueba
+#[derive(Debug)]
+struct Case {
+    phrase: String,
+    password: String,
+    iterations: u32,
+    memory_cost: u32,
+}
+
+fn random_case(rng: &mut StdRng) -> Case {
+    let words = [12, 15, 18, 21, 24][rng.gen_range(0..5)];
+    let mut entropy = vec![0u8; words * 4 / 3];
+    rng.fill(&mut entropy[..]);
+    let phrase = entropy_to_phrase(&entropy).unwrap();
+    let length = rng.gen_range(8..=32);
+    let password = (0..length).map(|_| char::from(rng.gen_range(b'!'..=b'~'))).collect();
+    Case {
+        phrase,
+        password,
+        iterations: rng.gen_range(1..=3),
+        memory_cost: [8192, 16384][rng.gen_range(0..2)],
+    }
+}
+
+/// Última línea no vacía de la salida del script (el resultado)
+fn legacy_output(stdout: &str) -> Option<&str> {
+    stdout.lines().map(str::trim).rev().find(|line| !line.is_empty())
+}
+
+/// Ejecutar `program` con `args`, pasarle `input` por stdin y devolver su stdout
+fn run_piped(program: &str, args: &[&str], input: &[u8]) -> Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run {}: {}", program, e))?;
+
+    child.stdin.take().unwrap().write_all(input).map_err(|e| e.to_string())?;
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!("{} {:?} exited with {}: {}", program, args, output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Transformar `case` con un script en modo silent; `memory` solo para el actual
+fn run_legacy(script: &str, case: &Case, memory: bool) -> Result<String, String> {
+    let iterations = case.iterations.to_string();
+    let memory_cost = case.memory_cost.to_string();
+    let mut args = vec![script, "-s", "-i", &iterations];
+    if memory {
+        args.extend(["-m", &memory_cost]);
+    }
+    let input = format!("{}\n{}\n", case.phrase, case.password);
+    let stdout = run_piped("bash", &args, input.as_bytes())?;
+    legacy_output(&stdout).map(str::to_string).ok_or_else(|| "no output".to_string())
+}
+
+/// Keystream de v2 calculado con openssl, ronda a ronda como el script
+fn openssl_keystream(password: &str, iterations: u32, len: usize) -> Result<String, String> {
+    let xoflen = len.to_string();
+    let mut digest = password.to_string();
+    for _ in 0..iterations {
+        let stdout = run_piped("openssl", &["dgst", "-shake256", "-xoflen", &xoflen], digest.as_bytes())?;
+        // "SHAKE-256(stdin)= <hex>" o solo "<hex>", según la versión
+        digest = stdout.split_whitespace().last().ok_or("no openssl output")?.to_string();
+    }
+    Ok(digest)
+}
+
+fn case_count() -> usize {
+    std::env::var(CASES_VAR).ok().and_then(|value| value.parse().ok()).unwrap_or(20)
+}
+
+fn case_rng() -> StdRng {
+    let seed: u64 = std::env::var(SEED_VAR).ok().and_then(|value| value.parse().ok()).unwrap_or_else(rand::random);
+    eprintln!("differential test: {} cases, {}={}", case_count(), SEED_VAR, seed);
+    StdRng::seed_from_u64(seed)
+}
+
+#[test]
+fn test_legacy_output_line() {
+    assert_eq!(legacy_output("\nzoo zoo wrong  \n\n"), Some("zoo zoo wrong"));
+    assert_eq!(legacy_output("\n \n"), None);
+}
+
+#[test]
+fn test_differential_against_bash() {
+    let Ok(script) = std::env::var(SCRIPT_VAR) else {
+        eprintln!("{} not set: differential test against the Bash implementation skipped", SCRIPT_VAR);
+        return;
+    };
+    assert!(std::path::Path::new(&script).is_file(), "{} points to a missing file: {}", SCRIPT_VAR, script);
+
+    let mut rng = case_rng();
+    for _ in 0..case_count() {
+        let case = random_case(&mut rng);
+        let expected = super::transform_seed(&case.phrase, &case.password, case.iterations, case.memory_cost).unwrap();
+        match run_legacy(&script, &case, true) {
+            Ok(legacy) => assert_eq!(legacy, *expected, "outputs differ for {:?}", case),
+            Err(e) => panic!("legacy run failed for {:?}: {}", case, e),
+        }
+    }
+}
+
+#[test]
+fn test_v2_keystream_against_openssl() {
+    if Command::new("openssl").arg("version").output().is_err() {
+        eprintln!("openssl not found: v2 keystream comparison skipped");
+        return;
+    }
+
+    let mut rng = case_rng();
+    for _ in 0..case_count() {
+        let case = random_case(&mut rng);
+        let iterations = rng.gen_range(1..=V2_MAX_CASE_ITERATIONS);
+        let len = case.phrase.split_whitespace().count() * 4 / 3;
+        let mut keystream = vec![0u8; len];
+        super::legacy::shake_keystream(&case.password, iterations, &mut keystream);
+        match openssl_keystream(&case.password, iterations, len) {
+            Ok(expected) => assert_eq!(hex::encode(&keystream), expected, "keystreams differ for {:?}, {} rounds", case, iterations),
+            Err(e) => panic!("openssl failed for {:?}: {}", case, e),
+        }
+    }
+}
+
+#[test]
+fn test_v2_differential_against_bash() {
+    let Ok(script) = std::env::var(V2_SCRIPT_VAR) else {
+        eprintln!("{} not set: differential test against SCypherV2.sh skipped", V2_SCRIPT_VAR);
+        return;
+    };
+    assert!(std::path::Path::new(&script).is_file(), "{} points to a missing file: {}", V2_SCRIPT_VAR, script);
+
+    let mut rng = case_rng();
+    for _ in 0..case_count() {
+        let mut case = random_case(&mut rng);
+        case.iterations = rng.gen_range(1..=V2_MAX_CASE_ITERATIONS);
+        let expected = super::legacy::transform_seed_v2(&case.phrase, &case.password, case.iterations).unwrap();
+        match run_legacy(&script, &case, false) {
+            Ok(legacy) => assert_eq!(legacy, *expected, "outputs differ for {:?}", case),
+            Err(e) => panic!("SCypherV2.sh run failed for {:?}: {}", case, e),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Varias salidas con una sola derivación Argon2id
+//!
+//! `MasterKey` ejecuta Argon2id una vez y expande con HKDF-SHA256 un
+//! keystream distinto por etiqueta (p.ej. "señuelo" y "real", o una por
+//! cartera). Es un esquema distinto del de `transform_seed`: una frase
+//! transformada con etiqueta solo se recupera con la misma contraseña,
+//! parámetros y etiqueta. La longitud del keystream también entra en el
+//! `info` de HKDF, así que frases de distinta longitud no comparten prefijo.
+//!
+//! `--index N` usa la etiqueta de `index_label`: una contraseña protege
+//! varias frases con keystreams independientes, y conocer un par
+//! frase/resultado de un índice no revela nada de los demás.
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::bip39::wordlist::{Wordlist, ENGLISH};
+use crate::crypto::keystream::{self, KdfWorkspace};
+use crate::crypto::{transform_with_keystream, NoProgress, ScypherParams};
+use crate::error::{SCypherError, Result};
+
+/// Tamaño de la clave maestra derivada con Argon2id
+const MASTER_KEY_LEN: usize = 32;
+
+/// Prefijo de dominio del `info` de HKDF
+const INFO_PREFIX: &[u8] = b"scypher/output/v1";
+
+/// Etiqueta de la salida número `index` (lleva '/', que no admiten las etiquetas de batch)
+pub fn index_label(index: u32) -> String {
+    format!("index/{}", index)
+}
+
+/// Clave maestra derivada una vez de la contraseña
+pub struct MasterKey {
+    key: Zeroizing<[u8; MASTER_KEY_LEN]>,
+    params: ScypherParams,
+}
+
+impl MasterKey {
+    /// Derivar la clave maestra (la única ejecución de Argon2id)
+    pub fn derive(password: &str, params: &ScypherParams) -> Result<Self> {
+        let mut key = Zeroizing::new([0u8; MASTER_KEY_LEN]);
+        keystream::derive_keystream_into(password, &mut key[..], params, &mut KdfWorkspace::new())?;
+
+        Ok(Self { key, params: params.clone() })
+    }
+
+    /// Parámetros usados en la derivación
+    pub fn params(&self) -> &ScypherParams {
+        &self.params
+    }
+
+    /// Escribir en `output` el keystream de la etiqueta `label`
+    pub fn expand(&self, label: &str, output: &mut [u8]) -> Result<()> {
+        if label.is_empty() {
+            return Err(SCypherError::InvalidRequest("output label cannot be empty".to_string()));
+        }
+        let length = u16::try_from(output.len())
+            .map_err(|_| SCypherError::crypto(format!("Keystream too long: {} bytes", output.len())))?;
+
+        let mut info = Zeroizing::new(Vec::with_capacity(INFO_PREFIX.len() + label.len() + 3));
+        info.extend_from_slice(INFO_PREFIX);
+        info.push(0);
+        info.extend_from_slice(label.as_bytes());
+        info.extend_from_slice(&length.to_be_bytes());
+
+        let hkdf = Hkdf::<Sha256>::from_prk(&self.key[..])
+            .map_err(|_| SCypherError::crypto("Invalid master key length".to_string()))?;
+        hkdf.expand(&info, output)
+            .map_err(|_| SCypherError::crypto(format!("Keystream too long: {} bytes", output.len())))
+    }
+
+    /// Transformar una frase con el keystream de `label`
+    pub fn transform(&self, label: &str, seed_phrase: &str) -> Result<Zeroizing<String>> {
+        self.transform_with_wordlist(label, seed_phrase, &ENGLISH)
+    }
+
+    /// Transformar una frase de cualquier `Wordlist` con el keystream de `label`
+    pub fn transform_with_wordlist(&self, label: &str, seed_phrase: &str, wordlist: &dyn Wordlist) -> Result<Zeroizing<String>> {
+        transform_with_keystream(seed_phrase, &self.params, wordlist, &NoProgress, &mut |output| {
+            self.expand(label, output)
+        })
+    }
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MasterKey")
+            .field("key", &"<redacted>")
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{ChecksumPolicy, TransformBuilder};
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_labeled_outputs() {
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let master = MasterKey::derive("test_password", &params).unwrap();
+
+        let decoy = master.transform("decoy", TEST_PHRASE).unwrap();
+        let real = master.transform("real", TEST_PHRASE).unwrap();
+        assert_ne!(decoy, real);
+        assert_ne!(*decoy, TEST_PHRASE);
+
+        // Misma etiqueta dos veces: vuelve la original; una clave nueva da lo mismo
+        assert_eq!(*master.transform("decoy", &decoy).unwrap(), TEST_PHRASE);
+        let again = MasterKey::derive("test_password", &params).unwrap();
+        assert_eq!(again.transform("real", TEST_PHRASE).unwrap(), real);
+
+        assert!(master.transform("", TEST_PHRASE).is_err());
+
+        // Índices distintos, keystreams distintos; el mismo índice recupera la frase
+        let first = master.transform(&index_label(1), TEST_PHRASE).unwrap();
+        assert_ne!(first, master.transform(&index_label(2), TEST_PHRASE).unwrap());
+        assert_eq!(*master.transform(&index_label(1), &first).unwrap(), TEST_PHRASE);
+
+        // La longitud separa dominios: 12 y 24 palabras no comparten prefijo
+        let skip = TransformBuilder::new().iterations(1).memory_cost(8192)
+            .checksum_policy(ChecksumPolicy::Skip).build().unwrap();
+        let master = MasterKey::derive("test_password", &skip).unwrap();
+        let (mut short, mut long) = ([0u8; 16], [0u8; 32]);
+        master.expand("real", &mut short).unwrap();
+        master.expand("real", &mut long).unwrap();
+        assert_ne!(short[..], long[..16]);
+    }
+}
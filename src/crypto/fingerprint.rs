@@ -0,0 +1,80 @@
+//! Identificador corto y no reversible de una frase
+//!
+//! `fp1` seguido de 8 caracteres del alfabeto bech32 (40 bits de SHA-256
+//! sobre la entropía, con un salt opcional y separación de dominio). Se
+//! puede anotar a la vista junto a una copia de seguridad: a diferencia de
+//! la huella maestra BIP32, no enlaza con las direcciones de la cartera, y
+//! con salts distintos dos registros de la misma frase no se pueden cruzar.
+//! Depende solo de la entropía, no del idioma de la lista de palabras.
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::bip39::wordlist::Wordlist;
+use crate::error::{SCypherError, Result};
+
+/// Prefijo de todo identificador
+pub const PREFIX: &str = "fp1";
+
+/// Caracteres tras el prefijo (5 bits cada uno)
+const ID_CHARS: usize = 8;
+
+/// Alfabeto bech32 (BIP173)
+pub(crate) const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Prefijo de dominio del hash
+const DOMAIN: &[u8] = b"scypher/fingerprint/v1";
+
+/// Identificador de `phrase` (palabras de `wordlist`) con el salt indicado
+pub fn phrase_id(phrase: &str, wordlist: &dyn Wordlist, salt: &str) -> Result<String> {
+    let entropy = Zeroizing::new(crate::bip39::conversion::phrase_to_entropy_with(phrase, wordlist)?);
+
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hasher.update((salt.len() as u64).to_be_bytes());
+    hasher.update(salt.as_bytes());
+    hasher.update(&entropy[..]);
+    let hash = hasher.finalize();
+
+    // Primeros 40 bits del hash, de 5 en 5
+    let bits = hash[..5].iter().fold(0u64, |acc, &byte| acc << 8 | u64::from(byte));
+    let id: String = (0..ID_CHARS)
+        .map(|i| CHARSET[(bits >> (5 * (ID_CHARS - 1 - i)) & 31) as usize] as char)
+        .collect();
+    Ok(format!("{}{}", PREFIX, id))
+}
+
+/// Leer un identificador (sin distinguir mayúsculas) y devolverlo normalizado
+pub fn parse_phrase_id(text: &str) -> Result<String> {
+    let id = text.trim().to_ascii_lowercase();
+    let valid = id.strip_prefix(PREFIX)
+        .is_some_and(|rest| rest.len() == ID_CHARS && rest.bytes().all(|c| CHARSET.contains(&c)));
+    if !valid {
+        return Err(SCypherError::InvalidRequest(format!(
+            "Invalid phrase fingerprint '{}': expected '{}' and {} bech32 characters", text, PREFIX, ID_CHARS)));
+    }
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39::Language;
+
+    #[test]
+    fn test_phrase_id() {
+        let english = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let id = phrase_id(english, Language::English.wordlist(), "").unwrap();
+        assert_eq!(id.len(), PREFIX.len() + ID_CHARS);
+        assert_eq!(parse_phrase_id(&id.to_uppercase()).unwrap(), id);
+
+        // Misma entropía en otro idioma: mismo identificador; otro salt: otro
+        let spanish = "\u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco abierto";
+        assert_eq!(phrase_id(spanish, Language::Spanish.wordlist(), "").unwrap(), id);
+        assert_ne!(phrase_id(english, Language::English.wordlist(), "vault-a").unwrap(), id);
+
+        assert!(parse_phrase_id("fp1abc").is_err());
+        assert!(parse_phrase_id("fp1abcdefgb").is_err()); // 'b' no está en bech32
+        assert!(parse_phrase_id("73c5da0a").is_err());
+    }
+}
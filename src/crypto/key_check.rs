@@ -0,0 +1,99 @@
+//! Código corto para detectar una contraseña mal tecleada (`--key-check`)
+//!
+//! Cuatro caracteres bech32 (20 bits) que se muestran al cifrar, solo con
+//! `--show-key-check`, y se recalculan al descifrar antes de Argon2id: un
+//! error al teclear se detecta en milisegundos en lugar de tras la
+//! derivación completa. Salen de una rama aparte y barata, Argon2id con 1
+//! iteración y 8 MiB sobre la contraseña, los keyfiles y el pepper, con un
+//! salt propio que incluye los parámetros principales: un `-m` o unas lanes
+//! distintas también se detectan.
+//!
+//! El código permite descartar contraseñas sin la derivación completa
+//! (todas salvo una de cada 2^20), saltándose el coste de `-i`/`-m`. Por eso
+//! es opcional y el CLI lo muestra con ese aviso: guardarlo aparte de la
+//! frase cifrada y no usarlo con contraseñas débiles.
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::crypto::fingerprint::CHARSET;
+use crate::crypto::keystream::derive_keystream_with_params;
+use crate::crypto::params::MIN_MEMORY_COST;
+use crate::crypto::{Argon2Version, Kdf, SaltSource, ScypherParams};
+use crate::error::{SCypherError, Result};
+
+/// Caracteres del código (5 bits cada uno)
+pub const CHECK_CHARS: usize = 4;
+
+/// Prefijo de dominio del salt de la rama de comprobación
+const DOMAIN: &[u8] = b"scypher/key-check/v1";
+
+/// Código de comprobación de `password` con `params`
+pub fn key_check(password: &str, params: &ScypherParams) -> Result<String> {
+    // Salt propio: el de la transformación (o la contraseña, si se deriva de ella) y los parámetros
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    let source: &[u8] = match params.salt() {
+        SaltSource::PasswordDerived => password.as_bytes(),
+        SaltSource::Explicit(salt) => salt,
+    };
+    hasher.update([u8::from(matches!(params.salt(), SaltSource::Explicit(_)))]);
+    hasher.update((source.len() as u64).to_be_bytes());
+    hasher.update(source);
+    hasher.update(params.kdf().name());
+    hasher.update(params.argon2_version().name());
+    for value in [params.iterations(), params.memory_cost(), params.lanes()] {
+        hasher.update(value.to_be_bytes());
+    }
+
+    // Keyfiles y pepper siguen en los parámetros de la rama barata
+    let check_params = params.to_builder()
+        .kdf(Kdf::Argon2id)
+        .argon2_version(Argon2Version::V0x13)
+        .iterations(1)
+        .memory_cost(MIN_MEMORY_COST)
+        .lanes(1)
+        .salt(SaltSource::Explicit(hasher.finalize().to_vec()))
+        .build()?;
+    let derived = Zeroizing::new(derive_keystream_with_params(password, 4, &check_params)?);
+
+    // Primeros 20 bits, de 5 en 5
+    let bits = u32::from(derived[0]) << 12 | u32::from(derived[1]) << 4 | u32::from(derived[2]) >> 4;
+    Ok((0..CHECK_CHARS)
+        .map(|i| CHARSET[(bits >> (5 * (CHECK_CHARS - 1 - i)) & 31) as usize] as char)
+        .collect())
+}
+
+/// Leer un código (sin distinguir mayúsculas) y devolverlo normalizado
+pub fn parse_key_check(text: &str) -> Result<String> {
+    let code = text.trim().to_ascii_lowercase();
+    if code.len() != CHECK_CHARS || !code.bytes().all(|c| CHARSET.contains(&c)) {
+        return Err(SCypherError::InvalidRequest(format!(
+            "Invalid key check '{}': expected {} bech32 characters", text, CHECK_CHARS)));
+    }
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_check() {
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+        let code = key_check("password123", &params).unwrap();
+        assert_eq!(code.len(), CHECK_CHARS);
+        assert_eq!(code, key_check("password123", &params).unwrap());
+        assert_eq!(parse_key_check(&format!(" {} ", code.to_uppercase())).unwrap(), code);
+
+        // Otra contraseña, otros parámetros u otro keyfile: otro código (casi siempre)
+        assert_ne!(code, key_check("password124", &params).unwrap());
+        let other_memory = ScypherParams::builder().iterations(1).memory_cost(16384).build().unwrap();
+        assert_ne!(code, key_check("password123", &other_memory).unwrap());
+        let with_keyfile = params.to_builder().keyfile(b"keyfile").build().unwrap();
+        assert_ne!(code, key_check("password123", &with_keyfile).unwrap());
+
+        assert!(parse_key_check("abc").is_err());
+        assert!(parse_key_check("abcb").is_err()); // 'b' no está en bech32
+    }
+}
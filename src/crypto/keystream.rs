@@ -4,8 +4,17 @@
 //! Argon2id a partir de una contraseña. El keystream se usa luego para
 //! la operación XOR con la frase semilla.
 
-use argon2::{Argon2, Algorithm, Version, Params};
-use crate::error::{SCypherError, Result};
+use argon2::Params;
+use scypher_core::argon2::{Argon2, Block, Variant, Version};
+use zeroize::{Zeroize, Zeroizing};
+use crate::crypto::params::{
+    ScypherParams, SaltSource, TransformBuilder, Kdf, Argon2Version,
+    MIN_ITERATIONS, MAX_ITERATIONS, MIN_MEMORY_COST, MAX_MEMORY_COST,
+};
+use crate::crypto::progress::{NoProgress, ProgressSink};
+use crate::error::{SCypherError, Parameter, Result};
+use crate::security::interrupt;
+use crate::security::memory::{preflight_memory, MemoryPreflight};
 
 /// Genera un keystream usando Argon2id
 ///
@@ -23,52 +32,234 @@ pub fn derive_keystream(
     iterations: u32,
     memory_cost: u32,
 ) -> Result<Vec<u8>> {
-    // TODO: Implementar Argon2id real en Fase 3
-    // Por ahora, generar datos pseudoaleatorios deterministas para desarrollo
+    let params = TransformBuilder::new()
+        .iterations(iterations)
+        .memory_cost(memory_cost)
+        .build()?;
 
-    // Usar un salt fijo derivado de la contraseña para hacer determinista
-    let salt_bytes = generate_deterministic_salt(password);
+    derive_keystream_with_params(password, length, &params)
+}
+
+/// Genera un keystream Argon2id usando parámetros completos
+///
+/// La entrada de Argon2id es la contraseña seguida de los hashes SHA-256
+/// de los keyfiles, en orden. El salt proviene de `params.salt()` y el
+/// pepper, si lo hay, es el secreto de Argon2.
+pub fn derive_keystream_with_params(
+    password: &str,
+    length: usize,
+    params: &ScypherParams,
+) -> Result<Vec<u8>> {
+    derive_keystream_in(password, length, params, &mut KdfWorkspace::new())
+}
+
+/// Memoria de trabajo de Argon2id reutilizable entre derivaciones
+///
+/// Los lotes derivan cientos de claves con los mismos parámetros; con un
+/// workspace por hilo la memoria (hasta 2 GB por derivación) se reserva una
+/// sola vez y se limpia una sola vez al liberarse, en lugar de en cada
+/// elemento. Argon2 sobrescribe todos los bloques antes de leerlos, así que
+/// el contenido previo no afecta al resultado.
+///
+/// Los bloques contienen material derivado de la contraseña. Nunca se
+/// liberan sin limpiar: ni al soltar el workspace ni al crecer (se limpia la
+/// reserva anterior en lugar de realocarla). `wipe` los limpia antes si el
+/// workspace va a quedarse vivo sin usarse.
+#[derive(Default)]
+pub struct KdfWorkspace {
+    blocks: Vec<Block>,
+}
+
+impl KdfWorkspace {
+    /// Workspace vacío; la memoria se reserva en la primera derivación
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    // Crear parámetros Argon2id
-    let _params = Params::new(
-        memory_cost,
-        iterations,
-        1, // parallelism
-        Some(length),
-    ).map_err(|e| SCypherError::crypto(format!("Invalid Argon2 parameters: {:?}", e)))?;
+    /// Memoria reservada actualmente, en KB (cada bloque ocupa 1 KB)
+    pub fn capacity_kb(&self) -> usize {
+        self.blocks.len()
+    }
 
-    let _argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, _params);
+    /// Limpiar ya todos los bloques, conservando la reserva
+    pub fn wipe(&mut self) {
+        self.blocks.iter_mut().for_each(Zeroize::zeroize);
+    }
 
-    // Para el placeholder, generar datos pseudoaleatorios deterministas
-    let mut keystream = vec![0u8; length];
-    for (i, byte) in keystream.iter_mut().enumerate() {
-        *byte = ((password.len() + salt_bytes.len() + i) % 256) as u8;
+    /// Los primeros `count` bloques, reservándolos si hace falta
+    ///
+    /// Antes de reservar se compara con la RAM disponible: es mejor fallar
+    /// con `InsufficientMemory` que morir por falta de memoria a mitad de la
+    /// derivación, sin llegar a limpiar nada.
+    fn blocks(&mut self, count: usize) -> Result<&mut [Block]> {
+        if self.blocks.len() < count {
+            if let MemoryPreflight::Insufficient { .. } = preflight_memory(count as u64) {
+                return Err(SCypherError::InsufficientMemory);
+            }
+
+            // `resize` realocaría y liberaría los bloques viejos sin limpiarlos
+            self.blocks.zeroize();
+            let mut blocks = Vec::new();
+            blocks.try_reserve_exact(count).map_err(|_| SCypherError::InsufficientMemory)?;
+            blocks.resize(count, Block::default());
+            self.blocks = blocks;
+        }
+        Ok(&mut self.blocks[..count])
+    }
+}
+
+impl Drop for KdfWorkspace {
+    fn drop(&mut self) {
+        self.blocks.zeroize();
     }
+}
 
+/// Derivar un keystream usando la memoria de `workspace`
+pub fn derive_keystream_in(
+    password: &str,
+    length: usize,
+    params: &ScypherParams,
+    workspace: &mut KdfWorkspace,
+) -> Result<Vec<u8>> {
+    let mut keystream = vec![0u8; length];
+    derive_keystream_into(password, &mut keystream, params, workspace)?;
     Ok(keystream)
 }
 
+/// Derivar un keystream de `output.len()` bytes directamente en `output`
+///
+/// No reserva memoria para el resultado: el llamador decide dónde vive el
+/// keystream (p.ej. un buffer en la pila que se limpia al salir). El salt
+/// derivado de la contraseña y el material de entrada se limpian al terminar.
+pub fn derive_keystream_into(
+    password: &str,
+    output: &mut [u8],
+    params: &ScypherParams,
+    workspace: &mut KdfWorkspace,
+) -> Result<()> {
+    derive_keystream_cancellable(password, output, params, workspace, &NoProgress)
+}
+
+/// Como `derive_keystream_into`, interrumpible a mitad de la derivación
+///
+/// `sink.is_cancelled()` y Ctrl-C (`security::interrupt`) se comprueban
+/// antes de cada segmento de Argon2. Al cancelar se limpian la memoria de
+/// `workspace` y `output` y se devuelve `SCypherError::Cancelled`.
+#[tracing::instrument(
+    name = "derive_key",
+    level = "debug",
+    skip_all,
+    fields(
+        length = output.len(),
+        iterations = params.iterations(),
+        memory_cost = params.memory_cost(),
+        lanes = params.lanes(),
+        keyfiles = params.keyfile_digests().len(),
+        pepper = params.pepper().is_some(),
+        reused = workspace.capacity_kb() >= params.memory_cost() as usize,
+    )
+)]
+pub fn derive_keystream_cancellable(
+    password: &str,
+    output: &mut [u8],
+    params: &ScypherParams,
+    workspace: &mut KdfWorkspace,
+    sink: &dyn ProgressSink,
+) -> Result<()> {
+    let derived_salt;
+    let salt: &[u8] = match params.salt() {
+        SaltSource::PasswordDerived => {
+            derived_salt = Zeroizing::new(generate_deterministic_salt(password));
+            derived_salt.as_slice()
+        }
+        SaltSource::Explicit(salt) => salt,
+    };
+
+    // Validar los parámetros con el crate argon2 (mismos límites y errores)
+    Params::new(
+        params.memory_cost(),
+        params.iterations(),
+        params.lanes(),
+        Some(output.len()),
+    ).map_err(SCypherError::KeyDerivationFailed)?;
+
+    let argon2 = Argon2 {
+        variant: match params.kdf() {
+            Kdf::Argon2id => Variant::Argon2id,
+            Kdf::Argon2i => Variant::Argon2i,
+        },
+        version: match params.argon2_version() {
+            Argon2Version::V0x10 => Version::V0x10,
+            Argon2Version::V0x13 => Version::V0x13,
+        },
+        m_cost: params.memory_cost(),
+        t_cost: params.iterations(),
+        lanes: params.lanes(),
+    };
+
+    // Material de entrada: contraseña + hashes de keyfiles. Se reserva el
+    // tamaño exacto para que ninguna realocación deje copias sin limpiar.
+    let digests = params.keyfile_digests();
+    let mut input = Zeroizing::new(Vec::with_capacity(password.len() + digests.len() * 32));
+    input.extend_from_slice(password.as_bytes());
+    for digest in digests {
+        input.extend_from_slice(digest);
+    }
+
+    let _interruptible = interrupt::enter();
+    let cancelled = || sink.is_cancelled() || interrupt::is_requested();
+    let blocks = workspace.blocks(argon2.block_count())?;
+    let secret = params.pepper().map_or(&[][..], |pepper| pepper.as_bytes());
+    Ok(argon2.hash_into(&input, secret, salt, output, blocks, &cancelled)?)
+}
+
+/// Keystream de `scypher_core` derivado con Argon2 de una contraseña
+///
+/// Es el mismo KDF que usa `transform_seed_with_params`; un firmador sin
+/// `std` implementa `scypher_core::KeystreamSource` con su propio hardware.
+pub struct PasswordKeystream<'a> {
+    password: &'a str,
+    params: &'a ScypherParams,
+    workspace: KdfWorkspace,
+}
+
+impl<'a> PasswordKeystream<'a> {
+    pub fn new(password: &'a str, params: &'a ScypherParams) -> Self {
+        Self { password, params, workspace: KdfWorkspace::new() }
+    }
+}
+
+impl scypher_core::KeystreamSource for PasswordKeystream<'_> {
+    type Error = SCypherError;
+
+    fn fill_keystream(&mut self, keystream: &mut [u8]) -> Result<()> {
+        derive_keystream_into(self.password, keystream, self.params, &mut self.workspace)
+    }
+}
+
 /// Genera un salt determinista basado en la contraseña
 /// Esto asegura que la misma contraseña produzca el mismo resultado
-fn generate_deterministic_salt(password: &str) -> Vec<u8> {
+fn generate_deterministic_salt(password: &str) -> [u8; 32] {
     use sha2::{Sha256, Digest};
 
     let mut hasher = Sha256::new();
     hasher.update(password.as_bytes());
     hasher.update(b"SCYPHER_SALT_V1"); // Valor constante para consistencia
 
-    hasher.finalize().to_vec()
+    hasher.finalize().into()
 }
 
 /// Valida que los parámetros Argon2id estén en rangos seguros
 pub fn validate_argon2_params(iterations: u32, memory_cost: u32) -> Result<()> {
     // Validaciones de rango seguro
-    if iterations == 0 || iterations > 100 {
-        return Err(SCypherError::InvalidIterations(iterations.to_string()));
+    if !(MIN_ITERATIONS..=MAX_ITERATIONS).contains(&iterations) {
+        return Err(SCypherError::invalid_parameter(
+            Parameter::Iterations, iterations, format!("{}-{}", MIN_ITERATIONS, MAX_ITERATIONS)));
     }
 
-    if memory_cost < 8192 || memory_cost > 2_097_152 {  // 8MB - 2GB
-        return Err(SCypherError::InvalidMemoryCost(memory_cost.to_string()));
+    if !(MIN_MEMORY_COST..=MAX_MEMORY_COST).contains(&memory_cost) {  // 8MB - 2GB
+        return Err(SCypherError::invalid_parameter(
+            Parameter::MemoryCost, memory_cost, format!("{}-{} KB", MIN_MEMORY_COST, MAX_MEMORY_COST)));
     }
 
     Ok(())
@@ -92,6 +283,53 @@ mod tests {
         assert_ne!(keystream, keystream3);
     }
 
+    #[test]
+    fn test_workspace_reuse_matches_fresh() {
+        let small = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let large = TransformBuilder::new().iterations(1).memory_cost(16384).build().unwrap();
+        let mut workspace = KdfWorkspace::new();
+
+        // El contenido previo del workspace no afecta al resultado
+        for params in [&small, &large, &small] {
+            let reused = derive_keystream_in("test_password", 32, params, &mut workspace).unwrap();
+            assert_eq!(reused, derive_keystream_with_params("test_password", 32, params).unwrap());
+        }
+        assert_eq!(workspace.capacity_kb(), 16384);
+
+        // Tras derivar quedan datos derivados; `wipe` los borra todos
+        let is_zero = |workspace: &KdfWorkspace| workspace.blocks.iter().all(|block| block.as_ref().iter().all(|&word| word == 0));
+        assert!(!is_zero(&workspace));
+        workspace.wipe();
+        assert!(is_zero(&workspace));
+        assert_eq!(workspace.capacity_kb(), 16384);
+    }
+
+    #[test]
+    fn test_keystream_params_affect_output() {
+        let base = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let keystream = derive_keystream_with_params("test_password", 16, &base).unwrap();
+
+        let with_keyfile = TransformBuilder::new().iterations(1).memory_cost(8192)
+            .keyfile(b"keyfile contents").build().unwrap();
+        assert_ne!(keystream, derive_keystream_with_params("test_password", 16, &with_keyfile).unwrap());
+
+        let with_salt = TransformBuilder::new().iterations(1).memory_cost(8192)
+            .salt(SaltSource::Explicit(b"explicit-salt".to_vec())).build().unwrap();
+        assert_ne!(keystream, derive_keystream_with_params("test_password", 16, &with_salt).unwrap());
+
+        let with_lanes = TransformBuilder::new().iterations(1).memory_cost(8192)
+            .lanes(2).build().unwrap();
+        assert_ne!(keystream, derive_keystream_with_params("test_password", 16, &with_lanes).unwrap());
+
+        let with_argon2i = TransformBuilder::new().iterations(1).memory_cost(8192)
+            .kdf(Kdf::Argon2i).build().unwrap();
+        assert_ne!(keystream, derive_keystream_with_params("test_password", 16, &with_argon2i).unwrap());
+
+        let with_v0x10 = TransformBuilder::new().iterations(1).memory_cost(8192)
+            .argon2_version(Argon2Version::V0x10).build().unwrap();
+        assert_ne!(keystream, derive_keystream_with_params("test_password", 16, &with_v0x10).unwrap());
+    }
+
     #[test]
     fn test_validate_argon2_params() {
         // Casos válidos
@@ -105,6 +343,18 @@ mod tests {
         assert!(validate_argon2_params(5, 3_000_000).is_err());
     }
 
+    #[test]
+    fn test_derive_keystream_matches_argon2() {
+        // Referencia: el crate argon2 llamado directamente (Argon2id v0x13, 1 lane)
+        let salt = generate_deterministic_salt("test_password");
+        let mut expected = [0u8; 32];
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, argon2::Params::new(8192, 1, 1, Some(32)).unwrap())
+            .hash_password_into(b"test_password", &salt, &mut expected)
+            .unwrap();
+        assert_eq!(hex::encode(expected), "271cb2867cbc953f404e8b5479338d1ad839a1fe4bca7972e21bbead72b98a4e");
+        assert_eq!(derive_keystream("test_password", 32, 1, 8192).unwrap(), expected);
+    }
+
     #[test]
     fn test_deterministic_salt() {
         let salt1 = generate_deterministic_salt("password");
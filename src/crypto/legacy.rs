@@ -0,0 +1,97 @@
+//! Compatibilidad con SCypher v2 (el script Bash original, `SCypherV2.sh`)
+//!
+//! Antes de Argon2id, el script derivaba el keystream con SHAKE-256
+//! encadenado a través de `openssl dgst -shake256 -xoflen N`: la primera
+//! ronda resume la contraseña y cada ronda siguiente resume la salida
+//! anterior tal como la imprime openssl (hex en minúsculas). La última
+//! salida, en bytes, es el keystream, con la longitud de la entropía. Sin
+//! salt, sin memoria, solo lista inglesa y sin validar el checksum de la
+//! entrada; el XOR y el checksum recalculado son los mismos que hoy.
+//!
+//! Solo sirve para recuperar copias hechas con ese script (`--compat v2`):
+//! SHAKE-256 no es un KDF lento, así que cada contraseña se prueba en
+//! microsegundos. Las frases nuevas se cifran con Argon2id.
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use zeroize::Zeroizing;
+
+use crate::bip39::wordlist::ENGLISH;
+use crate::error::{SCypherError, Parameter, Result};
+use super::{ChecksumPolicy, NoProgress, TransformBuilder};
+
+/// Iteraciones por defecto del script v2
+pub const V2_DEFAULT_ITERATIONS: u32 = 1000;
+
+/// Límite de iteraciones aceptado en modo v2
+pub const V2_MAX_ITERATIONS: u32 = 1_000_000;
+
+/// Transformar una frase inglesa como lo hacía SCypher v2
+///
+/// Simétrica como la transformación actual: aplicarla dos veces con la
+/// misma contraseña e iteraciones devuelve la frase original.
+pub fn transform_seed_v2(seed_phrase: &str, password: &str, iterations: u32) -> Result<Zeroizing<String>> {
+    if !(1..=V2_MAX_ITERATIONS).contains(&iterations) {
+        return Err(SCypherError::invalid_parameter(
+            Parameter::Iterations, iterations, format!("1-{} in --compat v2", V2_MAX_ITERATIONS)));
+    }
+
+    // Los parámetros solo aportan la política de checksum y el cifrado XOR
+    let params = TransformBuilder::new().checksum_policy(ChecksumPolicy::Skip).build()?;
+    super::transform_with_keystream(seed_phrase, &params, &ENGLISH, &NoProgress, &mut |output| {
+        shake_keystream(password, iterations, output);
+        Ok(())
+    })
+}
+
+/// Keystream de v2: `iterations` rondas de SHAKE-256 de `output.len()` bytes
+pub(super) fn shake_keystream(password: &str, iterations: u32, output: &mut [u8]) {
+    let mut hex_output = Zeroizing::new(vec![0u8; output.len() * 2]);
+    for round in 0..iterations {
+        let mut shake = Shake256::default();
+        match round {
+            0 => shake.update(password.as_bytes()),
+            _ => shake.update(&hex_output),
+        }
+        shake.finalize_xof().read(output);
+        hex::encode_to_slice(&*output, &mut hex_output[..]).expect("hex buffer is twice the keystream");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_v2_known_answers() {
+        // Keystreams capturados con openssl 3, el mismo comando que usa
+        // SCypherV2.sh, y no con esta implementación:
+        //   h=$(printf '%s' password123 | openssl dgst -shake256 -xoflen 16 | awk '{print $NF}')
+        //   h=$(printf '%s' "$h" | openssl dgst -shake256 -xoflen 16 | awk '{print $NF}')  # cada ronda más
+        // `crypto::differential` (característica `legacy-diff`) repite la
+        // comparación con openssl para casos al azar y, si se indica,
+        // contra el propio script.
+        let mut keystream = [0u8; 16];
+        shake_keystream("password123", 1, &mut keystream);
+        assert_eq!(hex::encode(keystream), "a92385aedbd4a384d0ee6ce64eb2c345");
+        shake_keystream("password123", 2, &mut keystream);
+        assert_eq!(hex::encode(keystream), "55ee85cd5013685bbc2fa4f639f1e51a");
+        shake_keystream("password123", V2_DEFAULT_ITERATIONS, &mut keystream);
+        assert_eq!(hex::encode(keystream), "d8913cad534146d431d99effa9f3eabb");
+
+        // La entropía de PHRASE es cero: la frase cifrada es el keystream de
+        // 1000 rondas con el checksum recalculado (el núcleo XOR compartido)
+        let encrypted = transform_seed_v2(PHRASE, "password123", V2_DEFAULT_ITERATIONS).unwrap();
+        assert_eq!(hex::encode(crate::bip39::conversion::phrase_to_entropy(&encrypted).unwrap()), hex::encode(keystream));
+        assert_eq!(*encrypted, "sudden measure clip pledge behave head shuffle critic zero exhibit wide iron");
+        assert_eq!(*transform_seed_v2(&encrypted, "password123", V2_DEFAULT_ITERATIONS).unwrap(), PHRASE);
+
+        // No es la transformación actual, y las iteraciones cuentan
+        assert_ne!(*encrypted, *super::super::transform_seed(PHRASE, "password123", 1, 8192).unwrap());
+        assert_ne!(*encrypted, *transform_seed_v2(PHRASE, "password123", 999).unwrap());
+        assert!(transform_seed_v2(PHRASE, "password123", 0).is_err());
+        assert!(transform_seed_v2("abandon ábaco", "password123", 1).is_err());
+    }
+}
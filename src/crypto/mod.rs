@@ -3,66 +3,418 @@
 pub mod keystream;
 pub mod xor;
 pub mod checksum;
+pub mod params;
+pub mod progress;
+pub mod session;
+pub mod batch;
+pub mod simd;
+pub mod expand;
+pub mod padding;
+pub mod fingerprint;
+pub mod raw_seed;
+pub mod rng;
+pub mod vectors;
+pub mod legacy;
+pub mod dual;
+pub mod key_check;
+pub mod stream;
+#[cfg(all(test, feature = "legacy-diff"))]
+mod differential;
 
-use crate::error::Result;
+pub use params::{ScypherParams, TransformBuilder, Kdf, Argon2Version, Cipher, ChecksumPolicy, SaltSource, Pepper};
+pub use progress::{ProgressSink, Phase, NoProgress, CancelFlag};
+pub use session::SessionKey;
+pub use batch::{transform_batch_parallel, transform_batch_parallel_timed, BatchOptions};
+pub use expand::{index_label, MasterKey};
+pub use rng::{SecureRng, seeded_rng};
+
+use zeroize::Zeroizing;
+
+use crate::bip39::wordlist::{Wordlist, WordlistChecksum, ENGLISH};
+use crate::error::{SCypherError, Result};
+use progress::checkpoint;
 
 /// Función principal para transformar seed phrase usando XOR
-/// Esta es la función que une todos los componentes criptográficos
+///
+/// Forma posicional histórica: equivale a `transform_seed_with_params` con
+/// los parámetros por defecto, las iteraciones/memoria indicadas y
+/// `ChecksumPolicy::Skip` (el checksum de entrada no se valida aquí).
 pub fn transform_seed(
     seed_phrase: &str,
     password: &str,
     iterations: u32,
     memory_cost: u32,
-) -> Result<String> {
-    // Validar parámetros Argon2id
-    keystream::validate_argon2_params(iterations, memory_cost)?;
+) -> Result<Zeroizing<String>> {
+    let params = TransformBuilder::new()
+        .iterations(iterations)
+        .memory_cost(memory_cost)
+        .checksum_policy(ChecksumPolicy::Skip)
+        .build()?;
+
+    transform_seed_with_params(seed_phrase, password, &params)
+}
+
+/// Transformar una seed phrase con parámetros construidos por `TransformBuilder`
+pub fn transform_seed_with_params(
+    seed_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+) -> Result<Zeroizing<String>> {
+    transform_seed_with_progress(seed_phrase, password, params, &ENGLISH, &NoProgress)
+}
+
+/// Transformar una frase codificada con cualquier `Wordlist`
+pub fn transform_seed_with_wordlist(
+    seed_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+    wordlist: &dyn Wordlist,
+) -> Result<Zeroizing<String>> {
+    transform_seed_with_progress(seed_phrase, password, params, wordlist, &NoProgress)
+}
 
-    // Convertir seed phrase a bits usando BIP39
-    let seed_bits = crate::bip39::conversion::phrase_to_bits(seed_phrase)?;
+/// Transformar informando el progreso por fases a `sink`
+///
+/// La cancelación se comprueba antes de cada fase; los buffers de entropía
+/// y keystream se limpian tanto al terminar como al cancelar.
+pub fn transform_seed_with_progress(
+    seed_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+    wordlist: &dyn Wordlist,
+    sink: &dyn ProgressSink,
+) -> Result<Zeroizing<String>> {
+    transform_with_keystream(seed_phrase, params, wordlist, sink, &mut |output| {
+        keystream::derive_keystream_cancellable(password, output, params, &mut keystream::KdfWorkspace::new(), sink)
+    })
+}
+
+/// Combinar dos frases: XOR de la entropía de `seed_phrase` con la de `key_phrase`
+///
+/// Sin contraseña ni KDF: el resultado junto con cualquiera de las dos
+/// frases recupera la otra (conocimiento dividido). La frase clave debe ser
+/// una frase BIP39 válida en `key_wordlist`, con las mismas palabras que
+/// `seed_phrase`, y generada al azar solo para esto: si es la semilla de
+/// otra cartera, quien la tenga y vea el resultado obtiene la frase protegida.
+pub fn transform_with_key_phrase(
+    seed_phrase: &str,
+    wordlist: &dyn Wordlist,
+    key_phrase: &str,
+    key_wordlist: &dyn Wordlist,
+    params: &ScypherParams,
+) -> Result<Zeroizing<String>> {
+    crate::bip39::validation::validate_seed_phrase_with(key_phrase, key_wordlist)?;
+    let key_words = key_phrase.split_whitespace().count();
+    let seed_words = seed_phrase.split_whitespace().count();
+    if key_words != seed_words {
+        return Err(SCypherError::InvalidRequest(format!(
+            "the key phrase has {} words but the seed phrase has {}; both must be the same length",
+            key_words, seed_words,
+        )));
+    }
+
+    let key_indices = crate::bip39::conversion::phrase_to_indices_with(key_phrase, key_wordlist)?;
+    transform_with_keystream(seed_phrase, params, wordlist, &NoProgress, &mut |output| {
+        let mut packed = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+        simd::pack_indices_into(&key_indices, &mut packed[..]);
+        output.copy_from_slice(&packed[..output.len()]);
+        Ok(())
+    })
+}
+
+/// Transformar reutilizando la memoria Argon2id de `workspace` (lotes)
+pub(crate) fn transform_with_workspace(
+    seed_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+    sink: &dyn ProgressSink,
+    workspace: &mut keystream::KdfWorkspace,
+) -> Result<Zeroizing<String>> {
+    transform_with_keystream(seed_phrase, params, &ENGLISH, sink, &mut |output| {
+        keystream::derive_keystream_cancellable(password, output, params, workspace, sink)
+    })
+}
+
+use scypher_core::{MAX_PACKED_BYTES, MAX_WORDS};
+
+/// Núcleo de la transformación: une todos los componentes criptográficos
+///
+/// `keystream_for` escribe el keystream en el buffer que recibe, del tamaño
+/// de la entropía; normalmente lo deriva con Argon2id, pero `SessionKey` lo
+/// copia de su caché. Índices, entropía y keystream viven en arrays de
+/// tamaño fijo en la pila que se limpian al salir: el único material
+/// secreto que se reserva en el heap es la frase resultante, con su tamaño
+/// exacto para que no haya realocaciones.
+#[tracing::instrument(
+    name = "transform",
+    level = "debug",
+    skip_all,
+    fields(word_count = seed_phrase.split_whitespace().count(), language = wordlist.language_id())
+)]
+pub(crate) fn transform_with_keystream(
+    seed_phrase: &str,
+    params: &ScypherParams,
+    wordlist: &dyn Wordlist,
+    sink: &dyn ProgressSink,
+    keystream_for: &mut dyn FnMut(&mut [u8]) -> Result<()>,
+) -> Result<Zeroizing<String>> {
+    // Aplicar política de checksum de la entrada
+    checkpoint(sink, Phase::Validate)?;
+    if params.checksum_policy() == ChecksumPolicy::Verify {
+        crate::bip39::validation::validate_seed_phrase_with(seed_phrase, wordlist)?;
+    }
+
+    // Convertir la frase a índices (11 bits por palabra)
+    let mut indices = Zeroizing::new([0u16; MAX_WORDS]);
+    let mut word_count = 0;
+    for word in seed_phrase.split_whitespace() {
+        if word_count == MAX_WORDS {
+            return Err(SCypherError::InvalidWordCount(seed_phrase.split_whitespace().count()));
+        }
+        let index = wordlist.index_of(word)
+            .ok_or_else(|| SCypherError::invalid_word_at(word_count + 1, word))?;
+        indices[word_count] = index as u16;
+        word_count += 1;
+    }
+    if word_count == 0 {
+        return Err(SCypherError::InvalidSeedPhrase);
+    }
 
-    // Separar entropía y checksum
-    let word_count = seed_phrase.split_whitespace().count();
     let entropy_bits = word_count * 32 / 3;  // Bits de entropía según BIP39
     let checksum_bits = entropy_bits / 32;   // Bits de checksum según BIP39
 
-    if seed_bits.len() != entropy_bits + checksum_bits {
-        return Err(crate::error::SCypherError::crypto(
+    if word_count * 11 != entropy_bits + checksum_bits {
+        return Err(SCypherError::crypto(
             "Invalid seed phrase bit length".to_string()
         ));
     }
 
-    // Extraer SOLO la parte de entropía (ignorar checksum actual)
-    let entropy_part = &seed_bits[0..entropy_bits];
+    // La entropía ocupa bytes completos (múltiplo de 32 bits); el checksum se ignora
+    let mut packed = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+    simd::pack_indices_into(&indices[..word_count], &mut packed[..]);
+    let entropy_len = entropy_bits / 8;
+
+    // Generar keystream del tamaño de la entropía; mientras tanto la
+    // entropía queda vigilada contra corrupción de memoria
+    checkpoint(sink, Phase::DeriveKey)?;
+    let mut keystream = Zeroizing::new([0u8; MAX_PACKED_BYTES]);
+    crate::security::integrity::with_watch(&mut packed[..entropy_len], || keystream_for(&mut keystream[..entropy_len]))?;
+
+    // Aplicar XOR solo a la entropía (único cifrado soportado)
+    checkpoint(sink, Phase::Encrypt)?;
+    match params.cipher() {
+        Cipher::Xor => xor::xor_in_place(&mut packed[..entropy_len], &keystream[..entropy_len])?,
+    }
+
+    // Recalcular checksum para la nueva entropía: primeros ENT/32 bits del hash
+    let checksum_byte = match wordlist.checksum() {
+        WordlistChecksum::Bip39 => scypher_core::checksum_byte(&packed[..entropy_len]),
+    };
+
+    // Combinar entropía cifrada + nuevo checksum y volver a palabras
+    checkpoint(sink, Phase::Encode)?;
+    packed[entropy_len] = checksum_byte;
+    simd::unpack_indices_into(&packed[..=entropy_len], &mut indices[..word_count]);
+    crate::bip39::conversion::indices_to_phrase_with(&indices[..word_count], wordlist).map(Zeroizing::new)
+}
+
+/// Transformar varias frases con la misma contraseña y parámetros
+///
+/// Un error en un elemento no detiene el resto; `sink` recibe las fases de
+/// cada frase y `on_progress` tras cada una. La memoria de Argon2id se
+/// reserva una vez y se reutiliza para todo el lote. Si se cancela, el lote entero
+/// devuelve `SCypherError::Cancelled`.
+pub fn transform_batch(
+    seed_phrases: &[&str],
+    password: &str,
+    params: &ScypherParams,
+    sink: &dyn ProgressSink,
+) -> Result<Vec<Result<Zeroizing<String>>>> {
+    let total = seed_phrases.len();
+    let mut results = Vec::with_capacity(total);
+    let mut workspace = keystream::KdfWorkspace::new();
+
+    for (index, seed_phrase) in seed_phrases.iter().enumerate() {
+        let result = transform_with_workspace(seed_phrase, password, params, sink, &mut workspace);
+
+        if let Err(crate::error::SCypherError::Cancelled) = result {
+            return Err(crate::error::SCypherError::Cancelled);
+        }
+
+        results.push(result);
+        sink.on_progress(index + 1, total);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn fast_params() -> TransformBuilder {
+        TransformBuilder::new().iterations(1).memory_cost(8192)
+    }
+
+    #[test]
+    fn test_transform_roundtrip() {
+        let params = fast_params().build().unwrap();
+
+        let encrypted = params.transform(TEST_PHRASE, "test_password").unwrap();
+        assert_ne!(*encrypted, TEST_PHRASE);
+        assert!(crate::bip39::verify_checksum(&encrypted).unwrap());
+
+        let decrypted = params.transform(&encrypted, "test_password").unwrap();
+        assert_eq!(*decrypted, TEST_PHRASE);
+    }
+
+    #[test]
+    fn test_key_phrase_recovers_either_side() {
+        let params = fast_params().build().unwrap();
+        let key = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+
+        let combined = transform_with_key_phrase(TEST_PHRASE, &ENGLISH, key, &ENGLISH, &params).unwrap();
+        assert!(crate::bip39::verify_checksum(&combined).unwrap());
+        assert_eq!(*transform_with_key_phrase(&combined, &ENGLISH, key, &ENGLISH, &params).unwrap(), TEST_PHRASE);
+        assert_eq!(*transform_with_key_phrase(&combined, &ENGLISH, TEST_PHRASE, &ENGLISH, &params).unwrap(), key);
+
+        // XOR es conmutativo: da igual cuál de las dos se use como clave
+        assert_eq!(transform_with_key_phrase(key, &ENGLISH, TEST_PHRASE, &ENGLISH, &params).unwrap(), combined);
+
+        let short_key = "abandon abandon abandon";
+        assert!(transform_with_key_phrase(TEST_PHRASE, &ENGLISH, short_key, &ENGLISH, &params).is_err());
+        let longer_key = "abandon ".repeat(23) + "art";
+        assert!(matches!(
+            transform_with_key_phrase(TEST_PHRASE, &ENGLISH, &longer_key, &ENGLISH, &params),
+            Err(SCypherError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_known_answer() {
+        // Vector fijo: cualquier cambio en la ruta de transformación lo rompe
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        assert_eq!(
+            *params.transform(TEST_PHRASE, "password123").unwrap(),
+            "shock strong glory exchange syrup east illness crater board genuine wish clarify"
+        );
+    }
 
-    // Convertir entropía a bytes para XOR
-    let entropy_bytes = crate::crypto::checksum::bits_to_bytes_padded(entropy_part);
+    #[test]
+    fn test_core_transform_matches() {
+        // El núcleo no_std con el KDF de este crate da el mismo resultado
+        use crate::bip39::conversion::{indices_to_phrase_with, phrase_to_indices_with};
+
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let mut indices = phrase_to_indices_with(TEST_PHRASE, &ENGLISH).unwrap();
+        let mut source = keystream::PasswordKeystream::new("password123", &params);
+        scypher_core::transform_indices(&mut indices[..], &mut source).unwrap();
+        assert_eq!(
+            indices_to_phrase_with(&indices, &ENGLISH).unwrap(),
+            *params.transform(TEST_PHRASE, "password123").unwrap()
+        );
+    }
 
-    // Generar keystream del tamaño de la entropía
-    let keystream = keystream::derive_keystream(password, entropy_bytes.len(), iterations, memory_cost)?;
+    #[test]
+    fn test_indices_path_matches_bit_path() {
+        use crate::bip39::conversion::{bits_to_phrase, phrase_to_bits};
 
-    // Aplicar XOR solo a la entropía
-    let encrypted_entropy_bytes = xor::xor_data(&entropy_bytes, &keystream)?;
+        let params = fast_params().build().unwrap();
 
-    // Convertir entropía cifrada de vuelta a bits
-    let mut encrypted_entropy_bits = Vec::new();
-    for byte in &encrypted_entropy_bytes {
-        for i in (0..8).rev() {
-            encrypted_entropy_bits.push((byte >> i) & 1 == 1);
+        for words in [12, 15, 18, 21, 24] {
+            let phrase = crate::bip39::conversion::generate_seed_phrase(words * 32 / 3).unwrap();
+
+            // Referencia: implementación original con vectores de bits
+            let entropy_bits = words * 32 / 3;
+            let bits = phrase_to_bits(&phrase).unwrap();
+            let entropy = checksum::bits_to_bytes_padded(&bits[..entropy_bits]);
+            let key = keystream::derive_keystream_with_params("test_password", entropy.len(), &params).unwrap();
+            let mut expected_bits = xor::bytes_to_bits(&xor::xor_data(&entropy, &key).unwrap());
+            expected_bits.extend(checksum::recalculate_bip39_checksum(&expected_bits).unwrap());
+
+            assert_eq!(*params.transform(&phrase, "test_password").unwrap(), bits_to_phrase(&expected_bits).unwrap());
         }
     }
 
-    // Truncar a la longitud exacta de entropía
-    encrypted_entropy_bits.truncate(entropy_bits);
+    #[test]
+    fn test_positional_matches_params() {
+        let params = fast_params().checksum_policy(ChecksumPolicy::Skip).build().unwrap();
+
+        assert_eq!(
+            transform_seed(TEST_PHRASE, "test_password", 1, 8192).unwrap(),
+            transform_seed_with_params(TEST_PHRASE, "test_password", &params).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_checksum_policy() {
+        // Checksum inválido: "abandon" x12
+        let bad_checksum = ["abandon"; 12].join(" ");
 
-    // Recalcular checksum BIP39 para la nueva entropía
-    let new_checksum_bits = crate::crypto::checksum::recalculate_bip39_checksum(&encrypted_entropy_bits)?;
+        let verify = fast_params().build().unwrap();
+        assert!(verify.transform(&bad_checksum, "test_password").is_err());
 
-    // Combinar entropía cifrada + nuevo checksum
-    let mut final_bits = encrypted_entropy_bits;
-    final_bits.extend(new_checksum_bits);
+        let skip = fast_params().checksum_policy(ChecksumPolicy::Skip).build().unwrap();
+        assert!(skip.transform(&bad_checksum, "test_password").is_ok());
 
-    // Convertir de vuelta a seed phrase BIP39
-    let result_phrase = crate::bip39::conversion::bits_to_phrase(&final_bits)?;
+        // Más de 24 palabras no caben en los buffers fijos
+        let too_long = ["abandon"; 27].join(" ");
+        assert!(matches!(skip.transform(&too_long, "test_password"), Err(SCypherError::InvalidWordCount(27))));
+    }
+
+    #[test]
+    fn test_custom_wordlist_roundtrip() {
+        use crate::bip39::wordlist::{CustomWordlist, BIP39_WORDLIST};
+
+        // Lista inglesa invertida: mismas palabras, índices distintos
+        let words = BIP39_WORDLIST.iter().rev().map(|w| w.to_string()).collect();
+        let reversed = CustomWordlist::new("en-reversed", words).unwrap();
+        let phrase = ["zoo"; 11].join(" ") + " zebra";
+        let params = fast_params().build().unwrap();
+
+        let encrypted = transform_seed_with_wordlist(&phrase, "test_password", &params, &reversed).unwrap();
+        assert!(crate::bip39::validate_seed_phrase_with(&encrypted, &reversed).is_ok());
+
+        let decrypted = transform_seed_with_wordlist(&encrypted, "test_password", &params, &reversed).unwrap();
+        assert_eq!(*decrypted, phrase);
+    }
 
-    Ok(result_phrase)
+    #[test]
+    fn test_progress_and_cancel() {
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Recorder {
+            phases: Mutex<Vec<Phase>>,
+            progress: Mutex<Vec<(usize, usize)>>,
+        }
+
+        impl ProgressSink for Recorder {
+            fn on_phase(&self, phase: Phase) {
+                self.phases.lock().unwrap().push(phase);
+            }
+
+            fn on_progress(&self, completed: usize, total: usize) {
+                self.progress.lock().unwrap().push((completed, total));
+            }
+        }
+
+        let params = fast_params().build().unwrap();
+        let recorder = Recorder::default();
+        let results = transform_batch(&[TEST_PHRASE, "not a phrase"], "test_password", &params, &recorder).unwrap();
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(recorder.phases.lock().unwrap()[..4], [Phase::Validate, Phase::DeriveKey, Phase::Encrypt, Phase::Encode]);
+        assert_eq!(*recorder.progress.lock().unwrap(), vec![(1, 2), (2, 2)]);
+
+        let flag = CancelFlag::new();
+        flag.cancel();
+        assert!(matches!(
+            transform_batch(&[TEST_PHRASE], "test_password", &params, &flag),
+            Err(crate::error::SCypherError::Cancelled)
+        ));
+    }
 }
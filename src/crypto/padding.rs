@@ -0,0 +1,166 @@
+//! Relleno a 24 palabras para que todas las copias tengan el mismo aspecto
+//!
+//! Una frase de 12 a 21 palabras se cifra dentro de una de 24: la entropía
+//! original ocupa el principio de los 256 bits y el resto es relleno
+//! aleatorio terminado en una etiqueta de autenticación. Todo se cifra con
+//! XOR, así que el resultado es una frase BIP39 de 24 palabras
+//! indistinguible de cualquier otra.
+//!
+//! Argon2id produce 64 bytes: los 32 primeros son el keystream y los 32
+//! últimos la clave de la etiqueta. La etiqueta (SHA-256 con la clave como
+//! prefijo, truncada) cubre la longitud original, la entropía y el relleno;
+//! al deshacer el relleno se prueban las longitudes posibles y solo se
+//! acepta la que autentica, de modo que una contraseña equivocada da error
+//! en lugar de otra frase. Es un esquema distinto de `transform_seed`: una
+//! frase rellenada solo se recupera con `unpad_transform`.
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::bip39::conversion::{entropy_to_phrase_with, phrase_to_entropy_with};
+use crate::bip39::wordlist::Wordlist;
+use crate::crypto::keystream::{self, KdfWorkspace};
+use crate::crypto::{ChecksumPolicy, ScypherParams, SecureRng};
+use crate::error::{SCypherError, Result};
+
+/// Palabras de toda frase rellenada
+pub const PADDED_WORDS: usize = 24;
+
+/// Bytes de entropía de una frase de `PADDED_WORDS` palabras
+const PADDED_BYTES: usize = 32;
+
+/// Longitud máxima de la etiqueta de autenticación
+const TAG_LEN: usize = 8;
+
+/// Prefijo de dominio de la etiqueta
+const TAG_DOMAIN: &[u8] = b"scypher/pad/v1";
+
+/// Longitudes de entropía que se pueden rellenar (12, 15, 18 y 21 palabras)
+const ENTROPY_LENGTHS: [usize; 4] = [16, 20, 24, 28];
+
+/// Keystream (32 bytes) y clave de la etiqueta (32 bytes) de una sola derivación
+fn derive_keys(password: &str, params: &ScypherParams) -> Result<Zeroizing<[u8; 2 * PADDED_BYTES]>> {
+    let mut keys = Zeroizing::new([0u8; 2 * PADDED_BYTES]);
+    keystream::derive_keystream_into(password, &mut keys[..], params, &mut KdfWorkspace::new())?;
+    Ok(keys)
+}
+
+/// Etiqueta sobre la longitud, la entropía y el relleno aleatorio
+fn tag(tag_key: &[u8], entropy_len: usize, body: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(tag_key);
+    hasher.update(TAG_DOMAIN);
+    hasher.update([entropy_len as u8]);
+    hasher.update(body);
+    hasher.finalize().into()
+}
+
+/// Bytes de etiqueta para una entropía de `entropy_len` bytes (el resto del relleno es aleatorio)
+fn tag_len(entropy_len: usize) -> usize {
+    (PADDED_BYTES - entropy_len).min(TAG_LEN)
+}
+
+/// Cifrar `seed_phrase` dentro de una frase de 24 palabras
+pub fn pad_transform(
+    seed_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+    wordlist: &dyn Wordlist,
+) -> Result<Zeroizing<String>> {
+    pad_transform_with_rng(seed_phrase, password, params, wordlist, &mut rand::rngs::OsRng)
+}
+
+/// `pad_transform` con el relleno tomado de `rng`
+pub fn pad_transform_with_rng(
+    seed_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+    wordlist: &dyn Wordlist,
+    rng: &mut dyn SecureRng,
+) -> Result<Zeroizing<String>> {
+    if params.checksum_policy() == ChecksumPolicy::Verify {
+        crate::bip39::validation::validate_seed_phrase_with(seed_phrase, wordlist)?;
+    }
+    let entropy = Zeroizing::new(phrase_to_entropy_with(seed_phrase, wordlist)?);
+    let entropy_len = entropy.len();
+    if !ENTROPY_LENGTHS.contains(&entropy_len) {
+        return Err(SCypherError::InvalidRequest(format!(
+            "only 12 to 21 word phrases can be padded to {} words", PADDED_WORDS)));
+    }
+
+    // Entropía | relleno aleatorio | etiqueta
+    let tag_start = PADDED_BYTES - tag_len(entropy_len);
+    let mut padded = Zeroizing::new([0u8; PADDED_BYTES]);
+    padded[..entropy_len].copy_from_slice(&entropy);
+    rng.fill_bytes(&mut padded[entropy_len..tag_start]);
+
+    let keys = derive_keys(password, params)?;
+    let tag = tag(&keys[PADDED_BYTES..], entropy_len, &padded[..tag_start]);
+    padded[tag_start..].copy_from_slice(&tag[..PADDED_BYTES - tag_start]);
+
+    crate::crypto::xor::xor_in_place(&mut padded[..], &keys[..PADDED_BYTES])?;
+    entropy_to_phrase_with(&padded[..], wordlist).map(Zeroizing::new)
+}
+
+/// Recuperar la frase original de una frase rellenada de 24 palabras
+pub fn unpad_transform(
+    padded_phrase: &str,
+    password: &str,
+    params: &ScypherParams,
+    wordlist: &dyn Wordlist,
+) -> Result<Zeroizing<String>> {
+    let words = padded_phrase.split_whitespace().count();
+    if words != PADDED_WORDS {
+        return Err(SCypherError::InvalidRequest(format!(
+            "a padded phrase has {} words, found {}", PADDED_WORDS, words)));
+    }
+
+    let mut padded = Zeroizing::new([0u8; PADDED_BYTES]);
+    padded.copy_from_slice(&Zeroizing::new(phrase_to_entropy_with(padded_phrase, wordlist)?));
+
+    let keys = derive_keys(password, params)?;
+    crate::crypto::xor::xor_in_place(&mut padded[..], &keys[..PADDED_BYTES])?;
+
+    // Probar cada longitud original: solo la correcta autentica
+    for entropy_len in ENTROPY_LENGTHS {
+        let tag_start = PADDED_BYTES - tag_len(entropy_len);
+        let expected = tag(&keys[PADDED_BYTES..], entropy_len, &padded[..tag_start]);
+        if expected[..PADDED_BYTES - tag_start] == padded[tag_start..] {
+            return entropy_to_phrase_with(&padded[..entropy_len], wordlist).map(Zeroizing::new);
+        }
+    }
+
+    Err(SCypherError::crypto(
+        "Padded phrase does not authenticate: wrong password or parameters, or not a padded phrase"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39::wordlist::ENGLISH;
+
+    #[test]
+    fn test_pad_round_trip() {
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+
+        for words in [12, 15, 18, 21] {
+            let phrase = crate::bip39::conversion::generate_seed_phrase(words * 32 / 3).unwrap();
+            let padded = pad_transform(&phrase, "test_password", &params, &ENGLISH).unwrap();
+            assert_eq!(padded.split_whitespace().count(), PADDED_WORDS);
+            assert!(crate::bip39::verify_checksum(&padded).unwrap());
+            assert_eq!(*unpad_transform(&padded, "test_password", &params, &ENGLISH).unwrap(), phrase);
+            assert!(unpad_transform(&padded, "wrong_password", &params, &ENGLISH).is_err());
+        }
+
+        // El relleno aleatorio hace que dos cifrados de la misma frase difieran
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_ne!(
+            pad_transform(phrase, "test_password", &params, &ENGLISH).unwrap(),
+            pad_transform(phrase, "test_password", &params, &ENGLISH).unwrap(),
+        );
+
+        let full = crate::bip39::conversion::generate_seed_phrase(256).unwrap();
+        assert!(pad_transform(&full, "test_password", &params, &ENGLISH).is_err());
+        assert!(unpad_transform(phrase, "test_password", &params, &ENGLISH).is_err());
+    }
+}
@@ -0,0 +1,571 @@
+//! Parámetros de transformación y su builder
+//!
+//! `ScypherParams` agrupa todas las opciones de la transformación (KDF,
+//! iteraciones, memoria, lanes, salt, keyfiles, cifrado y política de
+//! checksum). Solo se obtiene a través de `TransformBuilder::build`, que
+//! valida todos los valores, de modo que unos parámetros construidos
+//! siempre son utilizables.
+//!
+//! Los parámetros se pueden serializar (JSON, archivos de configuración,
+//! trabajos batch). Los hashes de keyfiles nunca se serializan, y al
+//! deserializar se pasa por el builder, de modo que un archivo con valores
+//! fuera de rango se rechaza igual que en la API.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Digest};
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Parameter, Result};
+use crate::security::memory::SecureBuffer;
+
+/// Iteraciones Argon2id por defecto
+pub const DEFAULT_ITERATIONS: u32 = 5;
+
+/// Costo de memoria por defecto en KB (128MB)
+pub const DEFAULT_MEMORY_COST: u32 = 131072;
+
+/// Lanes (paralelismo) Argon2id por defecto
+pub const DEFAULT_LANES: u32 = 1;
+
+/// Límites de iteraciones aceptados
+pub const MIN_ITERATIONS: u32 = 1;
+pub const MAX_ITERATIONS: u32 = 100;
+
+/// Límites de memoria aceptados en KB (8MB - 2GB)
+pub const MIN_MEMORY_COST: u32 = 8192;
+pub const MAX_MEMORY_COST: u32 = 2_097_152;
+
+/// Límite de lanes aceptado
+pub const MAX_LANES: u32 = 64;
+
+/// Lanes según los núcleos detectados (1 a `MAX_LANES`)
+///
+/// Con la feature `parallel` cada lane se calcula en su propio hilo, así que
+/// con tantas lanes como núcleos la derivación tarda casi N veces menos con
+/// la misma memoria. Las lanes forman parte del resultado: una frase cifrada
+/// con N lanes solo se recupera con N lanes. Por eso `DEFAULT_LANES` sigue
+/// siendo 1 en la biblioteca y al descifrar, y la CLI usa este valor solo
+/// para los cifrados nuevos, indicando cuántas lanes hay que repetir.
+pub fn detected_lanes() -> u32 {
+    std::thread::available_parallelism()
+        .map_or(DEFAULT_LANES, |n| u32::try_from(n.get()).unwrap_or(MAX_LANES))
+        .clamp(1, MAX_LANES)
+}
+
+/// Mayor memoria segura para `--low-memory` con `available_kb` libres
+///
+/// Usa como mucho la mitad de la memoria disponible, redondeada a potencia
+/// de dos y sin pasar de `requested_kb`. Nunca baja de `MIN_MEMORY_COST`:
+/// si ni eso cabe, falla con `InsufficientMemory`.
+pub fn low_memory_cost(available_kb: u64, requested_kb: u32) -> Result<u32> {
+    let half = u32::try_from(available_kb / 2).unwrap_or(u32::MAX);
+    if half < MIN_MEMORY_COST {
+        return Err(SCypherError::InsufficientMemory);
+    }
+    let largest = 1u32 << (31 - half.leading_zeros());
+    Ok(largest.clamp(MIN_MEMORY_COST, requested_kb.max(MIN_MEMORY_COST)))
+}
+
+/// Longitud mínima de un salt explícito (requisito de Argon2)
+pub const MIN_SALT_LENGTH: usize = 8;
+
+/// Función de derivación de clave
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kdf {
+    /// Argon2id (la versión la fija `Argon2Version`)
+    #[default]
+    Argon2id,
+    /// Argon2i, para compatibilidad con resultados de otras implementaciones
+    Argon2i,
+}
+
+impl Kdf {
+    /// Todas las funciones de derivación disponibles
+    pub const ALL: [Kdf; 2] = [Kdf::Argon2id, Kdf::Argon2i];
+
+    /// Nombre usado en la línea de comandos y en los formatos serializados
+    pub fn name(self) -> &'static str {
+        match self {
+            Kdf::Argon2id => "argon2id",
+            Kdf::Argon2i => "argon2i",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Kdf> {
+        Kdf::ALL.into_iter().find(|kdf| kdf.name() == name)
+    }
+}
+
+/// Versión del algoritmo Argon2
+///
+/// 0x13 es la actual; 0x10 solo sirve para recuperar resultados de
+/// implementaciones o despliegues antiguos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Argon2Version {
+    #[serde(rename = "0x10")]
+    V0x10,
+    #[default]
+    #[serde(rename = "0x13")]
+    V0x13,
+}
+
+impl Argon2Version {
+    /// Todas las versiones disponibles
+    pub const ALL: [Argon2Version; 2] = [Argon2Version::V0x10, Argon2Version::V0x13];
+
+    /// Nombre usado en la línea de comandos y en los formatos serializados
+    pub fn name(self) -> &'static str {
+        match self {
+            Argon2Version::V0x10 => "0x10",
+            Argon2Version::V0x13 => "0x13",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Argon2Version> {
+        Argon2Version::ALL.into_iter().find(|version| version.name() == name)
+    }
+}
+
+/// Cifrado aplicado a la entropía
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cipher {
+    /// XOR con el keystream derivado (simétrico)
+    #[default]
+    Xor,
+}
+
+impl Cipher {
+    /// Todos los cifrados disponibles
+    pub const ALL: [Cipher; 1] = [Cipher::Xor];
+}
+
+/// Qué hacer con el checksum BIP39 de la frase de entrada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumPolicy {
+    /// Validar la frase completa (palabras, longitud y checksum) antes de transformar
+    #[default]
+    Verify,
+    /// Solo exigir palabras BIP39 válidas; el checksum de entrada se ignora
+    Skip,
+}
+
+/// Origen del salt de Argon2id
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaltSource {
+    /// Salt determinista derivado de la contraseña (comportamiento histórico)
+    #[default]
+    PasswordDerived,
+    /// Salt proporcionado explícitamente (hex en formatos serializados)
+    Explicit(#[serde(with = "hex_bytes")] Vec<u8>),
+}
+
+impl SaltSource {
+    /// Salt determinista derivado de una etiqueta fácil de recordar
+    ///
+    /// Separa las copias (cada etiqueta da otro keystream con la misma
+    /// contraseña) sin tener que guardar un salt aleatorio.
+    pub fn from_label(label: &str) -> Result<SaltSource> {
+        if label.trim().is_empty() {
+            return Err(SCypherError::invalid_parameter(Parameter::Salt, "empty label", "a non-empty label"));
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(b"scypher/salt-label/v1");
+        hasher.update(label.as_bytes());
+        Ok(SaltSource::Explicit(hasher.finalize().to_vec()))
+    }
+}
+
+/// Pepper: valor secreto K de Argon2, leído de un archivo local
+///
+/// Las copias de unos parámetros comparten el mismo `SecureBuffer`, que se
+/// limpia al soltar la última; en la CLI, en cuanto termina la operación.
+/// Nunca se serializa ni aparece en `Debug`.
+#[derive(Clone)]
+pub struct Pepper(Arc<SecureBuffer>);
+
+impl Pepper {
+    pub fn new(buffer: SecureBuffer) -> Self {
+        Self(Arc::new(buffer))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl std::fmt::Debug for Pepper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Pepper(..)")
+    }
+}
+
+impl PartialEq for Pepper {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for Pepper {}
+
+/// Parámetros validados de una transformación
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawParams")]
+pub struct ScypherParams {
+    kdf: Kdf,
+    argon2_version: Argon2Version,
+    iterations: u32,
+    memory_cost: u32,
+    lanes: u32,
+    salt: SaltSource,
+    #[serde(skip)]
+    keyfile_digests: Vec<[u8; 32]>,
+    #[serde(skip)]
+    pepper: Option<Pepper>,
+    cipher: Cipher,
+    checksum_policy: ChecksumPolicy,
+}
+
+/// Forma serializada sin validar; solo se usa para deserializar
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawParams {
+    kdf: Kdf,
+    argon2_version: Argon2Version,
+    iterations: u32,
+    memory_cost: u32,
+    lanes: u32,
+    salt: SaltSource,
+    cipher: Cipher,
+    checksum_policy: ChecksumPolicy,
+}
+
+impl Default for RawParams {
+    fn default() -> Self {
+        let params = ScypherParams::default();
+        Self {
+            kdf: params.kdf,
+            argon2_version: params.argon2_version,
+            iterations: params.iterations,
+            memory_cost: params.memory_cost,
+            lanes: params.lanes,
+            salt: params.salt,
+            cipher: params.cipher,
+            checksum_policy: params.checksum_policy,
+        }
+    }
+}
+
+impl TryFrom<RawParams> for ScypherParams {
+    type Error = SCypherError;
+
+    fn try_from(raw: RawParams) -> Result<Self> {
+        TransformBuilder::new()
+            .kdf(raw.kdf)
+            .argon2_version(raw.argon2_version)
+            .iterations(raw.iterations)
+            .memory_cost(raw.memory_cost)
+            .lanes(raw.lanes)
+            .salt(raw.salt)
+            .cipher(raw.cipher)
+            .checksum_policy(raw.checksum_policy)
+            .build()
+    }
+}
+
+/// Serialización de bytes como cadena hexadecimal
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        hex::decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+impl ScypherParams {
+    /// Crear un builder con los valores por defecto
+    pub fn builder() -> TransformBuilder {
+        TransformBuilder::new()
+    }
+
+    /// Builder con estos valores, para añadir o cambiar opciones
+    pub fn to_builder(&self) -> TransformBuilder {
+        TransformBuilder { params: self.clone() }
+    }
+
+    /// Leer parámetros en JSON (el objeto `params` de `scypher api`); los
+    /// campos ausentes toman el valor por defecto
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| SCypherError::InvalidRequest(format!("invalid parameters: {}", e)))
+    }
+
+    pub fn kdf(&self) -> Kdf {
+        self.kdf
+    }
+
+    pub fn argon2_version(&self) -> Argon2Version {
+        self.argon2_version
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    pub fn memory_cost(&self) -> u32 {
+        self.memory_cost
+    }
+
+    pub fn lanes(&self) -> u32 {
+        self.lanes
+    }
+
+    pub fn salt(&self) -> &SaltSource {
+        &self.salt
+    }
+
+    /// Hashes SHA-256 de los keyfiles, en el orden en que se añadieron
+    pub fn keyfile_digests(&self) -> &[[u8; 32]] {
+        &self.keyfile_digests
+    }
+
+    /// Pepper de `--pepper-file`, si lo hay
+    pub fn pepper(&self) -> Option<&Pepper> {
+        self.pepper.as_ref()
+    }
+
+    pub fn cipher(&self) -> Cipher {
+        self.cipher
+    }
+
+    pub fn checksum_policy(&self) -> ChecksumPolicy {
+        self.checksum_policy
+    }
+
+    /// Transformar una seed phrase con estos parámetros
+    pub fn transform(&self, seed_phrase: &str, password: &str) -> Result<Zeroizing<String>> {
+        super::transform_seed_with_params(seed_phrase, password, self)
+    }
+}
+
+impl Default for ScypherParams {
+    fn default() -> Self {
+        Self {
+            kdf: Kdf::default(),
+            argon2_version: Argon2Version::default(),
+            iterations: DEFAULT_ITERATIONS,
+            memory_cost: DEFAULT_MEMORY_COST,
+            lanes: DEFAULT_LANES,
+            salt: SaltSource::default(),
+            keyfile_digests: Vec::new(),
+            pepper: None,
+            cipher: Cipher::default(),
+            checksum_policy: ChecksumPolicy::default(),
+        }
+    }
+}
+
+/// Builder de `ScypherParams`; valida todos los valores en `build()`
+#[derive(Debug, Clone, Default)]
+pub struct TransformBuilder {
+    params: ScypherParams,
+}
+
+impl TransformBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn kdf(mut self, kdf: Kdf) -> Self {
+        self.params.kdf = kdf;
+        self
+    }
+
+    pub fn argon2_version(mut self, version: Argon2Version) -> Self {
+        self.params.argon2_version = version;
+        self
+    }
+
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.params.iterations = iterations;
+        self
+    }
+
+    pub fn memory_cost(mut self, memory_cost: u32) -> Self {
+        self.params.memory_cost = memory_cost;
+        self
+    }
+
+    pub fn lanes(mut self, lanes: u32) -> Self {
+        self.params.lanes = lanes;
+        self
+    }
+
+    pub fn salt(mut self, salt: SaltSource) -> Self {
+        self.params.salt = salt;
+        self
+    }
+
+    /// Añadir un keyfile; solo se conserva su hash SHA-256
+    pub fn keyfile(mut self, contents: &[u8]) -> Self {
+        self.params.keyfile_digests.push(Sha256::digest(contents).into());
+        self
+    }
+
+    /// Usar un pepper como secreto de Argon2 (no se serializa)
+    pub fn pepper(mut self, pepper: Pepper) -> Self {
+        self.params.pepper = Some(pepper);
+        self
+    }
+
+    pub fn cipher(mut self, cipher: Cipher) -> Self {
+        self.params.cipher = cipher;
+        self
+    }
+
+    pub fn checksum_policy(mut self, policy: ChecksumPolicy) -> Self {
+        self.params.checksum_policy = policy;
+        self
+    }
+
+    /// Validar y construir los parámetros
+    pub fn build(self) -> Result<ScypherParams> {
+        let params = self.params;
+
+        if !(MIN_ITERATIONS..=MAX_ITERATIONS).contains(&params.iterations) {
+            return Err(SCypherError::invalid_parameter(
+                Parameter::Iterations,
+                params.iterations,
+                format!("{}-{}", MIN_ITERATIONS, MAX_ITERATIONS),
+            ));
+        }
+
+        if !(MIN_MEMORY_COST..=MAX_MEMORY_COST).contains(&params.memory_cost) {
+            return Err(SCypherError::invalid_parameter(
+                Parameter::MemoryCost,
+                params.memory_cost,
+                format!("{}KB = 8MB to {}KB = 2GB", MIN_MEMORY_COST, MAX_MEMORY_COST),
+            ));
+        }
+
+        if params.lanes == 0 || params.lanes > MAX_LANES {
+            return Err(SCypherError::invalid_parameter(
+                Parameter::Lanes,
+                params.lanes,
+                format!("1-{}", MAX_LANES),
+            ));
+        }
+
+        if let SaltSource::Explicit(salt) = &params.salt {
+            if salt.len() < MIN_SALT_LENGTH {
+                return Err(SCypherError::invalid_parameter(
+                    Parameter::Salt,
+                    format!("{} bytes", salt.len()),
+                    format!("at least {} bytes", MIN_SALT_LENGTH),
+                ));
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_params() {
+        let params = TransformBuilder::new().build().unwrap();
+        assert_eq!(params, ScypherParams::default());
+        assert_eq!(params.iterations(), DEFAULT_ITERATIONS);
+        assert_eq!(params.memory_cost(), DEFAULT_MEMORY_COST);
+        assert_eq!(params.lanes(), DEFAULT_LANES);
+        assert_eq!(params.checksum_policy(), ChecksumPolicy::Verify);
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert!(TransformBuilder::new().iterations(0).build().is_err());
+        assert!(TransformBuilder::new().iterations(101).build().is_err());
+        assert!(TransformBuilder::new().memory_cost(4096).build().is_err());
+        assert!(TransformBuilder::new().memory_cost(3_000_000).build().is_err());
+        assert!(TransformBuilder::new().lanes(0).build().is_err());
+        assert!(TransformBuilder::new().salt(SaltSource::Explicit(vec![1, 2, 3])).build().is_err());
+
+        assert!(TransformBuilder::new().iterations(100).memory_cost(8192).lanes(4).build().is_ok());
+        assert!(TransformBuilder::new().lanes(detected_lanes()).build().is_ok());
+    }
+
+    #[test]
+    fn test_low_memory_cost() {
+        // Pi Zero con ~300MB libres: 128MB (la mitad, en potencia de dos)
+        assert_eq!(low_memory_cost(301_560, DEFAULT_MEMORY_COST).unwrap(), 131_072);
+        assert_eq!(low_memory_cost(100_000, DEFAULT_MEMORY_COST).unwrap(), 32_768);
+        assert_eq!(low_memory_cost(8_000_000, DEFAULT_MEMORY_COST).unwrap(), DEFAULT_MEMORY_COST);
+        assert_eq!(low_memory_cost(16_384, DEFAULT_MEMORY_COST).unwrap(), MIN_MEMORY_COST);
+        assert!(matches!(low_memory_cost(10_000, DEFAULT_MEMORY_COST), Err(SCypherError::InsufficientMemory)));
+    }
+
+    #[test]
+    fn test_salt_from_label() {
+        let salt = SaltSource::from_label("ledger-blue-2021").unwrap();
+        assert_eq!(salt, SaltSource::from_label("ledger-blue-2021").unwrap());
+        assert_ne!(salt, SaltSource::from_label("ledger-blue-2022").unwrap());
+        assert!(TransformBuilder::new().salt(salt).build().is_ok());
+        assert!(SaltSource::from_label("  ").is_err());
+    }
+
+    #[test]
+    fn test_keyfile_digest() {
+        let params = TransformBuilder::new()
+            .keyfile(b"first keyfile")
+            .keyfile(b"second keyfile")
+            .build()
+            .unwrap();
+
+        assert_eq!(params.keyfile_digests().len(), 2);
+        assert_ne!(params.keyfile_digests()[0], params.keyfile_digests()[1]);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let params = TransformBuilder::new()
+            .iterations(3)
+            .salt(SaltSource::Explicit(vec![0xAB; 16]))
+            .keyfile(b"secret keyfile")
+            .checksum_policy(ChecksumPolicy::Skip)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&params).unwrap();
+        assert!(json.contains("abababab"));
+        assert!(!json.contains("keyfile"));
+
+        let decoded: ScypherParams = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.iterations(), 3);
+        assert_eq!(decoded.salt(), params.salt());
+        assert!(decoded.keyfile_digests().is_empty());
+
+        // Variante y versión se guardan con sus nombres
+        let legacy = TransformBuilder::new().kdf(Kdf::Argon2i).argon2_version(Argon2Version::V0x10).build().unwrap();
+        let json = serde_json::to_string(&legacy).unwrap();
+        assert!(json.contains(r#""kdf":"argon2i""#) && json.contains(r#""argon2_version":"0x10""#));
+        assert_eq!(serde_json::from_str::<ScypherParams>(&json).unwrap(), legacy);
+
+        // Campos omitidos toman el valor por defecto; valores inválidos se rechazan
+        let partial: ScypherParams = serde_json::from_str(r#"{"iterations": 7}"#).unwrap();
+        assert_eq!(partial.memory_cost(), DEFAULT_MEMORY_COST);
+        assert!(serde_json::from_str::<ScypherParams>(r#"{"iterations": 0}"#).is_err());
+    }
+}
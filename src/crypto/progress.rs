@@ -0,0 +1,106 @@
+//! Notificación de progreso y cancelación
+//!
+//! `ProgressSink` es el punto de enganche común para barras de progreso del
+//! CLI, interfaces TUI y GUIs embebedoras. La transformación informa de cada
+//! fase y consulta `is_cancelled` entre fases; si se cancela, devuelve
+//! `SCypherError::Cancelled` y todos los buffers intermedios se limpian al
+//! salir de su ámbito.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::{SCypherError, Result};
+
+/// Fases de una transformación
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Validación de la frase de entrada
+    Validate,
+    /// Derivación de clave (Argon2id)
+    DeriveKey,
+    /// Cifrado de la entropía
+    Encrypt,
+    /// Codificación del resultado como palabras
+    Encode,
+}
+
+impl Phase {
+    /// Nombre estable de la fase (en eventos JSON)
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Validate => "validate",
+            Phase::DeriveKey => "derive_key",
+            Phase::Encrypt => "encrypt",
+            Phase::Encode => "encode",
+        }
+    }
+}
+
+/// Receptor de progreso y fuente de cancelación
+///
+/// Todos los métodos tienen implementación vacía por defecto.
+pub trait ProgressSink: Send + Sync {
+    /// Comienza una fase de la transformación
+    fn on_phase(&self, _phase: Phase) {}
+
+    /// `completed` de `total` elementos procesados (en lotes, frases)
+    fn on_progress(&self, _completed: usize, _total: usize) {}
+
+    /// Si devuelve `true`, la operación se interrumpe en el siguiente punto de control
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// Receptor que ignora todos los eventos
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {}
+
+/// Indicador de cancelación compartible entre hilos
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Solicitar la cancelación
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+impl ProgressSink for CancelFlag {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Punto de control: falla si se canceló, o notifica el inicio de la fase
+pub(crate) fn checkpoint(sink: &dyn ProgressSink, phase: Phase) -> Result<()> {
+    if sink.is_cancelled() {
+        return Err(SCypherError::Cancelled);
+    }
+
+    sink.on_phase(phase);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_flag() {
+        let flag = CancelFlag::new();
+        assert!(checkpoint(&flag, Phase::Validate).is_ok());
+
+        flag.clone().cancel();
+        assert!(matches!(checkpoint(&flag, Phase::DeriveKey), Err(SCypherError::Cancelled)));
+    }
+}
@@ -0,0 +1,67 @@
+//! Semilla BIP39 binaria (512 bits, en hexadecimal) como contenido protegido
+//!
+//! Para quien solo tiene la semilla que sale de PBKDF2 (algunas
+//! exportaciones de hardware) y no la frase. Los 64 bytes se cifran con
+//! XOR como la entropía de una frase y el resultado vuelve a ser una
+//! semilla de 64 bytes en hexadecimal, sin nada que la distinga de otra.
+//!
+//! El keystream se expande con `MasterKey` desde la etiqueta `seed/512`,
+//! así que no coincide con el de ninguna frase cifrada con la misma
+//! contraseña.
+
+use zeroize::Zeroizing;
+
+use crate::crypto::{MasterKey, ScypherParams};
+use crate::error::{SCypherError, Result};
+
+/// Bytes de una semilla BIP39
+pub const SEED_LEN: usize = 64;
+
+/// Etiqueta del keystream
+const LABEL: &str = "seed/512";
+
+/// Si `text` tiene forma de semilla de 512 bits en hexadecimal (128 dígitos)
+pub fn looks_like_raw_seed(text: &str) -> bool {
+    let text = text.trim();
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    text.len() == SEED_LEN * 2 && text.bytes().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Cifrar o descifrar (XOR es simétrico) una semilla de 512 bits en hexadecimal
+pub fn transform_raw_seed(seed_hex: &str, password: &str, params: &ScypherParams) -> Result<Zeroizing<String>> {
+    if !looks_like_raw_seed(seed_hex) {
+        return Err(SCypherError::InvalidRequest(format!(
+            "expected a {}-bit seed as {} hex digits", SEED_LEN * 8, SEED_LEN * 2)));
+    }
+    let seed_hex = seed_hex.trim();
+    let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+    hex::decode_to_slice(seed_hex.strip_prefix("0x").unwrap_or(seed_hex), &mut seed[..])
+        .map_err(|e| SCypherError::InvalidRequest(format!("invalid seed hex: {}", e)))?;
+
+    let mut keystream = Zeroizing::new([0u8; SEED_LEN]);
+    MasterKey::derive(password, params)?.expand(LABEL, &mut keystream[..])?;
+    crate::crypto::xor::xor_in_place(&mut seed[..], &keystream[..])?;
+
+    Ok(Zeroizing::new(hex::encode(&seed[..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_raw_seed() {
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+        // Semilla de "abandon ... about" sin passphrase
+        let seed = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+        let encrypted = transform_raw_seed(seed, "test_password", &params).unwrap();
+        assert!(looks_like_raw_seed(&encrypted));
+        assert_ne!(*encrypted, seed);
+        assert_eq!(*transform_raw_seed(&encrypted, "test_password", &params).unwrap(), seed);
+        assert_eq!(transform_raw_seed(&format!("0x{}\n", seed.to_uppercase()), "test_password", &params).unwrap(), encrypted);
+
+        assert!(transform_raw_seed(&seed[..64], "test_password", &params).is_err());
+        assert!(transform_raw_seed(&seed.replace('5', "g"), "test_password", &params).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Clave de sesión con keystream en caché
+//!
+//! La salida de Argon2id depende de la longitud pedida, así que el keystream
+//! de cada tamaño de frase (12, 15, 18, 21 o 24 palabras) se deriva una sola
+//! vez por sesión y se reutiliza después: las transformaciones siguientes de
+//! ese tamaño solo hacen XOR y recodificación.
+//!
+//! Los keystreams en caché viven en memoria bloqueada (sin swap). La
+//! contraseña y los keystreams se limpian de memoria con `lock` o al
+//! liberar la sesión.
+
+use std::collections::HashMap;
+
+use zeroize::Zeroizing;
+
+use crate::bip39::wordlist::{Wordlist, ENGLISH};
+use crate::crypto::keystream::{self, KdfWorkspace};
+use crate::crypto::{transform_with_keystream, NoProgress, ScypherParams};
+use crate::error::{SCypherError, Result};
+use crate::security::memory::LockedBuffer;
+
+/// Sesión desbloqueada con una contraseña y unos parámetros fijos
+pub struct SessionKey {
+    password: Option<Zeroizing<String>>,
+    params: ScypherParams,
+    keystreams: HashMap<usize, LockedBuffer>,
+}
+
+impl SessionKey {
+    /// Abrir una sesión; ningún keystream se deriva hasta que se necesita
+    pub fn unlock(password: &str, params: ScypherParams) -> Self {
+        Self {
+            password: Some(Zeroizing::new(password.to_string())),
+            params,
+            keystreams: HashMap::new(),
+        }
+    }
+
+    /// Parámetros de la sesión
+    pub fn params(&self) -> &ScypherParams {
+        &self.params
+    }
+
+    /// Si la sesión sigue desbloqueada
+    pub fn is_unlocked(&self) -> bool {
+        self.password.is_some()
+    }
+
+    /// Si el keystream para frases de `word_count` palabras ya está derivado
+    pub fn is_cached(&self, word_count: usize) -> bool {
+        self.keystreams.contains_key(&(word_count * 32 / 3).div_ceil(8))
+    }
+
+    /// Limpiar la contraseña y todos los keystreams en caché
+    pub fn lock(&mut self) {
+        self.password = None;
+        self.keystreams.clear();
+    }
+
+    /// Transformar una frase con la lista inglesa
+    pub fn transform(&mut self, seed_phrase: &str) -> Result<Zeroizing<String>> {
+        self.transform_with_wordlist(seed_phrase, &ENGLISH)
+    }
+
+    /// Transformar una frase con cualquier `Wordlist`
+    ///
+    /// Produce exactamente el mismo resultado que `transform_seed_with_params`
+    /// con la contraseña y parámetros de la sesión.
+    pub fn transform_with_wordlist(&mut self, seed_phrase: &str, wordlist: &dyn Wordlist) -> Result<Zeroizing<String>> {
+        let Self { password, params, keystreams } = self;
+        let password = password.as_ref().ok_or(SCypherError::SessionLocked)?;
+
+        transform_with_keystream(seed_phrase, params, wordlist, &NoProgress, &mut |output| {
+            if let Some(cached) = keystreams.get(&output.len()) {
+                output.copy_from_slice(cached.as_slice());
+                return Ok(());
+            }
+
+            let mut derived = LockedBuffer::new(output.len())?;
+            keystream::derive_keystream_into(password, derived.as_mut_slice(), params, &mut KdfWorkspace::new())?;
+            output.copy_from_slice(derived.as_slice());
+            keystreams.insert(output.len(), derived);
+            Ok(())
+        })
+    }
+}
+
+impl std::fmt::Debug for SessionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionKey")
+            .field("unlocked", &self.is_unlocked())
+            .field("cached_keystreams", &self.keystreams.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::TransformBuilder;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_session_matches_direct_transform() {
+        let params = TransformBuilder::new().iterations(1).memory_cost(8192).build().unwrap();
+        let mut session = SessionKey::unlock("test_password", params.clone());
+
+        assert!(!session.is_cached(12));
+        let encrypted = session.transform(TEST_PHRASE).unwrap();
+        assert!(session.is_cached(12));
+        assert!(!session.is_cached(24));
+
+        assert_eq!(encrypted, params.transform(TEST_PHRASE, "test_password").unwrap());
+        assert_eq!(*session.transform(&encrypted).unwrap(), TEST_PHRASE);
+
+        session.lock();
+        assert!(!session.is_cached(12));
+        assert!(matches!(session.transform(TEST_PHRASE), Err(SCypherError::SessionLocked)));
+    }
+}
@@ -0,0 +1,173 @@
+//! Rutas aceleradas para XOR y empaquetado de índices de palabras
+//!
+//! `xor_into` elige en tiempo de ejecución la ruta más ancha disponible
+//! (AVX2 o SSE2 en x86/x86_64, NEON en aarch64) y procesa el resto con
+//! palabras de 64 bits. El empaquetado de índices de 11 bits es el de
+//! `scypher_core` (acumulador de 64 bits): los campos cruzan límites de
+//! byte, así que no hay una forma vectorial más rápida para frases de
+//! 12-24 palabras.
+//!
+//! Todas las rutas producen exactamente los mismos bytes; las pruebas las
+//! comparan con la implementación escalar.
+
+pub use scypher_core::{pack_indices_into, unpack_indices_into};
+
+/// Ruta de XOR que se usará en esta CPU
+pub fn active_backend() -> &'static str {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            return "avx2";
+        }
+        if std::is_x86_feature_detected!("sse2") {
+            return "sse2";
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        return "neon";
+    }
+
+    #[allow(unreachable_code)]
+    "scalar"
+}
+
+/// `dst[i] ^= src[i]` para todo `i`
+///
+/// # Panics
+/// Si las longitudes no coinciden (los llamadores lo comprueban antes).
+pub fn xor_into(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "xor_into length mismatch");
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 detectado en tiempo de ejecución
+            return unsafe { x86::xor_avx2(dst, src) };
+        }
+        if std::is_x86_feature_detected!("sse2") {
+            // SAFETY: SSE2 detectado en tiempo de ejecución
+            return unsafe { x86::xor_sse2(dst, src) };
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON forma parte de la arquitectura base de aarch64
+        return unsafe { neon::xor_neon(dst, src) };
+    }
+
+    #[allow(unreachable_code)]
+    xor_scalar(dst, src)
+}
+
+/// XOR con palabras de 64 bits; también procesa los restos de las rutas SIMD
+fn xor_scalar(dst: &mut [u8], src: &[u8]) {
+    let mut dst_words = dst.chunks_exact_mut(8);
+    let mut src_words = src.chunks_exact(8);
+
+    for (d, s) in (&mut dst_words).zip(&mut src_words) {
+        let value = u64::from_ne_bytes(d.try_into().unwrap()) ^ u64::from_ne_bytes(s.try_into().unwrap());
+        d.copy_from_slice(&value.to_ne_bytes());
+    }
+
+    for (d, s) in dst_words.into_remainder().iter_mut().zip(src_words.remainder()) {
+        *d ^= s;
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn xor_avx2(dst: &mut [u8], src: &[u8]) {
+        let blocks = dst.len() / 32;
+        for i in 0..blocks {
+            let d = dst.as_mut_ptr().add(i * 32) as *mut __m256i;
+            let s = src.as_ptr().add(i * 32) as *const __m256i;
+            _mm256_storeu_si256(d, _mm256_xor_si256(_mm256_loadu_si256(d), _mm256_loadu_si256(s)));
+        }
+        super::xor_scalar(&mut dst[blocks * 32..], &src[blocks * 32..]);
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn xor_sse2(dst: &mut [u8], src: &[u8]) {
+        let blocks = dst.len() / 16;
+        for i in 0..blocks {
+            let d = dst.as_mut_ptr().add(i * 16) as *mut __m128i;
+            let s = src.as_ptr().add(i * 16) as *const __m128i;
+            _mm_storeu_si128(d, _mm_xor_si128(_mm_loadu_si128(d), _mm_loadu_si128(s)));
+        }
+        super::xor_scalar(&mut dst[blocks * 16..], &src[blocks * 16..]);
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    pub(super) unsafe fn xor_neon(dst: &mut [u8], src: &[u8]) {
+        let blocks = dst.len() / 16;
+        for i in 0..blocks {
+            let d = dst.as_mut_ptr().add(i * 16);
+            let s = src.as_ptr().add(i * 16);
+            vst1q_u8(d, veorq_u8(vld1q_u8(d), vld1q_u8(s)));
+        }
+        super::xor_scalar(&mut dst[blocks * 16..], &src[blocks * 16..]);
+    }
+}
+
+/// Empaquetar índices de 11 bits (MSB primero) en bytes, rellenando con ceros
+pub fn pack_indices(indices: &[u16]) -> Vec<u8> {
+    let mut bytes = vec![0u8; (indices.len() * 11).div_ceil(8)];
+    pack_indices_into(indices, &mut bytes);
+    bytes
+}
+
+/// Extraer `count` índices de 11 bits (MSB primero) de `bytes`
+///
+/// # Panics
+/// Si `bytes` no contiene `count * 11` bits.
+pub fn unpack_indices(bytes: &[u8], count: usize) -> Vec<u16> {
+    let mut indices = vec![0u16; count];
+    unpack_indices_into(bytes, &mut indices);
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xor_matches_scalar() {
+        // Longitudes que cubren bloques completos y restos de cada ruta
+        for len in [0, 1, 7, 16, 20, 31, 32, 33, 100] {
+            let src: Vec<u8> = (0..len).map(|i| (i * 37 + 11) as u8).collect();
+            let original: Vec<u8> = (0..len).map(|i| (i * 101 + 3) as u8).collect();
+
+            let mut fast = original.clone();
+            xor_into(&mut fast, &src);
+
+            let expected: Vec<u8> = original.iter().zip(&src).map(|(a, b)| a ^ b).collect();
+            assert_eq!(fast, expected, "len {}", len);
+        }
+        assert!(!active_backend().is_empty());
+    }
+
+    #[test]
+    fn test_pack_unpack_indices() {
+        let indices = [0u16, 2047, 1, 1024, 3, 1337, 2046, 5, 6, 7, 8, 9];
+        let bytes = pack_indices(&indices);
+
+        assert_eq!(bytes.len(), 17); // 132 bits
+        assert_eq!(unpack_indices(&bytes, indices.len()), indices);
+
+        // "zoo" = 2047: once unos seguidos de ceros de relleno
+        assert_eq!(pack_indices(&[2047]), vec![0xFF, 0xE0]);
+    }
+}
@@ -0,0 +1,368 @@
+//! Cifrado por bloques de archivos y secretos arbitrarios (`scypher file`)
+//!
+//! Para contenido que no es una frase (un archivo, un secreto en bruto de
+//! cualquier tamaño) se procesa en bloques de `CHUNK_LEN` bytes, así que la
+//! memoria no depende del tamaño y el progreso se informa por bloque. La
+//! clave sale de Argon2id con los mismos parámetros (y keyfiles o pepper)
+//! que una frase, con un salt aleatorio propio; el contenido se cifra con
+//! ChaCha20-Poly1305 en la construcción STREAM de Hoang, Reyhanitabar,
+//! Rogaway y Vizár: cada bloque lleva su número en el nonce y el último va
+//! marcado, de modo que reordenar, quitar o truncar bloques se detecta. La
+//! cabecera entera se autentica como datos asociados de cada bloque.
+//!
+//! Cabecera (`HEADER_LEN` bytes, enteros big-endian):
+//!
+//! | bytes | campo                                        |
+//! |-------|----------------------------------------------|
+//! | 8     | `SCYSTRM` y la versión del formato (1)       |
+//! | 1     | KDF: 0 Argon2id, 1 Argon2i                   |
+//! | 1     | versión de Argon2 (0x13 o 0x10)              |
+//! | 4     | iteraciones                                  |
+//! | 4     | memoria en KiB                               |
+//! | 4     | lanes                                        |
+//! | 1     | número de keyfiles (sus hashes no se guardan)|
+//! | 4     | bytes de texto claro por bloque              |
+//! | 16    | salt                                         |
+//! | 7     | prefijo del nonce                            |
+//!
+//! Después van los bloques: el texto claro más 16 bytes de etiqueta; el
+//! último puede ser más corto, o solo la etiqueta.
+
+use std::io::{self, Read, Write};
+
+use chacha20poly1305::aead::generic_array::GenericArray;
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit};
+use zeroize::Zeroizing;
+
+use crate::crypto::keystream::derive_keystream_with_params;
+use crate::crypto::{Argon2Version, Kdf, Phase, ProgressSink, SaltSource, ScypherParams, SecureRng};
+use crate::error::{Defect, SCypherError, Result};
+
+/// Firma del formato, seguida de su versión
+const MAGIC: &[u8; 7] = b"SCYSTRM";
+
+/// Versión del formato que se escribe
+pub const STREAM_VERSION: u8 = 1;
+
+/// Bytes de texto claro por bloque al cifrar
+pub const CHUNK_LEN: usize = 64 * 1024;
+
+/// Bloque más grande que se acepta al descifrar (la memoria sigue acotada)
+const MAX_CHUNK_LEN: usize = 1024 * 1024;
+
+/// Etiqueta de Poly1305 de cada bloque
+const TAG_LEN: usize = 16;
+
+const SALT_LEN: usize = 16;
+
+/// Prefijo del nonce de STREAM-BE32 (los 12 bytes menos contador y marca de último)
+const NONCE_PREFIX_LEN: usize = 7;
+
+/// Bytes de la cabecera
+pub const HEADER_LEN: usize = MAGIC.len() + 1 + 2 + 12 + 1 + 4 + SALT_LEN + NONCE_PREFIX_LEN;
+
+fn malformed(defect: Defect, message: impl Into<String>) -> SCypherError {
+    SCypherError::malformed("scypher-stream", defect, message)
+}
+
+fn io_error(e: io::Error) -> SCypherError {
+    SCypherError::from(e)
+}
+
+fn kdf_id(kdf: Kdf) -> u8 {
+    match kdf {
+        Kdf::Argon2id => 0,
+        Kdf::Argon2i => 1,
+    }
+}
+
+/// Cabecera de un archivo cifrado por bloques
+struct Header {
+    params: ScypherParams,
+    keyfiles: usize,
+    chunk_len: usize,
+    nonce_prefix: [u8; NONCE_PREFIX_LEN],
+}
+
+impl Header {
+    fn to_bytes(&self) -> Result<[u8; HEADER_LEN]> {
+        let SaltSource::Explicit(salt) = self.params.salt() else {
+            return Err(SCypherError::crypto("stream header needs an explicit salt".to_string()));
+        };
+        let keyfiles = u8::try_from(self.keyfiles)
+            .map_err(|_| SCypherError::InvalidRequest("at most 255 keyfiles can be recorded".to_string()))?;
+
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.push(STREAM_VERSION);
+        header.push(kdf_id(self.params.kdf()));
+        header.push(match self.params.argon2_version() {
+            Argon2Version::V0x10 => 0x10,
+            Argon2Version::V0x13 => 0x13,
+        });
+        for value in [self.params.iterations(), self.params.memory_cost(), self.params.lanes()] {
+            header.extend_from_slice(&value.to_be_bytes());
+        }
+        header.push(keyfiles);
+        header.extend_from_slice(&(self.chunk_len as u32).to_be_bytes());
+        header.extend_from_slice(salt);
+        header.extend_from_slice(&self.nonce_prefix);
+        header.try_into().map_err(|_| SCypherError::crypto("stream header has the wrong length".to_string()))
+    }
+
+    /// Leer una cabecera; keyfiles y pepper se toman de `secrets`
+    fn parse(bytes: &[u8; HEADER_LEN], secrets: &ScypherParams) -> Result<Self> {
+        let (magic, rest) = bytes.split_at(MAGIC.len());
+        if magic != MAGIC {
+            return Err(malformed(Defect::Encoding, "not a SCypher stream (bad magic)"));
+        }
+        if rest[0] != STREAM_VERSION {
+            return Err(malformed(Defect::Unsupported, format!("format version {}", rest[0])));
+        }
+        let kdf = Kdf::ALL
+            .into_iter()
+            .find(|&known| kdf_id(known) == rest[1])
+            .ok_or_else(|| malformed(Defect::Unsupported, format!("unknown KDF id {}", rest[1])))?;
+        let argon2_version = match rest[2] {
+            0x10 => Argon2Version::V0x10,
+            0x13 => Argon2Version::V0x13,
+            version => return Err(malformed(Defect::Unsupported, format!("Argon2 version {:#x}", version))),
+        };
+        let word = |offset: usize| u32::from_be_bytes(rest[offset..offset + 4].try_into().expect("4-byte field"));
+        let keyfiles = usize::from(rest[15]);
+        let chunk_len = word(16) as usize;
+        if !(1..=MAX_CHUNK_LEN).contains(&chunk_len) {
+            return Err(malformed(Defect::Inconsistent, format!("chunk size {}", chunk_len)));
+        }
+        let salt = rest[20..20 + SALT_LEN].to_vec();
+        let nonce_prefix = rest[20 + SALT_LEN..].try_into().expect("nonce prefix field");
+
+        // Los parámetros pasan por el builder: una cabecera manipulada no pide memoria fuera de rango
+        let params = secrets.to_builder()
+            .kdf(kdf)
+            .argon2_version(argon2_version)
+            .iterations(word(3))
+            .memory_cost(word(7))
+            .lanes(word(11))
+            .salt(SaltSource::Explicit(salt))
+            .build()?;
+        Ok(Self { params, keyfiles, chunk_len, nonce_prefix })
+    }
+
+    fn cipher(&self, password: &str, progress: &dyn ProgressSink) -> Result<ChaCha20Poly1305> {
+        progress.on_phase(Phase::DeriveKey);
+        let key = Zeroizing::new(derive_keystream_with_params(password, 32, &self.params)?);
+        ChaCha20Poly1305::new_from_slice(&key).map_err(|_| SCypherError::crypto("invalid stream key length".to_string()))
+    }
+}
+
+/// Leer hasta `len` bytes en `buffer` (sin reservar más memoria); menos solo al final de la entrada
+fn read_chunk(input: &mut dyn Read, buffer: &mut Zeroizing<Vec<u8>>, len: usize) -> Result<()> {
+    buffer.clear();
+    buffer.resize(len, 0);
+    let mut filled = 0;
+    while filled < len {
+        match input.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(io_error(e)),
+        }
+    }
+    buffer.truncate(filled);
+    Ok(())
+}
+
+/// Cifrar `input` en `output` por bloques; devuelve los bytes cifrados
+///
+/// `input_len` (0 si no se conoce) solo sirve para el progreso, que se
+/// informa en bytes leídos tras cada bloque.
+pub fn encrypt_stream(
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+    password: &str,
+    params: &ScypherParams,
+    input_len: u64,
+    progress: &dyn ProgressSink,
+) -> Result<u64> {
+    encrypt_stream_with_rng(input, output, password, params, input_len, progress, &mut rand::rngs::OsRng)
+}
+
+/// `encrypt_stream` con el salt y el prefijo del nonce tomados de `rng`
+pub fn encrypt_stream_with_rng(
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+    password: &str,
+    params: &ScypherParams,
+    input_len: u64,
+    progress: &dyn ProgressSink,
+    rng: &mut dyn SecureRng,
+) -> Result<u64> {
+    let mut salt = vec![0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+    rng.fill_bytes(&mut nonce_prefix);
+    let header = Header {
+        params: params.to_builder().salt(SaltSource::Explicit(salt)).build()?,
+        keyfiles: params.keyfile_digests().len(),
+        chunk_len: CHUNK_LEN,
+        nonce_prefix,
+    };
+    let header_bytes = header.to_bytes()?;
+
+    let mut encryptor = EncryptorBE32::from_aead(header.cipher(password, progress)?, GenericArray::from_slice(&nonce_prefix));
+    output.write_all(&header_bytes).map_err(io_error)?;
+
+    progress.on_phase(Phase::Encrypt);
+    // Cada bloque se cifra en su sitio: la etiqueta cabe sin reubicar el buffer
+    let mut current = Zeroizing::new(Vec::with_capacity(CHUNK_LEN + TAG_LEN));
+    let mut next = Zeroizing::new(Vec::with_capacity(CHUNK_LEN + TAG_LEN));
+    let mut processed = 0u64;
+    read_chunk(input, &mut current, CHUNK_LEN)?;
+    loop {
+        if progress.is_cancelled() {
+            return Err(SCypherError::Cancelled);
+        }
+        processed += current.len() as u64;
+
+        // Solo un bloque lleno puede tener otro detrás
+        if current.len() == CHUNK_LEN {
+            read_chunk(input, &mut next, CHUNK_LEN)?;
+        } else {
+            next.clear();
+        }
+        let failed = |_| SCypherError::crypto("stream encryption failed".to_string());
+        if next.is_empty() {
+            encryptor.encrypt_last_in_place(&header_bytes, &mut *current).map_err(failed)?;
+            output.write_all(&current).map_err(io_error)?;
+            progress.on_progress(processed as usize, input_len as usize);
+            return Ok(processed);
+        }
+        encryptor.encrypt_next_in_place(&header_bytes, &mut *current).map_err(failed)?;
+        output.write_all(&current).map_err(io_error)?;
+        progress.on_progress(processed as usize, input_len as usize);
+        std::mem::swap(&mut current, &mut next);
+    }
+}
+
+/// Descifrar un archivo de `encrypt_stream`; devuelve los bytes descifrados
+///
+/// Iteraciones, memoria, lanes y salt vienen de la cabecera; keyfiles y
+/// pepper, de `secrets`. Cada bloque se autentica antes de escribirlo, pero
+/// un archivo manipulado a mitad deja escrito lo anterior: quien llama debe
+/// descartar la salida si hay error. Una contraseña equivocada falla en el
+/// primer bloque con `InvalidPassword`.
+pub fn decrypt_stream(
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+    password: &str,
+    secrets: &ScypherParams,
+    input_len: u64,
+    progress: &dyn ProgressSink,
+) -> Result<u64> {
+    let mut header_bytes = [0u8; HEADER_LEN];
+    input.read_exact(&mut header_bytes).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => malformed(Defect::Truncated, "header"),
+        _ => io_error(e),
+    })?;
+    let header = Header::parse(&header_bytes, secrets)?;
+    if header.keyfiles != secrets.keyfile_digests().len() {
+        return Err(SCypherError::InvalidRequest(format!(
+            "the file was encrypted with {} keyfile(s) and {} were given (-k)", header.keyfiles, secrets.keyfile_digests().len())));
+    }
+
+    let mut decryptor = DecryptorBE32::from_aead(header.cipher(password, progress)?, GenericArray::from_slice(&header.nonce_prefix));
+
+    progress.on_phase(Phase::Encrypt);
+    let sealed_len = header.chunk_len + TAG_LEN;
+    let mut current = Zeroizing::new(Vec::with_capacity(sealed_len));
+    let mut next = Zeroizing::new(Vec::with_capacity(sealed_len));
+    let (mut consumed, mut written, mut first) = (HEADER_LEN as u64, 0u64, true);
+    read_chunk(input, &mut current, sealed_len)?;
+    loop {
+        if progress.is_cancelled() {
+            return Err(SCypherError::Cancelled);
+        }
+        if current.len() < TAG_LEN {
+            return Err(malformed(Defect::Truncated, "last chunk"));
+        }
+        consumed += current.len() as u64;
+
+        if current.len() == sealed_len {
+            read_chunk(input, &mut next, sealed_len)?;
+        } else {
+            next.clear();
+        }
+        // En el primer bloque un fallo casi siempre es la contraseña; después, el archivo
+        let rejected = || if first {
+            SCypherError::InvalidPassword
+        } else {
+            malformed(Defect::Inconsistent, format!("chunk ending at byte {} does not authenticate", consumed))
+        };
+        if next.is_empty() {
+            decryptor.decrypt_last_in_place(&header_bytes, &mut *current).map_err(|_| rejected())?;
+            output.write_all(&current).map_err(io_error)?;
+            progress.on_progress(consumed as usize, input_len as usize);
+            return Ok(written + current.len() as u64);
+        }
+        decryptor.decrypt_next_in_place(&header_bytes, &mut *current).map_err(|_| rejected())?;
+        output.write_all(&current).map_err(io_error)?;
+        written += current.len() as u64;
+        progress.on_progress(consumed as usize, input_len as usize);
+        first = false;
+        std::mem::swap(&mut current, &mut next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{seeded_rng, NoProgress};
+
+    fn params() -> ScypherParams {
+        ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap()
+    }
+
+    fn encrypt(data: &[u8], password: &str) -> Vec<u8> {
+        let mut sealed = Vec::new();
+        encrypt_stream_with_rng(&mut &data[..], &mut sealed, password, &params(), 0, &NoProgress, &mut seeded_rng([3; 32])).unwrap();
+        sealed
+    }
+
+    fn decrypt(sealed: &[u8], password: &str) -> Result<Vec<u8>> {
+        let mut opened = Vec::new();
+        decrypt_stream(&mut &sealed[..], &mut opened, password, &params(), 0, &NoProgress).map(|_| opened)
+    }
+
+    #[test]
+    fn test_stream_roundtrip() {
+        // Vacío, menos de un bloque, justo un bloque y varios con resto
+        for len in [0, 100, CHUNK_LEN, 2 * CHUNK_LEN + 7] {
+            let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let sealed = encrypt(&data, "password123");
+            let chunks = len / CHUNK_LEN + usize::from(len % CHUNK_LEN != 0 || len == 0);
+            assert_eq!(sealed.len(), HEADER_LEN + len + chunks * TAG_LEN);
+            assert_eq!(decrypt(&sealed, "password123").unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_stream_tampering() {
+        let data = vec![0x42; 2 * CHUNK_LEN + 7];
+        let sealed = encrypt(&data, "password123");
+        assert!(matches!(decrypt(&sealed, "password124"), Err(SCypherError::InvalidPassword)));
+
+        // Un byte cambiado en el segundo bloque, en la cabecera o un bloque entero quitado
+        let mut flipped = sealed.clone();
+        flipped[HEADER_LEN + CHUNK_LEN + TAG_LEN + 3] ^= 1;
+        assert!(matches!(decrypt(&flipped, "password123"), Err(SCypherError::MalformedContainer { .. })));
+        let mut header = sealed.clone();
+        header[HEADER_LEN - 1] ^= 1;
+        assert!(decrypt(&header, "password123").is_err());
+        let truncated = &sealed[..HEADER_LEN + 2 * (CHUNK_LEN + TAG_LEN)];
+        assert!(decrypt(truncated, "password123").is_err());
+        assert!(decrypt(&sealed[..10], "password123").is_err());
+        assert!(decrypt(b"not a stream at all, long enough for a header......", "password123").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+//! Vectores de respuesta conocida oficiales
+//!
+//! `known_answers.json` fija, para cada combinación representativa de
+//! parámetros (KDF, versión, salt explícito, lanes, idioma, longitud), qué
+//! frase sale de una frase y contraseña dadas. El archivo va dentro del
+//! binario y no se modifica nunca: un vector que deja de cumplirse significa
+//! que esta compilación no descifra lo que cifraron las anteriores. Otras
+//! implementaciones pueden validarse contra el mismo archivo.
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::bip39::languages::Language;
+use crate::error::{SCypherError, Result};
+
+use super::ScypherParams;
+
+/// Contenido del archivo de vectores, tal como se publica
+pub const KNOWN_ANSWERS_JSON: &str = include_str!("known_answers.json");
+
+/// Un vector: entrada, parámetros y resultado esperado
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownAnswer {
+    pub name: String,
+    /// Código de idioma de la frase (`Language::code`)
+    pub language: String,
+    pub phrase: String,
+    pub password: String,
+    pub params: ScypherParams,
+    pub expected: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KnownAnswerFile {
+    version: u32,
+    vectors: Vec<KnownAnswer>,
+}
+
+/// Versión del formato del archivo que entiende esta compilación
+const FILE_VERSION: u32 = 1;
+
+/// Vectores incluidos en el binario
+pub fn known_answers() -> Result<Vec<KnownAnswer>> {
+    let file: KnownAnswerFile = serde_json::from_str(KNOWN_ANSWERS_JSON)
+        .map_err(|e| SCypherError::crypto(format!("invalid known-answer file: {}", e)))?;
+    if file.version != FILE_VERSION {
+        return Err(SCypherError::crypto(format!("unsupported known-answer file version {}", file.version)));
+    }
+    Ok(file.vectors)
+}
+
+/// SHA-256 del archivo de vectores, para compararlo con el publicado
+pub fn known_answers_digest() -> String {
+    hex::encode(Sha256::digest(KNOWN_ANSWERS_JSON.as_bytes()))
+}
+
+impl KnownAnswer {
+    /// Comprobar el vector en ambos sentidos: cifrar y descifrar
+    pub fn verify(&self) -> Result<()> {
+        let language = Language::from_code(&self.language)
+            .ok_or_else(|| SCypherError::crypto(format!("unknown language '{}'", self.language)))?;
+        let wordlist = language.wordlist();
+
+        let output = super::transform_seed_with_wordlist(&self.phrase, &self.password, &self.params, wordlist)?;
+        if !same_words(&output, &self.expected) {
+            return Err(SCypherError::crypto(format!("expected '{}', got '{}'", self.expected, *output)));
+        }
+        let restored = super::transform_seed_with_wordlist(&output, &self.password, &self.params, wordlist)?;
+        if !same_words(&restored, &self.phrase) {
+            return Err(SCypherError::crypto(format!("reverse transform gave '{}'", *restored)));
+        }
+        Ok(())
+    }
+}
+
+/// Qué se genera en un vector de generación
+#[derive(Debug, Clone, Copy)]
+enum Generation {
+    /// `generate_seed_phrase` de `words` palabras
+    Mnemonic { words: usize },
+    /// `generate_seed_phrase_mixed` de `words` palabras con `extra`
+    Mixed { words: usize, extra: &'static [u8] },
+    /// `pad_transform` de `phrase` con iteraciones 1 y 8 MiB
+    Padded { phrase: &'static str, password: &'static str },
+}
+
+/// Vector de generación: lo que producen las rutas aleatorias con `seeded_rng(seed)`
+///
+/// No están en `known_answers.json` (ese archivo no se modifica) porque
+/// dependen también de ChaCha20 como RNG inyectado, no solo de SCypher.
+#[derive(Debug, Clone, Copy)]
+pub struct GenerationAnswer {
+    pub name: &'static str,
+    seed: [u8; 32],
+    generation: Generation,
+    expected: &'static str,
+}
+
+/// Vectores de generación incluidos en el binario
+pub fn generation_answers() -> &'static [GenerationAnswer] {
+    const ANSWERS: &[GenerationAnswer] = &[
+        GenerationAnswer {
+            name: "generate-12",
+            seed: [0; 32],
+            generation: Generation::Mnemonic { words: 12 },
+            expected: "issue shove clock draft because sight accident pull torch order quantum fade",
+        },
+        GenerationAnswer {
+            name: "generate-24-mixed",
+            seed: [1; 32],
+            generation: Generation::Mixed { words: 24, extra: b"keyboard timings" },
+            expected: "east popular vacuum prize matter whale fluid bone voyage hurry pilot amused \
+                       edge elevator mobile jelly arrest beyond box giggle stamp battle inflict recycle",
+        },
+        GenerationAnswer {
+            name: "pad-12-to-24",
+            seed: [2; 32],
+            generation: Generation::Padded {
+                phrase: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                password: "password123",
+            },
+            expected: "piano pattern answer border endless start festival easily expose powder dad upon \
+                       glow feature lava bar announce match exclude law give solution exotic kick",
+        },
+    ];
+    ANSWERS
+}
+
+impl GenerationAnswer {
+    /// Generar con el RNG de la semilla y comparar; el relleno se comprueba también al revés
+    pub fn verify(&self) -> Result<()> {
+        use crate::bip39::conversion::{generate_seed_phrase_mixed_with_rng, generate_seed_phrase_with_rng};
+
+        let rng = &mut super::seeded_rng(self.seed);
+        let output = match self.generation {
+            Generation::Mnemonic { words } => Zeroizing::new(generate_seed_phrase_with_rng(words * 32 / 3, rng)?),
+            Generation::Mixed { words, extra } => Zeroizing::new(generate_seed_phrase_mixed_with_rng(words * 32 / 3, extra, rng)?),
+            Generation::Padded { phrase, password } => {
+                let params = super::TransformBuilder::new().iterations(1).memory_cost(8192).build()?;
+                let wordlist = Language::English.wordlist();
+                let padded = super::padding::pad_transform_with_rng(phrase, password, &params, wordlist, rng)?;
+                let restored = super::padding::unpad_transform(&padded, password, &params, wordlist)?;
+                if *restored != phrase {
+                    return Err(SCypherError::crypto(format!("unpadding gave '{}'", *restored)));
+                }
+                padded
+            }
+        };
+
+        if *output != self.expected {
+            return Err(SCypherError::crypto(format!("expected '{}', got '{}'", self.expected, *output)));
+        }
+        Ok(())
+    }
+}
+
+/// Igualdad en NFKD: las listas guardan las palabras descompuestas y el
+/// archivo puede estar en cualquier forma normal
+fn same_words(a: &str, b: &str) -> bool {
+    a.nfkd().eq(b.nfkd())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_answers() {
+        let vectors = known_answers().unwrap();
+        assert!(!vectors.is_empty());
+        for vector in &vectors {
+            vector.verify().unwrap_or_else(|e| panic!("{}: {}", vector.name, e));
+        }
+        for vector in generation_answers() {
+            vector.verify().unwrap_or_else(|e| panic!("{}: {}", vector.name, e));
+        }
+    }
+}
@@ -24,15 +24,27 @@ pub fn xor_data(data: &[u8], keystream: &[u8]) -> Result<Vec<u8>> {
         ));
     }
     
-    let result: Vec<u8> = data
-        .iter()
-        .zip(keystream.iter())
-        .map(|(a, b)| a ^ b)
-        .collect();
-    
+    let mut result = data.to_vec();
+    crate::crypto::simd::xor_into(&mut result, keystream);
+
     Ok(result)
 }
 
+/// Aplica XOR sobre `data` sin reservar memoria nueva
+///
+/// Usa la ruta SIMD disponible (ver `crypto::simd`).
+pub fn xor_in_place(data: &mut [u8], keystream: &[u8]) -> Result<()> {
+    if data.len() != keystream.len() {
+        return Err(SCypherError::crypto(
+            format!("Data length ({}) doesn't match keystream length ({})",
+                   data.len(), keystream.len())
+        ));
+    }
+
+    crate::crypto::simd::xor_into(data, keystream);
+    Ok(())
+}
+
 /// Aplica XOR bit a bit entre dos vectores de bits (representados como Vec<bool>)
 /// Útil para operaciones a nivel de bits individuales
 pub fn xor_bits(bits_a: &[bool], bits_b: &[bool]) -> Result<Vec<bool>> {
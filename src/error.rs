@@ -1,33 +1,255 @@
 // src/error.rs - Manejo centralizado de errores para SCypher
+//
+// Cada variante tiene un código numérico estable (`code()`) y una categoría
+// (`category()`) que coincide con el código de salida del CLI. Los códigos
+// no cambian entre versiones: se añaden nuevos, nunca se reutilizan.
 
 use std::fmt;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Parámetro de configuración al que se refiere un `InvalidParameter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Parameter {
+    Iterations,
+    MemoryCost,
+    Lanes,
+    Salt,
+    DerivationPath,
+}
+
+impl Parameter {
+    /// Nombre estable del parámetro (coincide con la opción del CLI)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Parameter::Iterations => "iterations",
+            Parameter::MemoryCost => "memory",
+            Parameter::Lanes => "lanes",
+            Parameter::Salt => "salt",
+            Parameter::DerivationPath => "derivation-path",
+        }
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Operación de E/S que falló en un `IoError`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IoOperation {
+    Read,
+    Write,
+    CreateDir,
+    Metadata,
+    SetPermissions,
+    Remove,
+    Bind,
+    Execute,
+}
+
+impl IoOperation {
+    /// Nombre estable de la operación (en la salida JSON)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IoOperation::Read => "read",
+            IoOperation::Write => "write",
+            IoOperation::CreateDir => "create_dir",
+            IoOperation::Metadata => "metadata",
+            IoOperation::SetPermissions => "set_permissions",
+            IoOperation::Remove => "remove",
+            IoOperation::Bind => "bind",
+            IoOperation::Execute => "execute",
+        }
+    }
+
+    /// Verbo para los mensajes: "Cannot {verb} '{path}'"
+    fn verb(&self) -> &'static str {
+        match self {
+            IoOperation::Read => "read",
+            IoOperation::Write => "write",
+            IoOperation::CreateDir => "create directory",
+            IoOperation::Metadata => "read metadata of",
+            IoOperation::SetPermissions => "set permissions on",
+            IoOperation::Remove => "remove",
+            IoOperation::Bind => "bind",
+            IoOperation::Execute => "run",
+        }
+    }
+}
+
+impl fmt::Display for IoOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Defecto encontrado al leer un contenedor (`MalformedContainer`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Defect {
+    /// Faltan bytes: la entrada acaba antes de lo que anuncia
+    Truncated,
+    /// La entrada o un campo supera el límite admitido
+    TooLarge,
+    /// Demasiados niveles de anidamiento
+    TooDeep,
+    /// Codificación inválida (base64, bytewords, cabeceras...)
+    Encoding,
+    /// Checksum o CRC que no coincide
+    Checksum,
+    /// Campos que se contradicen entre sí
+    Inconsistent,
+    /// Estructura válida pero no admitida (algoritmo, versión, tipo)
+    Unsupported,
+}
+
+impl Defect {
+    /// Nombre estable del defecto (en la salida JSON)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Defect::Truncated => "truncated",
+            Defect::TooLarge => "too_large",
+            Defect::TooDeep => "too_deep",
+            Defect::Encoding => "encoding",
+            Defect::Checksum => "checksum",
+            Defect::Inconsistent => "inconsistent",
+            Defect::Unsupported => "unsupported",
+        }
+    }
+}
+
+impl fmt::Display for Defect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Categoría de error; cada una corresponde a un código de salida del CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// Error general o del sistema (salida 1)
+    General,
+    /// Entrada o parámetros inválidos (salida 2)
+    Validation,
+    /// Contraseña rechazada (salida 3)
+    Password,
+    /// Errores de archivos y E/S (salida 4)
+    Io,
+    /// Errores criptográficos (salida 5)
+    Crypto,
+}
+
+impl ErrorCategory {
+    /// Todas las categorías, por código de salida
+    pub const ALL: [ErrorCategory; 5] = [
+        ErrorCategory::General,
+        ErrorCategory::Validation,
+        ErrorCategory::Password,
+        ErrorCategory::Io,
+        ErrorCategory::Crypto,
+    ];
+
+    /// Descripción breve (en inglés, como los mensajes del CLI)
+    pub fn description(&self) -> &'static str {
+        match self {
+            ErrorCategory::General => "general or system error",
+            ErrorCategory::Validation => "invalid input or parameters",
+            ErrorCategory::Password => "password rejected",
+            ErrorCategory::Io => "file or I/O error",
+            ErrorCategory::Crypto => "cryptographic error",
+        }
+    }
+
+    /// Código de salida del proceso para esta categoría
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCategory::General => 1,
+            ErrorCategory::Validation => 2,
+            ErrorCategory::Password => 3,
+            ErrorCategory::Io => 4,
+            ErrorCategory::Crypto => 5,
+        }
+    }
+
+    /// Nombre estable de la categoría
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::General => "general",
+            ErrorCategory::Validation => "validation",
+            ErrorCategory::Password => "password",
+            ErrorCategory::Io => "io",
+            ErrorCategory::Crypto => "crypto",
+        }
+    }
+}
 
 /// Enum principal que representa todos los posibles errores en SCypher
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum SCypherError {
     // Errores de entrada y validación
     InvalidSeedPhrase,
     InvalidWordCount(usize),           // Guarda el número de palabras encontradas
-    InvalidBip39Word(String),          // Guarda la palabra inválida
+    InvalidBip39Word {
+        word: String,                  // Palabra rechazada
+        position: Option<usize>,       // Posición (desde 1) en la frase, si se conoce
+        suggestion: Option<String>,    // Palabra de la lista más parecida
+    },
     InvalidChecksum,
-    
+    WrongWordlist {
+        wordlist: String,              // Lista indicada (código de idioma)
+        detected: String,              // Lista con la mayoría de las palabras
+    },
+
+    // Errores de parámetros
+    InvalidParameter {
+        parameter: Parameter,
+        value: String,                 // Valor rechazado
+        allowed: String,               // Rango o requisito aceptado
+    },
+    InvalidRequest(String),            // Petición JSON mal formada o no soportada
+    UnknownFormat(String),             // Formato de salida no registrado
+    MalformedContainer {
+        format: &'static str,          // "openpgp", "age", "ur", "cbor"...
+        defect: Defect,
+        message: String,               // Qué campo o comprobación falló
+    },
+
     // Errores de entrada del usuario
     InvalidPassword,
     PasswordMismatch,
-    InvalidIterations(String),         // Guarda el valor inválido
-    InvalidMemoryCost(String),         // Guarda el valor inválido
-    
+    SessionLocked,                     // La sesión se bloqueó (lock o inactividad)
+    KeyCheckMismatch,                  // La contraseña no da el código de --key-check
+
     // Errores criptográficos
-    CryptoError(String),               // Errores de Argon2 u otras operaciones crypto
-    KeyDerivationFailed,
-    
+    CryptoError(String),               // Otras operaciones crypto
+    KeyDerivationFailed(argon2::Error),
+
     // Errores de E/O
-    IoError(std::io::Error),
-    FileError(String),
-    
+    IoError {
+        operation: Option<IoOperation>, // Qué se intentaba hacer, si se conoce
+        path: Option<String>,          // Archivo o socket afectado, si se conoce
+        source: std::io::Error,
+    },
+    FileError {
+        path: Option<String>,          // Archivo afectado, si se conoce
+        message: String,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>, // Causa, si la hay
+    },
+
     // Errores del sistema
     InsufficientMemory,
     UnsupportedPlatform,
+    Cancelled,                         // Operación cancelada vía ProgressSink
+    NetworkActive(Vec<String>),        // Interfaces levantadas con --require-airgap
+    ElevatedPrivileges,                // Root/Administrador con --no-root
+    NotRemovable { path: String, location: String }, // Destino no extraíble con --require-removable
 }
 
 impl fmt::Display for SCypherError {
@@ -40,13 +262,38 @@ impl fmt::Display for SCypherError {
             SCypherError::InvalidWordCount(count) => {
                 write!(f, "Invalid word count: found {} words (expected: 12, 15, 18, 21, or 24)", count)
             }
-            SCypherError::InvalidBip39Word(word) => {
-                write!(f, "Word '{}' is not in the BIP39 wordlist", word)
+            SCypherError::InvalidBip39Word { word, position, suggestion } => {
+                match position {
+                    Some(position) => write!(f, "Word #{} '{}' is not in the BIP39 wordlist", position, word)?,
+                    None => write!(f, "Word '{}' is not in the BIP39 wordlist", word)?,
+                }
+                match suggestion {
+                    Some(suggestion) => write!(f, " (did you mean '{}'?)", suggestion),
+                    None => Ok(()),
+                }
             }
             SCypherError::InvalidChecksum => {
                 write!(f, "Invalid BIP39 checksum - seed phrase may be corrupted")
             }
-            
+            SCypherError::WrongWordlist { wordlist, detected } => {
+                write!(f, "Seed phrase is not in the '{}' wordlist; most of its words are in '{}' (--language {})", wordlist, detected, detected)
+            }
+
+            // Errores de parámetros
+            SCypherError::InvalidParameter { parameter, value, allowed } => {
+                write!(f, "Invalid {} '{}' (allowed: {})", parameter, value, allowed)
+            }
+
+            SCypherError::InvalidRequest(msg) => {
+                write!(f, "Invalid request: {}", msg)
+            }
+            SCypherError::UnknownFormat(name) => {
+                write!(f, "Unknown output format '{}' (see --list-formats)", name)
+            }
+            SCypherError::MalformedContainer { format, message, .. } => {
+                write!(f, "Malformed {} data: {}", format, message)
+            }
+
             // Errores de entrada del usuario
             SCypherError::InvalidPassword => {
                 write!(f, "Password does not meet security requirements")
@@ -54,29 +301,42 @@ impl fmt::Display for SCypherError {
             SCypherError::PasswordMismatch => {
                 write!(f, "Passwords do not match")
             }
-            SCypherError::InvalidIterations(val) => {
-                write!(f, "Invalid iteration count '{}' (must be a positive number)", val)
+            SCypherError::SessionLocked => {
+                write!(f, "Session is locked; unlock it with the password first")
             }
-            SCypherError::InvalidMemoryCost(val) => {
-                write!(f, "Invalid memory cost '{}' (must be a positive number in KB)", val)
+            SCypherError::KeyCheckMismatch => {
+                write!(f, "Key check does not match: wrong password, or different parameters, keyfiles or pepper than when encrypting")
             }
-            
+
             // Errores criptográficos
             SCypherError::CryptoError(msg) => {
                 write!(f, "Cryptographic error: {}", msg)
             }
-            SCypherError::KeyDerivationFailed => {
-                write!(f, "Failed to derive encryption key")
+            SCypherError::KeyDerivationFailed(err) => {
+                write!(f, "Failed to derive encryption key: {}", err)
             }
-            
+
             // Errores de E/O
-            SCypherError::IoError(err) => {
-                write!(f, "IO error: {}", err)
+            SCypherError::IoError { operation, path, source } => {
+                match (operation, path) {
+                    (Some(operation), Some(path)) => write!(f, "Cannot {} '{}': {}", operation.verb(), path, source),
+                    (Some(operation), None) => write!(f, "Cannot {}: {}", operation.verb(), source),
+                    (None, Some(path)) => write!(f, "IO error on '{}': {}", path, source),
+                    (None, None) => write!(f, "IO error: {}", source),
+                }
             }
-            SCypherError::FileError(msg) => {
-                write!(f, "File error: {}", msg)
+            SCypherError::FileError { path, message, source } => {
+                write!(f, "File error: ")?;
+                if let Some(path) = path {
+                    write!(f, "'{}': ", path)?;
+                }
+                write!(f, "{}", message)?;
+                match source {
+                    Some(source) => write!(f, ": {}", source),
+                    None => Ok(()),
+                }
             }
-            
+
             // Errores del sistema
             SCypherError::InsufficientMemory => {
                 write!(f, "Insufficient system memory for secure operation")
@@ -84,23 +344,72 @@ impl fmt::Display for SCypherError {
             SCypherError::UnsupportedPlatform => {
                 write!(f, "This platform is not supported")
             }
+            SCypherError::Cancelled => {
+                write!(f, "Operation cancelled")
+            }
+            SCypherError::NetworkActive(interfaces) => {
+                write!(f, "Refusing to run: network interfaces are up ({}); disconnect them or drop --require-airgap", interfaces.join(", "))
+            }
+            SCypherError::ElevatedPrivileges => {
+                write!(f, "Refusing to run with root/Administrator privileges (--no-root); run it as a regular user, or through sudo so it can drop back")
+            }
+            SCypherError::NotRemovable { path, location } => {
+                write!(f, "Refusing to write '{}' to {}; --require-removable only allows removable media", path, location)
+            }
         }
     }
 }
 
-impl std::error::Error for SCypherError {}
+impl std::error::Error for SCypherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SCypherError::IoError { source, .. } => Some(source),
+            SCypherError::FileError { source: Some(source), .. } => Some(source.as_ref()),
+            SCypherError::KeyDerivationFailed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+// Forma serializada: `{"code", "category", "message"}` para salidas JSON,
+// más `details` con los datos estructurados de las variantes que los tienen
+impl Serialize for SCypherError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let details = self.details();
+        let mut state = serializer.serialize_struct("SCypherError", 3 + usize::from(details.is_some()))?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("category", &self.category())?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some(details) = details {
+            state.serialize_field("details", &details)?;
+        }
+        state.end()
+    }
+}
 
 // Conversión automática desde std::io::Error
 impl From<std::io::Error> for SCypherError {
     fn from(error: std::io::Error) -> Self {
-        SCypherError::IoError(error)
+        SCypherError::IoError { operation: None, path: None, source: error }
     }
 }
 
 // Conversión desde errores de Argon2
 impl From<argon2::Error> for SCypherError {
     fn from(error: argon2::Error) -> Self {
-        SCypherError::CryptoError(format!("Argon2 error: {:?}", error))
+        SCypherError::KeyDerivationFailed(error)
+    }
+}
+
+// Conversión desde el núcleo no_std
+impl From<scypher_core::Error> for SCypherError {
+    fn from(error: scypher_core::Error) -> Self {
+        match error {
+            scypher_core::Error::InvalidWordCount(count) => SCypherError::InvalidWordCount(count),
+            scypher_core::Error::InvalidChecksum => SCypherError::InvalidChecksum,
+            scypher_core::Error::Cancelled => SCypherError::Cancelled,
+            other => SCypherError::CryptoError(other.to_string()),
+        }
     }
 }
 
@@ -108,34 +417,194 @@ impl From<argon2::Error> for SCypherError {
 /// Esto nos permite escribir `Result<T>` en lugar de `Result<T, SCypherError>`
 pub type Result<T> = std::result::Result<T, SCypherError>;
 
-/// Funciones helper para crear errores comunes de manera más fácil
 impl SCypherError {
+    /// Código numérico estable del error
+    ///
+    /// El primer dígito coincide con el código de salida de la categoría.
+    pub fn code(&self) -> u16 {
+        match self {
+            SCypherError::InsufficientMemory => 1001,
+            SCypherError::UnsupportedPlatform => 1002,
+            SCypherError::Cancelled => 1003,
+            SCypherError::NetworkActive(_) => 1004,
+            SCypherError::ElevatedPrivileges => 1005,
+            SCypherError::NotRemovable { .. } => 1006,
+
+            SCypherError::InvalidSeedPhrase => 2001,
+            SCypherError::InvalidWordCount(_) => 2002,
+            SCypherError::InvalidBip39Word { .. } => 2003,
+            SCypherError::InvalidChecksum => 2004,
+            SCypherError::InvalidParameter { .. } => 2005,
+            SCypherError::InvalidRequest(_) => 2006,
+            SCypherError::UnknownFormat(_) => 2007,
+            SCypherError::MalformedContainer { .. } => 2008,
+            SCypherError::WrongWordlist { .. } => 2009,
+
+            SCypherError::InvalidPassword => 3001,
+            SCypherError::PasswordMismatch => 3002,
+            SCypherError::SessionLocked => 3003,
+            SCypherError::KeyCheckMismatch => 3004,
+
+            SCypherError::IoError { .. } => 4001,
+            SCypherError::FileError { .. } => 4002,
+
+            SCypherError::CryptoError(_) => 5001,
+            SCypherError::KeyDerivationFailed(_) => 5002,
+        }
+    }
+
+    /// Categoría del error (determina el código de salida del CLI)
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            SCypherError::InvalidSeedPhrase
+            | SCypherError::InvalidWordCount(_)
+            | SCypherError::InvalidBip39Word { .. }
+            | SCypherError::InvalidChecksum
+            | SCypherError::InvalidParameter { .. }
+            | SCypherError::InvalidRequest(_)
+            | SCypherError::UnknownFormat(_)
+            | SCypherError::MalformedContainer { .. }
+            | SCypherError::WrongWordlist { .. } => ErrorCategory::Validation,
+
+            SCypherError::InvalidPassword
+            | SCypherError::PasswordMismatch
+            | SCypherError::SessionLocked
+            | SCypherError::KeyCheckMismatch => ErrorCategory::Password,
+
+            SCypherError::IoError { .. }
+            | SCypherError::FileError { .. } => ErrorCategory::Io,
+
+            SCypherError::CryptoError(_)
+            | SCypherError::KeyDerivationFailed(_) => ErrorCategory::Crypto,
+
+            SCypherError::InsufficientMemory
+            | SCypherError::UnsupportedPlatform
+            | SCypherError::Cancelled
+            | SCypherError::NetworkActive(_)
+            | SCypherError::ElevatedPrivileges
+            | SCypherError::NotRemovable { .. } => ErrorCategory::General,
+        }
+    }
+
+    /// Código de salida del proceso para este error
+    pub fn exit_code(&self) -> i32 {
+        // Interrumpido con Ctrl-C: el mismo código que si la señal lo hubiera terminado
+        if matches!(self, SCypherError::Cancelled) && crate::security::interrupt::is_requested() {
+            return 130;
+        }
+        self.category().exit_code()
+    }
+
     /// Crear error de palabra BIP39 inválida
     pub fn invalid_word<S: Into<String>>(word: S) -> Self {
-        SCypherError::InvalidBip39Word(word.into())
+        SCypherError::InvalidBip39Word { word: word.into(), position: None, suggestion: None }
     }
-    
+
+    /// Crear error de palabra inválida en la posición `position` (desde 1)
+    pub fn invalid_word_at<S: Into<String>>(position: usize, word: S) -> Self {
+        SCypherError::InvalidBip39Word { word: word.into(), position: Some(position), suggestion: None }
+    }
+
+    /// Crear error de parámetro fuera de rango
+    pub fn invalid_parameter<V: ToString, A: Into<String>>(parameter: Parameter, value: V, allowed: A) -> Self {
+        SCypherError::InvalidParameter {
+            parameter,
+            value: value.to_string(),
+            allowed: allowed.into(),
+        }
+    }
+
     /// Crear error criptográfico con mensaje personalizado
     pub fn crypto<S: Into<String>>(msg: S) -> Self {
         SCypherError::CryptoError(msg.into())
     }
-    
+
     /// Crear error de archivo con mensaje personalizado
     pub fn file<S: Into<String>>(msg: S) -> Self {
-        SCypherError::FileError(msg.into())
+        SCypherError::FileError {
+            path: None,
+            message: msg.into(),
+            source: None,
+        }
+    }
+
+    /// Crear error de archivo indicando la ruta afectada
+    pub fn file_at<P: Into<String>, S: Into<String>>(path: P, msg: S) -> Self {
+        SCypherError::FileError {
+            path: Some(path.into()),
+            message: msg.into(),
+            source: None,
+        }
+    }
+
+    /// Crear error de archivo con la ruta y el error que lo causó
+    pub fn file_caused_by<P, S, E>(path: P, msg: S, source: E) -> Self
+    where
+        P: Into<String>,
+        S: Into<String>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        SCypherError::FileError {
+            path: Some(path.into()),
+            message: msg.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// Crear error de contenedor o codificación mal formados
+    pub fn malformed<S: Into<String>>(format: &'static str, defect: Defect, message: S) -> Self {
+        SCypherError::MalformedContainer { format, defect, message: message.into() }
+    }
+
+    /// Crear error de E/S indicando la operación y la ruta afectada
+    pub fn io_at<P: Into<String>>(operation: IoOperation, path: P, source: std::io::Error) -> Self {
+        SCypherError::IoError {
+            operation: Some(operation),
+            path: Some(path.into()),
+            source,
+        }
+    }
+
+    /// Datos estructurados del error para la salida JSON (`details`)
+    pub fn details(&self) -> Option<serde_json::Value> {
+        use serde_json::json;
+
+        match self {
+            SCypherError::InvalidWordCount(count) => Some(json!({ "word_count": count })),
+            SCypherError::InvalidBip39Word { word, position, suggestion } => {
+                Some(json!({ "word": word, "position": position, "suggestion": suggestion }))
+            }
+            SCypherError::WrongWordlist { wordlist, detected } => {
+                Some(json!({ "wordlist": wordlist, "detected": detected }))
+            }
+            SCypherError::InvalidParameter { parameter, value, allowed } => {
+                Some(json!({ "parameter": parameter.as_str(), "value": value, "allowed": allowed }))
+            }
+            SCypherError::IoError { operation, path, source } => Some(json!({
+                "operation": operation.map(|operation| operation.as_str()),
+                "path": path,
+                "kind": format!("{:?}", source.kind()),
+            })),
+            SCypherError::MalformedContainer { format, defect, .. } => {
+                Some(json!({ "format": format, "defect": defect.as_str() }))
+            }
+            SCypherError::FileError { path: Some(path), .. } => Some(json!({ "path": path })),
+            _ => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::error::Error;
 
     #[test]
     fn test_error_display() {
         let error = SCypherError::InvalidWordCount(10);
         assert!(error.to_string().contains("10"));
-        
-        let error = SCypherError::InvalidBip39Word("invalid".to_string());
+
+        let error = SCypherError::invalid_word("invalid");
         assert!(error.to_string().contains("invalid"));
     }
 
@@ -143,8 +612,80 @@ mod tests {
     fn test_error_helpers() {
         let error = SCypherError::invalid_word("test");
         match error {
-            SCypherError::InvalidBip39Word(word) => assert_eq!(word, "test"),
+            SCypherError::InvalidBip39Word { word, position: None, .. } => assert_eq!(word, "test"),
             _ => panic!("Wrong error type"),
         }
+
+        let error = SCypherError::invalid_word_at(3, "abandn");
+        assert_eq!(error.to_string(), "Word #3 'abandn' is not in the BIP39 wordlist");
+
+        let error = SCypherError::file_at("backup.txt", "Cannot read");
+        assert!(error.to_string().contains("backup.txt"));
+    }
+
+    #[test]
+    fn test_codes_and_categories() {
+        let errors = [
+            SCypherError::InvalidChecksum,
+            SCypherError::invalid_parameter(Parameter::Iterations, 0, "1-100"),
+            SCypherError::PasswordMismatch,
+            SCypherError::KeyCheckMismatch,
+            SCypherError::file("missing"),
+            SCypherError::io_at(IoOperation::Read, "x", std::io::Error::other("x")),
+            SCypherError::invalid_word_at(1, "x"),
+            SCypherError::malformed("ur", Defect::Truncated, "x"),
+            SCypherError::crypto("failure"),
+            SCypherError::InsufficientMemory,
+            SCypherError::NetworkActive(vec!["eth0".to_string()]),
+        ];
+
+        for error in &errors {
+            // El primer dígito del código es el código de salida
+            assert_eq!(error.code() as i32 / 1000, error.exit_code());
+        }
+
+        assert_eq!(SCypherError::InvalidChecksum.category(), ErrorCategory::Validation);
+        assert_eq!(SCypherError::crypto("x").exit_code(), 5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_error_source() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let error = SCypherError::from(io);
+        assert!(error.source().is_some());
+
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        let error = SCypherError::io_at(IoOperation::Write, "out.txt", io);
+        assert_eq!(error.to_string(), "Cannot write 'out.txt': denied");
+        assert_eq!(error.source().unwrap().to_string(), "denied");
+
+        let error = SCypherError::file_caused_by("in.txt", "Cannot decode UR", SCypherError::InvalidChecksum);
+        assert!(error.source().unwrap().to_string().contains("checksum"));
+
+        assert!(SCypherError::InvalidChecksum.source().is_none());
+    }
+
+    #[test]
+    fn test_error_serialize() {
+        let json = serde_json::to_value(SCypherError::InvalidChecksum).unwrap();
+        assert_eq!(json["code"], 2004);
+        assert_eq!(json["category"], "validation");
+        assert!(json["message"].as_str().unwrap().contains("checksum"));
+        assert!(json.get("details").is_none());
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let json = serde_json::to_value(SCypherError::io_at(IoOperation::Read, "seed.txt", io)).unwrap();
+        assert_eq!(json["details"]["operation"], "read");
+        assert_eq!(json["details"]["path"], "seed.txt");
+        assert_eq!(json["details"]["kind"], "NotFound");
+
+        let json = serde_json::to_value(SCypherError::invalid_word_at(2, "abandn")).unwrap();
+        assert_eq!(json["details"]["position"], 2);
+        assert_eq!(json["details"]["word"], "abandn");
+
+        let json = serde_json::to_value(SCypherError::malformed("openpgp", Defect::Checksum, "armor checksum mismatch")).unwrap();
+        assert_eq!(json["code"], 2008);
+        assert_eq!(json["message"], "Malformed openpgp data: armor checksum mismatch");
+        assert_eq!(json["details"]["defect"], "checksum");
+    }
+}
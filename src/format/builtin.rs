@@ -0,0 +1,315 @@
+//! Formatos incluidos en el núcleo
+
+use sha2::{Digest, Sha256};
+
+use crate::bip39::wordlist::{index_to_word, word_to_index, words_with_prefix};
+use crate::error::{SCypherError, Result};
+
+use super::OutputFormat;
+
+/// Palabras BIP39 separadas por un espacio (formato por defecto)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Words;
+
+impl OutputFormat for Words {
+    fn name(&self) -> &str {
+        "words"
+    }
+
+    fn description(&self) -> &str {
+        "BIP39 words separated by spaces (default)"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        Ok(phrase.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        self.encode(&encoded.to_lowercase())
+    }
+}
+
+/// Índices 0-2047 de la lista inglesa, separados por espacios
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Indices;
+
+impl OutputFormat for Indices {
+    fn name(&self) -> &str {
+        "indices"
+    }
+
+    fn description(&self) -> &str {
+        "Zero-based BIP39 English wordlist indices (0-2047)"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        let indices = phrase
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, word)| {
+                word_to_index(word).map(|index| index.to_string()).ok_or_else(|| SCypherError::invalid_word_at(position + 1, word))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(indices.join(" "))
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        let words = encoded
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, token)| {
+                token
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(index_to_word)
+                    .ok_or_else(|| SCypherError::invalid_word_at(position + 1, token))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(words.join(" "))
+    }
+}
+
+/// UR de Blockchain Commons (`ur:crypto-seed`) con la entropía de la frase
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UrSeed;
+
+impl OutputFormat for UrSeed {
+    fn name(&self) -> &str {
+        "ur"
+    }
+
+    fn description(&self) -> &str {
+        "Blockchain Commons ur:crypto-seed (Keystone, SeedSigner and other air-gapped devices)"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        Ok(crate::ur::Ur::crypto_seed(phrase)?.to_single_part())
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        crate::ur::Ur::from_single_part(encoded)?.to_phrase()
+    }
+}
+
+/// Hoja de recuperación numerada, como las de Trezor y Ledger
+///
+/// Sale una palabra por línea (` 1. abandon`). Al leer se aceptan varias
+/// entradas por línea, números con `.`, `)` o `:` y las abreviaturas de 4
+/// letras que muestran los dispositivos (las palabras BIP39 inglesas se
+/// distinguen por sus 4 primeras letras). Si una palabra lleva detrás su
+/// valor de control (`[k7]`, ver [`Worksheet`]), se comprueba.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecoverySheet;
+
+impl RecoverySheet {
+    /// Si el texto empieza como una hoja numerada
+    pub fn looks_like_sheet(text: &str) -> bool {
+        text.split_whitespace().next().is_some_and(|token| parse_number(token).is_some())
+    }
+}
+
+impl OutputFormat for RecoverySheet {
+    fn name(&self) -> &str {
+        "sheet"
+    }
+
+    fn description(&self) -> &str {
+        "Numbered recovery-sheet layout (Trezor/Ledger); reads 4-letter abbreviations"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        let lines: Vec<String> = phrase
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, word)| format!("{:>2}. {}", position + 1, word))
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        let invalid = |what: String| SCypherError::InvalidRequest(format!("invalid recovery sheet: {}", what));
+
+        let mut entries: Vec<(usize, &'static str)> = Vec::new();
+        let mut mismatches = Vec::new();
+        let mut tokens = encoded.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            let number = parse_number(token).ok_or_else(|| invalid(format!("expected a word number, found '{}'", token)))?;
+            let word = tokens.next().ok_or_else(|| invalid(format!("word {} is missing", number)))?;
+            let word = expand_word(word).ok_or_else(|| SCypherError::invalid_word_at(number, word))?;
+            if let Some(check) = tokens.next_if(|token| token.starts_with('[')).map(parse_check) {
+                if check.as_deref() != Some(check_value(number, word).as_str()) {
+                    mismatches.push(number.to_string());
+                }
+            }
+            entries.push((number, word));
+        }
+
+        // Todas las palabras mal copiadas de una vez, antes de descifrar nada
+        if !mismatches.is_empty() {
+            return Err(invalid(format!(
+                "word(s) {} do not match their check value; compare them with the written sheet",
+                mismatches.join(", "),
+            )));
+        }
+
+        entries.sort_by_key(|&(number, _)| number);
+        for (position, &(number, _)) in entries.iter().enumerate() {
+            if number != position + 1 {
+                return Err(invalid(format!("word {} is missing or repeated", position + 1)));
+            }
+        }
+
+        Ok(entries.iter().map(|&(_, word)| word).collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// Hoja de recuperación para imprimir, con un valor de control por palabra
+///
+/// Cada línea lleva el número, la palabra y dos caracteres (`[k7]`) que
+/// dependen de ambos: al volver a teclear la hoja, una palabra mal copiada
+/// o cambiada de sitio se señala por su número antes de descifrar. Los
+/// valores solo detectan errores de copia (1 entre 1024 pasa); no protegen
+/// nada y la hoja es tan secreta como la frase.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Worksheet;
+
+impl Worksheet {
+    /// Título de la hoja, que la identifica al leerla
+    pub const TITLE: &'static str = "SCypher recovery worksheet";
+
+    /// Si el texto es una hoja de `encode`
+    pub fn looks_like_worksheet(text: &str) -> bool {
+        text.trim_start().starts_with(Self::TITLE)
+    }
+}
+
+impl OutputFormat for Worksheet {
+    fn name(&self) -> &str {
+        "worksheet"
+    }
+
+    fn description(&self) -> &str {
+        "Printable numbered worksheet with a 2-character check value per word"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        let mut lines = vec![
+            format!("{} ({} words)", Self::TITLE, words.len()),
+            String::new(),
+        ];
+        for (position, &word) in words.iter().enumerate() {
+            let word = expand_word(word).ok_or_else(|| SCypherError::invalid_word_at(position + 1, word))?;
+            lines.push(format!("{:>2}. {:<8}  [{}]", position + 1, word, check_value(position + 1, word)));
+        }
+        lines.push(String::new());
+        lines.push("Type the sheet back (numbers, words and checks) to have each word verified.".to_string());
+        lines.push("Checks only catch copying mistakes: keep this sheet as secret as the phrase.".to_string());
+        Ok(lines.join("\n"))
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        // Solo las líneas numeradas; el título y las notas se ignoran
+        let entries: Vec<&str> = encoded.lines().filter(|line| RecoverySheet::looks_like_sheet(line)).collect();
+        RecoverySheet.decode(&entries.join("\n"))
+    }
+}
+
+/// Alfabeto bech32 (sin `1`, `b`, `i` ni `o`, que se confunden al copiar)
+const CHECK_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Valor de control de la palabra `word` en la posición `number` (desde 1)
+pub fn check_value(number: usize, word: &str) -> String {
+    let hash = Sha256::new()
+        .chain_update(b"scypher/worksheet/v1")
+        .chain_update((number as u32).to_be_bytes())
+        .chain_update(word.as_bytes())
+        .finalize();
+    let bits = u16::from_be_bytes([hash[0], hash[1]]) >> 6;
+    [bits >> 5, bits & 31].iter().map(|&i| CHECK_CHARSET[i as usize] as char).collect()
+}
+
+/// Valor de control escrito como `[k7]` (sin distinguir mayúsculas)
+fn parse_check(token: &str) -> Option<String> {
+    token
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .map(str::to_ascii_lowercase)
+}
+
+/// Número de una entrada: `1`, `01`, `1.`, `1)` o `1:`
+fn parse_number(token: &str) -> Option<usize> {
+    token
+        .trim_end_matches(['.', ')', ':'])
+        .parse()
+        .ok()
+        .filter(|&number| number > 0)
+}
+
+/// Palabra completa, o la única palabra que empieza por una abreviatura de 4 letras
+fn expand_word(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    if let Some(full) = word_to_index(&word).and_then(index_to_word) {
+        return Some(full);
+    }
+
+    match (word.len(), words_with_prefix(&word).as_slice()) {
+        (4, [expanded]) => Some(expanded),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_roundtrip() {
+        let phrase = "abandon ability zoo";
+
+        assert_eq!(Indices.encode(phrase).unwrap(), "0 1 2047");
+        assert_eq!(Indices.decode("0 1 2047").unwrap(), phrase);
+        assert!(Indices.decode("2048").is_err());
+        assert!(Indices.encode("notaword").is_err());
+
+        assert_eq!(Words.decode("  Abandon\nABILITY zoo ").unwrap(), phrase);
+
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let encoded = UrSeed.encode(phrase).unwrap();
+        assert!(encoded.starts_with("ur:crypto-seed/"));
+        assert_eq!(UrSeed.decode(&encoded.to_uppercase()).unwrap(), phrase);
+
+        let sheet = RecoverySheet.encode("abandon ability zoo").unwrap();
+        assert_eq!(sheet, " 1. abandon\n 2. ability\n 3. zoo");
+        assert_eq!(RecoverySheet.decode(&sheet).unwrap(), "abandon ability zoo");
+        assert_eq!(RecoverySheet.decode("2) ABIL 3: zoo\n01 aban").unwrap(), "abandon ability zoo");
+        assert!(RecoverySheet.decode("1. abandon 3. zoo").is_err());
+        assert!(RecoverySheet.decode("1. abandon 1. zoo").is_err());
+        assert!(RecoverySheet.decode("1. aba").is_err());
+        assert!(RecoverySheet::looks_like_sheet(" 1. abandon"));
+        assert!(!RecoverySheet::looks_like_sheet("abandon"));
+    }
+
+    #[test]
+    fn test_worksheet_checks() {
+        let phrase = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+        let sheet = Worksheet.encode(phrase).unwrap();
+        assert!(Worksheet::looks_like_worksheet(&sheet));
+        assert!(sheet.contains(&format!(" 1. shock     [{}]", check_value(1, "shock"))));
+        assert_eq!(Worksheet.decode(&sheet).unwrap(), phrase);
+
+        // Las abreviaturas se verifican con la palabra completa
+        let typed = format!("1. shoc [{}] 2 strong [{}]", check_value(1, "shock"), check_value(2, "strong").to_uppercase());
+        assert_eq!(RecoverySheet.decode(&typed).unwrap(), "shock strong");
+
+        // Una palabra mal copiada y dos intercambiadas se señalan por su número
+        let (check1, check2) = (check_value(1, "shock"), check_value(2, "strong"));
+        assert_ne!(check_value(1, "stock"), check1);
+        let err = RecoverySheet.decode(&format!("1. stock [{}] 2. strong [{}]", check1, check2)).unwrap_err();
+        assert!(err.to_string().contains("word(s) 1 do not match"));
+        let err = RecoverySheet.decode(&format!("1. strong [{}] 2. shock [{}]", check1, check2)).unwrap_err();
+        assert!(err.to_string().contains("word(s) 1, 2 do not match"));
+    }
+}
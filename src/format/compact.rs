@@ -0,0 +1,181 @@
+//! Codificaciones compactas de la entropía: bech32m y Base58Check
+//!
+//! Pensadas para copiar a mano la frase cifrada o meterla en un QR pequeño:
+//! una sola cadena con detección de errores incorporada (el checksum de
+//! bech32m localiza hasta 4 caracteres erróneos, el de Base58Check los
+//! detecta). La carga es un byte de versión seguido de la entropía (16-32
+//! bytes); con 12 palabras salen 42 caracteres en bech32m y unos 29 en Base58.
+//!
+//! - bech32m (BIP350) con HRP `scypher`: `scypher1...`, sin mayúsculas
+//!   mezcladas; en mayúsculas cabe en el modo alfanumérico de un QR.
+//! - Base58Check (la de Bitcoin): más corta, pero distingue mayúsculas.
+
+use bech32::{FromBase32, ToBase32, Variant};
+use zeroize::Zeroizing;
+
+use crate::error::{Defect, SCypherError, Result};
+
+use super::OutputFormat;
+
+/// Parte legible de las cadenas bech32m
+pub const BECH32_HRP: &str = "scypher";
+
+/// Versión de la carga (primer byte, antes de la entropía)
+pub const PAYLOAD_VERSION: u8 = 0;
+
+/// Longitud mínima de una cadena Base58Check con 16 bytes de entropía (con
+/// entropía nula cada byte a cero da un solo `1`)
+const MIN_BASE58_LEN: usize = 20;
+
+/// Entropía de la frase en bech32m con HRP `scypher`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bech32Seed;
+
+impl Bech32Seed {
+    /// Si el texto empieza por `scypher1` (en minúsculas o mayúsculas)
+    pub fn looks_like(text: &str) -> bool {
+        let text = text.trim();
+        text.get(..BECH32_HRP.len() + 1)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{}1", BECH32_HRP)))
+    }
+}
+
+impl OutputFormat for Bech32Seed {
+    fn name(&self) -> &str {
+        "bech32"
+    }
+
+    fn description(&self) -> &str {
+        "Phrase entropy as a bech32m string (scypher1...), checksummed and easy to transcribe"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        let payload = payload(phrase)?;
+        bech32::encode(BECH32_HRP, payload.to_base32(), Variant::Bech32m)
+            .map_err(|e| SCypherError::malformed("bech32", Defect::Encoding, e.to_string()))
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        let (hrp, data, variant) = bech32::decode(encoded.trim()).map_err(|e| {
+            let defect = match e {
+                bech32::Error::InvalidChecksum => Defect::Checksum,
+                _ => Defect::Encoding,
+            };
+            SCypherError::malformed("bech32", defect, e.to_string())
+        })?;
+
+        if hrp != BECH32_HRP {
+            return Err(SCypherError::malformed("bech32", Defect::Unsupported,
+                format!("expected the '{}' prefix, found '{}'", BECH32_HRP, hrp)));
+        }
+        if variant != Variant::Bech32m {
+            return Err(SCypherError::malformed("bech32", Defect::Unsupported, "bech32 checksum instead of bech32m"));
+        }
+
+        let payload = Zeroizing::new(Vec::<u8>::from_base32(&data)
+            .map_err(|e| SCypherError::malformed("bech32", Defect::Encoding, e.to_string()))?);
+        phrase_from_payload("bech32", &payload)
+    }
+}
+
+/// Entropía de la frase en Base58Check
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Base58Seed;
+
+impl Base58Seed {
+    /// Si el texto es una sola cadena Base58 del tamaño de una carga
+    ///
+    /// Una palabra BIP39 suelta también es Base58 válido, de ahí la longitud mínima.
+    pub fn looks_like(text: &str) -> bool {
+        let text = text.trim();
+        text.len() >= MIN_BASE58_LEN
+            && text.bytes().all(|b| b.is_ascii_alphanumeric() && !matches!(b, b'0' | b'O' | b'I' | b'l'))
+    }
+}
+
+impl OutputFormat for Base58Seed {
+    fn name(&self) -> &str {
+        "base58"
+    }
+
+    fn description(&self) -> &str {
+        "Phrase entropy as a Base58Check string (shortest, case-sensitive)"
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        Ok(bs58::encode(&payload(phrase)?[..]).with_check().into_string())
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        let payload = Zeroizing::new(bs58::decode(encoded.trim()).with_check(None).into_vec().map_err(|e| {
+            let defect = match e {
+                bs58::decode::Error::InvalidChecksum { .. } => Defect::Checksum,
+                bs58::decode::Error::NoChecksum => Defect::Truncated,
+                _ => Defect::Encoding,
+            };
+            SCypherError::malformed("base58", defect, e.to_string())
+        })?);
+        phrase_from_payload("base58", &payload)
+    }
+}
+
+/// Byte de versión seguido de la entropía de `phrase`
+fn payload(phrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let entropy = Zeroizing::new(crate::bip39::phrase_to_entropy(phrase)?);
+    let mut payload = Zeroizing::new(Vec::with_capacity(entropy.len() + 1));
+    payload.push(PAYLOAD_VERSION);
+    payload.extend_from_slice(&entropy);
+    Ok(payload)
+}
+
+fn phrase_from_payload(format: &'static str, payload: &[u8]) -> Result<String> {
+    match payload.split_first() {
+        Some((&PAYLOAD_VERSION, entropy)) if matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) => {
+            crate::bip39::entropy_to_phrase(entropy)
+        }
+        Some((&PAYLOAD_VERSION, entropy)) => Err(SCypherError::malformed(format, Defect::Inconsistent,
+            format!("{} bytes of entropy (expected 16, 20, 24, 28 or 32)", entropy.len()))),
+        Some((version, _)) => Err(SCypherError::malformed(format, Defect::Unsupported,
+            format!("payload version {}", version))),
+        None => Err(SCypherError::malformed(format, Defect::Truncated, "empty payload")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_compact_roundtrip() {
+        let encoded = Bech32Seed.encode(PHRASE).unwrap();
+        assert_eq!(encoded, "scypher1qqqqqqqqqqqqqqqqqqqqqqqqqqqq8ld5g4");
+        assert!(Bech32Seed::looks_like(&encoded.to_uppercase()));
+        assert_eq!(Bech32Seed.decode(&encoded.to_uppercase()).unwrap(), PHRASE);
+
+        // Un carácter cambiado lo detecta el checksum
+        let mut typo = encoded.clone().into_bytes();
+        typo[12] = b'p';
+        let typo = String::from_utf8(typo).unwrap();
+        assert!(matches!(Bech32Seed.decode(&typo),
+            Err(SCypherError::MalformedContainer { defect: Defect::Checksum, .. })));
+        assert!(Bech32Seed.decode(&bech32::encode("other", [0u8; 17].to_base32(), Variant::Bech32m).unwrap()).is_err());
+        assert!(Bech32Seed.decode(&bech32::encode(BECH32_HRP, [0u8; 17].to_base32(), Variant::Bech32).unwrap()).is_err());
+
+        let phrase = "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote";
+        let encoded = Base58Seed.encode(phrase).unwrap();
+        assert!(Base58Seed::looks_like(&encoded));
+        assert!(!Base58Seed::looks_like("abandon"));
+        assert_eq!(Base58Seed.decode(&encoded).unwrap(), phrase);
+        assert!(Base58Seed.decode(&encoded.to_lowercase()).is_err());
+
+        // Versión y longitud de la carga
+        let unsupported = bs58::encode([1u8; 17]).with_check().into_string();
+        assert!(matches!(Base58Seed.decode(&unsupported),
+            Err(SCypherError::MalformedContainer { defect: Defect::Unsupported, .. })));
+        let short = bs58::encode([0u8; 9]).with_check().into_string();
+        assert!(matches!(Base58Seed.decode(&short),
+            Err(SCypherError::MalformedContainer { defect: Defect::Inconsistent, .. })));
+    }
+}
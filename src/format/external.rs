@@ -0,0 +1,152 @@
+//! Formatos implementados por ejecutables externos
+//!
+//! Protocolo: `scypher-format-<nombre> encode|decode`, entrada completa por
+//! stdin, resultado por stdout y código de salida 0 si tuvo éxito. stderr se
+//! hereda para que el plugin pueda informar de sus errores.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use zeroize::Zeroizing;
+
+use crate::error::{IoOperation, SCypherError, Result};
+
+use super::{is_valid_name, OutputFormat};
+
+/// Prefijo de los ejecutables de formato buscados en el `PATH`
+pub const EXTERNAL_PREFIX: &str = "scypher-format-";
+
+/// Formato servido por un ejecutable `scypher-format-<nombre>`
+#[derive(Debug, Clone)]
+pub struct ExternalFormat {
+    name: String,
+    path: PathBuf,
+    description: String,
+}
+
+impl ExternalFormat {
+    /// Ejecutable del formato `name` en el `PATH`, si existe
+    pub fn find(name: &str) -> Option<Self> {
+        Self::discover().into_iter().find(|format| format.name == name)
+    }
+
+    /// Ejecutables de formato en el `PATH`; si un nombre se repite gana el primero
+    pub fn discover() -> Vec<Self> {
+        let mut formats: Vec<Self> = Vec::new();
+        let Some(path) = std::env::var_os("PATH") else {
+            return formats;
+        };
+
+        for dir in std::env::split_paths(&path) {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Some(name) = plugin_name(&path) else {
+                    continue;
+                };
+
+                if is_executable(&path) && !formats.iter().any(|format| format.name == name) {
+                    formats.push(Self {
+                        description: format!("external plugin ({})", path.display()),
+                        name,
+                        path,
+                    });
+                }
+            }
+        }
+
+        formats.sort_by(|a, b| a.name.cmp(&b.name));
+        formats
+    }
+
+    /// Ruta del ejecutable
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn run(&self, operation: &str, input: &str) -> Result<String> {
+        let plugin_error = |message: String| SCypherError::file_at(self.path.display().to_string(), message);
+        let io_error = |operation, e| SCypherError::io_at(operation, self.path.display().to_string(), e);
+
+        let mut child = Command::new(&self.path)
+            .arg(operation)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| io_error(IoOperation::Execute, e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|e| io_error(IoOperation::Write, e))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| io_error(IoOperation::Read, e))?;
+        let stdout = Zeroizing::new(output.stdout);
+
+        if !output.status.success() {
+            return Err(plugin_error(format!("Format plugin {} failed ({})", operation, output.status)));
+        }
+
+        let text = std::str::from_utf8(&stdout)
+            .map_err(|_| plugin_error("Format plugin produced invalid UTF-8".to_string()))?;
+        Ok(text.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+impl OutputFormat for ExternalFormat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn encode(&self, phrase: &str) -> Result<String> {
+        self.run("encode", phrase)
+    }
+
+    fn decode(&self, encoded: &str) -> Result<String> {
+        self.run("decode", encoded)
+    }
+}
+
+/// Nombre del formato a partir del nombre de archivo del ejecutable
+fn plugin_name(path: &Path) -> Option<String> {
+    let file_name = if cfg!(windows) { path.file_stem()? } else { path.file_name()? };
+    let name = file_name.to_str()?.strip_prefix(EXTERNAL_PREFIX)?;
+
+    is_valid_name(name).then(|| name.to_string())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(plugin_name(Path::new("/usr/bin/scypher-format-seedqr")).as_deref(), Some("seedqr"));
+        assert_eq!(plugin_name(Path::new("/usr/bin/scypher-rust")), None);
+        assert_eq!(plugin_name(Path::new("/usr/bin/scypher-format-Bad_Name")), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Formatos de salida enchufables
+//!
+//! Un `OutputFormat` convierte una frase BIP39 a otra representación y de
+//! vuelta (SeedQR, SLIP-39, aezeed, codificaciones internas...). El núcleo
+//! solo incluye `words`, `indices`, `ur`, `sheet`, `worksheet`, `bech32` y
+//! `base58` (todos menos el primero con la feature `formats-extra`); el
+//! resto vive fuera:
+//!
+//! - crates que dependen de `scypher-rust` y llaman a `register_format` al
+//!   arrancar;
+//! - ejecutables `scypher-format-<nombre>` en el `PATH` (feature
+//!   `formats-extra`), que
+//!   reciben `encode` o `decode` como argumento, leen la entrada por stdin y
+//!   escriben el resultado por stdout.
+//!
+//! Un formato externo solo recibe frases cuando se selecciona explícitamente.
+
+mod builtin;
+#[cfg(feature = "formats-extra")]
+mod compact;
+#[cfg(feature = "formats-extra")]
+mod external;
+
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::error::{SCypherError, Result};
+
+pub use builtin::{check_value, Indices, RecoverySheet, UrSeed, Words, Worksheet};
+#[cfg(feature = "formats-extra")]
+pub use compact::{Base58Seed, Bech32Seed, BECH32_HRP, PAYLOAD_VERSION};
+#[cfg(feature = "formats-extra")]
+pub use external::{ExternalFormat, EXTERNAL_PREFIX};
+
+/// Codificación alternativa de una frase BIP39
+pub trait OutputFormat: Send + Sync {
+    /// Nombre único (minúsculas, dígitos y guiones)
+    fn name(&self) -> &str;
+
+    /// Descripción de una línea para `--list-formats`
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Convertir una frase BIP39 (palabras separadas por espacios) a este formato
+    fn encode(&self, phrase: &str) -> Result<String>;
+
+    /// Recuperar la frase BIP39 a partir de este formato
+    fn decode(&self, encoded: &str) -> Result<String>;
+}
+
+fn registry() -> &'static RwLock<Vec<Arc<dyn OutputFormat>>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Arc<dyn OutputFormat>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        #[cfg(feature = "formats-extra")]
+        let formats: Vec<Arc<dyn OutputFormat>> = vec![
+            Arc::new(Words), Arc::new(Indices), Arc::new(UrSeed), Arc::new(RecoverySheet), Arc::new(Worksheet), Arc::new(Bech32Seed), Arc::new(Base58Seed),
+        ];
+        #[cfg(not(feature = "formats-extra"))]
+        let formats: Vec<Arc<dyn OutputFormat>> = vec![Arc::new(Words)];
+        RwLock::new(formats)
+    })
+}
+
+/// Comprobar que un nombre de formato es válido
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Registrar un formato; falla si el nombre es inválido o ya existe
+pub fn register_format<F: OutputFormat + 'static>(format: F) -> Result<()> {
+    let name = format.name();
+    if !is_valid_name(name) {
+        return Err(SCypherError::InvalidRequest(format!("invalid format name '{}'", name)));
+    }
+
+    let mut formats = registry().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if formats.iter().any(|existing| existing.name() == name) {
+        return Err(SCypherError::InvalidRequest(format!("format '{}' is already registered", name)));
+    }
+
+    formats.push(Arc::new(format));
+    Ok(())
+}
+
+/// Formatos registrados en el proceso, en orden de registro
+pub fn registered_formats() -> Vec<Arc<dyn OutputFormat>> {
+    registry().read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Buscar un formato por nombre
+///
+/// Los formatos registrados tienen prioridad; con la feature `formats-extra` se busca
+/// después un ejecutable `scypher-format-<nombre>` en el `PATH`.
+pub fn find_format(name: &str) -> Result<Arc<dyn OutputFormat>> {
+    if let Some(format) = registered_formats().into_iter().find(|format| format.name() == name) {
+        return Ok(format);
+    }
+
+    #[cfg(feature = "formats-extra")]
+    if let Some(format) = ExternalFormat::find(name) {
+        return Ok(Arc::new(format));
+    }
+
+    Err(SCypherError::UnknownFormat(name.to_string()))
+}
+
+/// Todos los formatos disponibles: registrados y, con `formats-extra`, externos
+///
+/// Un ejecutable externo con el nombre de un formato registrado se ignora.
+pub fn available_formats() -> Vec<Arc<dyn OutputFormat>> {
+    #[allow(unused_mut)]
+    let mut formats = registered_formats();
+
+    #[cfg(feature = "formats-extra")]
+    for external in ExternalFormat::discover() {
+        if !formats.iter().any(|format| format.name() == external.name()) {
+            formats.push(Arc::new(external));
+        }
+    }
+
+    formats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Upper;
+
+    impl OutputFormat for Upper {
+        fn name(&self) -> &str {
+            "test-upper"
+        }
+
+        fn encode(&self, phrase: &str) -> Result<String> {
+            Ok(phrase.to_uppercase())
+        }
+
+        fn decode(&self, encoded: &str) -> Result<String> {
+            Ok(encoded.to_lowercase())
+        }
+    }
+
+    #[test]
+    fn test_registration() {
+        register_format(Upper).unwrap();
+        assert!(register_format(Upper).is_err());
+
+        let format = find_format("test-upper").unwrap();
+        assert_eq!(format.encode("abandon about").unwrap(), "ABANDON ABOUT");
+        assert!(available_formats().iter().any(|format| format.name() == "words"));
+
+        assert!(matches!(find_format("no-such-format"), Err(SCypherError::UnknownFormat(_))));
+        assert!(!is_valid_name("Bad Name"));
+    }
+}
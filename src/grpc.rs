@@ -0,0 +1,305 @@
+//! Servidor gRPC para pipelines de custodia
+//!
+//! Expone el servicio `scypher.v1.Scypher` (ver `proto/scypher.proto`):
+//! `TransformBatch` transforma un lote de frases y emite un evento por frase
+//! a medida que terminan, y `Validate` analiza una frase. Ambos usan el mismo
+//! núcleo que el resto de interfaces.
+//!
+//! En producción el servidor se levanta con mTLS: el cliente debe presentar un
+//! certificado firmado por la CA configurada. Las contraseñas y frases de las
+//! peticiones y respuestas se limpian de memoria al liberarse.
+
+use std::net::SocketAddr;
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
+use tonic::{Request, Response, Status};
+use zeroize::Zeroize;
+
+use crate::bip39::ENGLISH;
+use crate::crypto::{self, ChecksumPolicy, ProgressSink, ScypherParams};
+use crate::error::{IoOperation, SCypherError, Result};
+
+// Stubs de servicio generados por build.rs
+include!(concat!(env!("OUT_DIR"), "/scypher.v1.Scypher.rs"));
+
+pub use scypher_server::{Scypher, ScypherServer};
+
+/// Petición de `TransformBatch`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TransformRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub phrases: Vec<String>,
+    #[prost(string, tag = "2")]
+    pub password: String,
+    /// 0 = valor por defecto
+    #[prost(uint32, tag = "3")]
+    pub iterations: u32,
+    /// KB; 0 = valor por defecto
+    #[prost(uint32, tag = "4")]
+    pub memory_cost: u32,
+    #[prost(bool, tag = "5")]
+    pub skip_checksum: bool,
+}
+
+/// Error de un elemento del lote (equivale al `SCypherError` serializado)
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ErrorInfo {
+    #[prost(uint32, tag = "1")]
+    pub code: u32,
+    #[prost(string, tag = "2")]
+    pub category: String,
+    #[prost(string, tag = "3")]
+    pub message: String,
+}
+
+/// Resultado de una frase del lote
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TransformEvent {
+    #[prost(uint32, tag = "1")]
+    pub index: u32,
+    #[prost(uint32, tag = "2")]
+    pub total: u32,
+    #[prost(oneof = "transform_event::Outcome", tags = "3, 4")]
+    pub outcome: Option<transform_event::Outcome>,
+}
+
+pub mod transform_event {
+    /// Frase transformada o error
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Outcome {
+        #[prost(string, tag = "3")]
+        Phrase(String),
+        #[prost(message, tag = "4")]
+        Error(super::ErrorInfo),
+    }
+}
+
+/// Petición de `Validate`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ValidateRequest {
+    #[prost(string, tag = "1")]
+    pub phrase: String,
+}
+
+/// Análisis de una frase
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ValidateResponse {
+    #[prost(bool, tag = "1")]
+    pub valid: bool,
+    #[prost(uint32, tag = "2")]
+    pub word_count: u32,
+    #[prost(string, repeated, tag = "3")]
+    pub invalid_words: Vec<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub suggestions: Vec<String>,
+    #[prost(bool, tag = "5")]
+    pub checksum_valid: bool,
+}
+
+// Los mensajes con secretos se limpian al liberarse
+impl Drop for TransformRequest {
+    fn drop(&mut self) {
+        self.phrases.zeroize();
+        self.password.zeroize();
+    }
+}
+
+impl Drop for TransformEvent {
+    fn drop(&mut self) {
+        if let Some(transform_event::Outcome::Phrase(phrase)) = &mut self.outcome {
+            phrase.zeroize();
+        }
+    }
+}
+
+impl Drop for ValidateRequest {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+    }
+}
+
+impl From<&SCypherError> for ErrorInfo {
+    fn from(error: &SCypherError) -> Self {
+        Self {
+            code: u32::from(error.code()),
+            category: error.category().as_str().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl TransformRequest {
+    /// Parámetros de la transformación (los campos a 0 toman el valor por defecto)
+    fn params(&self) -> Result<ScypherParams> {
+        let mut builder = ScypherParams::builder();
+        if self.iterations != 0 {
+            builder = builder.iterations(self.iterations);
+        }
+        if self.memory_cost != 0 {
+            builder = builder.memory_cost(self.memory_cost);
+        }
+        if self.skip_checksum {
+            builder = builder.checksum_policy(ChecksumPolicy::Skip);
+        }
+        builder.build()
+    }
+}
+
+type EventSender = mpsc::Sender<std::result::Result<TransformEvent, Status>>;
+
+/// Cancela el lote en cuanto el cliente cierra el stream
+struct StreamSink<'a> {
+    sender: &'a EventSender,
+}
+
+impl ProgressSink for StreamSink<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// Implementación del servicio sobre el núcleo de la biblioteca
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScypherService;
+
+#[tonic::async_trait]
+impl Scypher for ScypherService {
+    type TransformBatchStream = ReceiverStream<std::result::Result<TransformEvent, Status>>;
+
+    async fn transform_batch(
+        &self,
+        request: Request<TransformRequest>,
+    ) -> std::result::Result<Response<Self::TransformBatchStream>, Status> {
+        let request = request.into_inner();
+        let params = request.params().map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let (sender, receiver) = mpsc::channel(4);
+
+        tokio::task::spawn_blocking(move || {
+            let sink = StreamSink { sender: &sender };
+            let total = request.phrases.len() as u32;
+
+            for (index, phrase) in request.phrases.iter().enumerate() {
+                let outcome = match crypto::transform_seed_with_progress(phrase, &request.password, &params, &ENGLISH, &sink) {
+                    // El mensaje protobuf lleva un String: se mueve sin dejar copia atrás
+                    Ok(mut phrase) => transform_event::Outcome::Phrase(std::mem::take(&mut *phrase)),
+                    Err(SCypherError::Cancelled) => break,
+                    Err(error) => transform_event::Outcome::Error(ErrorInfo::from(&error)),
+                };
+
+                let event = TransformEvent { index: index as u32, total, outcome: Some(outcome) };
+                if sender.blocking_send(Ok(event)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+
+    async fn validate(
+        &self,
+        request: Request<ValidateRequest>,
+    ) -> std::result::Result<Response<ValidateResponse>, Status> {
+        let analysis = crate::bip39::analyze_seed_phrase(&request.get_ref().phrase);
+
+        Ok(Response::new(ValidateResponse {
+            valid: analysis.overall_valid,
+            word_count: analysis.word_count as u32,
+            invalid_words: analysis.invalid_words,
+            suggestions: analysis.suggestions,
+            checksum_valid: analysis.checksum_valid.unwrap_or(false),
+        }))
+    }
+}
+
+/// Material TLS del servidor (todo en formato PEM)
+pub struct TlsConfig {
+    /// Certificado del servidor
+    pub cert: Vec<u8>,
+    /// Clave privada del servidor
+    pub key: Vec<u8>,
+    /// CA que firma los certificados de cliente aceptados
+    pub client_ca: Vec<u8>,
+}
+
+impl Drop for TlsConfig {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+/// Servir el servicio gRPC en `addr` hasta que el proceso termine
+///
+/// Con `tls` se exige mTLS; sin él el tráfico viaja en claro y solo debe
+/// usarse detrás de un proxy que termine TLS o en pruebas locales.
+pub async fn serve(addr: SocketAddr, tls: Option<TlsConfig>) -> Result<()> {
+    let mut server = Server::builder();
+
+    if let Some(tls) = tls {
+        let config = ServerTlsConfig::new()
+            .identity(Identity::from_pem(&tls.cert, &tls.key))
+            .client_ca_root(Certificate::from_pem(&tls.client_ca));
+        server = server
+            .tls_config(config)
+            .map_err(|e| SCypherError::crypto(format!("Invalid TLS configuration: {}", e)))?;
+    }
+
+    server
+        .add_service(ScypherServer::new(ScypherService))
+        .serve(addr)
+        .await
+        .map_err(|e| SCypherError::io_at(IoOperation::Bind, addr.to_string(), std::io::Error::other(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_multi_thread().worker_threads(1).build().unwrap()
+    }
+
+    #[test]
+    fn test_transform_batch_stream() {
+        let request = TransformRequest {
+            phrases: vec![TEST_PHRASE.to_string(), "not a seed phrase".to_string()],
+            password: "test_password".to_string(),
+            iterations: 1,
+            memory_cost: 8192,
+            skip_checksum: false,
+        };
+
+        let events: Vec<TransformEvent> = runtime().block_on(async {
+            let stream = ScypherService.transform_batch(Request::new(request)).await.unwrap().into_inner();
+            stream.map(|event| event.unwrap()).collect().await
+        });
+
+        assert_eq!(events.len(), 2);
+        let expected = crypto::transform_seed(TEST_PHRASE, "test_password", 1, 8192).unwrap();
+        assert_eq!(events[0].outcome, Some(transform_event::Outcome::Phrase(expected.to_string())));
+        assert!(matches!(&events[1].outcome, Some(transform_event::Outcome::Error(e)) if e.code == 2002));
+        assert_eq!(events[1].total, 2);
+    }
+
+    #[test]
+    fn test_validate_and_invalid_params() {
+        let rt = runtime();
+
+        let request = ValidateRequest { phrase: TEST_PHRASE.to_string() };
+        let response = rt.block_on(ScypherService.validate(Request::new(request))).unwrap().into_inner();
+        assert!(response.valid);
+        assert_eq!(response.word_count, 12);
+
+        let mut request = TransformRequest::default();
+        request.iterations = 1;
+        request.memory_cost = 1;
+        let status = rt.block_on(ScypherService.transform_batch(Request::new(request))).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -12,6 +12,55 @@
 //! - Limpieza segura de memoria
 //! - Sin dependencias de red (operación completamente offline)
 //!
+//! ## Features de Cargo
+//! - `cli` (por defecto): interfaz de terminal, lectura de archivos y manejo de señales.
+//!   Sola (`--no-default-features --features cli`) da el binario mínimo para
+//!   equipos aislados: transformación y validación por stdin/stdout
+//! - `interactive` (por defecto): menús, `shell`, `preview`, entropía de
+//!   teclado (`generate --keyboard-entropy`) y tecla de pánico
+//! - `formats-extra` (por defecto): formatos `indices`, `ur`, `sheet`,
+//!   `worksheet`, `bech32` y `base58` y ejecutables `scypher-format-*` del
+//!   `PATH`; sin ella solo existe `words`
+//! - `hardware` (por defecto): `--hwi-check` contra una cartera hardware
+//! - `parallel` (por defecto): `transform_batch_parallel` reparte los lotes en
+//!   un thread pool de rayon limitado por un presupuesto de memoria, y las
+//!   lanes de Argon2 se calculan en hilos separados
+//! - `async`: `transform_seed_async`/`transform_batch_async` sobre tokio, con `ProgressSink` y cancelación
+//! - `wasm`: exportaciones wasm-bindgen para `wasm32-unknown-unknown`
+//!   (compilar con `--no-default-features --features wasm`)
+//! - `node`: bindings N-API para Node.js/Electron (síncronos y basados en Promise)
+//! - `mobile`: bindings UniFFI para Kotlin/Swift; `mobile-bindgen` compila además
+//!   el binario `uniffi-bindgen` que genera el código de cada plataforma
+//! - `wallet` (por defecto): derivación BIP32 de la frase resultante y exportación
+//!   de la clave de la cuenta (keystore V3 de Ethereum, BIP38)
+//! - `armor` (por defecto): el resultado dentro de un archivo age o un mensaje
+//!   OpenPGP con passphrase,
+//!   junto a los parámetros necesarios para recuperarlo
+//! - `qr` (por defecto): el resultado como QR en la terminal (UR de Blockchain
+//!   Commons, animado si necesita varias partes)
+//! - `stego` (por defecto): el resultado oculto en los bits bajos de una
+//!   imagen PNG o BMP, y su extracción al leerla con `-f`
+//! - `slip39` (por defecto): entrada desde shares SLIP-39, reconstruyendo el
+//!   master secret
+//! - `grpc`: servidor gRPC con mTLS (`scypher grpc`), lotes con progreso en streaming
+//!
+//! ## API estable
+//! `scypher_rust::api` (`Transform`, `Params`, `Mnemonic`, `Container`) es
+//! la superficie con garantías semver para otros crates; el resto de módulos
+//! públicos puede cambiar entre versiones menores.
+//!
+//! ## Núcleo sin `std`
+//! El empaquetado de índices, el checksum BIP39 y el XOR están en el crate
+//! `scypher-core` (`no_std` + `alloc`), con el KDF detrás del trait
+//! `scypher_core::KeystreamSource`; aquí lo implementa
+//! `crypto::keystream::PasswordKeystream` con Argon2.
+//!
+//! ## Diagnóstico
+//! Las operaciones emiten spans `tracing` a nivel debug (`validate`,
+//! `derive_key`, `transform`, `encode`, `input`, `output`) con tiempos y
+//! metadatos no sensibles (número de palabras, parámetros Argon2). Nunca
+//! incluyen frases, contraseñas ni claves.
+//!
 //! ## Ejemplo de uso
 //! ```rust,no_run
 //! use scypher_rust::{transform_seed, SCypherError};
@@ -27,7 +76,7 @@
 //! // Desencriptar (misma función debido a XOR)
 //! let decrypted = transform_seed(&encrypted, password, iterations, memory_cost)?;
 //!
-//! assert_eq!(seed, decrypted);
+//! assert_eq!(seed, *decrypted);
 //! # Ok::<(), SCypherError>(())
 //! ```
 
@@ -36,12 +85,65 @@ pub mod error;
 pub mod crypto;
 pub mod bip39;
 pub mod security;
+pub mod api;
+pub mod protocol;
+pub mod format;
+pub mod ur;
+
+// API asíncrona sobre tokio
+#[cfg(feature = "async")]
+pub mod async_api;
+
+// Servidor gRPC
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+// Derivación de cuentas y exportación de claves
+#[cfg(feature = "wallet")]
+pub mod wallet;
+
+// Contenedores cifrados estándar
+#[cfg(feature = "armor")]
+pub mod armor;
+
+// Datos ocultos en imágenes
+#[cfg(feature = "stego")]
+pub mod stego;
+
+// Reconstrucción de copias SLIP-39
+#[cfg(feature = "slip39")]
+pub mod slip39;
+
+// Interfaz de terminal (solo en builds nativos)
+#[cfg(feature = "cli")]
+pub mod cli;
+
+// Exportaciones para WebAssembly
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// Bindings N-API para Node.js
+#[cfg(feature = "node")]
+pub mod node;
+
+// Bindings UniFFI para Kotlin/Swift
+#[cfg(feature = "mobile")]
+pub mod mobile;
+
+#[cfg(feature = "mobile")]
+uniffi::setup_scaffolding!();
+
+use zeroize::Zeroizing;
 
 // Re-exportaciones públicas para facilitar el uso
 pub use error::{SCypherError, Result};
-pub use crypto::transform_seed;
-pub use bip39::{validate_seed_phrase_complete as validate_seed_phrase, verify_checksum};
+pub use crypto::{transform_seed, transform_seed_with_params, transform_seed_with_wordlist, transform_seed_with_progress, transform_batch, transform_batch_parallel};
+pub use crypto::{ScypherParams, TransformBuilder, ProgressSink, CancelFlag, SessionKey, BatchOptions};
+pub use bip39::{validate_seed_phrase_complete as validate_seed_phrase, verify_checksum, Wordlist};
 pub use security::{SecureString, SecureBytes};
+pub use format::{OutputFormat, register_format};
+#[cfg(feature = "async")]
+pub use async_api::{transform_seed_async, transform_batch_async};
 
 /// Versión de la biblioteca
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -53,18 +155,19 @@ pub fn scypher_transform(
     password: &str,
     iterations: Option<u32>,
     memory_cost: Option<u32>,
-) -> Result<String> {
-    let iterations = iterations.unwrap_or(5);
-    let memory_cost = memory_cost.unwrap_or(131072); // 128MB
-
-    // Validar entrada
-    bip39::validate_seed_phrase_complete(seed_phrase)?;
+) -> Result<Zeroizing<String>> {
+    let params = TransformBuilder::new()
+        .iterations(iterations.unwrap_or(crypto::params::DEFAULT_ITERATIONS))
+        .memory_cost(memory_cost.unwrap_or(crypto::params::DEFAULT_MEMORY_COST))
+        .build()?;
 
-    // Realizar transformación
-    transform_seed(seed_phrase, password, iterations, memory_cost)
+    // Validar entrada y realizar transformación (ChecksumPolicy::Verify por defecto)
+    transform_seed_with_params(seed_phrase, password, &params)
 }
 
 /// Configuración predeterminada para SCypher
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct SCypherConfig {
     pub iterations: u32,
     pub memory_cost: u32,
@@ -108,17 +211,23 @@ impl SCypherBuilder {
         self
     }
 
-    pub fn transform(&self, seed_phrase: &str, password: &str) -> Result<String> {
-        if self.config.verify_checksum {
-            bip39::validate_seed_phrase_complete(seed_phrase)?;
-        }
+    /// Convertir la configuración en parámetros validados
+    pub fn params(&self) -> Result<ScypherParams> {
+        let policy = if self.config.verify_checksum {
+            crypto::ChecksumPolicy::Verify
+        } else {
+            crypto::ChecksumPolicy::Skip
+        };
+
+        TransformBuilder::new()
+            .iterations(self.config.iterations)
+            .memory_cost(self.config.memory_cost)
+            .checksum_policy(policy)
+            .build()
+    }
 
-        transform_seed(
-            seed_phrase,
-            password,
-            self.config.iterations,
-            self.config.memory_cost,
-        )
+    pub fn transform(&self, seed_phrase: &str, password: &str) -> Result<Zeroizing<String>> {
+        transform_seed_with_params(seed_phrase, password, &self.params()?)
     }
 }
 
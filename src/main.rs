@@ -3,15 +3,13 @@
 use clap::{Arg, Command};
 use std::process;
 
-// Declaración de módulos
-mod crypto;
-mod bip39;
-mod cli;
-mod security;
-mod error;
+// Módulos de la biblioteca
+use scypher_rust::{bip39, cli, crypto, error, security};
+use scypher_rust::bip39::Language;
+use scypher_rust::crypto::{Argon2Version, Kdf};
 
 // Importaciones
-use crate::error::{SCypherError, Result};
+use crate::error::{IoOperation, SCypherError, Result};
 
 const VERSION: &str = "3.0";
 const DEFAULT_ITERATIONS: &str = "5";
@@ -152,7 +150,7 @@ fn main() {
     security::setup_security_cleanup();
 
     // Configurar CLI usando clap
-    let matches = Command::new("SCypher")
+    let command = Command::new("SCypher")
         .version(VERSION)
         .about("XOR-based BIP39 seed cipher with Argon2id key derivation")
         .long_about("SCypher provides secure, reversible transformation of BIP39 seed phrases \
@@ -190,6 +188,11 @@ fn main() {
             .value_name("FILE")
             .help("Save output to file (will add .txt extension if needed)")
             .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("no-stdout")
+            .long("no-stdout")
+            .help("Do not show the result on screen; it only goes to -o, --qr-out, --clipboard or --stego-out")
+            .conflicts_with("split-display")
+            .action(clap::ArgAction::SetTrue))
 
         // Parámetros de seguridad Argon2id
         .arg(Arg::new("iterations")
@@ -208,14 +211,175 @@ fn main() {
             .default_value(DEFAULT_MEMORY_COST)
             .value_parser(clap::value_parser!(u32)))
 
+        .arg(Arg::new("argon2-variant")
+            .long("argon2-variant")
+            .value_name("VARIANT")
+            .help("Argon2 variant, for outputs made by other implementations (default: argon2id). Decryption needs the same value")
+            .value_parser(Kdf::ALL.map(Kdf::name)))
+
+        .arg(Arg::new("argon2-version")
+            .long("argon2-version")
+            .value_name("VERSION")
+            .help("Argon2 version, for outputs made by older deployments (default: 0x13). Decryption needs the same value")
+            .value_parser(Argon2Version::ALL.map(Argon2Version::name)))
+
+        .arg(Arg::new("compat")
+            .long("compat")
+            .value_name("VERSION")
+            .help("Reproduce an older SCypher to recover its backups: 'v2' is the original Bash script (SHAKE-256, English wordlist; -i is its iteration count, default 1000). Not for new backups")
+            .value_parser(["v2"])
+            .conflicts_with_all(["memory", "lanes", "argon2-variant", "argon2-version", "salt-from-label", "low-memory",
+                                 "keyfile", "pepper-file", "key-mnemonic", "index", "raw-seed", "pad-24", "session",
+                                 "language", "preset"]))
+
+        .arg(Arg::new("preset")
+            .long("preset")
+            .value_name("NAME")
+            .help("Use the parameters saved on this device with 'preset save NAME' (options given here take precedence)")
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("salt-from-label")
+            .long("salt-from-label")
+            .value_name("LABEL")
+            .help("Derive the Argon2 salt from a memorable label (e.g. \"ledger-blue-2021\"), so each backup gets its own key without a stored salt. Decryption needs the same label")
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("low-memory")
+            .long("low-memory")
+            .help("Constrained devices: lower the Argon2 memory cost (-m is the upper limit) to fit the available RAM, never below 8MB. Prints the value needed to decrypt")
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("lanes")
+            .long("lanes")
+            .value_name("N|auto")
+            .help("Argon2id lanes (default: CPU count when encrypting, 1 with -d; 'auto' = CPU count). Decryption needs the value used to encrypt")
+            .value_parser(clap::value_parser!(String)))
+
         // Archivo de entrada
         .arg(Arg::new("input-file")
             .short('f')
             .long("file")
             .value_name("FILE")
-            .help("Read seed phrase from file instead of interactive input")
+            .help("Read seed phrase from file instead of interactive input (a GnuPG-encrypted file is decrypted with gpg in memory)")
+            .value_parser(clap::value_parser!(String)))
+
+        // Keyfiles adicionales mezclados en la derivación
+        .arg(Arg::new("keyfile")
+            .short('k')
+            .long("keyfile")
+            .value_name("FILE")
+            .help("Mix a keyfile into the key derivation (repeatable, order matters)")
+            .action(clap::ArgAction::Append)
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("pepper-file")
+            .long("pepper-file")
+            .value_name("FILE")
+            .help("Use a local secret file as the Argon2 pepper (yours, mode 0600, on a local filesystem). Decryption needs the same file")
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("split-display")
+            .long("split-display")
+            .help("Reveal the result in two halves with a clear step between them, never the whole phrase at once")
+            .conflicts_with("format")
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("key-mnemonic")
+            .long("key-mnemonic")
+            .value_name("FILE")
+            .num_args(0..=1)
+            .default_missing_value("-")
+            .help("XOR with a second BIP39 phrase instead of a password (no KDF); either phrase plus the result recovers the other. Reads FILE, or prompts when omitted")
+            .conflicts_with_all(["password-gpg", "session", "keyfile"])
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("index")
+            .long("index")
+            .value_name("N")
+            .help("Derive an independent key for output number N, so one password can protect several seeds. Decryption needs the same index")
+            .conflicts_with_all(["key-mnemonic", "session"])
+            .value_parser(clap::value_parser!(u32)))
+
+        .arg(Arg::new("raw-seed")
+            .long("raw-seed")
+            .help("Protect a raw 512-bit BIP39 seed (128 hex digits, as some hardware exports it) instead of a phrase; the result is another 512-bit seed in hex")
+            .conflicts_with_all(["key-mnemonic", "pad-24", "index", "session", "split-display", "format", "language"])
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("pad-24")
+            .long("pad-24")
+            .help("Encrypt a 12-21 word phrase into 24 words with authenticated random padding, so every backup looks the same; with -d, recover the original")
+            .conflicts_with_all(["key-mnemonic", "session", "index"])
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("session")
+            .long("session")
+            .help("Keep the derived key (locked in memory, wiped on exit) and offer to process more phrases with the same password")
+            .action(clap::ArgAction::SetTrue))
+
+        // Contraseña guardada en un archivo cifrado (dos personas: archivo y clave)
+        .arg(Arg::new("password-gpg")
+            .long("password-gpg")
+            .value_name("FILE")
+            .help("Read the password from the first line of a GnuPG-encrypted file (decrypted with gpg)")
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("key-check")
+            .long("key-check")
+            .value_name("CODE")
+            .help("With -d: the code from --show-key-check, so a mistyped password is reported before the slow key derivation")
+            .requires("decrypt")
+            .conflicts_with_all(["key-mnemonic", "compat"])
+            .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("show-key-check")
+            .long("show-key-check")
+            .help("When encrypting, also print a short key check code for --key-check, on stderr with --no-stdout (it lets a guesser skip most of the slow derivation: keep it apart from the backup)")
+            .conflicts_with_all(["decrypt", "key-mnemonic", "compat"])
+            .action(clap::ArgAction::SetTrue))
+
+        // Custodia con dos operadores: ninguno descifra solo
+        .arg(Arg::new("dual-control")
+            .long("dual-control")
+            .help("Two operators each enter their own password (screen cleared and a pause between them); both are needed to decrypt")
+            .conflicts_with_all(["password-gpg", "key-mnemonic"])
+            .action(clap::ArgAction::SetTrue))
+
+        // Formato de salida (plugins)
+        .arg(Arg::new("format")
+            .long("format")
+            .value_name("NAME")
+            .help("Output format for the result (default: words, see --list-formats)")
             .value_parser(clap::value_parser!(String)))
 
+        .arg(Arg::new("json-stdin")
+            .long("json-stdin")
+            .help("Read one JSON API request object from stdin, answer it on stdout and exit (no prompts, no TTY)")
+            .exclusive(true)
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("list-formats")
+            .long("list-formats")
+            .help("List available output formats, including scypher-format-* plugins in PATH")
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("run-vectors")
+            .long("run-vectors")
+            .help("Check this build against the built-in known-answer test vectors and exit")
+            .exclusive(true)
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("reset-failures")
+            .long("reset-failures")
+            .help("Clear this device's count of failed decryptions, and the delay it adds before each attempt, and exit")
+            .exclusive(true)
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("language")
+            .long("language")
+            .value_name("CODE")
+            .help("BIP39 wordlist of the input phrase (default: detected from the words)")
+            .value_parser(Language::ALL.map(Language::code)))
+
         // Verificación de checksum
         .arg(Arg::new("skip-checksum")
             .long("skip-checksum")
@@ -228,7 +392,402 @@ fn main() {
             .help("Silent mode - no prompts, reads from stdin (for scripting)")
             .action(clap::ArgAction::SetTrue))
 
-        .get_matches();
+        .arg(Arg::new("require-airgap")
+            .long("require-airgap")
+            .value_name("POLICY")
+            .num_args(0..=1)
+            .default_missing_value("require")
+            .value_parser(security::network::AirgapPolicy::NAMES)
+            .help("Refuse to run while any non-loopback network interface is up; 'warn' only prints a warning")
+            .action(clap::ArgAction::Set))
+
+        .arg(Arg::new("no-root")
+            .long("no-root")
+            .help("Refuse to run as root/Administrator; started through sudo, drop back to the invoking user before reading any secret")
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("ram-only")
+            .long("ram-only")
+            .help("Write nothing except explicit -o targets and confirm it at exit (automatic on live systems and read-only roots)")
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("require-removable")
+            .long("require-removable")
+            .help("Refuse to write output files anywhere but removable media (USB drive, SD card)")
+            .action(clap::ArgAction::SetTrue))
+
+        .arg(Arg::new("events")
+            .long("events")
+            .value_name("FORMAT")
+            .help("Also write progress, warnings and the final status to stderr as JSON lines, for GUI wrappers")
+            .value_parser(["json"]))
+
+        .arg(Arg::new("metrics")
+            .long("metrics")
+            .value_name("FILE")
+            .help("Append one JSON line per run with derivation time, memory and throughput to FILE (local only, no secrets)")
+            .value_parser(clap::value_parser!(String)))
+
+        .arg(Arg::new("plain-terminal")
+            .long("plain-terminal")
+            .help("No colors, progress bars or cursor control; line by line output for serial consoles and printers (default when TERM=dumb)")
+            .conflicts_with("split-display")
+            .action(clap::ArgAction::SetTrue))
+
+        // Subcomandos
+        .subcommand(Command::new("api")
+            .about("Read one JSON request from stdin and write one JSON response (versioned schema)"))
+
+        .subcommand(Command::new("serve")
+            .about("Serve transform/validate/generate over a local Unix-socket JSON-RPC interface")
+            .arg(Arg::new("socket")
+                .long("socket")
+                .value_name("PATH")
+                .help("Unix socket path to listen on")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("memory-budget")
+                .long("memory-budget")
+                .value_name("MB")
+                .help("Total memory for Argon2id derivations across all connections; requests wait for room (default: 1024)")
+                .default_value("1024")
+                .value_parser(clap::value_parser!(u64).range(8..))))
+
+        .subcommand(Command::new("batch")
+            .about("Transform many phrases with one password, in parallel within a memory budget")
+            .arg(Arg::new("input")
+                .value_name("FILE")
+                .help("One phrase per line, optionally prefixed with 'label:'")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Directory for the results (<label>.txt, never overwritten)")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("jobs")
+                .short('j')
+                .long("jobs")
+                .value_name("N")
+                .help("Maximum parallel derivations (default: one per core)")
+                .value_parser(clap::value_parser!(usize)))
+            .arg(Arg::new("memory-budget")
+                .long("memory-budget")
+                .value_name("MB")
+                .help("Total memory for concurrent Argon2id derivations (default: 1024)")
+                .default_value("1024")
+                .value_parser(clap::value_parser!(u64).range(8..)))
+            .arg(Arg::new("derive-once")
+                .long("derive-once")
+                .help("Run Argon2id once and derive each item's key from its label (reverse with the same labels)")
+                .action(clap::ArgAction::SetTrue))
+            .arg(Arg::new("resume")
+                .long("resume")
+                .help("Keep an encrypted checkpoint in the output directory and skip the items an interrupted run already finished")
+                .action(clap::ArgAction::SetTrue))
+            .arg(Arg::new("manifest")
+                .long("manifest")
+                .value_name("FILE")
+                .help("Also write a JSON inventory of the batch: label, salted fingerprint, output path, parameters and status (no phrases)")
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("fingerprint-salt")
+                .long("fingerprint-salt")
+                .value_name("TEXT")
+                .help("Salt for the manifest fingerprints (default: random, recorded in the manifest)")
+                .requires("manifest")
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("report")
+                .long("report")
+                .value_name("FILE")
+                .help("Also write a CSV with each row's validation result, status, processing time and error")
+                .value_parser(clap::value_parser!(String))))
+
+        .subcommand(Command::new("watch")
+            .about("Process job files dropped into a directory with a key unlocked once, moving them to done/ or failed/")
+            .arg(Arg::new("dir")
+                .value_name("DIR")
+                .help("Queue directory; each *.json job names an input, an output and optional params")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("poll-interval")
+                .long("poll-interval")
+                .value_name("SECONDS")
+                .help("Seconds between directory scans (default: 2)")
+                .value_parser(clap::value_parser!(u64).range(1..)))
+            .arg(Arg::new("once")
+                .long("once")
+                .help("Process the jobs already in the directory and exit")
+                .action(clap::ArgAction::SetTrue)))
+
+        .subcommand(Command::new("file")
+            .about("Encrypt any file or raw secret in authenticated chunks (ChaCha20-Poly1305 with an Argon2id key), with memory use independent of its size")
+            .arg(Arg::new("input")
+                .value_name("INPUT")
+                .help("File to encrypt, or with -d the encrypted file")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("output")
+                .value_name("OUTPUT")
+                .help("File to create (never overwritten; removed if anything fails)")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("decrypt")
+                .short('d')
+                .long("decrypt")
+                .help("Decrypt INPUT (parameters are read from its header; give the same -k keyfiles)")
+                .action(clap::ArgAction::SetTrue)))
+
+        .subcommand(Command::new("generate")
+            .about("Generate a new random BIP39 phrase (optionally mixing keyboard timing into the system RNG)")
+            .arg(Arg::new("words")
+                .long("words")
+                .value_name("N")
+                .help("Number of words (12, 15, 18, 21 or 24)")
+                .default_value("24")
+                .value_parser(clap::value_parser!(usize)))
+            .args(keyboard_entropy_args()))
+
+        .subcommand(Command::new("fingerprint")
+            .about("Print a short, salted, non-reversible identifier (fp1...) of a phrase that can be recorded openly")
+            .arg(Arg::new("salt")
+                .long("salt")
+                .value_name("TEXT")
+                .help("Salt mixed into the identifier, so records made with different salts cannot be linked")
+                .value_parser(clap::value_parser!(String))))
+
+        .subcommand(Command::new("rotate")
+            .about("Change the password (and parameters) of an encrypted phrase in one step, never showing the decrypted phrase")
+            .arg(Arg::new("new-iterations")
+                .long("new-iterations")
+                .value_name("NUMBER")
+                .help("Argon2id iterations for the new encryption (default: same as -i)")
+                .value_parser(clap::value_parser!(u32)))
+            .arg(Arg::new("new-memory")
+                .long("new-memory")
+                .value_name("KB")
+                .help("Argon2id memory cost in KB for the new encryption (default: same as -m)")
+                .value_parser(clap::value_parser!(u32)))
+            .arg(Arg::new("new-lanes")
+                .long("new-lanes")
+                .value_name("N|auto")
+                .help("Argon2id lanes for the new encryption (default: same as --lanes)")
+                .value_parser(clap::value_parser!(String))))
+
+        .subcommand(Command::new("inspect")
+            .about("Show what an encrypted container or backup file is (format, KDF, parameters, salt fingerprint, payload, dates) without any password")
+            .arg(Arg::new("file")
+                .value_name("FILE")
+                .help("age or OpenPGP container, SCypher backup QR parts, stego image or phrase file")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("json")
+                .long("json")
+                .help("Print the result as JSON")
+                .action(clap::ArgAction::SetTrue)))
+
+        .subcommand(Command::new("capabilities")
+            .about("List supported formats, KDFs, ciphers, languages, features and exit codes")
+            .arg(Arg::new("json")
+                .long("json")
+                .help("Print the listing as JSON (same fields as the API 'capabilities' operation)")
+                .action(clap::ArgAction::SetTrue)))
+
+        .subcommand(Command::new("preset")
+            .about("Save, list, show or remove named parameter presets for this device (integrity-protected)")
+            .subcommand_required(true)
+            .subcommand(Command::new("save")
+                .about("Save the -i, -m, --lanes, --argon2-variant, --argon2-version and --format values given on this command line")
+                .arg(Arg::new("name")
+                    .value_name("NAME")
+                    .required(true)
+                    .value_parser(clap::value_parser!(String))))
+            .subcommand(Command::new("list")
+                .about("List the saved presets"))
+            .subcommand(Command::new("show")
+                .about("Verify a preset and print its options")
+                .arg(Arg::new("name")
+                    .value_name("NAME")
+                    .required(true)
+                    .value_parser(clap::value_parser!(String))))
+            .subcommand(Command::new("remove")
+                .about("Delete a preset")
+                .arg(Arg::new("name")
+                    .value_name("NAME")
+                    .required(true)
+                    .value_parser(clap::value_parser!(String)))))
+
+        .subcommand(Command::new("bench")
+            .about("Measure phrases/second of validation, formats and post-KDF transform (JSON report)")
+            .hide(true)
+            .arg(Arg::new("duration-ms")
+                .long("duration-ms")
+                .value_name("MS")
+                .help("Time spent on each measurement")
+                .default_value("1000")
+                .value_parser(clap::value_parser!(u64).range(1..))));
+
+    #[cfg(feature = "wallet")]
+    let command = command
+        .arg(Arg::new("eth-keystore")
+            .long("eth-keystore")
+            .value_name("FILE")
+            .help("After processing, export the result's Ethereum account as a keystore V3 JSON file")
+            .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("bip38")
+            .long("bip38")
+            .value_name("FILE")
+            .help("After processing, export one Bitcoin private key of the result protected with BIP38")
+            .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("descriptors")
+            .long("descriptors")
+            .value_name("FILE")
+            .help("After processing, save Bitcoin Core watch-only descriptors (wpkh, tr) of the result for importdescriptors")
+            .value_parser(clap::value_parser!(String)))
+        .group(clap::ArgGroup::new("export")
+            .args(["eth-keystore", "bip38", "descriptors"])
+            .multiple(true))
+        .arg(Arg::new("cardano")
+            .long("cardano")
+            .help("After processing, show the result's Cardano (Icarus) account key and first address to confirm the wallet")
+            .action(clap::ArgAction::SetTrue))
+        .arg(Arg::new("xprv")
+            .long("xprv")
+            .help("Protect a BIP32 extended private key (xprv/yprv/zprv/tprv...) instead of a phrase; the result is another valid key of the same type")
+            .conflicts_with_all(["export", "cardano", "key-mnemonic", "pad-24", "index", "session", "split-display", "format", "language", "raw-seed", "compat"])
+            .action(clap::ArgAction::SetTrue))
+        .arg(Arg::new("derivation-path")
+            .long("derivation-path")
+            .value_name("PATH")
+            .help("BIP32 path of the exported key (default: m/44'/60'/0'/0/0 Ethereum, m/44'/0'/0'/0/0 BIP38; descriptors always use the standard account paths)")
+            .requires("export")
+            .value_parser(clap::value_parser!(String)))
+        .subcommand(Command::new("verify-backup")
+            .about("Backup drill: decrypt a backup copy and only report whether it matches the stored master fingerprint")
+            .arg(Arg::new("fingerprint")
+                .long("fingerprint")
+                .value_name("HEX")
+                .help("BIP32 master fingerprint recorded when the backup was made (8 hex digits, as wallets show it)")
+                .required(true)
+                .value_parser(clap::value_parser!(String)))
+            .arg(Arg::new("salt")
+                .long("salt")
+                .value_name("TEXT")
+                .help("Salt used when the fp1 phrase fingerprint was made")
+                .value_parser(clap::value_parser!(String))));
+
+    #[cfg(feature = "hardware")]
+    let command = command
+        .arg(Arg::new("hwi-check")
+            .long("hwi-check")
+            .value_name("HWI")
+            .num_args(0..=1)
+            .default_missing_value("hwi")
+            .help("After processing, compare the result with the connected hardware wallet through HWI (fingerprint and BIP84 xpub)")
+            .conflicts_with("xprv")
+            .value_parser(clap::value_parser!(String)));
+
+    #[cfg(feature = "interactive")]
+    let command = command
+        .subcommand(Command::new("shell")
+            .about("Interactive session: unlock once, then transform/validate many phrases")
+            .arg(Arg::new("idle-timeout")
+                .long("idle-timeout")
+                .value_name("SECONDS")
+                .help("Wipe the cached key after this many seconds without input")
+                .default_value("300")
+                .value_parser(clap::value_parser!(u64).range(1..))))
+        .subcommand(Command::new("preview")
+            .about("Training demo with throwaway data: what encrypted output and wrong-password results look like")
+            .arg(Arg::new("words")
+                .long("words")
+                .value_name("N")
+                .help("Length of the test phrase (12, 15, 18, 21 or 24)")
+                .default_value("12")
+                .value_parser(clap::value_parser!(usize))));
+
+    #[cfg(feature = "armor")]
+    let command = command
+        .arg(Arg::new("armor")
+            .long("armor")
+            .value_name("CONTAINER")
+            .help("Wrap the output in a passphrase-encrypted container with the recovery parameters (age, pgp)")
+            .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("password-age")
+            .long("password-age")
+            .value_name("FILE")
+            .help("Read the password from the first line of a passphrase-encrypted age file")
+            .conflicts_with_all(["password-gpg", "key-mnemonic", "dual-control"])
+            .value_parser(clap::value_parser!(String)));
+
+    #[cfg(feature = "qr")]
+    let command = command
+        .arg(Arg::new("ur")
+            .long("ur")
+            .help("Also show the result as a Blockchain Commons UR QR code (animated when it needs several parts)")
+            .action(clap::ArgAction::SetTrue))
+        .arg(Arg::new("qr-out")
+            .long("qr-out")
+            .value_name("FILE")
+            .help("Also save the result as a QR code PNG (the same UR as --ur; an animated PNG when it needs several parts)")
+            .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("qr-split")
+            .long("qr-split")
+            .value_name("N")
+            .help(format!("Split the --qr-out QR into N XOR shares (2-{}), one PNG each (FILE-1ofN.png...); all of them are needed to rebuild it. Other outputs still show the whole result", scypher_rust::ur::MAX_SHARES))
+            .requires("qr-out")
+            .conflicts_with("ur")
+            .value_parser(clap::value_parser!(u64).range(2..=scypher_rust::ur::MAX_SHARES as u64)));
+
+    #[cfg(feature = "clipboard")]
+    let command = command
+        .arg(Arg::new("clipboard")
+            .long("clipboard")
+            .help(format!("Also copy the result to the clipboard; it is cleared after {} s", cli::clipboard::CLEAR_AFTER.as_secs()))
+            .action(clap::ArgAction::SetTrue));
+
+    #[cfg(feature = "stego")]
+    let command = command
+        .arg(Arg::new("stego-carrier")
+            .long("stego-carrier")
+            .value_name("IMAGE")
+            .help("PNG or BMP image to hide the result in (see --stego-out); read it back with -f")
+            .requires("stego-out")
+            .value_parser(clap::value_parser!(String)))
+        .arg(Arg::new("stego-out")
+            .long("stego-out")
+            .value_name("FILE")
+            .help("Save a copy of --stego-carrier with the result hidden in its least significant bits")
+            .requires("stego-carrier")
+            .value_parser(clap::value_parser!(String)));
+
+    #[cfg(feature = "grpc")]
+    let command = command.subcommand(grpc_command());
+
+    let matches = command.clone().get_matches();
+
+    // Preset del equipo: sus valores entran como si se hubieran escrito, salvo los que ya da la orden
+    let matches = match matches.get_one::<String>("preset") {
+        Some(name) => match cli::preset::config_dir().and_then(|config| cli::preset::load_from(&config, name)) {
+            Ok(preset) => {
+                eprintln!("Using preset '{}': {}", name, preset.describe());
+                let args = cli::preset::apply(&command, &matches, &preset);
+                command.get_matches_from(args)
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(e.exit_code());
+            }
+        },
+        None => matches,
+    };
+
+    // Consolas serie e impresoras: nada de ANSI desde el primer mensaje
+    cli::display::set_plain_terminal(
+        matches.get_flag("plain-terminal") || std::env::var("TERM").is_ok_and(|term| term == "dumb"));
+    if matches.get_one::<String>("events").is_some() {
+        cli::events::enable();
+    }
 
     // Verificar argumentos especiales antes del procesamiento principal
     if matches.get_flag("license") {
@@ -241,62 +800,583 @@ fn main() {
         return;
     }
 
+    if matches.get_flag("list-formats") {
+        list_formats();
+        return;
+    }
+
+    if matches.get_flag("run-vectors") {
+        if let Err(e) = run_vectors() {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
 
+    if matches.get_flag("reset-failures") {
+        if let Err(e) = cli::backoff::reset() {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
 
-    // Ejecutar la aplicación y manejar errores
-    if let Err(e) = run(&matches) {
-        eprintln!("Error: {}", e);
+    // Sistema en vivo o raíz de solo lectura: nada se escribe sin pedirlo
+    if matches.get_flag("ram-only") {
+        security::persistence::enable_ram_only();
+    } else if security::persistence::detect_live_system() {
+        security::persistence::enable_ram_only();
+        eprintln!("Live system or read-only root detected: RAM-only mode (only -o targets are written)");
+    }
 
-        // Diferentes códigos de salida para diferentes tipos de error
-        let exit_code = match e {
-            SCypherError::InvalidSeedPhrase |
-            SCypherError::InvalidWordCount(_) |
-            SCypherError::InvalidBip39Word(_) |
-            SCypherError::InvalidChecksum => 2,           // Errores de validación
+    // Sin privilegios elevados: bajar al usuario de sudo o no seguir
+    if matches.get_flag("no-root") {
+        match security::process::drop_root_privileges() {
+            Ok(Some(uid)) => eprintln!("Dropped root privileges: continuing as uid {}", uid),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(e.exit_code());
+            }
+        }
+    }
 
-            SCypherError::InvalidPassword |
-            SCypherError::PasswordMismatch => 3,          // Errores de contraseña
+    // Política "solo sin red": se comprueba antes de leer ningún secreto
+    if let Some(policy) = matches.get_one::<String>("require-airgap") {
+        let policy = security::network::AirgapPolicy::from_name(policy).unwrap();
+        if let Err(e) = security::network::check_airgap(policy) {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+    }
 
-            SCypherError::IoError(_) |
-            SCypherError::FileError(_) => 4,              // Errores de E/O
+    // Presets de parámetros del equipo
+    if let Some(("preset", preset_matches)) = matches.subcommand() {
+        if let Err(e) = cli::preset::run_preset_command(preset_matches, &matches) {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
 
-            SCypherError::CryptoError(_) |
-            SCypherError::KeyDerivationFailed => 5,       // Errores criptográficos
+    // Solo medios extraíbles para los resultados (los presets no lo son)
+    if matches.get_flag("require-removable") {
+        security::storage::require_removable();
+    }
 
-            _ => 1,                                       // Error general
-        };
+    // Modo API JSON: la respuesta (incluidos los errores) siempre va a stdout
+    if let Some(("api", _)) = matches.subcommand() {
+        let exit_code = run_api_mode(false);
+        security::secure_cleanup();
+        process::exit(exit_code);
+    }
 
+    // Petición única sin prompts ni TTY: un objeto JSON por stdin, una respuesta por stdout
+    if matches.get_flag("json-stdin") {
+        let exit_code = run_api_mode(true);
+        security::secure_cleanup();
         process::exit(exit_code);
     }
 
+    // Daemon JSON-RPC: atiende peticiones hasta que el proceso termine
+    if let Some(("serve", serve_matches)) = matches.subcommand() {
+        let socket = serve_matches.get_one::<String>("socket").unwrap();
+        let memory_budget_kb = *serve_matches.get_one::<u64>("memory-budget").unwrap() * 1024;
+        if let Err(e) = cli::serve::run_server(socket, memory_budget_kb) {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Modo lote (usa -i/-m/-k/--skip-checksum globales)
+    if let Some(("batch", batch_matches)) = matches.subcommand() {
+        let result = params_from_matches(&matches).and_then(|params| {
+            let options = crypto::BatchOptions {
+                threads: batch_matches.get_one::<usize>("jobs").copied(),
+                memory_budget_kb: *batch_matches.get_one::<u64>("memory-budget").unwrap() * 1024,
+            };
+            let summary = cli::batch::run_batch(
+                batch_matches.get_one::<String>("input").unwrap(),
+                batch_matches.get_one::<String>("output-dir").unwrap(),
+                &params,
+                &options,
+                cli::batch::BatchMode {
+                    derive_once: batch_matches.get_flag("derive-once"),
+                    resume: batch_matches.get_flag("resume"),
+                },
+                batch_matches.get_one::<String>("manifest").map(|path| cli::batch::ManifestOptions {
+                    path,
+                    salt: batch_matches.get_one::<String>("fingerprint-salt").map(String::as_str),
+                }),
+                batch_matches.get_one::<String>("report").map(String::as_str),
+            )?;
+            cli::metrics::record(matches.get_one::<String>("metrics"), || {
+                cli::metrics::RunMetrics::new("batch", &params, summary.succeeded + summary.failed, summary.duration)
+                    .with_derivations(summary.derivations, summary.concurrency)
+            });
+            Ok(summary)
+        });
+        security::secure_cleanup();
+        match result {
+            Ok(summary) => {
+                cli::events::emit(&cli::events::Event::Done { exit_code: summary.exit_code });
+                process::exit(summary.exit_code)
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                cli::events::emit(&cli::events::Event::error(&e));
+                process::exit(e.exit_code());
+            }
+        }
+    }
+
+    // Cola de trabajos en un directorio (usa -i/-m/-k/--skip-checksum globales)
+    if let Some(("watch", watch_matches)) = matches.subcommand() {
+        let poll_interval = watch_matches.get_one::<u64>("poll-interval")
+            .map_or(cli::watch::DEFAULT_POLL_INTERVAL, |seconds| std::time::Duration::from_secs(*seconds));
+        let result = params_from_matches(&matches).and_then(|params| cli::watch::run_watch(
+            watch_matches.get_one::<String>("dir").unwrap(),
+            params,
+            poll_interval,
+            watch_matches.get_flag("once"),
+        ));
+        security::secure_cleanup();
+        match result {
+            Ok(summary) => process::exit(summary.exit_code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(e.exit_code());
+            }
+        }
+    }
+
+    // Archivos arbitrarios por bloques (usa -i/-m/-k globales)
+    if let Some(("file", file_matches)) = matches.subcommand() {
+        let decrypt = file_matches.get_flag("decrypt");
+        let output = file_matches.get_one::<String>("output").unwrap();
+        let params = if decrypt { params_from_matches(&matches) } else { encryption_params_from_matches(&matches) };
+        let result = params.and_then(|params| cli::file::run_file(
+            file_matches.get_one::<String>("input").unwrap(),
+            output,
+            decrypt,
+            &params,
+        ));
+        security::secure_cleanup();
+        match result {
+            Ok(written) => println!("✓ {} ({} bytes) {}", output, written, if decrypt { "decrypted" } else { "encrypted" }),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(e.exit_code());
+            }
+        }
+        return;
+    }
+
+    // Sesión interactiva con clave en caché (usa -i/-m/-k/--skip-checksum globales)
+    #[cfg(feature = "interactive")]
+    if let Some(("shell", shell_matches)) = matches.subcommand() {
+        let idle_timeout = std::time::Duration::from_secs(*shell_matches.get_one::<u64>("idle-timeout").unwrap());
+        let result = params_from_matches(&matches)
+            .and_then(|params| cli::shell::run_shell(params, idle_timeout));
+        security::secure_cleanup();
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Demostración educativa con datos de prueba (usa -i/-m/-k globales)
+    #[cfg(feature = "interactive")]
+    if let Some(("preview", preview_matches)) = matches.subcommand() {
+        let words = *preview_matches.get_one::<usize>("words").unwrap();
+        let result = params_from_matches(&matches)
+            .and_then(|params| cli::preview::run_preview(&params, words));
+        security::secure_cleanup();
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Frase nueva (usa -o global)
+    if let Some(("generate", generate_matches)) = matches.subcommand() {
+        let result = output_router(&matches, false)
+            .and_then(|router| generate_phrase(generate_matches, &router));
+        security::secure_cleanup();
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Identificador de una frase (usa -f/--language globales)
+    if let Some(("fingerprint", fingerprint_matches)) = matches.subcommand() {
+        let result = show_fingerprint(&matches, fingerprint_matches.get_one::<String>("salt").map(String::as_str));
+        security::secure_cleanup();
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Cambio de contraseña en un paso: -f/-i/-m/-k/--lanes globales son los antiguos, -o la salida
+    if let Some(("rotate", rotate_matches)) = matches.subcommand() {
+        let result = params_from_matches(&matches)
+            .and_then(|old_params| {
+                let new_params = rotated_params(rotate_matches, &old_params)?;
+                let router = output_router(&matches, false)?;
+                let input_file = matches.get_one::<String>("input-file").map(String::as_str);
+                let rotated = cli::rotate::run_rotate(&old_params, &new_params, input_file)?;
+                deliver_phrase(&router, &rotated, Some(&new_params))
+            });
+        security::secure_cleanup();
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Capacidades de esta compilación, para que otras herramientas no lean --help
+    if let Some(("capabilities", capabilities_matches)) = matches.subcommand() {
+        show_capabilities(capabilities_matches.get_flag("json"));
+        return;
+    }
+
+    // Qué es un archivo de copia, sin pedir contraseñas
+    if let Some(("inspect", inspect_matches)) = matches.subcommand() {
+        let file = inspect_matches.get_one::<String>("file").unwrap();
+        if let Err(e) = cli::inspect::run_inspect(file, inspect_matches.get_flag("json")) {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Informe de rendimiento en JSON para detectar regresiones
+    if let Some(("bench", bench_matches)) = matches.subcommand() {
+        let duration = std::time::Duration::from_millis(*bench_matches.get_one::<u64>("duration-ms").unwrap());
+        match cli::bench::run_bench(duration) {
+            Ok(report) => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(e.exit_code());
+            }
+        }
+        return;
+    }
+
+    // Simulacro de recuperación (usa -f/-i/-m/-k globales); nunca muestra la frase
+    #[cfg(feature = "wallet")]
+    if let Some(("verify-backup", verify_matches)) = matches.subcommand() {
+        let salt = verify_matches.get_one::<String>("salt").map(String::as_str);
+        let result = cli::verify::ExpectedFingerprint::parse(verify_matches.get_one::<String>("fingerprint").unwrap(), salt)
+            .and_then(|expected| {
+                let params = params_from_matches(&matches)?;
+                let input_file = matches.get_one::<String>("input-file").map(String::as_str);
+                cli::verify::verify_backup(&params, input_file, &expected)
+            });
+        security::secure_cleanup();
+        match result {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(e.exit_code());
+            }
+        }
+    }
+
+    // Servidor gRPC con mTLS
+    #[cfg(feature = "grpc")]
+    if let Some(("grpc", grpc_matches)) = matches.subcommand() {
+        if let Err(e) = run_grpc_mode(grpc_matches) {
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
+        return;
+    }
+
+    // Ejecutar la aplicación y manejar errores
+    if let Err(e) = run(&matches) {
+        eprintln!("Error: {}", e);
+        cli::events::emit(&cli::events::Event::error(&e));
+        security::secure_cleanup();
+
+        // El código de salida depende de la categoría del error:
+        // 2 validación, 3 contraseña, 4 E/O, 5 criptografía, 1 general
+        process::exit(e.exit_code());
+    }
+
+    cli::events::emit(&cli::events::Event::Done { exit_code: 0 });
+
     // Limpieza segura antes de salir
     security::secure_cleanup();
 }
 
-/// Función helper para verificar si clap::ArgMatches tiene argumentos presentes
-trait ArgMatchesExt {
-    fn args_present(&self) -> bool;
+/// Ejecutar una petición de la API JSON leída de stdin
+///
+/// Con `one_object` se lee solo el primer objeto JSON (`--json-stdin`), sin
+/// esperar al cierre de stdin; si no, toda la entrada (`api`).
+/// Retorna el código de salida correspondiente a la respuesta
+fn run_api_mode(one_object: bool) -> i32 {
+    use std::io::{Read, Write};
+
+    let input = if one_object {
+        scypher_rust::protocol::read_json_object(std::io::stdin().lock())
+    } else {
+        let mut input = zeroize::Zeroizing::new(String::new());
+        std::io::stdin().read_to_string(&mut input).map(|_| input).map_err(SCypherError::from)
+    };
+    let response = match input {
+        Ok(input) => scypher_rust::protocol::handle_json(&input),
+        Err(e) => scypher_rust::protocol::handle_json_error(e),
+    };
+
+    let json = response.to_json();
+    let mut stdout = std::io::stdout();
+    if writeln!(stdout, "{}", json.as_str()).and_then(|_| stdout.flush()).is_err() {
+        return 4;
+    }
+
+    response.error.as_ref().map_or(0, SCypherError::exit_code)
+}
+
+/// Mostrar los formatos de salida disponibles
+fn list_formats() {
+    println!("Available output formats:");
+    for format in scypher_rust::format::available_formats() {
+        println!("  {:<12} {}", format.name(), format.description());
+    }
+    #[cfg(feature = "formats-extra")]
+    println!("\nPlugins: executables named {}<name> in PATH (args: encode|decode, data on stdin/stdout)",
+             scypher_rust::format::EXTERNAL_PREFIX);
+}
+
+/// Validar esta compilación contra los vectores de respuesta conocida
+fn run_vectors() -> Result<()> {
+    let vectors = crypto::vectors::known_answers()?;
+    println!("Known-answer vectors (sha256 {})", crypto::vectors::known_answers_digest());
+
+    let generation = crypto::vectors::generation_answers();
+    let checks = vectors.iter().map(|vector| (vector.name.as_str(), vector.verify()))
+        .chain(generation.iter().map(|vector| (vector.name, vector.verify())));
+
+    let total = vectors.len() + generation.len();
+    let mut failed = 0;
+    for (name, outcome) in checks {
+        match outcome {
+            Ok(()) => println!("  {}PASS{} {}", cli::colors::SUCCESS, cli::colors::RESET, name),
+            Err(e) => {
+                failed += 1;
+                println!("  {}FAIL{} {}: {}", cli::colors::ERROR, cli::colors::RESET, name, e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(SCypherError::crypto(format!(
+            "{} of {} known-answer vectors failed: this build does not reproduce earlier results", failed, total)));
+    }
+    println!("{}✓ All {} vectors passed{}", cli::colors::SUCCESS, total, cli::colors::RESET);
+    Ok(())
+}
+
+/// Mostrar las capacidades de la biblioteca y los códigos de salida propios del CLI
+fn show_capabilities(json: bool) {
+    let mut capabilities = scypher_rust::protocol::capabilities();
+    capabilities.exit_codes.extend([
+        scypher_rust::protocol::ExitCodeInfo { code: 130, name: "interrupted", description: "terminated by Ctrl-C" },
+        scypher_rust::protocol::ExitCodeInfo { code: 131, name: "panic_wipe", description: "panic-wipe key (Ctrl-X twice)" },
+    ]);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&capabilities).unwrap());
+        return;
+    }
+
+    // Los nombres son los mismos que en JSON
+    fn names<T: serde::Serialize>(values: &[T]) -> String {
+        let names: Vec<String> = values.iter()
+            .filter_map(|value| serde_json::to_value(value).ok())
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect();
+        names.join(", ")
+    }
+
+    println!("SCypher v{} (library {}, API schema {})", VERSION, capabilities.library, capabilities.schema);
+    let formats: Vec<&str> = capabilities.formats.iter().map(|format| format.name.as_str()).collect();
+    println!("Formats:    {}", formats.join(", "));
+    println!("KDFs:       {}", names(&capabilities.kdfs));
+    println!("Argon2:     {}", names(&capabilities.argon2_versions));
+    println!("Ciphers:    {}", names(&capabilities.ciphers));
+    println!("Languages:  {}", capabilities.languages.join(", "));
+    println!("Containers: {}", capabilities.containers.join(", "));
+    println!("Features:   {}", capabilities.features.join(", "));
+    println!("\nExit codes:");
+    for exit_code in &capabilities.exit_codes {
+        println!("  {:>3}  {:<12} {}", exit_code.code, exit_code.name, exit_code.description);
+    }
+}
+
+/// Definición del subcomando `grpc`
+/// Opciones de `generate` para mezclar tiempos de teclado (solo con `interactive`)
+fn keyboard_entropy_args() -> Vec<Arg> {
+    #[cfg(feature = "interactive")]
+    {
+        vec![
+            Arg::new("keyboard-entropy")
+                .long("keyboard-entropy")
+                .help("Also collect inter-keystroke timing and mix it with the system RNG through SHA-256")
+                .action(clap::ArgAction::SetTrue),
+            Arg::new("keystrokes")
+                .long("keystrokes")
+                .value_name("N")
+                .help("Keystrokes to collect with --keyboard-entropy")
+                .requires("keyboard-entropy")
+                .default_value("100")
+                .value_parser(clap::value_parser!(u64).range(16..10_000)),
+        ]
+    }
+
+    #[cfg(not(feature = "interactive"))]
+    {
+        Vec::new()
+    }
 }
 
-impl ArgMatchesExt for clap::ArgMatches {
-    fn args_present(&self) -> bool {
-        // Verificar si algún argumento fue proporcionado
-        self.get_flag("decrypt") ||
-        self.get_one::<String>("output").is_some() ||
-        self.get_one::<String>("input-file").is_some() ||
-        self.get_flag("skip-checksum") ||
-        *self.get_one::<u32>("iterations").unwrap() != 5 ||  // Default value
-        *self.get_one::<u32>("memory").unwrap() != 131072    // Default value
+#[cfg(feature = "grpc")]
+fn grpc_command() -> Command {
+    let pem_arg = |name: &'static str, help: &'static str| {
+        Arg::new(name)
+            .long(name)
+            .value_name("PEM")
+            .help(help)
+            .required_unless_present("insecure-plaintext")
+            .value_parser(clap::value_parser!(String))
+    };
+
+    Command::new("grpc")
+        .about("Serve batch transform and validation over gRPC with mutual TLS")
+        .arg(Arg::new("listen")
+            .long("listen")
+            .value_name("ADDR")
+            .help("Address to listen on")
+            .default_value("127.0.0.1:50051")
+            .value_parser(clap::value_parser!(std::net::SocketAddr)))
+        .arg(pem_arg("cert", "Server certificate (PEM)"))
+        .arg(pem_arg("key", "Server private key (PEM)"))
+        .arg(pem_arg("client-ca", "CA that signs accepted client certificates (PEM)"))
+        .arg(Arg::new("insecure-plaintext")
+            .long("insecure-plaintext")
+            .help("Serve without TLS (only behind a TLS-terminating proxy or for local testing)")
+            .conflicts_with_all(["cert", "key", "client-ca"])
+            .action(clap::ArgAction::SetTrue))
+}
+
+/// Levantar el servidor gRPC hasta que el proceso termine
+#[cfg(feature = "grpc")]
+fn run_grpc_mode(matches: &clap::ArgMatches) -> Result<()> {
+    use scypher_rust::grpc::TlsConfig;
+
+    let read_pem = |name: &str| -> Result<Vec<u8>> {
+        let path = matches.get_one::<String>(name).unwrap();
+        std::fs::read(path).map_err(|e| SCypherError::file_caused_by(path, format!("Cannot read {}", name), e))
+    };
+
+    let tls = if matches.get_flag("insecure-plaintext") {
+        eprintln!("Warning: gRPC server running WITHOUT TLS");
+        None
+    } else {
+        Some(TlsConfig {
+            cert: read_pem("cert")?,
+            key: read_pem("key")?,
+            client_ca: read_pem("client-ca")?,
+        })
+    };
+
+    let addr = *matches.get_one::<std::net::SocketAddr>("listen").unwrap();
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    eprintln!("Listening on {} (gRPC scypher.v1.Scypher{})", addr, if tls.is_some() { ", mTLS" } else { "" });
+    runtime.block_on(scypher_rust::grpc::serve(addr, tls))
+}
+
+/// Función helper para verificar si clap::ArgMatches tiene argumentos presentes
+#[cfg(feature = "interactive")]
+fn args_present(matches: &clap::ArgMatches) -> bool {
+    #[cfg(feature = "wallet")]
+    if matches.contains_id("export") || matches.get_flag("cardano") || matches.get_flag("xprv") {
+        return true;
+    }
+
+    #[cfg(feature = "hardware")]
+    if matches.contains_id("hwi-check") {
+        return true;
     }
+
+    #[cfg(feature = "armor")]
+    if matches.get_one::<String>("armor").is_some() || matches.get_one::<String>("password-age").is_some() {
+        return true;
+    }
+
+    #[cfg(feature = "qr")]
+    if matches.get_flag("ur") || matches.get_one::<String>("qr-out").is_some() {
+        return true;
+    }
+
+    #[cfg(feature = "clipboard")]
+    if matches.get_flag("clipboard") {
+        return true;
+    }
+
+    #[cfg(feature = "stego")]
+    if matches.get_one::<String>("stego-out").is_some() {
+        return true;
+    }
+
+    // Verificar si algún argumento fue proporcionado
+    matches.get_flag("decrypt") ||
+    matches.get_one::<String>("output").is_some() ||
+    matches.get_flag("no-stdout") ||
+    matches.get_one::<String>("input-file").is_some() ||
+    matches.get_many::<String>("keyfile").is_some() ||
+    matches.get_one::<String>("pepper-file").is_some() ||
+    matches.get_one::<String>("password-gpg").is_some() ||
+    matches.get_flag("dual-control") ||
+    matches.get_flag("session") ||
+    matches.contains_id("key-mnemonic") ||
+    matches.get_one::<u32>("index").is_some() ||
+    matches.get_flag("pad-24") ||
+    matches.get_flag("raw-seed") ||
+    matches.get_flag("split-display") ||
+    matches.get_one::<String>("format").is_some() ||
+    matches.get_one::<String>("lanes").is_some() ||
+    matches.get_one::<String>("argon2-variant").is_some() ||
+    matches.get_flag("low-memory") ||
+    matches.get_one::<String>("salt-from-label").is_some() ||
+    matches.get_one::<String>("argon2-version").is_some() ||
+    matches.get_one::<String>("compat").is_some() ||
+    matches.get_flag("show-key-check") ||
+    matches.get_one::<String>("language").is_some() ||
+    matches.get_flag("skip-checksum") ||
+    matches.get_one::<String>("metrics").is_some() ||
+    matches.get_one::<String>("events").is_some() ||
+    *matches.get_one::<u32>("iterations").unwrap() != 5 ||  // Default value
+    *matches.get_one::<u32>("memory").unwrap() != 131072    // Default value
 }
 
 /// Función principal que coordina toda la operación
 fn run(matches: &clap::ArgMatches) -> Result<()> {
-    // Verificar si hay argumentos CLI (modo no-interactivo)
-    let has_cli_args = matches.args_present();
-
-    // Si no hay argumentos CLI, ejecutar modo interactivo con menús
-    if !has_cli_args {
+    // Si no hay argumentos CLI, ejecutar modo interactivo con menús; sin la
+    // feature `interactive` se sigue siempre por el modo CLI (stdin/stdout)
+    #[cfg(feature = "interactive")]
+    if !args_present(matches) {
         return run_interactive_mode();
     }
 
@@ -305,7 +1385,11 @@ fn run(matches: &clap::ArgMatches) -> Result<()> {
 }
 
 /// Ejecutar modo interactivo con sistema de menús
+#[cfg(feature = "interactive")]
 fn run_interactive_mode() -> Result<()> {
+    // Ctrl-X dos veces borra la pantalla y sale en cualquier momento
+    cli::panic_wipe::arm();
+
     loop {
         // Mostrar menú y obtener estado
         let menu_state = cli::run_interactive_menu()?;
@@ -327,10 +1411,12 @@ fn run_interactive_mode() -> Result<()> {
 }
 
 /// Ejecutar procesamiento interactivo (desde menú)
+#[cfg(feature = "interactive")]
 fn run_interactive_processing() -> Result<()> {
     // Valores por defecto para modo interactivo
-    let iterations = 5u32;
-    let memory_cost = 131072u32;
+    let params = crypto::ScypherParams::default();
+    let iterations = params.iterations();
+    let memory_cost = params.memory_cost();
 
     cli::clear_screen();
 
@@ -343,9 +1429,10 @@ fn run_interactive_processing() -> Result<()> {
     // 1. Obtener frase semilla de forma interactiva
     let seed_phrase = cli::read_seed_interactive(false)?;
 
-    // 2. Validar formato BIP39
+    // 2. Validar formato BIP39 con la lista de palabras detectada
     println!("Validating BIP39 format...");
-    bip39::validate_seed_phrase_complete(&seed_phrase)?;
+    let wordlist = resolve_wordlist(&seed_phrase, None)?;
+    bip39::validate_seed_phrase_with(&seed_phrase, wordlist)?;
     println!("{}✓ Seed phrase format is valid{}\n", cli::colors::SUCCESS, cli::colors::RESET);
 
     // 3. Obtener contraseña de forma segura
@@ -353,10 +1440,10 @@ fn run_interactive_processing() -> Result<()> {
 
     // 4. Realizar transformación XOR
     println!("Processing with Argon2id key derivation...");
-    let result = crypto::transform_seed(&seed_phrase, &password, iterations, memory_cost)?;
+    let result = crypto::transform_seed_with_wordlist(&seed_phrase, &password, &params, wordlist)?;
 
     // 5. Verificar resultado
-    match bip39::verify_checksum(&result) {
+    match bip39::verify_checksum_with(&result, wordlist) {
         Ok(true) => println!("{}✓ Result has valid BIP39 checksum{}", cli::colors::SUCCESS, cli::colors::RESET),
         Ok(false) => println!("{}⚠️  Result checksum is invalid - check password and input{}", cli::colors::WARNING, cli::colors::RESET),
         Err(_) => println!("{}⚠️  Could not verify result checksum{}", cli::colors::WARNING, cli::colors::RESET),
@@ -365,9 +1452,9 @@ fn run_interactive_processing() -> Result<()> {
     // 6. Mostrar resultado
     println!();
     println!("{}Result:{}", cli::colors::SUCCESS, cli::colors::RESET);
-    println!("─────────────────────────────────────────────────────────────");
-    println!("{}{}{}", cli::colors::PRIMARY, result, cli::colors::RESET);
-    println!("─────────────────────────────────────────────────────────────");
+    println!("{}", cli::output::format::separator_line(61));
+    println!("{}{}{}", cli::colors::PRIMARY, *result, cli::colors::RESET);
+    println!("{}", cli::output::format::separator_line(61));
 
     // 7. Manejar menú post-procesamiento
     let should_exit = cli::handle_post_processing_menu(&result)?;
@@ -382,94 +1469,796 @@ fn run_interactive_processing() -> Result<()> {
 }
 
 /// Ejecutar modo CLI tradicional (con argumentos)
+/// Construir los parámetros de transformación a partir de los argumentos globales
+fn params_from_matches(matches: &clap::ArgMatches) -> Result<crypto::ScypherParams> {
+    let iterations = *matches.get_one::<u32>("iterations").unwrap();
+    let memory_cost = *matches.get_one::<u32>("memory").unwrap();
+
+    let memory_cost = if matches.get_flag("low-memory") {
+        low_memory_cost(memory_cost)?
+    } else {
+        memory_cost
+    };
+
+    let mut builder = validate_crypto_params(iterations, memory_cost)?;
+
+    // Lanes: número explícito o los núcleos detectados
+    if let Some(lanes) = matches.get_one::<String>("lanes") {
+        builder = builder.lanes(parse_lanes(lanes)?);
+    }
+
+    if let Some(label) = matches.get_one::<String>("salt-from-label") {
+        builder = builder.salt(crypto::SaltSource::from_label(label)?);
+    }
+    if let Some(kdf) = matches.get_one::<String>("argon2-variant").and_then(|name| Kdf::from_name(name)) {
+        builder = builder.kdf(kdf);
+    }
+    if let Some(version) = matches.get_one::<String>("argon2-version").and_then(|name| Argon2Version::from_name(name)) {
+        builder = builder.argon2_version(version);
+    }
+
+    if matches.get_flag("skip-checksum") {
+        builder = builder.checksum_policy(crypto::ChecksumPolicy::Skip);
+    }
+
+    if let Some(path) = matches.get_one::<String>("pepper-file") {
+        builder = builder.pepper(cli::pepper::read_pepper_file(path)?);
+    }
+
+    add_keyfiles(matches, builder)?.build()
+}
+
+/// Parámetros para un cifrado nuevo: sin --lanes, una lane por núcleo
+///
+/// Al descifrar el valor por defecto sigue siendo 1, el de todas las copias
+/// hechas antes de que existiera --lanes.
+fn encryption_params_from_matches(matches: &clap::ArgMatches) -> Result<crypto::ScypherParams> {
+    let params = params_from_matches(matches)?;
+    if matches.get_one::<String>("lanes").is_some() {
+        return Ok(params);
+    }
+    params.to_builder().lanes(crypto::params::detected_lanes()).build()
+}
+
+/// Rondas de SHAKE-256 de --compat v2: las de -i si se indica, si no las del script
+fn legacy_iterations(matches: &clap::ArgMatches) -> u32 {
+    match matches.value_source("iterations") {
+        Some(clap::parser::ValueSource::CommandLine) => *matches.get_one::<u32>("iterations").unwrap(),
+        _ => crypto::legacy::V2_DEFAULT_ITERATIONS,
+    }
+}
+
+/// Un contenedor o QR con parámetros de Argon2 no lo hizo el script v2
+fn legacy_conflict(what: &str) -> SCypherError {
+    SCypherError::InvalidRequest(format!(
+        "The {} records Argon2 parameters, so it was not made by SCypher v2; drop --compat", what))
+}
+
+/// Memoria de Argon2 para --low-memory, con el aviso de seguridad si baja del valor por defecto
+fn low_memory_cost(requested_kb: u32) -> Result<u32> {
+    let available_kb = security::memory::available_memory_kb().ok_or_else(|| SCypherError::InvalidRequest(
+        "--low-memory cannot detect the available memory on this platform; set -m explicitly".to_string()))?;
+    let memory_cost = crypto::params::low_memory_cost(available_kb, requested_kb)?;
+
+    println!("Low-memory mode: {}MB available, using {}KB for Argon2", available_kb / 1024, memory_cost);
+    if memory_cost < crypto::params::DEFAULT_MEMORY_COST {
+        cli::events::warning(&format!("SECURITY WARNING: this is below the default {}KB, so each password guess is cheaper",
+                 crypto::params::DEFAULT_MEMORY_COST));
+        println!("   for an attacker. Use a stronger password, and re-encrypt on a larger machine if you can.");
+    }
+    println!("   Record it: decrypting needs -m {}\n", memory_cost);
+    Ok(memory_cost)
+}
+
+/// Avisar si Argon2 va a usar casi toda la RAM libre, o fallar si no cabe
+fn memory_preflight(params: &crypto::ScypherParams) -> Result<()> {
+    let required_kb = u64::from(params.memory_cost());
+    match security::memory::preflight_memory(required_kb) {
+        security::memory::MemoryPreflight::Insufficient { available_kb } => {
+            eprintln!("Argon2 needs {}MB but only {}MB of memory is available.", required_kb / 1024, available_kb / 1024);
+            eprintln!("Free some memory, or use --low-memory (encrypting) or the -m recorded for this backup.");
+            Err(SCypherError::InsufficientMemory)
+        }
+        security::memory::MemoryPreflight::Tight { available_kb } => {
+            let message = format!("Argon2 needs {}MB of the {}MB available; other programs may be swapped out or killed",
+                                  required_kb / 1024, available_kb / 1024);
+            eprintln!("⚠️  Warning: {}", message);
+            cli::events::emit(&cli::events::Event::Warning { message: &message });
+            Ok(())
+        }
+        security::memory::MemoryPreflight::Fits | security::memory::MemoryPreflight::Unknown => Ok(()),
+    }
+}
+
+/// Valor de --lanes: un número o 'auto' (los núcleos detectados)
+fn parse_lanes(value: &str) -> Result<u32> {
+    match value {
+        "auto" => Ok(crypto::params::detected_lanes()),
+        value => value.parse::<u32>().map_err(|_| SCypherError::invalid_parameter(
+            error::Parameter::Lanes, value, "a number or 'auto'")),
+    }
+}
+
+/// Parámetros nuevos de `rotate`: los antiguos con los --new-* que se indiquen
+fn rotated_params(matches: &clap::ArgMatches, old_params: &crypto::ScypherParams) -> Result<crypto::ScypherParams> {
+    let mut builder = old_params.to_builder();
+    if let Some(&iterations) = matches.get_one::<u32>("new-iterations") {
+        builder = builder.iterations(iterations);
+    }
+    if let Some(&memory_cost) = matches.get_one::<u32>("new-memory") {
+        builder = builder.memory_cost(memory_cost);
+    }
+    if let Some(lanes) = matches.get_one::<String>("new-lanes") {
+        builder = builder.lanes(parse_lanes(lanes)?);
+    }
+    builder.build()
+}
+
+/// Añadir al builder los keyfiles de -k, en orden
+fn add_keyfiles(matches: &clap::ArgMatches, mut builder: crypto::TransformBuilder) -> Result<crypto::TransformBuilder> {
+    if let Some(keyfiles) = matches.get_many::<String>("keyfile") {
+        for path in keyfiles {
+            let contents = zeroize::Zeroizing::new(std::fs::read(path)
+                .map_err(|e| SCypherError::io_at(IoOperation::Read, path.as_str(), e))?);
+            builder = builder.keyfile(&contents);
+        }
+    }
+    Ok(builder)
+}
+
+/// Parámetros guardados en un contenedor o en un QR de copia, con los keyfiles de -k
+///
+/// Los valores del contenedor mandan: -i/-m/--lanes explícitos que no
+/// coinciden se ignoran con un aviso.
+fn params_from_container(
+    matches: &clap::ArgMatches,
+    cli_params: &crypto::ScypherParams,
+    recorded: &crypto::ScypherParams,
+    keyfiles: usize,
+) -> Result<crypto::ScypherParams> {
+    let explicit = |id: &str| matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine);
+    let conflicts = [
+        ("iterations", "-i", cli_params.iterations().to_string(), recorded.iterations().to_string()),
+        ("memory", "-m", cli_params.memory_cost().to_string(), recorded.memory_cost().to_string()),
+        ("lanes", "--lanes", cli_params.lanes().to_string(), recorded.lanes().to_string()),
+        ("argon2-variant", "--argon2-variant", cli_params.kdf().name().to_string(), recorded.kdf().name().to_string()),
+        ("argon2-version", "--argon2-version", cli_params.argon2_version().name().to_string(), recorded.argon2_version().name().to_string()),
+    ];
+    for (id, flag, given, stored) in conflicts {
+        if explicit(id) && given != stored {
+            cli::events::warning(&format!("Ignoring {} {}: the container records {}", flag, given, stored));
+        }
+    }
+    if explicit("salt-from-label") && cli_params.salt() != recorded.salt() {
+        cli::events::warning("Ignoring --salt-from-label: the container records a different salt");
+    }
+
+    if cli_params.keyfile_digests().len() != keyfiles {
+        return Err(SCypherError::InvalidRequest(format!(
+            "The phrase was encrypted with {} keyfile(s); pass the same files, in the same order, with -k",
+            keyfiles,
+        )));
+    }
+
+    let mut builder = add_keyfiles(matches, recorded.to_builder())?;
+    if let Some(pepper) = cli_params.pepper() {
+        builder = builder.pepper(pepper.clone());
+    }
+    if matches.get_flag("skip-checksum") {
+        builder = builder.checksum_policy(crypto::ChecksumPolicy::Skip);
+    }
+    builder.build()
+}
+
 fn run_cli_mode(matches: &clap::ArgMatches) -> Result<()> {
     // Extraer argumentos
     let is_decrypt_mode = matches.get_flag("decrypt");
-    let output_file = matches.get_one::<String>("output");
     let input_file = matches.get_one::<String>("input-file");
     let skip_checksum = matches.get_flag("skip-checksum");
+    let language = matches.get_one::<String>("language")
+        .and_then(|code| Language::from_code(code));
 
-    // Obtener parámetros de seguridad
-    let iterations = *matches.get_one::<u32>("iterations").unwrap();
-    let memory_cost = *matches.get_one::<u32>("memory").unwrap();
+    // Con --compat v2 la clave sale de SHAKE-256 como en el script Bash; -i son sus rondas
+    let compat_v2 = matches.get_one::<String>("compat").map(|_| legacy_iterations(matches));
 
-    // Validar parámetros
-    validate_crypto_params(iterations, memory_cost)?;
+    // Validar parámetros y construir la configuración completa
+    let params = match compat_v2 {
+        Some(_) => crypto::ScypherParams::default(),
+        None if is_decrypt_mode => params_from_matches(matches)?,
+        None => encryption_params_from_matches(matches)?,
+    };
+
+    // Un contenedor de --armor trae la frase y los parámetros con que se cifró
+    #[cfg(feature = "armor")]
+    let container = input_file.map(|path| cli::input::read_container(path)).transpose()?.flatten();
+    #[cfg(feature = "armor")]
+    let (params, container_phrase) = match container {
+        Some(document) if document.params.is_some() && compat_v2.is_some() => return Err(legacy_conflict("container")),
+        Some(document) => {
+            let params = match &document.params {
+                Some(recorded) => params_from_container(matches, &params, recorded, document.keyfiles)?,
+                None => params,
+            };
+            (params, Some(document.phrase))
+        }
+        None => (params, None),
+    };
+    #[cfg(not(feature = "armor"))]
+    let container_phrase: Option<zeroize::Zeroizing<String>> = None;
+
+    // También un QR de copia de seguridad (ur:scypher-backup)
+    let backup = match container_phrase {
+        Some(_) => None,
+        None => input_file.map(|path| cli::input::read_backup(path)).transpose()?.flatten(),
+    };
+    let (params, container_phrase) = match backup {
+        Some(_) if compat_v2.is_some() => return Err(legacy_conflict("backup QR")),
+        Some(backup) => (params_from_container(matches, &params, &backup.params, backup.keyfiles)?, Some(backup.phrase)),
+        None => (params, container_phrase),
+    };
+
+    // Comprobar la memoria de Argon2 antes de pedir la contraseña
+    if !matches.contains_id("key-mnemonic") && compat_v2.is_none() {
+        memory_preflight(&params)?;
+    }
+
+    // Resolver el formato de salida antes de pedir datos sensibles
+    let format = matches.get_one::<String>("format")
+        .map(|name| scypher_rust::format::find_format(name))
+        .transpose()?;
+
+    // Código de --key-check, también antes de pedir secretos
+    let expected_check = matches.get_one::<String>("key-check")
+        .map(|code| crypto::key_check::parse_key_check(code))
+        .transpose()?;
+
+    // Ruta de la cuenta a exportar, también antes de pedir secretos
+    #[cfg(feature = "wallet")]
+    let derivation_path = matches.get_one::<String>("derivation-path")
+        .map(|path| path.parse::<scypher_rust::wallet::DerivationPath>())
+        .transpose()?;
+
+    #[cfg(feature = "armor")]
+    let armor = matches.get_one::<String>("armor")
+        .map(|name| scypher_rust::armor::Armor::from_name(name))
+        .transpose()?;
+
+    // La vista partida solo tiene sentido para las palabras en pantalla
+    let split_display = matches.get_flag("split-display");
+    #[cfg(feature = "armor")]
+    if split_display && armor.is_some() {
+        return Err(SCypherError::InvalidRequest("--split-display cannot be combined with --armor".to_string()));
+    }
+    if split_display && cli::display::is_plain_terminal() {
+        return Err(SCypherError::InvalidRequest("--split-display needs a screen that can be cleared (TERM=dumb or --plain-terminal)".to_string()));
+    }
+    #[cfg(feature = "qr")]
+    if split_display && matches.get_flag("ur") {
+        return Err(SCypherError::InvalidRequest("--split-display cannot be combined with --ur (the QR shows the whole phrase)".to_string()));
+    }
+    let router = output_router(matches, split_display)?;
+
+    // Con --key-mnemonic la clave es una segunda frase, sin contraseña ni Argon2id
+    let key_mnemonic = matches.get_one::<String>("key-mnemonic")
+        .map(|source| (source != "-").then_some(source.as_str()));
+
+    let index = matches.get_one::<u32>("index").copied();
 
     // Mostrar modo de operación (solo informativo, XOR es simétrico)
     let mode_name = if is_decrypt_mode { "Decryption" } else { "Encryption" };
     println!("SCypher v{} - {} Mode", VERSION, mode_name);
-    println!("Security: Argon2id with {} iterations, {}KB memory\n", iterations, memory_cost);
+    if key_mnemonic.is_some() {
+        println!("Key: second BIP39 phrase (XOR, no password or key derivation)");
+    } else if let Some(iterations) = compat_v2 {
+        println!("Security: SCypher v2 compatibility, SHAKE-256 with {} iterations", iterations);
+        cli::events::warning("--compat v2 is only for recovering backups of the original Bash script: SHAKE-256 makes password guessing fast, so re-encrypt the seed without it");
+    } else {
+        println!("Security: Argon2id with {} iterations, {}KB memory", params.iterations(), params.memory_cost());
+    }
+    if params.lanes() != crypto::params::DEFAULT_LANES {
+        println!("Lanes: {} (use --lanes {} again to decrypt)", params.lanes(), params.lanes());
+    }
+    if let Some(label) = matches.get_one::<String>("salt-from-label") {
+        println!("Salt: derived from label '{}' (use the same label to decrypt)", label);
+    }
+    if params.kdf() != Kdf::default() || params.argon2_version() != Argon2Version::default() {
+        cli::events::warning(&format!("Compatibility KDF: {} version {} (use the same values to decrypt)",
+                 params.kdf().name(), params.argon2_version().name()));
+    }
+    if !params.keyfile_digests().is_empty() {
+        println!("Keyfiles: {}", params.keyfile_digests().len());
+    }
+    if let Some(path) = matches.get_one::<String>("pepper-file") {
+        println!("Pepper: {} (pass the same --pepper-file to decrypt)", path);
+    }
+    if matches.get_flag("dual-control") {
+        println!("Dual control: two operator passwords (both operators are needed to decrypt)");
+    }
+    if let Some(index) = index {
+        println!("Output index: {} (use --index {} again to decrypt)", index, index);
+    }
+    let pad = matches.get_flag("pad-24");
+    if pad {
+        println!("Padding: 24-word output (use --pad-24 again to decrypt)");
+    }
+    println!();
+
+    // Semilla binaria o clave extendida en lugar de frase: mismo cifrado, se guarda igual
+    let raw_seed = matches.get_flag("raw-seed");
+    #[cfg(feature = "wallet")]
+    let xprv = matches.get_flag("xprv");
+    #[cfg(not(feature = "wallet"))]
+    let xprv = false;
+    if raw_seed || xprv {
+        let payload = match container_phrase {
+            Some(text) => text,
+            None => read_payload(input_file, raw_seed)?,
+        };
+        let password = read_password(matches)?;
+        println!("Processing with Argon2id key derivation...");
+        let result = transform_payload(&payload, &password, &params, raw_seed)?;
+
+        #[cfg(feature = "armor")]
+        let result = match armor {
+            Some(armor) => {
+                let params = (!is_decrypt_mode).then_some(&params);
+                let document = scypher_rust::armor::recovery_document(&result, params)?;
+                zeroize::Zeroizing::new(cli::output::armor_result(armor, &document)?)
+            }
+            None => result,
+        };
+        router.deliver(&cli::Output::new(&result))?.finish();
+        println!("\n✓ Operation completed successfully");
+        return Ok(());
+    }
 
     // 1. Obtener frase semilla
-    let seed_phrase = if let Some(file_path) = input_file {
+    let seed_phrase = if let Some(phrase) = container_phrase {
+        phrase
+    } else if let Some(file_path) = input_file {
         cli::read_seed_from_file(file_path)?
     } else {
         cli::read_seed_interactive(is_decrypt_mode)?
     };
 
-    // 2. Validar formato BIP39
+    // 2. Validar formato BIP39 con la lista de palabras indicada o detectada
+    let wordlist = match compat_v2 {
+        Some(_) => Language::English.wordlist(),
+        None => resolve_wordlist(&seed_phrase, language)?,
+    };
     if !skip_checksum {
         println!("Validating BIP39 format...");
-        bip39::validate_seed_phrase_complete(&seed_phrase)?;
+        bip39::validate_seed_phrase_with(&seed_phrase, wordlist)?;
         println!("✓ Seed phrase format is valid\n");
     } else {
-        println!("⚠️  Skipping BIP39 validation (not recommended)\n");
+        cli::events::warning("Skipping BIP39 validation (not recommended)");
+        println!();
     }
 
-    // 3. Obtener contraseña de forma segura
-    let password = cli::read_password_secure()?;
+    // 3-4. Obtener la clave y realizar la transformación XOR
+    let mut session = None;
+    let result = if let Some(key_file) = key_mnemonic {
+        // Frase clave: de un archivo o tecleada sin eco
+        let key_phrase = cli::input::read_key_phrase(key_file)?;
+        let key_wordlist = resolve_wordlist(&key_phrase, None)?;
+        println!("Combining with the key phrase...");
+        crypto::transform_with_key_phrase(&seed_phrase, wordlist, &key_phrase, key_wordlist, &params)?
+    } else {
+        // Tras varios descifrados fallidos seguidos, esperar antes de otro intento
+        if is_decrypt_mode {
+            cli::backoff::before_decrypt()?;
+        }
 
-    // 4. Realizar transformación XOR
-    println!("Processing with Argon2id key derivation...");
-    let result = crypto::transform_seed(&seed_phrase, &password, iterations, memory_cost)?;
+        // Contraseña: de un archivo cifrado o de forma segura
+        let password = read_password(matches)?;
+
+        // Con --key-check una contraseña mal tecleada se detecta antes de Argon2id
+        if let Some(expected) = &expected_check {
+            if crypto::key_check::key_check(&password, &params)? != *expected {
+                cli::backoff::record(false)?;
+                return Err(SCypherError::KeyCheckMismatch);
+            }
+            key_check_line(matches, "✓ Key check matches");
+            cli::backoff::record(true)?;
+        }
+
+        // Con --session se guarda el keystream para más frases
+        println!("Processing with {} key derivation...", if compat_v2.is_some() { "SHAKE-256" } else { "Argon2id" });
+        let started = std::time::Instant::now();
+        session = matches.get_flag("session")
+            .then(|| crypto::SessionKey::unlock(&password, params.clone()));
+        let result = match (session.as_mut(), index) {
+            // Relleno a 24 palabras: esquema propio, autenticado
+            (None, None) if pad && is_decrypt_mode => {
+                let unpadded = crypto::padding::unpad_transform(&seed_phrase, &password, &params, wordlist);
+                match unpadded {
+                    Ok(_) => cli::backoff::record(true)?,
+                    Err(SCypherError::CryptoError(_)) => cli::backoff::record(false)?,
+                    Err(_) => {}
+                }
+                unpadded?
+            }
+            (None, None) if pad => crypto::padding::pad_transform(&seed_phrase, &password, &params, wordlist)?,
+            (Some(session), _) => session.transform_with_wordlist(&seed_phrase, wordlist)?,
+            // Con --index el keystream se expande con HKDF desde la etiqueta del índice
+            (None, Some(index)) => crypto::MasterKey::derive(&password, &params)?
+                .transform_with_wordlist(&crypto::index_label(index), &seed_phrase, wordlist)?,
+            (None, None) => match compat_v2 {
+                Some(iterations) => crypto::legacy::transform_seed_v2(&seed_phrase, &password, iterations)?,
+                None => crypto::transform_seed_with_progress(&seed_phrase, &password, &params, wordlist, &cli::events::EventProgress)?,
+            },
+        };
+        if compat_v2.is_none() {
+            cli::metrics::record(matches.get_one::<String>("metrics"), || {
+                cli::metrics::RunMetrics::new("transform", &params, 1, started.elapsed())
+            });
+        }
+        if matches.get_flag("show-key-check") {
+            let code = crypto::key_check::key_check(&password, &params)?;
+            key_check_line(matches, &format!("Key check: {} (decrypting with --key-check {} reports a mistyped password at once)", code, code));
+            key_check_line(matches, "⚠️  The code lets anyone holding the encrypted phrase rule out most password guesses with a cheap derivation instead of the full one.");
+            key_check_line(matches, "   Store it apart from the backup, and do not use it with a weak password.");
+        }
+        result
+    };
 
     // 5. Verificar resultado si es modo descifrado
     if is_decrypt_mode && !skip_checksum {
-        match bip39::verify_checksum(&result) {
+        match bip39::verify_checksum_with(&result, wordlist) {
             Ok(true) => println!("✓ Result has valid BIP39 checksum"),
-            Ok(false) => println!("⚠️  Result checksum is invalid - check password and input"),
-            Err(_) => println!("⚠️  Could not verify result checksum"),
+            Ok(false) => cli::events::warning("Result checksum is invalid - check password and input"),
+            Err(_) => cli::events::warning("Could not verify result checksum"),
         }
     }
 
-    // 6. Mostrar y guardar resultado
-    cli::output_result(&result, output_file)?;
+    // Exportar la cuenta derivada del resultado, si se pidió
+    #[cfg(feature = "wallet")]
+    if let Some(keystore_file) = matches.get_one::<String>("eth-keystore") {
+        cli::export::export_eth_keystore(&result, derivation_path.as_ref(), keystore_file)?;
+    }
+    #[cfg(feature = "wallet")]
+    if let Some(bip38_file) = matches.get_one::<String>("bip38") {
+        cli::export::export_bip38(&result, derivation_path.as_ref(), bip38_file)?;
+    }
+    #[cfg(feature = "wallet")]
+    if let Some(descriptors_file) = matches.get_one::<String>("descriptors") {
+        cli::export::export_descriptors(&result, descriptors_file)?;
+    }
+    #[cfg(feature = "wallet")]
+    if matches.get_flag("cardano") {
+        cli::export::show_cardano_account(&result)?;
+    }
+    #[cfg(feature = "hardware")]
+    if let Some(hwi) = matches.get_one::<String>("hwi-check") {
+        cli::hwi::cross_check(&result, hwi)?;
+    }
+
+    // 6. Mostrar y guardar resultado en el formato pedido
+    let phrase = result;
+    let result = match format {
+        Some(format) => zeroize::Zeroizing::new(format.encode(&phrase)?),
+        None => phrase.clone(),
+    };
+
+    // Contenedor cifrado; una frase cifrada viaja con sus parámetros de recuperación
+    #[cfg(feature = "armor")]
+    let result = match armor {
+        Some(armor) => {
+            // Los parámetros de Argon2id no sirven para recuperar una combinación con --key-mnemonic ni con --compat
+            let params = (!is_decrypt_mode && key_mnemonic.is_none() && compat_v2.is_none()).then_some(&params);
+            let document = scypher_rust::armor::recovery_document(&result, params)?;
+            zeroize::Zeroizing::new(cli::output::armor_result(armor, &document)?)
+        }
+        None => result,
+    };
+    // QR: el contenedor si lo hay; una frase cifrada con sus parámetros, que
+    // bastan con la contraseña para recuperarla; si no, la semilla
+    #[cfg(feature = "qr")]
+    let ur = if matches.get_flag("ur") || router.needs_qr() {
+        #[cfg(feature = "armor")]
+        let armored = armor.is_some();
+        #[cfg(not(feature = "armor"))]
+        let armored = false;
+
+        Some(if armored {
+            scypher_rust::ur::Ur::bytes(result.as_bytes())
+        } else if !is_decrypt_mode && key_mnemonic.is_none() && compat_v2.is_none() {
+            scypher_rust::ur::Ur::scypher_backup(&phrase, &params)?
+        } else {
+            scypher_rust::ur::Ur::crypto_seed(&phrase)?
+        })
+    } else {
+        None
+    };
+
+    let output = cli::Output::new(&result);
+    #[cfg(feature = "qr")]
+    let output = match &ur {
+        Some(ur) => output.with_ur(ur),
+        None => output,
+    };
+    let delivery = router.deliver(&output)?;
 
+    #[cfg(feature = "qr")]
+    if let (true, Some(ur)) = (matches.get_flag("ur"), &ur) {
+        cli::qr::show_ur(ur)?;
+    }
+
+    // Más frases con la misma contraseña sin volver a derivar la clave
+    if let Some(session) = session.as_mut() {
+        run_session_phrases(session, is_decrypt_mode, language, skip_checksum, split_display)?;
+    }
+
+    delivery.finish();
     println!("\n✓ Operation completed successfully");
     Ok(())
 }
 
-/// Validar que los parámetros criptográficos estén en rangos seguros
-fn validate_crypto_params(iterations: u32, memory_cost: u32) -> Result<()> {
-    // Validar iteraciones
-    if iterations == 0 {
-        return Err(SCypherError::InvalidIterations("0".to_string()));
+/// Procesar frases adicionales con la clave de `--session`
+///
+/// Solo se muestran (con la opción de guardarlas); las exportaciones y
+/// formatos se aplican a la primera frase. La clave se limpia al salir.
+fn run_session_phrases(
+    session: &mut crypto::SessionKey,
+    is_decrypt_mode: bool,
+    language: Option<Language>,
+    skip_checksum: bool,
+    split_display: bool,
+) -> Result<()> {
+    while cli::input::read_confirmation("\nProcess another phrase with the same password?")? {
+        let seed_phrase = cli::read_seed_interactive(is_decrypt_mode)?;
+        let wordlist = resolve_wordlist(&seed_phrase, language)?;
+        if !skip_checksum {
+            bip39::validate_seed_phrase_with(&seed_phrase, wordlist)?;
+        }
+
+        let cached = session.is_cached(seed_phrase.split_whitespace().count());
+        println!("{}", if cached { "Reusing the session key..." } else { "Processing with Argon2id key derivation..." });
+        let result = session.transform_with_wordlist(&seed_phrase, wordlist)?;
+
+        if is_decrypt_mode && !skip_checksum {
+            match bip39::verify_checksum_with(&result, wordlist) {
+                Ok(true) => println!("✓ Result has valid BIP39 checksum"),
+                Ok(false) => cli::events::warning("Result checksum is invalid - check password and input"),
+                Err(_) => cli::events::warning("Could not verify result checksum"),
+            }
+        }
+        if split_display {
+            cli::output_result_split(&result, None)?;
+        } else {
+            cli::output_result(&result, None)?;
+        }
     }
 
-    if iterations > 100 {
-        return Err(SCypherError::InvalidIterations(
-            format!("{} (maximum recommended: 100)", iterations)
-        ));
+    session.lock();
+    Ok(())
+}
+
+/// Contraseña de --password-gpg / --password-age, o pedida por teclado
+fn read_password(matches: &clap::ArgMatches) -> Result<zeroize::Zeroizing<String>> {
+    if matches.get_flag("dual-control") {
+        return cli::input::read_dual_control_password();
+    }
+    if let Some(path) = matches.get_one::<String>("password-gpg") {
+        return cli::password_file::read_password_gpg(path);
+    }
+    #[cfg(feature = "armor")]
+    if let Some(path) = matches.get_one::<String>("password-age") {
+        return cli::password_file::read_password_age(path);
     }
+    cli::read_password_secure()
+}
 
-    // Validar costo de memoria (mínimo 8MB, máximo 2GB)
-    if memory_cost < 8192 {  // 8MB
-        return Err(SCypherError::InvalidMemoryCost(
-            format!("{}KB (minimum: 8192KB = 8MB)", memory_cost)
-        ));
+/// Lista de palabras de la frase: la pedida con --language o la detectada
+///
+/// Informa de las posiciones de palabras que existen en varias listas, nunca
+/// de las palabras.
+fn resolve_wordlist(seed_phrase: &str, language: Option<Language>) -> Result<&'static dyn bip39::Wordlist> {
+    if let Some(language) = language {
+        return Ok(offer_language_switch(seed_phrase, language)?.wordlist());
     }
 
-    if memory_cost > 2_097_152 {  // 2GB
-        return Err(SCypherError::InvalidMemoryCost(
-            format!("{}KB (maximum: 2097152KB = 2GB)", memory_cost)
-        ));
+    let detection = bip39::detect_language(seed_phrase).inspect_err(|_| {
+        // Con alguna errata no se detecta: decir al menos de qué lista parece
+        if let Some(likely) = bip39::likely_language(seed_phrase).filter(|likely| *likely != Language::English) {
+            println!("ℹ️  Most words are in the {} wordlist ({}); check the word below against it", likely.name(), likely.code());
+        }
+    })?;
+    if detection.language != Language::English {
+        println!("Wordlist: {} (detected)", detection.language.code());
+    }
+    if !detection.ambiguous_positions.is_empty() {
+        let positions: Vec<String> = detection.ambiguous_positions.iter().map(usize::to_string).collect();
+        println!("ℹ️  Word(s) {} also exist in other wordlists; using {} (--language to override)",
+                 positions.join(", "), detection.language.code());
+    }
+    Ok(detection.language.wordlist())
+}
+
+/// Con --language, si la mayoría de las palabras son de otra lista, ofrecer
+/// cambiar a ella
+///
+/// Solo se pregunta en un terminal; en un script la validación falla con
+/// `WrongWordlist`, que indica la lista correcta.
+fn offer_language_switch(seed_phrase: &str, language: Language) -> Result<Language> {
+    let wordlist = language.wordlist();
+    if seed_phrase.split_whitespace().all(|word| wordlist.contains(word)) {
+        return Ok(language);
+    }
+    let Some(likely) = bip39::likely_language(seed_phrase).filter(|likely| *likely != language) else {
+        return Ok(language);
+    };
+
+    println!("\nThe phrase is not in the {} wordlist (--language {}), but most of its words are in the {} one ({}).",
+             language.name(), language.code(), likely.name(), likely.code());
+    if std::io::IsTerminal::is_terminal(&std::io::stdin())
+        && cli::input::read_confirmation(&format!("Use the {} wordlist instead?", likely.name()))?
+    {
+        println!("Wordlist: {} (switched)", likely.code());
+        return Ok(likely);
+    }
+    Ok(language)
+}
+
+/// Mostrar una línea del código de `--show-key-check` / `--key-check`
+///
+/// Va con el resultado: con `--no-stdout` sale por stderr, para que no
+/// acabe en la tubería o el log que se pidió limpios.
+fn key_check_line(matches: &clap::ArgMatches, line: &str) {
+    if matches.get_flag("no-stdout") {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Destinos del resultado según -o, --qr-out, --clipboard y --no-stdout
+///
+/// Se resuelve antes de pedir datos sensibles, para fallar sin haberlos leído.
+fn output_router(matches: &clap::ArgMatches, split_display: bool) -> Result<cli::OutputRouter> {
+    use cli::Sink;
+
+    let mut sinks = Vec::new();
+    if !matches.get_flag("no-stdout") {
+        sinks.push(Sink::Stdout { split: split_display });
+    }
+    if let Some(path) = matches.get_one::<String>("output") {
+        sinks.push(Sink::File(path.clone()));
+    }
+    #[cfg(feature = "qr")]
+    if let Some(path) = matches.get_one::<String>("qr-out") {
+        cli::output::validate_output_path(path)?;
+        sinks.push(match matches.get_one::<u64>("qr-split") {
+            Some(&total) => Sink::QrShares { path: path.clone(), total: total as usize },
+            None => Sink::QrImage(path.clone()),
+        });
+    }
+    #[cfg(feature = "clipboard")]
+    if matches.get_flag("clipboard") {
+        sinks.push(Sink::Clipboard);
+    }
+    #[cfg(feature = "stego")]
+    if let (Some(carrier), Some(path)) = (matches.get_one::<String>("stego-carrier"), matches.get_one::<String>("stego-out")) {
+        // Una portadora inservible se descubre antes de pedir secretos
+        cli::output::read_carrier(carrier)?;
+        cli::output::validate_output_path(path)?;
+        sinks.push(Sink::Stego { carrier: carrier.clone(), path: path.clone() });
+    }
+    cli::OutputRouter::new(sinks)
+}
+
+/// Entregar una frase BIP39; en un QR va su `ur:crypto-seed`, o su
+/// `ur:scypher-backup` si es una frase cifrada con `params`
+#[cfg_attr(not(feature = "qr"), allow(unused_variables))]
+fn deliver_phrase(router: &cli::OutputRouter, phrase: &str, params: Option<&crypto::ScypherParams>) -> Result<()> {
+    let output = cli::Output::new(phrase);
+    #[cfg(feature = "qr")]
+    let ur = router.needs_qr().then(|| match params {
+        Some(params) => scypher_rust::ur::Ur::scypher_backup(phrase, params),
+        None => scypher_rust::ur::Ur::crypto_seed(phrase),
+    }).transpose()?;
+    #[cfg(feature = "qr")]
+    let output = match &ur {
+        Some(ur) => output.with_ur(ur),
+        None => output,
+    };
+    router.deliver(&output)?.finish();
+    Ok(())
+}
+
+/// Generar una frase nueva, con tiempos de teclado mezclados si se pide
+fn generate_phrase(matches: &clap::ArgMatches, router: &cli::OutputRouter) -> Result<()> {
+    let words = *matches.get_one::<usize>("words").unwrap();
+    bip39::validate_word_count(words)?;
+
+    #[cfg(feature = "interactive")]
+    if matches.get_flag("keyboard-entropy") {
+        let keystrokes = *matches.get_one::<u64>("keystrokes").unwrap() as usize;
+        let timings = cli::keyboard_entropy::collect_keystroke_timings(keystrokes)?;
+        println!("Mixing keyboard timing with the system RNG...");
+        let phrase = zeroize::Zeroizing::new(bip39::conversion::generate_seed_phrase_mixed(words * 32 / 3, &timings)?);
+        return deliver_phrase(router, &phrase, None);
     }
 
+    let phrase = zeroize::Zeroizing::new(bip39::conversion::generate_seed_phrase(words * 32 / 3)?);
+    deliver_phrase(router, &phrase, None)
+}
+
+/// Leer una frase y mostrar su identificador `fp1...`
+fn show_fingerprint(matches: &clap::ArgMatches, salt: Option<&str>) -> Result<()> {
+    let phrase = match matches.get_one::<String>("input-file") {
+        Some(file_path) => cli::read_seed_from_file(file_path)?,
+        None => cli::read_seed_interactive(false)?,
+    };
+    let language = matches.get_one::<String>("language").and_then(|code| Language::from_code(code));
+    let wordlist = resolve_wordlist(&phrase, language)?;
+    bip39::validate_seed_phrase_with(&phrase, wordlist)?;
+
+    let id = crypto::fingerprint::phrase_id(&phrase, wordlist, salt.unwrap_or_default())?;
+    println!("Phrase fingerprint: {}", id);
+    if salt.is_some() {
+        println!("Record the salt with it: verify-backup needs --salt to match");
+    }
     Ok(())
 }
 
+/// Leer una semilla binaria (`raw_seed`) o una clave extendida: de un archivo (primera línea con texto) o sin eco
+fn read_payload(input_file: Option<&String>, raw_seed: bool) -> Result<zeroize::Zeroizing<String>> {
+    let what = if raw_seed { "512-bit seed (hex)" } else { "extended private key" };
+    let payload = zeroize::Zeroizing::new(match input_file {
+        Some(path) => {
+            let content = zeroize::Zeroizing::new(std::fs::read_to_string(path)
+                .map_err(|e| SCypherError::io_at(IoOperation::Read, path.as_str(), e))?);
+            content.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("").to_string()
+        }
+        None => {
+            print!("Enter {}: ", what);
+            std::io::Write::flush(&mut std::io::stdout()).map_err(SCypherError::from)?;
+            let typed = cli::secure_input::read_masked(cli::secure_input::Mask::Password)?;
+            println!();
+            typed.trim().to_string()
+        }
+    });
+
+    #[cfg(feature = "wallet")]
+    let valid = if raw_seed {
+        crypto::raw_seed::looks_like_raw_seed(&payload)
+    } else {
+        scypher_rust::wallet::xprv::looks_like_xprv(&payload)
+    };
+    #[cfg(not(feature = "wallet"))]
+    let valid = crypto::raw_seed::looks_like_raw_seed(&payload);
+    if !valid {
+        return Err(SCypherError::InvalidRequest(format!("expected a {}", what)));
+    }
+    Ok(payload)
+}
+
+/// Cifrar o descifrar una semilla binaria (`raw_seed`) o una clave extendida
+fn transform_payload(payload: &str, password: &str, params: &crypto::ScypherParams, raw_seed: bool) -> Result<zeroize::Zeroizing<String>> {
+    #[cfg(feature = "wallet")]
+    if !raw_seed {
+        return scypher_rust::wallet::xprv::transform_xprv(payload, password, params);
+    }
+    debug_assert!(raw_seed);
+    crypto::raw_seed::transform_raw_seed(payload, password, params)
+}
+
+/// Validar que los parámetros criptográficos estén en rangos seguros
+/// Retorna el builder con esos valores, listo para añadir el resto de opciones
+fn validate_crypto_params(iterations: u32, memory_cost: u32) -> Result<crypto::TransformBuilder> {
+    let builder = crypto::TransformBuilder::new()
+        .iterations(iterations)
+        .memory_cost(memory_cost);
+
+    // La validación (1-100 iteraciones, 8MB-2GB) vive en el builder
+    builder.clone().build()?;
+
+    Ok(builder)
+}
+
 /// Mostrar información de ayuda extendida
+#[allow(dead_code)]
 fn show_extended_help() {
     cli::clear_screen();
     cli::show_banner();
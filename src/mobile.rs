@@ -0,0 +1,183 @@
+//! Bindings UniFFI para Kotlin y Swift
+//!
+//! Expone la API de transformación y validación a apps móviles de
+//! almacenamiento en frío (iOS/Android). Las contraseñas pueden pasarse
+//! como `SecurePassword`, un objeto que vive en memoria Rust (bloqueada
+//! cuando la plataforma lo permite) y que se limpia al liberarse o con `wipe()`.
+
+use std::sync::{Arc, Mutex};
+
+use crate::crypto::{Phase, ProgressSink, ScypherParams};
+use crate::error::{ErrorCategory, SCypherError};
+use crate::security::SecureString;
+
+/// Error expuesto a Kotlin/Swift
+#[derive(Debug, uniffi::Error)]
+pub enum MobileError {
+    /// Seed phrase, palabra o checksum inválidos
+    Validation { message: String },
+    /// Contraseña inválida o ya limpiada
+    Password { message: String },
+    /// Fallo de derivación o de parámetros criptográficos
+    Crypto { message: String },
+    /// Cualquier otro error
+    Other { message: String },
+}
+
+impl std::fmt::Display for MobileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MobileError::Validation { message }
+            | MobileError::Password { message }
+            | MobileError::Crypto { message }
+            | MobileError::Other { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for MobileError {}
+
+impl From<SCypherError> for MobileError {
+    fn from(error: SCypherError) -> Self {
+        let message = error.to_string();
+        match error.category() {
+            ErrorCategory::Validation => MobileError::Validation { message },
+            ErrorCategory::Password => MobileError::Password { message },
+            ErrorCategory::Crypto => MobileError::Crypto { message },
+            _ => MobileError::Other { message },
+        }
+    }
+}
+
+/// Contraseña retenida en memoria segura del lado Rust
+#[derive(uniffi::Object)]
+pub struct SecurePassword {
+    inner: Mutex<Option<SecureString>>,
+}
+
+#[uniffi::export]
+impl SecurePassword {
+    /// Crear desde el texto introducido por el usuario
+    #[uniffi::constructor]
+    pub fn new(password: String) -> Arc<Self> {
+        Arc::new(Self {
+            inner: Mutex::new(Some(SecureString::from(password))),
+        })
+    }
+
+    /// Limpiar la contraseña inmediatamente (sin esperar al GC)
+    pub fn wipe(&self) {
+        if let Ok(mut guard) = self.inner.lock() {
+            guard.take(); // El Drop de SecureString pone los bytes a cero
+        }
+    }
+
+    /// Verificar si la contraseña ya fue limpiada
+    pub fn is_wiped(&self) -> bool {
+        self.inner.lock().map(|guard| guard.is_none()).unwrap_or(true)
+    }
+}
+
+/// Versión de la biblioteca
+#[uniffi::export]
+pub fn version() -> String {
+    crate::VERSION.to_string()
+}
+
+/// Progreso y cancelación implementados en Kotlin/Swift
+///
+/// Se llama desde el hilo que ejecuta la transformación, nunca desde el hilo
+/// principal de la app.
+#[uniffi::export(with_foreign)]
+pub trait TransformListener: Send + Sync {
+    /// Comienza una fase: "validate", "derive_key", "encrypt" o "encode"
+    fn on_phase(&self, phase: String);
+
+    /// Si devuelve `true`, la transformación falla con un error de cancelación
+    fn is_cancelled(&self) -> bool;
+}
+
+/// Adaptador del listener extranjero al `ProgressSink` de la biblioteca
+struct ListenerSink(Option<Arc<dyn TransformListener>>);
+
+impl ProgressSink for ListenerSink {
+    fn on_phase(&self, phase: Phase) {
+        if let Some(listener) = &self.0 {
+            listener.on_phase(phase.name().to_string());
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.as_ref().is_some_and(|listener| listener.is_cancelled())
+    }
+}
+
+/// Transformar una seed phrase (la misma operación cifra y descifra)
+///
+/// `params` es el JSON de `ScypherParams` (`{"iterations": 5, "memory_cost": 131072}`);
+/// sin él se usan los valores por defecto.
+#[uniffi::export]
+pub fn transform_seed(
+    seed_phrase: String,
+    password: Arc<SecurePassword>,
+    params: Option<String>,
+    listener: Option<Arc<dyn TransformListener>>,
+) -> Result<String, MobileError> {
+    let params = match params {
+        Some(json) => ScypherParams::from_json(&json)?,
+        None => ScypherParams::default(),
+    };
+
+    let guard = password.inner.lock().map_err(|_| MobileError::Password {
+        message: "Password storage is poisoned".to_string(),
+    })?;
+
+    let secret = guard.as_ref().ok_or_else(|| MobileError::Password {
+        message: "Password has already been wiped".to_string(),
+    })?;
+
+    // El resultado cruza la FFI como String: se mueve sin dejar copia atrás
+    let mut phrase = crate::crypto::transform_seed_with_progress(
+        &seed_phrase,
+        secret.as_str(),
+        &params,
+        &crate::bip39::ENGLISH,
+        &ListenerSink(listener),
+    )?;
+    Ok(std::mem::take(&mut *phrase))
+}
+
+/// Validar formato, palabras y checksum de una seed phrase
+#[uniffi::export]
+pub fn validate_seed_phrase(seed_phrase: String) -> Result<(), MobileError> {
+    Ok(crate::bip39::validate_seed_phrase_complete(&seed_phrase)?)
+}
+
+/// Verificar solo el checksum BIP39
+#[uniffi::export]
+pub fn verify_checksum(seed_phrase: String) -> Result<bool, MobileError> {
+    Ok(crate::bip39::verify_checksum(&seed_phrase)?)
+}
+
+/// Verificar si una palabra pertenece a la lista BIP39
+#[uniffi::export]
+pub fn is_valid_word(word: String) -> bool {
+    crate::bip39::is_valid_word(&word)
+}
+
+/// Palabras BIP39 que comienzan con un prefijo (autocompletado)
+#[uniffi::export]
+pub fn words_with_prefix(prefix: String) -> Vec<String> {
+    crate::bip39::wordlist::words_with_prefix(&prefix)
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Generar una seed phrase nueva
+#[uniffi::export]
+pub fn generate_seed_phrase(word_count: u32) -> Result<String, MobileError> {
+    let word_count = word_count as usize;
+    crate::bip39::validate_word_count(word_count)?;
+    Ok(crate::bip39::conversion::generate_seed_phrase(word_count * 32 / 3)?)
+}
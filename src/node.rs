@@ -0,0 +1,186 @@
+//! Bindings N-API para Node.js
+//!
+//! Expone la API principal a JavaScript/TypeScript (Electron y herramientas
+//! de wallets) sin lanzar un subproceso ni pasar secretos por stdio.
+//! Los nombres se exportan en camelCase (`transformSeed`, `validateSeedPhrase`, ...).
+//! Los parámetros de derivación se pasan como el JSON de `ScypherParams`
+//! (`'{"iterations": 5, "memory_cost": 131072}'`).
+
+use napi::bindgen_prelude::AsyncTask;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, Error, Status, Task};
+use napi_derive::napi;
+use zeroize::Zeroize;
+
+use crate::crypto::{CancelFlag, Phase, ProgressSink, ScypherParams};
+use crate::error::SCypherError;
+
+/// Convertir errores de SCypher a errores de JavaScript
+fn to_napi_error(error: SCypherError) -> Error {
+    Error::new(Status::GenericFailure, error.to_string())
+}
+
+/// Versión de la biblioteca
+#[napi]
+pub fn version() -> String {
+    crate::VERSION.to_string()
+}
+
+/// Parámetros en JSON (el objeto `params` de `scypher api`); sin ellos, los por defecto
+fn parse_params(params: Option<String>) -> napi::Result<ScypherParams> {
+    match params {
+        Some(json) => ScypherParams::from_json(&json).map_err(to_napi_error),
+        None => Ok(ScypherParams::default()),
+    }
+}
+
+/// Transformar una seed phrase de forma síncrona (bloquea el event loop)
+#[napi]
+pub fn transform_seed(
+    seed_phrase: String,
+    mut password: String,
+    params: Option<String>,
+) -> napi::Result<String> {
+    let result = parse_params(params)
+        .and_then(|params| params.transform(&seed_phrase, &password).map_err(to_napi_error));
+    password.zeroize();
+    // El resultado pasa a JS como String: se mueve sin dejar copia atrás
+    result.map(|mut phrase| std::mem::take(&mut *phrase))
+}
+
+/// Indicador de cancelación para `transformSeedAsync`
+#[napi]
+#[derive(Default)]
+pub struct CancelToken {
+    flag: CancelFlag,
+}
+
+#[napi]
+impl CancelToken {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interrumpir la transformación en el siguiente punto de control
+    #[napi]
+    pub fn cancel(&self) {
+        self.flag.cancel();
+    }
+
+    #[napi(getter)]
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.is_cancelled()
+    }
+}
+
+/// Progreso hacia JavaScript: cada fase llama a `onPhase` en el event loop
+struct NodeProgress {
+    on_phase: Option<ThreadsafeFunction<String, ErrorStrategy::Fatal>>,
+    cancel: CancelFlag,
+}
+
+impl ProgressSink for NodeProgress {
+    fn on_phase(&self, phase: Phase) {
+        if let Some(callback) = &self.on_phase {
+            callback.call(phase.name().to_string(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+/// Tarea de transformación ejecutada en el thread pool de libuv
+pub struct TransformTask {
+    seed_phrase: String,
+    password: String,
+    params: ScypherParams,
+    progress: NodeProgress,
+}
+
+impl Task for TransformTask {
+    type Output = String;
+    type JsValue = String;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        crate::crypto::transform_seed_with_progress(
+            &self.seed_phrase,
+            &self.password,
+            &self.params,
+            &crate::bip39::ENGLISH,
+            &self.progress,
+        )
+        .map(|mut phrase| std::mem::take(&mut *phrase))
+        .map_err(to_napi_error)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+impl Drop for TransformTask {
+    fn drop(&mut self) {
+        self.seed_phrase.zeroize();
+        self.password.zeroize();
+    }
+}
+
+/// Transformar una seed phrase sin bloquear el event loop (retorna una Promise)
+///
+/// `onPhase` recibe el nombre de cada fase ("validate", "derive_key"...);
+/// tras `cancel.cancel()` la Promise se rechaza con "Operation cancelled".
+#[napi]
+pub fn transform_seed_async(
+    seed_phrase: String,
+    password: String,
+    params: Option<String>,
+    on_phase: Option<ThreadsafeFunction<String, ErrorStrategy::Fatal>>,
+    cancel: Option<&CancelToken>,
+) -> napi::Result<AsyncTask<TransformTask>> {
+    let params = parse_params(params)?;
+    let cancel = cancel.map(|token| token.flag.clone()).unwrap_or_default();
+    Ok(AsyncTask::new(TransformTask {
+        seed_phrase,
+        password,
+        params,
+        progress: NodeProgress { on_phase, cancel },
+    }))
+}
+
+/// Validar formato, palabras y checksum de una seed phrase
+#[napi]
+pub fn validate_seed_phrase(seed_phrase: String) -> napi::Result<()> {
+    crate::bip39::validate_seed_phrase_complete(&seed_phrase).map_err(to_napi_error)
+}
+
+/// Verificar solo el checksum BIP39
+#[napi]
+pub fn verify_checksum(seed_phrase: String) -> napi::Result<bool> {
+    crate::bip39::verify_checksum(&seed_phrase).map_err(to_napi_error)
+}
+
+/// Verificar si una palabra pertenece a la lista BIP39
+#[napi]
+pub fn is_valid_word(word: String) -> bool {
+    crate::bip39::is_valid_word(&word)
+}
+
+/// Palabras BIP39 que comienzan con un prefijo (autocompletado)
+#[napi]
+pub fn words_with_prefix(prefix: String) -> Vec<String> {
+    crate::bip39::wordlist::words_with_prefix(&prefix)
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Generar una seed phrase nueva
+#[napi]
+pub fn generate_seed_phrase(word_count: u32) -> napi::Result<String> {
+    let word_count = word_count as usize;
+    crate::bip39::validate_word_count(word_count).map_err(to_napi_error)?;
+    crate::bip39::conversion::generate_seed_phrase(word_count * 32 / 3).map_err(to_napi_error)
+}
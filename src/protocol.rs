@@ -0,0 +1,394 @@
+//! Protocolo JSON para front-ends (GUI Tauri/Electron, scripts)
+//!
+//! Una petición JSON produce exactamente una respuesta JSON. El esquema está
+//! versionado con `API_VERSION`: los campos nuevos se añaden de forma
+//! compatible y cualquier cambio incompatible incrementa la versión.
+//!
+//! Lo estable es el esquema JSON, no los tipos de Rust de este módulo, que
+//! exponen `ScypherParams`, `SecureString` y los enums de `crypto`. Para
+//! usar la biblioteca desde otro crate está [`crate::api`].
+//!
+//! Petición:
+//! ```json
+//! {"version": 1, "operation": "transform", "phrase": "...", "password": "...",
+//!  "params": {"iterations": 5, "memory_cost": 131072}}
+//! ```
+//!
+//! Respuesta:
+//! ```json
+//! {"version": 1, "ok": true, "result": {"phrase": "..."}}
+//! {"version": 1, "ok": false, "error": {"code": 2004, "category": "validation", "message": "..."}}
+//! {"version": 1, "ok": false, "error": {"code": 2003, "category": "validation", "message": "...",
+//!  "details": {"word": "abandn", "position": 3, "suggestion": "abandon"}}}
+//! ```
+//!
+//! `details` solo aparece en los errores que tienen datos estructurados
+//! (palabra y posición, parámetro rechazado, operación y ruta de E/S).
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::bip39::validation::SeedPhraseAnalysis;
+use crate::crypto::{Argon2Version, Cipher, Kdf, ScypherParams};
+use crate::error::{ErrorCategory, SCypherError, Result};
+use crate::security::SecureString;
+
+/// Versión actual del esquema de peticiones y respuestas
+pub const API_VERSION: u32 = 1;
+
+/// Petición de la API
+#[derive(Deserialize)]
+pub struct ApiRequest {
+    /// Versión del esquema que usa el cliente
+    pub version: u32,
+    #[serde(flatten)]
+    pub operation: Operation,
+}
+
+/// Operaciones disponibles
+#[derive(Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum Operation {
+    /// Cifrar/descifrar una frase (operación simétrica)
+    Transform(TransformArgs),
+    /// Validar y analizar una frase
+    Validate(ValidateArgs),
+    /// Generar una frase BIP39 aleatoria
+    Generate(GenerateArgs),
+    /// Versiones de la biblioteca y del esquema
+    Version,
+    /// Formatos, KDFs, idiomas, features y códigos de salida disponibles
+    Capabilities,
+}
+
+/// Argumentos de `transform`
+#[derive(Deserialize)]
+pub struct TransformArgs {
+    pub phrase: SecureString,
+    pub password: SecureString,
+    #[serde(default)]
+    pub params: ScypherParams,
+}
+
+/// Argumentos de `validate`
+#[derive(Deserialize)]
+pub struct ValidateArgs {
+    pub phrase: SecureString,
+}
+
+/// Argumentos de `generate`
+#[derive(Deserialize)]
+pub struct GenerateArgs {
+    #[serde(default = "default_word_count")]
+    pub words: usize,
+}
+
+fn default_word_count() -> usize {
+    12
+}
+
+/// Resultado de una operación exitosa
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ApiResult {
+    Phrase { phrase: String },
+    Validation { valid: bool, analysis: SeedPhraseAnalysis },
+    Version { library: &'static str, schema: u32 },
+    Capabilities(Capabilities),
+}
+
+/// Lo que admite esta compilación, para detectar funciones sin leer `--help`
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub library: &'static str,
+    pub schema: u32,
+    pub formats: Vec<FormatInfo>,
+    pub kdfs: Vec<Kdf>,
+    pub argon2_versions: Vec<Argon2Version>,
+    pub ciphers: Vec<Cipher>,
+    /// Códigos de las listas de palabras BIP39
+    pub languages: Vec<&'static str>,
+    /// Contenedores de `--armor` (vacío sin la feature `armor`)
+    pub containers: Vec<&'static str>,
+    /// Features de Cargo activas en esta compilación
+    pub features: Vec<&'static str>,
+    pub exit_codes: Vec<ExitCodeInfo>,
+}
+
+/// Formato de salida disponible
+#[derive(Debug, Serialize)]
+pub struct FormatInfo {
+    pub name: String,
+    pub description: String,
+}
+
+/// Código de salida del CLI y su significado
+#[derive(Debug, Serialize)]
+pub struct ExitCodeInfo {
+    pub code: i32,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Features opcionales que cambian lo que hay disponible
+const FEATURES: [(&str, bool); 11] = [
+    ("cli", cfg!(feature = "cli")),
+    ("parallel", cfg!(feature = "parallel")),
+    ("wallet", cfg!(feature = "wallet")),
+    ("armor", cfg!(feature = "armor")),
+    ("qr", cfg!(feature = "qr")),
+    ("slip39", cfg!(feature = "slip39")),
+    ("async", cfg!(feature = "async")),
+    ("grpc", cfg!(feature = "grpc")),
+    ("wasm", cfg!(feature = "wasm")),
+    ("node", cfg!(feature = "node")),
+    ("mobile", cfg!(feature = "mobile")),
+];
+
+/// Capacidades de esta compilación
+///
+/// Los códigos de salida son los de las categorías de error más el 0; el
+/// CLI añade los suyos propios (señales, comprobaciones).
+pub fn capabilities() -> Capabilities {
+    let formats = crate::format::available_formats()
+        .iter()
+        .map(|format| FormatInfo { name: format.name().to_string(), description: format.description().to_string() })
+        .collect();
+
+    #[cfg(feature = "armor")]
+    let containers = crate::armor::Armor::ALL.iter().map(|armor| armor.name()).collect();
+    #[cfg(not(feature = "armor"))]
+    let containers = Vec::new();
+
+    let mut exit_codes = vec![ExitCodeInfo { code: 0, name: "success", description: "operation completed" }];
+    exit_codes.extend(ErrorCategory::ALL.iter().map(|category| ExitCodeInfo {
+        code: category.exit_code(),
+        name: category.as_str(),
+        description: category.description(),
+    }));
+
+    Capabilities {
+        library: crate::VERSION,
+        schema: API_VERSION,
+        formats,
+        kdfs: Kdf::ALL.to_vec(),
+        argon2_versions: Argon2Version::ALL.to_vec(),
+        ciphers: Cipher::ALL.to_vec(),
+        languages: crate::bip39::Language::ALL.iter().map(|language| language.code()).collect(),
+        containers,
+        features: FEATURES.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect(),
+        exit_codes,
+    }
+}
+
+/// Respuesta de la API
+#[derive(Serialize)]
+pub struct ApiResponse {
+    pub version: u32,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ApiResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SCypherError>,
+}
+
+impl ApiResponse {
+    fn from_result(result: Result<ApiResult>) -> Self {
+        match result {
+            Ok(result) => Self { version: API_VERSION, ok: true, result: Some(result), error: None },
+            Err(error) => Self { version: API_VERSION, ok: false, result: None, error: Some(error) },
+        }
+    }
+
+    /// Serializar a JSON; el buffer se limpia al liberarse porque puede contener frases
+    pub fn to_json(&self) -> Zeroizing<String> {
+        // La serialización de estos tipos no puede fallar
+        Zeroizing::new(serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+// Las frases de los resultados se limpian al liberarse
+impl Drop for ApiResult {
+    fn drop(&mut self) {
+        if let ApiResult::Phrase { phrase } = self {
+            zeroize::Zeroize::zeroize(phrase);
+        }
+    }
+}
+
+/// Ejecutar una petición ya deserializada
+pub fn execute(request: ApiRequest) -> ApiResponse {
+    if request.version != API_VERSION {
+        return ApiResponse::from_result(Err(SCypherError::InvalidRequest(format!(
+            "unsupported schema version {} (supported: {})",
+            request.version, API_VERSION
+        ))));
+    }
+
+    ApiResponse::from_result(execute_operation(request.operation))
+}
+
+/// Procesar una petición JSON completa y devolver la respuesta
+///
+/// Los errores de formato también se devuelven como respuesta JSON.
+pub fn handle_json(input: &str) -> ApiResponse {
+    match serde_json::from_str::<ApiRequest>(input) {
+        Ok(request) => execute(request),
+        Err(e) => ApiResponse::from_result(Err(SCypherError::InvalidRequest(e.to_string()))),
+    }
+}
+
+/// Leer un único objeto JSON de `reader` sin esperar al fin de la entrada
+///
+/// Se detiene en la llave que cierra el objeto, así que sirve aunque quien
+/// llama deje stdin abierto. El buffer se limpia de memoria al liberarse.
+pub fn read_json_object<R: std::io::BufRead>(reader: R) -> Result<Zeroizing<String>> {
+    let mut object = Zeroizing::new(Vec::new());
+    let mut depth = 0usize;
+    let (mut in_string, mut escaped) = (false, false);
+
+    for byte in reader.bytes() {
+        let byte = byte?;
+        if object.is_empty() && byte.is_ascii_whitespace() {
+            continue;
+        }
+        if object.is_empty() && byte != b'{' {
+            return Err(SCypherError::InvalidRequest("expected a JSON object".to_string()));
+        }
+        object.push(byte);
+
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' if in_string => escaped = true,
+            b'"' => in_string = !in_string,
+            b'{' | b'[' if !in_string => depth += 1,
+            b'}' | b']' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    let object = std::mem::take(&mut *object);
+                    return String::from_utf8(object)
+                        .map(Zeroizing::new)
+                        .map_err(|_| SCypherError::InvalidRequest("request is not valid UTF-8".to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(SCypherError::InvalidRequest("incomplete JSON object".to_string()))
+}
+
+/// Respuesta de error para fallos previos a la petición (p.ej. al leer stdin)
+pub fn handle_json_error(error: SCypherError) -> ApiResponse {
+    ApiResponse::from_result(Err(error))
+}
+
+/// Ejecutar una operación (compartido por el modo API y el daemon JSON-RPC)
+pub fn execute_operation(operation: Operation) -> Result<ApiResult> {
+    match operation {
+        Operation::Transform(TransformArgs { phrase, password, params }) => {
+            let mut phrase = params.transform(phrase.as_str(), password.as_str())?;
+            // Se mueve sin copiar; `ApiResult` la limpia al soltarse
+            Ok(ApiResult::Phrase { phrase: std::mem::take(&mut *phrase) })
+        }
+        Operation::Validate(ValidateArgs { phrase }) => {
+            let analysis = crate::bip39::analyze_seed_phrase(phrase.as_str());
+            Ok(ApiResult::Validation { valid: analysis.overall_valid, analysis })
+        }
+        Operation::Generate(GenerateArgs { words }) => {
+            crate::bip39::validate_word_count(words)?;
+            let phrase = crate::bip39::conversion::generate_seed_phrase(words * 32 / 3)?;
+            Ok(ApiResult::Phrase { phrase })
+        }
+        Operation::Version => Ok(ApiResult::Version {
+            library: crate::VERSION,
+            schema: API_VERSION,
+        }),
+        Operation::Capabilities => Ok(ApiResult::Capabilities(capabilities())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn call(input: &str) -> serde_json::Value {
+        serde_json::from_str(&handle_json(input).to_json()).unwrap()
+    }
+
+    #[test]
+    fn test_transform_roundtrip() {
+        let request = serde_json::json!({
+            "version": 1,
+            "operation": "transform",
+            "phrase": TEST_PHRASE,
+            "password": "test_password",
+            "params": {"iterations": 1, "memory_cost": 8192},
+        });
+
+        let response = call(&request.to_string());
+        assert_eq!(response["ok"], true);
+        let encrypted = response["result"]["phrase"].as_str().unwrap().to_string();
+
+        let mut request = request;
+        request["phrase"] = encrypted.into();
+        let response = call(&request.to_string());
+        assert_eq!(response["result"]["phrase"], TEST_PHRASE);
+    }
+
+    #[test]
+    fn test_validate_and_generate() {
+        let response = call(r#"{"version": 1, "operation": "validate", "phrase": "abandon"}"#);
+        assert_eq!(response["ok"], true);
+        assert_eq!(response["result"]["valid"], false);
+
+        let response = call(r#"{"version": 1, "operation": "generate", "words": 24}"#);
+        let phrase = response["result"]["phrase"].as_str().unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+    }
+
+    #[test]
+    fn test_errors_are_json() {
+        let response = call("not json");
+        assert_eq!(response["ok"], false);
+        assert_eq!(response["error"]["code"], 2006);
+
+        let response = call(r#"{"version": 99, "operation": "version"}"#);
+        assert_eq!(response["error"]["category"], "validation");
+
+        let response = call(r#"{"version": 1, "operation": "generate", "words": 13}"#);
+        assert_eq!(response["error"]["code"], 2002);
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let response = call(r#"{"version": 1, "operation": "capabilities"}"#);
+        let capabilities = &response["result"];
+        assert_eq!(capabilities["schema"], API_VERSION);
+        assert_eq!(capabilities["kdfs"], serde_json::json!(["argon2id", "argon2i"]));
+        assert_eq!(capabilities["argon2_versions"], serde_json::json!(["0x10", "0x13"]));
+        assert_eq!(capabilities["ciphers"], serde_json::json!(["xor"]));
+        assert!(capabilities["languages"].as_array().unwrap().contains(&"es".into()));
+        assert!(capabilities["formats"].as_array().unwrap().iter().any(|format| format["name"] == "words"));
+
+        let exit_codes = capabilities["exit_codes"].as_array().unwrap();
+        assert_eq!(exit_codes[0]["code"], 0);
+        assert!(exit_codes.iter().any(|code| code["code"] == 3 && code["name"] == "password"));
+    }
+
+    #[test]
+    fn test_read_json_object() {
+        let input = b"  {\"phrase\": \"a } \\\" {\", \"params\": {\"x\": [1]}}\n{\"next\": 1}";
+        let mut reader = &input[..];
+        assert_eq!(
+            read_json_object(&mut reader).unwrap().as_str(),
+            r#"{"phrase": "a } \" {", "params": {"x": [1]}}"#
+        );
+        assert_eq!(read_json_object(&mut reader).unwrap().as_str(), r#"{"next": 1}"#);
+
+        assert!(read_json_object(&b"{\"open\": "[..]).is_err());
+        assert!(read_json_object(&b"[1]"[..]).is_err());
+    }
+}
@@ -135,7 +135,7 @@ pub fn validate_execution_context() -> Result<()> {
 
             // Advertir si se ejecuta como root sin necesidad
             if effective_uid == 0 {
-                eprintln!("Warning: Running as root - consider using a regular user account");
+                eprintln!("Warning: Running as root - consider using a regular user account (or --no-root)");
             }
         }
     }
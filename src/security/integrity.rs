@@ -0,0 +1,210 @@
+//! Vigilancia de integridad de los buffers secretos
+//!
+//! Durante una derivación larga (segundos con mucha memoria) la entropía de
+//! la frase espera en memoria sin que nadie la toque. Un bit cambiado ahí
+//! (rayo cósmico, rowhammer o un bug) daría en silencio otra frase cifrada
+//! que nunca se podría descifrar a la original.
+//!
+//! `with_watch` presta en exclusiva un buffer mientras se ejecuta una
+//! función y guarda un resumen con clave (SHA-256 con una clave aleatoria
+//! del proceso) de su contenido. Un hilo de fondo comprueba los buffers
+//! registrados cada `CHECK_INTERVAL`; si uno no coincide, borra todos los
+//! registrados y aborta el proceso en el acto. Al terminar la función se
+//! hace una última comprobación síncrona, que devuelve error en lugar de
+//! abortar, para que nada corrupto llegue a usarse.
+//!
+//! El registro guarda un puntero crudo al buffer, así que la vigilancia no
+//! puede sobrevivir al préstamo: por eso no hay un guardián público (un
+//! `mem::forget` dejaría al hilo leyendo memoria liberada), y `with_watch`
+//! quita el registro antes de volver, también si la función entra en
+//! pánico.
+
+use std::marker::PhantomData;
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::Duration;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use crate::error::{SCypherError, Result};
+
+/// Periodo de comprobación del hilo de fondo
+const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Región registrada: el hilo de fondo tiene acceso exclusivo mientras dura
+struct Region {
+    id: u64,
+    ptr: *mut u8,
+    len: usize,
+    digest: [u8; 32],
+}
+
+// SAFETY: el `Watch` que creó la región retiene el préstamo exclusivo del
+// buffer; solo se accede a él con el registro bloqueado
+unsafe impl Send for Region {}
+
+impl Region {
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: ver `unsafe impl Send`; el buffer vive mientras la región esté registrada
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    fn is_intact(&self) -> bool {
+        digest(self.bytes()) == self.digest
+    }
+
+    fn wipe(&mut self) {
+        // SAFETY: acceso exclusivo, ver `unsafe impl Send`
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }.zeroize();
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        self.digest.zeroize();
+    }
+}
+
+/// Regiones vigiladas y siguiente identificador
+static REGISTRY: Mutex<(u64, Vec<Region>)> = Mutex::new((0, Vec::new()));
+
+/// Arranque único del hilo de fondo
+static MONITOR: Once = Once::new();
+
+/// Resumen con la clave aleatoria del proceso
+fn digest(bytes: &[u8]) -> [u8; 32] {
+    static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+    let key = KEY.get_or_init(|| {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        key
+    });
+
+    let mut hasher = Sha256::new();
+    hasher.update(key);
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+/// Ejecutar `f` con `buffer` vigilado; error si el buffer cambió entretanto
+///
+/// `buffer` queda prestado en exclusiva durante `f`, que no lo ve. La
+/// vigilancia termina siempre antes de volver.
+pub fn with_watch<T>(buffer: &mut [u8], f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let watched = Watch::register(buffer);
+    let result = f();
+    watched.release()?;
+    result
+}
+
+/// Registro de un buffer vigilado; se quita al soltarlo (también en un pánico)
+///
+/// Privado: solo `with_watch` lo crea y lo suelta antes de que termine el préstamo.
+struct Watch<'a> {
+    id: u64,
+    _buffer: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Watch<'a> {
+    fn register(buffer: &'a mut [u8]) -> Self {
+        let region_digest = digest(buffer);
+        let mut registry = REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        registry.0 += 1;
+        let id = registry.0;
+        registry.1.push(Region { id, ptr: buffer.as_mut_ptr(), len: buffer.len(), digest: region_digest });
+        drop(registry);
+
+        start_monitor();
+        Watch { id, _buffer: PhantomData }
+    }
+
+    /// Dejar de vigilar el buffer comprobando antes que sigue intacto
+    fn release(self) -> Result<()> {
+        let intact = unregister(self.id).is_none_or(|region| region.is_intact());
+        std::mem::forget(self);
+        if intact {
+            Ok(())
+        } else {
+            Err(SCypherError::crypto("Memory corruption detected in secret data during key derivation; the result was discarded"))
+        }
+    }
+}
+
+impl Drop for Watch<'_> {
+    fn drop(&mut self) {
+        unregister(self.id);
+    }
+}
+
+fn unregister(id: u64) -> Option<Region> {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let position = registry.1.iter().position(|region| region.id == id)?;
+    Some(registry.1.swap_remove(position))
+}
+
+/// Comprobar ahora todos los buffers vigilados
+pub fn verify_all() -> bool {
+    let registry = REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    registry.1.iter().all(Region::is_intact)
+}
+
+/// Arrancar (una sola vez) el hilo que comprueba los buffers periódicamente
+fn start_monitor() {
+    // En wasm no hay hilos: queda la comprobación de `release`
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+
+    MONITOR.call_once(|| {
+        let _ = std::thread::Builder::new()
+            .name("scypher-integrity".to_string())
+            .spawn(|| loop {
+                std::thread::sleep(CHECK_INTERVAL);
+                let mut registry = REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                if !registry.1.iter().all(Region::is_intact) {
+                    registry.1.iter_mut().for_each(Region::wipe);
+                    eprintln!("FATAL: memory corruption detected in secret data; buffers wiped, aborting");
+                    std::process::abort();
+                }
+            });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_registered(ptr: *const u8) -> bool {
+        let registry = REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        registry.1.iter().any(|region| std::ptr::eq(region.ptr, ptr))
+    }
+
+    #[test]
+    fn test_watch_and_corruption() {
+        let mut secret = [0x5Au8; 32];
+        let ptr = secret.as_ptr();
+        let value = with_watch(&mut secret, || {
+            assert!(is_registered(ptr) && verify_all());
+            Ok(7)
+        });
+        assert_eq!(value.unwrap(), 7);
+        assert!(!is_registered(ptr));
+
+        // Un error o un pánico de la función también quitan el registro
+        assert!(with_watch(&mut secret, || Err::<(), _>(SCypherError::Cancelled)).is_err());
+        assert!(!is_registered(ptr));
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| with_watch(&mut secret, || -> Result<()> { panic!("boom") })));
+        assert!(panicked.is_err());
+        assert!(!is_registered(ptr));
+
+        // Una región cuyo contenido cambia deja de estar intacta
+        let mut other = [1u8; 16];
+        let mut region = Region { id: 0, ptr: other.as_mut_ptr(), len: other.len(), digest: digest(&other) };
+        assert!(region.is_intact());
+        unsafe { *region.ptr ^= 0x01 };
+        assert!(!region.is_intact());
+        region.wipe();
+        assert_eq!(other, [0u8; 16]);
+    }
+}
@@ -0,0 +1,50 @@
+//! Interrupción de derivaciones en curso con Ctrl-C
+//!
+//! Argon2 con mucha memoria puede tardar minutos. Mientras hay una
+//! derivación en marcha, el primer Ctrl-C no mata el proceso: lo anota aquí,
+//! la derivación lo ve antes de su siguiente segmento, limpia su memoria y
+//! devuelve `Cancelled`, que sale por el camino normal de limpieza (con
+//! código 130). Fuera de una derivación, o con un segundo Ctrl-C, el handler
+//! termina en el acto como antes.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Derivaciones en marcha que atenderán la interrupción
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Se pulsó Ctrl-C durante una derivación
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Marca de derivación interrumpible; deja de serlo al soltarse
+#[must_use]
+pub struct Interruptible(());
+
+impl Drop for Interruptible {
+    fn drop(&mut self) {
+        ACTIVE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Empieza una derivación que comprobará `is_requested`
+pub fn enter() -> Interruptible {
+    ACTIVE.fetch_add(1, Ordering::SeqCst);
+    Interruptible(())
+}
+
+/// Pedir la interrupción (desde el handler de Ctrl-C)
+///
+/// Devuelve `false` si nadie va a atenderla (no hay derivación en marcha o
+/// ya se pidió antes): entonces el llamador debe terminar el proceso.
+pub fn request() -> bool {
+    ACTIVE.load(Ordering::SeqCst) > 0 && !REQUESTED.swap(true, Ordering::SeqCst)
+}
+
+/// Si se pidió la interrupción
+pub fn is_requested() -> bool {
+    REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Olvidar una interrupción ya atendida (sesiones que siguen tras cancelar)
+pub fn reset() {
+    REQUESTED.store(false, Ordering::SeqCst);
+}
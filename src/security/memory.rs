@@ -16,26 +16,10 @@ pub fn secure_clear(buffer: &mut [u8]) {
     buffer.zeroize();
 }
 
-/// Verificar integridad de memoria básica
-/// Retorna true si la memoria parece estar íntegra
+/// Verificar que los buffers secretos vigilados siguen intactos
+/// Retorna true si ninguno ha cambiado (ver `security::integrity`)
 pub fn check_memory_integrity() -> bool {
-    // Test básico: allocar y verificar que podemos escribir/leer
-    let mut test_buffer = vec![0u8; 1024];
-
-    // Escribir patrón
-    for (i, byte) in test_buffer.iter_mut().enumerate() {
-        *byte = (i % 256) as u8;
-    }
-
-    // Verificar patrón
-    let is_intact = test_buffer.iter().enumerate().all(|(i, &byte)| {
-        byte == (i % 256) as u8
-    });
-
-    // Limpiar buffer de prueba
-    secure_clear(&mut test_buffer);
-
-    is_intact
+    crate::security::integrity::verify_all()
 }
 
 /// Limpieza profunda de un vector
@@ -300,6 +284,76 @@ pub fn configure_memory_limits() -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Memoria disponible en KB (`MemAvailable` de /proc/meminfo), si se puede saber
+pub fn available_memory_kb() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/meminfo").ok().and_then(|text| parse_mem_available(&text))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Pico de memoria residente del proceso en KB (`VmHWM` de /proc/self/status), si se puede saber
+pub fn peak_resident_memory_kb() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/self/status").ok().and_then(|text| parse_kb_field(&text, "VmHWM:"))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Comparación del coste de memoria de Argon2 con la RAM disponible
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryPreflight {
+    /// Cabe con margen
+    Fits,
+    /// Cabe, pero usa más de tres cuartos de la memoria disponible
+    Tight { available_kb: u64 },
+    /// No cabe: la reserva acabaría con el proceso a mitad de la derivación
+    Insufficient { available_kb: u64 },
+    /// No se puede saber en esta plataforma
+    Unknown,
+}
+
+/// Comprobar si `required_kb` de memoria de trabajo caben en la RAM disponible
+pub fn preflight_memory(required_kb: u64) -> MemoryPreflight {
+    match available_memory_kb() {
+        Some(available_kb) => classify_memory(required_kb, available_kb),
+        None => MemoryPreflight::Unknown,
+    }
+}
+
+fn classify_memory(required_kb: u64, available_kb: u64) -> MemoryPreflight {
+    if required_kb > available_kb {
+        MemoryPreflight::Insufficient { available_kb }
+    } else if required_kb > available_kb / 4 * 3 {
+        MemoryPreflight::Tight { available_kb }
+    } else {
+        MemoryPreflight::Fits
+    }
+}
+
+/// Valor de `MemAvailable` (en kB) de un /proc/meminfo
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    parse_kb_field(meminfo, "MemAvailable:")
+}
+
+/// Valor en kB del campo `field` (con sus dos puntos) de un archivo de /proc
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_kb_field(text: &str, field: &str) -> Option<u64> {
+    text.lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|value| value.trim().parse().ok())
+}
+
 // Agregar estos tests adicionales a la sección #[cfg(test)]
 
 #[cfg(test)]
@@ -382,6 +436,21 @@ mod tests {
         // Al salir del scope, el drop debería limpiar automáticamente
     }
 
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:         439412 kB\nMemFree:           52100 kB\nMemAvailable:     301560 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(301560));
+        assert_eq!(parse_mem_available("MemTotal: 439412 kB\n"), None);
+        assert_eq!(parse_kb_field("VmPeak:\t  20000 kB\nVmHWM:\t    5120 kB\n", "VmHWM:"), Some(5120));
+    }
+
+    #[test]
+    fn test_classify_memory() {
+        assert_eq!(classify_memory(131072, 1_048_576), MemoryPreflight::Fits);
+        assert_eq!(classify_memory(131072, 150_000), MemoryPreflight::Tight { available_kb: 150_000 });
+        assert_eq!(classify_memory(131072, 100_000), MemoryPreflight::Insufficient { available_kb: 100_000 });
+    }
+
     #[test]
     fn test_secure_buffer_from_slice() {
         let data = b"sensitive data";
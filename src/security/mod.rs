@@ -3,23 +3,38 @@
 pub mod memory;
 pub mod process;
 pub mod environment;
+pub mod integrity;
+pub mod network;
+pub mod persistence;
+pub mod storage;
+pub mod interrupt;
 
+#[cfg(feature = "cli")]
 use std::sync::atomic::{AtomicBool, Ordering};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 use crate::error::Result;
 
 // Flag global para rastrear si la limpieza está configurada
+#[cfg(feature = "cli")]
 static CLEANUP_CONFIGURED: AtomicBool = AtomicBool::new(false);
 
 /// Configurar limpieza de seguridad al inicio de la aplicación
+#[cfg(feature = "cli")]
 pub fn setup_security_cleanup() {
     if CLEANUP_CONFIGURED.load(Ordering::Relaxed) {
         return; // Ya configurado
     }
 
     // Configurar handler para limpieza en caso de señales de terminación
+    // Durante una derivación, el primer Ctrl-C la interrumpe y deja que
+    // el error `Cancelled` limpie por el camino normal
     let _ = ctrlc::set_handler(move || {
+        if interrupt::request() {
+            eprintln!("\nInterrupted: stopping the key derivation and wiping its memory (Ctrl-C again to exit now)...");
+            return;
+        }
         eprintln!("\nReceived termination signal. Performing secure cleanup...");
+        persistence::wipe_pending();
         secure_cleanup();
         std::process::exit(130); // 128 + 2 (SIGINT)
     });
@@ -39,11 +54,12 @@ pub fn setup_comprehensive_security() -> Result<()> {
     memory::configure_memory_limits()?;
 
     // Intentar deshabilitar swap para el proceso
-    if let Err(_) = memory::disable_swap_for_process() {
+    if memory::disable_swap_for_process().is_err() {
         eprintln!("Warning: Could not disable swap for process - sensitive data may be written to disk");
     }
 
     // Configurar limpieza de señales
+    #[cfg(feature = "cli")]
     setup_security_cleanup();
 
     Ok(())
@@ -54,6 +70,11 @@ pub fn secure_cleanup() {
     // Limpiar variables de entorno sensibles si las hay
     clear_environment_variables();
 
+    // En modo solo RAM, confirmar qué llegó a disco
+    if let Some(report) = persistence::persistence_report() {
+        eprintln!("{}", report);
+    }
+
     // Limpiar información del proceso
     process::cleanup_process_info();
 
@@ -125,8 +146,15 @@ impl Drop for SecureString {
 }
 
 impl From<String> for SecureString {
+    /// Toma el buffer sin copiarlo: no queda una copia sin limpiar
     fn from(s: String) -> Self {
-        Self::new(&s)
+        Self { data: s.into_bytes() }
+    }
+}
+
+impl From<Zeroizing<String>> for SecureString {
+    fn from(mut s: Zeroizing<String>) -> Self {
+        Self::from(std::mem::take(&mut *s))
     }
 }
 
@@ -136,6 +164,15 @@ impl From<&str> for SecureString {
     }
 }
 
+// Solo deserialización (p.ej. contraseñas en archivos de trabajo batch);
+// un SecureString nunca se serializa de vuelta
+impl<'de> serde::Deserialize<'de> for SecureString {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = zeroize::Zeroizing::new(String::deserialize(deserializer)?);
+        Ok(SecureString::new(&value))
+    }
+}
+
 /// Estructura para manejar datos binarios sensibles con memoria bloqueada
 pub struct SecureBytes {
     data: memory::LockedBuffer,
@@ -229,6 +266,7 @@ pub mod utils {
     }
 
     /// Crear SecureString desde entrada de usuario
+    #[cfg(feature = "cli")]
     pub fn secure_string_from_input(prompt: &str) -> Result<SecureString> {
         use rpassword::read_password;
         use std::io::{self, Write};
@@ -258,7 +296,7 @@ pub fn security_audit() -> SecurityAuditReport {
     }
 
     // Verificar límites de memoria
-    let (current_limit, max_limit) = memory::check_memory_lock_limits();
+    let (current_limit, _max_limit) = memory::check_memory_lock_limits();
     if current_limit == 0 {
         report.add_warning("No memory locking limits configured".to_string());
     } else if current_limit < 64 * 1024 * 1024 {
@@ -279,6 +317,7 @@ pub fn security_audit() -> SecurityAuditReport {
 }
 
 /// Reporte de auditoría de seguridad
+#[derive(serde::Serialize)]
 pub struct SecurityAuditReport {
     critical_issues: Vec<String>,
     warnings: Vec<String>,
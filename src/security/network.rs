@@ -0,0 +1,107 @@
+//! Comprobación de aislamiento de red (air gap)
+//!
+//! Enumera las interfaces de red con `getifaddrs` y devuelve las que están
+//! levantadas y no son de loopback. Es una comprobación de política, no una
+//! garantía: una interfaz caída puede levantarse después, y un módem USB
+//! que no se presente como interfaz no se ve.
+
+use crate::error::{SCypherError, Result};
+
+/// Qué hacer si hay interfaces de red activas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AirgapPolicy {
+    /// Negarse a continuar
+    Require,
+    /// Avisar y continuar
+    Warn,
+}
+
+impl AirgapPolicy {
+    /// Nombres aceptados en la línea de comandos
+    pub const NAMES: [&'static str; 2] = ["require", "warn"];
+
+    /// Política por nombre
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "require" => Some(AirgapPolicy::Require),
+            "warn" => Some(AirgapPolicy::Warn),
+            _ => None,
+        }
+    }
+}
+
+/// Nombres (sin repetir) de las interfaces levantadas que no son de loopback
+fn external_interfaces<I: IntoIterator<Item = (String, bool, bool)>>(interfaces: I) -> Vec<String> {
+    let mut names: Vec<String> = interfaces.into_iter()
+        .filter(|(_, up, loopback)| *up && !loopback)
+        .map(|(name, _, _)| name)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Interfaces de red levantadas que no son de loopback
+pub fn active_interfaces() -> Result<Vec<String>> {
+    #[cfg(unix)]
+    {
+        let mut interfaces = Vec::new();
+        // SAFETY: la lista de getifaddrs se recorre sin modificarla y se libera una vez
+        unsafe {
+            let mut list: *mut libc::ifaddrs = std::ptr::null_mut();
+            if libc::getifaddrs(&mut list) != 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            let mut entry = list;
+            while !entry.is_null() {
+                let flags = (*entry).ifa_flags as libc::c_int;
+                let name = std::ffi::CStr::from_ptr((*entry).ifa_name).to_string_lossy().into_owned();
+                interfaces.push((name, flags & libc::IFF_UP != 0, flags & libc::IFF_LOOPBACK != 0));
+                entry = (*entry).ifa_next;
+            }
+            libc::freeifaddrs(list);
+        }
+        Ok(external_interfaces(interfaces))
+    }
+
+    #[cfg(not(unix))]
+    {
+        Err(SCypherError::UnsupportedPlatform)
+    }
+}
+
+/// Aplicar la política: error con `Require`, aviso por stderr con `Warn`
+pub fn check_airgap(policy: AirgapPolicy) -> Result<()> {
+    let active = active_interfaces()?;
+    if active.is_empty() {
+        return Ok(());
+    }
+    match policy {
+        AirgapPolicy::Require => Err(SCypherError::NetworkActive(active)),
+        AirgapPolicy::Warn => {
+            eprintln!("⚠️  Warning: network interfaces are up: {}", active.join(", "));
+            eprintln!("   Disconnect them before handling a real seed phrase.");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_interfaces() {
+        let interfaces = [
+            ("lo".to_string(), true, true),
+            ("eth0".to_string(), true, false),
+            ("eth0".to_string(), true, false), // una entrada por familia de direcciones
+            ("wlan0".to_string(), false, false),
+        ];
+        assert_eq!(external_interfaces(interfaces), vec!["eth0".to_string()]);
+        assert!(external_interfaces([("lo".to_string(), true, true)]).is_empty());
+
+        assert_eq!(AirgapPolicy::from_name("warn"), Some(AirgapPolicy::Warn));
+        assert_eq!(AirgapPolicy::from_name("off"), None);
+    }
+}
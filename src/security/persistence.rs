@@ -0,0 +1,212 @@
+//! Modo solo RAM para sistemas en vivo y raíces de solo lectura
+//!
+//! En un USB en vivo (Tails, Debian live) o con la raíz montada de solo
+//! lectura no se debe escribir nada que no se haya pedido. En modo solo RAM
+//! no se ofrece guardar el resultado y solo se escriben los destinos
+//! explícitos (`-o` y similares). SCypher no guarda caché en ningún modo,
+//! ni configuración salvo con `preset save` y el contador de descifrados
+//! fallidos (`cli::backoff`, nunca en modo solo RAM); lo único que llega a
+//! disco son esos archivos, y al salir se confirma cuáles fueron
+//! (normalmente ninguno).
+//!
+//! En cualquier modo, un archivo de salida a medio escribir (error o
+//! Ctrl-C durante la escritura) se sobrescribe con ceros y se borra en lugar
+//! de dejar en disco parte del resultado: ver `PendingWrite`.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Modo solo RAM activo
+static RAM_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Archivos escritos durante la ejecución
+static WRITTEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Archivos de salida que se están escribiendo, por guarda
+///
+/// Puede haber varios a la vez (lote, cola de trabajos, daemon): cada
+/// `PendingWrite` quita solo su propia entrada.
+static PENDING: Mutex<BTreeMap<u64, String>> = Mutex::new(BTreeMap::new());
+
+/// Identificador de la siguiente guarda
+static NEXT_PENDING: AtomicU64 = AtomicU64::new(0);
+
+/// Escritura de un archivo de salida en curso
+///
+/// Si se suelta sin `commit` (error a mitad) el archivo se borra de forma
+/// segura; `wipe_pending` hace lo mismo desde el manejador de Ctrl-C.
+#[must_use = "drop without commit wipes the file"]
+pub struct PendingWrite {
+    id: u64,
+    path: String,
+    committed: bool,
+}
+
+impl PendingWrite {
+    /// Marcar `path` como escritura en curso
+    pub fn begin(path: &str) -> Self {
+        let id = NEXT_PENDING.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut pending) = PENDING.lock() {
+            pending.insert(id, path.to_string());
+        }
+        Self { id, path: path.to_string(), committed: false }
+    }
+
+    /// La escritura terminó bien: el archivo se conserva
+    pub fn commit(mut self) {
+        self.committed = true;
+        record_write(&self.path);
+    }
+}
+
+impl Drop for PendingWrite {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = PENDING.lock() {
+            pending.remove(&self.id);
+        }
+        if !self.committed {
+            wipe_file(&self.path);
+        }
+    }
+}
+
+/// Sobrescribir con ceros y borrar un archivo (mejor esfuerzo)
+fn wipe_file(path: &str) {
+    use std::io::Write;
+
+    if let Ok(mut file) = std::fs::OpenOptions::new().write(true).open(path) {
+        let len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        let zeros = [0u8; 4096];
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(zeros.len() as u64) as usize;
+            if file.write_all(&zeros[..chunk]).is_err() {
+                break;
+            }
+            remaining -= chunk as u64;
+        }
+        let _ = file.sync_all();
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+/// Borrar de forma segura las salidas a medio escribir, si las hay (al abortar)
+pub fn wipe_pending() {
+    let paths = PENDING.lock().map(|mut pending| std::mem::take(&mut *pending)).unwrap_or_default();
+    for path in paths.into_values() {
+        wipe_file(&path);
+        eprintln!("Partially written output removed: {}", path);
+    }
+}
+
+/// Activar el modo solo RAM
+pub fn enable_ram_only() {
+    RAM_ONLY.store(true, Ordering::Relaxed);
+}
+
+/// Si no se deben ofrecer ni hacer escrituras implícitas
+pub fn is_ram_only() -> bool {
+    RAM_ONLY.load(Ordering::Relaxed)
+}
+
+/// Anotar un archivo escrito (para el informe de salida)
+pub fn record_write(path: &str) {
+    if let Ok(mut written) = WRITTEN.lock() {
+        written.push(path.to_string());
+    }
+}
+
+/// Si la línea de comandos del kernel es la de un sistema en vivo
+fn is_live_cmdline(cmdline: &str) -> bool {
+    cmdline.split_whitespace().any(|arg| arg == "boot=live" || arg == "boot=casper")
+}
+
+/// Sistema en vivo o raíz de solo lectura
+pub fn detect_live_system() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: statvfs escribe solo en la estructura local
+        let read_only = unsafe {
+            let mut stats: libc::statvfs = std::mem::zeroed();
+            libc::statvfs(c"/".as_ptr(), &mut stats) == 0 && stats.f_flag & libc::ST_RDONLY != 0
+        };
+        read_only || std::fs::read_to_string("/proc/cmdline").is_ok_and(|cmdline| is_live_cmdline(&cmdline))
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Texto del informe de salida
+fn report(written: &[String]) -> String {
+    if written.is_empty() {
+        "✓ RAM-only mode: nothing was written to disk".to_string()
+    } else {
+        format!("RAM-only mode: only the requested files were written: {}", written.join(", "))
+    }
+}
+
+/// Informe de lo que se escribió, solo en modo solo RAM
+pub fn persistence_report() -> Option<String> {
+    if !is_ram_only() {
+        return None;
+    }
+    let written = WRITTEN.lock().map(|written| written.clone()).unwrap_or_default();
+    Some(report(&written))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_detection_and_report() {
+        assert!(is_live_cmdline("BOOT_IMAGE=/live/vmlinuz boot=live config nopersistence"));
+        assert!(!is_live_cmdline("BOOT_IMAGE=/vmlinuz root=/dev/sda1 ro quiet"));
+
+        assert!(report(&[]).contains("nothing was written"));
+        assert!(report(&["out.txt".to_string()]).contains("out.txt"));
+    }
+
+    #[test]
+    fn test_pending_write_wiped_unless_committed() {
+        let dir = std::env::temp_dir();
+        let aborted = dir.join(format!("scypher_pending_{}.txt", std::process::id()));
+        let aborted = aborted.to_str().unwrap();
+
+        let pending = PendingWrite::begin(aborted);
+        std::fs::write(aborted, "partial result").unwrap();
+        drop(pending);
+        assert!(!std::path::Path::new(aborted).exists());
+
+        let pending = PendingWrite::begin(aborted);
+        std::fs::write(aborted, "full result").unwrap();
+        pending.commit();
+        assert_eq!(std::fs::read_to_string(aborted).unwrap(), "full result");
+        std::fs::remove_file(aborted).unwrap();
+    }
+
+    #[test]
+    fn test_overlapping_pending_writes() {
+        let dir = std::env::temp_dir();
+        let first = dir.join(format!("scypher_pending_first_{}.txt", std::process::id()));
+        let second = dir.join(format!("scypher_pending_second_{}.txt", std::process::id()));
+        let (first, second) = (first.to_str().unwrap(), second.to_str().unwrap());
+        let is_pending = |path: &str| PENDING.lock().unwrap().values().any(|pending| pending == path);
+
+        // Terminar la primera no borra el registro de la segunda
+        let first_write = PendingWrite::begin(first);
+        let second_write = PendingWrite::begin(second);
+        std::fs::write(first, "first").unwrap();
+        std::fs::write(second, "partial").unwrap();
+        first_write.commit();
+        assert!(!is_pending(first));
+        assert!(is_pending(second));
+        drop(second_write);
+        assert!(!std::path::Path::new(second).exists());
+        std::fs::remove_file(first).unwrap();
+    }
+}
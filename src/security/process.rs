@@ -190,10 +190,122 @@ pub fn cleanup_process_info() {
     std::env::remove_var("RUST_LOG");
 }
 
+/// Si el proceso corre como root (Unix) o elevado como Administrador (Windows)
+pub fn is_elevated() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid no tiene precondiciones
+        unsafe { libc::geteuid() == 0 }
+    }
+
+    #[cfg(windows)]
+    {
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+        use winapi::um::securitybaseapi::GetTokenInformation;
+        use winapi::um::winnt::{TokenElevation, HANDLE, TOKEN_ELEVATION, TOKEN_QUERY};
+
+        // SAFETY: token del propio proceso y estructura local del tamaño indicado
+        unsafe {
+            let mut token: HANDLE = std::ptr::null_mut();
+            if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+                return false;
+            }
+            let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+            let mut size = 0;
+            let ok = GetTokenInformation(
+                token,
+                TokenElevation,
+                (&mut elevation as *mut TOKEN_ELEVATION).cast(),
+                std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+                &mut size,
+            );
+            CloseHandle(token);
+            ok != 0 && elevation.TokenIsElevated != 0
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        false
+    }
+}
+
+/// Usuario que invocó `sudo` (uid y gid), si no es root
+fn sudo_ids(uid: Option<&str>, gid: Option<&str>) -> Option<(u32, u32)> {
+    let uid = uid?.parse().ok().filter(|&uid| uid != 0)?;
+    let gid = gid?.parse().ok().filter(|&gid| gid != 0)?;
+    Some((uid, gid))
+}
+
+/// No seguir con privilegios elevados (`--no-root`)
+///
+/// Sin privilegios no hace nada y devuelve `None`. Lanzado con `sudo` (para
+/// poder bloquear más memoria en RAM) sube primero el límite de memoria
+/// bloqueada, que se conserva, y vuelve al usuario que lo invocó antes de
+/// tocar ningún secreto: devuelve su uid. Como root sin `sudo`, o elevado
+/// en Windows, falla con `ElevatedPrivileges`.
+pub fn drop_root_privileges() -> Result<Option<u32>> {
+    if !is_elevated() {
+        return Ok(None);
+    }
+
+    #[cfg(unix)]
+    {
+        let sudo_uid = std::env::var("SUDO_UID").ok();
+        let sudo_gid = std::env::var("SUDO_GID").ok();
+        let Some((uid, gid)) = sudo_ids(sudo_uid.as_deref(), sudo_gid.as_deref()) else {
+            return Err(SCypherError::ElevatedPrivileges);
+        };
+
+        // SAFETY: llamadas al sistema con argumentos por valor o estructuras locales
+        unsafe {
+            let unlimited = libc::rlimit { rlim_cur: libc::RLIM_INFINITY, rlim_max: libc::RLIM_INFINITY };
+            let raised = libc::setrlimit(libc::RLIMIT_MEMLOCK, &unlimited) == 0;
+
+            // Grupos suplementarios, grupo y usuario, en ese orden
+            if libc::setgroups(0, std::ptr::null()) != 0
+                || libc::setgid(gid) != 0
+                || libc::setuid(uid) != 0
+            {
+                return Err(std::io::Error::last_os_error().into());
+            }
+
+            // Si root se pudiera recuperar, la bajada no sirve
+            if libc::setuid(0) == 0 || libc::geteuid() == 0 {
+                return Err(SCypherError::ElevatedPrivileges);
+            }
+
+            // El mlockall(MCL_FUTURE) hecho como root sigue activo; con el
+            // límite del usuario cada reserva grande (y el crecimiento de la
+            // pila) fallaría, así que se deshace
+            if !raised {
+                libc::munlockall();
+                eprintln!("Warning: Could not raise the memory lock limit; memory is no longer locked after dropping privileges");
+            }
+        }
+        Ok(Some(uid))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = sudo_ids;
+        Err(SCypherError::ElevatedPrivileges)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sudo_ids() {
+        assert_eq!(sudo_ids(Some("1000"), Some("1000")), Some((1000, 1000)));
+        assert_eq!(sudo_ids(Some("0"), Some("0")), None);
+        assert_eq!(sudo_ids(None, Some("1000")), None);
+        assert_eq!(sudo_ids(Some("abc"), Some("1000")), None);
+    }
+
     #[test]
     fn test_disable_core_dumps() {
         // Test que core dumps están deshabilitados
@@ -0,0 +1,261 @@
+//! Dónde acaba físicamente un archivo de salida
+//!
+//! No es lo mismo escribir la frase en un USB que luego se guarda en un
+//! cajón que en el disco interno, donde puede sobrevivir al borrado (copias
+//! de seguridad, sectores que el SSD reasigna). `locate` lo averigua a
+//! partir del dispositivo del directorio de destino: en Linux con
+//! `/proc/self/mountinfo` y `/sys/dev/block` (USB, tarjeta SD o el
+//! indicador `removable` del disco, atravesando particiones, LVM y LUKS);
+//! en otros sistemas el lugar queda como desconocido.
+//!
+//! Con `--require-removable` solo se escriben archivos en medios
+//! extraíbles. Lo demás, desconocido incluido, se rechaza antes de crear el
+//! archivo: ver `check_destination`.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{SCypherError, Result};
+
+/// Política activa: solo medios extraíbles
+static REQUIRE_REMOVABLE: AtomicBool = AtomicBool::new(false);
+
+/// Tipo de almacenamiento de un destino
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    /// USB, tarjeta SD u otro disco extraíble
+    Removable,
+    /// Disco interno (o virtual, en una máquina virtual)
+    Internal,
+    /// Memoria (tmpfs, zram): se pierde al apagar
+    Memory,
+    /// Sistema de archivos de red: vive en otra máquina
+    Network,
+    /// No se pudo determinar
+    Unknown,
+}
+
+/// Dónde vive un destino, con una descripción para mostrar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageLocation {
+    pub kind: StorageKind,
+    pub description: String,
+}
+
+impl StorageLocation {
+    fn new(kind: StorageKind, description: impl Into<String>) -> Self {
+        Self { kind, description: description.into() }
+    }
+}
+
+impl fmt::Display for StorageLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let note = match self.kind {
+            StorageKind::Removable => "removable media",
+            StorageKind::Internal => "internal storage, stays in this computer",
+            StorageKind::Memory => "in memory, lost at power-off",
+            StorageKind::Network => "on another machine",
+            StorageKind::Unknown => "could not tell whether it is removable",
+        };
+        write!(f, "{} ({})", self.description, note)
+    }
+}
+
+/// Activar `--require-removable`
+pub fn require_removable() {
+    REQUIRE_REMOVABLE.store(true, Ordering::Relaxed);
+}
+
+/// Si solo se permiten medios extraíbles
+pub fn is_removable_required() -> bool {
+    REQUIRE_REMOVABLE.load(Ordering::Relaxed)
+}
+
+/// Dónde vive `path`; si aún no existe, el directorio que lo contendrá
+pub fn locate(path: &Path) -> StorageLocation {
+    let existing = path.ancestors()
+        .find(|dir| !dir.as_os_str().is_empty() && dir.exists())
+        .unwrap_or(Path::new("."));
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::locate(existing).unwrap_or_else(|| StorageLocation::new(StorageKind::Unknown, "unknown device"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = existing;
+        StorageLocation::new(StorageKind::Unknown, "unknown device")
+    }
+}
+
+/// Comprobar la política antes de escribir `path` y devolver dónde vive
+pub fn check_destination(path: &Path) -> Result<StorageLocation> {
+    let location = locate(path);
+    if is_removable_required() && location.kind != StorageKind::Removable {
+        return Err(SCypherError::NotRemovable {
+            path: path.display().to_string(),
+            location: location.to_string(),
+        });
+    }
+    Ok(location)
+}
+
+/// Montaje de una línea de `/proc/self/mountinfo`
+#[derive(Debug, PartialEq, Eq)]
+struct Mount<'a> {
+    fstype: &'a str,
+    source: &'a str,
+}
+
+/// Montaje del dispositivo `major:minor` en el contenido de `mountinfo`
+///
+/// Formato: `id padre mayor:menor raíz punto opciones [etiquetas...] - tipo origen opciones`
+fn find_mount(mountinfo: &str, major: u32, minor: u32) -> Option<Mount<'_>> {
+    let device = format!("{}:{}", major, minor);
+    mountinfo.lines().find_map(|line| {
+        let (mount, filesystem) = line.split_once(" - ")?;
+        if mount.split_whitespace().nth(2)? != device {
+            return None;
+        }
+        let mut filesystem = filesystem.split_whitespace();
+        Some(Mount { fstype: filesystem.next()?, source: filesystem.next().unwrap_or("") })
+    })
+}
+
+/// Clasificar por tipo de sistema de archivos; `None` si depende del disco
+fn classify_filesystem(mount: &Mount) -> Option<StorageLocation> {
+    match mount.fstype {
+        "tmpfs" | "ramfs" => Some(StorageLocation::new(StorageKind::Memory, format!("RAM ({})", mount.fstype))),
+        "nfs" | "nfs4" | "cifs" | "smb3" | "smbfs" | "9p" | "afs" | "ceph" | "glusterfs" | "fuse.sshfs" | "davfs" => {
+            Some(StorageLocation::new(StorageKind::Network, format!("network share {} ({})", mount.source, mount.fstype)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs;
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    use std::path::{Path, PathBuf};
+
+    use super::{classify_filesystem, find_mount, StorageKind, StorageLocation};
+
+    /// Niveles de dispositivos apilados (LUKS sobre LVM sobre partición...)
+    const MAX_STACK: usize = 4;
+
+    pub(super) fn locate(path: &Path) -> Option<StorageLocation> {
+        let dev = fs::metadata(path).ok()?.dev();
+        let mountinfo = fs::read_to_string("/proc/self/mountinfo").ok()?;
+        let mount = find_mount(&mountinfo, libc::major(dev), libc::minor(dev))?;
+        if let Some(location) = classify_filesystem(&mount) {
+            return Some(location);
+        }
+
+        // Btrfs y otros usan un dispositivo anónimo: el disco es el origen del montaje
+        let block = if libc::major(dev) != 0 {
+            dev
+        } else {
+            let metadata = fs::metadata(mount.source).ok().filter(|metadata| metadata.file_type().is_block_device());
+            match metadata {
+                Some(metadata) => metadata.rdev(),
+                None => return Some(StorageLocation::new(StorageKind::Unknown, format!("{} filesystem", mount.fstype))),
+            }
+        };
+        let device = fs::canonicalize(format!("/sys/dev/block/{}:{}", libc::major(block), libc::minor(block))).ok()?;
+        Some(describe_disk(&underlying_disk(device)))
+    }
+
+    /// Disco físico bajo un dispositivo de bloques de sysfs
+    fn underlying_disk(mut device: PathBuf) -> PathBuf {
+        for _ in 0..MAX_STACK {
+            // Partición: el disco es el directorio padre
+            if device.join("partition").exists() {
+                if let Some(disk) = device.parent() {
+                    device = disk.to_path_buf();
+                }
+            }
+            // Device mapper (LVM, LUKS): el primer dispositivo de debajo
+            let slave = fs::read_dir(device.join("slaves")).ok()
+                .and_then(|mut entries| entries.next())
+                .and_then(|entry| entry.ok())
+                .and_then(|entry| fs::canonicalize(entry.path()).ok());
+            match slave {
+                Some(slave) => device = slave,
+                None => break,
+            }
+        }
+        device
+    }
+
+    fn read_attribute(path: PathBuf) -> Option<String> {
+        let value = fs::read_to_string(path).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn describe_disk(disk: &Path) -> StorageLocation {
+        let name = disk.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let sysfs = disk.to_string_lossy();
+        // Los discos virtio dan como fabricante un identificador PCI ("0x1af4")
+        let vendor = read_attribute(disk.join("device/vendor")).filter(|vendor| !vendor.starts_with("0x"));
+        let model = [vendor, read_attribute(disk.join("device/model"))]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let label = |what: &str| match model.is_empty() {
+            true => format!("{} {}", what, name),
+            false => format!("{} {} ({})", what, model, name),
+        };
+
+        if sysfs.contains("/usb") {
+            return StorageLocation::new(StorageKind::Removable, label("USB drive"));
+        }
+        // La tarjeta SD de arranque de una Raspberry Pi también cuenta: se puede sacar
+        if read_attribute(disk.join("device/type")).as_deref() == Some("SD") {
+            return StorageLocation::new(StorageKind::Removable, label("SD card"));
+        }
+        if read_attribute(disk.join("removable")).as_deref() == Some("1") {
+            return StorageLocation::new(StorageKind::Removable, label("removable drive"));
+        }
+        if name.starts_with("zram") {
+            return StorageLocation::new(StorageKind::Memory, format!("compressed RAM disk {}", name));
+        }
+        if name.starts_with("loop") {
+            // El archivo de respaldo puede estar en cualquier sitio
+            return StorageLocation::new(StorageKind::Unknown, format!("loop device {}", name));
+        }
+        StorageLocation::new(StorageKind::Internal, label("internal disk"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mountinfo_and_policy() {
+        let mountinfo = "\
+26 25 0:24 / /dev/shm rw,relatime - tmpfs tmpfs rw,size=6158152k
+28 1 254:0 / / rw,relatime shared:1 - ext4 /dev/vda rw,discard
+40 28 0:45 / /mnt/backup rw,relatime - nfs4 server:/export rw,vers=4.2
+";
+        assert_eq!(find_mount(mountinfo, 254, 0), Some(Mount { fstype: "ext4", source: "/dev/vda" }));
+        assert_eq!(find_mount(mountinfo, 8, 1), None);
+
+        let tmpfs = find_mount(mountinfo, 0, 24).unwrap();
+        assert_eq!(classify_filesystem(&tmpfs).unwrap().kind, StorageKind::Memory);
+        let nfs = find_mount(mountinfo, 0, 45).unwrap();
+        let location = classify_filesystem(&nfs).unwrap();
+        assert_eq!(location.kind, StorageKind::Network);
+        assert!(location.to_string().contains("server:/export"));
+        assert!(classify_filesystem(&find_mount(mountinfo, 254, 0).unwrap()).is_none());
+
+        // Un archivo que aún no existe se ubica por su directorio
+        let dir = std::env::temp_dir();
+        assert_eq!(locate(&dir.join("scypher_missing/out.txt")), locate(&dir));
+    }
+}
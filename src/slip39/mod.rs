@@ -0,0 +1,362 @@
+//! Lectura de shares SLIP-39 (Shamir de Trezor)
+//!
+//! Reconstruye el master secret a partir de un conjunto de shares para que
+//! quien migra desde una copia Shamir pueda protegerlo con SCypher sin una
+//! herramienta aparte. Solo se implementa la recuperación: comprobación
+//! RS1024 de cada share, interpolación por grupos con su digest y
+//! descifrado Feistel con la passphrase.
+//!
+//! El master secret no es una frase BIP39: la cartera SLIP-39 lo usa
+//! directamente como semilla BIP32. Convertido a frase BIP39 (como entropía)
+//! sirve para guardarlo y recuperarlo, pero esa frase importada en una
+//! cartera BIP39 daría cuentas distintas.
+
+mod shamir;
+pub mod wordlist;
+
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Result};
+
+/// Palabras de cabecera (identificador y grupos) y de checksum
+const HEADER_WORDS: usize = 4;
+const CHECKSUM_WORDS: usize = 3;
+/// Share más corto: master secret de 128 bits
+const MIN_WORDS: usize = 20;
+/// Longitud mínima del master secret en bytes
+const MIN_SECRET_LEN: usize = 16;
+
+/// Índices reservados del polinomio para el secreto y su digest
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+const DIGEST_LEN: usize = 4;
+
+/// Iteraciones de PBKDF2 (por exponente 0) repartidas entre las 4 rondas Feistel
+const BASE_ITERATION_COUNT: u32 = 10_000;
+const ROUND_COUNT: u8 = 4;
+
+const RS1024_GENERATOR: [u32; 10] = [
+    0x00E0_E040, 0x01C1_C080, 0x0383_8100, 0x0707_0200, 0x0E0E_0009,
+    0x1C0C_2412, 0x3808_6C24, 0x3090_FC48, 0x21B1_F890, 0x03F3_F120,
+];
+
+fn invalid(what: impl std::fmt::Display) -> SCypherError {
+    SCypherError::InvalidRequest(format!("invalid SLIP-39 share: {}", what))
+}
+
+/// Share SLIP-39 decodificado
+#[derive(Clone)]
+pub struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl std::fmt::Debug for Share {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Share")
+            .field("identifier", &self.identifier)
+            .field("group_index", &self.group_index)
+            .field("member_index", &self.member_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Share {
+    /// Decodificar y verificar un share (palabras separadas por espacios)
+    pub fn parse(mnemonic: &str) -> Result<Self> {
+        let values = mnemonic
+            .split_whitespace()
+            .map(|word| wordlist::word_index(word).ok_or_else(|| invalid(format!("unknown word '{}'", word))))
+            .collect::<Result<Vec<u16>>>()?;
+        if values.len() < MIN_WORDS {
+            return Err(invalid(format!("{} words, at least {} expected", values.len(), MIN_WORDS)));
+        }
+
+        let extendable = values[1] >> 4 & 1 == 1;
+        let customization: &[u8] = if extendable { b"shamir_extendable" } else { b"shamir" };
+        if rs1024_polymod(customization.iter().map(|&b| b as u16).chain(values.iter().copied())) != 1 {
+            return Err(invalid("checksum mismatch"));
+        }
+
+        // Cabecera: id(15) ext(1) e(4) | GI(4) Gt(4) g(4) I(4) t(4)
+        let header = |first: usize| ((values[first] as u32) << 10) | values[first + 1] as u32;
+        let (id_bits, group_bits) = (header(0), header(2));
+        let nibble = |shift: u32| ((group_bits >> shift) & 0xF) as u8;
+
+        let share = Share {
+            identifier: (id_bits >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_bits & 0xF) as u8,
+            group_index: nibble(16),
+            group_threshold: nibble(12) + 1,
+            group_count: nibble(8) + 1,
+            member_index: nibble(4),
+            member_threshold: nibble(0) + 1,
+            value: unpack_value(&values[HEADER_WORDS..values.len() - CHECKSUM_WORDS])?,
+        };
+        if share.group_threshold > share.group_count {
+            return Err(invalid("group threshold exceeds the group count"));
+        }
+        Ok(share)
+    }
+}
+
+/// Quitar el relleno inicial (ceros) de los valores de 10 bits y pasarlos a bytes
+fn unpack_value(values: &[u16]) -> Result<Zeroizing<Vec<u8>>> {
+    let bits = values.len() * 10;
+    let padding = bits % 16;
+    if padding > 8 {
+        return Err(invalid("bad length"));
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(bits / 8));
+    let mut accumulator = 0u32;
+    let mut pending = 0usize;
+    let mut skip = padding;
+    for &value in values {
+        accumulator = (accumulator << 10) | value as u32;
+        pending += 10;
+        if skip > 0 {
+            pending -= skip;
+            if accumulator >> pending != 0 {
+                return Err(invalid("non-zero padding"));
+            }
+            skip = 0;
+        }
+        while pending >= 8 {
+            pending -= 8;
+            bytes.push((accumulator >> pending) as u8);
+        }
+        accumulator &= (1 << pending) - 1;
+    }
+
+    if bytes.len() < MIN_SECRET_LEN {
+        return Err(invalid("share value is too short"));
+    }
+    Ok(bytes)
+}
+
+fn rs1024_polymod(values: impl Iterator<Item = u16>) -> u32 {
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 20;
+        checksum = ((checksum & 0xF_FFFF) << 10) ^ value as u32;
+        for (bit, generator) in RS1024_GENERATOR.iter().enumerate() {
+            if top >> bit & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Conjunto de shares de una misma copia, que se va completando
+#[derive(Debug, Default)]
+pub struct ShareSet {
+    shares: Vec<Share>,
+}
+
+impl ShareSet {
+    /// Conjunto vacío
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Añadir un share; debe pertenecer a la misma copia que los anteriores
+    ///
+    /// Un share repetido se ignora.
+    pub fn add(&mut self, mnemonic: &str) -> Result<()> {
+        let share = Share::parse(mnemonic)?;
+
+        if let Some(first) = self.shares.first() {
+            let same_backup = share.identifier == first.identifier
+                && share.extendable == first.extendable
+                && share.iteration_exponent == first.iteration_exponent
+                && share.group_threshold == first.group_threshold
+                && share.group_count == first.group_count
+                && share.value.len() == first.value.len();
+            if !same_backup {
+                return Err(invalid("it belongs to a different backup"));
+            }
+        }
+
+        for existing in self.shares.iter().filter(|existing| existing.group_index == share.group_index) {
+            if existing.member_threshold != share.member_threshold {
+                return Err(invalid("member threshold differs within the group"));
+            }
+            if existing.member_index == share.member_index {
+                if existing.value != share.value {
+                    return Err(invalid("two different shares with the same index"));
+                }
+                return Ok(());
+            }
+        }
+
+        self.shares.push(share);
+        Ok(())
+    }
+
+    /// Shares añadidos
+    pub fn len(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Si no hay ningún share
+    pub fn is_empty(&self) -> bool {
+        self.shares.is_empty()
+    }
+
+    /// Grupos con shares suficientes y grupos necesarios
+    pub fn progress(&self) -> (usize, usize) {
+        let needed = self.shares.first().map_or(1, |share| share.group_threshold as usize);
+        (self.complete_groups().len(), needed)
+    }
+
+    /// Si ya se puede reconstruir el master secret
+    pub fn is_complete(&self) -> bool {
+        let (complete, needed) = self.progress();
+        !self.shares.is_empty() && complete >= needed
+    }
+
+    /// Reconstruir y descifrar el master secret con `passphrase`
+    pub fn master_secret(&self, passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let first = self.shares.first().ok_or_else(|| invalid("no shares"))?;
+        let groups = self.complete_groups();
+        if groups.len() < first.group_threshold as usize {
+            return Err(SCypherError::InvalidRequest(format!(
+                "not enough SLIP-39 shares: {} of {} groups complete",
+                groups.len(),
+                first.group_threshold,
+            )));
+        }
+
+        let mut group_secrets = Vec::new();
+        for &group_index in groups.iter().take(first.group_threshold as usize) {
+            let members: Vec<&Share> = self.shares.iter().filter(|share| share.group_index == group_index).collect();
+            let threshold = members[0].member_threshold as usize;
+            let points: Vec<(u8, &[u8])> = members.iter().take(threshold)
+                .map(|share| (share.member_index, &share.value[..]))
+                .collect();
+            group_secrets.push((group_index, recover_secret(&points)?));
+        }
+
+        let points: Vec<(u8, &[u8])> = group_secrets.iter().map(|(index, secret)| (*index, &secret[..])).collect();
+        let encrypted = recover_secret(&points)?;
+        Ok(decrypt(&encrypted, passphrase, first))
+    }
+
+    fn complete_groups(&self) -> Vec<u8> {
+        let mut groups: Vec<u8> = Vec::new();
+        for share in &self.shares {
+            let members = self.shares.iter().filter(|other| other.group_index == share.group_index).count();
+            if members >= share.member_threshold as usize && !groups.contains(&share.group_index) {
+                groups.push(share.group_index);
+            }
+        }
+        groups
+    }
+}
+
+/// Secreto compartido por `points`; con umbral > 1 se comprueba su digest
+fn recover_secret(points: &[(u8, &[u8])]) -> Result<Zeroizing<Vec<u8>>> {
+    if points.len() == 1 {
+        return Ok(Zeroizing::new(points[0].1.to_vec()));
+    }
+
+    let secret = shamir::interpolate(points, SECRET_INDEX);
+    let digest_share = shamir::interpolate(points, DIGEST_INDEX);
+    let (digest, random) = digest_share.split_at(DIGEST_LEN);
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(random).expect("HMAC accepts any key length");
+    mac.update(&secret);
+    if mac.finalize().into_bytes()[..DIGEST_LEN] != *digest {
+        return Err(invalid("shares do not combine (digest mismatch)"));
+    }
+    Ok(secret)
+}
+
+/// Deshacer la red Feistel de 4 rondas que cifra el master secret
+fn decrypt(encrypted: &[u8], passphrase: &str, share: &Share) -> Zeroizing<Vec<u8>> {
+    let half = encrypted.len() / 2;
+    let mut left = Zeroizing::new(encrypted[..half].to_vec());
+    let mut right = Zeroizing::new(encrypted[half..].to_vec());
+
+    let mut salt_prefix = Vec::new();
+    if !share.extendable {
+        salt_prefix.extend_from_slice(b"shamir");
+        salt_prefix.extend_from_slice(&share.identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << share.iteration_exponent) / ROUND_COUNT as u32;
+
+    for round in (0..ROUND_COUNT).rev() {
+        let mut password = Zeroizing::new(Vec::with_capacity(1 + passphrase.len()));
+        password.push(round);
+        password.extend_from_slice(passphrase.as_bytes());
+        let mut salt = salt_prefix.clone();
+        salt.extend_from_slice(&right);
+
+        let mut round_key = Zeroizing::new(vec![0u8; half]);
+        pbkdf2_hmac::<Sha256>(&password, &salt, iterations, &mut round_key);
+        for (byte, key) in left.iter_mut().zip(round_key.iter()) {
+            *byte ^= key;
+        }
+        std::mem::swap(&mut left, &mut right);
+    }
+
+    let mut secret = Zeroizing::new(Vec::with_capacity(encrypted.len()));
+    secret.extend_from_slice(&right);
+    secret.extend_from_slice(&left);
+    secret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combine(shares: &[&str], passphrase: &str) -> Result<String> {
+        let mut set = ShareSet::new();
+        for share in shares {
+            set.add(share)?;
+        }
+        Ok(hex::encode(&set.master_secret(passphrase)?[..]))
+    }
+
+    #[test]
+    fn test_slip39_vectors() {
+        // Vectores oficiales de SLIP-39 (passphrase "TREZOR")
+        assert_eq!(
+            combine(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"], "TREZOR").unwrap(),
+            "bb54aac4b89dc868ba37d9cc21b2cece"
+        );
+        assert_eq!(
+            combine(&[
+                "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ], "TREZOR").unwrap(),
+            "b43ceb7e57a0ea8766221624d01b0864"
+        );
+    }
+
+    #[test]
+    fn test_invalid_shares() {
+        // Checksum roto (última palabra cambiada)
+        assert!(Share::parse("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney").is_err());
+        assert!(Share::parse("duckling enlarge academic").is_err());
+
+        let mut set = ShareSet::new();
+        set.add("shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed").unwrap();
+        assert!(!set.is_complete());
+        assert!(set.master_secret("TREZOR").is_err());
+        assert!(set.add("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard").is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Interpolación de Lagrange en GF(256) (polinomio de Rijndael, x^8+x^4+x^3+x+1)
+
+use std::sync::OnceLock;
+
+use zeroize::Zeroizing;
+
+/// Tablas de exponenciales y logaritmos con generador 3
+struct Tables {
+    exp: [u8; 255],
+    log: [u8; 256],
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = Tables { exp: [0; 255], log: [0; 256] };
+        let mut value: u16 = 1;
+        for power in 0..255 {
+            tables.exp[power] = value as u8;
+            tables.log[value as usize] = power as u8;
+            // value *= 3 (value * 2 + value) con reducción por 0x11B
+            value = (value << 1) ^ value;
+            if value & 0x100 != 0 {
+                value ^= 0x11B;
+            }
+        }
+        tables
+    })
+}
+
+/// Evaluar en `x` el polinomio que pasa por los puntos `(x_i, y_i)`
+///
+/// Los `x_i` deben ser distintos y todos los `y_i` de la misma longitud.
+pub(super) fn interpolate(points: &[(u8, &[u8])], x: u8) -> Zeroizing<Vec<u8>> {
+    if let Some(&(_, y)) = points.iter().find(|&&(x_i, _)| x_i == x) {
+        return Zeroizing::new(y.to_vec());
+    }
+
+    let tables = tables();
+    let log = |value: u8| tables.log[value as usize] as i64;
+    let log_product: i64 = points.iter().map(|&(x_i, _)| log(x_i ^ x)).sum();
+
+    let mut result = Zeroizing::new(vec![0u8; points[0].1.len()]);
+    for &(x_i, y) in points {
+        // log(0) vale 0 en la tabla, así que el término j = i no cuenta
+        let log_denominator: i64 = points.iter().map(|&(x_j, _)| log(x_i ^ x_j)).sum();
+        let log_basis = (log_product - log(x_i ^ x) - log_denominator).rem_euclid(255);
+
+        for (output, &y_byte) in result.iter_mut().zip(y) {
+            if y_byte != 0 {
+                *output ^= tables.exp[((log(y_byte) + log_basis) % 255) as usize];
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate_line() {
+        // y = 5 + 7x con la aritmética de GF(256): y(1) = 2, y(2) = 0x0B, y(0) = 5
+        let tables = tables();
+        let mul = |a: u8, b: u8| if a == 0 || b == 0 {
+            0
+        } else {
+            tables.exp[(tables.log[a as usize] as usize + tables.log[b as usize] as usize) % 255]
+        };
+        let y1 = [5 ^ mul(7, 1)];
+        let y2 = [5 ^ mul(7, 2)];
+        let y3 = [5 ^ mul(7, 3)];
+
+        assert_eq!(*interpolate(&[(1, &y1), (2, &y2)], 0), [5]);
+        assert_eq!(*interpolate(&[(1, &y1), (2, &y2)], 3), y3);
+        assert_eq!(*interpolate(&[(1, &y1), (2, &y2)], 2), y2);
+    }
+}
@@ -0,0 +1,67 @@
+//! Portadoras BMP: sin comprimir, 24 o 32 bits por píxel
+
+use crate::error::{Defect, SCypherError, Result};
+
+use super::{Carrier, Encoding, ImageFormat, Layout};
+
+/// BITMAPFILEHEADER (14 bytes) más un BITMAPINFOHEADER (40 bytes) como mínimo
+const MIN_HEADER_LEN: usize = 54;
+
+/// `BI_RGB`: píxeles sin comprimir
+const BI_RGB: u32 = 0;
+
+fn malformed(defect: Defect, message: impl Into<String>) -> SCypherError {
+    SCypherError::malformed("BMP", defect, message)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+pub(super) fn parse(bytes: &[u8]) -> Result<Carrier> {
+    if bytes.len() < MIN_HEADER_LEN {
+        return Err(malformed(Defect::Truncated, "header is incomplete"));
+    }
+
+    let pixel_offset = u32_at(bytes, 10) as usize;
+    let header_len = u32_at(bytes, 14) as usize;
+    let width = u32_at(bytes, 18) as i32;
+    let height = u32_at(bytes, 22) as i32;
+    let bits_per_pixel = u16_at(bytes, 28);
+    let compression = u32_at(bytes, 30);
+
+    if header_len < 40 || u16_at(bytes, 26) != 1 {
+        return Err(malformed(Defect::Unsupported, "only BITMAPINFOHEADER and later headers are supported"));
+    }
+    if compression != BI_RGB || !matches!(bits_per_pixel, 24 | 32) {
+        return Err(malformed(Defect::Unsupported, format!(
+            "only uncompressed 24 or 32-bit images are supported (found {} bits, compression {})", bits_per_pixel, compression,
+        )));
+    }
+    if width <= 0 || height == 0 {
+        return Err(malformed(Defect::Inconsistent, format!("invalid size {}x{}", width, height)));
+    }
+
+    // Filas alineadas a 4 bytes; con altura negativa van de arriba abajo (da igual aquí)
+    let (width, rows) = (width as usize, height.unsigned_abs() as usize);
+    let pixel_stride = usize::from(bits_per_pixel / 8);
+    let row_stride = (width * pixel_stride).div_ceil(4) * 4;
+    let end = row_stride
+        .checked_mul(rows)
+        .and_then(|len| len.checked_add(pixel_offset))
+        .ok_or_else(|| malformed(Defect::TooLarge, "image size overflows"))?;
+    if pixel_offset < MIN_HEADER_LEN || end > bytes.len() {
+        return Err(malformed(Defect::Truncated, "pixel data is incomplete"));
+    }
+
+    Ok(Carrier {
+        format: ImageFormat::Bmp,
+        data: bytes.to_vec(),
+        layout: Layout { start: pixel_offset, rows, width, row_stride, pixel_stride, colors: 3 },
+        encoding: Encoding::Bmp,
+    })
+}
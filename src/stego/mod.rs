@@ -0,0 +1,273 @@
+//! Ocultación del resultado en una imagen (LSB en PNG y BMP)
+//!
+//! Para quien necesita que no se sepa que existe una copia de seguridad: el
+//! resultado (la frase cifrada o un contenedor de `--armor`) se guarda en el
+//! bit menos significativo de los canales de color de una imagen que aporta
+//! el usuario. La imagen resultante se ve igual que la original.
+//!
+//! Los datos van enmarcados (longitud, datos y CRC32) y mezclados con un
+//! keystream fijo para que los bits no muestren estructura a simple vista.
+//! El keystream no es una clave: cualquiera con SCypher puede extraer los
+//! datos, que siguen protegidos por su propio cifrado. Tampoco resiste un
+//! estegoanálisis estadístico, y quien compare la imagen con la original
+//! verá la diferencia.
+//!
+//! Se admiten PNG de 8 bits por canal (gris o RGB, con o sin alfa; el alfa
+//! no se toca) y BMP sin comprimir de 24 o 32 bits. Un PNG se vuelve a
+//! comprimir al guardarlo (sin los chunks auxiliares); un BMP conserva todo
+//! salvo los bits usados. Cualquier conversión con pérdida (JPEG, redimensionar)
+//! destruye los datos.
+
+mod bmp;
+mod png;
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::error::{Defect, SCypherError, Result};
+
+/// Tamaño máximo de los datos ocultos
+pub const MAX_PAYLOAD_LEN: usize = 1 << 20;
+
+/// Longitud (4 bytes) y CRC32 (4 bytes) que acompañan a los datos
+const FRAME_OVERHEAD: usize = 8;
+
+/// Etiqueta del keystream que mezcla los bits
+const WHITENING_LABEL: &[u8] = b"scypher-stego-v1";
+
+/// Formato de una imagen portadora
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Bmp,
+}
+
+impl ImageFormat {
+    /// Reconocer el formato por su firma
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(ImageFormat::Png)
+        } else if bytes.starts_with(b"BM") {
+            Some(ImageFormat::Bmp)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Bmp => "BMP",
+        }
+    }
+}
+
+/// Posición de los bytes de color dentro de los datos de una imagen
+///
+/// Las muestras se recorren fila a fila y, en cada píxel, los `colors`
+/// primeros canales (el alfa, si lo hay, va detrás y se salta).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Layout {
+    start: usize,
+    rows: usize,
+    width: usize,
+    row_stride: usize,
+    pixel_stride: usize,
+    colors: usize,
+}
+
+impl Layout {
+    fn samples(&self) -> usize {
+        self.rows * self.width * self.colors
+    }
+
+    fn index(&self, sample: usize) -> usize {
+        let pixel = sample / self.colors;
+        let (row, column) = (pixel / self.width, pixel % self.width);
+        self.start + row * self.row_stride + column * self.pixel_stride + sample % self.colors
+    }
+}
+
+/// Cómo volver a guardar la imagen
+#[derive(Debug, Clone)]
+enum Encoding {
+    /// Píxeles decodificados que se vuelven a comprimir
+    Png(png::Header),
+    /// El archivo tal cual, modificado en el sitio
+    Bmp,
+}
+
+/// Imagen que puede llevar datos ocultos
+#[derive(Debug, Clone)]
+pub struct Carrier {
+    format: ImageFormat,
+    data: Vec<u8>,
+    layout: Layout,
+    encoding: Encoding,
+}
+
+impl Carrier {
+    /// Leer una imagen PNG o BMP admitida
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        match ImageFormat::detect(bytes) {
+            Some(ImageFormat::Png) => png::parse(bytes),
+            Some(ImageFormat::Bmp) => bmp::parse(bytes),
+            None => Err(SCypherError::malformed("image", Defect::Unsupported, "not a PNG or BMP image")),
+        }
+    }
+
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Bytes que caben ocultos en la imagen
+    pub fn capacity(&self) -> usize {
+        (self.layout.samples() / 8).saturating_sub(FRAME_OVERHEAD).min(MAX_PAYLOAD_LEN)
+    }
+
+    /// La imagen con `payload` oculto, en el mismo formato
+    pub fn embed(&self, payload: &[u8]) -> Result<Vec<u8>> {
+        if payload.len() > self.capacity() {
+            return Err(SCypherError::InvalidRequest(format!(
+                "the {} image can hide {} bytes, {} needed (use a larger image)",
+                self.format.name(), self.capacity(), payload.len(),
+            )));
+        }
+
+        let mut frame = Zeroizing::new(Vec::with_capacity(payload.len() + FRAME_OVERHEAD));
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        let crc = crc32fast::hash(&frame);
+        frame.extend_from_slice(&crc.to_be_bytes());
+        whiten(&mut frame);
+
+        let mut data = self.data.clone();
+        for (position, byte) in frame.iter().enumerate() {
+            for bit in 0..8 {
+                let index = self.layout.index(position * 8 + bit);
+                data[index] = (data[index] & !1) | ((byte >> (7 - bit)) & 1);
+            }
+        }
+
+        match &self.encoding {
+            Encoding::Png(header) => png::encode(header, &data),
+            Encoding::Bmp => Ok(data),
+        }
+    }
+
+    /// Datos ocultos con `embed`
+    pub fn extract(&self) -> Result<Zeroizing<Vec<u8>>> {
+        let not_found = |defect| SCypherError::malformed("stego", defect, "no hidden data in the image (or the image was modified)");
+
+        let mut length = self.read_bytes(4)?;
+        whiten(&mut length);
+        let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize;
+        if length > self.capacity() {
+            return Err(not_found(Defect::Inconsistent));
+        }
+
+        let mut frame = self.read_bytes(length + FRAME_OVERHEAD)?;
+        whiten(&mut frame);
+        let (body, crc) = frame.split_at(length + 4);
+        if crc32fast::hash(body).to_be_bytes() != crc {
+            return Err(not_found(Defect::Checksum));
+        }
+        Ok(Zeroizing::new(body[4..].to_vec()))
+    }
+
+    /// Los `len` primeros bytes ocultos, sin deshacer la mezcla
+    fn read_bytes(&self, len: usize) -> Result<Zeroizing<Vec<u8>>> {
+        if len * 8 > self.layout.samples() {
+            return Err(SCypherError::malformed("stego", Defect::Truncated, "the image is too small to hold hidden data"));
+        }
+        let bytes = (0..len)
+            .map(|position| (0..8).fold(0u8, |byte, bit| (byte << 1) | (self.data[self.layout.index(position * 8 + bit)] & 1)))
+            .collect();
+        Ok(Zeroizing::new(bytes))
+    }
+}
+
+/// Ocultar `payload` en la imagen `carrier`
+pub fn embed(carrier: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+    Carrier::parse(carrier)?.embed(payload)
+}
+
+/// Recuperar lo oculto en `image`
+pub fn extract(image: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    Carrier::parse(image)?.extract()
+}
+
+/// XOR de `data` con el keystream (SHA-256 de la etiqueta y un contador)
+fn whiten(data: &mut [u8]) {
+    for (counter, chunk) in data.chunks_mut(32).enumerate() {
+        let block = Sha256::new().chain_update(WHITENING_LABEL).chain_update((counter as u64).to_be_bytes()).finalize();
+        chunk.iter_mut().zip(block).for_each(|(byte, key)| *byte ^= key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: &[u8] = b"shock strong glory exchange syrup east illness crater board genuine wish clarify";
+
+    /// BMP de 24 bits sin comprimir, con filas de relleno
+    fn bmp_image(width: u32, height: u32) -> Vec<u8> {
+        let row_stride = (width as usize * 3).div_ceil(4) * 4;
+        let pixels = row_stride * height as usize;
+        let mut image = Vec::new();
+        image.extend_from_slice(b"BM");
+        image.extend_from_slice(&(54 + pixels as u32).to_le_bytes());
+        image.extend_from_slice(&[0; 4]);
+        image.extend_from_slice(&54u32.to_le_bytes());
+        image.extend_from_slice(&40u32.to_le_bytes());
+        image.extend_from_slice(&width.to_le_bytes());
+        image.extend_from_slice(&height.to_le_bytes());
+        image.extend_from_slice(&1u16.to_le_bytes());
+        image.extend_from_slice(&24u16.to_le_bytes());
+        image.extend_from_slice(&[0; 24]);
+        image.extend((0..pixels).map(|i| (i * 7) as u8));
+        image
+    }
+
+    fn png_image(width: u32, height: u32) -> Vec<u8> {
+        let mut image = Vec::new();
+        let mut encoder = ::png::Encoder::new(&mut image, width, height);
+        encoder.set_color(::png::ColorType::Rgba);
+        encoder.set_depth(::png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&(0..width * height * 4).map(|i| (i * 13) as u8).collect::<Vec<_>>()).unwrap();
+        writer.finish().unwrap();
+        image
+    }
+
+    #[test]
+    fn test_embed_extract() {
+        for carrier in [bmp_image(31, 20), png_image(30, 20)] {
+            let format = ImageFormat::detect(&carrier).unwrap();
+            assert!(matches!(extract(&carrier), Err(SCypherError::MalformedContainer { .. })));
+
+            let image = embed(&carrier, PAYLOAD).unwrap();
+            assert_eq!(ImageFormat::detect(&image), Some(format));
+            assert_eq!(extract(&image).unwrap().as_slice(), PAYLOAD);
+
+            // Los píxeles solo cambian en el bit menos significativo
+            let (before, after) = (Carrier::parse(&carrier).unwrap(), Carrier::parse(&image).unwrap());
+            assert!(before.data.iter().zip(&after.data).all(|(a, b)| a & !1 == b & !1));
+
+            let too_big = vec![0u8; before.capacity() + 1];
+            assert!(matches!(embed(&carrier, &too_big), Err(SCypherError::InvalidRequest(_))));
+        }
+
+        // 30x20 píxeles RGBA: el alfa no lleva datos
+        assert_eq!(Carrier::parse(&png_image(30, 20)).unwrap().capacity(), 30 * 20 * 3 / 8 - FRAME_OVERHEAD);
+
+        // Un bit cambiado en los datos lo detecta el CRC
+        let mut image = embed(&bmp_image(31, 20), PAYLOAD).unwrap();
+        image[54 + 100] ^= 1;
+        assert!(matches!(extract(&image), Err(SCypherError::MalformedContainer { defect: Defect::Checksum, .. })));
+
+        assert!(extract(b"GIF89a").is_err());
+        assert!(extract(&bmp_image(31, 20)[..60]).is_err());
+    }
+}
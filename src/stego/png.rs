@@ -0,0 +1,67 @@
+//! Portadoras PNG: 8 bits por canal, gris o RGB, con o sin alfa
+
+use ::png::{BitDepth, ColorType, Decoder, Encoder, Limits, Transformations};
+
+use crate::error::{Defect, SCypherError, Result};
+
+use super::{Carrier, Encoding, ImageFormat, Layout};
+
+/// Memoria máxima para los píxeles decodificados
+const MAX_DECODED_LEN: usize = 256 << 20;
+
+/// Lo necesario para volver a codificar los píxeles
+#[derive(Debug, Clone)]
+pub(super) struct Header {
+    width: u32,
+    height: u32,
+    color: ColorType,
+}
+
+fn malformed(defect: Defect, message: impl Into<String>) -> SCypherError {
+    SCypherError::malformed("PNG", defect, message)
+}
+
+pub(super) fn parse(bytes: &[u8]) -> Result<Carrier> {
+    let mut decoder = Decoder::new_with_limits(bytes, Limits { bytes: MAX_DECODED_LEN });
+    decoder.set_transformations(Transformations::IDENTITY);
+    let mut reader = decoder.read_info().map_err(|e| malformed(Defect::Encoding, e.to_string()))?;
+
+    if reader.info().animation_control().is_some() {
+        return Err(malformed(Defect::Unsupported, "animated images are not supported"));
+    }
+    let (color, depth) = reader.output_color_type();
+    let (channels, colors) = match color {
+        ColorType::Grayscale => (1, 1),
+        ColorType::GrayscaleAlpha => (2, 1),
+        ColorType::Rgb => (3, 3),
+        ColorType::Rgba => (4, 3),
+        ColorType::Indexed => return Err(malformed(Defect::Unsupported, "palette images are not supported (convert to RGB)")),
+    };
+    if depth != BitDepth::Eight {
+        return Err(malformed(Defect::Unsupported, "only 8 bits per channel are supported"));
+    }
+
+    let mut data = vec![0u8; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).map_err(|e| malformed(Defect::Encoding, e.to_string()))?;
+    data.truncate(info.buffer_size());
+
+    let width = info.width as usize;
+    Ok(Carrier {
+        format: ImageFormat::Png,
+        data,
+        layout: Layout { start: 0, rows: info.height as usize, width, row_stride: info.line_size, pixel_stride: channels, colors },
+        encoding: Encoding::Png(Header { width: info.width, height: info.height, color }),
+    })
+}
+
+pub(super) fn encode(header: &Header, data: &[u8]) -> Result<Vec<u8>> {
+    let png_error = |e: ::png::EncodingError| SCypherError::crypto(format!("Cannot encode PNG: {}", e));
+    let mut output = Vec::new();
+    let mut encoder = Encoder::new(&mut output, header.width, header.height);
+    encoder.set_color(header.color);
+    encoder.set_depth(BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(data).map_err(png_error)?;
+    writer.finish().map_err(png_error)?;
+    Ok(output)
+}
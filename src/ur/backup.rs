@@ -0,0 +1,197 @@
+//! `ur:scypher-backup`: frase cifrada junto con los parámetros para recuperarla
+//!
+//! Es lo que lleva el QR de una frase cifrada: escanearlo años después basta,
+//! con la contraseña, para recuperar la original aunque nadie recuerde las
+//! iteraciones, la memoria o el salt. El cuerpo es un mapa CBOR:
+//!
+//! | clave | valor                                                    |
+//! |-------|----------------------------------------------------------|
+//! | 1     | versión del sobre (1)                                    |
+//! | 2     | entropía de la frase cifrada (bytes)                     |
+//! | 3     | KDF: 0 Argon2id, 1 Argon2i                               |
+//! | 4     | versión de Argon2 (0x13 o 0x10)                          |
+//! | 5     | iteraciones                                              |
+//! | 6     | memoria en KiB                                           |
+//! | 7     | lanes                                                    |
+//! | 8     | salt explícito (bytes); sin él, derivado de la contraseña |
+//! | 9     | número de keyfiles (sin ella, ninguno)                   |
+//!
+//! Todas las claves son obligatorias salvo 8 y 9. Una clave desconocida se
+//! rechaza: un parámetro que no se entiende daría otra frase sin avisar.
+
+use zeroize::Zeroizing;
+
+use crate::crypto::{Argon2Version, Kdf, SaltSource, ScypherParams};
+use crate::error::{Defect, SCypherError, Result};
+
+use super::{cbor, Ur};
+
+/// Tipo de UR de una copia de seguridad de SCypher
+pub const SCYPHER_BACKUP: &str = "scypher-backup";
+
+/// Versión del sobre que se escribe
+pub const BACKUP_VERSION: u64 = 1;
+
+/// Frase cifrada y parámetros leídos de un `ur:scypher-backup`
+pub struct SeedBackup {
+    pub phrase: Zeroizing<String>,
+    pub params: ScypherParams,
+    /// Keyfiles usados al cifrar (sus hashes no se guardan)
+    pub keyfiles: usize,
+}
+
+fn malformed(defect: Defect, message: impl Into<String>) -> SCypherError {
+    SCypherError::malformed("scypher-backup", defect, message)
+}
+
+fn kdf_id(kdf: Kdf) -> u64 {
+    match kdf {
+        Kdf::Argon2id => 0,
+        Kdf::Argon2i => 1,
+    }
+}
+
+/// Escribir una entrada `key: value` con un entero sin signo
+fn write_field(cbor: &mut Vec<u8>, key: u64, value: u64) {
+    cbor::write_head(cbor, cbor::UNSIGNED, key);
+    cbor::write_head(cbor, cbor::UNSIGNED, value);
+}
+
+impl Ur {
+    /// `ur:scypher-backup` con la frase cifrada `phrase` y sus `params`
+    pub fn scypher_backup(phrase: &str, params: &ScypherParams) -> Result<Self> {
+        let entropy = Zeroizing::new(crate::bip39::phrase_to_entropy(phrase)?);
+        let salt = match params.salt() {
+            SaltSource::PasswordDerived => None,
+            SaltSource::Explicit(salt) => Some(salt),
+        };
+        let keyfiles = params.keyfile_digests().len();
+
+        let mut cbor = Zeroizing::new(Vec::with_capacity(entropy.len() + 64));
+        let fields = 7 + u64::from(salt.is_some()) + u64::from(keyfiles > 0);
+        cbor::write_head(&mut cbor, cbor::MAP, fields);
+        write_field(&mut cbor, 1, BACKUP_VERSION);
+        cbor::write_head(&mut cbor, cbor::UNSIGNED, 2);
+        cbor::write_bytes(&mut cbor, &entropy);
+        write_field(&mut cbor, 3, kdf_id(params.kdf()));
+        write_field(&mut cbor, 4, match params.argon2_version() {
+            Argon2Version::V0x10 => 0x10,
+            Argon2Version::V0x13 => 0x13,
+        });
+        write_field(&mut cbor, 5, u64::from(params.iterations()));
+        write_field(&mut cbor, 6, u64::from(params.memory_cost()));
+        write_field(&mut cbor, 7, u64::from(params.lanes()));
+        if let Some(salt) = salt {
+            cbor::write_head(&mut cbor, cbor::UNSIGNED, 8);
+            cbor::write_bytes(&mut cbor, salt);
+        }
+        if keyfiles > 0 {
+            write_field(&mut cbor, 9, keyfiles as u64);
+        }
+
+        Ok(Self { ur_type: SCYPHER_BACKUP.to_string(), cbor })
+    }
+
+    /// Frase cifrada y parámetros de un `ur:scypher-backup`
+    pub fn to_scypher_backup(&self) -> Result<SeedBackup> {
+        if self.ur_type != SCYPHER_BACKUP {
+            return Err(SCypherError::InvalidRequest(format!("expected ur:{}, found ur:{}", SCYPHER_BACKUP, self.ur_type)));
+        }
+
+        let mut reader = cbor::Reader::new(&self.cbor);
+        let mut numbers: [Option<u64>; 10] = [None; 10];
+        let (mut entropy, mut salt) = (None, None);
+        for _ in 0..reader.expect(cbor::MAP)? {
+            let key = reader.expect(cbor::UNSIGNED)?;
+            let duplicate = || malformed(Defect::Inconsistent, format!("key {} appears twice", key));
+            match key {
+                2 if entropy.is_none() => entropy = Some(reader.bytes()?),
+                8 if salt.is_none() => salt = Some(reader.bytes()?),
+                2 | 8 => return Err(duplicate()),
+                1 | 3..=7 | 9 if numbers[key as usize].is_none() => numbers[key as usize] = Some(reader.expect(cbor::UNSIGNED)?),
+                1 | 3..=7 | 9 => return Err(duplicate()),
+                _ => return Err(malformed(Defect::Unsupported, format!("unknown key {}", key))),
+            }
+        }
+        if !reader.is_empty() {
+            return Err(malformed(Defect::Inconsistent, "data after the backup map"));
+        }
+
+        let required = |key: usize| numbers[key].ok_or_else(|| malformed(Defect::Truncated, format!("key {} is missing", key)));
+        let small = |key: usize| {
+            required(key).and_then(|value| u32::try_from(value).map_err(|_| malformed(Defect::Inconsistent, format!("key {} is out of range", key))))
+        };
+
+        match required(1)? {
+            BACKUP_VERSION => {}
+            version => return Err(malformed(Defect::Unsupported, format!("envelope version {}", version))),
+        }
+        let kdf = required(3)?;
+        let kdf = Kdf::ALL
+            .into_iter()
+            .find(|&known| kdf_id(known) == kdf)
+            .ok_or_else(|| malformed(Defect::Unsupported, format!("unknown KDF id {}", kdf)))?;
+        let argon2_version = match required(4)? {
+            0x10 => Argon2Version::V0x10,
+            0x13 => Argon2Version::V0x13,
+            version => return Err(malformed(Defect::Unsupported, format!("Argon2 version {:#x}", version))),
+        };
+
+        let mut builder = ScypherParams::builder()
+            .kdf(kdf)
+            .argon2_version(argon2_version)
+            .iterations(small(5)?)
+            .memory_cost(small(6)?)
+            .lanes(small(7)?);
+        if let Some(salt) = salt {
+            builder = builder.salt(SaltSource::Explicit(salt.to_vec()));
+        }
+        let params = builder.build()?;
+
+        let entropy = entropy.ok_or_else(|| malformed(Defect::Truncated, "key 2 is missing"))?;
+        let phrase = Zeroizing::new(crate::bip39::entropy_to_phrase(entropy)?);
+        let keyfiles = numbers[9].map_or(Ok(0), |count| usize::try_from(count).map_err(|_| malformed(Defect::Inconsistent, "key 9 is out of range")))?;
+        Ok(SeedBackup { phrase, params, keyfiles })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scypher_backup() {
+        let phrase = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).lanes(2).build().unwrap();
+
+        let ur = Ur::scypher_backup(phrase, &params).unwrap();
+        let encoded = ur.to_single_part();
+        assert!(encoded.starts_with("ur:scypher-backup/"));
+        let backup = Ur::from_single_part(&encoded.to_uppercase()).unwrap().to_scypher_backup().unwrap();
+        assert_eq!(backup.phrase.as_str(), phrase);
+        assert_eq!(backup.params, params);
+        assert_eq!(backup.keyfiles, 0);
+        assert!(ur.to_phrase().is_err());
+
+        // Salt explícito, Argon2i y keyfiles
+        let params = params.to_builder()
+            .kdf(Kdf::Argon2i)
+            .salt(SaltSource::Explicit(vec![7; 16]))
+            .keyfile(b"key")
+            .build()
+            .unwrap();
+        let backup = Ur::scypher_backup(phrase, &params).unwrap().to_scypher_backup().unwrap();
+        assert_eq!((backup.params.kdf(), backup.params.salt(), backup.keyfiles), (Kdf::Argon2i, params.salt(), 1));
+
+        // Claves desconocidas, repetidas o ausentes y otra versión se rechazan
+        let body = |hex_body: &str| Ur { ur_type: SCYPHER_BACKUP.to_string(), cbor: Zeroizing::new(hex::decode(hex_body).unwrap()) };
+        let entropy = format!("0250{}", "00".repeat(16));
+        let fields = format!("0101{}0300041305010619200007 01", entropy).replace(' ', "");
+        assert!(body(&format!("a7{}", fields)).to_scypher_backup().is_ok());
+        assert!(body(&format!("a8{}0a00", fields)).to_scypher_backup().is_err());
+        assert!(body(&format!("a8{}0101", fields)).to_scypher_backup().is_err());
+        assert!(body(&format!("a6{}", &fields[4..])).to_scypher_backup().is_err());
+        assert!(matches!(body(&format!("a7{}", fields.replacen("0101", "0102", 1))).to_scypher_backup(),
+            Err(SCypherError::MalformedContainer { defect: Defect::Unsupported, .. })));
+    }
+}
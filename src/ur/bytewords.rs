@@ -0,0 +1,87 @@
+//! Bytewords (BCR-2020-012): cada byte es una palabra de cuatro letras
+//!
+//! Los UR usan el estilo mínimo (primera y última letra de cada palabra),
+//! seguido del CRC-32 de los datos, también en bytewords.
+
+use crate::error::{Defect, SCypherError, Result};
+
+/// Las 256 palabras, en orden alfabético (la posición es el valor del byte)
+const WORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt", "away", "axis", "back", "bald", "barn", "belt", "beta", "bias",
+    "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash", "cats", "chef", "city", "claw", "code", "cola", "cook", "cost",
+    "crux", "curl", "cusp", "cyan", "dark", "data", "days", "deli", "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam", "exit", "eyes", "fact", "fair", "fern", "figs", "film", "fish",
+    "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel", "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow",
+    "good", "gray", "grim", "guru", "gush", "gyro", "half", "hang", "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into", "iris", "iron", "item", "jade", "jazz", "join", "jolt", "jowl",
+    "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept", "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb",
+    "lava", "lazy", "leaf", "legs", "liar", "limp", "lion", "list", "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss", "monk", "nail", "navy", "need", "news", "next", "noon", "note",
+    "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls", "paid", "part", "peck", "play", "plus", "poem", "pool", "pose",
+    "puff", "puma", "purr", "quad", "quiz", "race", "ramp", "real", "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot", "soap", "solo", "song", "stub", "surf", "swan", "taco", "task",
+    "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys", "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user",
+    "vast", "very", "veto", "vial", "vibe", "view", "visa", "void", "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell", "yoga", "yurt", "zaps", "zero", "zest", "zinc", "zone", "zoom",
+];
+
+/// Codificar `data` en estilo mínimo, con CRC-32 al final
+pub fn encode_minimal(data: &[u8]) -> String {
+    let checksum = crc32fast::hash(data).to_be_bytes();
+    let mut encoded = String::with_capacity((data.len() + 4) * 2);
+    for &byte in data.iter().chain(&checksum) {
+        let word = WORDS[byte as usize].as_bytes();
+        encoded.push(word[0] as char);
+        encoded.push(word[3] as char);
+    }
+    encoded
+}
+
+/// Decodificar estilo mínimo (sin distinguir mayúsculas) y comprobar el CRC-32
+///
+/// Se aceptan hasta `super::MAX_MESSAGE_LEN` bytes de datos.
+pub fn decode_minimal(encoded: &str) -> Result<Vec<u8>> {
+    let malformed = |defect, what: &str| SCypherError::malformed("bytewords", defect, what);
+
+    let encoded = encoded.as_bytes();
+    if encoded.len() > (super::MAX_MESSAGE_LEN + 4) * 2 {
+        return Err(malformed(Defect::TooLarge, "too many words"));
+    }
+    if !encoded.len().is_multiple_of(2) || encoded.len() < 10 {
+        return Err(malformed(Defect::Truncated, "wrong length"));
+    }
+
+    let mut data = encoded
+        .chunks(2)
+        .map(|pair| {
+            let (first, last) = (pair[0].to_ascii_lowercase(), pair[1].to_ascii_lowercase());
+            WORDS
+                .iter()
+                .position(|word| word.as_bytes()[0] == first && word.as_bytes()[3] == last)
+                .map(|index| index as u8)
+                .ok_or_else(|| malformed(Defect::Encoding, "unknown word"))
+        })
+        .collect::<Result<Vec<u8>>>()?;
+
+    let checksum = data.split_off(data.len() - 4);
+    if crc32fast::hash(&data).to_be_bytes()[..] != checksum[..] {
+        return Err(malformed(Defect::Checksum, "CRC-32 mismatch"));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytewords() {
+        // Ejemplo de BCR-2020-012: "able acid also lava zoom jade need echo taxi"
+        let data = [0, 1, 2, 128, 255];
+        assert_eq!(encode_minimal(&data), "aeadaolazmjendeoti");
+        assert_eq!(decode_minimal("AEADAOLAZMJENDEOTI").unwrap(), data);
+
+        assert!(decode_minimal("aeadaolazmjendeotj").is_err());
+        assert!(decode_minimal("aeadaolazmjendeot").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Subconjunto de CBOR (RFC 8949) que necesitan los UR
+//!
+//! Enteros sin signo, cadenas de bytes, arrays y mapas de longitud definida;
+//! el resto de tipos solo se sabe saltar, hasta `MAX_DEPTH` niveles.
+
+use crate::error::{Defect, SCypherError, Result};
+
+pub(super) const UNSIGNED: u8 = 0;
+pub(super) const BYTES: u8 = 2;
+pub(super) const ARRAY: u8 = 4;
+pub(super) const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+/// Anidamiento máximo al saltar elementos (los UR admitidos usan dos o tres)
+const MAX_DEPTH: usize = 16;
+
+/// Escribir la cabecera de un elemento con su valor en la forma más corta
+pub(super) fn write_head(output: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => output.push(major | value as u8),
+        24..=0xFF => output.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xFFFF => {
+            output.push(major | 25);
+            output.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xFFFF_FFFF => {
+            output.push(major | 26);
+            output.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            output.push(major | 27);
+            output.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Escribir una cadena de bytes
+pub(super) fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    write_head(output, BYTES, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+}
+
+fn malformed(defect: Defect, what: &str) -> SCypherError {
+    SCypherError::malformed("cbor", defect, what)
+}
+
+/// Lector secuencial sobre un buffer
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Si se ha consumido todo el buffer
+    pub(super) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Leer una cabecera: (tipo mayor, valor)
+    pub(super) fn head(&mut self) -> Result<(u8, u64)> {
+        let (&initial, rest) = self.data.split_first().ok_or_else(|| malformed(Defect::Truncated, "missing item"))?;
+        let (major, info) = (initial >> 5, initial & 0x1F);
+
+        let length = match info {
+            0..=23 => 0,
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(malformed(Defect::Unsupported, "indefinite lengths")),
+        };
+        if rest.len() < length {
+            return Err(malformed(Defect::Truncated, "item header"));
+        }
+
+        let value = if length == 0 {
+            info as u64
+        } else {
+            rest[..length].iter().fold(0u64, |value, &byte| (value << 8) | byte as u64)
+        };
+        self.data = &rest[length..];
+        Ok((major, value))
+    }
+
+    /// Leer un elemento de tipo `major` y devolver su valor
+    pub(super) fn expect(&mut self, major: u8) -> Result<u64> {
+        match self.head()? {
+            (found, value) if found == major => Ok(value),
+            _ => Err(malformed(Defect::Inconsistent, "unexpected item type")),
+        }
+    }
+
+    /// Leer una cadena de bytes
+    pub(super) fn bytes(&mut self) -> Result<&'a [u8]> {
+        let length = self.expect(BYTES)?;
+        self.take(length)
+    }
+
+    /// Saltar un elemento completo (con su contenido)
+    pub(super) fn skip(&mut self) -> Result<()> {
+        self.skip_nested(0)
+    }
+
+    fn skip_nested(&mut self, depth: usize) -> Result<()> {
+        if depth >= MAX_DEPTH {
+            return Err(malformed(Defect::TooDeep, "nested items"));
+        }
+
+        let (major, value) = self.head()?;
+        match major {
+            BYTES | 3 => {
+                self.take(value)?;
+            }
+            ARRAY => {
+                for _ in 0..value {
+                    self.skip_nested(depth + 1)?;
+                }
+            }
+            MAP => {
+                for _ in 0..value.saturating_mul(2) {
+                    self.skip_nested(depth + 1)?;
+                }
+            }
+            TAG => self.skip_nested(depth + 1)?,
+            UNSIGNED | 1 | SIMPLE => {}
+            _ => unreachable!("major type has three bits"),
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, length: u64) -> Result<&'a [u8]> {
+        let length = usize::try_from(length).ok().filter(|&length| length <= self.data.len())
+            .ok_or_else(|| malformed(Defect::Truncated, "string contents"))?;
+        let (taken, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(taken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cbor_roundtrip() {
+        let mut output = Vec::new();
+        write_head(&mut output, ARRAY, 3);
+        write_head(&mut output, UNSIGNED, 259);
+        write_head(&mut output, UNSIGNED, 0xEDA0_AE73);
+        write_bytes(&mut output, &[1, 2, 3]);
+        assert_eq!(hex::encode(&output), "831901031aeda0ae7343010203");
+
+        let mut reader = Reader::new(&output);
+        assert_eq!(reader.expect(ARRAY).unwrap(), 3);
+        assert_eq!(reader.expect(UNSIGNED).unwrap(), 259);
+        reader.skip().unwrap();
+        assert_eq!(reader.bytes().unwrap(), [1, 2, 3]);
+        assert!(reader.is_empty());
+
+        assert!(Reader::new(&[0x43, 1]).bytes().is_err());
+
+        // Arrays anidados sin fin: error, no un desbordamiento de pila
+        let nested = vec![0x81; 100_000];
+        assert!(matches!(
+            Reader::new(&nested).skip(),
+            Err(SCypherError::MalformedContainer { defect: Defect::TooDeep, .. })
+        ));
+    }
+}
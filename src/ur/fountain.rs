@@ -0,0 +1,446 @@
+//! Códigos fuente (fountain codes) de los UR multiparte (BCR-2020-005)
+//!
+//! Las partes `1..=n` llevan cada fragmento tal cual; a partir de ahí cada
+//! parte es el XOR de un subconjunto pseudoaleatorio de fragmentos, elegido
+//! con Xoshiro256** a partir del número de parte y el checksum. El lector
+//! puede empezar a escanear en cualquier momento del bucle y recupera el
+//! mensaje con cualquier combinación suficiente de partes.
+
+use std::collections::{BTreeSet, HashMap};
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use super::cbor;
+use crate::error::{Defect, SCypherError, Result};
+
+/// Longitud mínima de fragmento al repartir un mensaje
+const MIN_FRAGMENT_LEN: usize = 10;
+
+/// Máximo de fragmentos aceptado al leer
+///
+/// Con partes manipuladas un `seqLen` enorme haría reservar memoria y
+/// barajar índices (coste cuadrático) en cada parte mixta.
+const MAX_SEQ_LEN: usize = 1 << 10;
+
+/// Partes mixtas pendientes por fragmento antes de dar el mensaje por perdido
+const MAX_MIXED_PER_FRAGMENT: usize = 16;
+
+/// Cabecera y fragmento de una parte
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub seq_num: u32,
+    pub seq_len: usize,
+    pub message_len: usize,
+    pub checksum: u32,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    /// CBOR `[seqNum, seqLen, messageLen, checksum, data]`
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut output = Vec::with_capacity(self.data.len() + 20);
+        cbor::write_head(&mut output, cbor::ARRAY, 5);
+        cbor::write_head(&mut output, cbor::UNSIGNED, self.seq_num as u64);
+        cbor::write_head(&mut output, cbor::UNSIGNED, self.seq_len as u64);
+        cbor::write_head(&mut output, cbor::UNSIGNED, self.message_len as u64);
+        cbor::write_head(&mut output, cbor::UNSIGNED, self.checksum as u64);
+        cbor::write_bytes(&mut output, &self.data);
+        output
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        let invalid = || SCypherError::malformed("ur", Defect::Inconsistent, "part header out of range");
+        let mut reader = cbor::Reader::new(bytes);
+
+        if reader.expect(cbor::ARRAY)? != 5 {
+            return Err(invalid());
+        }
+        let seq_num = u32::try_from(reader.expect(cbor::UNSIGNED)?).map_err(|_| invalid())?;
+        let seq_len = usize::try_from(reader.expect(cbor::UNSIGNED)?).map_err(|_| invalid())?;
+        let message_len = usize::try_from(reader.expect(cbor::UNSIGNED)?).map_err(|_| invalid())?;
+        let checksum = u32::try_from(reader.expect(cbor::UNSIGNED)?).map_err(|_| invalid())?;
+        let data = reader.bytes()?.to_vec();
+
+        if seq_len > MAX_SEQ_LEN || message_len > super::MAX_MESSAGE_LEN {
+            return Err(SCypherError::malformed("ur", Defect::TooLarge, "message split in too many or too large parts"));
+        }
+        let consistent = reader.is_empty()
+            && seq_num > 0
+            && seq_len > 0
+            && !data.is_empty()
+            && seq_len.checked_mul(data.len()).is_some_and(|total| total >= message_len && total - message_len < data.len());
+        if !consistent {
+            return Err(invalid());
+        }
+
+        Ok(Self { seq_num, seq_len, message_len, checksum, data })
+    }
+}
+
+/// Longitud de fragmento: la mayor posible hasta `max_fragment_len`, repartiendo por igual
+fn fragment_length(message_len: usize, max_fragment_len: usize) -> usize {
+    let max_fragment_count = (message_len / MIN_FRAGMENT_LEN).max(1);
+    (1..=max_fragment_count)
+        .map(|count| message_len.div_ceil(count))
+        .find(|&length| length <= max_fragment_len)
+        .unwrap_or_else(|| message_len.div_ceil(max_fragment_count))
+}
+
+/// Generador de partes para un mensaje
+pub struct Encoder {
+    fragments: Vec<Vec<u8>>,
+    message_len: usize,
+    checksum: u32,
+    seq_num: u32,
+}
+
+impl Encoder {
+    /// Partir `message` en fragmentos de hasta `max_fragment_len` bytes (con relleno de ceros)
+    pub fn new(message: &[u8], max_fragment_len: usize) -> Self {
+        let length = fragment_length(message.len(), max_fragment_len.max(MIN_FRAGMENT_LEN)).max(1);
+        let fragments = message
+            .chunks(length)
+            .map(|chunk| {
+                let mut fragment = chunk.to_vec();
+                fragment.resize(length, 0);
+                fragment
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            fragments,
+            message_len: message.len(),
+            checksum: crc32fast::hash(message),
+            seq_num: 0,
+        }
+    }
+
+    /// Número de fragmentos (partes mínimas para un lector sin pérdidas)
+    pub fn seq_len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// Siguiente parte; la secuencia no termina
+    pub fn next_part(&mut self) -> Part {
+        self.seq_num = self.seq_num.wrapping_add(1).max(1);
+
+        let mut data = vec![0u8; self.fragments[0].len()];
+        for index in choose_fragments(self.seq_num, self.seq_len(), self.checksum) {
+            xor_into(&mut data, &self.fragments[index]);
+        }
+
+        Part {
+            seq_num: self.seq_num,
+            seq_len: self.seq_len(),
+            message_len: self.message_len,
+            checksum: self.checksum,
+            data,
+        }
+    }
+}
+
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        self.fragments.zeroize();
+    }
+}
+
+/// Reconstrucción del mensaje a partir de partes en cualquier orden
+#[derive(Default)]
+pub struct Decoder {
+    /// (seqLen, messageLen, checksum, longitud de fragmento) de la primera parte
+    expected: Option<(usize, usize, u32, usize)>,
+    simple: HashMap<usize, Vec<u8>>,
+    mixed: Vec<(BTreeSet<usize>, Vec<u8>)>,
+    message: Option<Vec<u8>>,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Incorporar una parte; las que no encajan con las anteriores son un error
+    pub fn receive(&mut self, part: &Part) -> Result<()> {
+        if self.message.is_some() {
+            return Ok(());
+        }
+
+        let shape = (part.seq_len, part.message_len, part.checksum, part.data.len());
+        match self.expected {
+            None => self.expected = Some(shape),
+            Some(expected) if expected != shape => {
+                return Err(SCypherError::malformed("ur", Defect::Inconsistent, "part belongs to a different message"));
+            }
+            Some(_) => {}
+        }
+
+        if self.mixed.len() >= MAX_MIXED_PER_FRAGMENT * part.seq_len {
+            return Err(SCypherError::malformed("ur", Defect::TooLarge, "too many parts without recovering the message"));
+        }
+
+        let indexes: BTreeSet<usize> = choose_fragments(part.seq_num, part.seq_len, part.checksum).into_iter().collect();
+        self.add(indexes, part.data.clone());
+        self.finish()
+    }
+
+    /// Fracción estimada de fragmentos ya conocidos (0.0 a 1.0)
+    pub fn progress(&self) -> f64 {
+        match self.expected {
+            _ if self.message.is_some() => 1.0,
+            Some((seq_len, ..)) => self.simple.len() as f64 / seq_len as f64,
+            None => 0.0,
+        }
+    }
+
+    /// Mensaje completo, cuando ya se tiene
+    pub fn message(&self) -> Option<&[u8]> {
+        self.message.as_deref()
+    }
+
+    /// Reducir la parte con lo conocido y propagar los fragmentos que aparezcan
+    fn add(&mut self, indexes: BTreeSet<usize>, data: Vec<u8>) {
+        let mut queue = vec![(indexes, data)];
+
+        while let Some((mut indexes, mut data)) = queue.pop() {
+            for (&index, fragment) in &self.simple {
+                if indexes.remove(&index) {
+                    xor_into(&mut data, fragment);
+                }
+            }
+
+            match indexes.len() {
+                0 => {}
+                1 => {
+                    let index = *indexes.iter().next().expect("one index");
+                    self.simple.insert(index, data);
+
+                    // Las partes mixtas que contenían este fragmento pueden haber quedado simples
+                    let (reducible, pending): (Vec<_>, Vec<_>) =
+                        self.mixed.drain(..).partition(|(mixed, _)| mixed.contains(&index));
+                    self.mixed = pending;
+                    queue.extend(reducible);
+                }
+                _ => {
+                    if !self.mixed.iter().any(|(mixed, _)| *mixed == indexes) {
+                        self.mixed.push((indexes, data));
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let Some((seq_len, message_len, checksum, _)) = self.expected else {
+            return Ok(());
+        };
+        if self.simple.len() < seq_len {
+            return Ok(());
+        }
+
+        let mut message: Vec<u8> = (0..seq_len).flat_map(|index| self.simple[&index].iter().copied()).collect();
+        message.truncate(message_len);
+        if crc32fast::hash(&message) != checksum {
+            return Err(SCypherError::malformed("ur", Defect::Checksum, "message checksum mismatch"));
+        }
+
+        self.message = Some(message);
+        self.simple.values_mut().for_each(Zeroize::zeroize);
+        self.simple.clear();
+        self.mixed.clear();
+        Ok(())
+    }
+}
+
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        self.simple.values_mut().for_each(Zeroize::zeroize);
+        self.mixed.iter_mut().for_each(|(_, data)| data.zeroize());
+        self.message.zeroize();
+    }
+}
+
+fn xor_into(target: &mut [u8], source: &[u8]) {
+    for (target, source) in target.iter_mut().zip(source) {
+        *target ^= source;
+    }
+}
+
+/// Fragmentos que combina la parte `seq_num`
+fn choose_fragments(seq_num: u32, seq_len: usize, checksum: u32) -> Vec<usize> {
+    if seq_num as usize <= seq_len {
+        return vec![seq_num as usize - 1];
+    }
+
+    let mut seed = [0u8; 8];
+    seed[..4].copy_from_slice(&seq_num.to_be_bytes());
+    seed[4..].copy_from_slice(&checksum.to_be_bytes());
+    let mut rng = Xoshiro256::from_seed(&seed);
+
+    let weights: Vec<f64> = (1..=seq_len).map(|degree| 1.0 / degree as f64).collect();
+    let degree = WeightedSampler::new(&weights).next(&mut rng) + 1;
+
+    let mut indexes = shuffled((0..seq_len).collect(), &mut rng);
+    indexes.truncate(degree);
+    indexes
+}
+
+/// Barajado de la implementación de referencia (extraer en posiciones aleatorias)
+fn shuffled<T>(mut remaining: Vec<T>, rng: &mut Xoshiro256) -> Vec<T> {
+    let mut result = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let index = rng.next_int(0, remaining.len() as u64 - 1) as usize;
+        result.push(remaining.remove(index));
+    }
+    result
+}
+
+/// Xoshiro256** sembrado con SHA-256 de la semilla (palabras big-endian)
+struct Xoshiro256 {
+    state: [u64; 4],
+}
+
+impl Xoshiro256 {
+    fn from_seed(seed: &[u8]) -> Self {
+        let digest = Sha256::digest(seed);
+        let mut state = [0u64; 4];
+        for (word, bytes) in state.iter_mut().zip(digest.chunks(8)) {
+            *word = u64::from_be_bytes(bytes.try_into().expect("8 bytes"));
+        }
+        Self { state }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    fn next_double(&mut self) -> f64 {
+        self.next_u64() as f64 / (u64::MAX as f64 + 1.0)
+    }
+
+    fn next_int(&mut self, low: u64, high: u64) -> u64 {
+        (self.next_double() * (high - low + 1) as f64) as u64 + low
+    }
+}
+
+/// Muestreo ponderado por el método alias de Vose, tal como en la referencia
+struct WeightedSampler {
+    probabilities: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl WeightedSampler {
+    fn new(weights: &[f64]) -> Self {
+        let count = weights.len();
+        let sum: f64 = weights.iter().sum();
+        let mut scaled: Vec<f64> = weights.iter().map(|weight| weight * count as f64 / sum).collect();
+
+        let (mut small, mut large) = (Vec::new(), Vec::new());
+        for index in (0..count).rev() {
+            if scaled[index] < 1.0 { small.push(index) } else { large.push(index) }
+        }
+
+        let mut probabilities = vec![0.0; count];
+        let mut aliases = vec![0; count];
+        while let (Some(&less), Some(&greater)) = (small.last(), large.last()) {
+            small.pop();
+            large.pop();
+            probabilities[less] = scaled[less];
+            aliases[less] = greater;
+            scaled[greater] += scaled[less] - 1.0;
+            if scaled[greater] < 1.0 { small.push(greater) } else { large.push(greater) }
+        }
+        for index in large.into_iter().chain(small) {
+            probabilities[index] = 1.0;
+        }
+
+        Self { probabilities, aliases }
+    }
+
+    fn next(&self, rng: &mut Xoshiro256) -> usize {
+        let r1 = rng.next_double();
+        let r2 = rng.next_double();
+        let index = (self.probabilities.len() as f64 * r1) as usize;
+        if r2 < self.probabilities[index] { index } else { self.aliases[index] }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Mensaje de prueba de la implementación de referencia
+    pub(in crate::ur) fn make_message(seed: &str, length: usize) -> Vec<u8> {
+        let mut rng = Xoshiro256::from_seed(seed.as_bytes());
+        (0..length).map(|_| rng.next_int(0, 255) as u8).collect()
+    }
+
+    #[test]
+    fn test_reference_rng() {
+        // Vectores de la implementación de referencia (semilla "Wolf")
+        let mut rng = Xoshiro256::from_seed(b"Wolf");
+        assert_eq!(shuffled((1..=10).collect(), &mut rng), [6, 4, 9, 3, 10, 5, 7, 8, 1, 2]);
+
+        let mut rng = Xoshiro256::from_seed(b"Wolf");
+        let sampler = WeightedSampler::new(&[1.0, 2.0, 4.0, 8.0]);
+        let samples: Vec<usize> = (0..10).map(|_| sampler.next(&mut rng)).collect();
+        assert_eq!(samples, [3, 3, 3, 3, 3, 3, 3, 0, 2, 3]);
+
+        let message = make_message("Wolf", 1024);
+        let checksum = crc32fast::hash(&message);
+        let chosen: Vec<Vec<usize>> = (12..=17)
+            .map(|seq_num| {
+                let mut indexes = choose_fragments(seq_num, 11, checksum);
+                indexes.sort();
+                indexes
+            })
+            .collect();
+        assert_eq!(chosen, [vec![9], vec![2, 5, 6, 8, 9, 10], vec![8], vec![1, 5], vec![1], vec![0, 2, 4, 5, 8, 10]]);
+    }
+
+    #[test]
+    fn test_fountain_roundtrip() {
+        let message = make_message("Wolf", 1024);
+        let mut encoder = Encoder::new(&message, 100);
+        assert_eq!(encoder.seq_len(), 11);
+
+        // Un lector que pierde todas las partes simples se recupera con las mixtas
+        let mut decoder = Decoder::new();
+        let mut received = 0;
+        while decoder.message().is_none() {
+            let part = encoder.next_part();
+            if part.seq_num as usize > part.seq_len {
+                let part = Part::from_cbor(&part.to_cbor()).unwrap();
+                decoder.receive(&part).unwrap();
+                received += 1;
+            }
+            assert!(received < 100);
+        }
+        assert_eq!(decoder.message().unwrap(), message);
+        assert_eq!(decoder.progress(), 1.0);
+
+        let mut other = Encoder::new(&make_message("Other", 1024), 100);
+        let mut decoder = Decoder::new();
+        decoder.receive(&Encoder::new(&message, 100).next_part()).unwrap();
+        assert!(decoder.receive(&other.next_part()).is_err());
+
+        // Cabeceras que anuncian demasiadas partes no se aceptan
+        let mut part = Encoder::new(&message, 100).next_part();
+        part.seq_len = MAX_SEQ_LEN + 1;
+        assert!(matches!(
+            Part::from_cbor(&part.to_cbor()),
+            Err(SCypherError::MalformedContainer { defect: Defect::TooLarge, .. })
+        ));
+    }
+}
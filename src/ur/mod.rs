@@ -0,0 +1,312 @@
+//! Uniform Resources de Blockchain Commons (BCR-2020-005)
+//!
+//! Un UR es `ur:<tipo>/<bytewords>`, o `ur:<tipo>/<n>-<total>/<bytewords>` en
+//! varias partes para QR animados. Es lo que entienden los dispositivos sin
+//! conexión tipo Keystone o SeedSigner. Se implementan los tipos:
+//!
+//! - `crypto-seed` (BCR-2020-006): la entropía BIP39 de una frase;
+//! - `bytes`: datos arbitrarios (p.ej. un contenedor de `--armor`);
+//! - `scypher-backup` (propio): una frase cifrada con sus parámetros de
+//!   derivación, ver [`backup`];
+//! - `scypher-share` (propio): una de las partes de un QR repartido con
+//!   `--qr-split`, ver [`share`].
+
+pub mod backup;
+mod bytewords;
+mod cbor;
+pub mod fountain;
+pub mod share;
+
+use zeroize::Zeroizing;
+
+use crate::error::{Defect, SCypherError, Result};
+
+pub use backup::{SeedBackup, BACKUP_VERSION, SCYPHER_BACKUP};
+pub use bytewords::{decode_minimal, encode_minimal};
+pub use share::{UrShare, MAX_SHARES, SCYPHER_SHARE};
+
+/// Tipo de UR de una semilla
+pub const CRYPTO_SEED: &str = "crypto-seed";
+
+/// Tipo de UR de datos arbitrarios
+pub const BYTES: &str = "bytes";
+
+/// Tamaño máximo del CBOR de un UR que se acepta al leer
+pub const MAX_MESSAGE_LEN: usize = 1 << 20;
+
+/// Longitud de fragmento por defecto: QR de tamaño cómodo para cámaras modestas
+pub const DEFAULT_FRAGMENT_LEN: usize = 100;
+
+/// Un UR decodificado: tipo y cuerpo CBOR
+///
+/// El CBOR puede contener material secreto y se limpia de memoria al liberarse.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ur {
+    ur_type: String,
+    cbor: Zeroizing<Vec<u8>>,
+}
+
+impl std::fmt::Debug for Ur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ur").field("ur_type", &self.ur_type).finish_non_exhaustive()
+    }
+}
+
+impl Ur {
+    /// `ur:crypto-seed` con la entropía BIP39 de `phrase`
+    pub fn crypto_seed(phrase: &str) -> Result<Self> {
+        let entropy = Zeroizing::new(crate::bip39::phrase_to_entropy(phrase)?);
+
+        let mut cbor = Zeroizing::new(Vec::with_capacity(entropy.len() + 4));
+        cbor::write_head(&mut cbor, cbor::MAP, 1);
+        cbor::write_head(&mut cbor, cbor::UNSIGNED, 1);
+        cbor::write_bytes(&mut cbor, &entropy);
+
+        Ok(Self { ur_type: CRYPTO_SEED.to_string(), cbor })
+    }
+
+    /// `ur:bytes` con `data`
+    pub fn bytes(data: &[u8]) -> Self {
+        let mut cbor = Zeroizing::new(Vec::with_capacity(data.len() + 9));
+        cbor::write_bytes(&mut cbor, data);
+        Self { ur_type: BYTES.to_string(), cbor }
+    }
+
+    /// Tipo del UR (`crypto-seed`, `bytes`...)
+    pub fn ur_type(&self) -> &str {
+        &self.ur_type
+    }
+
+    /// Frase BIP39 de un `ur:crypto-seed` (se ignoran la fecha y demás campos opcionales)
+    pub fn to_phrase(&self) -> Result<String> {
+        if self.ur_type != CRYPTO_SEED {
+            return Err(SCypherError::InvalidRequest(format!("expected ur:{}, found ur:{}", CRYPTO_SEED, self.ur_type)));
+        }
+
+        let mut reader = cbor::Reader::new(&self.cbor);
+        let mut entropy = None;
+        for _ in 0..reader.expect(cbor::MAP)? {
+            match reader.head()? {
+                (cbor::UNSIGNED, 1) => entropy = Some(reader.bytes()?),
+                (cbor::UNSIGNED, _) => reader.skip()?,
+                _ => return Err(SCypherError::malformed("ur", Defect::Unsupported, "crypto-seed map key")),
+            }
+        }
+
+        if !reader.is_empty() {
+            return Err(SCypherError::malformed("ur", Defect::Inconsistent, "data after the crypto-seed map"));
+        }
+        let entropy = entropy.ok_or_else(|| SCypherError::malformed("ur", Defect::Truncated, "crypto-seed without payload"))?;
+        crate::bip39::entropy_to_phrase(entropy)
+    }
+
+    /// Contenido de un `ur:bytes`
+    pub fn to_bytes(&self) -> Result<Zeroizing<Vec<u8>>> {
+        if self.ur_type != BYTES {
+            return Err(SCypherError::InvalidRequest(format!("expected ur:{}, found ur:{}", BYTES, self.ur_type)));
+        }
+        let mut reader = cbor::Reader::new(&self.cbor);
+        let bytes = Zeroizing::new(reader.bytes()?.to_vec());
+        if !reader.is_empty() {
+            return Err(SCypherError::malformed("ur", Defect::Inconsistent, "data after the byte string"));
+        }
+        Ok(bytes)
+    }
+
+    /// Codificación en una sola parte (`ur:<tipo>/<bytewords>`)
+    pub fn to_single_part(&self) -> String {
+        format!("ur:{}/{}", self.ur_type, encode_minimal(&self.cbor))
+    }
+
+    /// Leer un UR de una sola parte
+    pub fn from_single_part(encoded: &str) -> Result<Self> {
+        match parse(encoded)? {
+            (ur_type, None, body) => Ok(Self { ur_type, cbor: Zeroizing::new(decode_minimal(body)?) }),
+            (_, Some(_), _) => Err(SCypherError::InvalidRequest("multi-part UR; use a UR decoder".to_string())),
+        }
+    }
+}
+
+/// Número de parte y total de partes de un UR multiparte
+type Sequence = (u32, usize);
+
+/// Separar `ur:<tipo>/[<n>-<total>/]<cuerpo>`; el tipo se devuelve en minúsculas
+fn parse(encoded: &str) -> Result<(String, Option<Sequence>, &str)> {
+    let invalid = |what: &str| SCypherError::malformed("ur", Defect::Encoding, what);
+
+    let encoded = encoded.trim();
+    let rest = encoded
+        .get(..3)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("ur:"))
+        .map(|_| &encoded[3..])
+        .ok_or_else(|| invalid("missing ur: prefix"))?;
+
+    let components: Vec<&str> = rest.split('/').collect();
+    let ur_type = components[0].to_ascii_lowercase();
+    if ur_type.is_empty() || !ur_type.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-') {
+        return Err(invalid("bad type"));
+    }
+
+    match components[1..] {
+        [body] => Ok((ur_type, None, body)),
+        [sequence, body] => {
+            let (seq_num, seq_len) = sequence
+                .split_once('-')
+                .and_then(|(num, len)| Some((num.parse().ok()?, len.parse().ok()?)))
+                .ok_or_else(|| invalid("bad sequence"))?;
+            Ok((ur_type, Some((seq_num, seq_len)), body))
+        }
+        _ => Err(invalid("wrong number of components")),
+    }
+}
+
+/// Emisor de partes de un UR, para un QR animado
+pub struct UrEncoder {
+    ur_type: String,
+    single: Option<String>,
+    fountain: fountain::Encoder,
+}
+
+impl UrEncoder {
+    /// Partir `ur` en fragmentos de hasta `max_fragment_len` bytes
+    pub fn new(ur: &Ur, max_fragment_len: usize) -> Self {
+        let fountain = fountain::Encoder::new(&ur.cbor, max_fragment_len);
+        let single = (fountain.seq_len() == 1).then(|| ur.to_single_part());
+        Self { ur_type: ur.ur_type.clone(), single, fountain }
+    }
+
+    /// Si cabe en una sola parte (QR estático)
+    pub fn is_single_part(&self) -> bool {
+        self.single.is_some()
+    }
+
+    /// Partes mínimas para un lector que no pierde ninguna
+    pub fn seq_len(&self) -> usize {
+        self.fountain.seq_len()
+    }
+
+    /// Siguiente parte; en un UR de una sola parte siempre es la misma
+    pub fn next_part(&mut self) -> String {
+        if let Some(single) = &self.single {
+            return single.clone();
+        }
+
+        let part = self.fountain.next_part();
+        format!("ur:{}/{}-{}/{}", self.ur_type, part.seq_num, part.seq_len, encode_minimal(&part.to_cbor()))
+    }
+}
+
+/// Receptor de partes de un UR (una sola o varias, en cualquier orden)
+#[derive(Default)]
+pub struct UrDecoder {
+    ur_type: Option<String>,
+    fountain: fountain::Decoder,
+    result: Option<Ur>,
+}
+
+impl UrDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Añadir una parte leída del QR
+    pub fn receive(&mut self, encoded: &str) -> Result<()> {
+        if self.result.is_some() {
+            return Ok(());
+        }
+
+        let (ur_type, sequence, body) = parse(encoded)?;
+        if let Some(expected) = self.ur_type.as_ref().filter(|expected| **expected != ur_type) {
+            return Err(SCypherError::malformed(
+                "ur",
+                Defect::Inconsistent,
+                format!("part of type {} in a ur:{} sequence", ur_type, expected),
+            ));
+        }
+
+        match sequence {
+            None => {
+                self.result = Some(Ur { ur_type, cbor: Zeroizing::new(decode_minimal(body)?) });
+            }
+            Some((seq_num, seq_len)) => {
+                let part = fountain::Part::from_cbor(&decode_minimal(body)?)?;
+                if (part.seq_num, part.seq_len) != (seq_num, seq_len) {
+                    return Err(SCypherError::malformed("ur", Defect::Inconsistent, "sequence does not match its part"));
+                }
+
+                self.fountain.receive(&part)?;
+                if let Some(message) = self.fountain.message() {
+                    self.result = Some(Ur { ur_type: ur_type.clone(), cbor: Zeroizing::new(message.to_vec()) });
+                }
+                self.ur_type = Some(ur_type);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fracción estimada recibida (0.0 a 1.0)
+    pub fn progress(&self) -> f64 {
+        if self.result.is_some() { 1.0 } else { self.fountain.progress() }
+    }
+
+    /// UR completo, cuando ya se tiene
+    pub fn result(&self) -> Option<&Ur> {
+        self.result.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crypto_seed() {
+        // BCR-2020-006: semilla de 16 bytes, sin fecha
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let ur = Ur::crypto_seed(phrase).unwrap();
+        let encoded = ur.to_single_part();
+        let cbor = hex::decode(format!("a10150{}", "00".repeat(16))).unwrap();
+        assert_eq!(encoded, format!("ur:crypto-seed/{}", encode_minimal(&cbor)));
+
+        let decoded = Ur::from_single_part(&encoded.to_uppercase()).unwrap();
+        assert_eq!(decoded.to_phrase().unwrap(), phrase);
+        assert!(decoded.to_bytes().is_err());
+
+        // Campos opcionales (2: fecha con tag 100) se ignoran
+        let with_date = hex::decode(format!("a20150{}02d8641a5f5e1000", "00".repeat(16))).unwrap();
+        let ur = Ur::from_single_part(&format!("ur:crypto-seed/{}", encode_minimal(&with_date))).unwrap();
+        assert_eq!(ur.to_phrase().unwrap(), phrase);
+    }
+
+    #[test]
+    fn test_multipart_reference() {
+        // Partes del ejemplo multiparte de la implementación de referencia
+        let message = fountain::tests::make_message("Wolf", 256);
+        let mut encoder = UrEncoder::new(&Ur::bytes(&message), 30);
+        let parts: Vec<String> = (0..12).map(|_| encoder.next_part()).collect();
+
+        assert_eq!(parts[0], "ur:bytes/1-9/lpadascfadaxcywenbpljkhdcahkadaemejtswhhylkepmykhhtsytsnoyoyaxaedsuttydmmhhpktpmsrjtdkgslpgh");
+        assert_eq!(parts[1], "ur:bytes/2-9/lpaoascfadaxcywenbpljkhdcagwdpfnsboxgwlbaawzuefywkdplrsrjynbvygabwjldapfcsgmghhkhstlrdcxaefz");
+        assert_eq!(parts[9], "ur:bytes/10-9/lpbkascfadaxcywenbpljkhdcahkadaemejtswhhylkepmykhhtsytsnoyoyaxaedsuttydmmhhpktpmsrjtwdkiplzs");
+        assert_eq!(parts[11], "ur:bytes/12-9/lpbnascfadaxcywenbpljkhdcarllaluzmdmgstospeyiefmwejlwtpedamktksrvlcygmzemovovllarodtmtbnptrs");
+
+        // Sin las partes 1 y 7: la 10 repite el fragmento 0 y la 12 (fragmentos 2 y 6) da el 6
+        let mut decoder = UrDecoder::new();
+        for (index, part) in parts.iter().enumerate() {
+            if index != 0 && index != 6 {
+                decoder.receive(&part.to_uppercase()).unwrap();
+            }
+        }
+        assert_eq!(*decoder.result().unwrap().to_bytes().unwrap(), message);
+    }
+
+    #[test]
+    fn test_invalid_parts() {
+        let mut decoder = UrDecoder::new();
+        assert!(decoder.receive("bytes/aeadaolazmjendeoti").is_err());
+        assert!(decoder.receive("ur:bytes/x-9/aeadaolazmjendeoti").is_err());
+        assert!(decoder.receive("ur:Bad Type/aeadaolazmjendeoti").is_err());
+        assert!(decoder.result().is_none());
+        assert!(Ur::from_single_part("ur:bytes/1-9/lpadascfadaxcywenbpljkhdcahkadaemejtswhhylkepmykhhtsytsnoyoyaxaedsuttydmmhhpktpmsrjtdkgslpgh").is_err());
+    }
+}
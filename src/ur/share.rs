@@ -0,0 +1,280 @@
+//! `ur:scypher-share`: una de las partes de un QR repartido con `--qr-split`
+//!
+//! El UR del resultado se reparte en N partes XOR: las N-1 primeras son
+//! bytes aleatorios y la última es el CBOR del UR con todas ellas aplicadas.
+//! Hacen falta todas para reconstruirlo; con cualquier grupo menor el
+//! contenido es indistinguible del ruido y solo se ve su longitud y su tipo.
+//! El cuerpo es un mapa CBOR:
+//!
+//! | clave | valor                                              |
+//! |-------|----------------------------------------------------|
+//! | 1     | versión del sobre (1)                              |
+//! | 2     | identificador del reparto (8 bytes aleatorios)     |
+//! | 3     | número de la parte (de 1 al total)                 |
+//! | 4     | total de partes                                    |
+//! | 5     | tipo del UR repartido (ASCII, como bytes)          |
+//! | 6     | parte XOR del CBOR del UR repartido (bytes)        |
+//!
+//! Todas las claves son obligatorias y una desconocida se rechaza, como en
+//! [`super::backup`].
+
+use std::collections::BTreeMap;
+
+use zeroize::Zeroizing;
+
+use crate::crypto::SecureRng;
+use crate::error::{Defect, SCypherError, Result};
+
+use super::{cbor, parse, Ur, UrDecoder};
+
+/// Tipo de UR de una parte de un QR repartido
+pub const SCYPHER_SHARE: &str = "scypher-share";
+
+/// Versión del sobre que se escribe
+pub const SHARE_VERSION: u64 = 1;
+
+/// Máximo de partes de un reparto
+pub const MAX_SHARES: usize = 16;
+
+/// Bytes del identificador que une las partes de un mismo reparto
+const SET_ID_LEN: usize = 8;
+
+fn malformed(defect: Defect, message: impl Into<String>) -> SCypherError {
+    SCypherError::malformed("scypher-share", defect, message)
+}
+
+/// Parte leída de un `ur:scypher-share`
+pub struct UrShare {
+    set_id: Vec<u8>,
+    index: usize,
+    total: usize,
+    ur_type: String,
+    data: Zeroizing<Vec<u8>>,
+}
+
+impl UrShare {
+    /// Número de la parte (de 1 a `total`)
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Partes del reparto
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    fn to_ur(&self) -> Ur {
+        let mut cbor = Zeroizing::new(Vec::with_capacity(self.data.len() + 64));
+        cbor::write_head(&mut cbor, cbor::MAP, 6);
+        for (key, value) in [(1, SHARE_VERSION), (3, self.index as u64), (4, self.total as u64)] {
+            cbor::write_head(&mut cbor, cbor::UNSIGNED, key);
+            cbor::write_head(&mut cbor, cbor::UNSIGNED, value);
+        }
+        for (key, value) in [(2, &self.set_id[..]), (5, self.ur_type.as_bytes()), (6, &self.data[..])] {
+            cbor::write_head(&mut cbor, cbor::UNSIGNED, key);
+            cbor::write_bytes(&mut cbor, value);
+        }
+        Ur { ur_type: SCYPHER_SHARE.to_string(), cbor }
+    }
+
+    /// Reconstruir el UR repartido; hacen falta todas las partes
+    ///
+    /// Una parte repetida (el mismo QR escaneado dos veces) se ignora.
+    pub fn combine(shares: Vec<UrShare>) -> Result<Ur> {
+        let mut shares = shares.into_iter();
+        let first = shares.next().ok_or_else(|| malformed(Defect::Truncated, "no shares"))?;
+
+        let mut by_index = BTreeMap::new();
+        for share in shares {
+            if (&share.set_id, share.total, &share.ur_type, share.data.len()) != (&first.set_id, first.total, &first.ur_type, first.data.len()) {
+                return Err(SCypherError::InvalidRequest(format!(
+                    "share {} of {} does not belong to the same split QR as share {} of {}", share.index, share.total, first.index, first.total)));
+            }
+            if share.index == first.index || by_index.contains_key(&share.index) {
+                let known = by_index.get(&share.index).unwrap_or(&first);
+                if known.data != share.data {
+                    return Err(malformed(Defect::Inconsistent, format!("two different shares numbered {}", share.index)));
+                }
+                continue;
+            }
+            by_index.insert(share.index, share);
+        }
+
+        let (index, total, ur_type) = (first.index, first.total, first.ur_type.clone());
+        let mut cbor = first.data.clone();
+        by_index.insert(index, first);
+        let missing: Vec<String> = (1..=total).filter(|index| !by_index.contains_key(index)).map(|index| index.to_string()).collect();
+        if !missing.is_empty() {
+            return Err(SCypherError::InvalidRequest(format!(
+                "split QR is incomplete: missing share(s) {} of {}; every share is needed", missing.join(", "), total)));
+        }
+        for share in by_index.values().filter(|share| share.index != index) {
+            crate::crypto::xor::xor_in_place(&mut cbor, &share.data)?;
+        }
+        Ok(Ur { ur_type, cbor })
+    }
+}
+
+impl Ur {
+    /// Repartir el UR en `total` partes `ur:scypher-share`, todas necesarias
+    pub fn split(&self, total: usize) -> Result<Vec<Ur>> {
+        self.split_with_rng(total, &mut rand::rngs::OsRng)
+    }
+
+    /// `split` con las partes aleatorias tomadas de `rng`
+    pub fn split_with_rng(&self, total: usize, rng: &mut dyn SecureRng) -> Result<Vec<Ur>> {
+        if !(2..=MAX_SHARES).contains(&total) {
+            return Err(SCypherError::InvalidRequest(format!("a QR can be split into 2-{} shares, not {}", MAX_SHARES, total)));
+        }
+
+        let mut set_id = vec![0u8; SET_ID_LEN];
+        rng.fill_bytes(&mut set_id);
+        let mut last = self.cbor.clone();
+        let mut data = Vec::with_capacity(total);
+        for _ in 1..total {
+            let mut random = Zeroizing::new(vec![0u8; self.cbor.len()]);
+            rng.fill_bytes(&mut random);
+            crate::crypto::xor::xor_in_place(&mut last, &random)?;
+            data.push(random);
+        }
+        data.push(last);
+
+        Ok(data
+            .into_iter()
+            .enumerate()
+            .map(|(index, data)| UrShare { set_id: set_id.clone(), index: index + 1, total, ur_type: self.ur_type.clone(), data }.to_ur())
+            .collect())
+    }
+
+    /// Parte de un `ur:scypher-share`
+    pub fn to_share(&self) -> Result<UrShare> {
+        if self.ur_type != SCYPHER_SHARE {
+            return Err(SCypherError::InvalidRequest(format!("expected ur:{}, found ur:{}", SCYPHER_SHARE, self.ur_type)));
+        }
+
+        let mut reader = cbor::Reader::new(&self.cbor);
+        let mut numbers: [Option<u64>; 5] = [None; 5];
+        let mut fields: [Option<&[u8]>; 7] = [None; 7];
+        for _ in 0..reader.expect(cbor::MAP)? {
+            let key = reader.expect(cbor::UNSIGNED)?;
+            let duplicate = || malformed(Defect::Inconsistent, format!("key {} appears twice", key));
+            match key {
+                1 | 3 | 4 if numbers[key as usize].is_none() => numbers[key as usize] = Some(reader.expect(cbor::UNSIGNED)?),
+                2 | 5 | 6 if fields[key as usize].is_none() => fields[key as usize] = Some(reader.bytes()?),
+                1..=6 => return Err(duplicate()),
+                _ => return Err(malformed(Defect::Unsupported, format!("unknown key {}", key))),
+            }
+        }
+        if !reader.is_empty() {
+            return Err(malformed(Defect::Inconsistent, "data after the share map"));
+        }
+
+        let missing = |key: usize| malformed(Defect::Truncated, format!("key {} is missing", key));
+        match numbers[1].ok_or_else(|| missing(1))? {
+            SHARE_VERSION => {}
+            version => return Err(malformed(Defect::Unsupported, format!("envelope version {}", version))),
+        }
+        let total = numbers[4].ok_or_else(|| missing(4))?;
+        let index = numbers[3].ok_or_else(|| missing(3))?;
+        if !(2..=MAX_SHARES as u64).contains(&total) || !(1..=total).contains(&index) {
+            return Err(malformed(Defect::Inconsistent, format!("share {} of {}", index, total)));
+        }
+        let ur_type = std::str::from_utf8(fields[5].ok_or_else(|| missing(5))?)
+            .ok()
+            .filter(|name| !name.is_empty() && name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'))
+            .ok_or_else(|| malformed(Defect::Encoding, "bad UR type"))?;
+
+        Ok(UrShare {
+            set_id: fields[2].ok_or_else(|| missing(2))?.to_vec(),
+            index: index as usize,
+            total: total as usize,
+            ur_type: ur_type.to_string(),
+            data: Zeroizing::new(fields[6].ok_or_else(|| missing(6))?.to_vec()),
+        })
+    }
+}
+
+/// Si `line` es una parte UR de un QR repartido
+pub fn is_share_line(line: &str) -> bool {
+    parse(line).is_ok_and(|(ur_type, ..)| ur_type == SCYPHER_SHARE)
+}
+
+/// Reconstruir el UR repartido a partir de las partes escaneadas, una por línea
+///
+/// Una parte que cabe en un QR estático ocupa una línea; las piezas de una
+/// parte animada van seguidas y una línea vacía la separa de la siguiente.
+/// Las piezas de sobra de una parte ya completa se ignoran.
+pub fn combine_share_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Result<Ur> {
+    let mut shares = Vec::new();
+    let mut decoder = UrDecoder::new();
+    let mut pending = false;
+
+    for line in lines.into_iter().map(str::trim).chain([""]) {
+        if line.is_empty() {
+            if pending {
+                return Err(malformed(Defect::Truncated, format!(
+                    "an animated share is incomplete ({:.0}% of its parts)", decoder.progress() * 100.0)));
+            }
+            decoder = UrDecoder::new();
+            continue;
+        }
+
+        if parse(line)?.1.is_none() {
+            shares.push(Ur::from_single_part(line)?.to_share()?);
+            continue;
+        }
+        if decoder.result().is_some() {
+            continue;
+        }
+        decoder.receive(line)?;
+        pending = true;
+        if let Some(ur) = decoder.result() {
+            shares.push(ur.to_share()?);
+            pending = false;
+        }
+    }
+
+    UrShare::combine(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seeded_rng;
+    use crate::ur::{UrEncoder, DEFAULT_FRAGMENT_LEN};
+
+    #[test]
+    fn test_split_and_combine() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let ur = Ur::crypto_seed(phrase).unwrap();
+        let parts = ur.split_with_rng(3, &mut seeded_rng([1; 32])).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|part| part.ur_type() == SCYPHER_SHARE));
+
+        // En cualquier orden, con una parte repetida
+        let lines: Vec<String> = [2, 0, 2, 1].iter().map(|&i| parts[i].to_single_part().to_uppercase()).collect();
+        assert!(lines.iter().all(|line| is_share_line(line)));
+        assert_eq!(combine_share_lines(lines.iter().map(String::as_str)).unwrap(), ur);
+
+        // Sin una parte, o con una de otro reparto, no se reconstruye
+        let two: Vec<String> = parts[..2].iter().map(Ur::to_single_part).collect();
+        let error = combine_share_lines(two.iter().map(String::as_str)).unwrap_err();
+        assert!(error.to_string().contains("missing share(s) 3 of 3"));
+        let other = ur.split_with_rng(3, &mut seeded_rng([2; 32])).unwrap();
+        let mixed = [parts[0].to_single_part(), parts[1].to_single_part(), other[2].to_single_part()];
+        assert!(combine_share_lines(mixed.iter().map(String::as_str)).is_err());
+        assert!(ur.split(1).is_err() && ur.split(MAX_SHARES + 1).is_err());
+
+        // Partes animadas: piezas seguidas, de sobra, y una línea vacía entre partes
+        let large = Ur::bytes(&[0x5A; 500]);
+        let mut lines = Vec::new();
+        for part in large.split(2).unwrap() {
+            let mut encoder = UrEncoder::new(&part, DEFAULT_FRAGMENT_LEN);
+            assert!(!encoder.is_single_part());
+            lines.extend((0..encoder.seq_len() + 2).map(|_| encoder.next_part()));
+            lines.push(String::new());
+        }
+        assert_eq!(combine_share_lines(lines.iter().map(String::as_str)).unwrap(), large);
+        assert!(!is_share_line(&large.to_single_part()));
+    }
+}
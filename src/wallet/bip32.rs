@@ -0,0 +1,249 @@
+//! Derivación jerárquica BIP32 sobre secp256k1
+
+use std::fmt;
+use std::str::FromStr;
+
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{FieldBytes, NonZeroScalar, PublicKey, Scalar, SecretKey};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::error::{Parameter, SCypherError, Result};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Bit que marca un índice endurecido (hardened)
+const HARDENED: u32 = 0x8000_0000;
+
+/// Índice de un hijo en la derivación
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    /// Hijo normal `index` (< 2^31)
+    pub fn normal(index: u32) -> Option<Self> {
+        (index < HARDENED).then_some(Self(index))
+    }
+
+    /// Hijo endurecido `index'` (< 2^31)
+    pub fn hardened(index: u32) -> Option<Self> {
+        (index < HARDENED).then_some(Self(index | HARDENED))
+    }
+
+    /// Si el índice es endurecido
+    pub fn is_hardened(self) -> bool {
+        self.0 & HARDENED != 0
+    }
+
+    /// Valor de 32 bits tal como entra en la derivación
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.0 & !HARDENED)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// Ruta de derivación, p.ej. `m/44'/60'/0'/0/0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    /// Índices de la ruta, desde la raíz
+    pub fn children(&self) -> &[ChildNumber] {
+        &self.0
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(children: Vec<ChildNumber>) -> Self {
+        Self(children)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = SCypherError;
+
+    /// Acepta `'`, `h` o `H` como marca de endurecido
+    fn from_str(path: &str) -> Result<Self> {
+        let invalid = || SCypherError::invalid_parameter(Parameter::DerivationPath, path, "BIP32 path such as m/44'/60'/0'/0/0");
+
+        let mut parts = path.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(invalid());
+        }
+
+        parts
+            .map(|part| {
+                let (digits, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+                    Some(digits) => (digits, true),
+                    None => (part, false),
+                };
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+
+                let index: u32 = digits.parse().map_err(|_| invalid())?;
+                let child = if hardened { ChildNumber::hardened(index) } else { ChildNumber::normal(index) };
+                child.ok_or_else(invalid)
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("m")?;
+        for child in &self.0 {
+            write!(f, "/{}", child)?;
+        }
+        Ok(())
+    }
+}
+
+/// Clave privada extendida (clave secp256k1 + chain code)
+///
+/// `SecretKey` y el chain code se limpian de memoria al liberarse.
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    secret: SecretKey,
+    chain_code: Zeroizing<[u8; 32]>,
+}
+
+impl ExtendedPrivateKey {
+    /// Clave maestra a partir de una semilla BIP39
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let mut mac = HmacSha512::new_from_slice(b"Bitcoin seed")
+            .map_err(|_| SCypherError::crypto("Cannot initialize HMAC-SHA512"))?;
+        mac.update(seed);
+
+        Self::from_hmac(mac, None)
+    }
+
+    /// Derivar todos los hijos de `path` desde esta clave
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        let mut key = self.clone();
+        for &child in path.children() {
+            key = key.derive_child(child)?;
+        }
+        Ok(key)
+    }
+
+    /// Derivar el hijo `child` (CKDpriv)
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self> {
+        let mut mac = HmacSha512::new_from_slice(&self.chain_code[..])
+            .map_err(|_| SCypherError::crypto("Cannot initialize HMAC-SHA512"))?;
+
+        if child.is_hardened() {
+            mac.update(&[0]);
+            mac.update(&self.secret.to_bytes());
+        } else {
+            mac.update(self.public_key().to_encoded_point(true).as_bytes());
+        }
+        mac.update(&child.to_u32().to_be_bytes());
+
+        Self::from_hmac(mac, Some(&self.secret))
+    }
+
+    /// Separar I = IL || IR; IL se suma a la clave del padre si la hay
+    fn from_hmac(mac: HmacSha512, parent: Option<&SecretKey>) -> Result<Self> {
+        let mut output = Zeroizing::new([0u8; 64]);
+        output.copy_from_slice(&mac.finalize().into_bytes());
+
+        // IL >= n o una clave resultante nula invalidan el índice (probabilidad ~2^-127)
+        let invalid = || SCypherError::crypto("Derived key is invalid for this index; use the next one");
+        let tweak = Option::<Scalar>::from(Scalar::from_repr(*FieldBytes::from_slice(&output[..32])))
+            .ok_or_else(invalid)?;
+        let scalar = match parent {
+            Some(parent) => tweak + parent.to_nonzero_scalar().as_ref(),
+            None => tweak,
+        };
+        let scalar = Option::<NonZeroScalar>::from(NonZeroScalar::new(scalar)).ok_or_else(invalid)?;
+
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&output[32..]);
+
+        Ok(Self {
+            secret: SecretKey::from(scalar),
+            chain_code,
+        })
+    }
+
+    /// Clave privada secp256k1
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret
+    }
+
+    /// Clave privada en bytes (32, big-endian)
+    pub fn private_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        bytes.copy_from_slice(&self.secret.to_bytes());
+        bytes
+    }
+
+    /// Clave pública correspondiente
+    pub fn public_key(&self) -> PublicKey {
+        self.secret.public_key()
+    }
+
+    /// Chain code (32 bytes)
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip32_vector_1() {
+        // Vector de prueba 1 de BIP32
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+        assert_eq!(
+            hex::encode(&master.private_key_bytes()[..]),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+
+        let path: DerivationPath = "m/0'/1/2'/2/1000000000".parse().unwrap();
+        let child = master.derive_path(&path).unwrap();
+        assert_eq!(
+            hex::encode(&child.private_key_bytes()[..]),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+        assert_eq!(
+            hex::encode(child.chain_code()),
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e"
+        );
+    }
+
+    #[test]
+    fn test_derivation_path() {
+        let path: DerivationPath = "m/44'/60h/0H/0/7".parse().unwrap();
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/7");
+        assert!(path.children()[0].is_hardened());
+        assert!(!path.children()[4].is_hardened());
+
+        assert_eq!("m".parse::<DerivationPath>().unwrap().children().len(), 0);
+        for invalid in ["", "44'/0", "m/", "m/-1", "m/2147483648", "m/1''", "m/x"] {
+            assert!(invalid.parse::<DerivationPath>().is_err(), "{}", invalid);
+        }
+    }
+}
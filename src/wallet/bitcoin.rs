@@ -0,0 +1,167 @@
+//! Claves Bitcoin: direcciones P2PKH y claves protegidas BIP38
+//!
+//! BIP38 (sin multiplicación EC) cifra una clave privada con una passphrase:
+//! scrypt(passphrase, hash de la dirección) da la clave AES-256 y una
+//! máscara para las dos mitades de la clave. El resultado (`6P...`) se
+//! puede entregar suelto: sin la passphrase no revela la clave, y con ella
+//! no revela nada de la semilla de la que procede.
+
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes256;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{PublicKey, SecretKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::error::{SCypherError, Result};
+
+/// Ruta BIP44 de la primera dirección de recepción Bitcoin
+pub const DEFAULT_PATH: &str = "m/44'/0'/0'/0/0";
+
+/// Prefijo y flags de una clave BIP38 sin multiplicación EC y con clave pública comprimida
+const BIP38_PREFIX: [u8; 2] = [0x01, 0x42];
+const BIP38_FLAG_COMPRESSED: u8 = 0xE0;
+
+/// Parámetros de scrypt fijados por BIP38: N = 2^14, r = 8, p = 8
+const BIP38_LOG_N: u8 = 14;
+const BIP38_R: u32 = 8;
+const BIP38_P: u32 = 8;
+
+/// RIPEMD-160(SHA-256(datos))
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Dirección P2PKH (`1...`) de la clave pública comprimida
+pub fn p2pkh_address(public_key: &PublicKey) -> String {
+    p2pkh(public_key.to_encoded_point(true).as_bytes())
+}
+
+fn p2pkh(public_key: &[u8]) -> String {
+    let mut payload = [0u8; 21];
+    payload[1..].copy_from_slice(&hash160(public_key));
+    bs58::encode(payload).with_check().into_string()
+}
+
+/// Cifrar una clave privada en formato BIP38 (`6P...`) con su dirección comprimida
+pub fn encrypt_bip38(private_key: &[u8; 32], passphrase: &str) -> Result<String> {
+    let secret = SecretKey::from_slice(private_key)
+        .map_err(|_| SCypherError::crypto("Invalid secp256k1 private key"))?;
+    let address_hash = address_hash(&p2pkh_address(&secret.public_key()));
+    let derived = derive_key(passphrase, &address_hash)?;
+
+    let cipher = Aes256::new(derived[32..].into());
+    let mut payload = Zeroizing::new([0u8; 39]);
+    payload[..2].copy_from_slice(&BIP38_PREFIX);
+    payload[2] = BIP38_FLAG_COMPRESSED;
+    payload[3..7].copy_from_slice(&address_hash);
+    for half in 0..2 {
+        let block = &mut payload[7 + half * 16..7 + (half + 1) * 16];
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte = private_key[half * 16 + i] ^ derived[half * 16 + i];
+        }
+        cipher.encrypt_block(block.into());
+    }
+
+    Ok(bs58::encode(&payload[..]).with_check().into_string())
+}
+
+/// Descifrar una clave BIP38 sin multiplicación EC
+///
+/// Si la dirección de la clave obtenida no coincide con el hash guardado, la
+/// passphrase es incorrecta (`InvalidPassword`).
+pub fn decrypt_bip38(encrypted: &str, passphrase: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let invalid = |what: &str| SCypherError::InvalidRequest(format!("unsupported BIP38 key: {}", what));
+
+    let payload = bs58::decode(encrypted.trim()).with_check(None).into_vec()
+        .map_err(|_| invalid("bad base58check"))?;
+    if payload.len() != 39 || payload[..2] != BIP38_PREFIX {
+        return Err(invalid("expected a non-EC-multiplied key"));
+    }
+    let compressed = match payload[2] {
+        BIP38_FLAG_COMPRESSED => true,
+        0xC0 => false,
+        _ => return Err(invalid("unknown flags")),
+    };
+
+    let derived = derive_key(passphrase, &payload[3..7])?;
+    let cipher = Aes256::new(derived[32..].into());
+    let mut private_key = Zeroizing::new([0u8; 32]);
+    private_key.copy_from_slice(&payload[7..]);
+    for half in 0..2 {
+        let block = &mut private_key[half * 16..(half + 1) * 16];
+        cipher.decrypt_block(block.into());
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte ^= derived[half * 16 + i];
+        }
+    }
+
+    let secret = SecretKey::from_slice(&private_key[..]).map_err(|_| SCypherError::InvalidPassword)?;
+    let address = p2pkh(secret.public_key().to_encoded_point(compressed).as_bytes());
+    if address_hash(&address)[..] != payload[3..7] {
+        return Err(SCypherError::InvalidPassword);
+    }
+    Ok(private_key)
+}
+
+/// Primeros 4 bytes de SHA-256(SHA-256(dirección)), que hacen de salt
+fn address_hash(address: &str) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(address.as_bytes()));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// 64 bytes de scrypt: máscara (32) || clave AES-256 (32); la passphrase va en NFC
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 64]>> {
+    let mut normalized = Zeroizing::new(String::with_capacity(passphrase.len() * 3));
+    normalized.extend(passphrase.nfc());
+
+    let params = scrypt::Params::new(BIP38_LOG_N, BIP38_R, BIP38_P, 64)
+        .map_err(|e| SCypherError::crypto(format!("Invalid scrypt parameters: {}", e)))?;
+
+    let mut derived = Zeroizing::new([0u8; 64]);
+    scrypt::scrypt(normalized.as_bytes(), salt, &params, &mut derived[..])
+        .map_err(|e| SCypherError::crypto(format!("scrypt failed: {}", e)))?;
+    Ok(derived)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip38_vectors() {
+        // Vectores de BIP38: sin multiplicación EC, con compresión
+        let vectors = [
+            (
+                "TestingOneTwoThree",
+                "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+                "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+            ),
+            (
+                "Satoshi",
+                "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+                "6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7",
+            ),
+        ];
+
+        for (passphrase, private_key, encrypted) in vectors {
+            let private_key: [u8; 32] = hex::decode(private_key).unwrap().try_into().unwrap();
+            assert_eq!(encrypt_bip38(&private_key, passphrase).unwrap(), encrypted);
+            assert_eq!(*decrypt_bip38(encrypted, passphrase).unwrap(), private_key);
+        }
+
+        assert!(matches!(
+            decrypt_bip38("6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7", "satoshi"),
+            Err(SCypherError::InvalidPassword)
+        ));
+    }
+
+    #[test]
+    fn test_p2pkh_address() {
+        let private_key = hex::decode("cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5").unwrap();
+        let secret = SecretKey::from_slice(&private_key).unwrap();
+        assert_eq!(p2pkh_address(&secret.public_key()), "164MQi977u9GUteHr4EPH27VkkdxmfCvGW");
+    }
+}
@@ -0,0 +1,196 @@
+//! Cartera Cardano (Icarus) para comprobar una recuperación
+//!
+//! La suma de comprobación BIP39 solo dice que la frase está bien formada;
+//! para saber que es la cartera del usuario hay que ver su dirección. Las
+//! carteras Shelley (Yoroi, Eternl, Daedalus, Ledger/Trezor) derivan la
+//! clave maestra Icarus a partir de la entropía y luego BIP32-Ed25519 (V2)
+//! hasta las claves de pago y de stake de la dirección base.
+
+use blake2::digest::consts::U28;
+use blake2::{Blake2b, Digest};
+use bech32::{ToBase32, Variant};
+use curve25519_dalek::{EdwardsPoint, Scalar};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use super::{ChildNumber, DerivationPath};
+use crate::error::{SCypherError, Result};
+
+type HmacSha512 = Hmac<Sha512>;
+type Blake2b224 = Blake2b<U28>;
+
+/// Cuenta 0 de CIP-1852 y las claves de la primera dirección base
+pub const ACCOUNT_PATH: &str = "m/1852'/1815'/0'";
+const PAYMENT_CHILDREN: [u32; 2] = [0, 0];
+const STAKE_CHILDREN: [u32; 2] = [2, 0];
+
+/// Iteraciones de PBKDF2 de la clave maestra Icarus
+const ICARUS_ITERATIONS: u32 = 4096;
+
+/// Cabecera de una dirección base de red principal con clave de pago y de stake (CIP-19)
+const BASE_ADDRESS_MAINNET: u8 = 0x01;
+
+/// Clave privada extendida BIP32-Ed25519: kL || kR y chain code
+///
+/// Todo se limpia de memoria al liberarse.
+#[derive(Clone)]
+pub struct ExtendedSigningKey {
+    key: Zeroizing<[u8; 64]>,
+    chain_code: Zeroizing<[u8; 32]>,
+}
+
+impl ExtendedSigningKey {
+    /// Clave maestra Icarus: PBKDF2-HMAC-SHA512(passphrase, entropía) con los bits fijados
+    pub fn icarus_master(phrase: &str, passphrase: &str) -> Result<Self> {
+        let entropy = Zeroizing::new(crate::bip39::phrase_to_entropy(phrase)?);
+
+        let mut output = Zeroizing::new([0u8; 96]);
+        pbkdf2_hmac::<Sha512>(passphrase.as_bytes(), &entropy, ICARUS_ITERATIONS, &mut output[..]);
+        output[0] &= 0b1111_1000;
+        output[31] &= 0b0001_1111;
+        output[31] |= 0b0100_0000;
+
+        let mut key = Zeroizing::new([0u8; 64]);
+        key.copy_from_slice(&output[..64]);
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&output[64..]);
+        Ok(Self { key, chain_code })
+    }
+
+    /// Derivar todos los hijos de `path`
+    pub fn derive_path(&self, path: &DerivationPath) -> Self {
+        path.children().iter().fold(self.clone(), |key, &child| key.derive_child(child))
+    }
+
+    /// Derivar el hijo `child` (esquema V2 de BIP32-Ed25519)
+    pub fn derive_child(&self, child: ChildNumber) -> Self {
+        let index = child.to_u32().to_le_bytes();
+        let public_key;
+        let (z_tag, cc_tag, data): (u8, u8, &[u8]) = if child.is_hardened() {
+            (0x00, 0x01, &self.key[..])
+        } else {
+            public_key = self.public_key();
+            (0x02, 0x03, &public_key)
+        };
+
+        let hmac = |tag: u8| -> Zeroizing<[u8; 64]> {
+            let mut mac = HmacSha512::new_from_slice(&self.chain_code[..]).expect("HMAC accepts any key length");
+            mac.update(&[tag]);
+            mac.update(data);
+            mac.update(&index);
+            let mut output = Zeroizing::new([0u8; 64]);
+            output.copy_from_slice(&mac.finalize().into_bytes());
+            output
+        };
+        let z = hmac(z_tag);
+        let i = hmac(cc_tag);
+
+        // kL' = 8·zL[..28] + kL, kR' = zR + kR (mod 2^256), ambos little-endian
+        let mut key = Zeroizing::new([0u8; 64]);
+        let mut carry = 0u16;
+        for n in 0..32 {
+            let z_l = if n < 28 { (z[n] as u16) << 3 } else { 0 };
+            let sum = self.key[n] as u16 + z_l + carry;
+            key[n] = sum as u8;
+            carry = sum >> 8;
+        }
+        carry = 0;
+        for n in 32..64 {
+            let sum = self.key[n] as u16 + z[n] as u16 + carry;
+            key[n] = sum as u8;
+            carry = sum >> 8;
+        }
+
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&i[32..]);
+        Self { key, chain_code }
+    }
+
+    /// Clave pública Ed25519: kL·B
+    pub fn public_key(&self) -> [u8; 32] {
+        let mut k_l = Zeroizing::new([0u8; 32]);
+        k_l.copy_from_slice(&self.key[..32]);
+        let scalar = Scalar::from_bytes_mod_order(*k_l);
+        EdwardsPoint::mul_base(&scalar).compress().to_bytes()
+    }
+
+    /// Chain code (32 bytes)
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+}
+
+impl std::fmt::Debug for ExtendedSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtendedSigningKey").finish_non_exhaustive()
+    }
+}
+
+/// Datos públicos de la cuenta 0 para comparar con la cartera
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcarusAccount {
+    /// Clave pública extendida de la cuenta (`acct_xvk...`, CIP-5)
+    pub account_public_key: String,
+    /// Primera dirección base de red principal (`addr1...`)
+    pub first_address: String,
+}
+
+/// Cuenta 0 y primera dirección base de la cartera Icarus de `phrase`
+pub fn icarus_account(phrase: &str, passphrase: &str) -> Result<IcarusAccount> {
+    let master = ExtendedSigningKey::icarus_master(phrase, passphrase)?;
+    let account = master.derive_path(&ACCOUNT_PATH.parse()?);
+
+    let key_hash = |children: [u32; 2]| -> Result<[u8; 28]> {
+        let key = children.iter().try_fold(account.clone(), |key, &index| {
+            ChildNumber::normal(index)
+                .map(|child| key.derive_child(child))
+                .ok_or_else(|| SCypherError::crypto("Invalid Cardano derivation index"))
+        })?;
+        Ok(Blake2b224::digest(key.public_key()).into())
+    };
+
+    let mut address = Vec::with_capacity(57);
+    address.push(BASE_ADDRESS_MAINNET);
+    address.extend_from_slice(&key_hash(PAYMENT_CHILDREN)?);
+    address.extend_from_slice(&key_hash(STAKE_CHILDREN)?);
+
+    let mut account_key = Vec::with_capacity(64);
+    account_key.extend_from_slice(&account.public_key());
+    account_key.extend_from_slice(account.chain_code());
+
+    Ok(IcarusAccount {
+        account_public_key: bech32_encode("acct_xvk", &account_key)?,
+        first_address: bech32_encode("addr", &address)?,
+    })
+}
+
+fn bech32_encode(hrp: &str, data: &[u8]) -> Result<String> {
+    bech32::encode(hrp, data.to_base32(), Variant::Bech32)
+        .map_err(|e| SCypherError::crypto(format!("Cannot encode bech32: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bech32::FromBase32;
+
+    #[test]
+    fn test_icarus_base_address() {
+        // La clave de pago de los vectores de CIP-19 es la 0/0 de esta frase
+        // (su credencial de stake no sale de la misma cartera)
+        let account = icarus_account("test walk nut penalty hip pave soap entry language right filter choice", "").unwrap();
+        let (_, expected, _) = bech32::decode(
+            "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x",
+        ).unwrap();
+        let (hrp, address, _) = bech32::decode(&account.first_address).unwrap();
+        let expected = Vec::<u8>::from_base32(&expected).unwrap();
+        let address = Vec::<u8>::from_base32(&address).unwrap();
+
+        assert_eq!(hrp, "addr");
+        assert_eq!(address.len(), 57);
+        assert_eq!(address[..29], expected[..29]);
+        assert!(account.account_public_key.starts_with("acct_xvk1"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Descriptores de salida de Bitcoin Core para una cartera de solo lectura
+//!
+//! Con los descriptores de rango (`wpkh`/`tr` con el origen de la clave) de
+//! la cuenta, Bitcoin Core puede importar una cartera watch-only y mostrar
+//! los fondos de la semilla recuperada sin que la clave privada salga de
+//! aquí. Solo se usa la red principal (`xpub`).
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+use super::bitcoin::hash160;
+use super::{ChildNumber, DerivationPath, ExtendedPrivateKey};
+use crate::error::{SCypherError, Result};
+
+/// Versión de serialización BIP32 de una clave pública de red principal
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+
+/// Alfabetos de la suma de comprobación de descriptores (BIP380)
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Tipo de salida de la cuenta
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    /// SegWit nativo (BIP84)
+    Wpkh,
+    /// Taproot con solo ruta de clave (BIP86)
+    Tr,
+}
+
+impl ScriptType {
+    /// Todos los tipos, en el orden en que se exportan
+    pub const ALL: [ScriptType; 2] = [ScriptType::Wpkh, ScriptType::Tr];
+
+    /// Nombre de la función en el descriptor
+    pub fn name(self) -> &'static str {
+        match self {
+            ScriptType::Wpkh => "wpkh",
+            ScriptType::Tr => "tr",
+        }
+    }
+
+    /// Ruta estándar de la cuenta `account`
+    pub fn account_path(self, account: u32) -> Result<DerivationPath> {
+        let purpose = match self {
+            ScriptType::Wpkh => 84,
+            ScriptType::Tr => 86,
+        };
+        format!("m/{}'/0'/{}'", purpose, account).parse()
+    }
+}
+
+/// Descriptores de recepción (`/0/*`) y cambio (`/1/*`) de una cuenta, con su checksum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDescriptors {
+    pub receive: String,
+    pub change: String,
+}
+
+/// Descriptores de la cuenta `account` de tipo `script_type` bajo la clave maestra
+pub fn account_descriptors(master: &ExtendedPrivateKey, script_type: ScriptType, account: u32) -> Result<AccountDescriptors> {
+    let path = script_type.account_path(account)?;
+    let origin = format!(
+        "[{}{}]",
+        hex::encode(fingerprint(master)),
+        path.to_string().trim_start_matches('m'),
+    );
+    let xpub = xpub(master, &path)?;
+
+    let descriptor = |chain: u32| -> Result<String> {
+        let descriptor = format!("{}({}{}/{}/*)", script_type.name(), origin, xpub, chain);
+        let checksum = descriptor_checksum(&descriptor)?;
+        Ok(format!("{}#{}", descriptor, checksum))
+    };
+
+    Ok(AccountDescriptors {
+        receive: descriptor(0)?,
+        change: descriptor(1)?,
+    })
+}
+
+/// Huella de una clave: primeros 4 bytes de HASH160 de su clave pública comprimida
+pub fn fingerprint(key: &ExtendedPrivateKey) -> [u8; 4] {
+    let hash = hash160(key.public_key().to_encoded_point(true).as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Clave pública extendida (`xpub`) de `path` bajo la clave maestra
+pub fn xpub(master: &ExtendedPrivateKey, path: &DerivationPath) -> Result<String> {
+    let children = path.children();
+    let (parent_fingerprint, child, key) = match children.split_last() {
+        Some((&child, parents)) => {
+            let parent = master.derive_path(&DerivationPath::from(parents.to_vec()))?;
+            (fingerprint(&parent), child, parent.derive_child(child)?)
+        }
+        None => ([0; 4], ChildNumber::normal(0).expect("index below 2^31"), master.clone()),
+    };
+    let depth = u8::try_from(children.len())
+        .map_err(|_| SCypherError::crypto("Derivation path is too deep for BIP32"))?;
+
+    let mut payload = Vec::with_capacity(78);
+    payload.extend_from_slice(&XPUB_VERSION);
+    payload.push(depth);
+    payload.extend_from_slice(&parent_fingerprint);
+    payload.extend_from_slice(&child.to_u32().to_be_bytes());
+    payload.extend_from_slice(key.chain_code());
+    payload.extend_from_slice(key.public_key().to_encoded_point(true).as_bytes());
+    Ok(bs58::encode(payload).with_check().into_string())
+}
+
+/// Suma de comprobación de 8 caracteres de un descriptor (BIP380)
+pub fn descriptor_checksum(descriptor: &str) -> Result<String> {
+    let mut c = 1u64;
+    let mut class = 0u64;
+    let mut class_count = 0;
+
+    for ch in descriptor.chars() {
+        let position = INPUT_CHARSET.find(ch).ok_or_else(|| {
+            SCypherError::InvalidRequest(format!("invalid character {:?} in descriptor", ch))
+        })? as u64;
+        c = polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+
+    Ok((0..8).map(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char).collect())
+}
+
+fn polymod(c: u64, value: u64) -> u64 {
+    const GENERATOR: [u64; 5] = [0xF5_DEE5_1989, 0xA9_FDCA_3312, 0x1B_AB10_E32D, 0x37_06B1_677A, 0x64_4D62_6FFD];
+
+    let top = c >> 35;
+    let mut c = ((c & 0x7_FFFF_FFFF) << 5) ^ value;
+    for (bit, generator) in GENERATOR.iter().enumerate() {
+        if top >> bit & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::mnemonic_to_seed;
+
+    fn abandon_master() -> ExtendedPrivateKey {
+        let seed = mnemonic_to_seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "",
+        );
+        ExtendedPrivateKey::from_seed(&seed[..]).unwrap()
+    }
+
+    #[test]
+    fn test_account_xpub() {
+        // Cuentas 0 de los vectores de BIP84 y BIP86
+        let master = abandon_master();
+        assert_eq!(hex::encode(fingerprint(&master)), "73c5da0a");
+        assert_eq!(
+            xpub(&master, &ScriptType::Wpkh.account_path(0).unwrap()).unwrap(),
+            "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V"
+        );
+        assert_eq!(
+            xpub(&master, &ScriptType::Tr.account_path(0).unwrap()).unwrap(),
+            "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ"
+        );
+    }
+
+    #[test]
+    fn test_descriptor_checksum() {
+        // Ejemplo de la documentación de descriptores de Bitcoin Core
+        assert_eq!(
+            descriptor_checksum("wpkh([d34db33f/84h/0h/0h]xpub6DJ2dNUysrn5Vt36jH2KLBT2i1auw1tTSSomg8PhqNiUtx8QX2SvC9nrHu81fT41fvDUnhMjEzQgXnQjKEu3oaqMSzhSrHMxyyoEAmUHQbY/0/*)").unwrap(),
+            "cjjspncu"
+        );
+        assert!(descriptor_checksum("wpkh(é)").is_err());
+
+        let descriptors = account_descriptors(&abandon_master(), ScriptType::Wpkh, 0).unwrap();
+        assert!(descriptors.receive.starts_with("wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZ"));
+        assert!(descriptors.change.contains("/1/*)#"));
+    }
+}
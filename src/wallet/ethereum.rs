@@ -0,0 +1,268 @@
+//! Cuentas Ethereum y keystore V3 (Web3 Secret Storage)
+//!
+//! El keystore cifra la clave privada con AES-128-CTR bajo una clave
+//! derivada con scrypt de una contraseña elegida por el usuario, y la
+//! autentica con Keccak-256(clave[16..32] || texto cifrado). Es el formato
+//! que importan geth, MetaMask, MyEtherWallet y compañía.
+
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::PublicKey;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use zeroize::Zeroizing;
+
+use crate::crypto::SecureRng;
+use crate::error::{SCypherError, Result};
+
+/// Ruta BIP44 de la primera cuenta Ethereum
+pub const DEFAULT_PATH: &str = "m/44'/60'/0'/0/0";
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+/// Parámetros de scrypt del keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// log2(N)
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl ScryptParams {
+    /// Parámetros "standard" de geth: N = 2^18, r = 8, p = 1 (256 MB)
+    pub const STANDARD: ScryptParams = ScryptParams { log_n: 18, r: 8, p: 1 };
+}
+
+/// Dirección de 20 bytes: últimos bytes de Keccak-256 de la clave pública sin comprimir
+pub fn address(public_key: &PublicKey) -> [u8; 20] {
+    let point = public_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Dirección con mayúsculas de checksum (EIP-55), con prefijo `0x`
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+
+    let mut result = String::with_capacity(42);
+    result.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0F;
+        result.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+    }
+    result
+}
+
+#[derive(Serialize, Deserialize)]
+struct Keystore {
+    version: u32,
+    id: String,
+    address: String,
+    crypto: CryptoSection,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CryptoSection {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: KdfParams,
+    mac: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+/// Cifrar `private_key` en un keystore V3 (JSON) con salt, IV e id aleatorios
+pub fn encrypt_keystore(private_key: &[u8; 32], password: &str, params: ScryptParams) -> Result<String> {
+    encrypt_keystore_with_rng(private_key, password, params, &mut rand::rngs::OsRng)
+}
+
+/// `encrypt_keystore` con el salt, el IV y el id tomados de `rng`
+pub fn encrypt_keystore_with_rng(
+    private_key: &[u8; 32],
+    password: &str,
+    params: ScryptParams,
+    rng: &mut dyn SecureRng,
+) -> Result<String> {
+    let mut salt = [0u8; 32];
+    let mut iv = [0u8; 16];
+    let mut id = [0u8; 16];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut iv);
+    rng.fill_bytes(&mut id);
+
+    let keystore = build_keystore(private_key, password, params, &salt, &iv, id)?;
+    serde_json::to_string_pretty(&keystore)
+        .map_err(|e| SCypherError::crypto(format!("Cannot serialize keystore: {}", e)))
+}
+
+fn build_keystore(
+    private_key: &[u8; 32],
+    password: &str,
+    params: ScryptParams,
+    salt: &[u8],
+    iv: &[u8; 16],
+    mut id: [u8; 16],
+) -> Result<Keystore> {
+    let derived = derive_key(password, salt, params)?;
+    let (ciphertext, mac) = seal(&derived, iv, private_key);
+
+    let secret = k256::SecretKey::from_slice(private_key)
+        .map_err(|_| SCypherError::crypto("Invalid secp256k1 private key"))?;
+
+    // UUID versión 4 (RFC 4122)
+    id[6] = (id[6] & 0x0F) | 0x40;
+    id[8] = (id[8] & 0x3F) | 0x80;
+    let id = hex::encode(id);
+
+    Ok(Keystore {
+        version: 3,
+        id: format!("{}-{}-{}-{}-{}", &id[..8], &id[8..12], &id[12..16], &id[16..20], &id[20..]),
+        address: hex::encode(address(&secret.public_key())),
+        crypto: CryptoSection {
+            cipher: "aes-128-ctr".to_string(),
+            cipherparams: CipherParams { iv: hex::encode(iv) },
+            ciphertext: hex::encode(ciphertext),
+            kdf: "scrypt".to_string(),
+            kdfparams: KdfParams {
+                dklen: 32,
+                n: 1u64 << params.log_n,
+                r: params.r,
+                p: params.p,
+                salt: hex::encode(salt),
+            },
+            mac: hex::encode(mac),
+        },
+    })
+}
+
+/// Descifrar un keystore V3 con KDF scrypt
+///
+/// Un MAC que no coincide significa contraseña incorrecta (`InvalidPassword`).
+pub fn decrypt_keystore(json: &str, password: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let invalid = |what: &str| SCypherError::InvalidRequest(format!("unsupported keystore: {}", what));
+
+    let keystore: Keystore = serde_json::from_str(json).map_err(|e| invalid(&e.to_string()))?;
+    let crypto = &keystore.crypto;
+    if keystore.version != 3 || crypto.cipher != "aes-128-ctr" || crypto.kdf != "scrypt" || crypto.kdfparams.dklen != 32 {
+        return Err(invalid("expected version 3, aes-128-ctr and scrypt"));
+    }
+
+    let n = crypto.kdfparams.n;
+    if !n.is_power_of_two() || n < 2 {
+        return Err(invalid("scrypt N must be a power of two"));
+    }
+    let params = ScryptParams { log_n: n.trailing_zeros() as u8, r: crypto.kdfparams.r, p: crypto.kdfparams.p };
+
+    let decode = |field: &str, value: &str| hex::decode(value).map_err(|_| invalid(field));
+    let salt = decode("salt", &crypto.kdfparams.salt)?;
+    let iv: [u8; 16] = decode("iv", &crypto.cipherparams.iv)?.try_into().map_err(|_| invalid("iv"))?;
+    let ciphertext: [u8; 32] = decode("ciphertext", &crypto.ciphertext)?.try_into().map_err(|_| invalid("ciphertext"))?;
+    let mac = decode("mac", &crypto.mac)?;
+
+    let derived = derive_key(password, &salt, params)?;
+    if keystore_mac(&derived, &ciphertext)[..] != mac[..] {
+        return Err(SCypherError::InvalidPassword);
+    }
+
+    let mut private_key = Zeroizing::new(ciphertext);
+    Aes128Ctr::new(derived[..16].into(), (&iv).into()).apply_keystream(&mut private_key[..]);
+    Ok(private_key)
+}
+
+fn derive_key(password: &str, salt: &[u8], params: ScryptParams) -> Result<Zeroizing<[u8; 32]>> {
+    let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p, 32)
+        .map_err(|e| SCypherError::crypto(format!("Invalid scrypt parameters: {}", e)))?;
+
+    let mut derived = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(password.as_bytes(), salt, &scrypt_params, &mut derived[..])
+        .map_err(|e| SCypherError::crypto(format!("scrypt failed: {}", e)))?;
+    Ok(derived)
+}
+
+/// Cifrar con AES-128-CTR (clave = derived[..16]) y calcular el MAC
+fn seal(derived: &[u8; 32], iv: &[u8; 16], private_key: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let mut ciphertext = *private_key;
+    Aes128Ctr::new(derived[..16].into(), iv.into()).apply_keystream(&mut ciphertext);
+    let mac = keystore_mac(derived, &ciphertext);
+    (ciphertext, mac)
+}
+
+fn keystore_mac(derived: &[u8; 32], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived[16..]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::{derive_account, DerivationPath};
+
+    const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn test_keystore_vector() {
+        // Vector de la especificación Web3 Secret Storage (clave derivada del
+        // ejemplo PBKDF2; el de scrypt usa r = 1 con N = 2^18, fuera de RFC 7914)
+        let private_key: [u8; 32] = hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap();
+        let derived: [u8; 32] = hex::decode("f06d69cdc7da0faffb1008270bca38f5e31891a3a773950e6d0fea48a7188551").unwrap().try_into().unwrap();
+        let iv: [u8; 16] = hex::decode("6087dab2f9fdbbfaddc31a909735c1e6").unwrap().try_into().unwrap();
+
+        let (ciphertext, mac) = seal(&derived, &iv, &private_key);
+        assert_eq!(hex::encode(ciphertext), "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46");
+        assert_eq!(hex::encode(mac), "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2");
+
+        let keystore = build_keystore(&private_key, "testpassword", ScryptParams { log_n: 10, r: 8, p: 1 }, &[0; 32], &iv, [0; 16]).unwrap();
+        assert_eq!(keystore.address, "008aeeda4d805471df9b2a5b0f38a0c3bcba786b");
+    }
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let private_key: [u8; 32] = hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap();
+        let params = ScryptParams { log_n: 10, r: 8, p: 1 };
+
+        let json = encrypt_keystore(&private_key, "correct horse", params).unwrap();
+        assert_eq!(*decrypt_keystore(&json, "correct horse").unwrap(), private_key);
+        assert!(matches!(decrypt_keystore(&json, "wrong"), Err(SCypherError::InvalidPassword)));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["crypto"]["kdfparams"]["n"], 1024);
+        assert_eq!(&value["id"].as_str().unwrap()[14..15], "4");
+    }
+
+    #[test]
+    fn test_account_address() {
+        let path: DerivationPath = DEFAULT_PATH.parse().unwrap();
+        let account = derive_account(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "",
+            &path,
+        ).unwrap();
+
+        assert_eq!(
+            checksum_address(&address(&account.public_key())),
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+        );
+    }
+}
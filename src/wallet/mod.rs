@@ -0,0 +1,72 @@
+//! Derivación de cuentas a partir de la frase descifrada
+//!
+//! Tras descifrar, el usuario puede necesitar la clave de una cuenta
+//! concreta en un formato que entiendan otras herramientas, sin pasar por
+//! un archivo con la frase en claro. Este módulo implementa la semilla
+//! BIP39 (PBKDF2-HMAC-SHA512), la derivación BIP32 sobre secp256k1 y los
+//! formatos de exportación. Todo el material derivado se limpia de memoria
+//! al liberarse.
+
+pub mod bip32;
+pub mod bitcoin;
+pub mod cardano;
+pub mod descriptor;
+pub mod ethereum;
+pub mod xprv;
+
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha512;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+pub use bip32::{ChildNumber, DerivationPath, ExtendedPrivateKey};
+
+/// Iteraciones de PBKDF2 fijadas por BIP39
+const SEED_ITERATIONS: u32 = 2048;
+
+/// Semilla BIP39 de 512 bits para `phrase` y la passphrase opcional
+///
+/// No valida la frase: BIP39 define la semilla para cualquier texto. Frase
+/// y passphrase se normalizan a NFKD, como exige el estándar.
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Zeroizing<[u8; 64]> {
+    // Capacidad suficiente para NFKD: así las cadenas no se realojan dejando copias
+    let mut normalized = Zeroizing::new(String::with_capacity(phrase.len() * 3));
+    for (position, word) in phrase.split_whitespace().enumerate() {
+        if position > 0 {
+            normalized.push(' ');
+        }
+        normalized.extend(word.nfkd());
+    }
+
+    let mut salt = Zeroizing::new(String::with_capacity(8 + passphrase.len() * 3));
+    salt.push_str("mnemonic");
+    salt.extend(passphrase.nfkd());
+
+    let mut seed = Zeroizing::new([0u8; 64]);
+    pbkdf2_hmac::<Sha512>(normalized.as_bytes(), salt.as_bytes(), SEED_ITERATIONS, &mut seed[..]);
+    seed
+}
+
+/// Clave extendida de la cuenta en `path` para una frase y passphrase
+pub fn derive_account(phrase: &str, passphrase: &str, path: &DerivationPath) -> crate::error::Result<ExtendedPrivateKey> {
+    let seed = mnemonic_to_seed(phrase, passphrase);
+    ExtendedPrivateKey::from_seed(&seed[..])?.derive_path(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mnemonic_to_seed() {
+        // Vector oficial de BIP39 (passphrase "TREZOR")
+        let seed = mnemonic_to_seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "TREZOR",
+        );
+        assert_eq!(
+            hex::encode(&seed[..]),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Claves privadas extendidas (`xprv`) como contenido protegido
+//!
+//! Algunas carteras exportan una `xprv` en lugar de una frase. Se cifran el
+//! chain code y la clave privada (64 bytes) con XOR, igual que la entropía
+//! de una frase, y el resultado se vuelve a codificar como una `xprv`
+//! válida con la misma versión. Profundidad, huella del padre e índice
+//! quedan en claro: no son secretos y sin ellos la clave no se podría
+//! volver a codificar.
+//!
+//! El keystream se expande con `MasterKey` desde la etiqueta `xprv`, así que
+//! no coincide con el de ninguna frase cifrada con la misma contraseña.
+
+use k256::SecretKey;
+use zeroize::Zeroizing;
+
+use crate::crypto::{MasterKey, ScypherParams};
+use crate::error::{SCypherError, Result};
+
+/// Bytes de una clave extendida serializada (BIP32)
+const XPRV_LEN: usize = 78;
+
+/// Posición del chain code y de la clave privada (precedida de 0x00)
+const CHAIN_CODE: std::ops::Range<usize> = 13..45;
+const PRIVATE_KEY: std::ops::Range<usize> = 46..78;
+
+/// Etiqueta del keystream
+const LABEL: &str = "xprv";
+
+/// Versiones de claves privadas extendidas (BIP32, BIP49, BIP84; mainnet y testnet)
+const PRIVATE_VERSIONS: [(u32, &str); 6] = [
+    (0x0488_ADE4, "xprv"),
+    (0x049D_7878, "yprv"),
+    (0x04B2_430C, "zprv"),
+    (0x0435_8394, "tprv"),
+    (0x044A_4E28, "uprv"),
+    (0x045F_18BC, "vprv"),
+];
+
+/// Si `text` parece una clave privada extendida (por el prefijo)
+pub fn looks_like_xprv(text: &str) -> bool {
+    let text = text.trim();
+    PRIVATE_VERSIONS.iter().any(|(_, prefix)| text.starts_with(prefix)) && !text.contains(char::is_whitespace)
+}
+
+/// Decodificar y validar una clave privada extendida
+fn decode(xprv: &str) -> Result<Zeroizing<[u8; XPRV_LEN]>> {
+    let invalid = |what: &str| SCypherError::InvalidRequest(format!("invalid extended private key: {}", what));
+
+    let payload = Zeroizing::new(bs58::decode(xprv.trim()).with_check(None).into_vec()
+        .map_err(|_| invalid("bad base58check"))?);
+    if payload.len() != XPRV_LEN {
+        return Err(invalid("wrong length"));
+    }
+    let version = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+    if !PRIVATE_VERSIONS.iter().any(|(known, _)| *known == version) {
+        return Err(invalid("not a private key version (xpub and others are not secret)"));
+    }
+    if payload[PRIVATE_KEY.start - 1] != 0 || SecretKey::from_slice(&payload[PRIVATE_KEY]).is_err() {
+        return Err(invalid("the private key is not a valid secp256k1 key"));
+    }
+
+    let mut bytes = Zeroizing::new([0u8; XPRV_LEN]);
+    bytes.copy_from_slice(&payload);
+    Ok(bytes)
+}
+
+/// Cifrar o descifrar (XOR es simétrico) una clave privada extendida
+pub fn transform_xprv(xprv: &str, password: &str, params: &ScypherParams) -> Result<Zeroizing<String>> {
+    let mut bytes = decode(xprv)?;
+
+    let mut keystream = Zeroizing::new([0u8; 64]);
+    MasterKey::derive(password, params)?.expand(LABEL, &mut keystream[..])?;
+    for (byte, key) in bytes[CHAIN_CODE].iter_mut().zip(&keystream[..32]) {
+        *byte ^= key;
+    }
+    for (byte, key) in bytes[PRIVATE_KEY].iter_mut().zip(&keystream[32..]) {
+        *byte ^= key;
+    }
+
+    // Una clave fuera de rango tras el XOR tiene probabilidad ~2^-128
+    if SecretKey::from_slice(&bytes[PRIVATE_KEY]).is_err() {
+        return Err(SCypherError::crypto("The encrypted key is out of range for secp256k1; use another password"));
+    }
+    Ok(Zeroizing::new(bs58::encode(&bytes[..]).with_check().into_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vector de prueba 1 de BIP32, clave maestra
+    const XPRV: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+    #[test]
+    fn test_transform_xprv() {
+        let params = ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+        assert!(looks_like_xprv(XPRV));
+
+        let encrypted = transform_xprv(XPRV, "test_password", &params).unwrap();
+        assert!(encrypted.starts_with("xprv"));
+        assert_ne!(*encrypted, XPRV);
+        // Profundidad, huella e índice se conservan
+        assert_eq!(decode(&encrypted).unwrap()[..CHAIN_CODE.start], decode(XPRV).unwrap()[..CHAIN_CODE.start]);
+        assert_eq!(*transform_xprv(&encrypted, "test_password", &params).unwrap(), XPRV);
+
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        assert!(transform_xprv(xpub, "test_password", &params).is_err());
+        assert!(transform_xprv("xprv123", "test_password", &params).is_err());
+    }
+}
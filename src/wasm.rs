@@ -0,0 +1,77 @@
+//! Exportaciones WebAssembly
+//!
+//! Expone la transformación y las validaciones BIP39 a JavaScript mediante
+//! wasm-bindgen, para front-ends web que funcionan completamente offline y
+//! reutilizan el mismo código Rust que la CLI.
+
+use wasm_bindgen::prelude::*;
+
+use crate::crypto::ScypherParams;
+use crate::error::SCypherError;
+
+/// Convertir errores de SCypher a excepciones JavaScript
+fn to_js_error(error: SCypherError) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
+
+/// Versión de la biblioteca
+#[wasm_bindgen]
+pub fn version() -> String {
+    crate::VERSION.to_string()
+}
+
+/// Transformar una seed phrase (la misma operación cifra y descifra)
+///
+/// `params` es el JSON de `ScypherParams` (`'{"iterations": 5, "memory_cost": 131072}'`);
+/// sin él se usan los valores por defecto. La llamada es síncrona y no hay
+/// otro hilo que pueda cancelarla: para no congelar la página se ejecuta en
+/// un Web Worker, y terminar el worker es la forma de cancelarla.
+#[wasm_bindgen(js_name = transformSeed)]
+pub fn transform_seed(
+    seed_phrase: &str,
+    password: &str,
+    params: Option<String>,
+) -> Result<String, JsValue> {
+    let params = match params {
+        Some(json) => ScypherParams::from_json(&json).map_err(to_js_error)?,
+        None => ScypherParams::default(),
+    };
+    // El resultado pasa a JS como String: se mueve sin dejar copia atrás
+    params.transform(seed_phrase, password)
+        .map(|mut phrase| std::mem::take(&mut *phrase))
+        .map_err(to_js_error)
+}
+
+/// Validar formato, palabras y checksum de una seed phrase
+#[wasm_bindgen(js_name = validateSeedPhrase)]
+pub fn validate_seed_phrase(seed_phrase: &str) -> Result<(), JsValue> {
+    crate::bip39::validate_seed_phrase_complete(seed_phrase).map_err(to_js_error)
+}
+
+/// Verificar solo el checksum BIP39
+#[wasm_bindgen(js_name = verifyChecksum)]
+pub fn verify_checksum(seed_phrase: &str) -> Result<bool, JsValue> {
+    crate::bip39::verify_checksum(seed_phrase).map_err(to_js_error)
+}
+
+/// Verificar si una palabra pertenece a la lista BIP39
+#[wasm_bindgen(js_name = isValidWord)]
+pub fn is_valid_word(word: &str) -> bool {
+    crate::bip39::is_valid_word(word)
+}
+
+/// Palabras BIP39 que comienzan con un prefijo (autocompletado)
+#[wasm_bindgen(js_name = wordsWithPrefix)]
+pub fn words_with_prefix(prefix: &str) -> Vec<String> {
+    crate::bip39::wordlist::words_with_prefix(prefix)
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Generar una seed phrase nueva con el RNG del navegador
+#[wasm_bindgen(js_name = generateSeedPhrase)]
+pub fn generate_seed_phrase(word_count: usize) -> Result<String, JsValue> {
+    crate::bip39::validate_word_count(word_count).map_err(to_js_error)?;
+    crate::bip39::conversion::generate_seed_phrase(word_count * 32 / 3).map_err(to_js_error)
+}
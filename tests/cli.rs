@@ -0,0 +1,962 @@
+// tests/cli.rs - Pruebas de extremo a extremo del binario
+//
+// Ejecutan `scypher-rust` como lo haría un usuario o un script: preguntas
+// respondidas por stdin (sin terminal se lee una línea por pregunta),
+// archivos de entrada y salida, códigos de salida y Ctrl-C. Todas usan
+// Argon2id con 1 iteración y 8 MiB para que sean rápidas y 1 lane para que
+// el resultado no dependa de los núcleos de la máquina, y cada una trabaja
+// en su propio directorio temporal.
+
+use std::path::Path;
+use std::time::Duration;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const PASSWORD: &str = "password123";
+/// Vector en-12-fast de `known_answers.json`
+const ENCRYPTED: &str = "shock strong glory exchange syrup east illness crater board genuine wish clarify";
+
+/// Binario con parámetros rápidos, salida sin colores y un límite de tiempo
+/// (una pregunta que se repite al cerrarse stdin haría colgarse la prueba)
+fn scypher(dir: &Path) -> Command {
+    scypher_with_lanes(dir, "1")
+}
+
+/// Como `scypher`, con otro valor de --lanes
+fn scypher_with_lanes(dir: &Path, lanes: &str) -> Command {
+    let mut command = Command::cargo_bin("scypher-rust").unwrap();
+    command
+        .current_dir(dir)
+        .args(["--plain-terminal", "-i", "1", "-m", "8192", "--lanes", lanes])
+        .timeout(Duration::from_secs(60));
+    command
+}
+
+/// Frase y contraseña dos veces, como las pide el modo interactivo
+fn answers(phrase: &str) -> String {
+    format!("{}\n{}\n{}\n", phrase, PASSWORD, PASSWORD)
+}
+
+fn read(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap().trim_end().to_string()
+}
+
+#[test]
+fn test_prompts_from_stdin() {
+    let dir = TempDir::new().unwrap();
+    scypher(dir.path())
+        .args(["-o", "result.txt"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Enter seed phrase"))
+        .stdout(predicate::str::contains("Confirm password"))
+        .stdout(predicate::str::contains("Operation completed successfully"));
+
+    let output = dir.path().join("result.txt");
+    assert_eq!(read(&output), ENCRYPTED);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(std::fs::metadata(&output).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+}
+
+#[test]
+fn test_file_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("seed.txt"), format!("{}\n", PHRASE)).unwrap();
+    let password = format!("{}\n{}\n", PASSWORD, PASSWORD);
+
+    scypher(dir.path())
+        .args(["-f", "seed.txt", "-o", "encrypted.txt"])
+        .write_stdin(password.clone())
+        .assert()
+        .success();
+    assert_eq!(read(&dir.path().join("encrypted.txt")), ENCRYPTED);
+
+    scypher(dir.path())
+        .args(["-d", "-f", "encrypted.txt", "-o", "decrypted.txt"])
+        .write_stdin(password)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("valid BIP39 checksum"));
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[test]
+fn test_other_language_hints() {
+    let dir = TempDir::new().unwrap();
+    let french = "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille";
+    std::fs::write(dir.path().join("french.txt"), french).unwrap();
+    std::fs::write(dir.path().join("typo.txt"), french.replacen("abaisser", "abaiser", 1)).unwrap();
+
+    // Copia francesa con --language en: se indica la lista correcta sin pedir la contraseña
+    scypher(dir.path())
+        .args(["--language", "en", "-f", "french.txt"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("most of its words are in the French one (fr)"))
+        .stdout(predicate::str::contains("Enter password").not())
+        .stderr(predicate::str::contains("(--language fr)"));
+
+    // Sin --language, una errata se corrige con la lista francesa
+    scypher(dir.path())
+        .args(["-f", "typo.txt"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Most words are in the French wordlist (fr)"))
+        .stderr(predicate::str::contains("Word #1 'abaiser' is not in the BIP39 wordlist (did you mean 'abaisser'?)"));
+}
+
+#[test]
+fn test_json_events() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+
+    let output = scypher(dir.path())
+        .args(["--events", "json", "--skip-checksum", "-f", "seed.txt", "-o", "encrypted.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+
+    let events: Vec<serde_json::Value> = String::from_utf8(output.stderr).unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let kinds: Vec<&str> = events.iter().map(|event| event["event"].as_str().unwrap()).collect();
+    assert!(kinds.contains(&"warning"));
+    assert!(events.iter().any(|event| event["phase"] == "derive_key"));
+    assert_eq!(events.last().unwrap(), &serde_json::json!({"event": "done", "exit_code": 0}));
+    assert!(!String::from_utf8(output.stdout).unwrap().contains("\"event\""));
+
+    // Un error también llega como evento
+    scypher(dir.path())
+        .args(["--events", "json", "-f", "missing.txt"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains(r#""event":"error""#));
+}
+
+#[cfg(feature = "qr")]
+#[test]
+fn test_output_sinks() {
+    let dir = TempDir::new().unwrap();
+    scypher(dir.path())
+        .args(["-o", "result.txt", "--qr-out", "result.png", "--no-stdout"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(ENCRYPTED).not())
+        .stdout(predicate::str::contains("QR code saved to: result.png"));
+
+    assert_eq!(read(&dir.path().join("result.txt")), ENCRYPTED);
+    let image = dir.path().join("result.png");
+    assert!(std::fs::read(&image).unwrap().starts_with(b"\x89PNG"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(std::fs::metadata(&image).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+
+    // Sin ningún destino no se llega a pedir la frase
+    scypher(dir.path())
+        .arg("--no-stdout")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Enter seed phrase").not());
+}
+
+#[test]
+fn test_backup_qr_params() {
+    let dir = TempDir::new().unwrap();
+    let params = scypher_rust::crypto::ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+    let backup = scypher_rust::ur::Ur::scypher_backup(ENCRYPTED, &params).unwrap();
+    std::fs::write(dir.path().join("scanned.txt"), backup.to_single_part().to_uppercase()).unwrap();
+
+    // Los parámetros de la copia mandan sobre los de la línea de comandos
+    scypher_with_lanes(dir.path(), "3")
+        .args(["-d", "-f", "scanned.txt", "-o", "decrypted.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ignoring --lanes 3"));
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[cfg(feature = "qr")]
+#[test]
+fn test_qr_split() {
+    let dir = TempDir::new().unwrap();
+    scypher(dir.path())
+        .args(["--qr-out", "backup.png", "--qr-split", "3", "--no-stdout", "-o", "result.txt"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("QR share 3 of 3 saved to: backup-3of3.png"));
+    for index in 1..=3 {
+        assert!(std::fs::read(dir.path().join(format!("backup-{}of3.png", index))).unwrap().starts_with(b"\x89PNG"));
+    }
+    assert!(!dir.path().join("backup.png").exists());
+
+    // Las partes escaneadas, en cualquier orden, reconstruyen la copia con sus parámetros
+    let params = scypher_rust::crypto::ScypherParams::builder().iterations(1).memory_cost(8192).build().unwrap();
+    let shares = scypher_rust::ur::Ur::scypher_backup(ENCRYPTED, &params).unwrap().split(3).unwrap();
+    let lines: Vec<String> = shares.iter().rev().map(|share| share.to_single_part().to_uppercase()).collect();
+    std::fs::write(dir.path().join("scanned.txt"), lines.join("\n")).unwrap();
+    scypher(dir.path())
+        .args(["-d", "-f", "scanned.txt", "-o", "decrypted.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Split QR rebuilt from its shares"));
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+
+    // Sin una de las partes no hay nada que descifrar
+    std::fs::write(dir.path().join("partial.txt"), lines[..2].join("\n")).unwrap();
+    scypher(dir.path())
+        .args(["-d", "-f", "partial.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing share(s) 1 of 3"));
+
+    scypher(dir.path()).args(["--qr-split", "3"]).assert().code(2);
+}
+
+#[test]
+fn test_multipart_ur_bytes() {
+    let dir = TempDir::new().unwrap();
+    let mut encoder = scypher_rust::ur::UrEncoder::new(&scypher_rust::ur::Ur::bytes(ENCRYPTED.as_bytes()), 20);
+    let mut parts: Vec<String> = (0..encoder.seq_len() + 2).map(|_| encoder.next_part().to_uppercase()).collect();
+    assert!(parts.len() > 3);
+    // Un escáner no garantiza el orden y puede perder partes sueltas
+    parts.remove(0);
+    parts.reverse();
+    std::fs::write(dir.path().join("scanned.txt"), parts.join("\n")).unwrap();
+
+    scypher(dir.path())
+        .args(["-d", "-f", "scanned.txt", "-o", "decrypted.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success();
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[test]
+fn test_batch_manifest_and_report() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("batch.txt"), format!("vault-a: {}\nvault-b: abandon zzzz\n", PHRASE)).unwrap();
+
+    scypher(dir.path())
+        .args(["batch", "batch.txt", "--output-dir", "out", "--manifest", "manifest.json", "--fingerprint-salt", "audit-2026", "--report", "report.csv"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Manifest saved to: manifest.json"));
+
+    let manifest = std::fs::read_to_string(dir.path().join("manifest.json")).unwrap();
+    assert!(!manifest.contains("abandon") && !manifest.contains("zzzz"));
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(manifest["fingerprint_salt"], "audit-2026");
+    assert_eq!(manifest["params"]["iterations"], 1);
+
+    let id = scypher_rust::crypto::fingerprint::phrase_id(PHRASE, &scypher_rust::bip39::ENGLISH, "audit-2026").unwrap();
+    let items = manifest["items"].as_array().unwrap();
+    assert_eq!((&items[0]["label"], &items[0]["fingerprint"], &items[0]["status"]), (&"vault-a".into(), &id.into(), &"ok".into()));
+    assert_eq!((&items[1]["status"], &items[1]["fingerprint"]), (&"failed".into(), &serde_json::Value::Null));
+    assert!(items[1]["error_code"].is_u64());
+    assert_eq!(read(&dir.path().join("out/vault-a.txt")), ENCRYPTED);
+
+    let report = read(&dir.path().join("report.csv"));
+    let rows: Vec<Vec<&str>> = report.lines().map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows[0], ["label", "validation", "status", "duration_ms", "error_code", "error"]);
+    assert_eq!((rows[1][0], rows[1][1], rows[1][2], rows[1][4]), ("vault-a", "valid", "ok", ""));
+    assert_eq!((rows[2][0], rows[2][1], rows[2][2]), ("vault-b", "invalid", "failed"));
+    assert!(rows[1][3].parse::<f64>().is_ok());
+    assert!(!report.contains("zzzz"));
+}
+
+#[test]
+fn test_batch_resume() {
+    let dir = TempDir::new().unwrap();
+    let passwords = format!("{}\n{}\n", PASSWORD, PASSWORD);
+    std::fs::write(dir.path().join("batch.txt"), format!("vault-a: {}\nvault-b: abandon zzzz\n", PHRASE)).unwrap();
+
+    scypher(dir.path())
+        .args(["batch", "batch.txt", "--output-dir", "out", "--resume"])
+        .write_stdin(passwords.clone())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Checkpoint kept"));
+    let checkpoint = std::fs::read(dir.path().join("out/.scypher-checkpoint")).unwrap();
+    assert!(!String::from_utf8_lossy(&checkpoint).contains("vault-a"));
+
+    // Sin --resume no se empieza encima de un lote a medias
+    scypher(dir.path())
+        .args(["batch", "batch.txt", "--output-dir", "out"])
+        .write_stdin(passwords.clone())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--resume"));
+
+    let fixed = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+    std::fs::write(dir.path().join("batch.txt"), format!("vault-a: {}\nvault-b: {}\n", PHRASE, fixed)).unwrap();
+    scypher(dir.path())
+        .args(["batch", "batch.txt", "--output-dir", "out", "--resume", "--report", "report.csv"])
+        .write_stdin(passwords)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resuming: 1 of 2 phrases already done"))
+        .stdout(predicate::str::contains("2 succeeded (1 resumed), 0 failed"));
+
+    assert_eq!(read(&dir.path().join("out/vault-a.txt")), ENCRYPTED);
+    assert!(dir.path().join("out/vault-b.txt").exists());
+    assert!(!dir.path().join("out/.scypher-checkpoint").exists());
+    assert!(read(&dir.path().join("report.csv")).contains("vault-a,valid,resumed,,,"));
+}
+
+#[test]
+fn test_file_subcommand() {
+    let dir = TempDir::new().unwrap();
+    // Más de un bloque, para cruzar el límite entre bloques
+    let secret: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    std::fs::write(dir.path().join("seed.bin"), &secret).unwrap();
+
+    scypher(dir.path())
+        .args(["file", "seed.bin", "seed.scy"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("seed.scy"));
+    let sealed = std::fs::read(dir.path().join("seed.scy")).unwrap();
+    assert!(sealed.starts_with(b"SCYSTRM") && sealed.len() > secret.len());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(dir.path().join("seed.scy")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // La salida nunca se sobrescribe
+    scypher(dir.path())
+        .args(["file", "seed.bin", "seed.scy"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .failure();
+
+    // Una contraseña equivocada falla y no deja archivo
+    scypher(dir.path())
+        .args(["file", "-d", "seed.scy", "wrong.bin"])
+        .write_stdin("password124\n")
+        .assert()
+        .failure();
+    assert!(!dir.path().join("wrong.bin").exists());
+
+    // Los parámetros salen de la cabecera, no de -i/-m/--lanes
+    scypher_with_lanes(dir.path(), "2")
+        .args(["file", "-d", "seed.scy", "restored.bin"])
+        .write_stdin(format!("{}\n", PASSWORD))
+        .assert()
+        .success();
+    assert_eq!(std::fs::read(dir.path().join("restored.bin")).unwrap(), secret);
+}
+
+#[test]
+fn test_preset_save_and_use() {
+    let dir = TempDir::new().unwrap();
+    let config = dir.path().join("config");
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+
+    scypher(dir.path())
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .args(["preset", "save", "fast-lab"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--iterations 1 --lanes 1 --memory 8192"));
+
+    // Sin -i/-m/--lanes: los pone el preset
+    let mut command = Command::cargo_bin("scypher-rust").unwrap();
+    command
+        .current_dir(dir.path())
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .args(["--plain-terminal", "--preset", "fast-lab", "-f", "seed.txt", "-o", "result.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Using preset 'fast-lab'"));
+    assert_eq!(read(&dir.path().join("result.txt")), ENCRYPTED);
+
+    // Un preset editado a mano no se usa
+    let path = config.join("presets/fast-lab.json");
+    std::fs::write(&path, std::fs::read_to_string(&path).unwrap().replace("\"1\"", "\"2\"")).unwrap();
+    scypher(dir.path())
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .args(["--preset", "fast-lab", "-f", "seed.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("integrity check"));
+}
+
+#[test]
+fn test_default_lanes() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+    std::fs::write(dir.path().join("encrypted.txt"), ENCRYPTED).unwrap();
+    let password = format!("{}\n{}\n", PASSWORD, PASSWORD);
+    let without_lanes = || {
+        let mut command = Command::cargo_bin("scypher-rust").unwrap();
+        command.current_dir(dir.path()).args(["--plain-terminal", "-i", "1", "-m", "8192"]).timeout(Duration::from_secs(60));
+        command
+    };
+
+    // Un cifrado nuevo usa una lane por núcleo, igual que --lanes auto
+    without_lanes().args(["-f", "seed.txt", "-o", "default.txt"]).write_stdin(password.clone()).assert().success();
+    without_lanes().args(["--lanes", "auto", "-f", "seed.txt", "-o", "auto.txt"]).write_stdin(password.clone()).assert().success();
+    assert_eq!(read(&dir.path().join("default.txt")), read(&dir.path().join("auto.txt")));
+
+    // Al descifrar sigue siendo 1, el valor de las copias anteriores
+    without_lanes().args(["-d", "-f", "encrypted.txt", "-o", "decrypted.txt"]).write_stdin(password).assert().success();
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_pepper_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let pepper = dir.path().join("pepper.bin");
+    std::fs::write(&pepper, b"0123456789abcdef0123456789abcdef").unwrap();
+    std::fs::set_permissions(&pepper, std::fs::Permissions::from_mode(0o644)).unwrap();
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+    let passwords = format!("{}\n{}\n", PASSWORD, PASSWORD);
+
+    scypher(dir.path())
+        .args(["--pepper-file", "pepper.bin", "-f", "seed.txt", "-o", "encrypted.txt"])
+        .write_stdin(passwords.clone())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("chmod 600"));
+    assert!(!dir.path().join("encrypted.txt").exists());
+
+    std::fs::set_permissions(&pepper, std::fs::Permissions::from_mode(0o600)).unwrap();
+    scypher(dir.path())
+        .args(["--pepper-file", "pepper.bin", "-f", "seed.txt", "-o", "encrypted.txt"])
+        .write_stdin(passwords.clone())
+        .assert()
+        .success();
+    let encrypted = read(&dir.path().join("encrypted.txt"));
+    assert_ne!(encrypted, ENCRYPTED);
+
+    scypher(dir.path())
+        .args(["-d", "--pepper-file", "pepper.bin", "-f", "encrypted.txt", "-o", "decrypted.txt"])
+        .write_stdin(passwords)
+        .assert()
+        .success();
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gpg_encrypted_input() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Sin gpg instalado no hay nada que probar
+    if std::process::Command::new("gpg").arg("--version").output().is_err() {
+        return;
+    }
+    let dir = TempDir::new().unwrap();
+    let home = dir.path().join("gnupg");
+    std::fs::create_dir(&home).unwrap();
+    std::fs::set_permissions(&home, std::fs::Permissions::from_mode(0o700)).unwrap();
+    let gpg = |args: &[&str]| {
+        let status = std::process::Command::new("gpg")
+            .current_dir(dir.path())
+            .env("GNUPGHOME", &home)
+            .args(["--batch", "--quiet"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    // Clave sin passphrase: gpg-agent no necesita pinentry
+    gpg(&["--passphrase", "", "--quick-gen-key", "SCypher Test <test@example.invalid>", "future-default", "default", "never"]);
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+    gpg(&["--trust-model", "always", "-r", "test@example.invalid", "-o", "seed.txt.gpg", "-e", "seed.txt"]);
+    std::fs::remove_file(dir.path().join("seed.txt")).unwrap();
+
+    scypher(dir.path())
+        .env("GNUPGHOME", &home)
+        .args(["-f", "seed.txt.gpg", "-o", "result.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Decrypting input file with GnuPG: seed.txt.gpg"));
+    assert_eq!(read(&dir.path().join("result.txt")), ENCRYPTED);
+
+    // Nada descifrado quedó en disco
+    let mut files: Vec<String> = std::fs::read_dir(dir.path()).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, ["gnupg", "result.txt", "seed.txt.gpg"]);
+
+    let _ = std::process::Command::new("gpgconf").env("GNUPGHOME", &home).args(["--kill", "gpg-agent"]).status();
+}
+
+#[test]
+fn test_require_removable() {
+    use scypher_rust::security::storage::{locate, StorageKind};
+
+    let dir = TempDir::new().unwrap();
+    scypher(dir.path())
+        .args(["-o", "result.txt"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("result.txt will be written to"));
+
+    // Un directorio temporal en un USB no dice nada de la política
+    if locate(dir.path()).kind == StorageKind::Removable {
+        return;
+    }
+    scypher(dir.path())
+        .args(["--require-removable", "-o", "refused.txt"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Enter seed phrase").not())
+        .stderr(predicate::str::contains("--require-removable only allows removable media"));
+    assert!(!dir.path().join("refused.txt").exists());
+}
+
+#[test]
+fn test_watch_once() {
+    let dir = TempDir::new().unwrap();
+    let queue = dir.path().join("queue");
+    std::fs::create_dir(&queue).unwrap();
+    std::fs::write(queue.join("seed.txt"), PHRASE).unwrap();
+    std::fs::write(queue.join("a.json"), r#"{"input": "seed.txt", "output": "a.txt"}"#).unwrap();
+    std::fs::write(queue.join("b.json"), r#"{"input": "missing.txt", "output": "b.txt"}"#).unwrap();
+    std::fs::write(queue.join("notes.txt"), "not a job").unwrap();
+
+    scypher(dir.path())
+        .args(["watch", "queue", "--once"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("1 succeeded, 1 failed"));
+
+    assert_eq!(read(&queue.join("a.txt")), ENCRYPTED);
+    assert!(queue.join("done/a.json").exists());
+    assert!(queue.join("failed/b.json").exists());
+    assert!(queue.join("notes.txt").exists());
+}
+
+/// BMP de 24 bits sin comprimir con un degradado
+#[cfg(feature = "stego")]
+fn bmp_image(width: u32, height: u32) -> Vec<u8> {
+    let pixels = (width as usize * 3).div_ceil(4) * 4 * height as usize;
+    let mut image = b"BM".to_vec();
+    for field in [54 + pixels as u32, 0, 54, 40, width, height] {
+        image.extend_from_slice(&field.to_le_bytes());
+    }
+    image.extend_from_slice(&[1, 0, 24, 0]);
+    image.extend_from_slice(&[0; 24]);
+    image.extend((0..pixels).map(|i| (i % 251) as u8));
+    image
+}
+
+#[cfg(feature = "stego")]
+#[test]
+fn test_stego_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("carrier.bmp"), bmp_image(64, 48)).unwrap();
+
+    scypher(dir.path())
+        .args(["--stego-carrier", "carrier.bmp", "--stego-out", "holiday.bmp", "--no-stdout"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(ENCRYPTED).not())
+        .stdout(predicate::str::contains("Result hidden in BMP image: holiday.bmp"));
+
+    // Leer la imagen con -f extrae la frase cifrada
+    scypher(dir.path())
+        .args(["-d", "-f", "holiday.bmp", "-o", "decrypted.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success();
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+
+    // Una imagen sin datos ocultos es un error de archivo
+    scypher(dir.path())
+        .args(["-d", "-f", "carrier.bmp"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("no hidden data"));
+}
+
+#[cfg(feature = "armor")]
+#[test]
+fn test_inspect() {
+    let dir = TempDir::new().unwrap();
+    scypher(dir.path())
+        .args(["--armor", "age", "-o", "backup.txt", "--no-stdout"])
+        .write_stdin(format!("{}container secret\ncontainer secret\n", answers(PHRASE)))
+        .assert()
+        .success();
+
+    // Sin contraseñas ni preguntas: se lee la cabecera
+    scypher(dir.path())
+        .args(["inspect", "backup.txt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Container:   age (ASCII armor)"))
+        .stdout(predicate::str::contains("KDF:         scrypt"))
+        .stdout(predicate::str::contains("Salt:        fingerprint "))
+        .stdout(predicate::str::contains("a SCypher recovery document"))
+        .stdout(predicate::str::contains("Enter").not());
+
+    // Una frase suelta se reconoce sin mostrar sus palabras
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+    scypher(dir.path())
+        .args(["inspect", "seed.txt", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"container\": \"BIP39 phrase (12 words, English)\""))
+        .stdout(predicate::str::contains("abandon").not());
+
+    std::fs::write(dir.path().join("notes.txt"), "shopping list").unwrap();
+    scypher(dir.path())
+        .args(["inspect", "notes.txt"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("Not a recognized file"));
+}
+
+#[test]
+fn test_compat_v2() {
+    let dir = TempDir::new().unwrap();
+    // Copia de SCypherV2.sh: SHAKE-256 con sus 1000 iteraciones por defecto
+    std::fs::write(dir.path().join("legacy.txt"),
+                   "sudden measure clip pledge behave head shuffle critic zero exhibit wide iron\n").unwrap();
+
+    // Sin -m ni -i: --compat v2 no usa memoria y sus iteraciones son otras
+    let legacy = || {
+        let mut command = Command::cargo_bin("scypher-rust").unwrap();
+        command.current_dir(dir.path()).args(["--plain-terminal", "--compat", "v2"]).timeout(Duration::from_secs(60));
+        command
+    };
+    legacy()
+        .args(["-d", "-f", "legacy.txt", "-o", "recovered.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("SHAKE-256 with 1000 iterations"))
+        .stdout(predicate::str::contains("valid BIP39 checksum"));
+    assert_eq!(read(&dir.path().join("recovered.txt")), PHRASE);
+
+    // Opciones de Argon2 no tienen sentido con el esquema antiguo
+    legacy()
+        .args(["-m", "8192", "-f", "legacy.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_dual_control() {
+    let dir = TempDir::new().unwrap();
+    // Cada operador confirma su contraseña; el segundo pulsa Enter tras la pausa
+    let operators = |first: &str, second: &str| format!("{}\n{}\n\n{}\n{}\n", first, first, second, second);
+
+    scypher(dir.path())
+        .args(["--dual-control", "-o", "encrypted.txt", "--no-stdout"])
+        .write_stdin(format!("{}\n{}", PHRASE, operators("alice-secret", "bob-secret")))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Operator 2 can start in"))
+        .stdout(predicate::str::contains("Dual control: two operator passwords"));
+    let encrypted = read(&dir.path().join("encrypted.txt"));
+    assert_ne!(encrypted, ENCRYPTED);
+
+    // Al descifrar da igual quién empiece
+    scypher(dir.path())
+        .args(["--dual-control", "-d", "-f", "encrypted.txt", "-o", "decrypted.txt", "--no-stdout"])
+        .write_stdin(operators("bob-secret", "alice-secret"))
+        .assert()
+        .success();
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[test]
+fn test_failed_decrypt_backoff() {
+    let dir = TempDir::new().unwrap();
+    let config = dir.path().join("config");
+    scypher(dir.path())
+        .args(["--pad-24", "-o", "padded.txt", "--no-stdout"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success();
+
+    // Una frase rellenada no se autentica con otra contraseña: cada intento cuenta
+    let wrong_password = || {
+        let mut command = scypher(dir.path());
+        command
+            .env("SCYPHER_CONFIG_DIR", &config)
+            .args(["-d", "--pad-24", "-f", "padded.txt"])
+            .write_stdin("wrong password\nwrong password\n");
+        command
+    };
+    for _ in 0..3 {
+        wrong_password().assert().failure().stderr(predicate::str::contains("does not authenticate"));
+    }
+    assert_eq!(read(&config.join("failed-decrypts")), "3");
+
+    // El cuarto intento avisa y espera, aunque la entrada no sea un terminal
+    let started = std::time::Instant::now();
+    wrong_password()
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("3 failed decryption(s) in a row"))
+        .stdout(predicate::str::contains("Waiting 2 s before the next attempt"));
+    assert!(started.elapsed() >= Duration::from_secs(2));
+    assert_eq!(read(&config.join("failed-decrypts")), "4");
+
+    // Un descifrado normal no se puede comprobar: con otra contraseña sale
+    // una frase válida, y no pone el contador a cero
+    std::fs::write(dir.path().join("encrypted.txt"), ENCRYPTED).unwrap();
+    scypher(dir.path())
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .args(["-d", "-f", "encrypted.txt", "-o", "decrypted.txt"])
+        .write_stdin("wrong password\nwrong password\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("valid BIP39 checksum"));
+    assert_ne!(read(&dir.path().join("decrypted.txt")), PHRASE);
+    assert_eq!(read(&config.join("failed-decrypts")), "4");
+
+    Command::cargo_bin("scypher-rust").unwrap()
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .arg("--reset-failures")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("counter cleared (4 recorded)"));
+    assert!(!config.join("failed-decrypts").exists());
+}
+
+#[test]
+fn test_rotate_confirms_old_password() {
+    let dir = TempDir::new().unwrap();
+    // Una contraseña antigua mal tecleada daría otra frase válida: se pide dos veces
+    scypher(dir.path())
+        .args(["-o", "rotated.txt", "--no-stdout", "rotate"])
+        .write_stdin(format!("{}\n{}\npassword124\n{}\n{}\nnew_password\nnew_password\n", ENCRYPTED, PASSWORD, PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Password mismatch"));
+
+    scypher(dir.path())
+        .args(["-d", "-f", "rotated.txt", "-o", "decrypted.txt"])
+        .write_stdin("new_password\nnew_password\n")
+        .assert()
+        .success();
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+}
+
+#[test]
+fn test_key_check() {
+    let dir = TempDir::new().unwrap();
+    // Solo con --show-key-check se muestra el código
+    scypher(dir.path())
+        .args(["-o", "encrypted.txt", "--no-stdout"])
+        .write_stdin(answers(PHRASE))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Key check:").not());
+
+    let output = scypher(dir.path())
+        .args(["-o", "encrypted.txt", "--no-stdout", "--show-key-check"])
+        .write_stdin(answers(PHRASE))
+        .output()
+        .unwrap();
+    // Con --no-stdout el código no va a stdout, sino a stderr
+    assert!(!String::from_utf8(output.stdout).unwrap().contains("Key check"));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let code = stderr.lines()
+        .find_map(|line| line.strip_prefix("Key check: "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .unwrap()
+        .to_string();
+
+    // Contraseña equivocada: error de contraseña antes de derivar la clave, y cuenta como fallo
+    let config = dir.path().join("config");
+    scypher(dir.path())
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .args(["-d", "-f", "encrypted.txt", "--key-check", &code])
+        .write_stdin("password124\npassword124\n")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Key check does not match"))
+        .stdout(predicate::str::contains("Processing with").not());
+    assert_eq!(read(&config.join("failed-decrypts")), "1");
+
+    scypher(dir.path())
+        .env("SCYPHER_CONFIG_DIR", &config)
+        .args(["-d", "-f", "encrypted.txt", "--key-check", &code.to_uppercase(), "-o", "decrypted.txt"])
+        .write_stdin(format!("{}\n{}\n", PASSWORD, PASSWORD))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Key check matches"));
+    assert_eq!(read(&dir.path().join("decrypted.txt")), PHRASE);
+    assert!(!config.join("failed-decrypts").exists());
+}
+
+#[test]
+fn test_exit_codes() {
+    let dir = TempDir::new().unwrap();
+
+    // Validación: 2, con la palabra y su posición
+    scypher(dir.path())
+        .args(["-o", "result.txt"])
+        .write_stdin(answers("abandon abandon abandon"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("found 3 words"));
+    scypher(dir.path())
+        .args(["-o", "result.txt"])
+        .write_stdin(answers(&PHRASE.replace("about", "abuot")))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("#12 'abuot'"));
+
+    // Archivos: 4, con la operación y la ruta
+    scypher(dir.path())
+        .args(["-f", "missing.txt", "-o", "result.txt"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("Cannot read 'missing.txt'"));
+
+    // Contraseñas distintas y stdin agotado: error, no una pregunta sin fin
+    scypher(dir.path())
+        .args(["-o", "result.txt"])
+        .write_stdin(format!("{}\n{}\nsomething else\n", PHRASE, PASSWORD))
+        .assert()
+        .code(4)
+        .stdout(predicate::str::contains("Password mismatch"));
+
+    assert!(!dir.path().join("result.txt").exists());
+}
+
+#[test]
+fn test_json_api() {
+    let dir = TempDir::new().unwrap();
+    let request = serde_json::json!({
+        "version": 1,
+        "operation": "transform",
+        "phrase": PHRASE,
+        "password": PASSWORD,
+        "params": { "iterations": 1, "memory_cost": 8192 },
+    });
+
+    let output = scypher(dir.path()).arg("api").write_stdin(request.to_string()).output().unwrap();
+    assert!(output.status.success());
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(response["ok"], true);
+    assert_eq!(response["result"]["phrase"], ENCRYPTED);
+
+    let output = scypher(dir.path()).arg("api").write_stdin("{\"version\": 1}").output().unwrap();
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(response["ok"], false);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_run_vectors() {
+    // --run-vectors no admite más opciones
+    Command::cargo_bin("scypher-rust")
+        .unwrap()
+        .arg("--run-vectors")
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("en-12-fast"))
+        .stdout(predicate::str::contains("vectors passed"))
+        .stdout(predicate::str::contains("FAIL").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ctrl_c_exits_130() {
+    use std::process::Stdio;
+
+    let dir = TempDir::new().unwrap();
+    // stdin abierto y vacío: el proceso queda esperando la frase
+    let child = std::process::Command::new(assert_cmd::cargo::cargo_bin("scypher-rust"))
+        .current_dir(dir.path())
+        .args(["--plain-terminal", "-i", "1", "-m", "8192", "-o", "result.txt"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(500));
+    // SAFETY: señal a un proceso hijo que sigue vivo (no se ha esperado todavía)
+    assert_eq!(unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGINT) }, 0);
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(130));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("secure cleanup"));
+    assert!(!dir.path().join("result.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ctrl_c_interrupts_derivation() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::Stdio;
+    use std::time::Instant;
+
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("seed.txt"), PHRASE).unwrap();
+    // 100 pasadas sobre 64 MiB: varios segundos de Argon2id
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("scypher-rust"))
+        .current_dir(dir.path())
+        .args(["--plain-terminal", "--events", "json", "-i", "100", "-m", "65536", "-f", "seed.txt", "-o", "result.txt"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    write!(child.stdin.take().unwrap(), "{}\n{}\n", PASSWORD, PASSWORD).unwrap();
+
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    while !line.contains(r#""phase":"derive_key""#) {
+        line.clear();
+        assert!(stderr.read_line(&mut line).unwrap() > 0, "no derive_key event");
+    }
+    std::thread::sleep(Duration::from_millis(200));
+    let interrupted = Instant::now();
+    // SAFETY: señal a un proceso hijo que sigue vivo (no se ha esperado todavía)
+    assert_eq!(unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGINT) }, 0);
+
+    let mut rest = String::new();
+    std::io::Read::read_to_string(&mut stderr, &mut rest).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.code(), Some(130));
+    assert!(interrupted.elapsed() < Duration::from_secs(2));
+    assert!(rest.contains("stopping the key derivation"));
+    assert!(rest.contains(r#""event":"error","code":1003,"exit_code":130"#));
+    assert!(!dir.path().join("result.txt").exists());
+}